@@ -0,0 +1,66 @@
+//! The `audit-ignores` subcommand.
+
+use clap::Args;
+use engine::audit::{audit_ignores, DirectiveStatus};
+use engine::config::Config;
+use std::path::Path;
+
+#[derive(Args, Debug)]
+pub struct AuditIgnoresArgs {
+    /// The path to the repository to audit.
+    #[arg(long, default_value = ".")]
+    pub path: String,
+
+    /// Prints the directives as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Executes the `audit-ignores` subcommand.
+pub fn run(args: AuditIgnoresArgs, config: &Config) -> anyhow::Result<()> {
+    let audits = audit_ignores(Path::new(&args.path), config)?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&audits)?);
+        return Ok(());
+    }
+    if audits.is_empty() {
+        println!("No ignore directives found.");
+        return Ok(());
+    }
+    let locations: Vec<String> = audits
+        .iter()
+        .map(|a| format!("{}:{}", a.path, a.line))
+        .collect();
+    let rule_width = audits
+        .iter()
+        .map(|a| a.rule.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let location_width = locations.iter().map(String::len).max().unwrap_or(0).max(8);
+    println!(
+        "{:<12}  {:<rule_width$}  {:<location_width$}  REASON",
+        "STATUS", "RULE", "LOCATION"
+    );
+    for (audit, location) in audits.iter().zip(&locations) {
+        println!(
+            "{:<12}  {:<rule_width$}  {:<location_width$}  {}",
+            audit.status.as_str(),
+            audit.rule,
+            location,
+            audit.reason.as_deref().unwrap_or("-")
+        );
+    }
+    let count = |status| audits.iter().filter(|a| a.status == status).count();
+    println!(
+        "\n{} directive{}: {} active, {} stale, {} unknown rule, {} unchecked",
+        audits.len(),
+        if audits.len() == 1 { "" } else { "s" },
+        count(DirectiveStatus::Active),
+        count(DirectiveStatus::Stale),
+        count(DirectiveStatus::UnknownRule),
+        count(DirectiveStatus::Unchecked)
+    );
+    Ok(())
+}
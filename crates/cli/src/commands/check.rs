@@ -1,45 +1,106 @@
 //! The `check` subcommand.
 
+use crate::exit_code::ExitCode;
 use clap::{Args, ValueEnum};
-use engine::config::{Provider, Severity};
+use engine::config::{apply_ci_mode, Config, FailOn, LimitsConfig, PolicyConfig, Severity};
+use engine::content::GitIndexContentProvider;
+use engine::context::ReviewContext;
 use engine::error::EngineError;
-use engine::redact_text;
-use engine::report::{JsonGenerator, MarkdownGenerator, ReportGenerator};
-use engine::ReviewEngine;
-use std::env;
+use engine::privacy::Redactor;
+use engine::progress::ProgressEvent;
+use engine::publish::{Publisher, SlackPublisher};
+use engine::report::history::{self, HistoryRecord};
+use engine::report::{annotations, CombinedReport, RepoReport, ReviewReport};
+use engine::{render_report, Format, ReviewOptions};
+use serde_json::json;
+use std::collections::BTreeMap;
 use std::fs;
-use std::process::Command;
-use std::time::Duration;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::mpsc::{channel, Receiver};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
 use indicatif::{ProgressBar, ProgressStyle};
 
-#[derive(Clone, ValueEnum, Debug)]
+#[derive(Clone, ValueEnum, Debug, PartialEq, Eq)]
 pub enum ReportFormat {
     Md,
     Json,
 }
 
-#[derive(Args, Debug)]
+impl ReportFormat {
+    /// File extension used for reports in this format.
+    pub fn extension(&self) -> &'static str {
+        self.format().extension()
+    }
+
+    /// The engine format rendered for this choice.
+    fn format(&self) -> Format {
+        match self {
+            ReportFormat::Md => Format::Markdown,
+            ReportFormat::Json => Format::Json,
+        }
+    }
+}
+
+/// Where findings are annotated besides the report.
+#[derive(Clone, ValueEnum, Debug, PartialEq, Eq)]
+pub enum Annotations {
+    /// GitHub Actions workflow commands printed to stdout.
+    Github,
+    /// No annotations.
+    None,
+}
+
+/// Where a digest of the review is published.
+#[derive(Clone, ValueEnum, Debug, PartialEq, Eq)]
+pub enum PublishTarget {
+    /// A Slack incoming webhook, from `[integrations.slack] webhook-url` or
+    /// `SLACK_WEBHOOK_URL`.
+    Slack,
+}
+
+#[derive(Args, Debug, Clone)]
 pub struct CheckArgs {
-    /// Output format for the review report.
-    #[arg(long, value_enum, default_value = "md")]
-    pub format: ReportFormat,
+    /// Output format(s) for the review report. Pass a comma-separated list
+    /// (e.g. `md,json`) to write one file per format from a single review.
+    #[arg(long, value_enum, value_delimiter = ',', default_value = "md")]
+    pub format: Vec<ReportFormat>,
 
-    /// The base reference to compare against for generating a diff.
-    /// Use "auto" to detect the upstream of the current branch.
+    /// The base reference (branch, tag, or commit SHA) to compare against.
+    /// Changes are diffed against its merge base with `HEAD`, like
+    /// `git diff <base>...HEAD`. Use "auto" to take the target branch from
+    /// `GITHUB_BASE_REF`, `CI_MERGE_REQUEST_TARGET_BRANCH_NAME`, or
+    /// `CHANGE_TARGET`, falling back to the upstream of the current branch.
     #[arg(long, default_value = "auto", alias = "base-ref")]
     pub diff: String,
 
+    /// Diff directly against the base reference instead of its merge base,
+    /// including changes that landed on the base since the branch point.
+    #[arg(long, default_value_t = false, conflicts_with = "staged")]
+    pub two_dot: bool,
+
+    /// Review the changes staged for commit (`git diff --cached`), reading
+    /// file contents from the index rather than the working tree.
+    #[arg(long, default_value_t = false, conflicts_with = "diff")]
+    pub staged: bool,
+
     /// Run in CI mode (non-interactive).
     #[arg(long, default_value_t = false)]
     pub ci: bool,
 
     /// Analyze only files changed relative to the diff base. Use `--no-only-changed`
     /// to analyze all files.
-    #[arg(long, default_value_t = true)]
+    #[arg(long, default_value_t = true, overrides_with = "no_only_changed")]
     pub only_changed: bool,
 
+    /// Analyze every tracked file by diffing against the empty tree.
+    #[arg(long, default_value_t = false, overrides_with = "only_changed")]
+    pub no_only_changed: bool,
+
     /// Disable progress output.
     #[arg(long, default_value_t = false)]
     pub no_progress: bool,
@@ -52,160 +113,957 @@ pub struct CheckArgs {
     #[arg(long, default_value = ".")]
     pub path: String,
 
-    /// The path to write the review report to.
+    /// The path to write the review report to. With several formats, the
+    /// extension is replaced by each format's own (`report.md`, `report.json`).
+    /// `-` writes the single requested format to stdout, moving the summary
+    /// lines to stderr.
     #[arg(short, long)]
     pub output: Option<String>,
 
-    /// Minimum issue severity that will trigger a non-zero exit.
+    /// Fail the review if the LLM provider errors or times out, instead of
+    /// reporting scanner findings with a fallback summary.
+    #[arg(long, default_value_t = false)]
+    pub require_llm: bool,
+
+    /// Review every changed file, ignoring the `[limits]` caps on files and
+    /// changed lines.
+    #[arg(long, default_value_t = false)]
+    pub no_limits: bool,
+
+    /// Language of the LLM summary and of the report's headings, e.g. `ja`.
+    /// Overrides `[report] language`.
+    #[arg(long, value_name = "LANG")]
+    pub report_language: Option<String>,
+
+    /// Neither compare with nor record to `[report] history-path`, e.g. for
+    /// CI jobs that do not cache it.
+    #[arg(long, default_value_t = false)]
+    pub no_history: bool,
+
+    /// Skip loading the RAG index and retrieving context for the LLM, as
+    /// with `[rag] enabled = false`.
+    #[arg(long, default_value_t = false)]
+    pub no_rag: bool,
+
+    /// Run the scanners and the LLM even when every changed file matches
+    /// `[paths] docs-globs`.
+    #[arg(long, default_value_t = false)]
+    pub force_full: bool,
+
+    /// Neither read nor write `[engine] scan-cache-path` or the `[llm]`
+    /// response cache.
+    #[arg(long, default_value_t = false)]
+    pub no_cache: bool,
+
+    /// Minimum issue severity that will trigger a non-zero exit, or `never`.
     /// Defaults to the `fail-on` setting in `reviewlens.toml` (`high` if unset).
     #[arg(long, value_enum)]
-    pub fail_on: Option<Severity>,
+    pub fail_on: Option<FailOn>,
+
+    /// Write a small JSON summary of the outcome (exit code, counts per
+    /// severity, threshold, report paths, tokens, duration) to this path.
+    /// It is written even when the review fails.
+    #[arg(long, value_name = "PATH")]
+    pub summary_out: Option<PathBuf>,
+
+    /// Review only these changed files (comma separated). Paths and globs
+    /// are relative to `--path` and apply after the `[paths]` globs.
+    #[arg(long, value_delimiter = ',', value_name = "PATHS")]
+    pub files: Vec<String>,
+
+    /// Files to review, as with `--files`.
+    #[arg(value_name = "FILE")]
+    pub file_args: Vec<String>,
+
+    /// Review every repository listed in this workspace file and write one
+    /// combined report. Each `[[repo]]` has a `path`, an optional `name`, and
+    /// an optional `base` that overrides `--diff`.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["staged", "path"])]
+    pub workspace: Option<PathBuf>,
+
+    /// Print findings as annotations after the report is written. Defaults
+    /// to `github` when `GITHUB_ACTIONS=true` and `none` otherwise.
+    #[arg(long, value_enum)]
+    pub annotations: Option<Annotations>,
+
+    /// Most findings annotated; the rest are counted in a trailing notice.
+    #[arg(long, value_name = "N", default_value_t = annotations::DEFAULT_MAX_ANNOTATIONS)]
+    pub max_annotations: usize,
+
+    /// Title of the pull request, given to the LLM as the stated intent of
+    /// the change. Read from `GITHUB_EVENT_PATH` on GitHub Actions if unset.
+    #[arg(long, value_name = "TITLE")]
+    pub pr_title: Option<String>,
+
+    /// Description of the pull request, as with `--pr-title`.
+    #[arg(long, value_name = "BODY")]
+    pub pr_body: Option<String>,
+
+    /// Print whether the `[paths]` globs keep each changed file, and which
+    /// pattern decided, then exit without reviewing.
+    #[arg(long, default_value_t = false, conflicts_with = "workspace")]
+    pub explain_paths: bool,
+
+    /// Publish a digest of the review after writing the report. Failing to
+    /// publish is reported as a warning and does not change the exit code.
+    #[arg(long, value_enum, value_delimiter = ',', conflicts_with = "workspace")]
+    pub publish: Vec<PublishTarget>,
+
+    /// URL of the full report, e.g. a CI artifact, linked from published
+    /// digests.
+    #[arg(long, value_name = "URL")]
+    pub report_url: Option<String>,
+
+    /// Review policy file, replacing the `[policy]` table of the
+    /// configuration. Exits with 2 if the configuration breaks it, and with
+    /// 1 if the findings exceed its limits.
+    #[arg(long, value_name = "FILE")]
+    pub policy: Option<PathBuf>,
+}
+
+/// The `--output` value that writes the report to stdout.
+const STDOUT: &str = "-";
+
+impl CheckArgs {
+    /// Whether the report is written to stdout, which must then carry
+    /// nothing else.
+    fn to_stdout(&self) -> bool {
+        self.output.as_deref() == Some(STDOUT)
+    }
+
+    /// Prints a line for the user: to stdout, unless the report goes there.
+    fn say(&self, line: &str) {
+        if self.to_stdout() {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+    }
+
+    /// Whether GitHub Actions annotations are printed, from `--annotations`
+    /// or the `GITHUB_ACTIONS` variable.
+    fn github_annotations(&self) -> bool {
+        match &self.annotations {
+            Some(annotations) => *annotations == Annotations::Github,
+            None => std::env::var("GITHUB_ACTIONS").is_ok_and(|v| v == "true"),
+        }
+    }
 }
 
-/// Executes the `check` subcommand.
+/// Facts about a `check` run collected for `--summary-out`. Fields stay at
+/// their defaults if the run fails before reaching them.
+struct RunSummary {
+    fail_on: FailOn,
+    issues_by_severity: BTreeMap<Severity, usize>,
+    highest_severity: Option<Severity>,
+    reports: Vec<String>,
+    tokens_used: u32,
+}
+
+impl RunSummary {
+    /// Writes the summary as JSON to `path`.
+    fn write(
+        &self,
+        path: &Path,
+        exit_code: i32,
+        error: Option<String>,
+        elapsed: Duration,
+    ) -> anyhow::Result<()> {
+        // List every severity so consumers need not treat missing keys as 0.
+        let counts: BTreeMap<&str, usize> = Severity::value_variants()
+            .iter()
+            .map(|s| {
+                (
+                    s.as_str(),
+                    self.issues_by_severity.get(s).copied().unwrap_or(0),
+                )
+            })
+            .collect();
+        let summary = json!({
+            "exit_code": exit_code,
+            "error": error,
+            "fail_on": self.fail_on.as_str(),
+            "issues": self.issues_by_severity.values().sum::<usize>(),
+            "issues_by_severity": counts,
+            "highest_severity": self.highest_severity.as_ref().map(Severity::as_str),
+            "reports": self.reports,
+            "tokens_used": self.tokens_used,
+            "duration_ms": elapsed.as_millis(),
+        });
+        fs::write(path, serde_json::to_string_pretty(&summary)? + "\n")
+            .with_context(|| format!("failed to write summary to {}", path.display()))
+    }
+}
+
+/// Executes the `check` subcommand. `load_config` loads the configuration
+/// file of a workspace repository.
 /// Returns the appropriate exit code.
-pub async fn run(args: CheckArgs, engine: &ReviewEngine) -> i32 {
+pub async fn run(
+    args: CheckArgs,
+    config: Config,
+    load_config: &dyn Fn(&Path) -> anyhow::Result<Config>,
+) -> ExitCode {
+    let started = Instant::now();
+    let summary_out = args.summary_out.clone();
+    let mut summary = RunSummary {
+        fail_on: args.fail_on.clone().unwrap_or(config.fail_on.clone()),
+        issues_by_severity: BTreeMap::new(),
+        highest_severity: None,
+        reports: Vec::new(),
+        tokens_used: 0,
+    };
+    let result = match args.workspace.clone() {
+        Some(workspace) => {
+            review_workspace(args, &workspace, config, load_config, &mut summary).await
+        }
+        None => review(args, config, &mut summary).await,
+    };
+    let error = result.as_ref().err().map(|e| format!("{:#}", e));
+    let code = ExitCode::from_outcome(result);
+    if let Some(path) = summary_out {
+        if let Err(e) = summary.write(&path, code as i32, error, started.elapsed()) {
+            log::error!("{:#}", e);
+        }
+    }
+    code
+}
+
+/// Prepares the configuration and review options, then runs the review.
+async fn review(args: CheckArgs, config: Config, summary: &mut RunSummary) -> anyhow::Result<bool> {
+    let options = prepare_options(&args, config)?;
+    if args.explain_paths {
+        explain_paths(&args, &options.config)?;
+        return Ok(false);
+    }
+    execute(args, options, summary).await
+}
+
+/// Prints the `[paths]` decision for every file in the diff.
+fn explain_paths(args: &CheckArgs, config: &Config) -> anyhow::Result<()> {
+    let diff = if args.staged {
+        staged_diff(&args.path)?
+    } else {
+        base_diff(args)?
+    };
+    let files: Vec<String> = engine::diff_parser::parse(&diff)?
+        .into_iter()
+        .map(|file| file.path)
+        .collect();
+    let explanations = engine::paths::explain(&config.paths, &files)?;
+    if explanations.is_empty() {
+        println!("No files in the diff.");
+        return Ok(());
+    }
+    let pattern_width = explanations
+        .iter()
+        .filter_map(|e| e.decision.pattern())
+        .map(str::len)
+        .max()
+        .unwrap_or(0)
+        .max(7);
+    println!("{:<11}  {:<pattern_width$}  PATH", "DECISION", "PATTERN");
+    for explanation in &explanations {
+        println!(
+            "{:<11}  {:<pattern_width$}  {}",
+            explanation.decision.as_str(),
+            explanation.decision.pattern().unwrap_or("-"),
+            explanation.path
+        );
+    }
+    let kept = explanations.iter().filter(|e| e.decision.is_kept()).count();
+    println!(
+        "\n{} of {} file{} kept",
+        kept,
+        explanations.len(),
+        if explanations.len() == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+/// Applies the command-line settings to `config` and collects the options
+/// for reviewing the repository at `args.path`.
+fn prepare_options(args: &CheckArgs, mut config: Config) -> anyhow::Result<ReviewOptions> {
     if args.ci {
-        let mut config = engine.config().clone();
-        if config.generation.temperature != Some(0.0) {
-            log::warn!(
-                "CI mode overrides generation temperature to 0.0 (was {:?})",
-                config.generation.temperature
+        apply_ci_mode(&mut config)?;
+    }
+    if args.require_llm {
+        config.llm.required = true;
+    }
+    if args.no_limits {
+        config.limits = LimitsConfig::unlimited();
+    }
+    if let Some(language) = &args.report_language {
+        config.report.language = language.clone();
+    }
+    if args.no_history {
+        config.report.history_path = None;
+    }
+    if args.no_rag {
+        config.rag.enabled = false;
+    }
+    if args.force_full {
+        config.paths.docs_globs.clear();
+    }
+    if args.no_cache {
+        config.engine.scan_cache_path = None;
+        config.llm.cache = false;
+    }
+    if let Some(policy) = &args.policy {
+        config.policy = PolicyConfig::load_from_path(policy)?;
+    }
+    config.check_policy()?;
+    root_relative_paths(&mut config, Path::new(&args.path));
+    if config.report.link_template.is_none() {
+        config.report.link_template =
+            engine::report::links::detect_link_template(Path::new(&args.path));
+    }
+    // Catch redaction mistakes before running git.
+    Redactor::new(&config.privacy.redaction)?;
+    let mut options = ReviewOptions::new(config).root(&args.path);
+    if let Some(format) = args.format.first() {
+        options = options.format(format.format());
+    }
+    options.files = args.files.iter().chain(&args.file_args).cloned().collect();
+    options.context = Some(review_context(args));
+    options.branch = current_branch(Path::new(&args.path));
+    options.head_commit = engine::rag::repository_head(Path::new(&args.path));
+    Ok(options)
+}
+
+/// Collects the change's title and description from `--pr-title` and
+/// `--pr-body`, filling in what they leave unset from the GitHub Actions
+/// event payload. A payload that cannot be read is logged and skipped.
+fn review_context(args: &CheckArgs) -> ReviewContext {
+    let mut context = ReviewContext {
+        title: args.pr_title.clone(),
+        body: args.pr_body.clone(),
+    };
+    if context.title.is_some() && context.body.is_some() {
+        return context;
+    }
+    let Some(path) = std::env::var_os("GITHUB_EVENT_PATH") else {
+        return context;
+    };
+    match ReviewContext::from_github_event(Path::new(&path)) {
+        Ok(Some(event)) => {
+            context.title = context.title.or(event.title);
+            context.body = context.body.or(event.body);
+        }
+        Ok(None) => {}
+        Err(e) => log::warn!("Ignoring the GitHub event payload: {}", e),
+    }
+    context
+}
+
+/// Reviews every repository in the workspace file and writes one combined
+/// report. A repository with its own `reviewlens.toml` is reviewed with that
+/// file, loaded by `load_config`, instead of the invoking one. The token
+/// budget is shared: each repository may only spend what the ones before it
+/// left.
+async fn review_workspace(
+    args: CheckArgs,
+    workspace: &Path,
+    config: Config,
+    load_config: &dyn Fn(&Path) -> anyhow::Result<Config>,
+    summary: &mut RunSummary,
+) -> anyhow::Result<bool> {
+    let repos = engine::workspace::load(workspace)?;
+    let outputs = output_paths(&args.format, args.output.as_deref())?;
+    let budget = config.budget.tokens.max_per_run;
+    let mut redactors = vec![Redactor::new(&config.privacy.redaction)?];
+    let mut combined = CombinedReport::default();
+    let mut failing = 0;
+    let mut policy_violated = false;
+    for repo in repos {
+        let path = repo.path.to_string_lossy().into_owned();
+        let repo_file = repo.path.join("reviewlens.toml");
+        let mut repo_config = if repo_file.exists() {
+            log::info!(
+                "Loading configuration for {} from {:?}",
+                repo.name,
+                repo_file
             );
+            load_config(&repo_file)?
+        } else {
+            config.clone()
+        };
+        repo_config.budget.tokens.max_per_run =
+            budget.map(|max| max.saturating_sub(summary.tokens_used));
+        let repo_args = CheckArgs {
+            path: path.clone(),
+            diff: repo.base.clone().unwrap_or_else(|| args.diff.clone()),
+            ..args.clone()
+        };
+        let options = prepare_options(&repo_args, repo_config)
+            .with_context(|| format!("failed to review repository {}", repo.name))?;
+        let report = run_review(&repo_args, options)
+            .await
+            .with_context(|| format!("failed to review repository {}", repo.name))?;
+
+        summary.tokens_used += report.stats.tokens_used;
+        for (severity, count) in &report.stats.issues_by_severity {
+            *summary
+                .issues_by_severity
+                .entry(severity.clone())
+                .or_default() += count;
         }
-        config.generation.temperature = Some(0.0);
-        if config.llm.provider != Provider::Null && config.llm.model.is_none() {
-            log::error!("CI mode requires [llm].model to be set when provider is not 'null'");
-            return 2;
+        failing += match &args.fail_on {
+            Some(fail_on) => report.failing_issues(fail_on).len(),
+            None => report.failing_issues_by_path().len(),
+        };
+        report_policy_violations(&report);
+        policy_violated |= !report.policy_violations.is_empty();
+        args.say(&format!("{}: {}", repo.name, report.stats.headline()));
+        redactors.push(Redactor::new(&report.config.privacy.redaction)?);
+        combined.repos.push(RepoReport {
+            name: repo.name,
+            path,
+            report,
+        });
+    }
+    summary.highest_severity = combined.highest_severity();
+
+    for (format, path) in &outputs {
+        let mut report_out = match format {
+            ReportFormat::Md => combined.to_markdown(),
+            ReportFormat::Json => combined.to_json(),
         }
-        match ReviewEngine::new(config) {
-            Ok(ci_engine) => match execute(args, &ci_engine).await {
-                Ok(issues_found) => {
-                    if issues_found {
-                        1
-                    } else {
-                        0
-                    }
-                }
+        .map_err(|e| anyhow::anyhow!(e))?;
+        for redactor in &redactors {
+            report_out = match format {
+                ReportFormat::Md => redactor.redact(&report_out),
+                ReportFormat::Json => redactor.redact_json(&report_out),
+            };
+        }
+        write_report(path, &report_out)?;
+        summary.reports.push(path.display().to_string());
+        if !args.to_stdout() {
+            println!("Report written to {}", path.display());
+        }
+    }
+
+    let fail_on = args.fail_on.clone().unwrap_or(config.fail_on.clone());
+    summary.fail_on = fail_on.clone();
+    if failing > 0 {
+        eprintln!(
+            "failing because {} issue{} \u{2265} {} across the workspace",
+            failing,
+            if failing == 1 { "" } else { "s" },
+            fail_on.as_str()
+        );
+    }
+    Ok(failing > 0 || policy_violated)
+}
+
+/// Prints why the findings of `report` break the review policy, if they do.
+fn report_policy_violations(report: &ReviewReport) {
+    for violation in &report.policy_violations {
+        eprintln!(
+            "failing because the policy allows at most {} {} finding{}, found {}",
+            violation.max,
+            violation.severity.as_str(),
+            if violation.max == 1 { "" } else { "s" },
+            violation.count
+        );
+    }
+}
+
+/// Publishes a digest of `report` to each `--publish` target. Failures are
+/// printed as warnings so that they never fail the review.
+async fn publish(args: &CheckArgs, report: &ReviewReport) {
+    for target in &args.publish {
+        let publisher: Box<dyn Publisher> = match target {
+            PublishTarget::Slack => match SlackPublisher::from_config(&report.config) {
+                Ok(slack) => match &args.report_url {
+                    Some(url) => Box::new(slack.report_url(url)),
+                    None => Box::new(slack),
+                },
                 Err(e) => {
-                    if let Some(engine_error) = e.downcast_ref::<EngineError>() {
-                        match engine_error {
-                            EngineError::Config(_) => {
-                                log::error!("{}", e);
-                                2
-                            }
-                            _ => {
-                                log::error!("{}", e);
-                                3
-                            }
-                        }
-                    } else {
-                        log::error!("{}", e);
-                        3
-                    }
+                    eprintln!("warning: not publishing to Slack: {}", e);
+                    continue;
                 }
             },
-            Err(e) => {
-                log::error!("{}", e);
-                2
-            }
+        };
+        match publisher.publish(report).await {
+            Ok(()) => log::info!("Published the review to {}", publisher.name()),
+            Err(e) => eprintln!("warning: failed to publish to {}: {}", publisher.name(), e),
         }
+    }
+}
+
+/// CI variables naming the branch under review, most specific first.
+const BRANCH_VARS: [&str; 4] = [
+    "GITHUB_HEAD_REF",
+    "CI_MERGE_REQUEST_SOURCE_BRANCH_NAME",
+    "CI_COMMIT_REF_NAME",
+    "BRANCH_NAME",
+];
+
+/// Returns the branch under review: the one named by a CI variable, else the
+/// branch checked out at `repo`. `None` on a detached `HEAD` outside CI.
+fn current_branch(repo: &Path) -> Option<String> {
+    let from_env = BRANCH_VARS.iter().find_map(|var| {
+        std::env::var(var)
+            .ok()
+            .map(|b| b.trim().to_string())
+            .filter(|b| !b.is_empty())
+    });
+    if from_env.is_some() {
+        return from_env;
+    }
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()
+        .ok()?;
+    let branch = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !branch.is_empty() && branch != "HEAD").then_some(branch)
+}
+
+/// Appends this run's findings to `[report] history-path`, keeping the last
+/// `history-keep` runs. Does nothing without a history path or a branch.
+fn record_history(
+    report: &ReviewReport,
+    branch: Option<&str>,
+    head: Option<&str>,
+) -> anyhow::Result<()> {
+    let config = &report.config;
+    let Some(path) = &config.report.history_path else {
+        return Ok(());
+    };
+    let Some(branch) = branch else {
+        log::info!("Not recording run history: no branch is checked out");
+        return Ok(());
+    };
+    let record = HistoryRecord::new(branch, head, &report.issues);
+    history::append(Path::new(path), &record, config.report.history_keep)
+        .with_context(|| format!("failed to record run history in {}", path))
+}
+
+/// Resolves relative index, cache, and external scanner paths against the
+/// repository being checked, so they do not depend on the process working
+/// directory.
+fn root_relative_paths(config: &mut Config, repo: &Path) {
+    if let Some(path) = &config.report.history_path {
+        if Path::new(path).is_relative() {
+            config.report.history_path = Some(repo.join(path).to_string_lossy().into_owned());
+        }
+    }
+    if let Some(path) = config.index_path() {
+        if Path::new(path).is_relative() {
+            let rooted = repo.join(path).to_string_lossy().into_owned();
+            let mut index = config.index.clone().unwrap_or_default();
+            index.path = rooted;
+            config.index = Some(index);
+        }
+    }
+    if let Some(path) = &config.engine.scan_cache_path {
+        if Path::new(path).is_relative() {
+            config.engine.scan_cache_path = Some(repo.join(path).to_string_lossy().into_owned());
+        }
+    }
+    if config.llm.cache && Path::new(config.llm.cache_path()).is_relative() {
+        let rooted = repo.join(config.llm.cache_path());
+        config.llm.cache_path = Some(rooted.to_string_lossy().into_owned());
+    }
+    // Bare program names are looked up on `PATH` instead.
+    for scanner in &mut config.scanners.external {
+        if scanner.command.contains('/') && Path::new(&scanner.command).is_relative() {
+            scanner.command = repo.join(&scanner.command).to_string_lossy().into_owned();
+        }
+    }
+}
+
+/// Resolves where each requested format is written. A single format keeps
+/// `--output` verbatim; several formats share its stem. Only a single format
+/// can be written to stdout.
+fn output_paths(
+    formats: &[ReportFormat],
+    output: Option<&str>,
+) -> Result<Vec<(ReportFormat, PathBuf)>, EngineError> {
+    let mut unique: Vec<ReportFormat> = Vec::new();
+    for format in formats {
+        if !unique.contains(format) {
+            unique.push(format.clone());
+        }
+    }
+    let single = unique.len() == 1;
+    if output == Some(STDOUT) && !single {
+        return Err(EngineError::Config(
+            "`--output -` writes a single format to stdout; pass one --format".to_string(),
+        ));
+    }
+    Ok(unique
+        .into_iter()
+        .map(|format| {
+            let path = match output {
+                Some(path) if single => PathBuf::from(path),
+                Some(path) => Path::new(path).with_extension(format.extension()),
+                None => PathBuf::from(format!("review_report.{}", format.extension())),
+            };
+            (format, path)
+        })
+        .collect())
+}
+
+/// Writes a rendered report to `path`, or to stdout for `-`.
+fn write_report(path: &Path, report: &str) -> anyhow::Result<()> {
+    if path == Path::new(STDOUT) {
+        let mut stdout = std::io::stdout().lock();
+        stdout
+            .write_all(report.as_bytes())
+            .and_then(|()| stdout.flush())
+            .context("failed to write report to stdout")
     } else {
-        match execute(args, engine).await {
-            Ok(issues_found) => {
-                if issues_found {
-                    1
-                } else {
-                    0
+        fs::write(path, report)
+            .with_context(|| format!("failed to write report to {}", path.display()))
+    }
+}
+
+/// Follows the engine's progress events on a separate thread, updating
+/// `progress` as files are scanned. Returns the total time spent in each
+/// scanner.
+fn follow_progress(
+    events: Receiver<ProgressEvent>,
+    progress: Option<ProgressBar>,
+) -> JoinHandle<BTreeMap<&'static str, Duration>> {
+    thread::spawn(move || {
+        let mut timings: BTreeMap<&'static str, Duration> = BTreeMap::new();
+        for event in events {
+            match event {
+                ProgressEvent::ParsedDiff { files } => {
+                    if let Some(pb) = &progress {
+                        pb.set_length(files as u64);
+                        pb.set_style(
+                            ProgressStyle::with_template("{spinner} [{bar:30}] {msg}")
+                                .expect("progress bar template"),
+                        );
+                    }
                 }
-            }
-            Err(e) => {
-                if let Some(engine_error) = e.downcast_ref::<EngineError>() {
-                    match engine_error {
-                        EngineError::Config(_) => {
-                            log::error!("{}", e);
-                            2
-                        }
-                        _ => {
-                            log::error!("{}", e);
-                            3
-                        }
+                ProgressEvent::ScanningFile { path, index, total } => {
+                    if let Some(pb) = &progress {
+                        pb.set_position(index.saturating_sub(1) as u64);
+                        pb.set_message(format!("Scanning {}/{}: {}", index, total, path));
+                    }
+                }
+                ProgressEvent::ScannerFinished { name, elapsed, .. } => {
+                    *timings.entry(name).or_default() += elapsed;
+                }
+                ProgressEvent::RetrievingContext { .. } => {
+                    if let Some(pb) = &progress {
+                        pb.set_position(pb.length().unwrap_or(0));
+                        pb.set_message("Retrieving context...");
                     }
-                } else {
-                    log::error!("{}", e);
-                    3
                 }
+                ProgressEvent::CallingLlm => {
+                    if let Some(pb) = &progress {
+                        pb.set_position(pb.length().unwrap_or(0));
+                        pb.set_message("Calling LLM...");
+                    }
+                }
+                _ => {}
             }
         }
+        timings
+    })
+}
+
+/// Diffs the repository at `args.path` and reviews the changes with
+/// `options`, showing progress unless disabled, then adds blame if
+/// configured.
+async fn run_review(args: &CheckArgs, mut options: ReviewOptions) -> anyhow::Result<ReviewReport> {
+    // 1. Generate the diff.
+    let diff_content = if args.staged {
+        staged_diff(&args.path)?
+    } else {
+        base_diff(args)?
+    };
+
+    // 2. Call the engine to run the review and capture its report.
+    // The spinner would interleave with a report piped from stdout.
+    let progress = if !args.no_progress && !args.ci && !args.to_stdout() {
+        let pb = ProgressBar::new_spinner();
+        pb.set_style(ProgressStyle::with_template("{spinner} {msg}").expect("spinner template"));
+        pb.enable_steady_tick(Duration::from_millis(100));
+        pb.set_message("Reviewing diff...");
+        Some(pb)
+    } else {
+        None
+    };
+
+    if let Some(pb) = &progress {
+        pb.set_message("Running review engine...");
+    }
+    let (events, received) = channel();
+    let follower = follow_progress(received, progress.clone());
+    options.progress = Some(events);
+    // The engine reads changed files relative to the repository root.
+    // Staged reviews read the index, since unstaged edits would shift line
+    // numbers.
+    if args.staged {
+        options.content = Some(Box::new(GitIndexContentProvider::new(&args.path)));
+    }
+    let result = engine::review_diff(&diff_content, options).await;
+    // The engine has dropped its sender, so the follower has finished.
+    if let Ok(timings) = follower.join() {
+        for (scanner, elapsed) in timings {
+            log::info!("{} took {} ms", scanner, elapsed.as_millis());
+        }
     }
+    let mut report = result.map_err(|e| anyhow::anyhow!(e))?;
+    if report.config.report.blame {
+        if let Some(pb) = &progress {
+            pb.set_message("Running git blame...");
+        }
+        engine::blame::enrich(Path::new(&args.path), &mut report.issues);
+    }
+
+    if let Some(pb) = progress {
+        pb.finish_and_clear();
+    }
+    Ok(report)
 }
 
-async fn execute(args: CheckArgs, engine: &ReviewEngine) -> anyhow::Result<bool> {
-    let output_path = args.output.clone().unwrap_or_else(|| match args.format {
-        ReportFormat::Md => "review_report.md".to_string(),
-        ReportFormat::Json => "review_report.json".to_string(),
-    });
+async fn execute(
+    args: CheckArgs,
+    options: ReviewOptions,
+    summary: &mut RunSummary,
+) -> anyhow::Result<bool> {
+    let outputs = output_paths(&args.format, args.output.as_deref())?;
+    let output_list = outputs
+        .iter()
+        .map(|(_, path)| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
 
     log::info!("Running 'check' with the following arguments:");
     log::info!("  Path: {}", args.path);
-    log::info!("  Output: {}", output_path);
+    log::info!("  Output: {}", output_list);
     log::info!("  Format: {:?}", args.format);
     log::info!("  CI mode: {}", args.ci);
-    log::info!("  Only changed: {}", args.only_changed);
+    log::info!("  Only changed: {}", !args.no_only_changed);
+    log::info!("  Staged: {}", args.staged);
     log::info!("  No progress: {}", args.no_progress);
     log::info!("  Allow suggest: {}", args.allow_suggest);
 
-    if args.ci {
-        env::set_var("CI", "true");
-    }
     if !args.no_progress {
         log::info!("Starting review...");
     }
 
-    // Resolve the base reference, falling back to upstream if not provided.
-    let base_ref = if args.diff != "auto" {
-        args.diff.clone()
+    let branch = options.branch.clone();
+    let head = options.head_commit.clone();
+    let report = run_review(&args, options).await?;
+    let redactor = Redactor::new(&report.config.privacy.redaction)?;
+    summary.issues_by_severity = report.stats.issues_by_severity.clone();
+    summary.highest_severity = report.issues.iter().map(|i| i.severity.clone()).max();
+    summary.tokens_used = report.stats.tokens_used;
+
+    // Print the summary and hotspots for quick visibility: to stdout, unless
+    // the report is written there.
+    if args.ci {
+        args.say(&report.summary);
+        args.say(&report.stats.headline());
     } else {
-        let upstream_output = Command::new("git")
-            .args([
-                "-C",
-                &args.path,
-                "rev-parse",
-                "--abbrev-ref",
-                "--symbolic-full-name",
-                "@{u}",
-            ])
-            .output()
-            .map_err(|e| EngineError::Config(format!("failed to detect upstream base: {}", e)))?;
-        if !upstream_output.status.success() {
-            return Err(
-                EngineError::Config("failed to detect upstream base reference".into()).into(),
-            );
+        args.say(&format!("Summary: {}", report.summary));
+        args.say(&report.stats.headline());
+        if report.hotspots.is_empty() {
+            args.say("No hotspots identified.");
+        } else {
+            args.say("Top hotspots:");
+            for spot in &report.hotspots {
+                args.say(&format!("- {}", spot));
+            }
         }
-        String::from_utf8(upstream_output.stdout)
-            .context("upstream output was not valid UTF-8")?
-            .trim()
-            .to_string()
+    }
+
+    // 3. Render the report with each requested generator.
+    for (format, path) in &outputs {
+        let report_out = render_report(&report, format.format())?;
+        write_report(path, &report_out)?;
+        summary.reports.push(path.display().to_string());
+    }
+    if outputs.len() > 1 {
+        println!("Reports written:");
+        for (_, path) in &outputs {
+            println!("- {}", path.display());
+        }
+    }
+    log::info!("\nReview complete. Report written to {}.", output_list);
+    record_history(&report, branch.as_deref(), head.as_deref())?;
+
+    if args.github_annotations() {
+        let prefix = (args.path != ".").then_some(args.path.as_str());
+        for line in annotations::github(&report.issues, args.max_annotations, prefix) {
+            args.say(&redactor.redact(&line));
+        }
+    }
+    publish(&args, &report).await;
+
+    // 4. Determine if issues exceed the severity threshold.
+    // An explicit `--fail-on` applies everywhere; otherwise each file uses
+    // the threshold from its matching `[[overrides]]`, if any.
+    let (fail_on, failing) = match &args.fail_on {
+        Some(fail_on) => (fail_on.clone(), report.failing_issues(fail_on)),
+        None => (
+            report.config.fail_on.clone(),
+            report.failing_issues_by_path(),
+        ),
     };
-    log::info!("  Base ref: {}", base_ref);
+    summary.fail_on = fail_on.clone();
+    if !failing.is_empty() {
+        eprintln!(
+            "failing because {} issue{} \u{2265} {}",
+            failing.len(),
+            if failing.len() == 1 { "" } else { "s" },
+            fail_on.as_str()
+        );
+    }
+    report_policy_violations(&report);
 
-    // 1. Generate the diff.
-    let diff_content = if args.only_changed {
+    Ok(!failing.is_empty() || !report.policy_violations.is_empty())
+}
+
+/// Returns the changes staged for commit in the repository at `path`.
+fn staged_diff(path: &str) -> anyhow::Result<String> {
+    let diff_output = Command::new("git")
+        .args(["-C", path, "diff", "--cached"])
+        .output()
+        .map_err(|e| EngineError::Git(format!("failed to execute git diff --cached: {}", e)))?;
+    if !diff_output.status.success() {
+        return Err(git_failed("git diff --cached", &diff_output));
+    }
+    String::from_utf8(diff_output.stdout).context("diff output was not valid UTF-8")
+}
+
+/// Describes a git command that exited unsuccessfully, with its stderr.
+fn git_failed(command: &str, output: &Output) -> anyhow::Error {
+    EngineError::Git(format!(
+        "{} failed: {}",
+        command,
+        String::from_utf8_lossy(&output.stderr).trim()
+    ))
+    .into()
+}
+
+/// CI variables naming the target branch of a pull or merge request, in the
+/// order they are consulted: GitHub Actions, GitLab CI, Jenkins.
+const CI_BASE_VARS: &[&str] = &[
+    "GITHUB_BASE_REF",
+    "CI_MERGE_REQUEST_TARGET_BRANCH_NAME",
+    "CHANGE_TARGET",
+];
+
+/// Resolves `--diff auto` to a base reference: the target branch named by a
+/// CI variable (as `origin/<branch>`), else the upstream of the current
+/// branch. In CI mode a missing target branch is fetched.
+fn resolve_base_ref(args: &CheckArgs) -> anyhow::Result<String> {
+    if args.diff != "auto" {
+        return Ok(args.diff.clone());
+    }
+    log::info!(
+        "Resolving base ref from {}, then the upstream branch",
+        CI_BASE_VARS.join(", ")
+    );
+    for var in CI_BASE_VARS {
+        let Some(branch) = std::env::var(var).ok().filter(|b| !b.trim().is_empty()) else {
+            continue;
+        };
+        let branch = branch.trim();
+        let base = format!("origin/{}", branch);
+        log::info!("Using base ref {} from ${}", base, var);
+        if args.ci && !resolves(&args.path, &format!("{}^{{commit}}", base))? {
+            fetch_branch(&args.path, branch)?;
+        }
+        return Ok(base);
+    }
+
+    let upstream_output = Command::new("git")
+        .args([
+            "-C",
+            &args.path,
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{u}",
+        ])
+        .output()
+        .map_err(|e| EngineError::Git(format!("failed to detect upstream base: {}", e)))?;
+    if !upstream_output.status.success() {
+        return Err(EngineError::Config(format!(
+            "failed to detect upstream base reference (no upstream or detached HEAD, \
+             and none of {} is set); pass `--diff <ref or SHA>`",
+            CI_BASE_VARS.join(", ")
+        ))
+        .into());
+    }
+    let base = String::from_utf8(upstream_output.stdout)
+        .context("upstream output was not valid UTF-8")?
+        .trim()
+        .to_string();
+    log::info!("Using base ref {} from the upstream branch", base);
+    Ok(base)
+}
+
+/// Returns whether the repository at `path` is a shallow clone.
+fn is_shallow(path: &str) -> anyhow::Result<bool> {
+    let output = Command::new("git")
+        .args(["-C", path, "rev-parse", "--is-shallow-repository"])
+        .output()
+        .map_err(|e| EngineError::Git(format!("failed to execute git rev-parse: {}", e)))?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim() == "true")
+}
+
+/// Fetches `branch` from `origin` into `origin/<branch>` with depth 1.
+fn fetch_branch(path: &str, branch: &str) -> anyhow::Result<()> {
+    log::info!("Fetching origin/{} for the base ref", branch);
+    let output = Command::new("git")
+        .args(["-C", path, "fetch", "origin", "--depth=1"])
+        .arg(format!("+refs/heads/{0}:refs/remotes/origin/{0}", branch))
+        .output()
+        .map_err(|e| EngineError::Git(format!("failed to execute git fetch: {}", e)))?;
+    if !output.status.success() {
+        return Err(EngineError::Git(format!(
+            "failed to fetch base branch `{}` from origin: {}",
+            branch,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Returns the diff against `--diff` (or the detected base), or against
+/// the empty tree when reviewing every file.
+fn base_diff(args: &CheckArgs) -> anyhow::Result<String> {
+    if !args.no_only_changed {
+        let base_ref = resolve_base_ref(args)?;
+        log::info!("  Base ref: {}", base_ref);
+        verify_ref(&args.path, &base_ref)?;
+        let base = if args.two_dot {
+            base_ref
+        } else {
+            merge_base(&args.path, &base_ref, args.ci)?
+        };
+        log::info!("  Diff base: {}", base);
         let diff_output = Command::new("git")
-            .args(["-C", &args.path, "diff", &base_ref])
+            .args(["-C", &args.path, "diff", &base])
             .output()
-            .with_context(|| "failed to execute git diff")?;
+            .map_err(|e| EngineError::Git(format!("failed to execute git diff: {}", e)))?;
         if !diff_output.status.success() {
-            anyhow::bail!("git diff command failed");
+            return Err(git_failed("git diff", &diff_output));
         }
-        String::from_utf8(diff_output.stdout).context("diff output was not valid UTF-8")?
+        String::from_utf8(diff_output.stdout).context("diff output was not valid UTF-8")
     } else {
+        // Hash empty input rather than `/dev/null`, which does not exist on
+        // Windows, so the result also matches the repository's hash format.
         let empty_tree = Command::new("git")
-            .args(["-C", &args.path, "hash-object", "-t", "tree", "/dev/null"])
+            .args(["-C", &args.path, "hash-object", "-t", "tree", "--stdin"])
+            .stdin(Stdio::null())
             .output()
-            .with_context(|| "failed to hash empty tree")?;
+            .map_err(|e| EngineError::Git(format!("failed to hash empty tree: {}", e)))?;
         if !empty_tree.status.success() {
-            anyhow::bail!("git hash-object command failed");
+            return Err(git_failed("git hash-object", &empty_tree));
         }
         let empty_tree_ref = String::from_utf8(empty_tree.stdout)
             .context("empty tree hash output was not valid UTF-8")?
@@ -214,82 +1072,74 @@ async fn execute(args: CheckArgs, engine: &ReviewEngine) -> anyhow::Result<bool>
         let diff_output = Command::new("git")
             .args(["-C", &args.path, "diff", &empty_tree_ref])
             .output()
-            .with_context(|| "failed to execute git diff")?;
+            .map_err(|e| EngineError::Git(format!("failed to execute git diff: {}", e)))?;
         if !diff_output.status.success() {
-            anyhow::bail!("git diff command failed");
+            return Err(git_failed("git diff", &diff_output));
         }
-        String::from_utf8(diff_output.stdout).context("diff output was not valid UTF-8")?
-    };
-
-    // 2. Call the engine to run the review and capture its report.
-    // Ensure file reads are relative to the provided path.
-    let progress = if !args.no_progress && !args.ci {
-        let pb = ProgressBar::new_spinner();
-        pb.set_style(ProgressStyle::with_template("{spinner} {msg}").expect("spinner template"));
-        pb.enable_steady_tick(Duration::from_millis(100));
-        pb.set_message("Reviewing diff...");
-        Some(pb)
-    } else {
-        None
-    };
+        String::from_utf8(diff_output.stdout).context("diff output was not valid UTF-8")
+    }
+}
 
-    let report = {
-        let original_dir = env::current_dir().with_context(|| "failed to get current directory")?;
-        env::set_current_dir(&args.path)
-            .with_context(|| format!("failed to change to directory {}", args.path))?;
-        if let Some(pb) = &progress {
-            pb.set_message("Running review engine...");
-        }
-        let result = engine
-            .run(&diff_content)
-            .await
-            .map_err(|e| anyhow::anyhow!(e));
-        env::set_current_dir(original_dir)
-            .with_context(|| "failed to restore working directory")?;
-        result?
-    };
+/// Returns whether `rev` resolves to an object in the repository at `path`.
+fn resolves(path: &str, rev: &str) -> anyhow::Result<bool> {
+    let output = Command::new("git")
+        .args(["-C", path, "rev-parse", "--verify", "--quiet", rev])
+        .output()
+        .map_err(|e| EngineError::Git(format!("failed to execute git rev-parse: {}", e)))?;
+    Ok(output.status.success())
+}
 
-    if let Some(pb) = progress {
-        pb.finish_and_clear();
+/// Fails with a configuration error if `base_ref` does not name a commit or
+/// tree in the local repository, as happens in CI checkouts that only
+/// fetched `HEAD`.
+fn verify_ref(path: &str, base_ref: &str) -> anyhow::Result<()> {
+    if !resolves(path, &format!("{}^{{tree}}", base_ref))? {
+        return Err(EngineError::Config(format!(
+            "base ref `{}` does not exist locally; fetch it first (e.g. `git fetch origin {}`)",
+            base_ref,
+            base_ref.trim_start_matches("origin/")
+        ))
+        .into());
     }
+    Ok(())
+}
 
-    // Print the summary and hotspots to stdout for quick visibility.
-    if args.ci {
-        println!("{}", report.summary);
-    } else {
-        println!("Summary: {}", report.summary);
-        if report.hotspots.is_empty() {
-            println!("No hotspots identified.");
-        } else {
-            println!("Top hotspots:");
-            for spot in &report.hotspots {
-                println!("- {}", spot);
-            }
-        }
+/// Returns the commit where `HEAD` branched off `base_ref`. A bare tree has
+/// no history, so it is returned unchanged. In CI mode a shallow clone is
+/// unshallowed once if the merge base lies outside the fetched history.
+fn merge_base(path: &str, base_ref: &str, ci: bool) -> anyhow::Result<String> {
+    if !resolves(path, &format!("{}^{{commit}}", base_ref))? {
+        return Ok(base_ref.to_string());
     }
-
-    // 3. Generate the report and write it to `output_path`.
-    let generator: Box<dyn ReportGenerator> = match args.format {
-        ReportFormat::Md => Box::new(MarkdownGenerator),
-        ReportFormat::Json => Box::new(JsonGenerator),
+    let find = || {
+        Command::new("git")
+            .args(["-C", path, "merge-base", base_ref, "HEAD"])
+            .output()
+            .map_err(|e| EngineError::Git(format!("failed to execute git merge-base: {}", e)))
     };
-    let report_out = generator
-        .generate(&report)
-        .map_err(|e| anyhow::anyhow!(e))?;
-    let redacted_report = redact_text(engine.config(), &report_out);
-    fs::write(&output_path, &redacted_report)?;
-    log::info!("\nReview complete. Report written to {}.", output_path);
-
-    // 4. Determine if issues exceed the severity threshold.
-    let threshold = args
-        .fail_on
-        .unwrap_or_else(|| engine.config().fail_on.clone());
-    let issues_found = report
-        .issues
-        .iter()
-        .map(|issue| issue.severity.clone())
-        .max()
-        .map_or(false, |max| max >= threshold);
-
-    Ok(issues_found)
+    let mut output = find()?;
+    if !output.status.success() && ci && is_shallow(path)? {
+        log::info!("No merge base in the shallow history; fetching the rest of it");
+        let fetch = Command::new("git")
+            .args(["-C", path, "fetch", "--unshallow", "origin"])
+            .output()
+            .map_err(|e| {
+                EngineError::Git(format!("failed to execute git fetch --unshallow: {}", e))
+            })?;
+        if fetch.status.success() {
+            output = find()?;
+        }
+    }
+    if !output.status.success() {
+        return Err(EngineError::Config(format!(
+            "no merge base between `{}` and HEAD; fetch more history \
+             (e.g. `git fetch --unshallow`) or pass `--two-dot`",
+            base_ref
+        ))
+        .into());
+    }
+    Ok(String::from_utf8(output.stdout)
+        .context("merge-base output was not valid UTF-8")?
+        .trim()
+        .to_string())
 }
@@ -0,0 +1,70 @@
+//! The `compare` subcommand.
+
+use crate::exit_code::ExitCode;
+use anyhow::Context;
+use clap::Args;
+use engine::config::{Config, FailOn};
+use engine::report::{compare, ReviewReport};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug)]
+pub struct CompareArgs {
+    /// The earlier JSON report, e.g. from the target branch.
+    #[arg(long, value_name = "PATH")]
+    pub before: PathBuf,
+
+    /// The later JSON report, e.g. from the pull request.
+    #[arg(long, value_name = "PATH")]
+    pub after: PathBuf,
+
+    /// Also write the comparison as Markdown to this path.
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Minimum severity of a new finding that triggers a non-zero exit, or
+    /// `never`. Defaults to the `fail-on` setting in `reviewlens.toml`.
+    #[arg(long, value_enum)]
+    pub fail_on: Option<FailOn>,
+}
+
+/// Executes the `compare` subcommand.
+/// Returns the appropriate exit code.
+pub fn run(args: CompareArgs, config: &Config) -> ExitCode {
+    ExitCode::from_outcome(execute(args, config))
+}
+
+/// Compares the reports, returning whether any new finding meets the
+/// failure threshold.
+fn execute(args: CompareArgs, config: &Config) -> anyhow::Result<bool> {
+    let before = load(&args.before)?;
+    let after = load(&args.after)?;
+    let comparison = compare(&before, &after);
+
+    let rendered = comparison.to_markdown();
+    print!("{}", rendered);
+    if let Some(path) = &args.output {
+        fs::write(path, &rendered)
+            .with_context(|| format!("failed to write comparison to {}", path.display()))?;
+    }
+
+    let fail_on = args.fail_on.unwrap_or_else(|| config.fail_on.clone());
+    let failing = comparison.failing_new(&fail_on);
+    if !failing.is_empty() {
+        eprintln!(
+            "failing because {} new issue{} \u{2265} {}",
+            failing.len(),
+            if failing.len() == 1 { "" } else { "s" },
+            fail_on.as_str()
+        );
+    }
+    Ok(!failing.is_empty())
+}
+
+/// Reads the JSON report at `path`.
+fn load(path: &Path) -> anyhow::Result<ReviewReport> {
+    let json = fs::read_to_string(path)
+        .with_context(|| format!("failed to read report {}", path.display()))?;
+    ReviewReport::from_json(&json)
+        .with_context(|| format!("failed to parse report {}", path.display()))
+}
@@ -0,0 +1,107 @@
+//! The `config` subcommand.
+
+use crate::exit_code::ExitCode;
+use anyhow::Context;
+use clap::{Args, Subcommand};
+use engine::config::{migrate_document, unknown_keys, Config, ValidationProblem};
+use std::fs;
+use std::path::Path;
+
+#[derive(Args, Debug, Clone)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommands,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCommands {
+    /// Validates the configuration file and reports every problem found.
+    Validate,
+    /// Moves deprecated keys to their current names. Prints the migrated
+    /// file unless `--write` is given.
+    Migrate(MigrateArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct MigrateArgs {
+    /// Rewrite the configuration file in place, keeping comments and
+    /// formatting.
+    #[arg(long)]
+    pub write: bool,
+}
+
+/// Reports unknown keys and parse errors in the configuration file at `path`,
+/// prefixing each key path with the file name. A missing file has nothing to
+/// validate and yields no problems.
+pub fn file_problems(path: &Path) -> Vec<ValidationProblem> {
+    if !path.exists() {
+        return Vec::new();
+    }
+    let problems = match fs::read_to_string(path) {
+        Ok(content) => unknown_keys(&content),
+        Err(e) => vec![ValidationProblem {
+            path: "<file>".to_string(),
+            message: e.to_string(),
+        }],
+    };
+    problems
+        .into_iter()
+        .map(|problem| ValidationProblem {
+            path: format!("{}: {}", path.display(), problem.path),
+            ..problem
+        })
+        .collect()
+}
+
+/// Prints every problem to stderr and returns the configuration error exit code.
+pub fn report_problems(problems: &[ValidationProblem]) -> ExitCode {
+    eprintln!(
+        "{} configuration problem{}:",
+        problems.len(),
+        if problems.len() == 1 { "" } else { "s" }
+    );
+    for problem in problems {
+        eprintln!("  {}", problem);
+    }
+    ExitCode::ConfigError
+}
+
+/// Executes the `config` subcommand. `validate` runs against an already
+/// validated configuration and additionally warns about `[paths]` patterns
+/// that can never take effect in the repository holding the file.
+pub fn run(args: ConfigArgs, config: &Config, path: &Path) -> anyhow::Result<()> {
+    match args.command {
+        ConfigCommands::Validate => {
+            let root = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            for warning in config.path_warnings(Some(root)) {
+                eprintln!("warning: {}", warning);
+            }
+            println!("{}: configuration is valid", path.display());
+        }
+        ConfigCommands::Migrate(args) => migrate(&args, path)?,
+    }
+    Ok(())
+}
+
+fn migrate(args: &MigrateArgs, path: &Path) -> anyhow::Result<()> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let (migrated, deprecations) = migrate_document(&content)?;
+    if deprecations.is_empty() {
+        eprintln!("{}: nothing to migrate", path.display());
+        return Ok(());
+    }
+    for deprecation in &deprecations {
+        eprintln!("{}: {}", path.display(), deprecation);
+    }
+    if args.write {
+        fs::write(path, migrated).with_context(|| format!("failed to write {}", path.display()))?;
+        eprintln!("{}: migrated", path.display());
+    } else {
+        print!("{}", migrated);
+    }
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! The `fix` subcommand.
+
+use anyhow::Context;
+use clap::Args;
+use engine::fix::{apply_fixes, FixStatus};
+use engine::report::ReviewReport;
+use engine::scanner::Issue;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug)]
+pub struct FixArgs {
+    /// The JSON report whose fixes to apply.
+    #[arg(long, value_name = "PATH")]
+    pub report: PathBuf,
+
+    /// Only apply fixes for this rule. Repeatable.
+    #[arg(long = "rule", value_name = "RULE")]
+    pub rules: Vec<String>,
+
+    /// Lists the fixes that would be applied without changing any file.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// The repository the report's paths are relative to.
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+}
+
+/// Executes the `fix` subcommand.
+pub fn run(args: FixArgs) -> anyhow::Result<()> {
+    let json = fs::read_to_string(&args.report)
+        .with_context(|| format!("failed to read report {}", args.report.display()))?;
+    let report = ReviewReport::from_json(&json)
+        .with_context(|| format!("failed to parse report {}", args.report.display()))?;
+    let issues: Vec<Issue> = report
+        .issues
+        .into_iter()
+        .filter(|issue| args.rules.is_empty() || args.rules.contains(&issue.rule_id))
+        .collect();
+
+    let outcomes = apply_fixes(Path::new(&args.path), &issues, args.dry_run)?;
+    if outcomes.is_empty() {
+        println!("No fixes to apply.");
+        return Ok(());
+    }
+    for outcome in &outcomes {
+        let location = format!("{}:{}", outcome.path, outcome.line);
+        match &outcome.status {
+            FixStatus::Applied => println!("applied  {}  {}", location, outcome.rule),
+            FixStatus::Skipped(reason) => {
+                println!("skipped  {}  {}  ({})", location, outcome.rule, reason)
+            }
+        }
+    }
+    let applied = outcomes
+        .iter()
+        .filter(|o| o.status == FixStatus::Applied)
+        .count();
+    println!(
+        "\n{} fix{} {}, {} skipped",
+        applied,
+        if applied == 1 { "" } else { "es" },
+        if args.dry_run {
+            "would be applied"
+        } else {
+            "applied"
+        },
+        outcomes.len() - applied
+    );
+    Ok(())
+}
@@ -1,11 +1,19 @@
 //! The `index` subcommand.
 
-use clap::Args;
+use chrono::{TimeZone, Utc};
+use clap::{Args, Subcommand, ValueEnum};
 use engine::config::{Config, DEFAULT_INDEX_PATH};
-use engine::rag::index_repository;
+use engine::rag::{
+    create_embedder, index_repository_with, InMemoryVectorStore, IndexOptions, IndexStats,
+};
+use std::path::{Path, PathBuf};
 
 #[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct IndexArgs {
+    #[command(subcommand)]
+    pub command: Option<IndexCommands>,
+
     /// The path to the repository to index.
     #[arg(long, default_value = ".")]
     pub path: String,
@@ -14,33 +22,186 @@ pub struct IndexArgs {
     #[arg(long)]
     pub force: bool,
 
-    /// The path to write the generated index to.
-    #[arg(long, default_value = DEFAULT_INDEX_PATH)]
-    pub output: String,
+    /// The path to write the generated index to. Defaults to the configured
+    /// `[index].path`, resolved against `--path`.
+    #[arg(long)]
+    pub output: Option<String>,
+
+    /// Globs of files to include, overriding `[paths].allow` (comma separated).
+    #[arg(long, value_delimiter = ',')]
+    pub allow: Vec<String>,
+
+    /// Globs of files to exclude, overriding `[paths].deny` (comma separated).
+    #[arg(long, value_delimiter = ',')]
+    pub deny: Vec<String>,
+
+    /// Where to store the index.
+    #[arg(long, value_enum, default_value_t = IndexStore::Local)]
+    pub store: IndexStore,
+}
+
+/// Backends for `index --store`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum IndexStore {
+    /// A compressed file at `--output`.
+    Local,
+    /// The Qdrant collection configured in `[rag.qdrant]`. Requires the
+    /// `qdrant` feature.
+    Qdrant,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum IndexCommands {
+    /// Prints statistics about an existing index.
+    Stats(StatsArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// The index file to inspect. Defaults to the configured `[index].path`.
+    #[arg(long)]
+    pub index: Option<String>,
+
+    /// Prints the statistics as JSON.
+    #[arg(long)]
+    pub json: bool,
 }
 
 /// Executes the `index` subcommand.
 pub async fn run(args: IndexArgs, config: &Config) -> anyhow::Result<()> {
+    if let Some(IndexCommands::Stats(stats_args)) = &args.command {
+        return stats(stats_args, config);
+    }
+
+    // Write where `check --path` looks for the index unless told otherwise.
+    let output = match &args.output {
+        Some(output) => PathBuf::from(output),
+        None => {
+            let configured = Path::new(config.index_path().unwrap_or(DEFAULT_INDEX_PATH));
+            Path::new(&args.path).join(configured)
+        }
+    };
+    let allow = if args.allow.is_empty() {
+        &config.paths.allow
+    } else {
+        &args.allow
+    };
+    let deny = if args.deny.is_empty() {
+        &config.paths.deny
+    } else {
+        &args.deny
+    };
+
     log::info!("Running 'index' with the following arguments:");
     log::info!("  Path: {}", args.path);
     log::info!("  Force: {}", args.force);
-    log::info!("  Output: {}", args.output);
+    log::info!("  Output: {}", output.display());
+    log::info!("  Allow: {:?}", allow);
+    log::info!("  Deny: {:?}", deny);
+    log::info!("  Embedder: {:?}", config.rag.embedder);
+    log::info!("  Store: {:?}", args.store);
 
     // Build (or load) the index using the repository indexer and CLI configuration.
-    let store = index_repository(
-        &args.path,
-        &args.output,
-        args.force,
-        &config.paths.allow,
-        &config.paths.deny,
-    )
-    .await
-    .map_err(|e| anyhow::anyhow!(e))?;
+    let options = IndexOptions {
+        embedder: create_embedder(config)?,
+        ..IndexOptions::default()
+    };
+    if args.store == IndexStore::Qdrant {
+        return index_into_qdrant(&args.path, allow, deny, &options, config).await;
+    }
+    let store = index_repository_with(&args.path, &output, args.force, allow, deny, &options)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
     log::info!(
         "Index available with {} documents at {}",
         store.len(),
-        args.output
+        output.display()
     );
 
     Ok(())
 }
+
+/// Indexes the repository into the `[rag.qdrant]` collection.
+#[cfg(feature = "qdrant")]
+async fn index_into_qdrant(
+    path: &str,
+    allow: &[String],
+    deny: &[String],
+    options: &IndexOptions,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let mut store = engine::rag::QdrantVectorStore::from_config(config)?;
+    let count = engine::rag::index_repository_into(path, allow, deny, options, &mut store).await?;
+    log::info!("Indexed {} documents into Qdrant", count);
+    Ok(())
+}
+
+#[cfg(not(feature = "qdrant"))]
+async fn index_into_qdrant(
+    _path: &str,
+    _allow: &[String],
+    _deny: &[String],
+    _options: &IndexOptions,
+    _config: &Config,
+) -> anyhow::Result<()> {
+    anyhow::bail!("`--store qdrant` requires reviewlens to be built with the `qdrant` feature")
+}
+
+/// Executes `index stats`.
+fn stats(args: &StatsArgs, config: &Config) -> anyhow::Result<()> {
+    let path = args
+        .index
+        .as_deref()
+        .or(config.index_path())
+        .unwrap_or(DEFAULT_INDEX_PATH);
+    let store = InMemoryVectorStore::load_from_disk(path)
+        .map_err(|e| anyhow::anyhow!("failed to load index {}: {}", path, e))?;
+    let stats = store.stats();
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print!("{}", render_stats(path, &stats));
+    }
+    Ok(())
+}
+
+/// Formats index statistics for humans.
+fn render_stats(path: &str, stats: &IndexStats) -> String {
+    let mut out = format!("Index: {}\n", path);
+    out.push_str(&format!("Documents: {}\n", stats.documents));
+    out.push_str(&format!("Content: {} bytes\n", stats.content_bytes));
+    match stats.dimension {
+        Some(dimension) => out.push_str(&format!(
+            "Embedder: {} ({} dimensions)\n",
+            stats.embedder, dimension
+        )),
+        None => out.push_str(&format!("Embedder: {}\n", stats.embedder)),
+    }
+    out.push_str(&format!("Empty embeddings: {}\n", stats.empty_embeddings));
+    let built = stats
+        .built_at
+        .and_then(|secs| Utc.timestamp_opt(secs as i64, 0).single())
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_else(|| "unknown".to_string());
+    out.push_str(&format!("Built: {}\n", built));
+    out.push_str(&format!(
+        "Commit: {}\n",
+        stats.head_commit.as_deref().unwrap_or("unknown")
+    ));
+
+    if !stats.largest.is_empty() {
+        out.push_str("\nLargest documents:\n");
+        for doc in &stats.largest {
+            out.push_str(&format!("  {:>10}  {}\n", doc.bytes, doc.filename));
+        }
+    }
+    if !stats.extensions.is_empty() {
+        out.push_str("\nExtensions:\n");
+        let mut extensions: Vec<_> = stats.extensions.iter().collect();
+        extensions.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (ext, count) in extensions {
+            out.push_str(&format!("  {:>10}  {}\n", count, ext));
+        }
+    }
+    out
+}
@@ -0,0 +1,133 @@
+//! The `llm` subcommand.
+
+use crate::exit_code::ExitCode;
+use clap::{Args, Subcommand};
+use engine::compiled_providers;
+use engine::config::{Config, Provider};
+use engine::llm::create_llm_provider;
+use engine::privacy::Redactor;
+use std::time::Instant;
+
+/// The prompt sent by `llm ping`.
+const PING_PROMPT: &str = "Reply with OK";
+
+#[derive(Args, Debug, Clone)]
+pub struct LlmArgs {
+    #[command(subcommand)]
+    pub command: LlmCommands,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum LlmCommands {
+    /// Sends a tiny prompt to the configured provider and prints the
+    /// latency, model and token usage.
+    Ping(PingArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct PingArgs {
+    /// Also ping every other compiled provider whose API key is set in the
+    /// environment, e.g. `OPENAI_API_KEY`.
+    #[arg(long)]
+    pub all: bool,
+}
+
+/// Environment variable holding the API key `--all` uses for `provider`,
+/// and the model it pings with.
+fn env_credentials(provider: &Provider) -> Option<(&'static str, &'static str)> {
+    match provider {
+        Provider::Null => None,
+        Provider::Openai => Some(("OPENAI_API_KEY", "gpt-4o-mini")),
+        Provider::Anthropic => Some(("ANTHROPIC_API_KEY", "claude-3-5-haiku-latest")),
+        Provider::Deepseek => Some(("DEEPSEEK_API_KEY", "deepseek-chat")),
+    }
+}
+
+/// Executes the `llm` subcommand.
+pub async fn run(args: LlmArgs, config: &Config) -> anyhow::Result<ExitCode> {
+    match args.command {
+        LlmCommands::Ping(args) => ping(args, config).await,
+    }
+}
+
+/// Pings the configured provider, and with `--all` every other compiled
+/// provider with credentials in the environment. Succeeds only if every
+/// pinged provider answered; otherwise the first failure sets the exit code.
+async fn ping(args: PingArgs, config: &Config) -> anyhow::Result<ExitCode> {
+    let redactor = Redactor::new(&config.privacy.redaction)?;
+    let mut configs = vec![config.clone()];
+    if args.all {
+        for provider in compiled_providers() {
+            if provider == config.llm.provider {
+                continue;
+            }
+            let Some((var, model)) = env_credentials(&provider) else {
+                continue;
+            };
+            match std::env::var(var).ok().filter(|key| !key.is_empty()) {
+                Some(key) => {
+                    let mut config = config.clone();
+                    config.llm.provider = provider;
+                    config.llm.api_key = Some(key);
+                    config.llm.model = Some(model.to_string());
+                    config.llm.base_url = None;
+                    configs.push(config);
+                }
+                None => println!("{}: skipped, {} is not set", provider.as_str(), var),
+            }
+        }
+    }
+
+    let mut code = ExitCode::Success;
+    for mut config in configs {
+        // A cached answer would say nothing about the credentials.
+        config.llm.cache = false;
+        let line = match ping_one(&config).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                if code == ExitCode::Success {
+                    code = ExitCode::from(&e);
+                }
+                format!("failed: {:#}", e)
+            }
+        };
+        println!(
+            "{}: {}",
+            redactor.redact(&describe(&config)),
+            redactor.redact(&line)
+        );
+    }
+    Ok(code)
+}
+
+/// Names the provider of `config` with its model and base URL, if set.
+fn describe(config: &Config) -> String {
+    let mut details: Vec<&str> = Vec::new();
+    details.extend(config.llm.model.as_deref());
+    details.extend(config.llm.base_url.as_deref());
+    if details.is_empty() {
+        config.llm.provider.as_str().to_string()
+    } else {
+        format!(
+            "{} ({})",
+            config.llm.provider.as_str(),
+            details.join(" at ")
+        )
+    }
+}
+
+/// Sends the ping prompt with the provider of `config` and describes the
+/// answer. The null provider is not called.
+async fn ping_one(config: &Config) -> anyhow::Result<String> {
+    if config.llm.provider == Provider::Null {
+        return Ok("offline mode, no requests are sent".to_string());
+    }
+    let provider = create_llm_provider(config)?;
+    let start = Instant::now();
+    let response = provider.generate(PING_PROMPT).await?;
+    Ok(format!(
+        "OK in {} ms, {} tokens",
+        start.elapsed().as_millis(),
+        response.token_usage
+    ))
+}
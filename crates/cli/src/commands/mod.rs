@@ -1,6 +1,15 @@
 //! This module contains the logic for the CLI subcommands.
 
+pub mod audit_ignores;
 pub mod check;
+pub mod compare;
+pub mod config;
+pub mod fix;
 pub mod index;
+pub mod llm;
 pub mod print_config;
+pub mod rules;
+pub mod scan_file;
+pub mod schema;
+pub mod search;
 pub mod version;
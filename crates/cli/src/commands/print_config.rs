@@ -1,7 +1,10 @@
 //! The `print-config` subcommand.
 
 use clap::Args;
-use engine::{compiled_providers, config::Config};
+use engine::{
+    compiled_providers,
+    config::{Config, Provenance},
+};
 use std::process::Command;
 
 use anyhow::Context;
@@ -16,13 +19,33 @@ pub struct PrintConfigArgs {
     /// The path to the repository to inspect.
     #[arg(long, default_value = ".")]
     pub path: String,
+
+    /// Show the effective configuration for this file, with matching
+    /// `[[overrides]]` applied.
+    #[arg(long)]
+    pub for_path: Option<String>,
 }
 
-/// Executes the `print-config` subcommand.
-pub fn run(args: PrintConfigArgs, config: &Config) -> anyhow::Result<()> {
+/// Executes the `print-config` subcommand. When `verbose` is set, the layer
+/// that supplied each top-level section is listed after the configuration.
+pub fn run(
+    args: PrintConfigArgs,
+    config: &Config,
+    provenance: &Provenance,
+    verbose: bool,
+) -> anyhow::Result<()> {
     // Serialize the config to a pretty JSON string.
-    let config_json = serde_json::to_string_pretty(config)?;
-    log::info!("{}", config_json);
+    let config_json = match &args.for_path {
+        Some(path) => serde_json::to_string_pretty(&config.for_path(path)?)?,
+        None => serde_json::to_string_pretty(config)?,
+    };
+    println!("{}", config_json);
+    if !provenance.deprecations().is_empty() {
+        println!("Deprecated keys:");
+        for deprecation in provenance.deprecations() {
+            println!("  {}", deprecation);
+        }
+    }
 
     // Resolve the base reference, falling back to upstream if not provided.
     let base_ref = if let Some(base) = args.base_ref.clone() {
@@ -47,14 +70,20 @@ pub fn run(args: PrintConfigArgs, config: &Config) -> anyhow::Result<()> {
             .trim()
             .to_string()
     };
-    log::info!("Base ref: {}", base_ref);
+    println!("Base ref: {}", base_ref);
 
     let providers = compiled_providers()
         .into_iter()
         .map(|p| p.as_str().to_string())
         .collect::<Vec<_>>();
-    log::info!("Compiled providers: {}", providers.join(", "));
-    log::info!(
+    println!("Compiled providers: {}", providers.join(", "));
+    if verbose {
+        println!("Sources:");
+        for section in config.sections() {
+            println!("  {}: {}", section, provenance.source(&section));
+        }
+    }
+    println!(
         "CI mode ('check --ci') forces generation.temperature=0.0 and requires an LLM model when the provider isn't 'null'"
     );
     Ok(())
@@ -0,0 +1,67 @@
+//! The `rules` subcommand.
+
+use clap::Args;
+use engine::config::Config;
+use engine::scanner::rule_catalog;
+
+#[derive(Args, Debug)]
+pub struct RulesArgs {
+    /// Prints the rules as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Executes the `rules` subcommand.
+pub fn run(args: RulesArgs, config: &Config) -> anyhow::Result<()> {
+    let rules = rule_catalog(config);
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&rules)?);
+        return Ok(());
+    }
+    let rows: Vec<[String; 5]> = rules
+        .iter()
+        .map(|rule| {
+            [
+                rule.id.clone(),
+                rule.name.clone(),
+                rule.default_severity
+                    .as_ref()
+                    .map_or("-", |s| s.as_str())
+                    .to_string(),
+                if rule.languages.is_empty() {
+                    "all".to_string()
+                } else {
+                    rule.languages.join(", ")
+                },
+                if rule.enabled { "yes" } else { "no" }.to_string(),
+            ]
+        })
+        .collect();
+    let header = ["RULE", "NAME", "SEVERITY", "LANGUAGES", "ENABLED"];
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain([header[i].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: [&str; 5], description: &str| {
+        let mut out = String::new();
+        for (cell, width) in cells.iter().zip(&widths) {
+            out.push_str(&format!("{:<width$}  ", cell, width = width));
+        }
+        out.push_str(description);
+        println!("{}", out.trim_end());
+    };
+    line(header, "DESCRIPTION");
+    for (rule, row) in rules.iter().zip(&rows) {
+        line(
+            [&row[0], &row[1], &row[2], &row[3], &row[4]],
+            &rule.description,
+        );
+    }
+    Ok(())
+}
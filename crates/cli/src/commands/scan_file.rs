@@ -0,0 +1,49 @@
+//! The `scan-file` subcommand.
+
+use anyhow::Context;
+use clap::Args;
+use engine::config::Config;
+use engine::llm::NullProvider;
+use engine::ReviewEngine;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct ScanFileArgs {
+    /// The file to scan, relative to the repository root.
+    pub path: PathBuf,
+
+    /// Prints the findings as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Executes the `scan-file` subcommand.
+pub fn run(args: ScanFileArgs, config: &Config) -> anyhow::Result<()> {
+    let content = fs::read_to_string(&args.path)
+        .with_context(|| format!("failed to read {}", args.path.display()))?;
+    // Findings come from the scanners alone, so no LLM is set up.
+    let engine = ReviewEngine::builder(config.clone())
+        .llm(Box::new(NullProvider))
+        .build()?;
+    let path = args.path.to_string_lossy().replace('\\', "/");
+    let issues = engine.scan_content(&path, &content)?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&issues)?);
+    } else if issues.is_empty() {
+        println!("No issues found.");
+    } else {
+        for issue in &issues {
+            println!(
+                "{}:{}  {}  {}  {}",
+                issue.file_path,
+                issue.line_number,
+                issue.severity.as_str(),
+                issue.rule_id,
+                issue.title
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+//! The `schema` subcommand.
+
+use clap::{Args, ValueEnum};
+use engine::schema::SchemaKind;
+
+/// The format whose schema is printed.
+#[derive(Clone, ValueEnum, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// The JSON report written by `check --format json`.
+    Report,
+    /// The `reviewlens.toml` configuration file.
+    Config,
+}
+
+#[derive(Args, Debug)]
+pub struct SchemaArgs {
+    /// The format to describe.
+    #[arg(long, value_enum)]
+    pub kind: Kind,
+}
+
+/// Executes the `schema` subcommand.
+pub fn run(args: SchemaArgs) -> anyhow::Result<()> {
+    let kind = match args.kind {
+        Kind::Report => SchemaKind::Report,
+        Kind::Config => SchemaKind::Config,
+    };
+    println!("{}", generate(kind)?);
+    Ok(())
+}
+
+/// Generates the pretty-printed JSON Schema for `kind`.
+#[cfg(feature = "schema")]
+fn generate(kind: SchemaKind) -> anyhow::Result<String> {
+    Ok(serde_json::to_string_pretty(&engine::schema::generate(
+        kind,
+    ))?)
+}
+
+#[cfg(not(feature = "schema"))]
+fn generate(_kind: SchemaKind) -> anyhow::Result<String> {
+    anyhow::bail!("`schema` requires reviewlens to be built with the `schema` feature")
+}
@@ -0,0 +1,78 @@
+//! The `search` subcommand.
+
+use clap::Args;
+use engine::config::{Config, DEFAULT_INDEX_PATH};
+use engine::rag::{create_embedder, Document, InMemoryVectorStore, RagContextRetriever};
+use serde_json::json;
+
+/// Longest snippet printed per hit, in characters.
+const SNIPPET_CHARS: usize = 120;
+
+#[derive(Args, Debug)]
+pub struct SearchArgs {
+    /// The text to search the index for.
+    pub query: String,
+
+    /// Number of hits to return.
+    #[arg(long, default_value_t = 5)]
+    pub top_k: usize,
+
+    /// The index file to search. Defaults to the configured `[index].path`.
+    #[arg(long)]
+    pub index: Option<String>,
+
+    /// Prints the hits as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Executes the `search` subcommand.
+pub async fn run(args: SearchArgs, config: &Config) -> anyhow::Result<()> {
+    let path = args
+        .index
+        .as_deref()
+        .or(config.index_path())
+        .unwrap_or(DEFAULT_INDEX_PATH);
+    let store = InMemoryVectorStore::load_from_disk(path)
+        .map_err(|e| anyhow::anyhow!("failed to load index {}: {}", path, e))?;
+    let retriever = RagContextRetriever::for_index(store, create_embedder(config)?)?;
+    let hits = retriever.search(&args.query, args.top_k).await?;
+
+    if args.json {
+        let hits: Vec<_> = hits
+            .iter()
+            .map(|(doc, score)| {
+                json!({
+                    "filename": doc.filename,
+                    "score": score,
+                    "snippet": snippet(doc, &args.query),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+    } else if hits.is_empty() {
+        println!("No results.");
+    } else {
+        for (i, (doc, score)) in hits.iter().enumerate() {
+            println!("{}. {} (score {:.4})", i + 1, doc.filename, score);
+            println!("   {}", snippet(doc, &args.query));
+        }
+    }
+    Ok(())
+}
+
+/// Picks the first line of `doc` sharing a word with `query`, falling back to
+/// the first non-empty line, trimmed to `SNIPPET_CHARS`.
+fn snippet(doc: &Document, query: &str) -> String {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let mut lines = doc.content.lines().map(str::trim);
+    let line = lines
+        .clone()
+        .find(|l| words.iter().any(|w| l.contains(w)))
+        .or_else(|| lines.find(|l| !l.is_empty()))
+        .unwrap_or_default();
+    match line.char_indices().nth(SNIPPET_CHARS) {
+        Some((i, _)) => format!("{}…", &line[..i]),
+        None => line.to_string(),
+    }
+}
@@ -0,0 +1,71 @@
+//! Process exit codes.
+//!
+//! Every command ends through `ExitCode`, so the same failure always maps to
+//! the same code. Errors are classified by the first `EngineError` in their
+//! chain; anything else is a runtime error.
+
+use engine::error::EngineError;
+
+/// The exit status of a `reviewlens` invocation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitCode {
+    /// The command completed and nothing reached the failure threshold.
+    Success = 0,
+    /// Findings at or above `fail-on` were reported.
+    FindingsAboveThreshold = 1,
+    /// The configuration or command-line arguments are invalid.
+    ConfigError = 2,
+    /// Any other failure, such as an unreadable file or report.
+    RuntimeError = 3,
+    /// The LLM provider failed or timed out and `[llm] required` is set.
+    LlmError = 4,
+    /// A git command failed or could not be run.
+    GitError = 5,
+}
+
+impl ExitCode {
+    /// Maps the outcome of a command that reports whether findings reached
+    /// the failure threshold, logging the error if it failed.
+    pub fn from_outcome(result: anyhow::Result<bool>) -> Self {
+        match result {
+            Ok(true) => ExitCode::FindingsAboveThreshold,
+            Ok(false) => ExitCode::Success,
+            Err(e) => {
+                log::error!("{:#}", e);
+                ExitCode::from(&e)
+            }
+        }
+    }
+
+    /// Exits the process with this code.
+    pub fn exit(self) -> ! {
+        std::process::exit(self as i32)
+    }
+}
+
+impl From<&EngineError> for ExitCode {
+    fn from(e: &EngineError) -> Self {
+        match e {
+            EngineError::Config(_) => ExitCode::ConfigError,
+            EngineError::LlmProvider(_)
+            | EngineError::LlmTimeout(_)
+            | EngineError::TokenBudgetExceeded { .. } => ExitCode::LlmError,
+            EngineError::Git(_) => ExitCode::GitError,
+            EngineError::Io { .. }
+            | EngineError::Scanner(_)
+            | EngineError::Rag(_)
+            | EngineError::DiffParser(_)
+            | EngineError::Report(_)
+            | EngineError::Publish(_)
+            | EngineError::Unknown => ExitCode::RuntimeError,
+        }
+    }
+}
+
+impl From<&anyhow::Error> for ExitCode {
+    fn from(e: &anyhow::Error) -> Self {
+        e.chain()
+            .find_map(|cause| cause.downcast_ref::<EngineError>())
+            .map_or(ExitCode::RuntimeError, ExitCode::from)
+    }
+}
@@ -0,0 +1,52 @@
+//! Logger initialization for the CLI.
+//!
+//! Log records are always written to stderr so they never interleave with
+//! command output on stdout. Two formats are supported: a plain human format
+//! for interactive use and newline-delimited JSON for CI pipelines.
+
+use chrono::{SecondsFormat, Utc};
+use clap::ValueEnum;
+use env_logger::Target;
+use log::{LevelFilter, Record};
+use serde_json::json;
+use std::io::Write;
+
+/// Output format for log records.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Plain messages, one per line.
+    Human,
+    /// One JSON object per line with `ts`, `level`, `module`, and `msg` keys.
+    Json,
+}
+
+/// Serializes a log record as a single-line JSON object.
+fn json_line(record: &Record) -> String {
+    json!({
+        "ts": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        "level": record.level().to_string(),
+        "module": record.module_path().unwrap_or_default(),
+        "msg": record.args().to_string(),
+    })
+    .to_string()
+}
+
+/// Installs the global logger.
+///
+/// `level` is derived from the `-v` count; `RUST_LOG` is still honored for
+/// per-module filters.
+pub fn init(level: LevelFilter, format: LogFormat) {
+    let mut builder =
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"));
+    builder.filter_level(level);
+    builder.target(Target::Stderr);
+    match format {
+        LogFormat::Json => {
+            builder.format(|f, record| writeln!(f, "{}", json_line(record)));
+        }
+        LogFormat::Human => {
+            builder.format(|f, record| writeln!(f, "{}", record.args()));
+        }
+    }
+    builder.init();
+}
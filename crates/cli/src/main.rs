@@ -1,19 +1,17 @@
 //! The command-line interface for the Intelligent Code Review Agent.
 
-use chrono::Utc;
-use clap::Parser;
-use engine::{
-    config::{Config, IndexConfig, Provider},
-    error::EngineError,
-    ReviewEngine,
-};
-use env_logger::Target;
+use clap::{Parser, ValueEnum};
+use commands::config::ConfigCommands;
+use engine::config::{Config, ConfigSource, IndexConfig, Provider, Severity, ValidationProblem};
+use engine::error::EngineError;
+use exit_code::ExitCode;
 use log::LevelFilter;
-use serde_json::json;
-use std::io::Write;
-use std::path::PathBuf;
+use logging::LogFormat;
+use std::path::{Path, PathBuf};
 
 mod commands;
+mod exit_code;
+mod logging;
 
 /// A context-aware, security-first code review agent that runs locally or in CI.
 #[derive(Parser, Debug)]
@@ -24,10 +22,25 @@ struct Cli {
     #[arg(short, long, action = clap::ArgAction::Count)]
     verbose: u8,
 
+    /// Format of log records written to stderr. Defaults to `json` with `check --ci`
+    /// and `human` otherwise.
+    #[arg(long, value_enum, global = true, env = "REVIEWLENS_LOG_FORMAT")]
+    log_format: Option<LogFormat>,
+
     /// Path to configuration file.
     #[arg(long, value_name = "PATH", default_value = "reviewlens.toml")]
     config: PathBuf,
 
+    /// Path to the user-global configuration file, merged beneath `--config`.
+    /// Defaults to `reviewlens/config.toml` in the platform config directory.
+    #[arg(long, value_name = "PATH", env = "REVIEWLENS_USER_CONFIG")]
+    user_config: Option<PathBuf>,
+
+    /// Reject unknown configuration keys, invalid patterns, and incomplete
+    /// provider settings before running any command.
+    #[arg(long, global = true)]
+    strict_config: bool,
+
     /// Override the LLM provider.
     #[arg(long, value_enum, env = "REVIEWLENS_LLM_PROVIDER")]
     llm_provider: Option<Provider>,
@@ -44,6 +57,11 @@ struct Cli {
     #[arg(long, env = "REVIEWLENS_LLM_BASE_URL")]
     llm_base_url: Option<String>,
 
+    /// Write each LLM request and response, redacted, to numbered files in
+    /// this directory.
+    #[arg(long, value_name = "DIR", env = "REVIEWLENS_LLM_DEBUG_DIR")]
+    debug_llm: Option<String>,
+
     /// Override the path to the RAG index.
     #[arg(long, env = "REVIEWLENS_INDEX_PATH")]
     index_path: Option<String>,
@@ -56,6 +74,22 @@ struct Cli {
     #[arg(long, env = "REVIEWLENS_GENERATION_TEMPERATURE")]
     generation_temperature: Option<f32>,
 
+    /// Override the maximum tokens generated per response.
+    #[arg(long, env = "REVIEWLENS_GENERATION_MAX_TOKENS")]
+    generation_max_tokens: Option<u32>,
+
+    /// Override generation top-p.
+    #[arg(long, env = "REVIEWLENS_GENERATION_TOP_P")]
+    generation_top_p: Option<f32>,
+
+    /// Override the generation seed (OpenAI only).
+    #[arg(long, env = "REVIEWLENS_GENERATION_SEED")]
+    generation_seed: Option<u64>,
+
+    /// Override generation stop sequences (comma separated).
+    #[arg(long, value_delimiter = ',', env = "REVIEWLENS_GENERATION_STOP")]
+    generation_stop: Vec<String>,
+
     /// Override allowed paths (comma separated).
     #[arg(long, value_delimiter = ',', env = "REVIEWLENS_PATHS_ALLOW")]
     paths_allow: Vec<String>,
@@ -76,6 +110,17 @@ struct Cli {
     )]
     privacy_redaction_patterns: Vec<String>,
 
+    /// Override a rule's severity or disable it, e.g. `--rule secrets=low` or
+    /// `--rule conventions=off`. Repeatable; the environment variable takes
+    /// comma-separated pairs.
+    #[arg(
+        long = "rule",
+        value_name = "NAME=SEVERITY|off",
+        value_delimiter = ',',
+        env = "REVIEWLENS_RULES"
+    )]
+    rules: Vec<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -83,127 +128,298 @@ struct Cli {
 /// The subcommands for the CLI.
 #[derive(Parser, Debug)]
 enum Commands {
+    /// Lists `reviewlens:ignore` directives and whether each still silences a finding.
+    AuditIgnores(commands::audit_ignores::AuditIgnoresArgs),
     /// Checks a diff for issues and generates a review report.
-    Check(commands::check::CheckArgs),
+    Check(Box<commands::check::CheckArgs>),
+    /// Compares two JSON review reports and lists new, resolved, and persisting findings.
+    Compare(commands::compare::CompareArgs),
+    /// Inspects and validates the configuration.
+    Config(commands::config::ConfigArgs),
+    /// Applies the suggested fixes stored in a JSON review report.
+    Fix(commands::fix::FixArgs),
     /// Manages the RAG index for a repository.
     Index(commands::index::IndexArgs),
+    /// Checks that the configured LLM provider is reachable.
+    Llm(commands::llm::LlmArgs),
     /// Prints the effective configuration, compiled providers, and resolved base reference.
     PrintConfig(commands::print_config::PrintConfigArgs),
+    /// Lists every rule with its severity, languages and description.
+    Rules(commands::rules::RulesArgs),
+    /// Scans a single file without a diff or the LLM and lists its findings.
+    ScanFile(commands::scan_file::ScanFileArgs),
+    /// Prints the JSON Schema of the JSON report or the configuration file.
+    Schema(commands::schema::SchemaArgs),
+    /// Runs an ad-hoc query against the RAG index.
+    Search(commands::search::SearchArgs),
     /// Prints the CLI version.
     Version(commands::version::VersionArgs),
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
-
-    let ci_logs = matches!(&cli.command, Commands::Check(args) if args.ci);
-
-    let mut builder =
-        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"));
-    builder.filter_level(match cli.verbose {
-        0 => LevelFilter::Warn,
-        1 => LevelFilter::Info,
-        2 => LevelFilter::Debug,
-        _ => LevelFilter::Trace,
-    });
-    if matches!(cli.command, Commands::PrintConfig(_)) && cli.verbose == 0 {
-        builder.filter_level(LevelFilter::Info);
-    }
-    builder.target(Target::Stdout);
-    if ci_logs {
-        builder.format(|f, record| {
-            let ts = Utc::now().to_rfc3339();
-            let log = json!({
-                "level": record.level().to_string(),
-                "msg": record.args().to_string(),
-                "module": record.module_path().unwrap_or_default(),
-                "ts": ts,
-            });
-            writeln!(f, "{}", log)
-        });
-    } else {
-        builder.format(|f, record| writeln!(f, "{}", record.args()));
-    }
-    builder.init();
-
-    if let Commands::Version(args) = &cli.command {
-        return commands::version::run(args.clone());
-    }
+/// Returns the default location of the user-global configuration file.
+fn default_user_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("reviewlens").join("config.toml"))
+}
 
-    // Load configuration from the path specified in the CLI arguments.
-    // If the file doesn't exist, use the default configuration.
-    let mut config = if cli.config.exists() {
-        if !matches!(cli.command, Commands::PrintConfig(_)) {
-            log::info!("Loading configuration from: {:?}", cli.config);
-        }
-        Config::load_from_path(&cli.config)?
-    } else {
-        if !matches!(cli.command, Commands::PrintConfig(_)) {
-            log::info!(
-                "Configuration file {:?} not found. Using default configuration.",
-                cli.config
-            );
-        }
-        Config::default()
+/// Applies a `--rule NAME=SEVERITY|off` override to `config`.
+fn apply_rule_flag(config: &mut Config, spec: &str) -> Result<(), String> {
+    let (name, value) = spec
+        .split_once('=')
+        .ok_or_else(|| "expected NAME=SEVERITY or NAME=off".to_string())?;
+    let severity = match value.trim() {
+        "off" => None,
+        value => Some(Severity::from_str(value, true).map_err(|_| {
+            format!(
+                "invalid severity `{}`; expected critical, high, medium, low or off",
+                value
+            )
+        })?),
     };
+    engine::scanner::override_rule(config, name.trim(), severity).map_err(|e| match e {
+        EngineError::Config(message) => message,
+        e => e.to_string(),
+    })
+}
 
-    // Apply environment variable and CLI overrides.
-    if let Some(p) = cli.llm_provider {
+/// Applies the environment variable and command-line overrides in `cli` to
+/// `config`. Returns the sections overridden, or the `--rule` flags that
+/// could not be applied.
+fn apply_overrides(
+    config: &mut Config,
+    cli: &Cli,
+) -> Result<Vec<&'static str>, Vec<ValidationProblem>> {
+    let mut sections = Vec::new();
+    if let Some(p) = cli.llm_provider.clone() {
         config.llm.provider = p;
+        sections.push("llm");
     }
-    if let Some(model) = cli.llm_model {
+    if let Some(model) = cli.llm_model.clone() {
         config.llm.model = Some(model);
+        sections.push("llm");
     }
-    if let Some(key) = cli.llm_api_key {
+    if let Some(key) = cli.llm_api_key.clone() {
         config.llm.api_key = Some(key);
+        sections.push("llm");
     }
-    if let Some(url) = cli.llm_base_url {
+    if let Some(url) = cli.llm_base_url.clone() {
         config.llm.base_url = Some(url);
+        sections.push("llm");
     }
-    if let Some(path) = cli.index_path {
-        config.index = Some(IndexConfig { path });
+    if let Some(dir) = cli.debug_llm.clone() {
+        config.llm.debug_dir = Some(dir);
+        sections.push("llm");
+    }
+    if let Some(path) = cli.index_path.clone() {
+        config.index = Some(IndexConfig {
+            path,
+            ..config.index.clone().unwrap_or_default()
+        });
+        sections.push("index");
     }
     if let Some(max) = cli.budget_tokens_max_per_run {
         config.budget.tokens.max_per_run = Some(max);
+        sections.push("budget");
     }
     if let Some(temp) = cli.generation_temperature {
         config.generation.temperature = Some(temp);
+        sections.push("generation");
+    }
+    if let Some(max) = cli.generation_max_tokens {
+        config.generation.max_tokens = max;
+        sections.push("generation");
+    }
+    if let Some(top_p) = cli.generation_top_p {
+        config.generation.top_p = Some(top_p);
+        sections.push("generation");
+    }
+    if let Some(seed) = cli.generation_seed {
+        config.generation.seed = Some(seed);
+        sections.push("generation");
+    }
+    if !cli.generation_stop.is_empty() {
+        config.generation.stop = cli.generation_stop.clone();
+        sections.push("generation");
     }
     if !cli.paths_allow.is_empty() {
         config.paths.allow = cli.paths_allow.clone();
+        sections.push("paths");
     }
     if !cli.paths_deny.is_empty() {
         config.paths.deny = cli.paths_deny.clone();
+        sections.push("paths");
     }
     if let Some(enabled) = cli.privacy_redaction_enabled {
         config.privacy.redaction.enabled = enabled;
+        sections.push("privacy");
     }
     if !cli.privacy_redaction_patterns.is_empty() {
         config.privacy.redaction.patterns = cli.privacy_redaction_patterns.clone();
+        sections.push("privacy");
+    }
+    if !cli.rules.is_empty() {
+        let rule_problems: Vec<ValidationProblem> = cli
+            .rules
+            .iter()
+            .filter_map(|spec| {
+                apply_rule_flag(config, spec)
+                    .err()
+                    .map(|message| ValidationProblem {
+                        path: format!("--rule {}", spec),
+                        message,
+                    })
+            })
+            .collect();
+        if !rule_problems.is_empty() {
+            return Err(rule_problems);
+        }
+        sections.push("rules");
+    }
+    Ok(sections)
+}
+
+/// Loads a workspace repository's own configuration file the way the
+/// invoking one was loaded: over the user-global file, with the same
+/// overrides applied.
+fn load_repo_config(cli: &Cli, user_config: Option<&Path>, path: &Path) -> anyhow::Result<Config> {
+    let (mut config, provenance) = Config::load_layered(user_config, path)?;
+    for deprecation in provenance.deprecations() {
+        log::warn!("{}: {}", path.display(), deprecation);
+    }
+    if let Err(problems) = apply_overrides(&mut config, cli) {
+        let problems: Vec<String> = problems.iter().map(ToString::to_string).collect();
+        return Err(EngineError::Config(problems.join("; ")).into());
+    }
+    Ok(config)
+}
+
+#[tokio::main]
+async fn main() {
+    let code = run().await.unwrap_or_else(|e| {
+        eprintln!("Error: {:?}", e);
+        ExitCode::from(&e)
+    });
+    code.exit()
+}
+
+/// Parses the arguments and runs the command. Errors it returns are
+/// classified by `ExitCode::from`.
+async fn run() -> anyhow::Result<ExitCode> {
+    let cli = Cli::parse();
+
+    let ci_mode = matches!(&cli.command, Commands::Check(args) if args.ci);
+    let log_format = cli.log_format.unwrap_or(if ci_mode {
+        LogFormat::Json
+    } else {
+        LogFormat::Human
+    });
+    logging::init(
+        match cli.verbose {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Info,
+            2 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        },
+        log_format,
+    );
+
+    if let Commands::Version(args) = &cli.command {
+        return commands::version::run(args.clone()).map(|()| ExitCode::Success);
+    }
+
+    let user_config = cli.user_config.clone().or_else(default_user_config_path);
+
+    // Strict validation is always on for `config validate`.
+    let strict = cli.strict_config
+        || matches!(
+            &cli.command,
+            Commands::Config(args) if matches!(args.command, ConfigCommands::Validate)
+        );
+    let mut problems = Vec::new();
+    if strict {
+        if let Some(path) = &user_config {
+            problems.extend(commands::config::file_problems(path));
+        }
+        problems.extend(commands::config::file_problems(&cli.config));
+    }
+
+    // Merge the user-global file and the repository file; later layers win.
+    // If neither file exists, use the default configuration.
+    if let Some(path) = user_config.as_ref().filter(|p| p.exists()) {
+        log::info!("Loading user configuration from: {:?}", path);
+    }
+    if cli.config.exists() {
+        log::info!("Loading configuration from: {:?}", cli.config);
+    } else {
+        log::info!(
+            "Configuration file {:?} not found. Using default configuration.",
+            cli.config
+        );
+    }
+    let (mut config, mut provenance) =
+        match Config::load_layered(user_config.as_deref(), &cli.config) {
+            Ok(loaded) => loaded,
+            // A file that does not parse has no key-level problems to list;
+            // report the load error itself instead.
+            Err(_) if strict && !problems.is_empty() => {
+                return Ok(commands::config::report_problems(&problems))
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+    for deprecation in provenance.deprecations() {
+        log::warn!("{}", deprecation);
+    }
+
+    // Apply environment variable and CLI overrides.
+    match apply_overrides(&mut config, &cli) {
+        Ok(sections) => {
+            for section in sections {
+                provenance.record(section, ConfigSource::Override);
+            }
+        }
+        Err(rule_problems) => return Ok(commands::config::report_problems(&rule_problems)),
+    }
+
+    if strict {
+        problems.extend(config.validate());
+        if !problems.is_empty() {
+            return Ok(commands::config::report_problems(&problems));
+        }
     }
 
     match cli.command {
-        Commands::Check(args) => {
-            let engine = match ReviewEngine::new(config) {
-                Ok(engine) => engine,
-                Err(e) => {
-                    log::error!("{}", e);
-                    match e {
-                        EngineError::Config(_) => std::process::exit(2),
-                        _ => std::process::exit(3),
-                    }
-                }
-            };
-
-            let code = commands::check::run(args, &engine).await;
-            std::process::exit(code);
+        Commands::AuditIgnores(args) => {
+            commands::audit_ignores::run(args, &config)?;
+        }
+        Commands::Check(ref args) => {
+            let load_config = |path: &Path| load_repo_config(&cli, user_config.as_deref(), path);
+            return Ok(commands::check::run((**args).clone(), config, &load_config).await);
+        }
+        Commands::Compare(args) => return Ok(commands::compare::run(args, &config)),
+        Commands::Config(args) => {
+            commands::config::run(args, &config, &cli.config)?;
+        }
+        Commands::Fix(args) => {
+            commands::fix::run(args)?;
         }
         Commands::Index(args) => {
             commands::index::run(args, &config).await?;
         }
+        Commands::Llm(args) => return commands::llm::run(args, &config).await,
         Commands::PrintConfig(args) => {
-            commands::print_config::run(args, &config)?;
+            commands::print_config::run(args, &config, &provenance, cli.verbose > 0)?;
+        }
+        Commands::Rules(args) => {
+            commands::rules::run(args, &config)?;
+        }
+        Commands::ScanFile(args) => {
+            commands::scan_file::run(args, &config)?;
+        }
+        Commands::Schema(args) => {
+            commands::schema::run(args)?;
+        }
+        Commands::Search(args) => {
+            commands::search::run(args, &config).await?;
         }
         Commands::Version(_) => {
             // This case is handled above, but the compiler needs it to be exhaustive.
@@ -211,5 +427,5 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    Ok(())
+    Ok(ExitCode::Success)
 }
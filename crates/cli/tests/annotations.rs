@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::Path;
+use tempfile::{tempdir, TempDir};
+
+mod common;
+use common::{check, git};
+
+/// A repository with two hard-coded secrets staged for commit.
+fn repo_with_staged_secrets() -> TempDir {
+    let temp = tempdir().unwrap();
+    let repo = temp.path();
+    git(repo, &["init"]);
+    fs::write(
+        repo.join("a.js"),
+        "const API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\nconst B = 1;\nconst OTHER_API_KEY = \"ZYXWVUTSRQPONMLKJIHGFEDC\";\n",
+    )
+    .unwrap();
+    git(repo, &["add", "a.js"]);
+    temp
+}
+
+/// Runs `check --ci` on the staged changes and returns stdout.
+fn annotations(repo: &Path, github_actions: bool, extra: &[&str]) -> String {
+    let mut cmd = check(repo);
+    cmd.env_remove("GITHUB_ACTIONS")
+        .args(["--ci", "--staged", "--path", "."])
+        .args(["--format", "json", "--fail-on", "never"])
+        .args(extra);
+    if github_actions {
+        cmd.env("GITHUB_ACTIONS", "true");
+    }
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn github_actions_ci_prints_annotations() {
+    let temp = repo_with_staged_secrets();
+    let stdout = annotations(temp.path(), true, &[]);
+    let annotations: Vec<&str> = stdout.lines().filter(|l| l.starts_with("::")).collect();
+    assert_eq!(annotations.len(), 2, "{}", stdout);
+    assert!(
+        annotations[0].starts_with("::error file=a.js,line=1,title="),
+        "{}",
+        annotations[0]
+    );
+    assert!(annotations[1].starts_with("::error file=a.js,line=3,title="));
+    assert!(!stdout.contains("ABCDEFGHIJKLMNOPQRSTUVWX"));
+}
+
+#[test]
+fn annotations_are_capped_with_a_notice() {
+    let temp = repo_with_staged_secrets();
+    let stdout = annotations(temp.path(), true, &["--max-annotations", "1"]);
+    let annotations: Vec<&str> = stdout.lines().filter(|l| l.starts_with("::")).collect();
+    assert_eq!(annotations.len(), 2, "{}", stdout);
+    assert_eq!(
+        annotations[1],
+        "::notice::1 more finding omitted; see the review report"
+    );
+}
+
+#[test]
+fn annotations_follow_the_flag_outside_github_actions() {
+    let temp = repo_with_staged_secrets();
+    assert!(!annotations(temp.path(), false, &[]).contains("::error"));
+    assert!(annotations(temp.path(), false, &["--annotations", "github"]).contains("::error"));
+    assert!(!annotations(temp.path(), true, &["--annotations", "none"]).contains("::error"));
+}
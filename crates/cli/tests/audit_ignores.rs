@@ -0,0 +1,67 @@
+use serde_json::Value;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod common;
+use common::{repo_with_change, reviewlens};
+
+/// A repository with one directive still silencing a secret and one whose
+/// secret has been removed.
+fn repo() -> TempDir {
+    repo_with_change(
+        &[(
+            "a.js",
+            "// reviewlens:ignore secrets test fixture\n\
+             const API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n\
+             // reviewlens:ignore secrets\n\
+             const name = \"reviewlens\";\n",
+        )],
+        &[],
+    )
+}
+
+fn audit(root: &Path, extra: &[&str]) -> String {
+    let output = reviewlens(root)
+        .args(["audit-ignores", "--path", root.to_str().unwrap()])
+        .args(extra)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn json_lists_active_and_stale_directives() {
+    let temp = repo();
+    let audits: Value = serde_json::from_str(&audit(temp.path(), &["--json"])).unwrap();
+    let audits = audits.as_array().unwrap();
+    assert_eq!(audits.len(), 2, "{:#?}", audits);
+    assert_eq!(audits[0]["path"], "a.js");
+    assert_eq!(audits[0]["line"], 1);
+    assert_eq!(audits[0]["target"], 2);
+    assert_eq!(audits[0]["rule"], "secrets");
+    assert_eq!(audits[0]["reason"], "test fixture");
+    assert_eq!(audits[0]["status"], "active");
+    assert_eq!(audits[1]["line"], 3);
+    assert_eq!(audits[1]["status"], "stale");
+    assert!(audits[1].get("reason").is_none());
+}
+
+#[test]
+fn table_lists_each_directive_with_a_summary() {
+    let temp = repo();
+    let table = audit(temp.path(), &[]);
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines[0].starts_with("STATUS"), "{}", table);
+    assert!(lines[1].starts_with("active"));
+    assert!(lines[1].contains("a.js:1"));
+    assert!(lines[1].ends_with("test fixture"));
+    assert!(lines[2].starts_with("stale"));
+    assert!(table.contains("2 directives: 1 active, 1 stale, 0 unknown rule, 0 unchecked"));
+}
+
+#[test]
+fn repositories_without_directives_say_so() {
+    let temp = repo_with_change(&[("a.js", "const x = 1;\n")], &[]);
+    assert_eq!(audit(temp.path(), &[]), "No ignore directives found.\n");
+}
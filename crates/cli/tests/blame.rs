@@ -0,0 +1,84 @@
+use assert_cmd::Command;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+mod common;
+use common::git;
+
+fn commit_as(repo: &Path, name: &str, file: &str, content: &str) {
+    fs::write(repo.join(file), content).unwrap();
+    git(repo, &["add", file]);
+    git(
+        repo,
+        &[
+            "-c",
+            &format!("user.name={}", name),
+            "-c",
+            &format!("user.email={}@example.com", name.to_lowercase()),
+            "commit",
+            "-m",
+            &format!("add {}", file),
+        ],
+    );
+}
+
+#[test]
+fn check_annotates_issues_with_blame() {
+    let temp = tempdir().unwrap();
+    let repo = temp.path().join("repo");
+    fs::create_dir(&repo).unwrap();
+    git(&repo, &["init"]);
+    commit_as(
+        &repo,
+        "Alice",
+        "a.js",
+        "const x = 1;\nconst API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n",
+    );
+    commit_as(
+        &repo,
+        "Bob",
+        "b.js",
+        "const TOKEN = \"abcdefghijklmnopqrstuvwxyz\";\n",
+    );
+    let empty_tree = git(&repo, &["hash-object", "-t", "tree", "--stdin"]);
+
+    let config_path = temp.path().join("reviewlens.toml");
+    fs::write(&config_path, "[report]\nblame = true\n").unwrap();
+    let output_path = temp.path().join("report.json");
+
+    let mut cmd = Command::cargo_bin("reviewlens").unwrap();
+    cmd.env("REVIEWLENS_USER_CONFIG", temp.path().join("missing.toml"));
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "check",
+        "--path",
+        repo.to_str().unwrap(),
+        "--diff",
+        &empty_tree,
+        "--format",
+        "json",
+        "--output",
+        output_path.to_str().unwrap(),
+        "--fail-on",
+        "never",
+        "--no-progress",
+    ]);
+    cmd.assert().success();
+
+    let report: Value = serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    let author_of = |file: &str| {
+        issues
+            .iter()
+            .find(|i| i["file_path"] == file)
+            .unwrap_or_else(|| panic!("no issue for {}", file))["author"]
+            .clone()
+    };
+    assert_eq!(author_of("a.js"), "Alice");
+    assert_eq!(author_of("b.js"), "Bob");
+    let commit = issues[0]["commit"].as_str().unwrap();
+    assert_eq!(commit.len(), 40);
+}
@@ -0,0 +1,87 @@
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+mod common;
+use common::{git, reviewlens, run_with_json_report};
+
+const CI_BASE_VARS: &[&str] = &[
+    "GITHUB_BASE_REF",
+    "CI_MERGE_REQUEST_TARGET_BRANCH_NAME",
+    "CHANGE_TARGET",
+];
+
+/// A clone of a repository whose `main` advanced after the clone's
+/// detached `HEAD` branched off and changed `feature.js`. The clone has
+/// no upstream configured, as in a CI checkout.
+fn ci_checkout() -> tempfile::TempDir {
+    let temp = tempdir().unwrap();
+    let origin = temp.path().join("origin");
+    let clone = temp.path().join("clone");
+    fs::create_dir(&origin).unwrap();
+    git(&origin, &["init"]);
+    fs::write(origin.join("shared.js"), "const shared = 1;\n").unwrap();
+    fs::write(origin.join("feature.js"), "const feature = 1;\n").unwrap();
+    git(&origin, &["add", "."]);
+    git(&origin, &["commit", "-m", "initial"]);
+    git(&origin, &["branch", "-M", "main"]);
+    git(
+        temp.path(),
+        &["clone", origin.to_str().unwrap(), clone.to_str().unwrap()],
+    );
+    git(&clone, &["checkout", "--detach"]);
+    fs::write(clone.join("feature.js"), "const feature = 2;\n").unwrap();
+    git(&clone, &["commit", "-am", "feature work"]);
+    fs::write(origin.join("shared.js"), "const shared = 2;\n").unwrap();
+    git(&origin, &["commit", "-am", "unrelated work on main"]);
+    git(&clone, &["fetch", "origin"]);
+    temp
+}
+
+/// Runs `check --diff auto -v` in `repo` with only `var` set among the CI
+/// variables, returning the exit code, stderr, and files reviewed.
+fn check_in_ci(repo: &Path, var: &str, extra: &[&str]) -> (i32, String, Option<u64>) {
+    let mut cmd = reviewlens(repo);
+    for name in CI_BASE_VARS {
+        cmd.env_remove(name);
+    }
+    cmd.env(var, "main")
+        .args(["-v", "check", "--no-progress"])
+        .args(extra);
+    let (code, stderr, report) = run_with_json_report(&mut cmd, repo);
+    let reviewed = report.map(|report| report["stats"]["files_reviewed"].as_u64().unwrap());
+    (code, stderr, reviewed)
+}
+
+#[test]
+fn base_ref_comes_from_ci_variables() {
+    let temp = ci_checkout();
+    let clone = temp.path().join("clone");
+    for var in CI_BASE_VARS {
+        let (code, stderr, reviewed) = check_in_ci(&clone, var, &[]);
+        assert_eq!(code, 0, "{}", stderr);
+        assert!(
+            stderr.contains(&format!("Using base ref origin/main from ${}", var)),
+            "{}",
+            stderr
+        );
+        // Only the feature change, not main's later commit.
+        assert_eq!(reviewed, Some(1), "{}", stderr);
+    }
+}
+
+#[test]
+fn missing_base_branch_is_fetched_in_ci_mode() {
+    let temp = ci_checkout();
+    let clone = temp.path().join("clone");
+    git(&clone, &["update-ref", "-d", "refs/remotes/origin/main"]);
+
+    let (code, stderr, _) = check_in_ci(&clone, "GITHUB_BASE_REF", &[]);
+    assert_eq!(code, 2, "{}", stderr);
+    assert!(stderr.contains("git fetch origin main"), "{}", stderr);
+
+    let (code, stderr, reviewed) = check_in_ci(&clone, "GITHUB_BASE_REF", &["--ci"]);
+    assert_eq!(code, 0, "{}", stderr);
+    assert!(stderr.contains("Fetching origin/main"), "{}", stderr);
+    assert_eq!(reviewed, Some(1), "{}", stderr);
+}
@@ -62,15 +62,45 @@ fn check_ci_produces_json_logs() {
     assert!(output.status.success());
     assert!(output_path.exists());
 
-    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
     let mut count = 0;
-    for line in stdout.lines().filter(|l| l.trim_start().starts_with('{')) {
+    for line in stderr.lines() {
         let v: Value = serde_json::from_str(line).expect("log line is valid JSON");
         assert!(v.get("level").is_some());
         assert!(v.get("msg").is_some());
         assert!(v.get("module").is_some());
-        assert!(v.get("ts").is_some());
+        let ts = v["ts"].as_str().expect("ts is a string");
+        assert!(chrono::DateTime::parse_from_rfc3339(ts).is_ok());
         count += 1;
     }
     assert!(count > 0, "expected at least one JSON log line");
+
+    // The summary stays on stdout and is not mixed with log records.
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Reviewed 1 file"));
+    assert!(!stdout.contains("\"level\""));
+}
+
+#[test]
+fn log_format_json_applies_outside_ci() {
+    let mut cmd = Command::cargo_bin("reviewlens").unwrap();
+    let output = cmd
+        .args([
+            "-v",
+            "--log-format",
+            "json",
+            "print-config",
+            "--base-ref",
+            "HEAD",
+        ])
+        .output()
+        .expect("failed to execute command");
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.lines().count() > 0);
+    for line in stderr.lines() {
+        let v: Value = serde_json::from_str(line).expect("log line is valid JSON");
+        assert_eq!(v["level"], "INFO");
+    }
 }
@@ -92,7 +92,7 @@ fn ci_sets_generation_temperature_to_zero() {
 
     fs::write(
         repo.join("reviewlens.toml"),
-        "[generation]\ntemperature = 0.5\n",
+        "[generation]\ntemperature = 0.5\n\n[report]\ninclude-config = \"full\"\n",
     )
     .unwrap();
 
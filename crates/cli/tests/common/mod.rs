@@ -0,0 +1,91 @@
+//! Helpers shared by the CLI integration tests. Each test binary uses only
+//! some of them.
+#![allow(dead_code)]
+
+use assert_cmd::Command;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use tempfile::{tempdir, TempDir};
+
+/// Runs git in `repo` as a fixed test identity and returns its trimmed
+/// stdout. Panics if git fails.
+pub fn git(repo: &Path, args: &[&str]) -> String {
+    let output = StdCommand::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .expect("git failed");
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// Writes each `(path, content)` pair under `repo`, creating parent
+/// directories as needed.
+pub fn write_files(repo: &Path, files: &[(&str, &str)]) {
+    for (path, content) in files {
+        let path = repo.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
+
+/// Initialises a repository in `repo`, commits `initial` and then writes
+/// `changed` to the working tree without staging it.
+pub fn init_with_change(repo: &Path, initial: &[(&str, &str)], changed: &[(&str, &str)]) {
+    git(repo, &["init"]);
+    write_files(repo, initial);
+    git(repo, &["add", "."]);
+    git(repo, &["commit", "--allow-empty", "-m", "initial"]);
+    write_files(repo, changed);
+}
+
+/// A temporary repository set up by [`init_with_change`].
+pub fn repo_with_change(initial: &[(&str, &str)], changed: &[(&str, &str)]) -> TempDir {
+    let temp = tempdir().unwrap();
+    init_with_change(temp.path(), initial, changed);
+    temp
+}
+
+/// `reviewlens` run from `repo`, ignoring any user-global configuration.
+pub fn reviewlens(repo: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("reviewlens").unwrap();
+    cmd.env("REVIEWLENS_USER_CONFIG", repo.join("missing.toml"))
+        .current_dir(repo);
+    cmd
+}
+
+/// `reviewlens check` run from `repo` without a progress bar.
+pub fn check(repo: &Path) -> Command {
+    let mut cmd = reviewlens(repo);
+    cmd.args(["check", "--no-progress"]);
+    cmd
+}
+
+/// Runs `cmd` with a JSON report written to `report.json` in `repo` and
+/// returns the exit code, stderr, and the report if one was written.
+pub fn run_with_json_report(cmd: &mut Command, repo: &Path) -> (i32, String, Option<Value>) {
+    let report = repo.join("report.json");
+    let _ = fs::remove_file(&report);
+    let output = cmd
+        .args(["--format", "json", "--output"])
+        .arg(&report)
+        .output()
+        .unwrap();
+    let report = fs::read_to_string(report)
+        .ok()
+        .map(|r| serde_json::from_str(&r).unwrap());
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+        report,
+    )
+}
+
+/// Runs `check` in `repo` with `args`; see [`run_with_json_report`].
+pub fn check_json(repo: &Path, args: &[&str]) -> (i32, String, Option<Value>) {
+    run_with_json_report(check(repo).args(args), repo)
+}
@@ -0,0 +1,127 @@
+use engine::config::{Config, Severity};
+use engine::report::{JsonGenerator, ReportGenerator, ReviewReport, RuntimeMetadata, TimingInfo};
+use engine::scanner::Issue;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+mod common;
+use common::reviewlens;
+
+fn issue(title: &str, severity: Severity, fingerprint: &str) -> Issue {
+    Issue {
+        title: title.into(),
+        description: String::new(),
+        file_path: "a.js".into(),
+        line_number: 1,
+        severity,
+        rule_id: "secrets".into(),
+        fingerprint: fingerprint.into(),
+        ..Default::default()
+    }
+}
+
+/// Writes a JSON report with `issues` to `path`.
+fn write_report(path: &Path, issues: Vec<Issue>) {
+    let report = ReviewReport {
+        summary: String::new(),
+        issues,
+        code_quality: Vec::new(),
+        hotspots: Vec::new(),
+        mermaid_diagram: None,
+        config: Config::default(),
+        metadata: RuntimeMetadata {
+            ruleset_version: "1.0.0".into(),
+            model: None,
+            driver: "null".into(),
+            timings: TimingInfo {
+                total_ms: 0,
+                ..Default::default()
+            },
+            index_warm: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    fs::write(path, JsonGenerator.generate(&report).unwrap()).unwrap();
+}
+
+fn compare(dir: &Path, extra: &[&str]) -> std::process::Output {
+    reviewlens(dir)
+        .args([
+            "compare",
+            "--before",
+            "before.json",
+            "--after",
+            "after.json",
+        ])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn lists_buckets_and_fails_on_new_findings() {
+    let dir = tempdir().unwrap();
+    write_report(
+        &dir.path().join("before.json"),
+        vec![
+            issue("Fixed", Severity::High, "fixed"),
+            issue("Kept", Severity::High, "kept"),
+        ],
+    );
+    write_report(
+        &dir.path().join("after.json"),
+        vec![
+            issue("Kept", Severity::High, "kept"),
+            issue("Added", Severity::High, "added"),
+        ],
+    );
+
+    let output = compare(dir.path(), &["--output", "comparison.md"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("1 new, 1 resolved, 1 persisting"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("## New (1)\n\n- `high` Added"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("## Resolved (1)\n\n- `high` Fixed"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("## Persisting (1)\n\n- `high` Kept"),
+        "{}",
+        stdout
+    );
+    let written = fs::read_to_string(dir.path().join("comparison.md")).unwrap();
+    assert_eq!(written, stdout);
+}
+
+#[test]
+fn new_findings_below_threshold_pass() {
+    let dir = tempdir().unwrap();
+    write_report(&dir.path().join("before.json"), Vec::new());
+    write_report(
+        &dir.path().join("after.json"),
+        vec![issue("Minor", Severity::Low, "minor")],
+    );
+    let output = compare(dir.path(), &["--fail-on", "high"]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn unreadable_report_is_an_error() {
+    let dir = tempdir().unwrap();
+    write_report(&dir.path().join("before.json"), Vec::new());
+    fs::write(dir.path().join("after.json"), "not json").unwrap();
+    let output = compare(dir.path(), &[]);
+    assert_eq!(output.status.code(), Some(3));
+}
@@ -0,0 +1,102 @@
+use assert_cmd::Command;
+use std::fs;
+use tempfile::tempdir;
+
+const OLD: &str = "# Built by CI\nindex_path = \"ci/index.json.zst\"\n\n[rules.sql_injection_go]\nenabled = false\nseverity = \"low\"\n";
+
+#[test]
+fn migrate_write_rewrites_the_file_in_place() {
+    let temp = tempdir().unwrap();
+    let config_path = temp.path().join("reviewlens.toml");
+    fs::write(&config_path, OLD).unwrap();
+
+    let output = Command::cargo_bin("reviewlens")
+        .unwrap()
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "config",
+            "migrate",
+            "--write",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("index_path is deprecated, use [index] path"));
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        "[rules.sql-injection-go]\nenabled = false\nseverity = \"low\"\n\n[index]\n# Built by CI\npath = \"ci/index.json.zst\"\n"
+    );
+
+    // A migrated file has nothing left to migrate and loads without warnings.
+    let output = Command::cargo_bin("reviewlens")
+        .unwrap()
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "config",
+            "migrate",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("nothing to migrate"));
+    assert!(!stderr.contains("deprecated"));
+}
+
+#[test]
+fn migrate_without_write_prints_and_leaves_the_file() {
+    let temp = tempdir().unwrap();
+    let config_path = temp.path().join("reviewlens.toml");
+    fs::write(&config_path, OLD).unwrap();
+
+    let output = Command::cargo_bin("reviewlens")
+        .unwrap()
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "config",
+            "migrate",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("[rules.sql-injection-go]"));
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), OLD);
+}
+
+#[test]
+fn deprecated_keys_warn_once_and_are_listed_by_print_config() {
+    let temp = tempdir().unwrap();
+    let config_path = temp.path().join("reviewlens.toml");
+    fs::write(&config_path, OLD).unwrap();
+
+    let output = Command::cargo_bin("reviewlens")
+        .unwrap()
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "print-config",
+            "--base-ref",
+            "HEAD",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        stderr
+            .matches("index_path is deprecated, use [index] path")
+            .count(),
+        1,
+        "{}",
+        stderr
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Deprecated keys:"));
+    assert!(stdout.contains("  rules.sql_injection_go is deprecated, use [rules.sql-injection-go]"));
+    assert!(stdout.contains("ci/index.json.zst"));
+}
@@ -0,0 +1,127 @@
+use assert_cmd::Command;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn config_validate_lists_all_problems_and_exits_two() {
+    let temp = tempdir().unwrap();
+    let config_path = temp.path().join("reviewlens.toml");
+    fs::write(
+        &config_path,
+        "[llm]\nprovider = \"openai\"\napi-key = \"dummy\"\n\n[rules.secret]\nenabled = false\n\n[privacy.redaction]\nenabled = true\npatterns = [\"(unclosed\"]\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("reviewlens").unwrap();
+    let output = cmd
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "config",
+            "validate",
+        ])
+        .output()
+        .expect("failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("3 configuration problems"));
+    assert!(stderr.contains("rules.secret: unknown key (did you mean `secrets`?)"));
+    assert!(stderr.contains("privacy.redaction.patterns[0]"));
+    assert!(stderr.contains("llm.model"));
+}
+
+#[test]
+fn config_validate_accepts_valid_file() {
+    let temp = tempdir().unwrap();
+    let config_path = temp.path().join("reviewlens.toml");
+    fs::write(
+        &config_path,
+        "fail-on = \"never\"\n[rules.secrets]\nenabled = false\nseverity = \"low\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("reviewlens").unwrap();
+    cmd.args([
+        "--config",
+        config_path.to_str().unwrap(),
+        "config",
+        "validate",
+    ]);
+    cmd.assert().success();
+}
+
+#[test]
+fn config_validate_warns_about_dead_path_patterns() {
+    let temp = tempdir().unwrap();
+    let config_path = temp.path().join("reviewlens.toml");
+    fs::write(
+        &config_path,
+        "[paths]\nallow = [\"src/**/*.rs\"]\ndeny = [\"src/**\"]\n",
+    )
+    .unwrap();
+    fs::create_dir(temp.path().join("src")).unwrap();
+    fs::write(temp.path().join("src/lib.rs"), "").unwrap();
+
+    let output = Command::cargo_bin("reviewlens")
+        .unwrap()
+        .env("REVIEWLENS_USER_CONFIG", temp.path().join("missing.toml"))
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "config",
+            "validate",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("warning: paths.allow[0]: `src/**/*.rs` is unreachable"),
+        "{}",
+        stderr
+    );
+    assert!(!stderr.contains("matches no files"), "{}", stderr);
+}
+
+#[test]
+fn strict_config_rejects_unknown_keys_before_running() {
+    let temp = tempdir().unwrap();
+    let config_path = temp.path().join("reviewlens.toml");
+    fs::write(&config_path, "fial-on = \"low\"\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("reviewlens").unwrap();
+    cmd.args([
+        "--strict-config",
+        "--config",
+        config_path.to_str().unwrap(),
+        "print-config",
+        "--base-ref",
+        "HEAD",
+    ]);
+    cmd.assert().code(2);
+}
+
+#[test]
+fn config_validate_reports_the_error_for_unparsable_files() {
+    let temp = tempdir().unwrap();
+    let config_path = temp.path().join("reviewlens.toml");
+    fs::write(&config_path, "[llm\nprovider = \"openai\"\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("reviewlens").unwrap();
+    let output = cmd
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "config",
+            "validate",
+        ])
+        .output()
+        .expect("failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("0 configuration problems"), "{}", stderr);
+    assert!(stderr.contains("reviewlens.toml"), "{}", stderr);
+}
@@ -0,0 +1,36 @@
+mod common;
+use common::{check_json, repo_with_change};
+
+/// A repository whose only change is a secret pasted into `README.md`.
+fn repo() -> tempfile::TempDir {
+    repo_with_change(
+        &[("README.md", "# Project\n")],
+        &[(
+            "README.md",
+            "# Project\napi_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"\n",
+        )],
+    )
+}
+
+#[test]
+fn docs_only_change_passes_without_analysis() {
+    let temp = repo();
+    let (code, stderr, report) = check_json(temp.path(), &["--diff", "HEAD", "--fail-on", "high"]);
+    assert_eq!(code, 0, "{}", stderr);
+    let report = report.unwrap();
+    assert_eq!(report["metadata"]["docs_only"], true);
+    assert_eq!(report["issues"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn force_full_reviews_docs_only_change() {
+    let temp = repo();
+    let (code, stderr, report) = check_json(
+        temp.path(),
+        &["--diff", "HEAD", "--fail-on", "high", "--force-full"],
+    );
+    assert_eq!(code, 1, "{}", stderr);
+    let report = report.unwrap();
+    assert_eq!(report["metadata"]["docs_only"], false);
+    assert_eq!(report["issues"].as_array().unwrap().len(), 1);
+}
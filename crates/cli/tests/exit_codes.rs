@@ -0,0 +1,91 @@
+use std::fs;
+use std::net::TcpListener;
+use tempfile::{tempdir, TempDir};
+
+mod common;
+use common::{check, check_json, git, reviewlens, run_with_json_report};
+
+/// A repository with a hard-coded secret staged for commit.
+fn repo_with_staged_secret() -> TempDir {
+    let temp = tempdir().unwrap();
+    let repo = temp.path();
+    git(repo, &["init"]);
+    fs::write(
+        repo.join("a.js"),
+        "const API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n",
+    )
+    .unwrap();
+    git(repo, &["add", "a.js"]);
+    temp
+}
+
+#[test]
+fn success_is_zero() {
+    let temp = repo_with_staged_secret();
+    let (code, stderr, _) = check_json(temp.path(), &["--staged", "--fail-on", "never"]);
+    assert_eq!(code, 0, "{}", stderr);
+}
+
+#[test]
+fn findings_above_threshold_is_one() {
+    let temp = repo_with_staged_secret();
+    let (code, stderr, _) = check_json(temp.path(), &["--staged", "--fail-on", "high"]);
+    assert_eq!(code, 1, "{}", stderr);
+}
+
+#[test]
+fn config_error_is_two() {
+    let temp = repo_with_staged_secret();
+    let (code, stderr, _) = run_with_json_report(
+        reviewlens(temp.path()).args(["--rule", "secrets=urgent", "check", "--staged"]),
+        temp.path(),
+    );
+    assert!(stderr.contains("secrets=urgent"), "{}", stderr);
+    assert_eq!(code, 2, "{}", stderr);
+}
+
+#[test]
+fn runtime_error_is_three() {
+    let temp = tempdir().unwrap();
+    let output = reviewlens(temp.path())
+        .args([
+            "compare",
+            "--before",
+            "missing.json",
+            "--after",
+            "missing.json",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn required_llm_failure_is_four() {
+    let temp = repo_with_staged_secret();
+    // Bind and drop a listener to find a port nothing listens on.
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let base_url = format!("http://127.0.0.1:{}", port);
+    let (code, stderr, _) = run_with_json_report(
+        check(temp.path())
+            .env("REVIEWLENS_LLM_PROVIDER", "openai")
+            .env("REVIEWLENS_LLM_MODEL", "gpt-test")
+            .env("REVIEWLENS_LLM_API_KEY", "dummy")
+            .env("REVIEWLENS_LLM_BASE_URL", &base_url)
+            .args(["--staged", "--fail-on", "never", "--require-llm"]),
+        temp.path(),
+    );
+    assert_eq!(code, 4, "{}", stderr);
+}
+
+#[test]
+fn git_failure_is_five() {
+    let temp = tempdir().unwrap();
+    let (code, stderr, _) = check_json(temp.path(), &["--staged", "--fail-on", "never"]);
+    assert_eq!(code, 5, "{}", stderr);
+    assert!(stderr.contains("git diff --cached failed"), "{}", stderr);
+}
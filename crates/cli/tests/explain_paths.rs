@@ -0,0 +1,57 @@
+use std::fs;
+use std::process::Command as StdCommand;
+use tempfile::tempdir;
+
+mod common;
+use common::reviewlens;
+
+#[test]
+fn explain_paths_prints_each_decision_without_reviewing() {
+    let temp = tempdir().unwrap();
+    let repo = temp.path();
+    fs::create_dir_all(repo.join("vendor")).unwrap();
+    fs::create_dir_all(repo.join("src")).unwrap();
+    fs::write(repo.join("src/app.js"), "let a = 1;\n").unwrap();
+    fs::write(repo.join("vendor/lib.js"), "let b = 2;\n").unwrap();
+    fs::write(repo.join("notes.txt"), "hello\n").unwrap();
+    fs::write(
+        repo.join("reviewlens.toml"),
+        "[paths]\nallow = [\"**/*.js\", \"reviewlens.toml\"]\ndeny = [\"vendor/**\"]\n",
+    )
+    .unwrap();
+    for args in [&["init"][..], &["add", "."][..]] {
+        let output = StdCommand::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+
+    let output = reviewlens(repo)
+        .args(["check", "--staged", "--explain-paths", "--no-progress"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows: Vec<Vec<&str>> = stdout
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    assert_eq!(
+        rows,
+        [
+            ["not-allowed", "-", "notes.txt"],
+            ["kept", "reviewlens.toml", "reviewlens.toml"],
+            ["kept", "**/*.js", "src/app.js"],
+            ["denied", "vendor/**", "vendor/lib.js"],
+        ],
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("2 of 4 files kept"), "{}", stdout);
+    assert!(!repo.join("review_report.md").exists());
+}
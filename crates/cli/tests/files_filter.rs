@@ -0,0 +1,74 @@
+use serde_json::Value;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod common;
+use common::{check_json, git, repo_with_change};
+
+const SECRET: &str = "const API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n";
+
+/// A repository with the same secret staged in three files.
+fn repo() -> TempDir {
+    let temp = repo_with_change(
+        &[],
+        &[
+            ("src/auth.js", SECRET),
+            ("src/db.js", SECRET),
+            ("web.js", SECRET),
+        ],
+    );
+    git(temp.path(), &["add", "."]);
+    temp
+}
+
+/// Runs a staged review and returns the exit code, stderr and JSON report.
+fn review(repo: &Path, extra: &[&str]) -> (i32, String, Option<Value>) {
+    let args = [&["--staged", "--fail-on", "never"], extra].concat();
+    check_json(repo, &args)
+}
+
+fn flagged_paths(report: &Value) -> Vec<&str> {
+    report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["file_path"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn files_option_limits_the_review() {
+    let temp = repo();
+    let (code, stderr, report) = review(temp.path(), &["--files", "src/auth.js"]);
+    assert_eq!(code, 0, "{}", stderr);
+    let report = report.unwrap();
+    assert_eq!(flagged_paths(&report), ["src/auth.js"]);
+    assert_eq!(report["stats"]["files_reviewed"], 1);
+    assert_eq!(report["stats"]["files_excluded"], 2);
+}
+
+#[test]
+fn positional_files_and_globs_are_accepted() {
+    let temp = repo();
+    let (code, stderr, report) = review(temp.path(), &["./web.js", "src/d*.js"]);
+    assert_eq!(code, 0, "{}", stderr);
+    let report = report.unwrap();
+    let mut paths = flagged_paths(&report);
+    paths.sort();
+    assert_eq!(paths, ["src/db.js", "web.js"]);
+    assert_eq!(report["stats"]["files_excluded"], 1);
+}
+
+#[test]
+fn files_missing_from_the_diff_are_an_error() {
+    let temp = repo();
+    let (code, stderr, report) = review(temp.path(), &["--files", "src/main.rs"]);
+    assert_eq!(code, 2, "{}", stderr);
+    assert!(report.is_none());
+    assert!(
+        stderr.contains("none of the requested files (src/main.rs) are in the diff"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("src/auth.js"), "{}", stderr);
+}
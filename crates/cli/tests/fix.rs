@@ -0,0 +1,113 @@
+use engine::config::{Config, Severity};
+use engine::report::{JsonGenerator, ReportGenerator, ReviewReport, RuntimeMetadata, TimingInfo};
+use engine::scanner::{Fix, Issue};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+mod common;
+use common::reviewlens;
+
+fn issue(rule: &str, line: usize, find: &str, replace: &str) -> Issue {
+    let fix = Fix {
+        find: find.into(),
+        replace: replace.into(),
+    };
+    Issue {
+        title: "Fixable".into(),
+        description: String::new(),
+        file_path: "net.go".into(),
+        line_number: line,
+        severity: Severity::Medium,
+        diff: Some(fix.to_diff()),
+        rule_id: rule.into(),
+        fix: Some(fix),
+        ..Default::default()
+    }
+}
+
+/// Writes a JSON report with `issues` to `path`.
+fn write_report(path: &Path, issues: Vec<Issue>) {
+    let report = ReviewReport {
+        summary: String::new(),
+        issues,
+        code_quality: Vec::new(),
+        hotspots: Vec::new(),
+        mermaid_diagram: None,
+        config: Config::default(),
+        metadata: RuntimeMetadata {
+            ruleset_version: "1.0.0".into(),
+            model: None,
+            driver: "null".into(),
+            timings: TimingInfo {
+                total_ms: 0,
+                ..Default::default()
+            },
+            index_warm: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    fs::write(path, JsonGenerator.generate(&report).unwrap()).unwrap();
+}
+
+fn fix(dir: &Path, extra: &[&str]) -> String {
+    let output = reviewlens(dir)
+        .args(["fix", "--report", "report.json"])
+        .args(extra)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn applies_fixes_and_summarises_skipped_ones() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("net.go"), "a := 1\nb := 2\n").unwrap();
+    write_report(
+        &dir.path().join("report.json"),
+        vec![
+            issue("one", 1, "a := 1", "a := 10"),
+            issue("two", 2, "b := 3", "b := 30"),
+        ],
+    );
+
+    let stdout = fix(dir.path(), &[]);
+    assert!(stdout.contains("applied  net.go:1  one"), "{}", stdout);
+    assert!(
+        stdout.contains("skipped  net.go:2  two  (line no longer matches)"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("1 fix applied, 1 skipped"), "{}", stdout);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("net.go")).unwrap(),
+        "a := 10\nb := 2\n"
+    );
+}
+
+#[test]
+fn rule_and_dry_run_limit_what_is_written() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("net.go"), "a := 1\nb := 2\n").unwrap();
+    write_report(
+        &dir.path().join("report.json"),
+        vec![
+            issue("one", 1, "a := 1", "a := 10"),
+            issue("two", 2, "b := 2", "b := 20"),
+        ],
+    );
+
+    let stdout = fix(dir.path(), &["--rule", "two", "--dry-run"]);
+    assert!(!stdout.contains("one"), "{}", stdout);
+    assert!(
+        stdout.contains("1 fix would be applied, 0 skipped"),
+        "{}",
+        stdout
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("net.go")).unwrap(),
+        "a := 1\nb := 2\n"
+    );
+}
@@ -0,0 +1,85 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::{check, git, repo_with_change, run_with_json_report};
+
+/// A repository on branch `feature` that records run history.
+fn repo() -> tempfile::TempDir {
+    let temp = repo_with_change(
+        &[
+            (
+                "reviewlens.toml",
+                "[report]\nhistory-path = \".reviewlens/history.jsonl\"\n",
+            ),
+            ("a.js", "const x = 1;\n"),
+        ],
+        &[],
+    );
+    git(temp.path(), &["checkout", "-b", "feature"]);
+    temp
+}
+
+/// Changes `a.js` to `content`, runs `check` and returns the JSON report.
+fn review(repo: &Path, content: &str, extra: &[&str]) -> Value {
+    fs::write(repo.join("a.js"), content).unwrap();
+    let (code, stderr, report) = run_with_json_report(
+        check(repo)
+            .env_remove("GITHUB_HEAD_REF")
+            .env_remove("CI_MERGE_REQUEST_SOURCE_BRANCH_NAME")
+            .env_remove("CI_COMMIT_REF_NAME")
+            .env_remove("BRANCH_NAME")
+            .args(["--diff", "HEAD", "--fail-on", "never"])
+            .args(extra),
+        repo,
+    );
+    assert_eq!(code, 0, "{}", stderr);
+    report.unwrap()
+}
+
+fn history_lines(repo: &Path) -> Vec<Value> {
+    fs::read_to_string(repo.join(".reviewlens/history.jsonl"))
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn consecutive_runs_report_changes_since_the_last_run() {
+    let temp = repo();
+    let repo = temp.path();
+
+    let report = review(repo, "const api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n", &[]);
+    assert!(report.get("since_last_run").is_none());
+    let history = history_lines(repo);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0]["branch"], "feature");
+    assert_eq!(history[0]["findings"].as_array().unwrap().len(), 1);
+    assert_eq!(history[0]["findings"][0]["severity"], "high");
+
+    let report = review(
+        repo,
+        "const x = 1;\nconst token = \"ZYXWVUTSRQPONMLKJIHGFEDCBA\";\n",
+        &[],
+    );
+    let since = &report["since_last_run"];
+    assert_eq!(since["branch"], "feature");
+    assert_eq!(since["new"], 1);
+    assert_eq!(since["resolved"], 1);
+    assert_eq!(since["persisting"], 0);
+    assert_eq!(history_lines(repo).len(), 2);
+}
+
+#[test]
+fn no_history_neither_reads_nor_records() {
+    let temp = repo();
+    let repo = temp.path();
+    let secret = "const api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n";
+    review(repo, secret, &[]);
+
+    let report = review(repo, secret, &["--no-history"]);
+    assert!(report.get("since_last_run").is_none());
+    assert_eq!(history_lines(repo).len(), 1);
+}
@@ -0,0 +1,143 @@
+use engine::config::DEFAULT_INDEX_PATH;
+use engine::rag::{Document, InMemoryVectorStore};
+use std::fs;
+use std::time::{Duration, UNIX_EPOCH};
+use tempfile::tempdir;
+
+mod common;
+use common::reviewlens;
+
+#[test]
+fn index_honors_deny_flag_and_writes_to_configured_path() {
+    let temp = tempdir().unwrap();
+    let repo = temp.path();
+    fs::create_dir_all(repo.join("src")).unwrap();
+    fs::create_dir_all(repo.join("vendor/dep")).unwrap();
+    fs::write(repo.join("src/main.rs"), "fn main() {}\n").unwrap();
+    fs::write(repo.join("vendor/dep/lib.rs"), "pub fn dep() {}\n").unwrap();
+
+    let run = || {
+        reviewlens(repo)
+            .args([
+                "index",
+                "--path",
+                repo.to_str().unwrap(),
+                "--deny",
+                "vendor/**",
+            ])
+            .assert()
+            .success();
+    };
+    run();
+    // Re-indexing must not trip over the index stored inside the repository.
+    run();
+
+    let index_path = repo.join(DEFAULT_INDEX_PATH);
+    let store = InMemoryVectorStore::load_from_disk(&index_path).unwrap();
+    let files: Vec<&str> = store
+        .documents()
+        .iter()
+        .map(|d| d.filename.as_str())
+        .collect();
+    assert!(files.contains(&"src/main.rs"), "{:?}", files);
+    assert!(
+        files.iter().all(|f| !f.starts_with("vendor/")),
+        "{:?}",
+        files
+    );
+    assert!(
+        files.iter().all(|f| !f.starts_with(".reviewlens")),
+        "{:?}",
+        files
+    );
+}
+
+fn synthetic_index(dir: &std::path::Path) -> std::path::PathBuf {
+    let mut store = InMemoryVectorStore::default();
+    for (name, content) in [("src/lib.rs", "fn lib() {}"), ("docs/guide.md", "# Guide")] {
+        store.push_document(Document {
+            filename: name.into(),
+            content: content.into(),
+            embedding: vec![0.5; 8],
+            language: None,
+            function_signatures: vec![],
+            log_patterns: vec![],
+            error_snippets: vec![],
+            modified: 0,
+        });
+    }
+    store.set_build_info(
+        UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        Some("abc123".into()),
+    );
+    let path = dir.join("index.json.zst");
+    store.save_to_disk(&path).unwrap();
+    path
+}
+
+#[test]
+fn index_stats_prints_summary() {
+    let temp = tempdir().unwrap();
+    let index = synthetic_index(temp.path());
+
+    let output = reviewlens(temp.path())
+        .args(["index", "stats", "--index", index.to_str().unwrap()])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let text = String::from_utf8(output).unwrap();
+    assert!(text.contains("Documents: 2"), "{}", text);
+    assert!(text.contains("Content: 18 bytes"), "{}", text);
+    assert!(text.contains("Embedder: ngram (8 dimensions)"), "{}", text);
+    assert!(
+        text.contains("Built: 2023-11-14T22:13:20+00:00"),
+        "{}",
+        text
+    );
+    assert!(text.contains("Commit: abc123"), "{}", text);
+    assert!(text.contains("src/lib.rs"), "{}", text);
+}
+
+#[test]
+fn index_stats_json_output() {
+    let temp = tempdir().unwrap();
+    let index = synthetic_index(temp.path());
+
+    let output = reviewlens(temp.path())
+        .args([
+            "index",
+            "stats",
+            "--json",
+            "--index",
+            index.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stats: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(stats["documents"], 2);
+    assert_eq!(stats["dimension"], 8);
+    assert_eq!(stats["empty_embeddings"], 0);
+    assert_eq!(stats["head_commit"], "abc123");
+    assert_eq!(stats["extensions"]["rs"], 1);
+    assert_eq!(stats["largest"][0]["filename"], "src/lib.rs");
+}
+
+#[cfg(not(feature = "qdrant"))]
+#[test]
+fn qdrant_store_requires_feature() {
+    let temp = tempdir().unwrap();
+    let output = reviewlens(temp.path())
+        .args(["index", "--store", "qdrant"])
+        .assert()
+        .failure()
+        .get_output()
+        .stderr
+        .clone();
+    let stderr = String::from_utf8(output).unwrap();
+    assert!(stderr.contains("`qdrant` feature"), "{}", stderr);
+}
@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::{check, repo_with_change};
+
+/// A repository limited to one file per review, whose working tree changes
+/// a source file and a text file holding a secret.
+fn repo() -> tempfile::TempDir {
+    repo_with_change(
+        &[("a.js", "const x = 1;\n"), ("notes.txt", "nothing here\n")],
+        &[
+            ("a.js", "const x = 2;\n"),
+            ("notes.txt", "api_key = \"ABCDEFGHIJKLMNOP\"\n"),
+            ("reviewlens.toml", "[limits]\nmax-files = 1\n"),
+        ],
+    )
+}
+
+/// Runs `check --diff HEAD` and returns the exit code and Markdown report.
+fn review(repo: &Path, extra: &[&str]) -> (i32, String) {
+    let report = repo.join("report.md");
+    let output = check(repo)
+        .args(["--diff", "HEAD", "--output", report.to_str().unwrap()])
+        .args(extra)
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap(),
+        fs::read_to_string(report).unwrap(),
+    )
+}
+
+#[test]
+fn skipped_files_are_noted_without_affecting_exit_code() {
+    let temp = repo();
+    let (code, report) = review(temp.path(), &[]);
+    assert_eq!(code, 0, "{}", report);
+    assert!(
+        report.contains("1 file skipped due to size limits (notes.txt)"),
+        "{}",
+        report
+    );
+
+    let (code, report) = review(temp.path(), &["--no-limits"]);
+    assert_eq!(code, 1, "{}", report);
+    assert!(!report.contains("size limits"), "{}", report);
+}
@@ -0,0 +1,63 @@
+use serde_json::Value;
+use std::fs;
+use tempfile::tempdir;
+
+mod common;
+use common::{git, reviewlens};
+
+/// Reviews a repository with one committed secret and returns its issues.
+fn review(origin: Option<&str>) -> (String, Vec<Value>) {
+    let temp = tempdir().unwrap();
+    let repo = temp.path();
+    git(repo, &["init"]);
+    fs::write(
+        repo.join("a.js"),
+        "const API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n",
+    )
+    .unwrap();
+    git(repo, &["add", "a.js"]);
+    git(repo, &["commit", "-m", "add a.js"]);
+    if let Some(url) = origin {
+        git(repo, &["remote", "add", "origin", url]);
+    }
+    let head = git(repo, &["rev-parse", "HEAD"]);
+    let empty_tree = git(repo, &["hash-object", "-t", "tree", "--stdin"]);
+    let report = repo.join("report.json");
+
+    reviewlens(repo)
+        .args([
+            "check",
+            "--path",
+            repo.to_str().unwrap(),
+            "--diff",
+            &empty_tree,
+            "--two-dot",
+            "--format",
+            "json",
+            "--output",
+            report.to_str().unwrap(),
+            "--fail-on",
+            "never",
+            "--no-progress",
+        ])
+        .assert()
+        .success();
+    let report: Value = serde_json::from_str(&fs::read_to_string(report).unwrap()).unwrap();
+    (head, report["issues"].as_array().unwrap().clone())
+}
+
+#[test]
+fn links_issues_to_github_origin_at_head() {
+    let (head, issues) = review(Some("git@github.com:org/repo.git"));
+    assert_eq!(
+        issues[0]["url"],
+        format!("https://github.com/org/repo/blob/{}/a.js#L1", head)
+    );
+}
+
+#[test]
+fn omits_links_without_a_remote() {
+    let (_, issues) = review(None);
+    assert!(!issues.is_empty());
+    assert!(issues[0].get("url").is_none(), "{:?}", issues[0]);
+}
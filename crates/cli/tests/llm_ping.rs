@@ -0,0 +1,116 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use tempfile::tempdir;
+
+mod common;
+use common::reviewlens;
+
+/// Answers one chat completion request with a canned reply using 7 tokens.
+fn openai_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}/v1/chat/completions",
+        listener.local_addr().unwrap()
+    );
+    let body = r#"{"choices":[{"message":{"role":"assistant","content":"OK"}}],"usage":{"total_tokens":7}}"#;
+    let response = format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut request = vec![0; length];
+        reader.read_exact(&mut request).unwrap();
+        stream.write_all(response.as_bytes()).unwrap();
+    });
+    url
+}
+
+/// Runs `llm ping` with `env` and returns the exit code and stdout.
+fn ping(env: &[(&str, &str)], extra: &[&str]) -> (i32, String) {
+    let temp = tempdir().unwrap();
+    let output = reviewlens(temp.path())
+        .env_remove("OPENAI_API_KEY")
+        .env_remove("ANTHROPIC_API_KEY")
+        .env_remove("DEEPSEEK_API_KEY")
+        .envs(env.iter().copied())
+        .args(["llm", "ping"])
+        .args(extra)
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn null_provider_reports_offline_mode() {
+    let (code, stdout) = ping(&[], &["--all"]);
+    assert_eq!(code, 0, "{}", stdout);
+    assert!(stdout.contains("null: offline mode"), "{}", stdout);
+    assert!(stdout.contains("openai: skipped, OPENAI_API_KEY is not set"));
+}
+
+#[test]
+fn openai_ping_prints_model_latency_and_usage() {
+    let url = format!("{}?token=s3cret", openai_server());
+    let (code, stdout) = ping(
+        &[
+            ("REVIEWLENS_LLM_PROVIDER", "openai"),
+            ("REVIEWLENS_LLM_MODEL", "gpt-test"),
+            ("REVIEWLENS_LLM_API_KEY", "dummy"),
+            ("REVIEWLENS_LLM_BASE_URL", &url),
+            ("REVIEWLENS_PRIVACY_REDACTION_ENABLED", "true"),
+            ("REVIEWLENS_PRIVACY_REDACTION_PATTERNS", r"token=(\w+)"),
+        ],
+        &[],
+    );
+    assert_eq!(code, 0, "{}", stdout);
+    assert!(
+        stdout.starts_with("openai (gpt-test at http://127.0.0.1:"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("?token=[REDACTED]): OK in "), "{}", stdout);
+    assert!(stdout.trim_end().ends_with(" ms, 7 tokens"), "{}", stdout);
+    assert!(!stdout.contains("s3cret"));
+}
+
+#[test]
+fn unreachable_provider_exits_four() {
+    // Bind and drop a listener to find a port nothing listens on.
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let url = format!("http://127.0.0.1:{}", port);
+    let (code, stdout) = ping(
+        &[
+            ("REVIEWLENS_LLM_PROVIDER", "openai"),
+            ("REVIEWLENS_LLM_MODEL", "gpt-test"),
+            ("REVIEWLENS_LLM_API_KEY", "dummy"),
+            ("REVIEWLENS_LLM_BASE_URL", &url),
+        ],
+        &[],
+    );
+    assert_eq!(code, 4, "{}", stdout);
+    assert!(stdout.contains("openai (gpt-test at "), "{}", stdout);
+    assert!(stdout.contains(": failed: "), "{}", stdout);
+}
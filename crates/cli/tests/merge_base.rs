@@ -0,0 +1,67 @@
+use serde_json::Value;
+use std::fs;
+use tempfile::tempdir;
+
+mod common;
+use common::{check_json, git};
+
+/// A repository on branch `feature` that changed `feature.js`, while `main`
+/// changed `shared.js` after the branch point.
+fn diverged_repo() -> tempfile::TempDir {
+    let temp = tempdir().unwrap();
+    let repo = temp.path();
+    git(repo, &["init"]);
+    fs::write(repo.join("shared.js"), "const shared = 1;\n").unwrap();
+    fs::write(repo.join("feature.js"), "const feature = 1;\n").unwrap();
+    git(repo, &["add", "."]);
+    git(repo, &["commit", "-m", "initial"]);
+    git(repo, &["branch", "-M", "main"]);
+    git(repo, &["checkout", "-b", "feature"]);
+    fs::write(repo.join("feature.js"), "const feature = 2;\n").unwrap();
+    git(repo, &["commit", "-am", "feature work"]);
+    git(repo, &["checkout", "main"]);
+    fs::write(repo.join("shared.js"), "const shared = 2;\n").unwrap();
+    git(repo, &["commit", "-am", "unrelated work on main"]);
+    git(repo, &["checkout", "feature"]);
+    temp
+}
+
+fn files_reviewed(report: &Value) -> u64 {
+    report["stats"]["files_reviewed"].as_u64().unwrap()
+}
+
+#[test]
+fn diffs_against_merge_base_by_default() {
+    let temp = diverged_repo();
+    let (code, stderr, report) = check_json(temp.path(), &["--diff", "main"]);
+    assert_eq!(code, 0, "{}", stderr);
+    assert_eq!(files_reviewed(&report.unwrap()), 1);
+
+    let (code, stderr, report) = check_json(temp.path(), &["--diff", "main", "--two-dot"]);
+    assert_eq!(code, 0, "{}", stderr);
+    assert_eq!(files_reviewed(&report.unwrap()), 2);
+}
+
+#[test]
+fn accepts_sha_on_detached_head() {
+    let temp = diverged_repo();
+    let main = git(temp.path(), &["rev-parse", "main"]);
+    git(temp.path(), &["checkout", "--detach", "feature"]);
+    let (code, stderr, report) = check_json(temp.path(), &["--diff", &main]);
+    assert_eq!(code, 0, "{}", stderr);
+    assert_eq!(files_reviewed(&report.unwrap()), 1);
+}
+
+#[test]
+fn missing_base_ref_suggests_fetch() {
+    let temp = diverged_repo();
+    let (code, stderr, report) = check_json(temp.path(), &["--diff", "origin/release"]);
+    assert_eq!(code, 2);
+    assert!(report.is_none());
+    assert!(
+        stderr.contains("base ref `origin/release` does not exist locally")
+            && stderr.contains("git fetch origin release"),
+        "{}",
+        stderr
+    );
+}
@@ -3,6 +3,9 @@ use std::fs;
 use std::process::Command as StdCommand;
 use tempfile::tempdir;
 
+mod common;
+use common::git;
+
 #[test]
 fn check_defaults_to_only_changed() {
     let temp = tempdir().unwrap();
@@ -60,3 +63,57 @@ fn check_defaults_to_only_changed() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("Reviewed 1 file"));
 }
+
+/// Commits `files` to a new repository and returns it.
+fn committed_repo(files: &[(&str, &str)]) -> tempfile::TempDir {
+    let temp = tempdir().unwrap();
+    let repo = temp.path();
+    git(repo, &["init"]);
+    for (path, content) in files {
+        let path = repo.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    git(repo, &["add", "."]);
+    git(repo, &["commit", "-m", "init"]);
+    temp
+}
+
+fn check_all_files(path: &str) -> String {
+    let output = Command::cargo_bin("reviewlens")
+        .unwrap()
+        .env("REVIEWLENS_USER_CONFIG", "missing.toml")
+        .args([
+            "check",
+            "--path",
+            path,
+            "--no-only-changed",
+            "--fail-on",
+            "never",
+            "--no-progress",
+        ])
+        .output()
+        .expect("failed to execute command");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn no_only_changed_reviews_every_tracked_file() {
+    let temp = committed_repo(&[("a.txt", "hello\n"), ("src/b.txt", "world\n")]);
+    let stdout = check_all_files(temp.path().to_str().unwrap());
+    assert!(stdout.contains("Reviewed 2 files"), "{}", stdout);
+}
+
+#[cfg(windows)]
+#[test]
+fn no_only_changed_reads_nested_files_on_windows() {
+    let temp = committed_repo(&[("src\\nested\\a.txt", "hello\n")]);
+    let path = temp.path().to_str().unwrap().replace('/', "\\");
+    let stdout = check_all_files(&path);
+    assert!(stdout.contains("Reviewed 1 file"), "{}", stdout);
+}
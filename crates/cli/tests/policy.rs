@@ -0,0 +1,87 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command as StdCommand, Output};
+use tempfile::tempdir;
+
+mod common;
+use common::reviewlens;
+
+const POLICY: &str =
+    "required-rules = [\"secrets\"]\nmax-findings = { critical = 0, high = 1 }\nforbid-disabling-redaction = true\n";
+
+/// Stages `content` as `app.py` in a new repository configured with
+/// `config`, and runs `check --staged --policy` against `POLICY`.
+fn check_with_policy(repo: &Path, content: &str, config: &str) -> Output {
+    fs::write(repo.join("app.py"), content).unwrap();
+    fs::write(repo.join("reviewlens.toml"), config).unwrap();
+    fs::write(repo.join("reviewlens-policy.toml"), POLICY).unwrap();
+    for args in [&["init"][..], &["add", "app.py"][..]] {
+        let output = StdCommand::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+    reviewlens(repo)
+        .args([
+            "check",
+            "--staged",
+            "--no-progress",
+            "--policy",
+            "reviewlens-policy.toml",
+        ])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn config_that_breaks_the_policy_exits_2() {
+    let temp = tempdir().unwrap();
+    let output = check_with_policy(
+        temp.path(),
+        "print('hello')\n",
+        "[rules.secrets]\nenabled = false\n",
+    );
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("required rule `secrets` is disabled"),
+        "{}",
+        stderr
+    );
+    assert!(!temp.path().join("review_report.md").exists());
+}
+
+#[test]
+fn findings_over_the_policy_limit_exit_1() {
+    let temp = tempdir().unwrap();
+    let output = check_with_policy(
+        temp.path(),
+        "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"\ntoken = \"ZYXWVUTSRQPONMLKJIHGFEDC\"\n",
+        "fail-on = \"never\"\n",
+    );
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("policy allows at most 1 high finding, found 2"),
+        "{}",
+        stderr
+    );
+    let report = fs::read_to_string(temp.path().join("review_report.md")).unwrap();
+    assert!(report.contains("Policy Violations"), "{}", report);
+}
+
+#[test]
+fn compliant_run_exits_0() {
+    let temp = tempdir().unwrap();
+    let output = check_with_policy(
+        temp.path(),
+        "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"\n",
+        "fail-on = \"never\"\n",
+    );
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    let report = fs::read_to_string(temp.path().join("review_report.md")).unwrap();
+    assert!(!report.contains("Policy Violations"), "{}", report);
+}
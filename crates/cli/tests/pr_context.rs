@@ -0,0 +1,55 @@
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+mod common;
+use common::{check, git};
+
+fn staged_repo(repo: &Path) {
+    git(repo, &["init"]);
+    fs::write(repo.join("a.js"), "const x = 1;\n").unwrap();
+    git(repo, &["add", "a.js"]);
+}
+
+/// Runs `check --staged` and returns the Markdown report.
+fn review(repo: &Path, event: Option<&Path>, extra: &[&str]) -> String {
+    let output = repo.join("report.md");
+    let mut cmd = check(repo);
+    cmd.env_remove("GITHUB_EVENT_PATH")
+        .args(["--staged", "--output", output.to_str().unwrap()])
+        .args(extra);
+    if let Some(event) = event {
+        cmd.env("GITHUB_EVENT_PATH", event);
+    }
+    cmd.assert().success();
+    fs::read_to_string(output).unwrap()
+}
+
+#[test]
+fn pull_request_title_is_read_from_the_github_event() {
+    let temp = tempdir().unwrap();
+    staged_repo(temp.path());
+    let event = temp.path().join("event.json");
+    fs::write(
+        &event,
+        r#"{"pull_request": {"title": "feat(ui): dark mode", "body": "Adds a toggle."}}"#,
+    )
+    .unwrap();
+
+    let md = review(temp.path(), Some(&event), &[]);
+    assert!(md.contains("## Change Context"), "{}", md);
+    assert!(md.contains("**Title:** feat(ui): dark mode"));
+    assert!(md.contains("**Type:** `feat`"));
+
+    // Flags take precedence over the event.
+    let md = review(temp.path(), Some(&event), &["--pr-title", "fix: typo"]);
+    assert!(md.contains("**Title:** fix: typo"));
+}
+
+#[test]
+fn reports_have_no_change_context_without_a_title() {
+    let temp = tempdir().unwrap();
+    staged_repo(temp.path());
+    let md = review(temp.path(), None, &[]);
+    assert!(!md.contains("## Change Context"));
+}
@@ -0,0 +1,68 @@
+use std::fs;
+use std::process::Command as StdCommand;
+use tempfile::tempdir;
+
+mod common;
+use common::reviewlens;
+
+#[test]
+fn failed_publish_warns_without_failing_the_review() {
+    let temp = tempdir().unwrap();
+    let repo = temp.path();
+    fs::write(repo.join("main.js"), "let a = 1;\n").unwrap();
+    // Nothing listens on port 9, so the webhook cannot be reached.
+    fs::write(
+        repo.join("reviewlens.toml"),
+        "[integrations.slack]\nwebhook-url = \"http://127.0.0.1:9/hook\"\n",
+    )
+    .unwrap();
+    for args in [&["init"][..], &["add", "."][..]] {
+        let output = StdCommand::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+
+    let output = reviewlens(repo)
+        .env_remove("SLACK_WEBHOOK_URL")
+        .args(["check", "--staged", "--no-progress", "--publish", "slack"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("warning: failed to publish to slack"),
+        "{}",
+        stderr
+    );
+    assert!(repo.join("review_report.md").exists());
+}
+
+#[test]
+fn publish_without_a_webhook_warns() {
+    let temp = tempdir().unwrap();
+    let repo = temp.path();
+    fs::write(repo.join("main.js"), "let a = 1;\n").unwrap();
+    for args in [&["init"][..], &["add", "."][..]] {
+        let output = StdCommand::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+
+    let output = reviewlens(repo)
+        .env_remove("SLACK_WEBHOOK_URL")
+        .args(["check", "--staged", "--no-progress", "--publish", "slack"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("not publishing to Slack"), "{}", stderr);
+    assert!(stderr.contains("SLACK_WEBHOOK_URL"), "{}", stderr);
+}
@@ -0,0 +1,38 @@
+use std::fs;
+
+mod common;
+use common::{check_json, repo_with_change};
+
+#[test]
+fn no_rag_skips_the_index_with_identical_findings() {
+    let temp = repo_with_change(
+        &[
+            (".gitignore", ".reviewlens/\nreport.json\n"),
+            ("a.js", "const x = 1;\n"),
+        ],
+        &[("a.js", "const api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n")],
+    );
+    let repo = temp.path();
+    // An unreadable index is reported whenever it is loaded.
+    fs::create_dir_all(repo.join(".reviewlens/index")).unwrap();
+    fs::write(
+        repo.join(".reviewlens/index/index.json.zst"),
+        "not an index",
+    )
+    .unwrap();
+
+    let args = ["--diff", "HEAD", "--fail-on", "never"];
+    let (code, stderr, enabled) = check_json(repo, &args);
+    assert_eq!(code, 0, "{}", stderr);
+    assert!(stderr.contains("Failed to load vector index"), "{}", stderr);
+    let enabled = enabled.unwrap();
+    assert_eq!(enabled["metadata"]["rag_enabled"], true);
+
+    let (code, stderr, disabled) = check_json(repo, &[&args[..], &["--no-rag"]].concat());
+    assert_eq!(code, 0, "{}", stderr);
+    assert!(!stderr.contains("vector index"), "{}", stderr);
+    let disabled = disabled.unwrap();
+    assert_eq!(disabled["metadata"]["rag_enabled"], false);
+    assert_eq!(disabled["issues"].as_array().unwrap().len(), 1);
+    assert_eq!(disabled["issues"], enabled["issues"]);
+}
@@ -0,0 +1,132 @@
+use assert_cmd::Command;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::repo_with_change;
+
+/// A repository whose working tree adds a hardcoded secret on top of `HEAD`.
+fn repo_with_secret() -> tempfile::TempDir {
+    repo_with_change(
+        &[("a.js", "const x = 1;\n")],
+        &[(
+            "a.js",
+            "const x = 1;\nconst API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n",
+        )],
+    )
+}
+
+fn reviewlens(repo: &Path) -> Command {
+    let mut cmd = common::reviewlens(repo);
+    cmd.env_remove("REVIEWLENS_RULES");
+    cmd
+}
+
+/// Arguments for a JSON review of the working tree, placed after the
+/// top-level `--rule` options.
+const CHECK: [&str; 8] = [
+    "check",
+    "--diff",
+    "HEAD",
+    "--format",
+    "json",
+    "--output",
+    "report.json",
+    "--no-progress",
+];
+
+#[test]
+fn secret_fails_check_without_override() {
+    let temp = repo_with_secret();
+    reviewlens(temp.path()).args(CHECK).assert().code(1);
+}
+
+#[test]
+fn rule_flag_disables_secrets() {
+    let temp = repo_with_secret();
+    reviewlens(temp.path())
+        .args(["--rule", "secrets=off"])
+        .args(CHECK)
+        .assert()
+        .success();
+    let report: Value =
+        serde_json::from_str(&fs::read_to_string(temp.path().join("report.json")).unwrap())
+            .unwrap();
+    assert!(report["issues"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn rules_env_var_downgrades_severity() {
+    let temp = repo_with_secret();
+    reviewlens(temp.path())
+        .env("REVIEWLENS_RULES", "secrets=low,conventions=off")
+        .args(CHECK)
+        .assert()
+        .success();
+    let report: Value =
+        serde_json::from_str(&fs::read_to_string(temp.path().join("report.json")).unwrap())
+            .unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["severity"], "low");
+}
+
+#[test]
+fn unknown_rule_exits_two_listing_valid_names() {
+    let temp = repo_with_secret();
+    let output = reviewlens(temp.path())
+        .args(["--rule", "secret=off"])
+        .args(CHECK)
+        .assert()
+        .code(2)
+        .get_output()
+        .stderr
+        .clone();
+    let stderr = String::from_utf8(output).unwrap();
+    assert!(stderr.contains("unknown rule `secret`"), "{}", stderr);
+    assert!(
+        stderr.contains(
+            "artifacts, complexity, conventions, http-timeouts-go, network-hygiene, removed-controls, secrets, sql-injection-go, todo"
+        ),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn invalid_severity_exits_two() {
+    let temp = repo_with_secret();
+    reviewlens(temp.path())
+        .args(["--rule", "secrets=loud"])
+        .args(CHECK)
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn print_config_reflects_rule_overrides() {
+    let temp = repo_with_secret();
+    let output = reviewlens(temp.path())
+        .args([
+            "--rule",
+            "secrets=off",
+            "--rule",
+            "network-hygiene=critical",
+            "print-config",
+            "--path",
+            temp.path().to_str().unwrap(),
+            "--base-ref",
+            "HEAD",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    let json_end = stdout.find("\n}").unwrap() + 2;
+    let config: Value = serde_json::from_str(&stdout[..json_end]).unwrap();
+    assert_eq!(config["rules"]["secrets"]["enabled"], false);
+    assert_eq!(config["rules"]["network-hygiene"]["severity"], "critical");
+}
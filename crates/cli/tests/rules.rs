@@ -0,0 +1,61 @@
+use assert_cmd::Command;
+use serde_json::Value;
+use std::fs;
+use tempfile::tempdir;
+
+const BUILT_IN: [&str; 9] = [
+    "secrets",
+    "sql-injection-go",
+    "http-timeouts-go",
+    "conventions",
+    "network-hygiene",
+    "todo",
+    "complexity",
+    "removed-controls",
+    "artifacts",
+];
+
+fn rules(dir: &std::path::Path, extra: &[&str]) -> String {
+    let output = Command::cargo_bin("reviewlens")
+        .unwrap()
+        .current_dir(dir)
+        .env("REVIEWLENS_USER_CONFIG", dir.join("missing.toml"))
+        .arg("rules")
+        .args(extra)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn json_lists_every_built_in_rule() {
+    let temp = tempdir().unwrap();
+    fs::write(
+        temp.path().join("reviewlens.toml"),
+        "[rules.conventions]\nenabled = false\n",
+    )
+    .unwrap();
+    let rules: Value = serde_json::from_str(&rules(temp.path(), &["--json"])).unwrap();
+    let rules = rules.as_array().unwrap();
+    let ids: Vec<&str> = rules.iter().map(|r| r["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, BUILT_IN);
+    assert_eq!(rules[0]["default_severity"], "high");
+    assert_eq!(rules[1]["languages"][0], "go");
+    assert_eq!(rules[3]["enabled"], false);
+    assert_eq!(rules[4]["enabled"], true);
+}
+
+#[test]
+fn table_has_a_row_per_rule() {
+    let temp = tempdir().unwrap();
+    let table = rules(temp.path(), &[]);
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines[0].starts_with("RULE"), "{}", table);
+    assert!(lines[0].ends_with("DESCRIPTION"));
+    assert_eq!(lines.len(), 1 + BUILT_IN.len());
+    for (line, id) in lines[1..].iter().zip(BUILT_IN) {
+        assert!(line.starts_with(id), "{}", line);
+    }
+    assert!(lines[2].contains("critical"));
+}
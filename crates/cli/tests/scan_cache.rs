@@ -0,0 +1,57 @@
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::repo_with_change;
+
+/// A repository with a scan cache configured and a secret added to `a.js`.
+fn repo() -> tempfile::TempDir {
+    repo_with_change(
+        &[
+            (
+                "reviewlens.toml",
+                "[engine]\nscan-cache-path = \".reviewlens/cache/scan.json\"\n",
+            ),
+            ("a.js", "const x = 1;\n"),
+        ],
+        &[("a.js", "const api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n")],
+    )
+}
+
+/// Runs `check` from outside `repo` and returns the JSON report.
+fn check_from_outside(repo: &Path, extra: &[&str]) -> String {
+    let report = repo.join("report.json");
+    Command::cargo_bin("reviewlens")
+        .unwrap()
+        .env("REVIEWLENS_USER_CONFIG", repo.join("missing.toml"))
+        .args(["--config", repo.join("reviewlens.toml").to_str().unwrap()])
+        .args(["check", "--diff", "HEAD", "--path", repo.to_str().unwrap()])
+        .args(["--format", "json", "--no-progress", "--fail-on", "never"])
+        .args(["--output", report.to_str().unwrap()])
+        .args(extra)
+        .assert()
+        .success();
+    fs::read_to_string(report).unwrap()
+}
+
+#[test]
+fn cache_is_written_under_the_repository() {
+    let temp = repo();
+    let repo = temp.path();
+    let first = check_from_outside(repo, &[]);
+    assert!(repo.join(".reviewlens/cache/scan.json").exists());
+    let second = check_from_outside(repo, &[]);
+    let issues =
+        |report: &str| serde_json::from_str::<serde_json::Value>(report).unwrap()["issues"].clone();
+    assert_eq!(issues(&first), issues(&second));
+    assert_eq!(issues(&first).as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn no_cache_neither_reads_nor_writes() {
+    let temp = repo();
+    let repo = temp.path();
+    check_from_outside(repo, &["--no-cache"]);
+    assert!(!repo.join(".reviewlens/cache/scan.json").exists());
+}
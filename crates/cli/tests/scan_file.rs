@@ -0,0 +1,66 @@
+use std::fs;
+use tempfile::tempdir;
+
+mod common;
+use common::reviewlens;
+
+#[test]
+fn scan_file_lists_findings_without_git() {
+    let temp = tempdir().unwrap();
+    let dir = temp.path();
+    fs::write(
+        dir.join("main.rs"),
+        "fn main() {\n    // TODO: handle errors\n    let api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n}\n",
+    )
+    .unwrap();
+
+    let output = reviewlens(dir)
+        .args(["scan-file", "main.rs"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", stdout);
+    assert!(lines[0].starts_with("main.rs:2  "), "{}", stdout);
+    assert!(lines[0].contains("  todo  "), "{}", stdout);
+    assert!(
+        lines[1].starts_with("main.rs:3  high  secrets  "),
+        "{}",
+        stdout
+    );
+    assert!(!dir.join("review_report.md").exists());
+}
+
+#[test]
+fn scan_file_prints_json() {
+    let temp = tempdir().unwrap();
+    let dir = temp.path();
+    fs::write(
+        dir.join("net.go"),
+        "func fetch() {\n\tresp, _ := http.Get(\"http://example.com\")\n}\n",
+    )
+    .unwrap();
+
+    let output = reviewlens(dir)
+        .args(["scan-file", "net.go", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let issues: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let issues = issues.as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["rule_id"], "http-timeouts-go");
+    assert_eq!(issues[0]["file_path"], "net.go");
+    assert_eq!(issues[0]["line_number"], 2);
+    assert!(!issues[0]["fingerprint"].as_str().unwrap().is_empty());
+}
+
+#[test]
+fn scan_file_fails_for_a_missing_file() {
+    let temp = tempdir().unwrap();
+    reviewlens(temp.path())
+        .args(["scan-file", "absent.rs"])
+        .assert()
+        .code(3);
+}
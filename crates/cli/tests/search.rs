@@ -0,0 +1,66 @@
+use std::fs;
+use tempfile::tempdir;
+
+mod common;
+use common::reviewlens;
+
+fn indexed_repo() -> tempfile::TempDir {
+    let temp = tempdir().unwrap();
+    let repo = temp.path();
+    fs::write(
+        repo.join("db.go"),
+        "func find(id string) {\n\tq := fmt.Sprintf(\"SELECT name FROM users WHERE id = %s\", id)\n\trun(q)\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("README.md"),
+        "Welcome to the project documentation.\nRead the guide first.\n",
+    )
+    .unwrap();
+    reviewlens(repo)
+        .args(["index", "--path", repo.to_str().unwrap(), "--allow", "**"])
+        .assert()
+        .success();
+    temp
+}
+
+#[test]
+fn search_prints_ranked_hits() {
+    let temp = indexed_repo();
+    let output = reviewlens(temp.path())
+        .args(["search", "name FROM users WHERE", "--top-k", "1"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let text = String::from_utf8(output).unwrap();
+    assert!(text.starts_with("1. db.go (score "), "{}", text);
+    assert!(text.contains("FROM users"), "{}", text);
+    assert!(!text.contains("README.md"), "{}", text);
+}
+
+#[test]
+fn search_json_output_includes_scores() {
+    let temp = indexed_repo();
+    let index = temp.path().join(".reviewlens/index/index.json.zst");
+    let output = reviewlens(temp.path())
+        .args([
+            "search",
+            "name FROM users WHERE",
+            "--json",
+            "--index",
+            index.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let hits: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let hits = hits.as_array().unwrap();
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0]["filename"], "db.go");
+    assert!(hits[0]["score"].as_f64().unwrap() > hits[1]["score"].as_f64().unwrap());
+    assert!(hits[0]["snippet"].as_str().unwrap().contains("SELECT name"));
+}
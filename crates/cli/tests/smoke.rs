@@ -70,7 +70,7 @@ fn check_command_respects_path_argument() {
         .expect("git config name failed");
 
     // Create initial commit
-    fs::write(repo.join("file.txt"), "hello\n").unwrap();
+    fs::write(repo.join("file.env"), "hello\n").unwrap();
     StdCommand::new("git")
         .args(["-C", repo_str, "add", "."])
         .output()
@@ -81,7 +81,7 @@ fn check_command_respects_path_argument() {
         .expect("git commit failed");
 
     // Modify file to create diff
-    fs::write(repo.join("file.txt"), "hello world\n").unwrap();
+    fs::write(repo.join("file.env"), "hello world\n").unwrap();
 
     let output_path = repo.join("out.md");
     let output_str = output_path.to_str().unwrap();
@@ -128,7 +128,7 @@ fn check_command_reports_issues_and_exit_code() {
         .expect("git config name failed");
 
     // Create initial commit
-    fs::write(repo.join("file.txt"), "hello\n").unwrap();
+    fs::write(repo.join("file.env"), "hello\n").unwrap();
     StdCommand::new("git")
         .args(["-C", repo_str, "add", "."])
         .output()
@@ -139,7 +139,7 @@ fn check_command_reports_issues_and_exit_code() {
         .expect("git commit failed");
 
     // Modify file to introduce a secret
-    fs::write(repo.join("file.txt"), "api_key = \"ABCDEFGHIJKLMNOP\"\n").unwrap();
+    fs::write(repo.join("file.env"), "api_key = \"ABCDEFGHIJKLMNOP\"\n").unwrap();
 
     let mut cmd = Command::cargo_bin("reviewlens").unwrap();
     cmd.args([
@@ -155,6 +155,77 @@ fn check_command_reports_issues_and_exit_code() {
     cmd.assert().code(1);
 }
 
+#[test]
+fn check_command_fail_on_never_exits_zero() {
+    let temp = tempdir().unwrap();
+    let repo = temp.path();
+    let repo_str = repo.to_str().unwrap();
+
+    // Initialize git repository
+    StdCommand::new("git")
+        .args(["init", repo_str])
+        .output()
+        .expect("git init failed");
+    StdCommand::new("git")
+        .args(["-C", repo_str, "config", "user.email", "you@example.com"])
+        .output()
+        .expect("git config email failed");
+    StdCommand::new("git")
+        .args(["-C", repo_str, "config", "user.name", "Your Name"])
+        .output()
+        .expect("git config name failed");
+
+    // Create initial commit
+    fs::write(repo.join("file.env"), "hello\n").unwrap();
+    StdCommand::new("git")
+        .args(["-C", repo_str, "add", "."])
+        .output()
+        .expect("git add failed");
+    StdCommand::new("git")
+        .args(["-C", repo_str, "commit", "-m", "init"])
+        .output()
+        .expect("git commit failed");
+
+    // Modify file to introduce a secret
+    fs::write(repo.join("file.env"), "api_key = \"ABCDEFGHIJKLMNOP\"\n").unwrap();
+
+    let output_path = repo.join("out.md");
+    let output_str = output_path.to_str().unwrap();
+
+    let mut cmd = Command::cargo_bin("reviewlens").unwrap();
+    cmd.args([
+        "check",
+        "--path",
+        repo_str,
+        "--base-ref",
+        "HEAD",
+        "--fail-on",
+        "never",
+        "--output",
+        output_str,
+    ]);
+    cmd.assert().code(0);
+
+    let mut cmd = Command::cargo_bin("reviewlens").unwrap();
+    let output = cmd
+        .args([
+            "check",
+            "--path",
+            repo_str,
+            "--base-ref",
+            "HEAD",
+            "--fail-on",
+            "high",
+            "--output",
+            output_str,
+        ])
+        .output()
+        .expect("failed to execute command");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("failing because 1 issue \u{2265} high"));
+}
+
 #[test]
 fn check_command_respects_fail_on_from_config() {
     let temp = tempdir().unwrap();
@@ -176,7 +247,7 @@ fn check_command_respects_fail_on_from_config() {
         .expect("git config name failed");
 
     // Create initial commit
-    fs::write(repo.join("file.txt"), "hello\n").unwrap();
+    fs::write(repo.join("file.env"), "hello\n").unwrap();
     StdCommand::new("git")
         .args(["-C", repo_str, "add", "."])
         .output()
@@ -187,7 +258,7 @@ fn check_command_respects_fail_on_from_config() {
         .expect("git commit failed");
 
     // Modify file to introduce a secret
-    fs::write(repo.join("file.txt"), "api_key = \"ABCDEFGHIJKLMNOP\"\n").unwrap();
+    fs::write(repo.join("file.env"), "api_key = \"ABCDEFGHIJKLMNOP\"\n").unwrap();
 
     // Configure critical fail-on threshold
     fs::write(repo.join("reviewlens.toml"), "fail-on = \"critical\"\n").unwrap();
@@ -224,7 +295,7 @@ fn check_command_without_upstream_or_diff_errors() {
         .expect("git config name failed");
 
     // Create initial commit
-    fs::write(repo.join("file.txt"), "hello\n").unwrap();
+    fs::write(repo.join("file.env"), "hello\n").unwrap();
     StdCommand::new("git")
         .args(["-C", repo_str, "add", "."])
         .output()
@@ -235,7 +306,7 @@ fn check_command_without_upstream_or_diff_errors() {
         .expect("git commit failed");
 
     // Modify file to create diff
-    fs::write(repo.join("file.txt"), "hello world\n").unwrap();
+    fs::write(repo.join("file.env"), "hello world\n").unwrap();
 
     let mut cmd = Command::cargo_bin("reviewlens").unwrap();
     cmd.args(["check", "--path", repo_str]);
@@ -264,7 +335,7 @@ fn check_command_redacts_secrets_in_report() {
         .expect("git config name failed");
 
     // Create initial commit
-    fs::write(repo.join("file.txt"), "hello\n").unwrap();
+    fs::write(repo.join("file.env"), "hello\n").unwrap();
     StdCommand::new("git")
         .args(["-C", repo_str, "add", "."])
         .output()
@@ -276,7 +347,7 @@ fn check_command_redacts_secrets_in_report() {
 
     // Modify file to introduce a secret
     fs::write(
-        repo.join("file.txt"),
+        repo.join("file.env"),
         "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"\n",
     )
     .unwrap();
@@ -328,7 +399,7 @@ fn check_command_generates_json_report_and_redacts_secrets() {
         .expect("git config name failed");
 
     // Create initial commit
-    fs::write(repo.join("file.txt"), "hello\n").unwrap();
+    fs::write(repo.join("file.env"), "hello\n").unwrap();
     StdCommand::new("git")
         .args(["-C", repo_str, "add", "."])
         .output()
@@ -340,7 +411,7 @@ fn check_command_generates_json_report_and_redacts_secrets() {
 
     // Modify file to introduce a secret
     fs::write(
-        repo.join("file.txt"),
+        repo.join("file.env"),
         "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"\n",
     )
     .unwrap();
@@ -379,4 +450,166 @@ fn check_command_generates_json_report_and_redacts_secrets() {
     assert!(report.contains("[REDACTED]"));
     assert!(!report.contains("api_key"));
     assert!(!report.contains("ABCDEFGHIJKLMNOPQRSTUVWX"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn print_config_shows_layer_provenance_when_verbose() {
+    let temp = tempdir().unwrap();
+    let user_path = temp.path().join("user.toml");
+    let repo_path = temp.path().join("reviewlens.toml");
+    fs::write(
+        &user_path,
+        "[llm]\nprovider = \"null\"\nmodel = \"user-model\"\n",
+    )
+    .unwrap();
+    fs::write(&repo_path, "[paths]\ndeny = [\"vendor/**\"]\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("reviewlens").unwrap();
+    let output = cmd
+        .env("REVIEWLENS_USER_CONFIG", &user_path)
+        .args([
+            "-v",
+            "--config",
+            repo_path.to_str().unwrap(),
+            "print-config",
+            "--base-ref",
+            "HEAD",
+        ])
+        .output()
+        .expect("failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&format!("  llm: user ({})", user_path.display())));
+    assert!(stdout.contains(&format!("  paths: repo ({})", repo_path.display())));
+    assert!(stdout.contains("  rules: default"));
+}
+
+#[test]
+fn print_config_shows_effective_config_for_path() {
+    let temp = tempdir().unwrap();
+    let repo_path = temp.path().join("reviewlens.toml");
+    fs::write(
+        &repo_path,
+        "[[overrides]]\npaths = [\"docs/**\"]\n[overrides.rules.secrets]\nenabled = false\n",
+    )
+    .unwrap();
+
+    let effective = |path: &str| -> Value {
+        let output = Command::cargo_bin("reviewlens")
+            .unwrap()
+            .env("REVIEWLENS_USER_CONFIG", temp.path().join("missing.toml"))
+            .args([
+                "--config",
+                repo_path.to_str().unwrap(),
+                "print-config",
+                "--base-ref",
+                "HEAD",
+                "--for-path",
+                path,
+            ])
+            .output()
+            .expect("failed to execute command");
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let json_part = stdout.split("Base ref:").next().unwrap().trim().to_string();
+        serde_json::from_str(&json_part).unwrap()
+    };
+
+    assert_eq!(
+        effective("docs/guide.md")["rules"]["secrets"]["enabled"],
+        false
+    );
+    assert_eq!(
+        effective("src/main.rs")["rules"]["secrets"]["enabled"],
+        true
+    );
+}
+
+#[test]
+fn check_writes_each_requested_format() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    let repo_str = repo.to_str().unwrap();
+    for args in [
+        vec!["init", repo_str],
+        vec!["-C", repo_str, "config", "user.email", "you@example.com"],
+        vec!["-C", repo_str, "config", "user.name", "Your Name"],
+    ] {
+        StdCommand::new("git").args(&args).output().unwrap();
+    }
+    fs::write(repo.join("file.env"), "hello\n").unwrap();
+    StdCommand::new("git")
+        .args(["-C", repo_str, "add", "."])
+        .output()
+        .unwrap();
+    StdCommand::new("git")
+        .args(["-C", repo_str, "commit", "-m", "init"])
+        .output()
+        .unwrap();
+    fs::write(repo.join("file.env"), "hello world\n").unwrap();
+
+    let output_base = repo.join("report");
+    let mut cmd = Command::cargo_bin("reviewlens").unwrap();
+    cmd.args([
+        "check",
+        "--path",
+        repo_str,
+        "--diff",
+        "HEAD",
+        "--format",
+        "md,json",
+        "--output",
+        output_base.to_str().unwrap(),
+        "--no-progress",
+    ]);
+
+    let output = cmd.output().expect("failed to execute command");
+    assert!(output.status.success());
+    let md_path = repo.join("report.md");
+    let json_path = repo.join("report.json");
+    assert!(fs::read_to_string(&md_path)
+        .unwrap()
+        .contains("## 📊 Statistics"));
+    let json: Value = serde_json::from_str(&fs::read_to_string(&json_path).unwrap())
+        .expect("JSON report should parse");
+    assert!(json["summary"].is_string());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&md_path.display().to_string()));
+    assert!(stdout.contains(&json_path.display().to_string()));
+}
+
+#[test]
+fn print_config_shows_generation_overrides() {
+    let temp = tempdir().unwrap();
+    let output = Command::cargo_bin("reviewlens")
+        .unwrap()
+        .env("REVIEWLENS_USER_CONFIG", temp.path().join("missing.toml"))
+        .env("REVIEWLENS_GENERATION_STOP", "END,STOP")
+        .args([
+            "--config",
+            temp.path().join("reviewlens.toml").to_str().unwrap(),
+            "--generation-max-tokens",
+            "4096",
+            "--generation-top-p",
+            "0.5",
+            "--generation-seed",
+            "7",
+            "print-config",
+            "--base-ref",
+            "HEAD",
+        ])
+        .output()
+        .expect("failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json = stdout.split("Base ref:").next().unwrap();
+    let json = json.split("Deprecated keys:").next().unwrap();
+    let config: Value = serde_json::from_str(json).unwrap();
+    let generation = &config["generation"];
+    assert_eq!(generation["max-tokens"], 4096);
+    assert_eq!(generation["top-p"], 0.5);
+    assert_eq!(generation["seed"], 7);
+    assert_eq!(generation["stop"], serde_json::json!(["END", "STOP"]));
+}
@@ -0,0 +1,36 @@
+use std::fs;
+
+mod common;
+use common::{check, check_json, git, repo_with_change};
+
+#[test]
+fn staged_review_reads_the_index_not_the_working_tree() {
+    // Stage a secret on line 2, then push it down in the working tree.
+    let staged = "const x = 1;\nconst API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n";
+    let temp = repo_with_change(&[("a.js", "const x = 1;\n")], &[("a.js", staged)]);
+    let repo = temp.path();
+    git(repo, &["add", "a.js"]);
+    fs::write(
+        repo.join("a.js"),
+        format!("// one\n// two\n// three\n{}", staged),
+    )
+    .unwrap();
+
+    let (code, stderr, report) = check_json(repo, &["--staged", "--fail-on", "never"]);
+    assert_eq!(code, 0, "{}", stderr);
+    let report = report.unwrap();
+    let issues = report["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1, "{:#?}", issues);
+    assert_eq!(issues[0]["file_path"], "a.js");
+    assert_eq!(issues[0]["line_number"], 2);
+}
+
+#[test]
+fn staged_conflicts_with_diff() {
+    let temp = tempfile::tempdir().unwrap();
+    check(temp.path())
+        .args(["--staged", "--diff", "HEAD"])
+        .assert()
+        .failure()
+        .code(2);
+}
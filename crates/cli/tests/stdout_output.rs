@@ -0,0 +1,58 @@
+use assert_cmd::Command;
+use std::path::Path;
+
+mod common;
+use common::repo_with_change;
+
+/// A repository with a secret added to `a.js`.
+fn repo() -> tempfile::TempDir {
+    repo_with_change(
+        &[("a.js", "const x = 1;\n")],
+        &[("a.js", "const api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n")],
+    )
+}
+
+fn check(repo: &Path) -> Command {
+    let mut cmd = common::check(repo);
+    cmd.args(["--diff", "HEAD", "--fail-on", "never"]);
+    cmd
+}
+
+#[test]
+fn json_report_is_piped_to_stdout() {
+    let temp = repo();
+    let repo = temp.path();
+    let output = check(repo)
+        .args(["--format", "json", "--output", "-"])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+
+    // stdout holds the report and nothing else.
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect("stdout is the report");
+    assert_eq!(report["issues"][0]["file_path"], "a.js");
+    assert!(!stdout.contains("ABCDEFGHIJKLMNOPQRSTUVWX"), "{}", stdout);
+    assert!(!repo.join("-").exists());
+    assert!(!repo.join("review_report.json").exists());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Summary:"), "{}", stderr);
+    assert!(stderr.contains("Top hotspots:"), "{}", stderr);
+}
+
+#[test]
+fn stdout_takes_a_single_format() {
+    let temp = repo();
+    let output = check(temp.path())
+        .args(["--format", "json,md", "--output", "-"])
+        .assert()
+        .code(2)
+        .stdout("")
+        .get_output()
+        .stderr
+        .clone();
+    let stderr = String::from_utf8(output).unwrap();
+    assert!(stderr.contains("writes a single format"), "{}", stderr);
+}
@@ -0,0 +1,94 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::{repo_with_change, reviewlens};
+
+/// A repository whose working tree changes `a.js` to `content`.
+fn repo(content: &str) -> tempfile::TempDir {
+    repo_with_change(&[("a.js", "const x = 1;\n")], &[("a.js", content)])
+}
+
+/// Runs `check` with `--summary-out`, after the top-level options in
+/// `extra`, and returns the exit code and summary.
+fn summarize(repo: &Path, extra: &[&str]) -> (i32, Value) {
+    let summary = repo.join("summary.json");
+    let report = repo.join("report.json");
+    let output = reviewlens(repo)
+        .args(extra)
+        .args([
+            "check",
+            "--diff",
+            "HEAD",
+            "--format",
+            "json",
+            "--output",
+            report.to_str().unwrap(),
+            "--no-progress",
+            "--summary-out",
+            summary.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    let summary = serde_json::from_str(&fs::read_to_string(summary).unwrap()).unwrap();
+    (output.status.code().unwrap(), summary)
+}
+
+#[test]
+fn summary_for_clean_run() {
+    let temp = repo("const x = 2;\n");
+    let (code, summary) = summarize(temp.path(), &[]);
+    assert_eq!(code, 0);
+    assert_eq!(summary["exit_code"], 0);
+    assert_eq!(summary["error"], Value::Null);
+    assert_eq!(summary["fail_on"], "high");
+    assert_eq!(summary["issues"], 0);
+    assert_eq!(
+        summary["issues_by_severity"],
+        serde_json::json!({"critical": 0, "high": 0, "medium": 0, "low": 0})
+    );
+    assert_eq!(summary["highest_severity"], Value::Null);
+    let report = temp.path().join("report.json");
+    assert_eq!(
+        summary["reports"],
+        serde_json::json!([report.to_str().unwrap()])
+    );
+    assert_eq!(summary["tokens_used"], 0);
+    assert!(summary["duration_ms"].is_u64());
+}
+
+#[test]
+fn summary_for_findings_above_threshold() {
+    let temp = repo("const x = 1;\nconst API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n");
+    let (code, summary) = summarize(temp.path(), &[]);
+    assert_eq!(code, 1);
+    assert_eq!(summary["exit_code"], 1);
+    assert_eq!(summary["error"], Value::Null);
+    assert_eq!(summary["issues"], 1);
+
+    let report: Value =
+        serde_json::from_str(&fs::read_to_string(temp.path().join("report.json")).unwrap())
+            .unwrap();
+    let severity = report["issues"][0]["severity"].as_str().unwrap();
+    assert_eq!(summary["highest_severity"], severity);
+    assert_eq!(summary["issues_by_severity"][severity], 1);
+}
+
+#[test]
+fn summary_for_config_error() {
+    let temp = repo("const x = 2;\n");
+    fs::write(
+        temp.path().join("reviewlens.toml"),
+        "fail-on = \"medium\"\n\n[llm]\nprovider = \"openai\"\napi-key = \"dummy\"\n",
+    )
+    .unwrap();
+    let (code, summary) = summarize(temp.path(), &["--config", "reviewlens.toml"]);
+    assert_eq!(code, 2);
+    assert_eq!(summary["exit_code"], 2);
+    let error = summary["error"].as_str().unwrap();
+    assert!(error.contains("Configuration error"), "{}", error);
+    assert_eq!(summary["fail_on"], "medium");
+    assert_eq!(summary["issues"], 0);
+    assert_eq!(summary["reports"], serde_json::json!([]));
+}
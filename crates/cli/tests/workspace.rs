@@ -0,0 +1,235 @@
+use serde_json::Value;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+mod common;
+use common::{check, init_with_change, reviewlens};
+
+/// Creates a repository with one commit of `a.js`, then changes it to
+/// `changed` in the working tree.
+fn repo(root: &Path, name: &str, changed: &str) {
+    let repo = root.join(name);
+    fs::create_dir(&repo).unwrap();
+    init_with_change(&repo, &[("a.js", "const x = 1;\n")], &[("a.js", changed)]);
+}
+
+fn workspace(root: &Path) -> std::path::PathBuf {
+    repo(root, "clean", "const x = 1;\nconst y = 2;\n");
+    repo(
+        root,
+        "leaky",
+        "const x = 1;\nconst API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n",
+    );
+    let file = root.join("workspace.toml");
+    fs::write(
+        &file,
+        "[[repo]]\npath = \"clean\"\nbase = \"HEAD\"\n\n\
+         [[repo]]\nname = \"secrets\"\npath = \"leaky\"\nbase = \"HEAD\"\n",
+    )
+    .unwrap();
+    file
+}
+
+#[test]
+fn workspace_review_combines_reports_and_fails_on_any_repo() {
+    let temp = tempdir().unwrap();
+    let file = workspace(temp.path());
+    let output = temp.path().join("report.md");
+    check(temp.path())
+        .arg("--workspace")
+        .arg(&file)
+        .args(["--format", "md,json", "--output", output.to_str().unwrap()])
+        .assert()
+        .code(1);
+
+    let md = fs::read_to_string(&output).unwrap();
+    assert!(md.starts_with("# Workspace Review Report"), "{}", md);
+    assert!(md.contains("2 repositories, 1 issue"));
+    assert!(md.contains("\n## clean\n"));
+    assert!(md.contains("\n## secrets\n"));
+    assert!(md.contains("\n### Summary\n"));
+    assert!(!md.contains("ABCDEFGHIJKLMNOPQRSTUVWX"));
+
+    let json: Value =
+        serde_json::from_str(&fs::read_to_string(temp.path().join("report.json")).unwrap())
+            .unwrap();
+    let repos = json["repos"].as_array().unwrap();
+    assert_eq!(repos.len(), 2);
+    assert_eq!(repos[0]["name"], "clean");
+    assert!(repos[0]["report"]["issues"].as_array().unwrap().is_empty());
+    assert_eq!(repos[1]["name"], "secrets");
+    let issues = repos[1]["report"]["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1, "{:#?}", issues);
+    assert_eq!(issues[0]["file_path"], "a.js");
+}
+
+#[test]
+fn workspace_review_passes_below_the_threshold() {
+    let temp = tempdir().unwrap();
+    let file = workspace(temp.path());
+    let output = temp.path().join("report.md");
+    check(temp.path())
+        .arg("--workspace")
+        .arg(&file)
+        .args(["--output", output.to_str().unwrap(), "--fail-on", "never"])
+        .assert()
+        .success();
+    assert!(output.exists());
+}
+
+#[test]
+fn repository_config_applies_to_its_repository_only() {
+    let temp = tempdir().unwrap();
+    let file = workspace(temp.path());
+    fs::write(
+        temp.path().join("leaky/reviewlens.toml"),
+        "[paths]\ndeny = [\"a.js\"]\n",
+    )
+    .unwrap();
+    let output = temp.path().join("report.json");
+    check(temp.path())
+        .arg("--workspace")
+        .arg(&file)
+        .args(["--format", "json", "--output", output.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let json: Value = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+    assert_eq!(json["repos"][0]["report"]["stats"]["files_reviewed"], 1);
+    assert_eq!(json["repos"][1]["report"]["stats"]["files_reviewed"], 0);
+}
+
+#[test]
+fn overrides_apply_to_repositories_with_their_own_config() {
+    let temp = tempdir().unwrap();
+    let file = workspace(temp.path());
+    fs::write(
+        temp.path().join("leaky/reviewlens.toml"),
+        "fail-on = \"low\"\n",
+    )
+    .unwrap();
+    let output = temp.path().join("report.json");
+    reviewlens(temp.path())
+        .args([
+            "--rule",
+            "secrets=off",
+            "check",
+            "--no-progress",
+            "--workspace",
+        ])
+        .arg(&file)
+        .args(["--format", "json", "--output", output.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let json: Value = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+    assert_eq!(json["repos"][1]["report"]["stats"]["files_reviewed"], 1);
+    assert!(json["repos"][1]["report"]["issues"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+}
+
+/// Serves chat completions that each report `tokens` used. Returns the
+/// endpoint and a count of requests served.
+fn fake_openai(tokens: u32) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}/v1/chat/completions",
+        listener.local_addr().unwrap()
+    );
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = v.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            counter.fetch_add(1, Ordering::SeqCst);
+            let json = format!(
+                r#"{{"choices":[{{"message":{{"role":"assistant","content":"LLM summary"}}}}],"usage":{{"total_tokens":{}}}}}"#,
+                tokens
+            );
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                json.len(),
+                json
+            )
+            .unwrap();
+        }
+    });
+    (url, hits)
+}
+
+#[test]
+fn token_budget_is_shared_across_repositories() {
+    let temp = tempdir().unwrap();
+    let secret = "const x = 1;\nconst API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n";
+    repo(temp.path(), "first", secret);
+    repo(temp.path(), "second", secret);
+    let file = temp.path().join("workspace.toml");
+    fs::write(
+        &file,
+        "[[repo]]\npath = \"first\"\nbase = \"HEAD\"\n\n\
+         [[repo]]\npath = \"second\"\nbase = \"HEAD\"\n",
+    )
+    .unwrap();
+    let (url, hits) = fake_openai(1500);
+    let output = temp.path().join("report.json");
+
+    check(temp.path())
+        .env("REVIEWLENS_LLM_PROVIDER", "openai")
+        .env("REVIEWLENS_LLM_MODEL", "gpt-test")
+        .env("REVIEWLENS_LLM_API_KEY", "dummy")
+        .env("REVIEWLENS_LLM_BASE_URL", &url)
+        .env("REVIEWLENS_BUDGET_TOKENS_MAX_PER_RUN", "1500")
+        .arg("--workspace")
+        .arg(&file)
+        .args(["--format", "json", "--output", output.to_str().unwrap()])
+        .args(["--fail-on", "never"])
+        .assert()
+        .success();
+
+    // The first repository spends the whole budget, leaving none for the
+    // second.
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+    let json: Value = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+    assert_eq!(
+        json["repos"][0]["report"]["metadata"]["budget_exhausted"],
+        false
+    );
+    assert_eq!(
+        json["repos"][1]["report"]["metadata"]["budget_exhausted"],
+        true
+    );
+}
+
+#[test]
+fn workspace_without_repositories_is_a_config_error() {
+    let temp = tempdir().unwrap();
+    let file = temp.path().join("workspace.toml");
+    fs::write(&file, "").unwrap();
+    check(temp.path())
+        .arg("--workspace")
+        .arg(&file)
+        .assert()
+        .code(2);
+}
@@ -0,0 +1,123 @@
+//! Auditing of `reviewlens:ignore` directives.
+//!
+//! Directives tend to outlive the code they were added for. The audit runs
+//! each directive's scanner over the whole file, without applying any
+//! directives, and checks whether the scanner still reports the rule on the
+//! line the directive silences.
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::rag;
+use crate::scanner::{self, parse_ignore_directives, ScanRequest};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// What an ignore directive does today.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum DirectiveStatus {
+    /// The directive silences a finding the scanner still reports.
+    Active,
+    /// The scanner no longer reports the rule on that line.
+    Stale,
+    /// No scanner has the directive's rule id.
+    UnknownRule,
+    /// The rule only runs on diffs, so a whole-file scan cannot tell.
+    Unchecked,
+}
+
+impl DirectiveStatus {
+    /// Returns the kebab-case name of the status.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DirectiveStatus::Active => "active",
+            DirectiveStatus::Stale => "stale",
+            DirectiveStatus::UnknownRule => "unknown-rule",
+            DirectiveStatus::Unchecked => "unchecked",
+        }
+    }
+}
+
+/// The audit result for one directive.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct DirectiveAudit {
+    /// Path of the file, relative to the repository root.
+    pub path: String,
+    /// One-based line of the directive.
+    pub line: usize,
+    /// One-based line the directive silences.
+    pub target: usize,
+    pub rule: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub status: DirectiveStatus,
+}
+
+/// Audits the directives in every file under `root` selected by `[paths]`,
+/// sorted by path and line. Files that are not UTF-8 text are skipped.
+pub fn audit_ignores(root: &Path, config: &Config) -> Result<Vec<DirectiveAudit>> {
+    let allow = rag::build_globset(&config.paths.allow)?;
+    let deny = rag::build_globset(&config.paths.deny)?;
+    let mut audits = Vec::new();
+    for file in rag::matching_files(root, &allow, &deny)? {
+        let Ok(content) = std::fs::read_to_string(&file.path) else {
+            log::debug!("Skipping {}: not UTF-8 text", file.filename);
+            continue;
+        };
+        audits.extend(audit_file(&file.filename, &content, config)?);
+    }
+    audits.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+    Ok(audits)
+}
+
+/// Audits the directives in `content`, the contents of `path`, in line
+/// order. Each rule's scanner runs at most once.
+pub fn audit_file(path: &str, content: &str, config: &Config) -> Result<Vec<DirectiveAudit>> {
+    let ignores = parse_ignore_directives(path, content);
+    if ignores.is_empty() {
+        return Ok(Vec::new());
+    }
+    let file_config = config.for_path(path)?;
+    // Lines each rule reports on, or `None` if the rule cannot be checked.
+    let mut flagged: HashMap<&str, Option<Vec<usize>>> = HashMap::new();
+    let mut audits = Vec::new();
+    for (&target, directives) in &ignores {
+        for directive in directives {
+            let rule = directive.rule.as_str();
+            if !flagged.contains_key(rule) {
+                let lines = match scanner::scanner_for_rule(&file_config, rule) {
+                    Some(scanner) => Some(
+                        scanner
+                            .scan_with_context(&ScanRequest::whole_file(
+                                path,
+                                content,
+                                &file_config,
+                            ))?
+                            .into_iter()
+                            .map(|issue| issue.line_number)
+                            .collect(),
+                    ),
+                    None => None,
+                };
+                flagged.insert(rule, lines);
+            }
+            let status = match &flagged[rule] {
+                Some(lines) if lines.contains(&target) => DirectiveStatus::Active,
+                Some(_) => DirectiveStatus::Stale,
+                None if scanner::is_diff_rule(rule) => DirectiveStatus::Unchecked,
+                None => DirectiveStatus::UnknownRule,
+            };
+            audits.push(DirectiveAudit {
+                path: path.to_string(),
+                line: directive.line,
+                target,
+                rule: directive.rule.clone(),
+                reason: directive.reason.clone(),
+                status,
+            });
+        }
+    }
+    audits.sort_by_key(|audit| audit.line);
+    Ok(audits)
+}
@@ -0,0 +1,128 @@
+//! Optional `git blame` enrichment for findings.
+//!
+//! When `[report] blame = true`, each issue is annotated with the author and
+//! commit that last touched its line. Blame runs once per file with one `-L`
+//! range per flagged line. Any failure (untracked file, shallow clone, not a
+//! git repository) leaves the fields as `None` rather than failing the run.
+
+use crate::scanner::Issue;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use std::process::Command;
+
+/// Commit hash git reports for lines that are not yet committed.
+const UNCOMMITTED: &str = "0000000000000000000000000000000000000000";
+
+/// Blame information for a single line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    pub author: String,
+    pub commit: String,
+}
+
+/// Fills in `author` and `commit` on each issue from `git blame` run in `repo`.
+pub fn enrich(repo: &Path, issues: &mut [Issue]) {
+    let mut lines_by_file: BTreeMap<String, BTreeSet<usize>> = BTreeMap::new();
+    for issue in issues.iter() {
+        if issue.line_number > 0 {
+            lines_by_file
+                .entry(issue.file_path.clone())
+                .or_default()
+                .insert(issue.line_number);
+        }
+    }
+
+    let blamed: HashMap<String, HashMap<usize, BlameLine>> = lines_by_file
+        .into_iter()
+        .map(|(file, lines)| {
+            let blame = blame_lines(repo, &file, &lines);
+            (file, blame)
+        })
+        .collect();
+
+    for issue in issues.iter_mut() {
+        if let Some(line) = blamed
+            .get(&issue.file_path)
+            .and_then(|lines| lines.get(&issue.line_number))
+        {
+            issue.author = Some(line.author.clone());
+            issue.commit = Some(line.commit.clone());
+        }
+    }
+}
+
+/// Blames the given one-based `lines` of `file` with a single git invocation.
+/// Returns an empty map if git fails.
+pub fn blame_lines(repo: &Path, file: &str, lines: &BTreeSet<usize>) -> HashMap<usize, BlameLine> {
+    if lines.is_empty() {
+        return HashMap::new();
+    }
+    let mut cmd = Command::new("git");
+    cmd.arg("-C").arg(repo).args(["blame", "--porcelain"]);
+    for line in lines {
+        cmd.arg(format!("-L{},{}", line, line));
+    }
+    cmd.args(["--", file]);
+
+    match cmd.output() {
+        Ok(output) if output.status.success() => {
+            parse_porcelain(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            log::debug!(
+                "git blame failed for {}: {}",
+                file,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            HashMap::new()
+        }
+        Err(e) => {
+            log::debug!("failed to run git blame for {}: {}", file, e);
+            HashMap::new()
+        }
+    }
+}
+
+/// Parses `git blame --porcelain` output into a map of final line number to
+/// blame information. Uncommitted lines are omitted.
+pub fn parse_porcelain(output: &str) -> HashMap<usize, BlameLine> {
+    let mut authors: HashMap<String, String> = HashMap::new();
+    let mut pending: Vec<(String, usize)> = Vec::new();
+    let mut current: Option<String> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            current = None;
+            continue;
+        }
+        if let Some(sha) = &current {
+            if let Some(author) = line.strip_prefix("author ") {
+                authors.insert(sha.clone(), author.to_string());
+            }
+            continue;
+        }
+        // Header line: `<sha> <orig-line> <final-line> [<count>]`.
+        let mut parts = line.split_whitespace();
+        if let (Some(sha), Some(_), Some(final_line)) = (parts.next(), parts.next(), parts.next()) {
+            if let Ok(final_line) = final_line.parse() {
+                pending.push((sha.to_string(), final_line));
+                current = Some(sha.to_string());
+            }
+        }
+    }
+
+    pending
+        .into_iter()
+        .filter(|(sha, _)| sha != UNCOMMITTED)
+        .filter_map(|(sha, line)| {
+            let author = authors.get(&sha)?.clone();
+            Some((
+                line,
+                BlameLine {
+                    author,
+                    commit: sha,
+                },
+            ))
+        })
+        .collect()
+}
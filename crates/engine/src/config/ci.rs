@@ -0,0 +1,26 @@
+//! CI-mode configuration semantics.
+//!
+//! `check --ci` must produce reproducible output, so CI mode pins sampling
+//! to temperature 0 and refuses to run against a remote provider without an
+//! explicit model.
+
+use super::{Config, Provider};
+use crate::error::{EngineError, Result};
+
+/// Adjusts `config` for a CI run. Returns `EngineError::Config` when a
+/// remote provider is configured without `[llm].model`.
+pub fn apply_ci_mode(config: &mut Config) -> Result<()> {
+    if config.generation.temperature != Some(0.0) {
+        log::warn!(
+            "CI mode overrides generation temperature to 0.0 (was {:?})",
+            config.generation.temperature
+        );
+    }
+    config.generation.temperature = Some(0.0);
+    if config.llm.provider != Provider::Null && config.llm.model.is_none() {
+        return Err(EngineError::Config(
+            "CI mode requires [llm].model to be set when provider is not 'null'".into(),
+        ));
+    }
+    Ok(())
+}
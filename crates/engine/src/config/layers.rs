@@ -0,0 +1,164 @@
+//! Layered configuration loading.
+//!
+//! Configuration may come from a user-global file, the repository's
+//! `reviewlens.toml`, and environment/CLI overrides. Layers are merged in that
+//! order at the TOML level so that only keys explicitly set in a later layer
+//! replace earlier values: tables are merged recursively while scalars and
+//! arrays are replaced wholesale.
+
+use super::{migrate_table, Config, Deprecation};
+use crate::error::{EngineError, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Where a configuration value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// Built-in defaults.
+    Default,
+    /// The user-global configuration file.
+    User(PathBuf),
+    /// The repository-level configuration file.
+    Repo(PathBuf),
+    /// Environment variables or command-line flags.
+    Override,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::User(path) => write!(f, "user ({})", path.display()),
+            ConfigSource::Repo(path) => write!(f, "repo ({})", path.display()),
+            ConfigSource::Override => write!(f, "env/cli"),
+        }
+    }
+}
+
+/// Records which layer last set each top-level configuration section, and
+/// the deprecated keys found while loading.
+#[derive(Debug, Clone, Default)]
+pub struct Provenance {
+    sections: BTreeMap<String, ConfigSource>,
+    deprecations: Vec<Deprecation>,
+}
+
+impl Provenance {
+    /// Marks `section` as set by `source`.
+    pub fn record(&mut self, section: &str, source: ConfigSource) {
+        self.sections.insert(section.to_string(), source);
+    }
+
+    /// Returns the layer that last set `section`.
+    pub fn source(&self, section: &str) -> ConfigSource {
+        self.sections
+            .get(section)
+            .cloned()
+            .unwrap_or(ConfigSource::Default)
+    }
+
+    /// Records deprecated keys that were migrated, keeping one entry per key
+    /// when several layers use it.
+    pub fn record_deprecations(&mut self, deprecations: Vec<Deprecation>) {
+        for deprecation in deprecations {
+            if !self.deprecations.contains(&deprecation) {
+                self.deprecations.push(deprecation);
+            }
+        }
+    }
+
+    /// Returns the deprecated keys found while loading, in the order found.
+    pub fn deprecations(&self) -> &[Deprecation] {
+        &self.deprecations
+    }
+}
+
+/// Recursively merges `overlay` into `base`. Nested tables are merged key by
+/// key; any other value in `overlay` (including arrays) replaces the value in
+/// `base`.
+pub fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(incoming)) => {
+                merge_tables(existing, incoming);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn read_table(path: &Path) -> Result<toml::Table> {
+    let content = std::fs::read_to_string(path).map_err(|e| EngineError::io(path, e))?;
+    content
+        .parse::<toml::Table>()
+        .map_err(|e| EngineError::Config(format!("{}: {}", path.display(), e)))
+}
+
+impl Config {
+    /// Applies the keys explicitly present in `overlay` on top of this
+    /// configuration. Keys absent from `overlay` keep their current values.
+    pub fn merge(self, overlay: &toml::Table) -> Result<Self> {
+        let mut base = match toml::Value::try_from(&self) {
+            Ok(toml::Value::Table(table)) => table,
+            Ok(_) => toml::Table::new(),
+            Err(e) => return Err(EngineError::Config(e.to_string())),
+        };
+        merge_tables(&mut base, overlay.clone());
+        let mut merged: Config = toml::Value::Table(base)
+            .try_into()
+            .map_err(|e: toml::de::Error| EngineError::Config(e.to_string()))?;
+
+        // Secrets and deprecated keys are never serialized, so carry them over.
+        if merged.llm.api_key.is_none() {
+            merged.llm.api_key = self.llm.api_key;
+        }
+        #[allow(deprecated)]
+        if merged.index_path.is_none() {
+            merged.index_path = self.index_path;
+        }
+        Ok(merged)
+    }
+
+    /// Loads the user-global and repository configuration files, merging the
+    /// repository file over the user file. Missing files are skipped; if
+    /// neither exists the built-in defaults are returned. Deprecated keys are
+    /// migrated and listed in the returned `Provenance`.
+    pub fn load_layered(user: Option<&Path>, repo: &Path) -> Result<(Self, Provenance)> {
+        let mut provenance = Provenance::default();
+        let mut layers = Vec::new();
+        if let Some(user) = user.filter(|p| p.exists()) {
+            layers.push((read_table(user)?, ConfigSource::User(user.to_path_buf())));
+        }
+        if repo.exists() {
+            layers.push((read_table(repo)?, ConfigSource::Repo(repo.to_path_buf())));
+        }
+        if layers.is_empty() {
+            return Ok((Config::default(), provenance));
+        }
+
+        let mut merged = toml::Table::new();
+        for (mut table, source) in layers {
+            provenance.record_deprecations(migrate_table(&mut table));
+            for section in table.keys() {
+                provenance.record(section, source.clone());
+            }
+            merge_tables(&mut merged, table);
+        }
+        let mut config: Config = toml::Value::Table(merged)
+            .try_into()
+            .map_err(|e: toml::de::Error| EngineError::Config(e.to_string()))?;
+        provenance.record_deprecations(config.migrate());
+        Ok((config, provenance))
+    }
+
+    /// Returns the names of the top-level sections of the configuration.
+    pub fn sections(&self) -> Vec<String> {
+        match toml::Value::try_from(self) {
+            Ok(toml::Value::Table(table)) => table.keys().cloned().collect(),
+            _ => Vec::new(),
+        }
+    }
+}
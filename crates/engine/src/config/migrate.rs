@@ -0,0 +1,198 @@
+//! Migration of deprecated configuration keys.
+//!
+//! Older files may still use the top-level `index_path`, the snake_case
+//! and pre-`-go` rule names, or `convention-deviation` for `conventions`.
+//! Each loaded layer is migrated before it is merged, so the rest of the
+//! engine only ever sees current keys, and every key that was moved is
+//! reported as a `Deprecation`. `migrate_document` applies the same moves
+//! to the file itself, keeping its comments and formatting, for
+//! `reviewlens config migrate --write`.
+
+use super::{Config, IndexConfig};
+use crate::error::{EngineError, Result};
+use std::fmt;
+
+/// Deprecated dotted key paths and the paths that replace them.
+const MIGRATIONS: &[(&str, &str)] = &[
+    ("index_path", "index.path"),
+    ("index-path", "index.path"),
+    ("rules.sql-injection", "rules.sql-injection-go"),
+    ("rules.sql_injection_go", "rules.sql-injection-go"),
+    ("rules.http-timeouts", "rules.http-timeouts-go"),
+    ("rules.http_timeouts_go", "rules.http-timeouts-go"),
+    ("rules.network_hygiene", "rules.network-hygiene"),
+    ("rules.removed_controls", "rules.removed-controls"),
+    ("rules.convention-deviation", "rules.conventions"),
+];
+
+/// A deprecated key found in a configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deprecation {
+    /// The deprecated key as written, e.g. `index_path`.
+    pub key: String,
+    /// Where the setting lives now, e.g. `[index] path`.
+    pub replacement: String,
+    /// Whether the value was dropped because the new key is also set.
+    pub ignored: bool,
+}
+
+impl fmt::Display for Deprecation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is deprecated, use {}", self.key, self.replacement)?;
+        if self.ignored {
+            write!(f, " (ignored because it is already set)")?;
+        }
+        Ok(())
+    }
+}
+
+impl Deprecation {
+    fn new(old: &str, new: &str, is_table: bool, ignored: bool) -> Self {
+        let replacement = match new.rsplit_once('.') {
+            _ if is_table => format!("[{}]", new),
+            Some((table, key)) => format!("[{}] {}", table, key),
+            None => new.to_string(),
+        };
+        Self {
+            key: old.to_string(),
+            replacement,
+            ignored,
+        }
+    }
+}
+
+/// Moves deprecated keys in `table` to their new locations. A value already
+/// present at the new location wins over the deprecated one.
+pub fn migrate_table(table: &mut toml::Table) -> Vec<Deprecation> {
+    let mut deprecations = Vec::new();
+    for (old, new) in MIGRATIONS {
+        let Some(value) = take(table, old) else {
+            continue;
+        };
+        let is_table = value.is_table();
+        let (parents, leaf) = split(new);
+        // A new parent that is not a table fails deserialization anyway.
+        let Some(target) = parents.into_iter().try_fold(&mut *table, |target, parent| {
+            target
+                .entry(parent.to_string())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                .as_table_mut()
+        }) else {
+            continue;
+        };
+        let ignored = target.contains_key(leaf);
+        if !ignored {
+            target.insert(leaf.to_string(), value);
+        }
+        deprecations.push(Deprecation::new(old, new, is_table, ignored));
+    }
+    deprecations
+}
+
+fn take(table: &mut toml::Table, path: &str) -> Option<toml::Value> {
+    let (parents, leaf) = split(path);
+    let mut current = table;
+    for parent in parents {
+        current = current.get_mut(parent)?.as_table_mut()?;
+    }
+    current.remove(leaf)
+}
+
+fn split(path: &str) -> (Vec<&str>, &str) {
+    let mut parts: Vec<&str> = path.split('.').collect();
+    let leaf = parts.pop().unwrap_or_default();
+    (parts, leaf)
+}
+
+/// Rewrites deprecated keys in the TOML document `content`, preserving
+/// comments and formatting elsewhere. Returns the new document and the keys
+/// that were moved.
+pub fn migrate_document(content: &str) -> Result<(String, Vec<Deprecation>)> {
+    let mut doc: toml_edit::DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| EngineError::Config(e.to_string()))?;
+    let mut deprecations = Vec::new();
+    for (old, new) in MIGRATIONS {
+        let Some((key, item)) = take_item(doc.as_table_mut(), old) else {
+            continue;
+        };
+        let is_table = item.is_table_like();
+        let (parents, leaf) = split(new);
+        // Tables created for the new key go at the end of the file.
+        let mut created = toml_edit::Table::new();
+        created.set_position(last_position(doc.as_table()) + 1);
+        let Some(target) = parents
+            .into_iter()
+            .try_fold(doc.as_table_mut(), |target, parent| {
+                target
+                    .entry(parent)
+                    .or_insert_with(|| toml_edit::Item::Table(created.clone()))
+                    .as_table_mut()
+            })
+        else {
+            continue;
+        };
+        let ignored = target.contains_key(leaf);
+        if !ignored {
+            // Keep the comments attached to the old key.
+            let mut renamed = toml_edit::Key::new(leaf);
+            *renamed.leaf_decor_mut() = key.leaf_decor().clone();
+            target.insert_formatted(&renamed, item);
+        }
+        deprecations.push(Deprecation::new(old, new, is_table, ignored));
+    }
+    // Moving the first key away leaves the blank line that followed it at
+    // the top of the file.
+    let mut migrated = doc.to_string();
+    if !content.starts_with('\n') {
+        migrated = migrated.trim_start_matches('\n').to_string();
+    }
+    Ok((migrated, deprecations))
+}
+
+fn take_item(
+    table: &mut toml_edit::Table,
+    path: &str,
+) -> Option<(toml_edit::Key, toml_edit::Item)> {
+    let (parents, leaf) = split(path);
+    let mut current = table;
+    for parent in parents {
+        current = current.get_mut(parent)?.as_table_mut()?;
+    }
+    current.remove_entry(leaf)
+}
+
+/// Returns the highest position of any table header in `table`.
+fn last_position(table: &toml_edit::Table) -> usize {
+    table
+        .iter()
+        .filter_map(|(_, item)| item.as_table())
+        .map(|inner| inner.position().unwrap_or(0).max(last_position(inner)))
+        .max()
+        .unwrap_or(0)
+}
+
+impl Config {
+    /// Moves settings held in deprecated fields to their current locations,
+    /// for configurations built in code rather than loaded from a file.
+    pub fn migrate(&mut self) -> Vec<Deprecation> {
+        let mut deprecations = Vec::new();
+        #[allow(deprecated)]
+        if let Some(path) = self.index_path.take() {
+            // `Default` sets `index`, so only an explicit, different path
+            // there counts as already set.
+            let ignored = self
+                .index
+                .as_ref()
+                .is_some_and(|index| *index != IndexConfig::default());
+            if !ignored {
+                self.index = Some(IndexConfig {
+                    path,
+                    ..self.index.clone().unwrap_or_default()
+                });
+            }
+            deprecations.push(Deprecation::new("index-path", "index.path", false, ignored));
+        }
+        deprecations
+    }
+}
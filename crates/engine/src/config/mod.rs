@@ -0,0 +1,1412 @@
+//! Configuration structures for the code review agent.
+//!
+//! This module defines the structs that can be deserialized from the
+//! `reviewlens.toml` configuration file.
+
+use crate::error::{EngineError, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+mod ci;
+mod layers;
+mod migrate;
+mod overrides;
+mod policy;
+mod validate;
+pub use ci::apply_ci_mode;
+pub use layers::{merge_tables, ConfigSource, Provenance};
+pub use migrate::{migrate_document, migrate_table, Deprecation};
+pub use overrides::ConfigOverride;
+pub use policy::{PolicyConfig, PolicyViolation};
+pub use validate::{unknown_keys, ValidationProblem};
+
+/// Default path for the RAG index file.
+pub const DEFAULT_INDEX_PATH: &str = ".reviewlens/index/index.json.zst";
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct IndexConfig {
+    pub path: String,
+    /// Days the index may lag behind the reviewed changes before it is
+    /// reported as stale.
+    #[serde(default = "default_index_max_age_days")]
+    pub max_age_days: u64,
+}
+
+fn default_index_max_age_days() -> u64 {
+    14
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            path: DEFAULT_INDEX_PATH.to_string(),
+            max_age_days: default_index_max_age_days(),
+        }
+    }
+}
+
+/// Embedding backend used for the RAG index.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum EmbedderKind {
+    /// Hashed token bigrams; works offline.
+    #[default]
+    Ngram,
+    /// OpenAI embeddings API, using the `[llm]` api key and base URL.
+    Openai,
+}
+
+/// `[rag]` section: how documents and queries are embedded.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct RagConfig {
+    /// Load the vector index and retrieve context for LLM prompts. When
+    /// `false`, reviews neither read the index nor embed queries.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub embedder: EmbedderKind,
+    /// Embedding model for remote embedders. Defaults to
+    /// `text-embedding-3-small` for OpenAI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Source lines on each side of a finding used as its retrieval query.
+    #[serde(default = "default_query_context_lines")]
+    pub query_context_lines: usize,
+    /// Qdrant connection for `reviewlens index --store qdrant`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qdrant: Option<QdrantConfig>,
+}
+
+/// `[rag.qdrant]` section: a Qdrant collection holding the index.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct QdrantConfig {
+    /// Base URL of the Qdrant HTTP API, e.g. `http://localhost:6333`.
+    pub url: String,
+    /// Collection storing one point per indexed file.
+    pub collection: String,
+    #[serde(skip_serializing)]
+    pub api_key: Option<String>, // Sent as the `api-key` header; never printed
+}
+
+fn default_query_context_lines() -> usize {
+    10
+}
+
+impl Default for RagConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            embedder: EmbedderKind::default(),
+            model: None,
+            query_context_lines: default_query_context_lines(),
+            qdrant: None,
+        }
+    }
+}
+
+// As per PRD section 9
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct Config {
+    #[serde(default)]
+    pub llm: LlmConfig,
+    #[serde(default)]
+    pub budget: BudgetConfig,
+    /// Caps on how much of a diff is reviewed.
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    #[serde(default)]
+    pub generation: GenerationConfig,
+    #[serde(default)]
+    pub privacy: PrivacyConfig,
+    #[serde(default)]
+    pub paths: PathsConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub integrations: IntegrationsConfig,
+    /// Configuration for report generation.
+    #[serde(default)]
+    pub report: ReportConfig,
+    /// Optional path to a pre-built vector index used for RAG.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub index: Option<IndexConfig>,
+    /// Embedding settings for the RAG index.
+    #[serde(default)]
+    pub rag: RagConfig,
+    #[deprecated(note = "use [index] table instead")]
+    #[serde(skip_serializing, default)]
+    pub index_path: Option<String>,
+    #[serde(default)]
+    pub rules: RulesConfig,
+    /// Settings for the scanning engine itself.
+    #[serde(default)]
+    pub engine: EngineConfig,
+    /// Scanners provided outside the engine.
+    #[serde(default)]
+    pub scanners: ScannersConfig,
+    #[serde(default = "default_fail_on")]
+    pub fail_on: FailOn,
+    /// Findings below this confidence never fail the run, whatever their
+    /// severity.
+    #[serde(default = "default_fail_on_confidence")]
+    pub fail_on_confidence: Confidence,
+    /// Per-directory `[[overrides]]`, applied in order to matching files.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<ConfigOverride>,
+    /// Requirements the configuration and the findings must meet.
+    #[serde(default, skip_serializing_if = "PolicyConfig::is_empty")]
+    pub policy: PolicyConfig,
+}
+
+// As per PRD: `null | openai | anthropic | deepseek`
+// Default provider is "null"
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, ValueEnum)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum Provider {
+    #[serde(rename = "null")]
+    #[default]
+    Null,
+    Openai,
+    Anthropic,
+    Deepseek,
+}
+
+impl Provider {
+    /// Returns the kebab-case name of the provider.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Provider::Null => "null",
+            Provider::Openai => "openai",
+            Provider::Anthropic => "anthropic",
+            Provider::Deepseek => "deepseek",
+        }
+    }
+}
+
+// As per PRD: `[llm]` section
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct LlmConfig {
+    #[serde(default)]
+    pub provider: Provider,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>, // Model is optional, especially for null provider
+    #[serde(skip_serializing)]
+    pub api_key: Option<String>, // Keep for actual implementations, but don't print it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>, // Keep for actual implementations
+    /// Cache responses on disk, keyed by provider, model, temperature and prompt.
+    #[serde(default)]
+    pub cache: bool,
+    /// Location of the response cache. Defaults to `.reviewlens/cache/llm.json`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_path: Option<String>,
+    /// Seconds before a cached response expires.
+    #[serde(default = "default_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+    /// Maximum number of cached responses; the oldest are evicted first.
+    #[serde(default = "default_cache_max_entries")]
+    pub cache_max_entries: usize,
+    /// Seconds before an LLM request is abandoned.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Seconds allowed for connecting to the LLM provider.
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// Fail the review when the LLM errors instead of falling back to the
+    /// scanner-only summary.
+    #[serde(default)]
+    pub required: bool,
+    /// Estimated prompt size above which findings are summarized in several
+    /// LLM calls. `budget.tokens.max-per-run` also caps each prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_prompt_tokens: Option<u32>,
+    /// Ask the model for JSON comments and patches per finding instead of a
+    /// free-form summary.
+    #[serde(default)]
+    pub structured_output: bool,
+    /// Directory where each LLM request and response is written, redacted,
+    /// for debugging prompts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debug_dir: Option<String>,
+    /// Proxy URL for provider requests, e.g. `http://proxy.corp:3128`.
+    /// Without it, `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY` apply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// PEM file with extra root certificates to trust, such as a private CA.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle_path: Option<String>,
+}
+
+fn default_timeout_secs() -> u64 {
+    60
+}
+
+fn default_connect_timeout_secs() -> u64 {
+    10
+}
+
+/// `[engine]` section: how files are scanned.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct EngineConfig {
+    /// File caching scanner findings by file content, so unchanged files are
+    /// not rescanned on the next run. Disabled when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scan_cache_path: Option<String>,
+    /// Maximum number of cached scan results; the least recently used are
+    /// evicted first.
+    #[serde(default = "default_scan_cache_max_entries")]
+    pub scan_cache_max_entries: usize,
+}
+
+fn default_scan_cache_max_entries() -> usize {
+    5000
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            scan_cache_path: None,
+            scan_cache_max_entries: default_scan_cache_max_entries(),
+        }
+    }
+}
+
+/// Default location of the LLM response cache.
+pub const DEFAULT_LLM_CACHE_PATH: &str = ".reviewlens/cache/llm.json";
+
+fn default_cache_ttl_secs() -> u64 {
+    7 * 24 * 60 * 60
+}
+
+fn default_cache_max_entries() -> usize {
+    500
+}
+
+impl LlmConfig {
+    /// Returns the configured cache path or the default location.
+    pub fn cache_path(&self) -> &str {
+        self.cache_path.as_deref().unwrap_or(DEFAULT_LLM_CACHE_PATH)
+    }
+}
+
+// Default LLM config
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            provider: Provider::Null,
+            model: None,
+            api_key: None,
+            base_url: None,
+            cache: false,
+            cache_path: None,
+            cache_ttl_secs: default_cache_ttl_secs(),
+            cache_max_entries: default_cache_max_entries(),
+            timeout_secs: default_timeout_secs(),
+            connect_timeout_secs: default_connect_timeout_secs(),
+            required: false,
+            max_prompt_tokens: None,
+            structured_output: false,
+            debug_dir: None,
+            proxy: None,
+            ca_bundle_path: None,
+        }
+    }
+}
+
+// As per PRD: `[budget.tokens]` section
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct TokenBudgetConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_per_run: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct BudgetConfig {
+    #[serde(default)]
+    pub tokens: TokenBudgetConfig,
+}
+
+/// `[limits]` section: caps that keep very large diffs, such as vendored
+/// dependency updates, from exhausting memory. Counts are changed lines
+/// (additions plus deletions); `0` disables a limit.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct LimitsConfig {
+    /// Maximum number of files reviewed.
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+    /// Files with more changed lines than this are skipped.
+    #[serde(default = "default_max_lines_per_file")]
+    pub max_lines_per_file: usize,
+    /// Maximum changed lines reviewed across all files.
+    #[serde(default = "default_max_total_lines")]
+    pub max_total_lines: usize,
+}
+
+impl LimitsConfig {
+    /// Limits that never skip a file, used by `--no-limits`.
+    pub fn unlimited() -> Self {
+        Self {
+            max_files: 0,
+            max_lines_per_file: 0,
+            max_total_lines: 0,
+        }
+    }
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_files: default_max_files(),
+            max_lines_per_file: default_max_lines_per_file(),
+            max_total_lines: default_max_total_lines(),
+        }
+    }
+}
+
+fn default_max_files() -> usize {
+    1_000
+}
+
+fn default_max_lines_per_file() -> usize {
+    20_000
+}
+
+fn default_max_total_lines() -> usize {
+    200_000
+}
+
+// As per PRD: `[generation]` section
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Upper bound on tokens generated per response. Required by Anthropic.
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: u32,
+    /// Nucleus sampling cutoff, between 0 and 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Sampling seed for repeatable responses. Only OpenAI supports it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Sequences that end a response when generated.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+}
+
+impl Default for GenerationConfig {
+    fn default() -> Self {
+        Self {
+            temperature: None,
+            max_tokens: default_max_tokens(),
+            top_p: None,
+            seed: None,
+            stop: Vec::new(),
+        }
+    }
+}
+
+fn default_max_tokens() -> u32 {
+    1024
+}
+
+// As per PRD: `[privacy.redaction]` section
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct RedactionConfig {
+    pub enabled: bool,
+    /// Plain regex patterns; the whole match (or capture group 1) is redacted.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// Structured `[[privacy.redaction.rules]]` entries, applied after `patterns`.
+    #[serde(default)]
+    pub rules: Vec<RedactionRule>,
+}
+
+/// A single structured redaction rule.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct RedactionRule {
+    /// Regex to match. If it has a capture group, only group 1 is replaced.
+    pub pattern: String,
+    /// Replacement text. Defaults to `[REDACTED]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replace: Option<String>,
+    /// Keep the first and last two characters of the match, e.g. `sk…a9`.
+    #[serde(default)]
+    pub partial: bool,
+}
+
+impl RedactionRule {
+    /// Creates a rule that fully replaces matches of `pattern`.
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self {
+            pattern: pattern.into(),
+            replace: None,
+            partial: false,
+        }
+    }
+}
+
+impl RedactionConfig {
+    /// All rules to apply, with plain `patterns` first.
+    pub fn all_rules(&self) -> Vec<RedactionRule> {
+        self.patterns
+            .iter()
+            .map(RedactionRule::new)
+            .chain(self.rules.iter().cloned())
+            .collect()
+    }
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            patterns: vec![
+                "(?i)api[_-]?key".to_string(),
+                "aws_secret_access_key".to_string(),
+                "token".to_string(),
+            ],
+            rules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct PrivacyConfig {
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+}
+
+// As per PRD: `[paths]` section. Renaming ProjectConfig.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct PathsConfig {
+    /// Paths to include in the analysis. Globs are supported.
+    #[serde(default = "default_include")]
+    pub allow: Vec<String>,
+    /// Paths to exclude from the analysis. Globs are supported.
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Documentation and lockfile globs. When every changed file matches,
+    /// the scanners and the LLM are skipped. An empty list disables this.
+    #[serde(default = "default_docs_globs")]
+    pub docs_globs: Vec<String>,
+}
+
+impl Default for PathsConfig {
+    fn default() -> Self {
+        Self {
+            allow: default_include(),
+            deny: vec![],
+            docs_globs: default_docs_globs(),
+        }
+    }
+}
+
+fn default_include() -> Vec<String> {
+    vec!["**/*".to_string()]
+}
+
+fn default_docs_globs() -> Vec<String> {
+    ["**/*.md", "**/*.txt", "**/LICENSE*", "**/*.lock"]
+        .map(String::from)
+        .to_vec()
+}
+
+// Telemetry configuration
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct TelemetryConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// OTLP/HTTP collector to export spans to, e.g. `http://localhost:4318`.
+    /// Requires the `otel` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otlp_endpoint: Option<String>,
+}
+
+/// Services notified when a review completes.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct IntegrationsConfig {
+    #[serde(default)]
+    pub slack: SlackConfig,
+}
+
+/// The `[integrations.slack]` table, used by `check --publish slack`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct SlackConfig {
+    /// Incoming webhook the review is posted to. `SLACK_WEBHOOK_URL`
+    /// overrides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+}
+
+// As per PRD: `[report.hotspot_weights]` section
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct HotspotWeights {
+    #[serde(default = "default_severity_weight")]
+    pub severity: u32,
+    #[serde(default = "default_churn_weight")]
+    pub churn: u32,
+}
+
+impl Default for HotspotWeights {
+    fn default() -> Self {
+        Self {
+            severity: default_severity_weight(),
+            churn: default_churn_weight(),
+        }
+    }
+}
+
+fn default_report_language() -> String {
+    "en".to_string()
+}
+
+fn default_escalate_by() -> u8 {
+    1
+}
+
+fn default_severity_weight() -> u32 {
+    3
+}
+
+fn default_churn_weight() -> u32 {
+    1
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ReportConfig {
+    #[serde(default)]
+    pub hotspot_weights: HotspotWeights,
+    /// Whether to include a Mermaid diagram of interactions between changed files.
+    #[serde(default = "default_diagram")]
+    pub diagram: bool,
+    /// Maximum number of edges rendered in the diagram.
+    #[serde(default = "default_diagram_max_edges")]
+    pub diagram_max_edges: usize,
+    /// Whether to annotate findings with the author and commit from `git blame`.
+    #[serde(default)]
+    pub blame: bool,
+    /// URL template for linking findings, e.g.
+    /// `https://github.com/org/repo/blob/{commit}/{path}#L{line}`. Detected
+    /// from the `origin` remote when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_template: Option<String>,
+    /// How much of the configuration is embedded in reports.
+    #[serde(default)]
+    pub include_config: IncludeConfig,
+    /// Whether reports list the findings silenced by `reviewlens:ignore`.
+    #[serde(default = "default_show_suppressed")]
+    pub show_suppressed: bool,
+    /// Lines of code shown above and below each finding; `0` disables
+    /// snippets.
+    #[serde(default = "default_snippet_context")]
+    pub snippet_context: usize,
+    /// List each rule's suggested fix once, in a section grouping findings
+    /// by rule, instead of on every row of the findings table.
+    #[serde(default = "default_group_fixes")]
+    pub group_fixes: bool,
+    /// Raise the severity of findings in the hotspot files before `fail-on`
+    /// is evaluated and reports are rendered.
+    #[serde(default)]
+    pub escalate_hotspots: bool,
+    /// How many levels `escalate-hotspots` raises a severity by, up to
+    /// critical.
+    #[serde(default = "default_escalate_by")]
+    pub escalate_by: u8,
+    /// Language of the LLM summary and comments and of the report's
+    /// headings, as a language tag such as `en` or `ja`.
+    #[serde(default = "default_report_language")]
+    pub language: String,
+    /// JSON Lines file where `check` records each run's findings, so the
+    /// next report on the same branch can show what changed since. Off when
+    /// unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_path: Option<String>,
+    /// How many runs the history keeps, across all branches.
+    #[serde(default = "default_history_keep")]
+    pub history_keep: usize,
+    /// Findings below this confidence are dropped from the report.
+    #[serde(default = "default_min_confidence")]
+    pub min_confidence: Confidence,
+    /// Whether reports list the RAG context retrieved for each finding,
+    /// as given to the LLM.
+    #[serde(default)]
+    pub include_context: bool,
+    /// Characters of each retrieved document shown under `include-context`.
+    #[serde(default = "default_context_excerpt_chars")]
+    pub context_excerpt_chars: usize,
+}
+
+/// How much of the configuration a report embeds.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum IncludeConfig {
+    /// No configuration.
+    None,
+    /// Provider, model, fail level and the enabled rules with their
+    /// severities.
+    #[default]
+    Summary,
+    /// The whole configuration, redacted and without credentials.
+    Full,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            hotspot_weights: HotspotWeights::default(),
+            diagram: default_diagram(),
+            diagram_max_edges: default_diagram_max_edges(),
+            blame: false,
+            link_template: None,
+            include_config: IncludeConfig::default(),
+            show_suppressed: default_show_suppressed(),
+            snippet_context: default_snippet_context(),
+            group_fixes: default_group_fixes(),
+            escalate_hotspots: false,
+            escalate_by: default_escalate_by(),
+            language: default_report_language(),
+            history_path: None,
+            history_keep: default_history_keep(),
+            min_confidence: default_min_confidence(),
+            include_context: false,
+            context_excerpt_chars: default_context_excerpt_chars(),
+        }
+    }
+}
+
+fn default_diagram() -> bool {
+    true
+}
+
+fn default_history_keep() -> usize {
+    100
+}
+
+fn default_min_confidence() -> Confidence {
+    Confidence::Low
+}
+
+fn default_context_excerpt_chars() -> usize {
+    500
+}
+
+fn default_show_suppressed() -> bool {
+    true
+}
+
+fn default_snippet_context() -> usize {
+    2
+}
+
+fn default_group_fixes() -> bool {
+    true
+}
+
+fn default_diagram_max_edges() -> usize {
+    20
+}
+
+// As per PRD: `[rules]` section with severity
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, ValueEnum)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum Severity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl Severity {
+    /// Returns the kebab-case name of the severity.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Critical => "critical",
+            Severity::High => "high",
+            Severity::Medium => "medium",
+            Severity::Low => "low",
+        }
+    }
+
+    fn as_u8(&self) -> u8 {
+        match self {
+            Severity::Critical => 4,
+            Severity::High => 3,
+            Severity::Medium => 2,
+            Severity::Low => 1,
+        }
+    }
+
+    /// Returns the severity `levels` steps above this one, capped at
+    /// `Critical`.
+    pub fn raised(&self, levels: u8) -> Severity {
+        match self.as_u8().saturating_add(levels) {
+            1 => Severity::Low,
+            2 => Severity::Medium,
+            3 => Severity::High,
+            _ => Severity::Critical,
+        }
+    }
+}
+
+impl PartialOrd for Severity {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Severity {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_u8().cmp(&other.as_u8())
+    }
+}
+
+/// How likely a finding is to be a real problem rather than a false
+/// positive of the heuristic that reported it.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum Confidence {
+    High,
+    #[default]
+    Medium,
+    Low,
+}
+
+impl Confidence {
+    /// Returns the kebab-case name of the confidence level.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Confidence::High => "high",
+            Confidence::Medium => "medium",
+            Confidence::Low => "low",
+        }
+    }
+
+    fn as_u8(&self) -> u8 {
+        match self {
+            Confidence::High => 3,
+            Confidence::Medium => 2,
+            Confidence::Low => 1,
+        }
+    }
+}
+
+impl PartialOrd for Confidence {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Confidence {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_u8().cmp(&other.as_u8())
+    }
+}
+
+/// Threshold at which `check` exits with a non-zero status.
+///
+/// `never` always exits successfully; the remaining variants fail when any
+/// reported issue is at or above the corresponding severity.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, ValueEnum)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum FailOn {
+    Never,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl FailOn {
+    /// Returns the minimum severity that triggers a failure, or `None` for `never`.
+    pub fn threshold(&self) -> Option<Severity> {
+        match self {
+            FailOn::Never => None,
+            FailOn::Low => Some(Severity::Low),
+            FailOn::Medium => Some(Severity::Medium),
+            FailOn::High => Some(Severity::High),
+            FailOn::Critical => Some(Severity::Critical),
+        }
+    }
+
+    /// Returns the kebab-case name of the threshold.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FailOn::Never => "never",
+            FailOn::Low => "low",
+            FailOn::Medium => "medium",
+            FailOn::High => "high",
+            FailOn::Critical => "critical",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct RuleConfig {
+    pub enabled: bool,
+    pub severity: Severity,
+}
+
+// Sensible defaults for a rule. Let's say enabled by default with medium severity.
+impl Default for RuleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            severity: Severity::Medium,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct RulesConfig {
+    #[serde(default)]
+    pub secrets: SecretsRuleConfig,
+    #[serde(
+        default = "default_sql_injection_go_rule",
+        deserialize_with = "sql_injection_go_rule"
+    )]
+    pub sql_injection_go: RuleConfig,
+    #[serde(
+        default = "default_http_timeouts_go_rule",
+        deserialize_with = "http_timeouts_go_rule"
+    )]
+    pub http_timeouts_go: RuleConfig,
+    #[serde(default)]
+    pub conventions: ConventionsRuleConfig,
+    #[serde(default)]
+    pub network_hygiene: NetworkHygieneRuleConfig,
+    #[serde(default)]
+    pub removed_controls: RemovedControlsRuleConfig,
+    #[serde(default)]
+    pub artifacts: ArtifactsRuleConfig,
+    #[serde(default)]
+    pub todo: TodoRuleConfig,
+    #[serde(default)]
+    pub complexity: ComplexityRuleConfig,
+}
+
+/// Settings for the `secrets` rule.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct SecretsRuleConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_secrets_severity")]
+    pub severity: Severity,
+    /// Severity overrides keyed by pattern id, e.g. `jwt = "medium"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub patterns: BTreeMap<String, Severity>,
+}
+
+impl Default for SecretsRuleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            severity: default_secrets_severity(),
+            patterns: BTreeMap::new(),
+        }
+    }
+}
+
+/// Settings for the `conventions` rule.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ConventionsRuleConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_conventions_severity")]
+    pub severity: Severity,
+    /// How indexed files establish a convention.
+    #[serde(default)]
+    pub mode: ConventionsMode,
+    /// Flag console output (`println!`, `fmt.Println`, `print(`,
+    /// `console.log`) where the repository logs through a logger.
+    #[serde(default = "default_true")]
+    pub logging: bool,
+    /// Flag `unwrap`/`expect` in Rust, `panic(` in Go and bare `except:` in
+    /// Python where the repository propagates errors.
+    #[serde(default = "default_true")]
+    pub error_handling: bool,
+}
+
+impl Default for ConventionsRuleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            severity: default_conventions_severity(),
+            mode: ConventionsMode::default(),
+            logging: true,
+            error_handling: true,
+        }
+    }
+}
+
+/// How the `conventions` rule decides what the repository prefers.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum ConventionsMode {
+    /// The preferred form is used at least as often as the discouraged one
+    /// across indexed files of the language.
+    #[default]
+    Majority,
+    /// No indexed file of the language uses the discouraged form.
+    Strict,
+}
+
+/// Settings for the `network-hygiene` rule.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct NetworkHygieneRuleConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_network_hygiene_severity")]
+    pub severity: Severity,
+    /// Hosts and IP addresses that may be hardcoded.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+    /// Paths (globs) where hardcoded addresses are expected, such as config files.
+    #[serde(default = "default_network_hygiene_exempt_paths")]
+    pub exempt_paths: Vec<String>,
+}
+
+impl Default for NetworkHygieneRuleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            severity: default_network_hygiene_severity(),
+            allowed_hosts: vec![],
+            exempt_paths: default_network_hygiene_exempt_paths(),
+        }
+    }
+}
+
+/// Settings for the `removed-controls` rule.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct RemovedControlsRuleConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_removed_controls_severity")]
+    pub severity: Severity,
+    /// Regexes matching security-relevant lines whose deletion is flagged.
+    #[serde(default = "default_removed_controls_patterns")]
+    pub patterns: Vec<String>,
+}
+
+impl Default for RemovedControlsRuleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            severity: default_removed_controls_severity(),
+            patterns: default_removed_controls_patterns(),
+        }
+    }
+}
+
+/// Settings for the `artifacts` rule.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ArtifactsRuleConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_artifacts_severity")]
+    pub severity: Severity,
+    /// Added files larger than this many kilobytes are flagged; `0` turns
+    /// the size check off.
+    #[serde(default = "default_max_added_file_kb")]
+    pub max_added_file_kb: u64,
+    /// File name suffixes of minified bundles, such as `.min.js`.
+    #[serde(default = "default_minified_suffixes")]
+    pub minified_suffixes: Vec<String>,
+    /// Extensions of compiled artifacts, without the leading dot.
+    #[serde(default = "default_compiled_extensions")]
+    pub compiled_extensions: Vec<String>,
+    /// Directories holding third-party code, matched against every
+    /// component of the path.
+    #[serde(default = "default_vendored_dirs")]
+    pub vendored_dirs: Vec<String>,
+}
+
+impl Default for ArtifactsRuleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            severity: default_artifacts_severity(),
+            max_added_file_kb: default_max_added_file_kb(),
+            minified_suffixes: default_minified_suffixes(),
+            compiled_extensions: default_compiled_extensions(),
+            vendored_dirs: default_vendored_dirs(),
+        }
+    }
+}
+
+fn default_artifacts_severity() -> Severity {
+    Severity::Medium
+}
+
+fn default_max_added_file_kb() -> u64 {
+    1024
+}
+
+fn default_minified_suffixes() -> Vec<String> {
+    [".min.js", ".min.css"].map(String::from).to_vec()
+}
+
+fn default_compiled_extensions() -> Vec<String> {
+    [
+        "pyc", "pyo", "class", "o", "obj", "so", "dylib", "dll", "exe",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+fn default_vendored_dirs() -> Vec<String> {
+    ["node_modules", "vendor"].map(String::from).to_vec()
+}
+
+/// Settings for the `todo` rule.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct TodoRuleConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_todo_severity")]
+    pub severity: Severity,
+    /// Flag only markers without a ticket reference such as `TODO(JIRA-123)`,
+    /// `TODO #42` or a URL.
+    #[serde(default = "default_true")]
+    pub require_issue_link: bool,
+}
+
+impl Default for TodoRuleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            severity: default_todo_severity(),
+            require_issue_link: true,
+        }
+    }
+}
+
+fn default_todo_severity() -> Severity {
+    Severity::Low
+}
+
+/// Settings for the `complexity` rule.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ComplexityRuleConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_complexity_severity")]
+    pub severity: Severity,
+    /// Functions spanning more lines than this, declaration included, are
+    /// flagged.
+    #[serde(default = "default_max_function_lines")]
+    pub max_function_lines: usize,
+    /// Functions with blocks nested deeper than this inside their body are
+    /// flagged.
+    #[serde(default = "default_max_nesting")]
+    pub max_nesting: usize,
+}
+
+impl Default for ComplexityRuleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            severity: default_complexity_severity(),
+            max_function_lines: default_max_function_lines(),
+            max_nesting: default_max_nesting(),
+        }
+    }
+}
+
+fn default_complexity_severity() -> Severity {
+    Severity::Low
+}
+
+fn default_max_function_lines() -> usize {
+    80
+}
+
+fn default_max_nesting() -> usize {
+    5
+}
+
+fn default_removed_controls_severity() -> Severity {
+    Severity::High
+}
+
+fn default_removed_controls_patterns() -> Vec<String> {
+    [
+        r"(?i)\bauthori[sz]",
+        r"(?i)\bauthenticat",
+        r"(?i)csrf",
+        r"(?i)\bvalidat",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_network_hygiene_severity() -> Severity {
+    Severity::Medium
+}
+
+fn default_network_hygiene_exempt_paths() -> Vec<String> {
+    [
+        "**/*.toml",
+        "**/*.yaml",
+        "**/*.yml",
+        "**/*.ini",
+        "**/*.conf",
+        "**/*.env",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+fn default_secrets_severity() -> Severity {
+    Severity::High
+}
+
+/// A `RuleConfig` table in which either key may be omitted.
+#[derive(Deserialize)]
+struct PartialRuleConfig {
+    enabled: Option<bool>,
+    severity: Option<Severity>,
+}
+
+impl PartialRuleConfig {
+    fn or(self, default: RuleConfig) -> RuleConfig {
+        RuleConfig {
+            enabled: self.enabled.unwrap_or(default.enabled),
+            severity: self.severity.unwrap_or(default.severity),
+        }
+    }
+}
+
+fn sql_injection_go_rule<'de, D: serde::Deserializer<'de>>(
+    d: D,
+) -> std::result::Result<RuleConfig, D::Error> {
+    PartialRuleConfig::deserialize(d).map(|rule| rule.or(default_sql_injection_go_rule()))
+}
+
+fn http_timeouts_go_rule<'de, D: serde::Deserializer<'de>>(
+    d: D,
+) -> std::result::Result<RuleConfig, D::Error> {
+    PartialRuleConfig::deserialize(d).map(|rule| rule.or(default_http_timeouts_go_rule()))
+}
+
+fn default_sql_injection_go_rule() -> RuleConfig {
+    RuleConfig {
+        enabled: true,
+        severity: Severity::Critical,
+    }
+}
+
+fn default_http_timeouts_go_rule() -> RuleConfig {
+    RuleConfig {
+        enabled: true,
+        severity: Severity::Medium,
+    }
+}
+
+fn default_conventions_severity() -> Severity {
+    Severity::Low
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        Self {
+            secrets: SecretsRuleConfig::default(),
+            sql_injection_go: default_sql_injection_go_rule(),
+            http_timeouts_go: default_http_timeouts_go_rule(),
+            conventions: ConventionsRuleConfig::default(),
+            network_hygiene: NetworkHygieneRuleConfig::default(),
+            removed_controls: RemovedControlsRuleConfig::default(),
+            artifacts: ArtifactsRuleConfig::default(),
+            todo: TodoRuleConfig::default(),
+            complexity: ComplexityRuleConfig::default(),
+        }
+    }
+}
+
+/// `[scanners]` section: scanners provided outside the engine.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ScannersConfig {
+    /// `[[scanners.external]]` commands run as scanners; see
+    /// `scanner::external` for the protocol.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external: Vec<ExternalScannerConfig>,
+}
+
+/// A `[[scanners.external]]` entry: a command run once per changed file.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ExternalScannerConfig {
+    /// Rule id reported with the findings, matched by ignore directives and
+    /// `--rule` overrides.
+    pub rule: String,
+    /// Program to run. Relative paths containing a `/` are resolved against
+    /// the repository being checked.
+    pub command: String,
+    /// Arguments passed to the program.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_external_severity")]
+    pub severity: Severity,
+    /// Seconds the program may run per file before it is killed.
+    #[serde(default = "default_external_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Free-form settings forwarded to the program in each request.
+    #[serde(default, skip_serializing_if = "toml::Table::is_empty")]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "serde_json::Map<String, serde_json::Value>")
+    )]
+    pub options: toml::Table,
+}
+
+fn default_external_severity() -> Severity {
+    Severity::Medium
+}
+
+fn default_external_timeout_secs() -> u64 {
+    30
+}
+
+impl Config {
+    /// Loads configuration from a TOML file, migrating deprecated keys with a
+    /// warning for each.
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| EngineError::io(path, e))?;
+        let mut table: toml::Table =
+            toml::from_str(&content).map_err(|e| EngineError::Config(e.to_string()))?;
+        for deprecation in migrate_table(&mut table) {
+            log::warn!("{}: {}", path.display(), deprecation);
+        }
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|e: toml::de::Error| EngineError::Config(e.to_string()))
+    }
+
+    /// Returns the configured index path, respecting the deprecated field.
+    pub fn index_path(&self) -> Option<&str> {
+        if let Some(index) = &self.index {
+            Some(index.path.as_str())
+        } else {
+            #[allow(deprecated)]
+            {
+                self.index_path.as_deref()
+            }
+        }
+    }
+}
+
+// Need a Default implementation for Config as well, so we can create one if the file is missing.
+#[allow(deprecated)]
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            llm: LlmConfig::default(),
+            budget: BudgetConfig::default(),
+            limits: LimitsConfig::default(),
+            generation: GenerationConfig::default(),
+            privacy: PrivacyConfig::default(),
+            paths: PathsConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            integrations: IntegrationsConfig::default(),
+            index: Some(IndexConfig::default()),
+            rag: RagConfig::default(),
+            index_path: None,
+            report: ReportConfig::default(),
+            rules: RulesConfig::default(),
+            engine: EngineConfig::default(),
+            scanners: ScannersConfig::default(),
+            fail_on: default_fail_on(),
+            fail_on_confidence: default_fail_on_confidence(),
+            overrides: Vec::new(),
+            policy: PolicyConfig::default(),
+        }
+    }
+}
+
+fn default_fail_on() -> FailOn {
+    FailOn::High
+}
+
+fn default_fail_on_confidence() -> Confidence {
+    Confidence::Low
+}
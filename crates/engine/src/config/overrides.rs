@@ -0,0 +1,83 @@
+//! Per-directory configuration overrides.
+//!
+//! A monorepo can tune rules for parts of the tree with `[[overrides]]`
+//! sections. Each override lists `paths` globs and a partial `rules` table
+//! and/or `fail-on` value. For a given file, every matching override is
+//! applied in file order on top of the base configuration.
+
+use super::{Config, FailOn};
+use crate::error::{EngineError, Result};
+use globset::Glob;
+use serde::{Deserialize, Serialize};
+
+/// A single `[[overrides]]` section.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ConfigOverride {
+    /// Globs selecting the files this override applies to.
+    pub paths: Vec<String>,
+    /// Partial `[rules]` table merged over the base rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "Option<serde_json::Map<String, serde_json::Value>>")
+    )]
+    pub rules: Option<toml::Table>,
+    /// Severity threshold for findings in matching files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fail_on: Option<FailOn>,
+}
+
+impl ConfigOverride {
+    /// Returns `true` if any of this override's globs match `path`.
+    /// Invalid globs never match; `Config::validate` reports them.
+    pub fn matches(&self, path: &str) -> bool {
+        self.paths.iter().any(|pattern| {
+            Glob::new(pattern)
+                .map(|g| g.compile_matcher().is_match(path))
+                .unwrap_or(false)
+        })
+    }
+
+    /// The override as a partial configuration table.
+    pub(super) fn to_table(&self) -> toml::Table {
+        let mut table = toml::Table::new();
+        if let Some(rules) = &self.rules {
+            table.insert("rules".to_string(), toml::Value::Table(rules.clone()));
+        }
+        if let Some(fail_on) = &self.fail_on {
+            table.insert(
+                "fail-on".to_string(),
+                toml::Value::String(fail_on.as_str().to_string()),
+            );
+        }
+        table
+    }
+}
+
+impl Config {
+    /// Returns the effective configuration for `path`, applying every
+    /// matching override in order. Without a match this is a plain clone.
+    pub fn for_path(&self, path: &str) -> Result<Config> {
+        let mut effective = self.clone();
+        for (i, over) in self.overrides.iter().enumerate() {
+            if over.matches(path) {
+                effective = effective
+                    .merge(&over.to_table())
+                    .map_err(|e| EngineError::Config(format!("overrides[{}]: {}", i, e)))?;
+            }
+        }
+        Ok(effective)
+    }
+
+    /// Returns the `fail-on` threshold that applies to findings in `path`.
+    pub fn fail_on_for(&self, path: &str) -> FailOn {
+        self.overrides
+            .iter()
+            .filter(|o| o.matches(path))
+            .filter_map(|o| o.fail_on.clone())
+            .last()
+            .unwrap_or_else(|| self.fail_on.clone())
+    }
+}
@@ -0,0 +1,120 @@
+//! Review policies enforced on top of the configuration.
+//!
+//! A policy is set in `[policy]` or in a separate file passed with
+//! `check --policy`, so that a team can require settings that individual
+//! configurations must not weaken. Requirements on the configuration are
+//! checked before a review starts; limits on the number of findings are
+//! checked once it completes and listed in the report.
+
+use super::{Config, Severity};
+use crate::error::{EngineError, Result};
+use crate::scanner::{registered_rule_names, rule_enabled};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// The `[policy]` table, or the contents of a policy file.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct PolicyConfig {
+    /// Rules that must be enabled, including in every `[[overrides]]` entry.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_rules: Vec<String>,
+    /// Most findings allowed per severity, e.g. `{ critical = 0, high = 5 }`.
+    /// Severities without a limit are unrestricted.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub max_findings: BTreeMap<Severity, usize>,
+    /// Requires `[privacy.redaction] enabled = true`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub forbid_disabling_redaction: bool,
+}
+
+/// A severity with more findings than the policy allows.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PolicyViolation {
+    pub severity: Severity,
+    /// Findings of `severity` in the report.
+    pub count: usize,
+    /// Most findings of `severity` the policy allows.
+    pub max: usize,
+}
+
+impl PolicyConfig {
+    /// Loads a policy file, whose keys are those of the `[policy]` table.
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| EngineError::io(path, e))?;
+        toml::from_str(&content)
+            .map_err(|e| EngineError::Config(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Returns `true` if the policy requires nothing.
+    pub fn is_empty(&self) -> bool {
+        self == &PolicyConfig::default()
+    }
+
+    /// Returns every severity whose count in `counts` exceeds its limit,
+    /// most severe first.
+    pub fn count_violations(&self, counts: &BTreeMap<Severity, usize>) -> Vec<PolicyViolation> {
+        self.max_findings
+            .iter()
+            .rev()
+            .filter_map(|(severity, &max)| {
+                let count = counts.get(severity).copied().unwrap_or(0);
+                (count > max).then(|| PolicyViolation {
+                    severity: severity.clone(),
+                    count,
+                    max,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Config {
+    /// Returns each way this configuration breaks `[policy]`, as a message.
+    pub fn policy_problems(&self) -> Vec<String> {
+        let policy = &self.policy;
+        let mut problems = Vec::new();
+        if policy.forbid_disabling_redaction && !self.privacy.redaction.enabled {
+            problems.push("`[privacy.redaction]` must stay enabled".to_string());
+        }
+        let known = registered_rule_names();
+        for rule in &policy.required_rules {
+            let external = self.scanners.external.iter().any(|s| &s.rule == rule);
+            if !external && !known.contains(&rule.as_str()) {
+                problems.push(format!("required rule `{}` is not a known rule", rule));
+            } else if !rule_enabled(self, rule) {
+                problems.push(format!("required rule `{}` is disabled", rule));
+            }
+        }
+        for (i, over) in self.overrides.iter().enumerate() {
+            let Ok(effective) = self.clone().merge(&over.to_table()) else {
+                continue;
+            };
+            for rule in &policy.required_rules {
+                if rule_enabled(self, rule) && !rule_enabled(&effective, rule) {
+                    problems.push(format!(
+                        "required rule `{}` is disabled by overrides[{}]",
+                        rule, i
+                    ));
+                }
+            }
+        }
+        problems
+    }
+
+    /// Fails with `EngineError::Config` listing every way this configuration
+    /// breaks `[policy]`.
+    pub fn check_policy(&self) -> Result<()> {
+        let problems = self.policy_problems();
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(EngineError::Config(format!(
+            "configuration violates the review policy: {}",
+            problems.join("; ")
+        )))
+    }
+}
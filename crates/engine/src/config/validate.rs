@@ -0,0 +1,471 @@
+//! Strict validation of configuration files.
+//!
+//! Serde silently ignores unknown keys, so a typo such as `[rules.secret]`
+//! falls back to defaults without any warning. The helpers in this module
+//! surface those keys along with semantic problems (invalid regexes or globs,
+//! incomplete provider settings) so they can be reported all at once.
+
+use super::{merge_tables, migrate_table, Config, Provider};
+use globset::{Glob, GlobMatcher};
+use regex::Regex;
+use std::fmt;
+use std::path::Path;
+
+/// Keys that are valid but omitted when serializing the default configuration.
+const OPTIONAL_KEYS: &[&str] = &[
+    "llm.model",
+    "llm.api-key",
+    "llm.base-url",
+    "llm.cache-path",
+    "llm.max-prompt-tokens",
+    "llm.debug-dir",
+    "llm.proxy",
+    "llm.ca-bundle-path",
+    "budget.tokens.max-per-run",
+    "generation.temperature",
+    "generation.top-p",
+    "generation.seed",
+    "generation.stop",
+    "telemetry.file",
+    "telemetry.otlp-endpoint",
+    "index-path",
+    "rag.model",
+    "rag.qdrant",
+    "rag.qdrant.url",
+    "rag.qdrant.collection",
+    "rag.qdrant.api-key",
+    "overrides",
+    "policy",
+    "policy.required-rules",
+    "policy.max-findings",
+    "policy.forbid-disabling-redaction",
+    "scanners.external",
+    "report.link-template",
+    "engine.scan-cache-path",
+    "rules.secrets.patterns",
+];
+
+/// A single problem found while validating a configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationProblem {
+    /// Dotted path of the offending key, e.g. `rules.secret`.
+    pub path: String,
+    /// Human-readable description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for ValidationProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Parses `content` and reports every key that does not map to a config field.
+///
+/// Returns a single problem if the document is not valid TOML or does not
+/// match the expected types.
+pub fn unknown_keys(content: &str) -> Vec<ValidationProblem> {
+    // Deprecated keys are migrated on load, so only check what remains.
+    let mut migrated = content.to_string();
+    if let Ok(mut table) = content.parse::<toml::Table>() {
+        if !migrate_table(&mut table).is_empty() {
+            migrated = toml::to_string(&table).unwrap_or_default();
+        }
+    }
+    let mut unknown = Vec::new();
+    let deserializer = toml::Deserializer::new(&migrated);
+    let parsed: std::result::Result<Config, _> =
+        serde_ignored::deserialize(deserializer, |path| unknown.push(path.to_string()));
+    if let Err(e) = parsed {
+        return vec![ValidationProblem {
+            path: "<file>".to_string(),
+            message: e.to_string().trim().to_string(),
+        }];
+    }
+
+    let known = known_keys();
+    unknown
+        .into_iter()
+        .map(|path| {
+            let (parent, key) = match path.rsplit_once('.') {
+                Some((parent, key)) => (parent.to_string(), key.to_string()),
+                None => (String::new(), path.clone()),
+            };
+            let message = match suggest(&parent, &key, &known) {
+                Some(s) => format!("unknown key (did you mean `{}`?)", s),
+                None => "unknown key".to_string(),
+            };
+            ValidationProblem { path, message }
+        })
+        .collect()
+}
+
+impl Config {
+    /// Checks the configuration for semantic problems that deserialization
+    /// cannot catch. All problems are returned rather than just the first.
+    pub fn validate(&self) -> Vec<ValidationProblem> {
+        let mut problems = Vec::new();
+
+        for (i, pattern) in self.privacy.redaction.patterns.iter().enumerate() {
+            if let Err(e) = Regex::new(pattern) {
+                problems.push(ValidationProblem {
+                    path: format!("privacy.redaction.patterns[{}]", i),
+                    message: format!("invalid regex `{}`: {}", pattern, last_line(&e.to_string())),
+                });
+            }
+        }
+        for (i, rule) in self.privacy.redaction.rules.iter().enumerate() {
+            if let Err(e) = Regex::new(&rule.pattern) {
+                problems.push(ValidationProblem {
+                    path: format!("privacy.redaction.rules[{}].pattern", i),
+                    message: format!(
+                        "invalid regex `{}`: {}",
+                        rule.pattern,
+                        last_line(&e.to_string())
+                    ),
+                });
+            }
+        }
+
+        let language = &self.report.language;
+        let tag = Regex::new(r"^[A-Za-z]{2,3}(?:[-_][A-Za-z0-9]{2,8})*$").unwrap();
+        if !tag.is_match(language) {
+            problems.push(ValidationProblem {
+                path: "report.language".to_string(),
+                message: format!(
+                    "`{}` is not a language tag (expected e.g. `en` or `ja`)",
+                    language
+                ),
+            });
+        }
+
+        for (i, pattern) in self.rules.removed_controls.patterns.iter().enumerate() {
+            if let Err(e) = Regex::new(pattern) {
+                problems.push(ValidationProblem {
+                    path: format!("rules.removed-controls.patterns[{}]", i),
+                    message: format!("invalid regex `{}`: {}", pattern, last_line(&e.to_string())),
+                });
+            }
+        }
+
+        let secret_patterns = crate::scanner::secrets::pattern_ids();
+        for id in self.rules.secrets.patterns.keys() {
+            if !secret_patterns.contains(&id.as_str()) {
+                problems.push(ValidationProblem {
+                    path: format!("rules.secrets.patterns.{}", id),
+                    message: format!(
+                        "unknown secret pattern (expected one of: {})",
+                        secret_patterns.join(", ")
+                    ),
+                });
+            }
+        }
+
+        for (name, patterns) in [
+            ("paths.allow", &self.paths.allow),
+            ("paths.deny", &self.paths.deny),
+            ("paths.docs-globs", &self.paths.docs_globs),
+            (
+                "rules.network-hygiene.exempt-paths",
+                &self.rules.network_hygiene.exempt_paths,
+            ),
+        ] {
+            for (i, pattern) in patterns.iter().enumerate() {
+                if let Err(e) = Glob::new(pattern) {
+                    problems.push(ValidationProblem {
+                        path: format!("{}[{}]", name, i),
+                        message: format!("invalid glob `{}`: {}", pattern, e),
+                    });
+                }
+            }
+        }
+
+        for (i, over) in self.overrides.iter().enumerate() {
+            for (j, pattern) in over.paths.iter().enumerate() {
+                if let Err(e) = Glob::new(pattern) {
+                    problems.push(ValidationProblem {
+                        path: format!("overrides[{}].paths[{}]", i, j),
+                        message: format!("invalid glob `{}`: {}", pattern, e),
+                    });
+                }
+            }
+            if let Some(rules) = &over.rules {
+                // Check the partial rules against a complete base so that
+                // omitted required keys are not reported.
+                let mut partial = match toml::Value::try_from(Config::default()) {
+                    Ok(toml::Value::Table(table)) => table,
+                    _ => toml::Table::new(),
+                };
+                let mut overlay = toml::Table::new();
+                overlay.insert("rules".to_string(), toml::Value::Table(rules.clone()));
+                merge_tables(&mut partial, overlay);
+                for problem in unknown_keys(&toml::to_string(&partial).unwrap_or_default()) {
+                    problems.push(ValidationProblem {
+                        path: format!("overrides[{}].{}", i, problem.path),
+                        message: problem.message,
+                    });
+                }
+            }
+        }
+
+        let builtin = crate::scanner::registered_rule_names();
+        for (i, scanner) in self.scanners.external.iter().enumerate() {
+            let path = format!("scanners.external[{}]", i);
+            if scanner.command.trim().is_empty() {
+                problems.push(ValidationProblem {
+                    path: format!("{}.command", path),
+                    message: "command must not be empty".to_string(),
+                });
+            }
+            let duplicate = self.scanners.external[..i]
+                .iter()
+                .any(|other| other.rule == scanner.rule);
+            if builtin.contains(&scanner.rule.as_str()) || duplicate {
+                problems.push(ValidationProblem {
+                    path: format!("{}.rule", path),
+                    message: format!("rule id `{}` is already in use", scanner.rule),
+                });
+            }
+            if scanner.timeout_secs == 0 {
+                problems.push(ValidationProblem {
+                    path: format!("{}.timeout-secs", path),
+                    message: "timeout must be at least 1 second".to_string(),
+                });
+            }
+        }
+
+        if self.report.history_path.is_some() && self.report.history_keep == 0 {
+            problems.push(ValidationProblem {
+                path: "report.history-keep".to_string(),
+                message: "history-keep must be at least 1".to_string(),
+            });
+        }
+        if self.generation.max_tokens == 0 {
+            problems.push(ValidationProblem {
+                path: "generation.max-tokens".to_string(),
+                message: "max-tokens must be at least 1".to_string(),
+            });
+        }
+        if let Some(top_p) = self.generation.top_p {
+            if !(top_p > 0.0 && top_p <= 1.0) {
+                problems.push(ValidationProblem {
+                    path: "generation.top-p".to_string(),
+                    message: format!("top-p must be greater than 0 and at most 1 (got {})", top_p),
+                });
+            }
+        }
+
+        if self.llm.provider != Provider::Null {
+            if self.llm.model.is_none() {
+                problems.push(ValidationProblem {
+                    path: "llm.model".to_string(),
+                    message: format!(
+                        "a model is required when provider is '{}'",
+                        self.llm.provider.as_str()
+                    ),
+                });
+            }
+            if self.llm.api_key.is_none() {
+                problems.push(ValidationProblem {
+                    path: "llm.api-key".to_string(),
+                    message: format!(
+                        "an API key is required when provider is '{}'",
+                        self.llm.provider.as_str()
+                    ),
+                });
+            }
+        }
+
+        problems
+    }
+}
+
+/// Regex syntax errors span several lines; the last one carries the reason.
+fn last_line(message: &str) -> &str {
+    message.lines().last().unwrap_or(message).trim()
+}
+
+/// Returns every known dotted key path, including optional keys.
+fn known_keys() -> Vec<String> {
+    let mut keys = Vec::new();
+    if let Ok(toml::Value::Table(table)) = toml::Value::try_from(Config::default()) {
+        collect_keys("", &table, &mut keys);
+    }
+    keys.extend(OPTIONAL_KEYS.iter().map(|k| k.to_string()));
+    keys
+}
+
+fn collect_keys(prefix: &str, table: &toml::value::Table, keys: &mut Vec<String>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        if let toml::Value::Table(inner) = value {
+            collect_keys(&path, inner, keys);
+        }
+        keys.push(path);
+    }
+}
+
+/// Suggests the closest known sibling of `key` within the `parent` table.
+fn suggest(parent: &str, key: &str, known: &[String]) -> Option<String> {
+    known
+        .iter()
+        .filter_map(|path| {
+            let (p, k) = path.rsplit_once('.').unwrap_or(("", path.as_str()));
+            (p == parent).then_some(k)
+        })
+        .map(|candidate| (strsim::levenshtein(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= std::cmp::max(2, key.len() / 3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+impl Config {
+    /// Reports `[paths]` patterns that are valid but probably not what was
+    /// meant: allow patterns that every deny pattern together makes
+    /// unreachable, backslashes used as separators, and, when the
+    /// repository `root` is given, patterns that match no file in it.
+    pub fn path_warnings(&self, root: Option<&Path>) -> Vec<ValidationProblem> {
+        let compile = |patterns: &[String]| -> Vec<(usize, String, GlobMatcher)> {
+            patterns
+                .iter()
+                .enumerate()
+                .filter_map(|(i, p)| Some((i, p.clone(), Glob::new(p).ok()?.compile_matcher())))
+                .collect()
+        };
+        let allow = compile(&self.paths.allow);
+        let deny = compile(&self.paths.deny);
+        let mut warnings = Vec::new();
+
+        for (i, pattern, matcher) in &allow {
+            let samples: Vec<String> = glob_samples(pattern)
+                .into_iter()
+                .filter(|sample| matcher.is_match(sample))
+                .collect();
+            if samples.is_empty() {
+                continue;
+            }
+            let denied = |m: &GlobMatcher| samples.iter().all(|sample| m.is_match(sample));
+            let by = match deny.iter().find(|(_, _, m)| denied(m)) {
+                Some((_, deny_pattern, _)) => format!("`{}`", deny_pattern),
+                None if samples
+                    .iter()
+                    .all(|sample| deny.iter().any(|(_, _, m)| m.is_match(sample))) =>
+                {
+                    "`paths.deny`".to_string()
+                }
+                None => continue,
+            };
+            warnings.push(ValidationProblem {
+                path: format!("paths.allow[{}]", i),
+                message: format!(
+                    "`{}` is unreachable: the paths it matches are denied by {}",
+                    pattern, by
+                ),
+            });
+        }
+
+        if cfg!(not(windows)) {
+            for (name, patterns) in [
+                ("paths.allow", &self.paths.allow),
+                ("paths.deny", &self.paths.deny),
+            ] {
+                for (i, pattern) in patterns.iter().enumerate() {
+                    if pattern.contains('\\') {
+                        warnings.push(ValidationProblem {
+                            path: format!("{}[{}]", name, i),
+                            message: format!(
+                                "`{}` contains a backslash, which escapes the next character; separate directories with `/`",
+                                pattern
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(root) = root {
+            let files = repository_files(root);
+            for (name, patterns) in [("paths.allow", &allow), ("paths.deny", &deny)] {
+                for (i, pattern, matcher) in patterns {
+                    if !files.iter().any(|file| matcher.is_match(file)) {
+                        warnings.push(ValidationProblem {
+                            path: format!("{}[{}]", name, i),
+                            message: format!(
+                                "`{}` matches no files in {}",
+                                pattern,
+                                root.display()
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+        warnings
+    }
+}
+
+/// Returns a few paths matching `pattern`, with each `**` and `*` replaced
+/// by a different stand-in per sample. Together they approximate the set of
+/// paths the pattern matches, for checking whether it is covered by others.
+fn glob_samples(pattern: &str) -> Vec<String> {
+    const DEEP: [&str; 3] = ["", "a/", "a/b/"];
+    const SEGMENT: [&str; 3] = ["x", "x.y", "test"];
+    (0..DEEP.len())
+        .map(|k| {
+            let mut sample = String::new();
+            let mut chars = pattern.chars().peekable();
+            while let Some(c) = chars.next() {
+                match c {
+                    '*' if chars.peek() == Some(&'*') => {
+                        chars.next();
+                        if chars.peek() == Some(&'/') {
+                            chars.next();
+                            sample.push_str(DEEP[k]);
+                        } else {
+                            sample.push_str(DEEP[k].trim_end_matches('/'));
+                            if DEEP[k].is_empty() {
+                                sample.push_str(SEGMENT[k]);
+                            }
+                        }
+                    }
+                    '*' => sample.push_str(SEGMENT[k]),
+                    '?' => sample.push('x'),
+                    '[' => {
+                        let class: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                        match class.chars().next() {
+                            Some('!') | Some('^') | None => sample.push('~'),
+                            Some(first) => sample.push(first),
+                        }
+                    }
+                    '{' => {
+                        let group: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                        let alternatives: Vec<&str> = group.split(',').collect();
+                        sample.push_str(alternatives[k % alternatives.len()]);
+                    }
+                    '\\' => sample.extend(chars.next()),
+                    c => sample.push(c),
+                }
+            }
+            sample
+        })
+        .collect()
+}
+
+/// Returns the paths of the files under `root`, relative to it, skipping
+/// version control and state directories.
+fn repository_files(root: &Path) -> Vec<String> {
+    let everything = match crate::rag::build_globset(&["**/*".to_string()]) {
+        Ok(set) => set,
+        Err(_) => return Vec::new(),
+    };
+    let files = crate::rag::matching_files(root, &everything, &globset::GlobSet::empty())
+        .unwrap_or_default();
+    files
+        .into_iter()
+        .map(|file| file.filename.replace('\\', "/"))
+        .collect()
+}
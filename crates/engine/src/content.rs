@@ -0,0 +1,161 @@
+//! Sources of file contents for a review.
+//!
+//! The engine never reads changed files directly; it asks a
+//! `FileContentProvider` instead. This lets the CLI read from a repository
+//! checkout without changing the process working directory, or from the git
+//! index when reviewing staged changes, and lets library
+//! users review contents they already hold in memory (for example, files
+//! fetched from a code hosting API).
+
+use crate::diff_parser::normalize_path;
+use crate::error::{EngineError, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+/// A trait for looking up the contents of a changed file by its diff path.
+pub trait FileContentProvider: Send + Sync {
+    /// Returns the full contents of the file at `path`, as it appears in the diff.
+    fn read(&self, path: &str) -> Result<String>;
+
+    /// Returns when the file at `path` was last modified, if known. Used to
+    /// judge whether the RAG index predates the reviewed changes.
+    fn modified(&self, _path: &str) -> Option<SystemTime> {
+        None
+    }
+
+    /// Returns the size of the file at `path` in bytes, if known. Used by
+    /// the `artifacts` rule to flag large added files.
+    fn size(&self, _path: &str) -> Option<u64> {
+        None
+    }
+}
+
+/// Reads files from disk relative to an explicit root directory.
+pub struct FsContentProvider {
+    root: PathBuf,
+}
+
+impl FsContentProvider {
+    /// Creates a provider that resolves diff paths against `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl FileContentProvider for FsContentProvider {
+    fn read(&self, path: &str) -> Result<String> {
+        let full = native_path(&self.root, path)?;
+        let bytes = fs::read(&full).map_err(|e| EngineError::io(&full, e))?;
+        Ok(decode(path, bytes))
+    }
+
+    fn modified(&self, path: &str) -> Option<SystemTime> {
+        fs::metadata(native_path(&self.root, path).ok()?)
+            .ok()?
+            .modified()
+            .ok()
+    }
+
+    fn size(&self, path: &str) -> Option<u64> {
+        Some(
+            fs::metadata(native_path(&self.root, path).ok()?)
+                .ok()?
+                .len(),
+        )
+    }
+}
+
+/// Joins the diff path `path` onto `root` one component at a time, so the
+/// result uses the platform's separator throughout. Fails with
+/// `EngineError::Config` for absolute paths and paths with `..`
+/// components, which could name a file outside `root`.
+pub fn native_path(root: &Path, path: &str) -> Result<PathBuf> {
+    let normalized = normalize_path(path);
+    let inside = Path::new(&normalized)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if !inside {
+        return Err(EngineError::Config(format!(
+            "refusing to read `{}`: diff paths must stay inside the repository",
+            path
+        )));
+    }
+    Ok(normalized
+        .split('/')
+        .filter(|component| !component.is_empty() && *component != ".")
+        .fold(root.to_path_buf(), |full, component| full.join(component)))
+}
+
+/// Reads the staged version of files from a git repository's index
+/// (`git show :<path>`), ignoring any unstaged edits in the working tree.
+pub struct GitIndexContentProvider {
+    repo: PathBuf,
+}
+
+impl GitIndexContentProvider {
+    /// Creates a provider reading the index of the repository at `repo`.
+    pub fn new(repo: impl Into<PathBuf>) -> Self {
+        Self { repo: repo.into() }
+    }
+}
+
+impl FileContentProvider for GitIndexContentProvider {
+    fn read(&self, path: &str) -> Result<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.repo)
+            .args(["show", &format!(":{}", normalize_path(path))])
+            .output()
+            .map_err(|e| EngineError::Git(format!("failed to run git show: {}", e)))?;
+        if !output.status.success() {
+            return Err(EngineError::Git(format!(
+                "{} is not staged: {}",
+                path,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(decode(path, output.stdout))
+    }
+
+    fn size(&self, path: &str) -> Option<u64> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.repo)
+            .args(["cat-file", "-s", &format!(":{}", normalize_path(path))])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+    }
+}
+
+/// Decodes file contents, replacing invalid UTF-8 sequences so a file in a
+/// legacy encoding is still reviewed rather than failing the run.
+fn decode(path: &str, bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes).unwrap_or_else(|e| {
+        log::warn!("{} is not valid UTF-8; invalid bytes were replaced", path);
+        String::from_utf8_lossy(e.as_bytes()).into_owned()
+    })
+}
+
+/// Serves file contents from memory, keyed by diff path.
+impl FileContentProvider for HashMap<String, String> {
+    fn read(&self, path: &str) -> Result<String> {
+        self.get(path).cloned().ok_or_else(|| {
+            EngineError::from(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no contents provided for {}", path),
+            ))
+        })
+    }
+
+    fn size(&self, path: &str) -> Option<u64> {
+        self.get(path).map(|content| content.len() as u64)
+    }
+}
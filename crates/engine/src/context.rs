@@ -0,0 +1,107 @@
+//! The stated intent of a change under review.
+//!
+//! A pull request title and description tell the LLM what the change is
+//! meant to do, so it can point out changes the description does not
+//! account for. They come from `check --pr-title`/`--pr-body` or, on GitHub
+//! Actions, from the event payload at `GITHUB_EVENT_PATH`. Secret values in
+//! them are masked and the redaction rules applied before they reach a
+//! prompt.
+
+use crate::error::{EngineError, Result};
+use crate::privacy::Redactor;
+use crate::scanner::secrets::mask_values;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Longest description, in characters, included in a prompt.
+const MAX_BODY_CHARS: usize = 4000;
+
+/// Paragraph marker the LLM is asked to start its intent note with when
+/// structured output is off.
+const INTENT_MARKER: &str = "Intent vs. implementation:";
+
+/// Title and description of the change, e.g. from a pull request.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReviewContext {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GithubEvent {
+    pull_request: Option<ReviewContext>,
+}
+
+impl ReviewContext {
+    /// Reads the pull request title and body from a GitHub Actions event
+    /// payload. Returns `None` for events without a pull request, such as
+    /// pushes.
+    pub fn from_github_event(path: &Path) -> Result<Option<Self>> {
+        let content = std::fs::read_to_string(path).map_err(|e| EngineError::io(path, e))?;
+        let event: GithubEvent = serde_json::from_str(&content).map_err(|e| {
+            EngineError::Config(format!("invalid GitHub event {}: {}", path.display(), e))
+        })?;
+        Ok(event.pull_request.filter(|context| !context.is_empty()))
+    }
+
+    /// Whether neither a title nor a description is set.
+    pub fn is_empty(&self) -> bool {
+        let blank = |s: &Option<String>| s.as_deref().is_none_or(|s| s.trim().is_empty());
+        blank(&self.title) && blank(&self.body)
+    }
+
+    /// The Conventional Commits type of the title, such as `feat` in
+    /// `feat(auth)!: add SSO`.
+    pub fn change_type(&self) -> Option<&str> {
+        let title = self.title.as_deref()?;
+        let (head, _) = title.split_once(':')?;
+        let head = head.strip_suffix('!').unwrap_or(head);
+        let kind = match head.split_once('(') {
+            Some((kind, scope)) if scope.ends_with(')') => kind,
+            Some(_) => return None,
+            None => head,
+        };
+        (!kind.is_empty() && kind.chars().all(|c| c.is_ascii_lowercase())).then_some(kind)
+    }
+
+    /// Describes the change for a summary prompt, listing `changed` files
+    /// so the LLM can compare them with the description.
+    pub(crate) fn prompt_section(&self, redactor: &Redactor, changed: &[&str]) -> String {
+        let mut section = String::from("The author describes the change as follows.\n");
+        if let Some(title) = &self.title {
+            section.push_str(&format!("Title: {}\n", clean(redactor, title.trim())));
+        }
+        if let Some(kind) = self.change_type() {
+            section.push_str(&format!("Type: {}\n", kind));
+        }
+        if let Some(body) = self.body.as_deref().filter(|b| !b.trim().is_empty()) {
+            let body: String = body.trim().chars().take(MAX_BODY_CHARS).collect();
+            section.push_str(&format!("Description:\n{}\n", clean(redactor, &body)));
+        }
+        if !changed.is_empty() {
+            section.push_str(&format!("Changed files: {}\n", changed.join(", ")));
+        }
+        section
+    }
+}
+
+/// Masks secret values in `text`, then applies the redaction rules.
+fn clean(redactor: &Redactor, text: &str) -> String {
+    redactor.redact(&mask_values(text))
+}
+
+/// Splits the `Intent vs. implementation:` paragraph off an unstructured
+/// summary. Returns the remaining summary and the note, if there is one.
+pub(crate) fn split_intent(summary: &str) -> (String, Option<String>) {
+    match summary.find(INTENT_MARKER) {
+        Some(start) => {
+            let note = summary[start + INTENT_MARKER.len()..].trim();
+            let rest = summary[..start].trim_end().to_string();
+            (rest, Some(note.to_string()).filter(|n| !n.is_empty()))
+        }
+        None => (summary.to_string(), None),
+    }
+}
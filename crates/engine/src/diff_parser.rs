@@ -2,12 +2,19 @@
 
 use crate::error::{EngineError, Result};
 use patch::{Line as PatchLine, Patch};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 /// Represents a single changed file in a diff.
 #[derive(Debug)]
 pub struct ChangedFile {
     pub path: String,
     pub hunks: Vec<Hunk>,
+    /// Whether the change creates the file.
+    pub added: bool,
+    /// Whether the change deletes the file. `path` is then the old path and
+    /// the hunks only remove lines.
+    pub deleted: bool,
 }
 
 /// Represents a "hunk" or a contiguous block of changes in a file.
@@ -28,6 +35,163 @@ pub enum Line {
     Context(String),
 }
 
+impl ChangedFile {
+    /// Number of lines added across all hunks.
+    pub fn additions(&self) -> usize {
+        self.hunks.iter().map(Hunk::additions).sum()
+    }
+
+    /// Number of lines removed across all hunks.
+    pub fn deletions(&self) -> usize {
+        self.hunks.iter().map(Hunk::deletions).sum()
+    }
+}
+
+impl Hunk {
+    /// Number of added lines in this hunk.
+    pub fn additions(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|l| matches!(l, Line::Added(_)))
+            .count()
+    }
+
+    /// Number of removed lines in this hunk.
+    pub fn deletions(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|l| matches!(l, Line::Removed(_)))
+            .count()
+    }
+
+    /// One-based line numbers of the added lines in the new file.
+    pub fn added_line_numbers(&self) -> Vec<usize> {
+        let mut new_line = self.new_start as usize;
+        let mut numbers = Vec::new();
+        for line in &self.lines {
+            match line {
+                Line::Added(_) => {
+                    numbers.push(new_line);
+                    new_line += 1;
+                }
+                Line::Context(_) => new_line += 1,
+                Line::Removed(_) => {}
+            }
+        }
+        numbers
+    }
+
+    /// One-based line numbers of the removed lines in the old file, paired
+    /// with their text.
+    pub fn removed_lines(&self) -> Vec<(usize, &str)> {
+        let mut old_line = self.old_start as usize;
+        let mut removed = Vec::new();
+        for line in &self.lines {
+            match line {
+                Line::Removed(text) => {
+                    removed.push((old_line, text.as_str()));
+                    old_line += 1;
+                }
+                Line::Context(_) => old_line += 1,
+                Line::Added(_) => {}
+            }
+        }
+        removed
+    }
+
+    /// One-based line numbers of the removed lines in the old file.
+    pub fn removed_line_numbers(&self) -> Vec<usize> {
+        self.removed_lines().into_iter().map(|(n, _)| n).collect()
+    }
+}
+
+/// Returns the one-based line numbers in the new version of `file` that the
+/// diff adds or modifies.
+pub fn changed_new_lines(file: &ChangedFile) -> HashSet<usize> {
+    file.hunks
+        .iter()
+        .flat_map(Hunk::added_line_numbers)
+        .collect()
+}
+
+/// Where a line of the new file appears in the diff, for publishing inline
+/// comments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DiffPosition {
+    /// Zero-based index of the hunk within the file's diff.
+    pub hunk: usize,
+    /// One-based offset of the line within the hunk, counting every added,
+    /// removed and context line after the `@@` header.
+    pub offset: usize,
+    /// One-based line number in the old file, for context lines. Added
+    /// lines have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_line: Option<usize>,
+}
+
+/// Maps new-file line numbers to their position in a parsed diff. Only
+/// added and context lines have a position; a line outside every hunk
+/// cannot carry an inline comment.
+#[derive(Debug, Default)]
+pub struct LineMapper {
+    files: HashMap<String, HashMap<usize, DiffPosition>>,
+}
+
+impl LineMapper {
+    /// Indexes the hunks of `files`.
+    pub fn new(files: &[ChangedFile]) -> Self {
+        let files = files
+            .iter()
+            .map(|file| {
+                let mut positions = HashMap::new();
+                for (index, hunk) in file.hunks.iter().enumerate() {
+                    let mut old_line = hunk.old_start as usize;
+                    let mut new_line = hunk.new_start as usize;
+                    for (offset, line) in hunk.lines.iter().enumerate() {
+                        let position = DiffPosition {
+                            hunk: index,
+                            offset: offset + 1,
+                            old_line: None,
+                        };
+                        match line {
+                            Line::Added(_) => {
+                                positions.insert(new_line, position);
+                                new_line += 1;
+                            }
+                            Line::Context(_) => {
+                                positions.insert(
+                                    new_line,
+                                    DiffPosition {
+                                        old_line: Some(old_line),
+                                        ..position
+                                    },
+                                );
+                                old_line += 1;
+                                new_line += 1;
+                            }
+                            Line::Removed(_) => old_line += 1,
+                        }
+                    }
+                }
+                (file.path.clone(), positions)
+            })
+            .collect();
+        Self { files }
+    }
+
+    /// Returns where one-based `new_line` of `path` appears in the diff, or
+    /// `None` if it is outside every hunk.
+    pub fn position_in_diff(&self, path: &str, new_line: usize) -> Option<DiffPosition> {
+        self.files.get(path)?.get(&new_line).copied()
+    }
+
+    /// Whether an inline comment can be attached to `line` of `path`.
+    pub fn is_commentable(&self, path: &str, line: usize) -> bool {
+        self.position_in_diff(path, line).is_some()
+    }
+}
+
 /// Parses a raw diff string into a structured format using the `patch` crate.
 ///
 /// # Arguments
@@ -46,11 +210,14 @@ pub fn parse(diff_text: &str) -> Result<Vec<ChangedFile>> {
     let mut segment = String::new();
 
     for line in diff_text.lines() {
-        if line.starts_with("diff --git ") {
-            if !segment.is_empty() {
-                files.push(parse_segment(&segment)?);
-                segment.clear();
-            }
+        if line.starts_with("diff --git ") && !segment.is_empty() {
+            files.push(parse_segment(&segment)?);
+            segment.clear();
+        }
+        // `\ No newline at end of file` markers carry no line content, and
+        // the `patch` crate cannot parse them between hunk lines.
+        if line.starts_with("\\ ") {
+            continue;
         }
         segment.push_str(line);
         segment.push('\n');
@@ -63,16 +230,38 @@ pub fn parse(diff_text: &str) -> Result<Vec<ChangedFile>> {
     Ok(files)
 }
 
+/// Converts `path` to the form diff paths take and path globs are matched
+/// against: relative, with `/` separators. Diffs generated on Windows may
+/// use backslashes.
+pub fn normalize_path(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+/// Returns the path of a file from the `b/` side of a diff.
+fn new_path(path: &str) -> String {
+    normalize_path(path).trim_start_matches("b/").to_string()
+}
+
+/// Returns the path of a file from the `a/` side of a diff.
+fn old_path(path: &str) -> String {
+    normalize_path(path).trim_start_matches("a/").to_string()
+}
+
 fn parse_segment(segment: &str) -> Result<ChangedFile> {
     let header_path = segment
         .lines()
         .next()
         .and_then(|line| line.split_whitespace().nth(3))
-        .ok_or_else(|| EngineError::DiffParser("Malformed diff header".into()))?
-        .trim_start_matches("b/")
-        .to_string();
+        .map(new_path)
+        .ok_or_else(|| EngineError::DiffParser("Malformed diff header".into()))?;
 
     let has_patch = segment.lines().any(|l| l.starts_with("--- "));
+    let added = segment
+        .lines()
+        .any(|l| l.starts_with("new file mode") || l == "--- /dev/null");
+    let deleted = segment
+        .lines()
+        .any(|l| l.starts_with("deleted file mode") || l == "+++ /dev/null");
     let is_binary = segment
         .lines()
         .any(|l| l.starts_with("Binary files") || l.starts_with("GIT binary patch"));
@@ -81,6 +270,8 @@ fn parse_segment(segment: &str) -> Result<ChangedFile> {
         return Ok(ChangedFile {
             path: header_path,
             hunks: Vec::new(),
+            added,
+            deleted,
         });
     }
 
@@ -91,7 +282,11 @@ fn parse_segment(segment: &str) -> Result<ChangedFile> {
         .next()
         .ok_or_else(|| EngineError::DiffParser("No patch data found".into()))?;
 
-    let path = patch.new.path.trim_start_matches("b/").to_string();
+    let path = if deleted {
+        old_path(&patch.old.path)
+    } else {
+        new_path(&patch.new.path)
+    };
     let hunks = patch
         .hunks
         .into_iter()
@@ -115,5 +310,10 @@ fn parse_segment(segment: &str) -> Result<ChangedFile> {
         })
         .collect();
 
-    Ok(ChangedFile { path, hunks })
+    Ok(ChangedFile {
+        path,
+        hunks,
+        added,
+        deleted,
+    })
 }
@@ -1,5 +1,6 @@
 //! Custom error types for the engine crate.
 
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// A specialized `Result` type for engine operations.
@@ -10,12 +11,27 @@ pub enum EngineError {
     #[error("Configuration error: {0}")]
     Config(String),
 
-    #[error("I/O error: {0}")]
-    Io(#[from] std::io::Error),
+    /// A file or process operation failed, on `path` when it is known.
+    #[error(
+        "I/O error{}: {source}",
+        .path.as_ref().map(|p| format!(" on {}", p.display())).unwrap_or_default()
+    )]
+    Io {
+        path: Option<PathBuf>,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// A git command failed or could not be run.
+    #[error("Git error: {0}")]
+    Git(String),
 
     #[error("LLM provider error: {0}")]
     LlmProvider(String),
 
+    #[error("LLM request timed out: {0}")]
+    LlmTimeout(String),
+
     #[error("Token budget exceeded: used {used} tokens but budget is {max}")]
     TokenBudgetExceeded { used: u32, max: u32 },
 
@@ -31,6 +47,25 @@ pub enum EngineError {
     #[error("Report generation error: {0}")]
     Report(String),
 
+    #[error("Publish error: {0}")]
+    Publish(String),
+
     #[error("An unknown error occurred")]
     Unknown,
 }
+
+impl EngineError {
+    /// Wraps an I/O error that occurred on `path`.
+    pub fn io(path: impl AsRef<Path>, source: std::io::Error) -> Self {
+        EngineError::Io {
+            path: Some(path.as_ref().to_path_buf()),
+            source,
+        }
+    }
+}
+
+impl From<std::io::Error> for EngineError {
+    fn from(source: std::io::Error) -> Self {
+        EngineError::Io { path: None, source }
+    }
+}
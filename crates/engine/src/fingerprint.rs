@@ -0,0 +1,72 @@
+//! Stable identities for findings.
+//!
+//! A fingerprint hashes the rule id, file path, and the whitespace-normalised
+//! content of the flagged line, so it survives edits elsewhere in the file
+//! that only shift line numbers. Identical lines flagged by the same rule in
+//! one file are told apart by their occurrence index, counted from the top.
+
+use crate::scanner::Issue;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Returns the fingerprint of `issue`, whose flagged line reads
+/// `line_content`. This is the fingerprint of the first occurrence; see
+/// `fingerprint_occurrence` for repeated lines.
+pub fn fingerprint_issue(issue: &Issue, line_content: &str) -> String {
+    fingerprint_occurrence(issue, line_content, 0)
+}
+
+/// Returns the fingerprint of `issue` when it is the `occurrence`-th
+/// (zero-based) finding of its rule on a line reading `line_content` in the
+/// same file.
+pub fn fingerprint_occurrence(issue: &Issue, line_content: &str, occurrence: usize) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(issue.rule_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(issue.file_path.as_bytes());
+    hasher.update([0]);
+    hasher.update(normalize(line_content).as_bytes());
+    if occurrence > 0 {
+        hasher.update([0]);
+        hasher.update(occurrence.to_string().as_bytes());
+    }
+    hasher
+        .finalize()
+        .iter()
+        .take(16)
+        .fold(String::with_capacity(32), |mut hex, b| {
+            let _ = write!(hex, "{:02x}", b);
+            hex
+        })
+}
+
+/// Fills in the fingerprint of every issue in `issues`, all reported
+/// against the file with contents `content`.
+pub(crate) fn assign(issues: &mut [Issue], content: &str) {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut order: Vec<usize> = (0..issues.len()).collect();
+    order.sort_by_key(|&i| issues[i].line_number);
+    let mut seen: HashMap<(String, String), usize> = HashMap::new();
+    for i in order {
+        let issue = &issues[i];
+        let line = issue
+            .line_number
+            .checked_sub(1)
+            .and_then(|index| lines.get(index))
+            .copied()
+            .unwrap_or_default();
+        let occurrence = seen
+            .entry((issue.rule_id.clone(), normalize(line)))
+            .or_default();
+        let fingerprint = fingerprint_occurrence(issue, line, *occurrence);
+        *occurrence += 1;
+        issues[i].fingerprint = fingerprint;
+    }
+}
+
+/// Trims `line` and collapses runs of whitespace, so re-indenting a line
+/// keeps its fingerprint.
+fn normalize(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
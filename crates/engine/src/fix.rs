@@ -0,0 +1,141 @@
+//! Applying the fixes stored in a review report to the working tree.
+//!
+//! A fix names the flagged line by its trimmed content, so lines inserted or
+//! removed since the review do not stop it from applying. The line is looked
+//! up nearest to the recorded line number; when no line has that content any
+//! more, the fix is skipped rather than guessed at.
+
+use crate::error::{EngineError, Result};
+use crate::scanner::{Fix, Issue};
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+
+/// What happened to one fix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FixStatus {
+    /// The line was replaced, or would be under a dry run.
+    Applied,
+    /// The fix was left out, with the reason.
+    Skipped(String),
+}
+
+/// The outcome of one fix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixOutcome {
+    /// Path of the file, relative to the repository root.
+    pub path: String,
+    /// One-based line the fix was applied to in the fixed file, or the
+    /// reported line when it was skipped.
+    pub line: usize,
+    pub rule: String,
+    pub status: FixStatus,
+}
+
+/// Applies the fix of every issue that has one to the files under `root`,
+/// in path and line order. With `dry_run`, no file is written.
+///
+/// Issues whose file cannot be read, or lies outside `root`, are skipped.
+/// Failing to write a fixed file is an error.
+pub fn apply_fixes(root: &Path, issues: &[Issue], dry_run: bool) -> Result<Vec<FixOutcome>> {
+    let mut by_path: BTreeMap<&str, Vec<(&Issue, &Fix)>> = BTreeMap::new();
+    for issue in issues {
+        if let Some(fix) = &issue.fix {
+            by_path
+                .entry(issue.file_path.as_str())
+                .or_default()
+                .push((issue, fix));
+        }
+    }
+
+    let mut outcomes = Vec::new();
+    for (path, mut fixes) in by_path {
+        fixes.sort_by_key(|(issue, _)| issue.line_number);
+        if !is_relative(path) {
+            let reason = "path is outside the repository";
+            outcomes.extend(fixes.iter().map(|(issue, _)| skipped(issue, reason)));
+            continue;
+        }
+        let full_path = root.join(path);
+        let Ok(content) = std::fs::read_to_string(&full_path) else {
+            let reason = "file cannot be read";
+            outcomes.extend(fixes.iter().map(|(issue, _)| skipped(issue, reason)));
+            continue;
+        };
+
+        // Each entry stays one line of the original file, so a multi-line
+        // replacement moves the lines after it only in the written file.
+        let mut lines: Vec<String> = content.split_inclusive('\n').map(String::from).collect();
+        let mut inserted: Vec<(usize, usize)> = Vec::new();
+        for (issue, fix) in fixes {
+            let outcome = match nearest_line(&lines, &fix.find, issue.line_number) {
+                Some(index) => {
+                    lines[index] = replace_line(&lines[index], &fix.replace);
+                    let shift: usize = inserted
+                        .iter()
+                        .filter(|(at, _)| *at < index)
+                        .map(|(_, extra)| extra)
+                        .sum();
+                    inserted.push((index, fix.replace.lines().count().saturating_sub(1)));
+                    FixOutcome {
+                        path: path.to_string(),
+                        line: index + shift + 1,
+                        rule: issue.rule_id.clone(),
+                        status: FixStatus::Applied,
+                    }
+                }
+                None => skipped(issue, "line no longer matches"),
+            };
+            outcomes.push(outcome);
+        }
+        if !inserted.is_empty() && !dry_run {
+            std::fs::write(&full_path, lines.concat())
+                .map_err(|e| EngineError::io(&full_path, e))?;
+        }
+    }
+    Ok(outcomes)
+}
+
+fn skipped(issue: &Issue, reason: &str) -> FixOutcome {
+    FixOutcome {
+        path: issue.file_path.clone(),
+        line: issue.line_number,
+        rule: issue.rule_id.clone(),
+        status: FixStatus::Skipped(reason.to_string()),
+    }
+}
+
+/// Returns `true` when `path` stays below the directory it is joined to.
+fn is_relative(path: &str) -> bool {
+    Path::new(path)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Returns the index of the line whose trimmed content is `find`, nearest
+/// to the one-based `line_number`. Earlier lines win ties.
+fn nearest_line(lines: &[String], find: &str, line_number: usize) -> Option<usize> {
+    let target = line_number.saturating_sub(1);
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.trim() == find)
+        .min_by_key(|(index, _)| index.abs_diff(target))
+        .map(|(index, _)| index)
+}
+
+/// Replaces the content of `line` with `replace`, keeping its indentation on
+/// every replacement line and its line ending between and after them.
+fn replace_line(line: &str, replace: &str) -> String {
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let ending = &line[line.trim_end().len()..];
+    let newline = if ending.ends_with("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let body: Vec<String> = replace
+        .lines()
+        .map(|text| format!("{}{}", indent, text))
+        .collect();
+    format!("{}{}", body.join(newline), ending)
+}
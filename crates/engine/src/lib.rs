@@ -2,37 +2,80 @@
 //!
 //! This crate contains the primary logic for:
 //! - Parsing configurations (`config`).
+//! - Auditing inline ignore directives (`audit`).
+//! - Supplying changed file contents (`content`).
+//! - Describing the stated intent of a change (`context`).
 //! - Handling errors (`error`).
 //! - Parsing diffs (`diff_parser`).
+//! - Fingerprinting findings across runs (`fingerprint`).
+//! - Capping how much of a large diff is reviewed (`limits`).
 //! - Interacting with LLM providers (`llm`).
 //! - Performing Retrieval-Augmented Generation (`rag`).
 //! - Scanning for vulnerabilities and patterns (`scanner`).
 //! - Generating reports (`report`).
+//! - Reviewing a diff in one call, the supported embedding API (`review`).
+//! - Describing the report and configuration formats (`schema`).
+//! - Reading workspace files that list several repositories (`workspace`).
 
 // Public modules
+pub mod audit;
+pub mod blame;
 pub mod config;
+pub mod content;
+pub mod context;
 pub mod diff_parser;
 pub mod error;
+pub mod fingerprint;
+pub mod fix;
+pub mod limits;
 pub mod llm;
+pub mod paths;
+pub mod privacy;
+pub mod progress;
+pub mod publish;
 pub mod rag;
 pub mod report;
+pub mod review;
 pub mod scanner;
+pub mod schema;
 pub mod telemetry;
+pub mod workspace;
+
+pub use crate::fingerprint::fingerprint_issue;
+pub use crate::review::{render_report, review_diff, Format, ReviewOptions};
 
 use crate::config::{Config, Provider};
+use crate::content::{FileContentProvider, FsContentProvider};
+use crate::context::ReviewContext;
 use crate::error::{EngineError, Result};
-use crate::llm::{create_llm_provider, LlmProvider};
-use crate::rag::{InMemoryVectorStore, RagContextRetriever, VectorStore};
-use crate::report::{ReviewReport, RuntimeMetadata, TimingInfo};
-use crate::scanner::{Issue, Scanner};
-use crate::telemetry::Telemetry;
+use crate::llm::structured::{
+    parse_review, LlmSuggestion, INSTRUCTIONS, INTENT_FIELD_INSTRUCTIONS, INTENT_INSTRUCTIONS,
+};
+use crate::llm::{create_llm_provider, estimate_tokens, LlmProvider};
+use crate::paths::PathDecision;
+use crate::privacy::{Redactor, REDACTION_PLACEHOLDER};
+use crate::progress::{Observer, ProgressEvent};
+use crate::rag::{
+    create_embedder, Document, Embedder, InMemoryVectorStore, RagContextRetriever, VectorStore,
+};
+use crate::report::history::{self, SinceLastRun};
+use crate::report::{
+    i18n, summarize, summarize_rules, RetrievedContext, ReviewReport, ReviewStats, RuntimeMetadata,
+    SkippedFile, TimingInfo,
+};
+use crate::scanner::{
+    apply_suppressions, expired_suppressions, parse_ignore_directives, rule_applies_to,
+    rule_enabled, ConventionsScanner, DiffScanner, Issue, RuleCategory, ScanCache, ScanRequest,
+    Scanner,
+};
+use crate::telemetry::{Telemetry, TelemetrySink};
+use chrono::NaiveDate;
 use globset::{Glob, GlobSet, GlobSetBuilder};
-use regex::Regex;
-use std::collections::HashMap;
-use std::collections::HashSet;
-use std::fs;
-use std::path::Path;
-use std::time::Instant;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Returns the list of LLM providers compiled into this binary.
 pub fn compiled_providers() -> Vec<config::Provider> {
@@ -45,155 +88,634 @@ pub fn compiled_providers() -> Vec<config::Provider> {
     ]
 }
 
-/// Placeholder used when redacting sensitive information.
-const REDACTION_PLACEHOLDER: &str = "[REDACTED]";
-
 /// Version identifier for the ruleset bundled with the engine.
 const RULESET_VERSION: &str = "1.0.0";
 
+/// Summary of a change whose files all match `[paths] docs-globs`.
+pub const DOCS_ONLY_SUMMARY: &str = "Documentation-only change, no analysis performed.";
+
 /// Redacts sensitive information from the provided text based on the
-/// configured redaction patterns.
+/// configured redaction patterns and rules.
+///
+/// This compiles the patterns on every call; prefer a long-lived
+/// `privacy::Redactor` on hot paths. If a pattern is invalid, the whole text
+/// is replaced rather than risk leaking it.
 pub fn redact_text(config: &Config, text: &str) -> String {
-    if !config.privacy.redaction.enabled || config.privacy.redaction.patterns.is_empty() {
-        return text.to_string();
-    }
-
-    let mut redacted = text.to_string();
-    for pattern in &config.privacy.redaction.patterns {
-        if let Ok(re) = Regex::new(pattern) {
-            redacted = re.replace_all(&redacted, REDACTION_PLACEHOLDER).to_string();
+    match Redactor::new(&config.privacy.redaction) {
+        Ok(redactor) => redactor.redact(text),
+        Err(e) => {
+            log::error!("{}", e);
+            REDACTION_PLACEHOLDER.to_string()
         }
     }
-    redacted
-}
-
-/// Provides a simple on-device summary when no external LLM is configured.
-fn fallback_summary(file_count: usize, issues: &[Issue]) -> String {
-    let mut summary = format!(
-        "Reviewed {} file{}",
-        file_count,
-        if file_count == 1 { "" } else { "s" }
-    );
-    if issues.is_empty() {
-        summary.push_str(" with no issues found.");
-    } else {
-        summary.push_str(&format!(
-            " and found {} issue{}.",
-            issues.len(),
-            if issues.len() == 1 { "" } else { "s" }
-        ));
-        let highlights: Vec<String> = issues
-            .iter()
-            .take(5)
-            .map(|i| format!("{} in {}:{}", i.title, i.file_path, i.line_number))
-            .collect();
-        if !highlights.is_empty() {
-            summary.push_str(" Notable findings: ");
-            summary.push_str(&highlights.join("; "));
-        }
-    }
-    summary
 }
 
 /// The main engine struct.
 pub struct ReviewEngine {
     config: Config,
-    scanners: Vec<Box<dyn Scanner>>,
+    scanners: Vec<(&'static str, Box<dyn Scanner>)>,
+    diff_scanners: Vec<(&'static str, Box<dyn DiffScanner>)>,
     llm: Box<dyn LlmProvider>,
-    telemetry: Option<Telemetry>,
+    telemetry: Vec<Box<dyn TelemetrySink>>,
+    redactor: Redactor,
+    embedder: Arc<dyn Embedder>,
+    vector_store: Option<Arc<dyn VectorStore + Send + Sync>>,
+    head_commit: Option<String>,
+    files: Vec<String>,
+    context: Option<ReviewContext>,
+    root: PathBuf,
+    today: Option<NaiveDate>,
+    branch: Option<String>,
+    scan_cache: Option<Mutex<ScanCache>>,
 }
 
-impl ReviewEngine {
-    /// Creates a new instance of the review engine from a given configuration.
-    pub fn new(config: Config) -> Result<Self> {
-        let llm = create_llm_provider(&config)?;
-        let scanners = crate::scanner::load_enabled_scanners(&config);
-        let telemetry = Telemetry::from_config(&config.telemetry)?;
-        Ok(Self {
+/// Builds a `ReviewEngine`, letting library users supply their own LLM
+/// provider, vector store, embedder, or scanners in place of the ones the
+/// configuration would create.
+pub struct ReviewEngineBuilder {
+    config: Config,
+    llm: Option<Box<dyn LlmProvider>>,
+    vector_store: Option<Arc<dyn VectorStore + Send + Sync>>,
+    embedder: Option<Arc<dyn Embedder>>,
+    scanners: Option<Vec<(&'static str, Box<dyn Scanner>)>>,
+    diff_scanners: Option<Vec<(&'static str, Box<dyn DiffScanner>)>>,
+    telemetry: Vec<Box<dyn TelemetrySink>>,
+}
+
+impl ReviewEngineBuilder {
+    /// Uses `llm` instead of the provider configured in `[llm]`.
+    pub fn llm(mut self, llm: Box<dyn LlmProvider>) -> Self {
+        self.llm = Some(llm);
+        self
+    }
+
+    /// Uses `store` for RAG context instead of loading `[index].path`.
+    pub fn vector_store(mut self, store: Box<dyn VectorStore + Send + Sync>) -> Self {
+        self.vector_store = Some(Arc::from(store));
+        self
+    }
+
+    /// Uses `embedder` for RAG queries instead of the one configured in
+    /// `[rag]`. It must match the embedder that built the vector store.
+    pub fn embedder(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Runs `scanners`, each paired with its rule id, instead of the
+    /// built-in scanners enabled in `[rules]`. Rule ids are matched against
+    /// overrides and inline ignore directives.
+    pub fn scanners(mut self, scanners: Vec<(&'static str, Box<dyn Scanner>)>) -> Self {
+        self.scanners = Some(scanners);
+        self
+    }
+
+    /// Runs `scanners` over each file's diff, instead of the built-in diff
+    /// scanners enabled in `[rules]`.
+    pub fn diff_scanners(mut self, scanners: Vec<(&'static str, Box<dyn DiffScanner>)>) -> Self {
+        self.diff_scanners = Some(scanners);
+        self
+    }
+
+    /// Also records every run in `sink`, alongside the sinks configured in
+    /// `[telemetry]`.
+    pub fn telemetry_sink(mut self, sink: Box<dyn TelemetrySink>) -> Self {
+        self.telemetry.push(sink);
+        self
+    }
+
+    /// Builds the engine, creating anything not supplied from the
+    /// configuration. The vector index is loaded once, here.
+    pub fn build(self) -> Result<ReviewEngine> {
+        let config = self.config;
+        config.check_policy()?;
+        for warning in config.path_warnings(None) {
+            log::warn!("{}", warning);
+        }
+        let llm = match self.llm {
+            Some(llm) => llm,
+            None => create_llm_provider(&config)?,
+        };
+        let index = match self.vector_store {
+            _ if !config.rag.enabled => {
+                log::debug!("RAG is disabled; not loading the vector index");
+                None
+            }
+            Some(_) => None,
+            None => load_index(&config),
+        };
+        // Overrides may enable a rule for part of the tree, so load every
+        // scanner and decide per file.
+        let scanners = match self.scanners {
+            Some(scanners) => scanners,
+            None => {
+                let mut scanners = if config.overrides.is_empty() {
+                    crate::scanner::load_rule_scanners(|rule| rule_enabled(&config, rule))
+                } else {
+                    crate::scanner::load_rule_scanners(|_| true)
+                };
+                // Derive the convention baselines from the index loaded
+                // above rather than reading it again.
+                if let Some(store) = &index {
+                    for (rule, scanner) in scanners.iter_mut() {
+                        if *rule == "conventions" {
+                            *scanner =
+                                Box::new(ConventionsScanner::from_documents(store.documents()));
+                        }
+                    }
+                }
+                scanners.extend(crate::scanner::load_external_scanners(&config));
+                scanners
+            }
+        };
+        let diff_scanners = match self.diff_scanners {
+            Some(scanners) => scanners,
+            None if config.overrides.is_empty() => {
+                crate::scanner::load_diff_rule_scanners(|rule| rule_enabled(&config, rule))
+            }
+            None => crate::scanner::load_diff_rule_scanners(|_| true),
+        };
+        let embedder = match self.embedder {
+            Some(embedder) => embedder,
+            None => create_embedder(&config)?,
+        };
+        let vector_store = self
+            .vector_store
+            .or_else(|| index.map(|store| Arc::new(store) as Arc<dyn VectorStore + Send + Sync>))
+            .filter(|_| config.rag.enabled);
+        let redactor = Redactor::new(&config.privacy.redaction)?;
+        let mut telemetry: Vec<Box<dyn TelemetrySink>> = Vec::new();
+        if let Some(file) = Telemetry::from_config(&config.telemetry)? {
+            telemetry.push(Box::new(file.with_redactor(redactor.clone())));
+        }
+        #[cfg(feature = "otel")]
+        if let Some(otlp) = telemetry::otlp::OtlpSink::from_config(&config, redactor.clone()) {
+            telemetry.push(Box::new(otlp));
+        }
+        #[cfg(not(feature = "otel"))]
+        if config.telemetry.enabled && config.telemetry.otlp_endpoint.is_some() {
+            log::warn!(
+                "`[telemetry] otlp-endpoint` requires the `otel` feature; spans are not exported"
+            );
+        }
+        telemetry.extend(self.telemetry);
+        let scan_cache = ScanCache::from_config(&config).map(Mutex::new);
+        Ok(ReviewEngine {
             config,
             scanners,
+            diff_scanners,
             llm,
             telemetry,
+            redactor,
+            embedder,
+            vector_store,
+            head_commit: None,
+            files: Vec::new(),
+            context: None,
+            root: PathBuf::from("."),
+            today: None,
+            branch: None,
+            scan_cache,
         })
     }
+}
+
+impl ReviewEngine {
+    /// Creates a new instance of the review engine from a given configuration.
+    pub fn new(config: Config) -> Result<Self> {
+        Self::builder(config).build()
+    }
+
+    /// Starts building an engine from `config`; see `ReviewEngineBuilder`.
+    pub fn builder(config: Config) -> ReviewEngineBuilder {
+        ReviewEngineBuilder {
+            config,
+            llm: None,
+            vector_store: None,
+            embedder: None,
+            scanners: None,
+            diff_scanners: None,
+            telemetry: Vec::new(),
+        }
+    }
+
+    /// Replaces the LLM provider built from the configuration, for example
+    /// to use a custom or stub provider.
+    pub fn with_llm_provider(mut self, llm: Box<dyn LlmProvider>) -> Self {
+        self.llm = llm;
+        self
+    }
+
+    /// Replaces the embedder built from the configuration. It must match the
+    /// embedder that built the index for RAG context to be used.
+    pub fn with_embedder(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.embedder = embedder;
+        self
+    }
+
+    /// Sets the repository `HEAD` commit under review. The RAG index is
+    /// considered fresh when it was built from this commit, and `{commit}`
+    /// in `[report] link-template` resolves to it.
+    pub fn with_head_commit(mut self, head: impl Into<String>) -> Self {
+        self.head_commit = Some(head.into());
+        self
+    }
+
+    /// Sets the repository root that `run` and `run_with_progress` read
+    /// changed files from. Defaults to the current working directory, which
+    /// the engine never changes.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = root.into();
+        self
+    }
+
+    /// Names the branch under review, so that findings are compared with
+    /// its latest run in `[report] history-path`. Without a branch no
+    /// history is read.
+    pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = Some(branch.into());
+        self
+    }
+
+    /// Sets the date that `until=` attributes on ignore directives are
+    /// compared against. Defaults to the local date when a review runs.
+    pub fn with_today(mut self, today: NaiveDate) -> Self {
+        self.today = Some(today);
+        self
+    }
+
+    /// Restricts reviews to the changed files matching `patterns`, which are
+    /// paths or globs relative to the repository root. The filter applies
+    /// after `[paths]`; a run fails with `EngineError::Config` if it leaves
+    /// no file to review.
+    pub fn with_files(mut self, patterns: Vec<String>) -> Self {
+        self.files = patterns
+            .into_iter()
+            .map(|p| p.trim_start_matches("./").to_string())
+            .collect();
+        self
+    }
+
+    /// Sets the title and description of the change, which the summary
+    /// prompt includes so the LLM can compare them with the changes. An
+    /// empty context is ignored.
+    pub fn with_context(mut self, context: ReviewContext) -> Self {
+        self.context = Some(context).filter(|context| !context.is_empty());
+        self
+    }
 
     /// Returns a reference to the engine's configuration.
     pub fn config(&self) -> &Config {
         &self.config
     }
 
-    /// Runs a complete code review analysis on a given diff.
+    /// Returns the redactor compiled from the engine's privacy settings.
+    pub fn redactor(&self) -> &Redactor {
+        &self.redactor
+    }
+
+    /// Redacts the diff and suggested fix of each finding before they reach
+    /// the RAG query, the prompt, or the report. Scanners often quote the
+    /// offending line, which may hold a secret they did not mask themselves.
+    fn redact_findings(&self, found: &mut [Issue]) {
+        for issue in found {
+            if let Some(diff) = &issue.diff {
+                issue.diff = Some(self.redactor.redact(diff));
+            }
+            if let Some(fix) = &issue.suggested_fix {
+                issue.suggested_fix = Some(self.redactor.redact(fix));
+            }
+            // A redacted fix no longer matches the file, so it is dropped
+            // rather than written to the report with the secret in it.
+            if issue.fix.as_ref().is_some_and(|fix| {
+                self.redactor.redact(&fix.find) != fix.find
+                    || self.redactor.redact(&fix.replace) != fix.replace
+            }) {
+                issue.fix = None;
+            }
+        }
+    }
+
+    /// Captures the code around each finding from `content` for the report,
+    /// with secret values masked and the redaction rules applied.
+    fn attach_snippets(&self, found: &mut [Issue], content: &str) {
+        let context = self.config.report.snippet_context;
+        for issue in found {
+            issue.snippet =
+                report::snippet::extract(content, issue.line_number, context).map(|snippet| {
+                    self.redactor
+                        .redact(&scanner::secrets::mask_values(&snippet))
+                });
+        }
+    }
+
+    /// Keeps the files matching `with_files`, counting the rest in `stats`.
+    /// Warns about patterns that match nothing, and fails if no file is
+    /// left.
+    fn select_files(
+        &self,
+        files: Vec<diff_parser::ChangedFile>,
+        stats: &mut ReviewStats,
+    ) -> Result<Vec<diff_parser::ChangedFile>> {
+        if self.files.is_empty() {
+            return Ok(files);
+        }
+        let selected = build_globset(&self.files)?;
+        for pattern in &self.files {
+            let glob = build_globset(std::slice::from_ref(pattern))?;
+            if !files.iter().any(|file| glob.is_match(&file.path)) {
+                log::warn!(
+                    "`{}` does not match any reviewable file in the diff",
+                    pattern
+                );
+            }
+        }
+        let changed: Vec<String> = files.iter().map(|file| file.path.clone()).collect();
+        let (kept, excluded): (Vec<_>, Vec<_>) = files
+            .into_iter()
+            .partition(|file| selected.is_match(&file.path));
+        if kept.is_empty() {
+            return Err(EngineError::Config(format!(
+                "none of the requested files ({}) are in the diff; changed files: {}",
+                self.files.join(", "),
+                if changed.is_empty() {
+                    "none".to_string()
+                } else {
+                    changed.join(", ")
+                }
+            )));
+        }
+        stats.files_excluded = excluded.len();
+        Ok(kept)
+    }
+
+    /// Counts `found` in the run statistics under `scanner` and reports each
+    /// finding to `observer`.
+    fn record_findings(&self, stats: &mut ReviewStats, found: &[Issue], observer: &Observer<'_>) {
+        for issue in found {
+            observer.emit(ProgressEvent::Finding {
+                path: issue.file_path.clone(),
+                line: issue.line_number,
+                rule: issue.rule_id.clone(),
+            });
+        }
+        for issue in found {
+            *stats
+                .issues_by_severity
+                .entry(issue.severity.clone())
+                .or_default() += 1;
+            *stats
+                .issues_by_rule
+                .entry(issue.rule_id.clone())
+                .or_default() += 1;
+        }
+    }
+
+    /// Scans `content` as the file at `path`, without a diff, git, the LLM
+    /// or RAG, and returns the findings ordered by line.
+    ///
+    /// Every line counts as changed, and diff-only rules such as
+    /// `removed-controls` do not run. The `[paths]` globs, `[[overrides]]`,
+    /// `reviewlens:ignore` directives and `[report] min-confidence` apply as
+    /// in a review, and findings carry fingerprints. Nothing is loaded per
+    /// call, so editors can call this on every change to a buffer.
+    pub fn scan_content(&self, path: &str, content: &str) -> Result<Vec<Issue>> {
+        let decision = paths::explain(&self.config.paths, &[path.to_string()])?;
+        if !decision
+            .iter()
+            .all(|explanation| explanation.decision.is_kept())
+        {
+            log::debug!("Not scanning {}: excluded by [paths]", path);
+            return Ok(Vec::new());
+        }
+        let today = self
+            .today
+            .unwrap_or_else(|| chrono::Local::now().date_naive());
+        let file_config = self.config.for_path(path)?;
+        let ignores = parse_ignore_directives(path, content);
+        let request = ScanRequest::whole_file(path, content, &file_config);
+        let mut stats = ReviewStats::default();
+        let mut issues = Vec::new();
+        for (rule, scanner) in &self.scanners {
+            if !rule_enabled(&file_config, rule) || !rule_applies_to(rule, path) {
+                continue;
+            }
+            let mut found = scanner.scan_with_context(&request)?;
+            for issue in &mut found {
+                issue.rule_id = rule.to_string();
+            }
+            apply_suppressions(&mut found, &ignores, today);
+            self.redact_findings(&mut found);
+            drop_unconfident(&mut found, &file_config, &mut stats);
+            issues.append(&mut found);
+        }
+        fingerprint::assign(&mut issues, content);
+        issues.sort_by(|a, b| {
+            (a.line_number, &a.rule_id, &a.title).cmp(&(b.line_number, &b.rule_id, &b.title))
+        });
+        Ok(issues)
+    }
+
+    /// Runs a complete code review analysis on a given diff, reading changed
+    /// files relative to the repository root set by `with_root`.
     pub async fn run(&self, diff: &str) -> Result<ReviewReport> {
+        self.run_with_provider(diff, &FsContentProvider::new(&self.root))
+            .await
+    }
+
+    /// Runs a complete code review analysis on a given diff, reading changed
+    /// files relative to the repository root and sending a
+    /// [`ProgressEvent`] to `progress` at each step.
+    pub async fn run_with_progress(
+        &self,
+        diff: &str,
+        progress: Option<Sender<ProgressEvent>>,
+    ) -> Result<ReviewReport> {
+        self.run_with_provider_and_progress(diff, &FsContentProvider::new(&self.root), progress)
+            .await
+    }
+
+    /// Runs a complete code review analysis on a given diff, reading changed
+    /// files through `provider` instead of the filesystem.
+    pub async fn run_with_provider(
+        &self,
+        diff: &str,
+        provider: &dyn FileContentProvider,
+    ) -> Result<ReviewReport> {
+        self.run_with_provider_and_progress(diff, provider, None)
+            .await
+    }
+
+    /// Runs a complete code review analysis on a given diff, reading changed
+    /// files through `provider` and sending a [`ProgressEvent`] to
+    /// `progress` at each step.
+    pub async fn run_with_provider_and_progress(
+        &self,
+        diff: &str,
+        provider: &dyn FileContentProvider,
+        progress: Option<Sender<ProgressEvent>>,
+    ) -> Result<ReviewReport> {
         log::info!("Engine running with config: {:?}", self.config);
         log::debug!("Analyzing diff: {}", diff);
         let start_time = Instant::now();
-        if let Some(t) = &self.telemetry {
-            t.run_started();
-        }
+        let observer = Observer::new(progress, &self.telemetry);
+        observer.emit(ProgressEvent::Started);
 
         let mut total_tokens_used: u32 = 0;
+        let today = self
+            .today
+            .unwrap_or_else(|| chrono::Local::now().date_naive());
 
         // 1. Parse the diff to identify changed files and hunks.
         let changed_files = diff_parser::parse(diff)?;
 
-        // Build globsets for allowed and denied paths.
-        let allow_set = build_globset(&self.config.paths.allow)?;
-        let deny_set = build_globset(&self.config.paths.deny)?;
-
-        // Filter changed files based on glob patterns.
+        // Filter changed files based on glob patterns. Files without hunks
+        // (binary or mode-only changes) have nothing to review, except that
+        // the `artifacts` rule checks the files a change adds.
+        let artifacts = rule_enabled(&self.config, "artifacts");
+        let changed_paths: Vec<String> = changed_files.iter().map(|f| f.path.clone()).collect();
+        let decisions = paths::explain(&self.config.paths, &changed_paths)?;
+        let mut stats = ReviewStats::default();
         let filtered_files: Vec<_> = changed_files
             .into_iter()
-            .filter(|file| {
-                let path = Path::new(&file.path);
-                allow_set.is_match(path) && !deny_set.is_match(path)
+            .zip(decisions)
+            .filter(|(file, explanation)| {
+                let keep = match &explanation.decision {
+                    PathDecision::Kept { .. }
+                        if file.hunks.is_empty() && !(artifacts && file.added) =>
+                    {
+                        log::debug!("Dropping {}: no hunks to review", file.path);
+                        false
+                    }
+                    PathDecision::Kept { pattern } => {
+                        log::debug!("Keeping {}: matches allow pattern `{}`", file.path, pattern);
+                        true
+                    }
+                    PathDecision::Denied { pattern } => {
+                        log::debug!("Dropping {}: matches deny pattern `{}`", file.path, pattern);
+                        false
+                    }
+                    PathDecision::NotAllowed => {
+                        log::debug!("Dropping {}: matches no allow pattern", file.path);
+                        false
+                    }
+                };
+                if !keep {
+                    stats.files_skipped += 1;
+                }
+                keep
             })
+            .map(|(file, _)| file)
             .collect();
+        let filtered_files = self.select_files(filtered_files, &mut stats)?;
+        let (filtered_files, skipped) = limits::select(filtered_files, &self.config.limits);
+        stats.files_skipped += skipped.len();
+        stats.files_reviewed = filtered_files.len();
+        stats.skipped_for_limits = skipped;
+        let docs_only = self.docs_only(&filtered_files)?;
+        if docs_only {
+            log::info!("Documentation-only change; skipping scanners and the LLM");
+        }
+        observer.emit(ProgressEvent::ParsedDiff {
+            files: filtered_files.len(),
+        });
+        let mapper = diff_parser::LineMapper::new(&filtered_files);
 
         // Track line churn per file; hotspots are computed after scanning.
-        let mut churn_counts: HashMap<String, usize> = HashMap::new();
+        let mut churn_counts: BTreeMap<String, usize> = BTreeMap::new();
         for file in &filtered_files {
-            let mut changes = 0usize;
-            for hunk in &file.hunks {
-                for line in &hunk.lines {
-                    match line {
-                        diff_parser::Line::Added(_) | diff_parser::Line::Removed(_) => {
-                            changes += 1;
-                        }
-                        diff_parser::Line::Context(_) => {}
-                    }
-                }
-            }
-            churn_counts.insert(file.path.clone(), changes);
+            let (added, removed) = (file.additions(), file.deletions());
+            stats.lines_added += added;
+            stats.lines_removed += removed;
+            churn_counts.insert(file.path.clone(), added + removed);
         }
 
         // 2. Run configured scanners on the filtered files, limiting results to diff hunks.
         let mut issues = Vec::new();
         let mut code_quality = Vec::new();
-        let file_paths: Vec<String> = filtered_files.iter().map(|f| f.path.clone()).collect();
-        let mut interactions = HashSet::new();
-        for file in &filtered_files {
-            let content = fs::read_to_string(&file.path)?;
-            let mut changed_lines = HashSet::new();
-            for hunk in &file.hunks {
-                let mut new_line = hunk.new_start as usize;
-                for line in &hunk.lines {
-                    match line {
-                        diff_parser::Line::Added(_) => {
-                            changed_lines.insert(new_line);
-                            new_line += 1;
-                        }
-                        diff_parser::Line::Context(_) => {
-                            new_line += 1;
-                        }
-                        diff_parser::Line::Removed(_) => {}
+        let mut sources = Vec::new();
+        let mut newest_change = None;
+        let mut suppressed = Vec::new();
+        let scanned: &[_] = if docs_only { &[] } else { &filtered_files };
+        for (index, file) in scanned.iter().enumerate() {
+            observer.emit(ProgressEvent::ScanningFile {
+                path: file.path.clone(),
+                index: index + 1,
+                total: scanned.len(),
+            });
+            // A deleted file has no content left to read or scan; only the
+            // diff scanners look at the lines it removed.
+            let content = if file.deleted {
+                stats.files_reviewed -= 1;
+                stats.files_skipped += 1;
+                String::new()
+            } else {
+                // A missing or unreadable file should not sink the whole review.
+                match provider.read(&file.path) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        log::warn!("Skipping {}: {}", file.path, e);
+                        stats.files_reviewed -= 1;
+                        stats.files_skipped += 1;
+                        stats.unreadable.push(SkippedFile {
+                            path: file.path.clone(),
+                            reason: unreadable_reason(&e),
+                        });
+                        continue;
                     }
                 }
-            }
+            };
+            newest_change = newest_change.max(provider.modified(&file.path));
+            let changed_lines = diff_parser::changed_new_lines(file);
 
-            for scanner in &self.scanners {
-                let mut found = scanner.scan(&file.path, &content, &self.config)?;
+            let ignores = parse_ignore_directives(&file.path, &content);
+
+            let file_config = self.config.for_path(&file.path)?;
+            let file_start = issues.len();
+            let mut expired = Vec::new();
+            let digest = self.scan_cache.as_ref().map(|_| {
+                crate::scanner::cache::request_digest(&ScanRequest {
+                    path: &file.path,
+                    content: &content,
+                    config: &file_config,
+                    changed_lines: Some(&changed_lines),
+                    hunks: &file.hunks,
+                })
+            });
+            for (rule, scanner) in &self.scanners {
+                if file.deleted
+                    || !rule_enabled(&file_config, rule)
+                    || !rule_applies_to(rule, &file.path)
+                {
+                    continue;
+                }
+                let request = ScanRequest {
+                    path: &file.path,
+                    content: &content,
+                    config: &file_config,
+                    changed_lines: Some(&changed_lines),
+                    hunks: &file.hunks,
+                };
+                let scan_start = Instant::now();
+                let mut found =
+                    self.scan_cached(rule, scanner.as_ref(), &request, digest.as_deref())?;
                 found.retain(|issue| changed_lines.contains(&issue.line_number));
-                if scanner.name() == "Convention Deviation Scanner" {
+                for issue in &mut found {
+                    issue.rule_id = rule.to_string();
+                }
+                expired.extend(expired_suppressions(&found, &ignores, today));
+                let dropped = apply_suppressions(&mut found, &ignores, today);
+                stats.suppressed += dropped.len();
+                suppressed.extend(dropped);
+                self.redact_findings(&mut found);
+                drop_unconfident(&mut found, &file_config, &mut stats);
+                observer.emit(ProgressEvent::ScannerFinished {
+                    name: scanner.name(),
+                    path: file.path.clone(),
+                    findings: found.len(),
+                    elapsed: scan_start.elapsed(),
+                });
+                if scanner.category() == RuleCategory::Quality {
                     for issue in found {
                         code_quality.push(format!(
                             "{}:{} - {}",
@@ -201,54 +723,93 @@ impl ReviewEngine {
                         ));
                     }
                 } else {
-                    if let Some(t) = &self.telemetry {
-                        for issue in &found {
-                            t.finding(&issue.file_path, issue.line_number, &issue.title);
-                        }
-                    }
+                    self.record_findings(&mut stats, &found, &observer);
                     issues.append(&mut found);
                 }
             }
 
-            for other in &file_paths {
-                if other == &file.path {
+            // Diff scanners report deleted lines against a nearby new-file
+            // line, which need not be a changed one, so only ignore
+            // directives filter their findings.
+            for (rule, scanner) in &self.diff_scanners {
+                if !rule_enabled(&file_config, rule) {
                     continue;
                 }
-                let stem = Path::new(other)
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("");
-                if content.contains(&format!("use {}", stem))
-                    || content.contains(&format!("{}::", stem))
-                {
-                    interactions.insert((file.path.clone(), other.clone()));
+                let scan_start = Instant::now();
+                let mut found =
+                    scanner.scan_diff_sized(file, provider.size(&file.path), &file_config)?;
+                for issue in &mut found {
+                    issue.rule_id = rule.to_string();
                 }
+                expired.extend(expired_suppressions(&found, &ignores, today));
+                let dropped = apply_suppressions(&mut found, &ignores, today);
+                stats.suppressed += dropped.len();
+                suppressed.extend(dropped);
+                self.redact_findings(&mut found);
+                drop_unconfident(&mut found, &file_config, &mut stats);
+                observer.emit(ProgressEvent::ScannerFinished {
+                    name: scanner.name(),
+                    path: file.path.clone(),
+                    findings: found.len(),
+                    elapsed: scan_start.elapsed(),
+                });
+                if scanner.category() == RuleCategory::Quality {
+                    for issue in found {
+                        code_quality.push(format!(
+                            "{}:{} - {}",
+                            issue.file_path, issue.line_number, issue.description
+                        ));
+                    }
+                } else {
+                    self.record_findings(&mut stats, &found, &observer);
+                    issues.append(&mut found);
+                }
+            }
+            for note in expired {
+                code_quality.push(format!(
+                    "{}:{} - {}",
+                    note.file_path, note.line_number, note.description
+                ));
+            }
+            fingerprint::assign(&mut issues[file_start..], &content);
+            self.attach_snippets(&mut issues[file_start..], &content);
+            for issue in &mut issues[file_start..] {
+                issue.diff_position = mapper.position_in_diff(&issue.file_path, issue.line_number);
+            }
+
+            if !file.deleted {
+                sources.push((file.path.clone(), content));
+            }
+        }
+        if let Some(cache) = &self.scan_cache {
+            if let Err(e) = cache.lock().unwrap().save() {
+                log::warn!("Failed to write the scan cache: {}", e);
             }
         }
 
-        // 3. Perform lightweight flow extraction for sequence diagram.
-        let interactions: Vec<(String, String)> = interactions.into_iter().collect();
-        let mermaid_diagram = if interactions.len() >= 3 {
-            let mut diagram = String::from("sequenceDiagram\n");
-            for (from, to) in &interactions {
-                let from = Path::new(from)
-                    .file_name()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or(from);
-                let to = Path::new(to)
-                    .file_name()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or(to);
-                diagram.push_str(&format!("    {}->>{}: uses\n", from, to));
+        if let Some(template) = &self.config.report.link_template {
+            for issue in &mut issues {
+                issue.url = report::links::issue_url(
+                    template,
+                    self.head_commit.as_deref(),
+                    &issue.file_path,
+                    issue.line_number,
+                );
             }
-            Some(diagram)
+        }
+
+        code_quality.sort();
+        suppressed.sort_by(|a, b| (&a.path, a.line, &a.rule).cmp(&(&b.path, b.line, &b.rule)));
+
+        // 3. Perform lightweight flow extraction for the interaction diagram.
+        let mermaid_diagram = if self.config.report.diagram {
+            report::diagram::render(&sources, self.config.report.diagram_max_edges)
         } else {
             None
         };
 
-        // 4. Retrieve RAG context for flagged regions.
-        // Aggregate hotspots using configurable severity and churn weights.
-        let mut issue_counts: HashMap<String, usize> = HashMap::new();
+        // 4. Aggregate hotspots using configurable severity and churn weights.
+        let mut issue_counts: BTreeMap<String, usize> = BTreeMap::new();
         for issue in &issues {
             *issue_counts.entry(issue.file_path.clone()).or_insert(0) += 1;
         }
@@ -262,100 +823,124 @@ impl ReviewEngine {
                 (path, risk)
             })
             .collect();
-        file_risks.sort_by(|a, b| b.1.cmp(&a.1));
+        file_risks.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        file_risks.retain(|(_, risk)| *risk > 0 && !docs_only);
+        file_risks.truncate(5);
+        if self.config.report.escalate_hotspots {
+            let hot: Vec<&str> = file_risks.iter().map(|(path, _)| path.as_str()).collect();
+            escalate_hotspots(
+                &mut issues,
+                &hot,
+                self.config.report.escalate_by,
+                &mut stats,
+            );
+        }
+        // Order findings independently of scan order so identical runs
+        // produce identical reports. Escalation comes first, as it changes
+        // severities.
+        sort_issues(&mut issues);
         let hotspots: Vec<String> = file_risks
             .into_iter()
-            .filter(|(_, risk)| *risk > 0)
-            .take(5)
             .map(|(path, risk)| format!("{path} (risk {risk})"))
             .collect();
 
-        // 3. Retrieve RAG context for flagged regions.
-        let (vector_store, index_warm): (Box<dyn VectorStore + Send + Sync>, bool) =
-            if let Some(path) = self.config.index_path() {
-                match InMemoryVectorStore::load_from_disk(path) {
-                    Ok(store) => (Box::new(store), true),
-                    Err(e) => {
-                        log::warn!("Failed to load vector index from {}: {}", path, e);
-                        (Box::new(InMemoryVectorStore::default()), false)
-                    }
+        // 5. Check that the RAG index still reflects the working tree.
+        let mut notes = Vec::new();
+        if let Some(note) = limits::note(&stats.skipped_for_limits) {
+            log::warn!("{}", note);
+            notes.push(note);
+        }
+        for skipped in &stats.unreadable {
+            notes.push(format!(
+                "`{}` could not be read and was not reviewed: {}.",
+                skipped.path, skipped.reason
+            ));
+        }
+        let index = self.vector_store.clone();
+        let index_documents = index.as_ref().and_then(|store| store.document_count());
+        let mut index_fresh = index.is_some();
+        if let Some(store) = &index {
+            let max_age_days = self.config.index.clone().unwrap_or_default().max_age_days;
+            let max_age = Duration::from_secs(max_age_days.saturating_mul(86_400));
+            match store.staleness(self.head_commit.as_deref(), newest_change, max_age) {
+                Some(reason) => {
+                    log::warn!("RAG index is stale: {}", reason);
+                    notes.push(format!(
+                        "The RAG index is stale ({}); run `reviewlens index` to refresh it.",
+                        reason
+                    ));
+                    index_fresh = false;
                 }
-            } else {
-                (Box::new(InMemoryVectorStore::default()), false)
-            };
-        let rag = RagContextRetriever::new(vector_store);
-        let mut contexts = Vec::new();
-        for issue in &issues {
-            if let Ok(ctx) = rag
-                .retrieve(&format!(
-                    "{}:{} {}",
-                    issue.file_path, issue.line_number, issue.description
-                ))
-                .await
-            {
-                contexts.push(ctx);
+                None => log::debug!("RAG index is fresh"),
             }
         }
 
-        // 5. Prepare the prompt for the LLM.
-        let mut prompt = String::new();
-        if !contexts.is_empty() {
-            prompt.push_str("Context:\n");
-            prompt.push_str(&contexts.join("\n\n"));
-            prompt.push_str("\n\n");
-        }
-        prompt.push_str(&format!(
-            "Provide a review summary for the following issues: {:?}",
-            issues
-        ));
-
-        // 6. Redact issue descriptions and contexts before calling the LLM.
-        let redacted_issues: Vec<String> = issues
-            .iter()
-            .map(|issue| {
-                let redacted_desc = redact_text(&self.config, &issue.description);
-                format!(
-                    "{}:{} {} - {}",
-                    issue.file_path, issue.line_number, issue.title, redacted_desc
-                )
-            })
-            .collect();
-        let redacted_contexts: Vec<String> = contexts
-            .iter()
-            .map(|c| redact_text(&self.config, c))
-            .collect();
-        let prompt = format!(
-            "Provide a review summary for the following issues:\n{}\nContext:\n{}",
-            redacted_issues.join("\n"),
-            redacted_contexts.join("\n")
-        );
-
-        // 7. Produce a summary either via LLM or fallback routine.
-        let summary = if self.config.llm.provider == Provider::Null {
-            fallback_summary(filtered_files.len(), &issues)
+        // 6. Produce a summary either via LLM or the deterministic fallback.
+        // The null provider never sees a prompt, so offline runs skip RAG
+        // retrieval entirely.
+        let mut index_warm = false;
+        let mut llm_cache_hit = false;
+        let mut llm_error = None;
+        let mut llm_parse_error = None;
+        let mut retrieval_ms = 0;
+        let mut budget_exhausted = false;
+        let mut intent_note = None;
+        let mut contexts = Vec::new();
+        let summary = if docs_only {
+            DOCS_ONLY_SUMMARY.to_string()
+        } else if self.config.llm.provider == Provider::Null {
+            summarize(&issues, &stats)
         } else {
-            if let Some(max) = self.config.budget.tokens.max_per_run {
-                if total_tokens_used >= max {
-                    return Err(EngineError::TokenBudgetExceeded {
-                        used: total_tokens_used,
-                        max,
-                    });
+            let retriever = index.and_then(|store| {
+                match RagContextRetriever::for_store(store, self.embedder.clone()) {
+                    Ok(retriever) => Some(retriever),
+                    Err(e) => {
+                        log::warn!("Skipping RAG context: {}", e);
+                        None
+                    }
                 }
-            }
-            let llm_response = self.llm.generate(&prompt).await?;
-            total_tokens_used = total_tokens_used.saturating_add(llm_response.token_usage);
-            if let Some(max) = self.config.budget.tokens.max_per_run {
-                if total_tokens_used > max {
-                    return Err(EngineError::TokenBudgetExceeded {
-                        used: total_tokens_used,
-                        max,
-                    });
+            });
+            index_warm = index_fresh && retriever.is_some();
+            // An empty index cannot contribute context, so skip embedding
+            // the queries at all.
+            let retriever = retriever.filter(|_| {
+                let empty = index_documents == Some(0);
+                if empty {
+                    log::debug!("RAG index has no documents; skipping retrieval");
+                }
+                !empty
+            });
+            let outcome = self
+                .llm_summary(&issues, &sources, retriever.as_ref(), &observer)
+                .await?;
+            total_tokens_used = total_tokens_used.saturating_add(outcome.tokens_used);
+            llm_cache_hit = outcome.cache_hit;
+            llm_error = outcome.error;
+            llm_parse_error = outcome.parse_error;
+            retrieval_ms = outcome.retrieval_ms;
+            budget_exhausted = outcome.budget_exhausted;
+            notes.extend(outcome.note);
+            intent_note = outcome.intent_note;
+            contexts = outcome.contexts;
+            for suggestion in outcome.suggestions {
+                match issues.get_mut(suggestion.issue_index) {
+                    Some(issue) => {
+                        issue.llm_comment =
+                            Some(suggestion.comment).filter(|comment| !comment.is_empty());
+                        issue.llm_patch = suggestion.suggested_patch;
+                    }
+                    None => log::debug!(
+                        "Ignoring LLM suggestion for unknown finding {}",
+                        suggestion.issue_index
+                    ),
                 }
             }
-            llm_response.content
+            outcome
+                .summary
+                .unwrap_or_else(|| summarize(&issues, &stats))
         };
 
-        // 8. Build and return the ReviewReport.
+        // 7. Record run metadata.
         let elapsed_ms = start_time.elapsed().as_millis();
         let issue_count = issues.len();
         let metadata = RuntimeMetadata {
@@ -364,26 +949,603 @@ impl ReviewEngine {
             driver: self.config.llm.provider.as_str().to_string(),
             timings: TimingInfo {
                 total_ms: elapsed_ms,
+                retrieval_ms,
             },
             index_warm,
+            rag_enabled: self.config.rag.enabled,
+            index_documents,
+            llm_cache_hit,
+            llm_error,
+            budget_exhausted,
+            llm_parse_error,
+            docs_only,
         };
 
-        // 9. Build and return the ReviewReport.
+        // 8. Build and return the ReviewReport.
+        stats.tokens_used = total_tokens_used;
+        let rules = if self.config.report.group_fixes {
+            summarize_rules(&issues)
+        } else {
+            Vec::new()
+        };
+        let since_last_run = self.since_last_run(&issues);
+        let policy_violations = self
+            .config
+            .policy
+            .count_violations(&stats.issues_by_severity);
         let report = ReviewReport {
             summary,
+            stats,
             issues,
             code_quality,
             hotspots,
             mermaid_diagram,
             config: self.config.clone(),
             metadata,
+            notes,
+            suppressed,
+            rules,
+            context: self.context.clone(),
+            intent_note,
+            since_last_run,
+            contexts,
+            policy_violations,
         };
-        if let Some(t) = &self.telemetry {
-            t.run_finished(issue_count, elapsed_ms);
-        }
+        observer.emit(ProgressEvent::Done {
+            findings: issue_count,
+            tokens: total_tokens_used,
+            elapsed: start_time.elapsed(),
+        });
+        observer.flush().await;
 
         Ok(report)
     }
+
+    /// Whether every file in `files` matches `[paths] docs-globs`, so the
+    /// change needs no analysis. An empty diff or glob list never does.
+    fn docs_only(&self, files: &[diff_parser::ChangedFile]) -> Result<bool> {
+        if files.is_empty() || self.config.paths.docs_globs.is_empty() {
+            return Ok(false);
+        }
+        let docs = build_globset(&self.config.paths.docs_globs)?;
+        Ok(files
+            .iter()
+            .all(|file| docs.is_match(Path::new(&file.path))))
+    }
+
+    /// Runs `scanner` on `request`, reusing the findings cached under
+    /// `digest` for the same rule and scanner version. Without a scan cache
+    /// the scanner always runs.
+    fn scan_cached(
+        &self,
+        rule: &str,
+        scanner: &dyn Scanner,
+        request: &ScanRequest,
+        digest: Option<&str>,
+    ) -> Result<Vec<Issue>> {
+        let (Some(cache), Some(digest)) = (&self.scan_cache, digest) else {
+            return scanner.scan_with_context(request);
+        };
+        let key = crate::scanner::cache::key(rule, scanner.version(), digest);
+        if let Some(found) = cache.lock().unwrap().get(&key) {
+            log::debug!("Scan cache hit for {} in {}", rule, request.path);
+            return Ok(found);
+        }
+        let found = scanner.scan_with_context(request)?;
+        cache.lock().unwrap().insert(key, found.clone());
+        Ok(found)
+    }
+
+    /// Compares `issues` with the latest run of the branch in the history,
+    /// if there is one. A history that cannot be read is logged and skipped.
+    fn since_last_run(&self, issues: &[Issue]) -> Option<SinceLastRun> {
+        let path = self.config.report.history_path.as_ref()?;
+        let branch = self.branch.as_deref()?;
+        match history::latest(Path::new(path), branch) {
+            Ok(previous) => previous.map(|previous| SinceLastRun::compare(&previous, issues)),
+            Err(e) => {
+                log::warn!("Ignoring the run history: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Summarizes `issues` with the LLM.
+    ///
+    /// If the prompt would exceed `[llm] max-prompt-tokens` or
+    /// `budget.tokens.max-per-run`, the findings are split into batches with
+    /// one call each, and the partial summaries are merged by a final
+    /// synthesis call when the budget allows. Before every call the tokens
+    /// used so far plus the estimated prompt size are checked against
+    /// `max-per-run`; once it would be exceeded no further calls are made and
+    /// `budget_exhausted` is set.
+    async fn llm_summary(
+        &self,
+        issues: &[Issue],
+        sources: &[(String, String)],
+        rag: Option<&RagContextRetriever>,
+        observer: &Observer<'_>,
+    ) -> Result<LlmSummary> {
+        let budget = self.config.budget.tokens.max_per_run;
+        let limit = [self.config.llm.max_prompt_tokens, budget]
+            .into_iter()
+            .flatten()
+            .min();
+        let mut outcome = LlmSummary::default();
+        let retrieval_start = Instant::now();
+        let retrieved = match rag {
+            Some(rag) => {
+                observer.emit(ProgressEvent::RetrievingContext {
+                    findings: issues.len(),
+                });
+                let retrieved = self.retrieve_contexts(issues, sources, rag).await;
+                observer.emit(ProgressEvent::ContextRetrieved {
+                    elapsed: retrieval_start.elapsed(),
+                });
+                retrieved
+            }
+            None => vec![Vec::new(); issues.len()],
+        };
+        outcome.retrieval_ms = retrieval_start.elapsed().as_millis();
+        if self.config.report.include_context {
+            outcome.contexts = self.report_contexts(&retrieved);
+        }
+        let changed: Vec<&str> = sources.iter().map(|(path, _)| path.as_str()).collect();
+        let intent = self
+            .context
+            .as_ref()
+            .map(|context| context.prompt_section(&self.redactor, &changed));
+        let prompts = self.batched_prompts(issues, &retrieved, limit, intent.as_deref());
+        if prompts.len() > 1 {
+            log::info!(
+                "Summarizing {} findings in {} LLM calls",
+                issues.len(),
+                prompts.len()
+            );
+        }
+
+        let mut partials = Vec::new();
+        let mut all_cached = true;
+        for prompt in &prompts {
+            if !within_budget(budget, outcome.tokens_used, prompt) {
+                outcome.budget_exhausted = true;
+                break;
+            }
+            match self.generate(prompt, &mut outcome, observer).await? {
+                Some((content, cached)) => {
+                    all_cached &= cached;
+                    partials.push(self.read_structured(content, &mut outcome));
+                }
+                None => return Ok(outcome),
+            }
+        }
+        outcome.cache_hit = !partials.is_empty() && all_cached;
+
+        if partials.len() < prompts.len() {
+            log::warn!(
+                "Token budget exhausted after {} of {} LLM calls",
+                partials.len(),
+                prompts.len()
+            );
+            outcome.note = Some(format!(
+                "The token budget ran out after {} of {} LLM calls; the summary covers only part of the findings.",
+                partials.len(),
+                prompts.len()
+            ));
+        }
+
+        outcome.summary = match partials.len() {
+            0 => None,
+            1 => partials.pop(),
+            _ => {
+                let prompt = synthesis_prompt(&partials, &self.config.report.language);
+                let fits = limit.is_none_or(|limit| estimate_tokens(&prompt) <= limit);
+                if !fits {
+                    log::info!("Partial summaries are too long to synthesize; joining them");
+                } else if !within_budget(budget, outcome.tokens_used, &prompt) {
+                    outcome.budget_exhausted = true;
+                } else if let Some((content, cached)) =
+                    self.generate(&prompt, &mut outcome, observer).await?
+                {
+                    outcome.cache_hit &= cached;
+                    return Ok(LlmSummary {
+                        summary: Some(content),
+                        ..outcome
+                    });
+                } else {
+                    // The partial summaries are still worth reporting.
+                    outcome.error = None;
+                }
+                Some(partials.join("\n\n"))
+            }
+        };
+        Ok(outcome)
+    }
+
+    /// With `[llm] structured-output`, parses `content` as an `LlmReview`,
+    /// collecting its suggestions into `outcome` and returning its summary.
+    /// A response that does not parse is returned whole as the summary, and
+    /// the parse error is recorded.
+    fn read_structured(&self, content: String, outcome: &mut LlmSummary) -> String {
+        if !self.config.llm.structured_output {
+            if self.context.is_none() {
+                return content;
+            }
+            let (summary, note) = context::split_intent(&content);
+            if outcome.intent_note.is_none() {
+                outcome.intent_note = note;
+            }
+            return summary;
+        }
+        match parse_review(&content) {
+            Ok(review) => {
+                outcome.suggestions.extend(review.suggestions);
+                if outcome.intent_note.is_none() {
+                    outcome.intent_note = review.intent.filter(|note| !note.trim().is_empty());
+                }
+                review.summary
+            }
+            Err(e) => {
+                log::warn!("Could not parse structured LLM output: {}", e);
+                outcome.parse_error.get_or_insert(e);
+                content
+            }
+        }
+    }
+
+    /// Sends `prompt` to the provider, adding its usage to `outcome`. Returns
+    /// the response content and whether it was cached, or `None` after
+    /// recording a provider failure the run can recover from.
+    async fn generate(
+        &self,
+        prompt: &str,
+        outcome: &mut LlmSummary,
+        observer: &Observer<'_>,
+    ) -> Result<Option<(String, bool)>> {
+        observer.emit(ProgressEvent::CallingLlm);
+        let call_start = Instant::now();
+        let result = self.llm.generate(prompt).await;
+        observer.emit(match &result {
+            Ok(response) => ProgressEvent::LlmFinished {
+                tokens: response.token_usage,
+                cached: response.cached,
+                error: None,
+                elapsed: call_start.elapsed(),
+            },
+            Err(e) => ProgressEvent::LlmFinished {
+                tokens: 0,
+                cached: false,
+                error: Some(e.to_string()),
+                elapsed: call_start.elapsed(),
+            },
+        });
+        match result {
+            Ok(response) => {
+                outcome.tokens_used = outcome.tokens_used.saturating_add(response.token_usage);
+                Ok(Some((response.content, response.cached)))
+            }
+            // A failing or hung provider should not throw away scanner
+            // findings unless the configuration requires the LLM.
+            Err(e @ (EngineError::LlmProvider(_) | EngineError::LlmTimeout(_)))
+                if !self.config.llm.required =>
+            {
+                log::warn!("{}; using scanner-only summary", e);
+                outcome.error = Some(e.to_string());
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Builds one prompt per batch of `issues`, halving batches until each
+    /// prompt's estimated size is within `limit`. A single finding that is
+    /// still too large has its prompt truncated.
+    fn batched_prompts(
+        &self,
+        issues: &[Issue],
+        retrieved: &[Vec<(Document, f32)>],
+        limit: Option<u32>,
+        intent: Option<&str>,
+    ) -> Vec<String> {
+        let mut prompts = Vec::new();
+        // Ranges still to be built, popped front-first to keep issue order.
+        let mut pending = vec![(0, issues.len())];
+        while let Some((start, end)) = pending.pop() {
+            let prompt =
+                self.summary_prompt(&issues[start..end], start, &retrieved[start..end], intent);
+            match limit {
+                Some(limit) if estimate_tokens(&prompt) > limit && end - start > 1 => {
+                    let mid = start + (end - start) / 2;
+                    pending.push((mid, end));
+                    pending.push((start, mid));
+                }
+                Some(limit) if estimate_tokens(&prompt) > limit => {
+                    log::warn!(
+                        "Prompt for {}:{} exceeds {} tokens; truncating it",
+                        issues[start].file_path,
+                        issues[start].line_number,
+                        limit
+                    );
+                    prompts.push(truncate_to_tokens(&prompt, limit));
+                }
+                _ => prompts.push(prompt),
+            }
+        }
+        prompts
+    }
+
+    /// Retrieves RAG context for every finding in one batch, before any
+    /// prompt is built, so that splitting the findings into several prompts
+    /// does not repeat the search. Each finding is looked up by its title
+    /// and the source lines around it in `sources`. A failed retrieval
+    /// leaves every finding without context.
+    async fn retrieve_contexts(
+        &self,
+        issues: &[Issue],
+        sources: &[(String, String)],
+        rag: &RagContextRetriever,
+    ) -> Vec<Vec<(Document, f32)>> {
+        let window = self.config.rag.query_context_lines;
+        let queries: Vec<String> = issues
+            .iter()
+            .map(|issue| {
+                let content = sources
+                    .iter()
+                    .find(|(path, _)| *path == issue.file_path)
+                    .map(|(_, content)| content.as_str());
+                // Remote embedders see the query, so redact it like the prompt.
+                self.redactor
+                    .redact(&retrieval_query(issue, content, window))
+            })
+            .collect();
+        match rag.retrieve_many(&queries).await {
+            Ok(retrieved) => retrieved,
+            Err(e) => {
+                log::warn!("Failed to retrieve RAG context: {}", e);
+                vec![Vec::new(); issues.len()]
+            }
+        }
+    }
+
+    /// Lists the documents in `retrieved` that reach the prompt, with their
+    /// excerpts redacted and cut to `[report] context-excerpt-chars`.
+    fn report_contexts(&self, retrieved: &[Vec<(Document, f32)>]) -> Vec<RetrievedContext> {
+        let max_chars = self.config.report.context_excerpt_chars;
+        retrieved
+            .iter()
+            .enumerate()
+            .flat_map(|(index, docs)| {
+                docs.iter()
+                    .filter(|(_, score)| *score > 0.0)
+                    .map(move |(doc, score)| (index, doc, *score))
+            })
+            .map(|(index, doc, score)| {
+                let text = self.redactor.redact(&doc.content);
+                let excerpt = match text.char_indices().nth(max_chars) {
+                    Some((end, _)) => format!("{}\u{2026}", &text[..end]),
+                    None => text,
+                };
+                RetrievedContext {
+                    query_issue_index: index,
+                    filename: doc.filename.clone(),
+                    score,
+                    excerpt,
+                }
+            })
+            .collect()
+    }
+
+    /// Builds the redacted LLM prompt for `issues`, enriched with the RAG
+    /// context `retrieved` for each of them, each document listed once.
+    /// With `[llm] structured-output`, findings are numbered from `offset`,
+    /// their position in the run, and the model is asked for JSON.
+    fn summary_prompt(
+        &self,
+        issues: &[Issue],
+        offset: usize,
+        retrieved: &[Vec<(Document, f32)>],
+        intent: Option<&str>,
+    ) -> String {
+        let contexts = rag::merge_retrieved(retrieved.iter().cloned());
+
+        // Redact issue descriptions and contexts before calling the LLM.
+        let structured = self.config.llm.structured_output;
+        let redacted_issues: Vec<String> = issues
+            .iter()
+            .enumerate()
+            .map(|(i, issue)| {
+                let redacted_desc = self.redactor.redact(&issue.description);
+                let line = format!(
+                    "{}:{} {} - {}",
+                    issue.file_path, issue.line_number, issue.title, redacted_desc
+                );
+                if structured {
+                    format!("[{}] {}", offset + i, line)
+                } else {
+                    line
+                }
+            })
+            .collect();
+        let redacted_contexts: Vec<String> =
+            contexts.iter().map(|c| self.redactor.redact(c)).collect();
+        let mut prompt = intent.map_or(String::new(), |intent| format!("{}\n", intent));
+        prompt.push_str(&format!(
+            "Provide a review summary for the following issues:\n{}\nContext:\n{}",
+            redacted_issues.join("\n"),
+            redacted_contexts.join("\n")
+        ));
+        if structured {
+            prompt.push_str("\n\n");
+            prompt.push_str(INSTRUCTIONS);
+        }
+        if intent.is_some() {
+            prompt.push_str("\n\n");
+            prompt.push_str(if structured {
+                INTENT_FIELD_INSTRUCTIONS
+            } else {
+                INTENT_INSTRUCTIONS
+            });
+        }
+        if let Some(instruction) = i18n::prompt_instruction(&self.config.report.language) {
+            prompt.push_str("\n\n");
+            prompt.push_str(&instruction);
+        }
+        prompt
+    }
+}
+
+/// Outcome of summarizing findings with the LLM.
+#[derive(Default)]
+struct LlmSummary {
+    /// The merged summary, or `None` if no call succeeded.
+    summary: Option<String>,
+    /// Tokens reported by the provider across all calls.
+    tokens_used: u32,
+    /// Whether every response was served from the cache.
+    cache_hit: bool,
+    /// Whether calls were skipped to stay within `max-per-run`.
+    budget_exhausted: bool,
+    /// The provider failure that ended the summary, if any.
+    error: Option<String>,
+    /// Report note explaining a partial summary.
+    note: Option<String>,
+    /// Comments on findings from structured responses.
+    suggestions: Vec<LlmSuggestion>,
+    /// Why a structured response could not be parsed, if one could not.
+    parse_error: Option<String>,
+    /// Time spent retrieving RAG context, in milliseconds.
+    retrieval_ms: u128,
+    /// The LLM's note comparing the stated intent with the changes.
+    intent_note: Option<String>,
+    /// The retrieved documents listed under `[report] include-context`.
+    contexts: Vec<RetrievedContext>,
+}
+
+/// Whether a call with `prompt` keeps usage within the run budget `max`.
+/// Nothing fits once the budget is used up.
+fn within_budget(max: Option<u32>, used: u32, prompt: &str) -> bool {
+    max.is_none_or(|max| used < max && used.saturating_add(estimate_tokens(prompt)) <= max)
+}
+
+/// Builds the prompt merging per-batch summaries into one, written in
+/// `language`.
+fn synthesis_prompt(partials: &[String], language: &str) -> String {
+    let mut prompt = format!(
+        "Combine the following partial review summaries, each covering a subset of the findings, into a single review summary:\n\n{}",
+        partials.join("\n\n---\n\n")
+    );
+    if let Some(instruction) = i18n::prompt_instruction(language) {
+        prompt.push_str("\n\n");
+        prompt.push_str(&instruction);
+    }
+    prompt
+}
+
+/// Cuts `prompt` to roughly `limit` tokens.
+fn truncate_to_tokens(prompt: &str, limit: u32) -> String {
+    let max_chars = usize::try_from(limit)
+        .unwrap_or(usize::MAX)
+        .saturating_mul(4);
+    prompt.chars().take(max_chars).collect()
+}
+
+/// The RAG query for a finding: its title followed by up to `window` source
+/// lines on each side of it. Falls back to the description when the file
+/// contents are unavailable.
+fn retrieval_query(issue: &Issue, content: Option<&str>, window: usize) -> String {
+    let Some(content) = content else {
+        return format!("{}\n{}", issue.title, issue.description);
+    };
+    let line = issue.line_number.max(1);
+    let first = line.saturating_sub(window).max(1);
+    let snippet: Vec<&str> = content
+        .lines()
+        .skip(first - 1)
+        .take(line + window + 1 - first)
+        .collect();
+    format!("{}\n{}", issue.title, snippet.join("\n"))
+}
+
+/// Raises the severity of each issue in one of the `hotspots` files by
+/// `levels`, recording the scanner's severity, and moves its count in
+/// `stats` accordingly.
+fn escalate_hotspots(issues: &mut [Issue], hotspots: &[&str], levels: u8, stats: &mut ReviewStats) {
+    for issue in issues
+        .iter_mut()
+        .filter(|issue| hotspots.contains(&issue.file_path.as_str()))
+    {
+        let raised = issue.severity.raised(levels);
+        if raised == issue.severity {
+            continue;
+        }
+        log::info!(
+            "Escalating {} at {}:{} from {} to {} (hotspot)",
+            issue.rule_id,
+            issue.file_path,
+            issue.line_number,
+            issue.severity.as_str(),
+            raised.as_str()
+        );
+        if let Some(count) = stats.issues_by_severity.get_mut(&issue.severity) {
+            *count -= 1;
+            if *count == 0 {
+                stats.issues_by_severity.remove(&issue.severity);
+            }
+        }
+        *stats.issues_by_severity.entry(raised.clone()).or_default() += 1;
+        let original = std::mem::replace(&mut issue.severity, raised);
+        issue.original_severity = Some(original);
+    }
+}
+
+/// Drops the findings below `[report] min-confidence`, counting them.
+fn drop_unconfident(found: &mut Vec<Issue>, config: &Config, stats: &mut ReviewStats) {
+    let before = found.len();
+    found.retain(|issue| issue.confidence >= config.report.min_confidence);
+    stats.below_min_confidence += before - found.len();
+}
+
+/// Sorts findings by severity, highest first, then by file, line, rule and
+/// title.
+fn sort_issues(issues: &mut [Issue]) {
+    issues.sort_by(|a, b| {
+        b.severity.cmp(&a.severity).then_with(|| {
+            (&a.file_path, a.line_number, &a.rule_id, &a.title).cmp(&(
+                &b.file_path,
+                b.line_number,
+                &b.rule_id,
+                &b.title,
+            ))
+        })
+    });
+}
+
+/// Explains why a changed file could not be read, without the local path
+/// the error may carry.
+fn unreadable_reason(e: &EngineError) -> String {
+    match e {
+        EngineError::Io { source, .. } if source.kind() == std::io::ErrorKind::NotFound => {
+            "the file does not exist".to_string()
+        }
+        EngineError::Io { source, .. } => source.to_string(),
+        e => e.to_string(),
+    }
+}
+
+/// Loads the vector index configured in `[index].path`, if any.
+fn load_index(config: &Config) -> Option<InMemoryVectorStore> {
+    let path = config.index_path()?;
+    if !Path::new(path).exists() {
+        log::debug!("No vector index at {}", path);
+        return None;
+    }
+    match InMemoryVectorStore::load_from_disk(path) {
+        Ok(store) => Some(store),
+        Err(e) => {
+            log::warn!("Failed to load vector index from {}: {}", path, e);
+            None
+        }
+    }
 }
 
 fn build_globset(patterns: &[String]) -> Result<GlobSet> {
@@ -0,0 +1,110 @@
+//! Caps on how much of a diff is reviewed.
+//!
+//! When a diff exceeds `[limits]`, files are ranked so that hand-written
+//! source is reviewed first: non-vendored files before vendored ones, source
+//! extensions before everything else, then by churn (most changed lines
+//! first) and finally by path, which keeps the selection deterministic.
+//! Files are admitted in that order while they fit; the rest are skipped
+//! with the limit they hit.
+
+use crate::config::LimitsConfig;
+use crate::diff_parser::ChangedFile;
+use crate::report::SkippedFile;
+use std::cmp::Reverse;
+
+/// Directory names whose contents are treated as third-party code.
+const VENDOR_DIRS: &[&str] = &["vendor", "third_party", "third-party", "node_modules"];
+
+/// Extensions of files considered hand-written source code.
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "c", "cc", "cpp", "cs", "go", "h", "hpp", "java", "js", "jsx", "kt", "php", "py", "rb", "rs",
+    "scala", "sh", "swift", "ts", "tsx",
+];
+
+fn is_vendored(path: &str) -> bool {
+    path.split('/').any(|part| VENDOR_DIRS.contains(&part))
+}
+
+fn is_source(path: &str) -> bool {
+    path.rsplit_once('.')
+        .is_some_and(|(_, ext)| SOURCE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Splits `files` into those to review and those skipped for exceeding
+/// `limits`. Both keep the order of the diff.
+pub fn select(
+    files: Vec<ChangedFile>,
+    limits: &LimitsConfig,
+) -> (Vec<ChangedFile>, Vec<SkippedFile>) {
+    let churn: Vec<usize> = files
+        .iter()
+        .map(|f| f.additions() + f.deletions())
+        .collect();
+    let mut order: Vec<usize> = (0..files.len()).collect();
+    order.sort_by_key(|&i| {
+        let path = files[i].path.as_str();
+        (is_vendored(path), !is_source(path), Reverse(churn[i]), path)
+    });
+
+    let mut reasons: Vec<Option<String>> = vec![None; files.len()];
+    let (mut kept, mut total) = (0usize, 0usize);
+    for i in order {
+        let lines = churn[i];
+        reasons[i] = if limits.max_lines_per_file > 0 && lines > limits.max_lines_per_file {
+            Some(format!(
+                "{} changed lines exceed max-lines-per-file ({})",
+                lines, limits.max_lines_per_file
+            ))
+        } else if limits.max_files > 0 && kept >= limits.max_files {
+            Some(format!("max-files ({}) reached", limits.max_files))
+        } else if limits.max_total_lines > 0 && total + lines > limits.max_total_lines {
+            Some(format!(
+                "max-total-lines ({}) reached",
+                limits.max_total_lines
+            ))
+        } else {
+            kept += 1;
+            total += lines;
+            None
+        };
+    }
+
+    let mut selected = Vec::new();
+    let mut skipped = Vec::new();
+    for (file, reason) in files.into_iter().zip(reasons) {
+        match reason {
+            Some(reason) => skipped.push(SkippedFile {
+                path: file.path,
+                reason,
+            }),
+            None => selected.push(file),
+        }
+    }
+    (selected, skipped)
+}
+
+/// Number of skipped paths named in the report note.
+const NOTE_PATHS: usize = 5;
+
+/// Report note describing the files skipped by `select`, if any.
+pub fn note(skipped: &[SkippedFile]) -> Option<String> {
+    if skipped.is_empty() {
+        return None;
+    }
+    let mut paths: Vec<&str> = skipped
+        .iter()
+        .take(NOTE_PATHS)
+        .map(|s| s.path.as_str())
+        .collect();
+    let rest = skipped.len().saturating_sub(NOTE_PATHS);
+    let more = format!("{} more", rest);
+    if rest > 0 {
+        paths.push(&more);
+    }
+    Some(format!(
+        "{} file{} skipped due to size limits ({}). Raise `[limits]` or pass `--no-limits` to review them.",
+        skipped.len(),
+        if skipped.len() == 1 { "" } else { "s" },
+        paths.join(", ")
+    ))
+}
@@ -1,5 +1,6 @@
-use super::{LlmProvider, LlmResponse};
-use crate::error::{EngineError, Result};
+use super::{http_client, map_request_error, read_response, LlmProvider, LlmResponse, Timeouts};
+use crate::config::GenerationConfig;
+use crate::error::Result;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -9,21 +10,59 @@ pub struct AnthropicProvider {
     api_key: String,
     model: String,
     temperature: f32,
+    max_tokens: u32,
+    top_p: Option<f32>,
+    stop_sequences: Vec<String>,
+    system: Option<String>,
     base_url: String,
 }
 
 impl AnthropicProvider {
-    pub fn new(api_key: String, model: String, temperature: f32, base_url: Option<String>) -> Self {
+    pub fn new(
+        api_key: String,
+        model: String,
+        temperature: f32,
+        max_tokens: u32,
+        base_url: Option<String>,
+        timeouts: Timeouts,
+    ) -> Self {
         let base_url =
             base_url.unwrap_or_else(|| "https://api.anthropic.com/v1/messages".to_string());
         Self {
-            client: Client::new(),
+            client: http_client(timeouts),
             api_key,
             model,
             temperature,
+            max_tokens,
+            top_p: None,
+            stop_sequences: Vec::new(),
+            system: None,
             base_url,
         }
     }
+
+    /// Sends requests with `client`, e.g. one from
+    /// [`build_http_client`](super::build_http_client).
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Sends `max-tokens`, `top-p` and `stop` (as `stop_sequences`) from
+    /// `generation` with every request. Anthropic has no `seed` parameter, so
+    /// it is not sent.
+    pub fn with_generation(mut self, generation: &GenerationConfig) -> Self {
+        self.max_tokens = generation.max_tokens;
+        self.top_p = generation.top_p;
+        self.stop_sequences = generation.stop.clone();
+        self
+    }
+
+    /// Sends `system` as the system prompt with every request.
+    pub fn with_system(mut self, system: impl Into<String>) -> Self {
+        self.system = Some(system.into());
+        self
+    }
 }
 
 #[derive(Serialize)]
@@ -35,8 +74,15 @@ struct Message {
 #[derive(Serialize)]
 struct AnthropicRequest {
     model: String,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
     messages: Vec<Message>,
     temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop_sequences: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -62,14 +108,18 @@ impl LlmProvider for AnthropicProvider {
     async fn generate(&self, prompt: &str) -> Result<LlmResponse> {
         let req = AnthropicRequest {
             model: self.model.clone(),
+            max_tokens: self.max_tokens,
+            system: self.system.clone(),
             messages: vec![Message {
                 role: "user".into(),
                 content: prompt.to_string(),
             }],
             temperature: self.temperature,
+            top_p: self.top_p,
+            stop_sequences: self.stop_sequences.clone(),
         };
 
-        let res: AnthropicResponse = self
+        let response = self
             .client
             .post(&self.base_url)
             .header("x-api-key", &self.api_key)
@@ -77,10 +127,8 @@ impl LlmProvider for AnthropicProvider {
             .json(&req)
             .send()
             .await
-            .map_err(|e| EngineError::LlmProvider(e.to_string()))?
-            .json()
-            .await
-            .map_err(|e| EngineError::LlmProvider(e.to_string()))?;
+            .map_err(map_request_error)?;
+        let res: AnthropicResponse = read_response("Anthropic", response).await?;
 
         let content = res
             .content
@@ -94,6 +142,7 @@ impl LlmProvider for AnthropicProvider {
         Ok(LlmResponse {
             content,
             token_usage: tokens,
+            cached: false,
         })
     }
 }
@@ -0,0 +1,171 @@
+//! On-disk caching of LLM responses.
+//!
+//! Re-running a review on the same diff sends an identical prompt, so
+//! `CachingProvider` stores each response keyed by a SHA-256 of the provider,
+//! model, `[generation]` settings and prompt. A hit is returned without
+//! calling the wrapped provider and reports zero token usage. Entries expire
+//! after a TTL and the oldest are evicted once the cache exceeds its entry
+//! limit.
+
+use super::{LlmProvider, LlmResponse};
+use crate::config::Config;
+use crate::error::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A single cached response.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct CacheEntry {
+    content: String,
+    token_usage: u32,
+    /// Seconds since the Unix epoch when the entry was stored.
+    created: u64,
+}
+
+/// Wraps another provider and serves repeated prompts from disk.
+pub struct CachingProvider {
+    inner: Box<dyn LlmProvider + Send + Sync>,
+    path: PathBuf,
+    namespace: String,
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl CachingProvider {
+    /// Creates a cache stored at `path`. `namespace` identifies the provider,
+    /// model and sampling settings so different models never share entries.
+    pub fn new(
+        inner: Box<dyn LlmProvider + Send + Sync>,
+        path: impl Into<PathBuf>,
+        namespace: impl Into<String>,
+        ttl: Duration,
+        max_entries: usize,
+    ) -> Self {
+        let path = path.into();
+        let mut entries = load(&path);
+        evict(&mut entries, ttl, max_entries);
+        Self {
+            inner,
+            path,
+            namespace: namespace.into(),
+            ttl,
+            max_entries,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Creates a cache using the `[llm]` cache settings in `config`.
+    pub fn from_config(inner: Box<dyn LlmProvider + Send + Sync>, config: &Config) -> Self {
+        let namespace = format!(
+            "{}\0{}\0{:?}",
+            config.llm.provider.as_str(),
+            config.llm.model.as_deref().unwrap_or_default(),
+            config.generation
+        );
+        Self::new(
+            inner,
+            config.llm.cache_path(),
+            namespace,
+            Duration::from_secs(config.llm.cache_ttl_secs),
+            config.llm.cache_max_entries,
+        )
+    }
+
+    fn key(&self, prompt: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.namespace.as_bytes());
+        hasher.update([0]);
+        hasher.update(prompt.as_bytes());
+        hasher
+            .finalize()
+            .iter()
+            .fold(String::with_capacity(64), |mut hex, b| {
+                let _ = write!(hex, "{:02x}", b);
+                hex
+            })
+    }
+}
+
+#[async_trait]
+impl LlmProvider for CachingProvider {
+    async fn generate(&self, prompt: &str) -> Result<LlmResponse> {
+        let key = self.key(prompt);
+        {
+            let mut entries = self.entries.lock().unwrap();
+            evict(&mut entries, self.ttl, self.max_entries);
+            if let Some(entry) = entries.get(&key) {
+                log::info!("LLM cache hit ({} tokens saved)", entry.token_usage);
+                return Ok(LlmResponse {
+                    content: entry.content.clone(),
+                    token_usage: 0,
+                    cached: true,
+                });
+            }
+        }
+
+        let response = self.inner.generate(prompt).await?;
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(
+            key,
+            CacheEntry {
+                content: response.content.clone(),
+                token_usage: response.token_usage,
+                created: now_secs(),
+            },
+        );
+        evict(&mut entries, self.ttl, self.max_entries);
+        if let Err(e) = save(&self.path, &entries) {
+            log::warn!("failed to write LLM cache {}: {}", self.path.display(), e);
+        }
+        Ok(response)
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Drops expired entries, then the oldest entries beyond `max_entries`.
+fn evict(entries: &mut HashMap<String, CacheEntry>, ttl: Duration, max_entries: usize) {
+    let now = now_secs();
+    entries.retain(|_, e| now.saturating_sub(e.created) < ttl.as_secs());
+    if entries.len() > max_entries {
+        let mut by_age: Vec<(String, u64)> = entries
+            .iter()
+            .map(|(k, e)| (k.clone(), e.created))
+            .collect();
+        by_age.sort_by_key(|(_, created)| *created);
+        for (key, _) in by_age.into_iter().take(entries.len() - max_entries) {
+            entries.remove(&key);
+        }
+    }
+}
+
+/// Reads the cache file. A missing or unreadable cache starts empty.
+fn load(path: &Path) -> HashMap<String, CacheEntry> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        log::warn!("ignoring corrupt LLM cache {}: {}", path.display(), e);
+        HashMap::new()
+    })
+}
+
+fn save(path: &Path, entries: &HashMap<String, CacheEntry>) -> std::io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(entries)?)
+}
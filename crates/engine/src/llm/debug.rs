@@ -0,0 +1,138 @@
+//! Request and response logging for debugging prompts.
+//!
+//! With `[llm] debug-dir` set, `DebugLogProvider` writes every interaction
+//! to numbered files in that directory: `001-request.txt` holds the prompt
+//! and `001-response.json` the reply or the error, together with the model,
+//! temperature, token usage and latency. Both are redacted with the
+//! `[privacy.redaction]` rules. Numbering continues after the files already
+//! in the directory, so repeated runs do not overwrite each other. Failing
+//! to write a file is logged and never fails the review.
+
+use super::{LlmProvider, LlmResponse};
+use crate::config::Config;
+use crate::error::Result;
+use crate::privacy::Redactor;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Wraps another provider and logs each call to `dir`.
+pub struct DebugLogProvider {
+    inner: Box<dyn LlmProvider + Send + Sync>,
+    dir: PathBuf,
+    provider: String,
+    model: Option<String>,
+    temperature: Option<f32>,
+    redactor: Redactor,
+    next: AtomicUsize,
+}
+
+/// The contents of `NNN-response.json`.
+#[derive(Serialize)]
+struct DebugResponse<'a> {
+    provider: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    latency_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_usage: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cached: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl DebugLogProvider {
+    /// Creates a logger writing to `dir` with the `[llm]`, `[generation]`
+    /// and `[privacy.redaction]` settings in `config`. Returns
+    /// `EngineError::Config` if a redaction pattern is invalid.
+    pub fn from_config(
+        inner: Box<dyn LlmProvider + Send + Sync>,
+        dir: impl Into<PathBuf>,
+        config: &Config,
+    ) -> Result<Self> {
+        let dir = dir.into();
+        let next = AtomicUsize::new(last_number(&dir) + 1);
+        Ok(Self {
+            inner,
+            dir,
+            provider: config.llm.provider.as_str().to_string(),
+            model: config.llm.model.clone(),
+            temperature: config.generation.temperature,
+            redactor: Redactor::new(&config.privacy.redaction)?,
+            next,
+        })
+    }
+
+    fn write(&self, name: &str, content: &str) {
+        let path = self.dir.join(name);
+        let written = fs::create_dir_all(&self.dir).and_then(|()| fs::write(&path, content));
+        if let Err(e) = written {
+            log::warn!("failed to write LLM debug log {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Returns the highest `NNN-` prefix among the files in `dir`, or zero.
+fn last_number(dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.split_once('-')?.0.parse().ok()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[async_trait]
+impl LlmProvider for DebugLogProvider {
+    async fn generate(&self, prompt: &str) -> Result<LlmResponse> {
+        let n = self.next.fetch_add(1, Ordering::SeqCst);
+        let mut request = format!("provider: {}\n", self.provider);
+        if let Some(model) = &self.model {
+            request.push_str(&format!("model: {}\n", model));
+        }
+        if let Some(temperature) = self.temperature {
+            request.push_str(&format!("temperature: {}\n", temperature));
+        }
+        request.push('\n');
+        request.push_str(&self.redactor.redact(prompt));
+        self.write(&format!("{:03}-request.txt", n), &request);
+
+        let started = Instant::now();
+        let result = self.inner.generate(prompt).await;
+        let mut logged = DebugResponse {
+            provider: &self.provider,
+            model: self.model.as_deref(),
+            temperature: self.temperature,
+            latency_ms: started.elapsed().as_millis(),
+            token_usage: None,
+            cached: None,
+            content: None,
+            error: None,
+        };
+        match &result {
+            Ok(response) => {
+                logged.token_usage = Some(response.token_usage);
+                logged.cached = Some(response.cached);
+                logged.content = Some(self.redactor.redact(&response.content));
+            }
+            Err(e) => logged.error = Some(self.redactor.redact(&e.to_string())),
+        }
+        match serde_json::to_string_pretty(&logged) {
+            Ok(json) => self.write(&format!("{:03}-response.json", n), &json),
+            Err(e) => log::warn!("failed to serialize LLM debug log: {}", e),
+        }
+        result
+    }
+}
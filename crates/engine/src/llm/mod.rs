@@ -4,15 +4,21 @@
 //! interface for interacting with different Large Language Models (LLMs).
 //! It ensures that the core engine remains provider-agnostic.
 
-use crate::config::{Config, Provider};
+use crate::config::{Config, LlmConfig, Provider};
 use crate::error::{EngineError, Result};
 use async_trait::async_trait;
+use reqwest::{Client, Response};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::time::Duration;
 
 /// Represents a response from an LLM.
 pub struct LlmResponse {
     pub content: String,
     /// Number of tokens consumed to generate this response.
     pub token_usage: u32,
+    /// Whether the response was served from the on-disk cache.
+    pub cached: bool,
 }
 
 /// A trait for interacting with an LLM provider.
@@ -30,6 +36,12 @@ pub trait LlmProvider {
     async fn generate(&self, prompt: &str) -> Result<LlmResponse>;
 }
 
+/// Estimates the number of tokens in `text` at four characters per token,
+/// rounding up. Close enough for English prose and code to size prompts.
+pub fn estimate_tokens(text: &str) -> u32 {
+    u32::try_from(text.chars().count().div_ceil(4)).unwrap_or(u32::MAX)
+}
+
 /// The "null" provider for local-only/offline mode.
 pub struct NullProvider;
 
@@ -43,16 +55,142 @@ impl LlmProvider for NullProvider {
         Ok(LlmResponse {
             content: "This is a dummy response from the null provider.".to_string(),
             token_usage: tokens,
+            cached: false,
         })
     }
 }
 
+/// HTTP timeouts applied to provider requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Limit for the whole request, including reading the response.
+    pub request: Duration,
+    /// Limit for establishing the connection.
+    pub connect: Duration,
+}
+
+impl Timeouts {
+    /// Reads the `[llm]` timeout settings from `config`.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            request: Duration::from_secs(config.llm.timeout_secs),
+            connect: Duration::from_secs(config.llm.connect_timeout_secs),
+        }
+    }
+}
+
+/// Builds the HTTP client shared by the remote providers.
+pub(crate) fn http_client(timeouts: Timeouts) -> Client {
+    Client::builder()
+        .timeout(timeouts.request)
+        .connect_timeout(timeouts.connect)
+        .build()
+        .unwrap_or_else(|e| {
+            log::warn!("failed to configure HTTP client timeouts: {}", e);
+            Client::new()
+        })
+}
+
+/// Builds the HTTP client for the LLM providers from `[llm]`: its timeouts,
+/// an explicit `proxy` (otherwise reqwest honors `HTTPS_PROXY`, `HTTP_PROXY`
+/// and `NO_PROXY`), and the extra root certificates in `ca-bundle-path`.
+/// A bad proxy URL or certificate bundle is a configuration error.
+pub fn build_http_client(llm: &LlmConfig) -> Result<Client> {
+    let mut builder = Client::builder()
+        .timeout(Duration::from_secs(llm.timeout_secs))
+        .connect_timeout(Duration::from_secs(llm.connect_timeout_secs));
+    if let Some(url) = &llm.proxy {
+        let proxy = reqwest::Proxy::all(url)
+            .map_err(|e| EngineError::Config(format!("invalid llm.proxy {:?}: {}", url, e)))?
+            .no_proxy(reqwest::NoProxy::from_env());
+        builder = builder.proxy(proxy);
+    }
+    if let Some(path) = &llm.ca_bundle_path {
+        let invalid = |e: &dyn std::fmt::Display| {
+            EngineError::Config(format!("invalid llm.ca-bundle-path {}: {}", path, e))
+        };
+        let pem = std::fs::read(path).map_err(|e| invalid(&e))?;
+        let certificates = reqwest::Certificate::from_pem_bundle(&pem).map_err(|e| invalid(&e))?;
+        if certificates.is_empty() {
+            return Err(invalid(&"no PEM certificates found"));
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    builder
+        .build()
+        .map_err(|e| EngineError::Config(format!("failed to build the LLM HTTP client: {}", e)))
+}
+
+/// Maps a request failure to `EngineError::LlmTimeout` when it timed out.
+fn map_request_error(e: reqwest::Error) -> EngineError {
+    if e.is_timeout() {
+        EngineError::LlmTimeout(e.to_string())
+    } else {
+        EngineError::LlmProvider(e.to_string())
+    }
+}
+
+/// The error body returned by Anthropic (`{"type":"error","error":{...}}`)
+/// and by OpenAI-compatible APIs (`{"error":{...}}`).
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: ErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetail {
+    message: String,
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+}
+
+/// Reads a successful JSON response from `provider`. An error status becomes
+/// `EngineError::LlmProvider` carrying the message and type from the error
+/// body, or the raw body if it is not in the expected shape.
+async fn read_response<T: DeserializeOwned>(provider: &str, response: Response) -> Result<T> {
+    let status = response.status();
+    let body = response.text().await.map_err(map_request_error)?;
+    if !status.is_success() {
+        let detail = match serde_json::from_str::<ErrorBody>(&body) {
+            Ok(ErrorBody { error }) => match error.kind {
+                Some(kind) => format!("{} ({})", error.message, kind),
+                None => error.message,
+            },
+            Err(_) => body.trim().to_string(),
+        };
+        return Err(EngineError::LlmProvider(format!(
+            "{} returned {}: {}",
+            provider, status, detail
+        )));
+    }
+    serde_json::from_str(&body)
+        .map_err(|e| EngineError::LlmProvider(format!("invalid response from {}: {}", provider, e)))
+}
+
 pub mod anthropic;
+pub mod cache;
+pub mod debug;
 pub mod deepseek;
 pub mod openai;
+pub mod structured;
 
-/// Creates an `LlmProvider` instance based on configuration.
+/// Creates an `LlmProvider` instance based on configuration, wrapped in a
+/// `CachingProvider` when `[llm] cache = true` and in a `DebugLogProvider`
+/// when `[llm] debug-dir` is set.
 pub fn create_llm_provider(config: &Config) -> Result<Box<dyn LlmProvider>> {
+    let mut provider = create_base_provider(config)?;
+    if config.llm.cache && config.llm.provider != Provider::Null {
+        provider = Box::new(cache::CachingProvider::from_config(provider, config));
+    }
+    if let Some(dir) = &config.llm.debug_dir {
+        provider = Box::new(debug::DebugLogProvider::from_config(provider, dir, config)?);
+    }
+    Ok(provider)
+}
+
+fn create_base_provider(config: &Config) -> Result<Box<dyn LlmProvider + Send + Sync>> {
     match &config.llm.provider {
         Provider::Openai => {
             let api_key = config
@@ -65,12 +203,17 @@ pub fn create_llm_provider(config: &Config) -> Result<Box<dyn LlmProvider>> {
                     EngineError::Config("Missing model for OpenAI provider".into())
                 })?;
             let temperature = config.generation.temperature.unwrap_or(0.0);
-            Ok(Box::new(openai::OpenAiProvider::new(
-                api_key,
-                model,
-                temperature,
-                config.llm.base_url.clone(),
-            )))
+            Ok(Box::new(
+                openai::OpenAiProvider::new(
+                    api_key,
+                    model,
+                    temperature,
+                    config.llm.base_url.clone(),
+                    Timeouts::from_config(config),
+                )
+                .with_generation(&config.generation)
+                .with_client(build_http_client(&config.llm)?),
+            ))
         }
         Provider::Anthropic => {
             let api_key = config
@@ -82,12 +225,18 @@ pub fn create_llm_provider(config: &Config) -> Result<Box<dyn LlmProvider>> {
                 EngineError::Config("Missing model for Anthropic provider".into())
             })?;
             let temperature = config.generation.temperature.unwrap_or(0.0);
-            Ok(Box::new(anthropic::AnthropicProvider::new(
-                api_key,
-                model,
-                temperature,
-                config.llm.base_url.clone(),
-            )))
+            Ok(Box::new(
+                anthropic::AnthropicProvider::new(
+                    api_key,
+                    model,
+                    temperature,
+                    config.generation.max_tokens,
+                    config.llm.base_url.clone(),
+                    Timeouts::from_config(config),
+                )
+                .with_generation(&config.generation)
+                .with_client(build_http_client(&config.llm)?),
+            ))
         }
         Provider::Deepseek => {
             let api_key = config
@@ -100,12 +249,17 @@ pub fn create_llm_provider(config: &Config) -> Result<Box<dyn LlmProvider>> {
                     EngineError::Config("Missing model for DeepSeek provider".into())
                 })?;
             let temperature = config.generation.temperature.unwrap_or(0.0);
-            Ok(Box::new(deepseek::DeepSeekProvider::new(
-                api_key,
-                model,
-                temperature,
-                config.llm.base_url.clone(),
-            )))
+            Ok(Box::new(
+                deepseek::DeepSeekProvider::new(
+                    api_key,
+                    model,
+                    temperature,
+                    config.llm.base_url.clone(),
+                    Timeouts::from_config(config),
+                )
+                .with_generation(&config.generation)
+                .with_client(build_http_client(&config.llm)?),
+            ))
         }
         Provider::Null => Ok(Box::new(NullProvider)),
     }
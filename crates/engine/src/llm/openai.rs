@@ -1,5 +1,6 @@
-use super::{LlmProvider, LlmResponse};
-use crate::error::{EngineError, Result};
+use super::{http_client, map_request_error, read_response, LlmProvider, LlmResponse, Timeouts};
+use crate::config::GenerationConfig;
+use crate::error::Result;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -9,21 +10,52 @@ pub struct OpenAiProvider {
     api_key: String,
     model: String,
     temperature: f32,
+    max_tokens: Option<u32>,
+    top_p: Option<f32>,
+    seed: Option<u64>,
+    stop: Vec<String>,
     base_url: String,
 }
 
 impl OpenAiProvider {
-    pub fn new(api_key: String, model: String, temperature: f32, base_url: Option<String>) -> Self {
+    pub fn new(
+        api_key: String,
+        model: String,
+        temperature: f32,
+        base_url: Option<String>,
+        timeouts: Timeouts,
+    ) -> Self {
         let base_url =
             base_url.unwrap_or_else(|| "https://api.openai.com/v1/chat/completions".to_string());
         Self {
-            client: Client::new(),
+            client: http_client(timeouts),
             api_key,
             model,
             temperature,
+            max_tokens: None,
+            top_p: None,
+            seed: None,
+            stop: Vec::new(),
             base_url,
         }
     }
+
+    /// Sends requests with `client`, e.g. one from
+    /// [`build_http_client`](super::build_http_client).
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Sends `max-tokens`, `top-p`, `seed` and `stop` from `generation` with every
+    /// request.
+    pub fn with_generation(mut self, generation: &GenerationConfig) -> Self {
+        self.max_tokens = Some(generation.max_tokens);
+        self.top_p = generation.top_p;
+        self.seed = generation.seed;
+        self.stop = generation.stop.clone();
+        self
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -37,6 +69,14 @@ struct ChatCompletionRequest {
     model: String,
     messages: Vec<ChatMessage>,
     temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -53,8 +93,6 @@ struct ChatCompletionResponse {
 
 #[derive(Deserialize)]
 struct Usage {
-    prompt_tokens: u32,
-    completion_tokens: u32,
     total_tokens: u32,
 }
 
@@ -68,19 +106,21 @@ impl LlmProvider for OpenAiProvider {
                 content: prompt.to_string(),
             }],
             temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            top_p: self.top_p,
+            seed: self.seed,
+            stop: self.stop.clone(),
         };
 
-        let res: ChatCompletionResponse = self
+        let response = self
             .client
             .post(&self.base_url)
             .bearer_auth(&self.api_key)
             .json(&req)
             .send()
             .await
-            .map_err(|e| EngineError::LlmProvider(e.to_string()))?
-            .json()
-            .await
-            .map_err(|e| EngineError::LlmProvider(e.to_string()))?;
+            .map_err(map_request_error)?;
+        let res: ChatCompletionResponse = read_response("OpenAI", response).await?;
 
         let content = res
             .choices
@@ -91,6 +131,7 @@ impl LlmProvider for OpenAiProvider {
         Ok(LlmResponse {
             content,
             token_usage: tokens,
+            cached: false,
         })
     }
 }
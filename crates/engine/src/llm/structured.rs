@@ -0,0 +1,64 @@
+//! Structured LLM output for `[llm] structured-output = true`.
+//!
+//! The prompt numbers each finding and asks the model to answer with a JSON
+//! object holding an overall summary and one entry per finding it comments
+//! on. Models often wrap JSON in a code fence or add prose around it, so the
+//! response is parsed leniently: the first JSON object that matches
+//! [`LlmReview`] is used and everything around it is ignored.
+
+use serde::Deserialize;
+
+/// Appended to the prompt to request a structured response.
+pub const INSTRUCTIONS: &str = r#"Respond with a single JSON object and nothing else, in this shape:
+{"summary": "<overall review summary>", "suggestions": [{"issue_index": <number of the finding>, "comment": "<comment on the finding>", "suggested_patch": "<unified diff fixing it, or null>"}]}
+Only include suggestions for findings you have something to add to."#;
+
+/// Appended to a structured prompt that describes the change's intent.
+pub const INTENT_FIELD_INSTRUCTIONS: &str = r#"Also include an "intent" field in the JSON object: a short note on whether the changes match the described intent, naming changed areas the description does not mention."#;
+
+/// Appended to an unstructured prompt that describes the change's intent.
+pub const INTENT_INSTRUCTIONS: &str = "End with a paragraph starting with \"Intent vs. implementation:\" that says whether the changes match the described intent and names changed areas the description does not mention.";
+
+/// A structured review returned by the model.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LlmReview {
+    /// The overall review summary.
+    pub summary: String,
+    /// Comments on individual findings.
+    #[serde(default)]
+    pub suggestions: Vec<LlmSuggestion>,
+    /// How the changes compare with the described intent, when the prompt
+    /// described one.
+    #[serde(default)]
+    pub intent: Option<String>,
+}
+
+/// The model's comment on one finding.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LlmSuggestion {
+    /// The number the finding was given in the prompt.
+    pub issue_index: usize,
+    #[serde(default)]
+    pub comment: String,
+    #[serde(default)]
+    pub suggested_patch: Option<String>,
+}
+
+/// Parses the first JSON object in `response` that is a valid
+/// [`LlmReview`], skipping code fences and surrounding prose. Returns the
+/// parse error of the first candidate object if none is valid.
+pub fn parse_review(response: &str) -> Result<LlmReview, String> {
+    let mut first_error = None;
+    for (start, _) in response.match_indices('{') {
+        let mut values =
+            serde_json::Deserializer::from_str(&response[start..]).into_iter::<LlmReview>();
+        match values.next() {
+            Some(Ok(review)) => return Ok(review),
+            Some(Err(e)) => {
+                first_error.get_or_insert_with(|| e.to_string());
+            }
+            None => {}
+        }
+    }
+    Err(first_error.unwrap_or_else(|| "no JSON object in response".to_string()))
+}
@@ -0,0 +1,79 @@
+//! Explaining which changed files the `[paths]` globs keep.
+//!
+//! A file is reviewed when it matches an `allow` pattern and no `deny`
+//! pattern. Deny patterns win, so a denied file is reported as denied even
+//! when no allow pattern matches it either.
+
+use crate::config::PathsConfig;
+use crate::error::Result;
+use crate::rag::build_globset;
+use globset::GlobSet;
+
+/// Whether the `[paths]` globs keep a file, and which pattern decided it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathDecision {
+    /// The file matches the allow pattern and no deny pattern.
+    Kept { pattern: String },
+    /// The file matches the deny pattern.
+    Denied { pattern: String },
+    /// The file matches no allow pattern.
+    NotAllowed,
+}
+
+impl PathDecision {
+    /// Returns the kebab-case name of the decision.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PathDecision::Kept { .. } => "kept",
+            PathDecision::Denied { .. } => "denied",
+            PathDecision::NotAllowed => "not-allowed",
+        }
+    }
+
+    /// Returns `true` if the file is reviewed.
+    pub fn is_kept(&self) -> bool {
+        matches!(self, PathDecision::Kept { .. })
+    }
+
+    /// Returns the pattern that decided, if any matched.
+    pub fn pattern(&self) -> Option<&str> {
+        match self {
+            PathDecision::Kept { pattern } | PathDecision::Denied { pattern } => Some(pattern),
+            PathDecision::NotAllowed => None,
+        }
+    }
+}
+
+/// The decision for one changed file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathExplanation {
+    pub path: String,
+    pub decision: PathDecision,
+}
+
+/// Decides for each of `files`, in order, whether the `allow` and `deny`
+/// globs of `patterns` keep it. The first matching pattern of each list is
+/// reported. Fails if a pattern is not a valid glob.
+pub fn explain(patterns: &PathsConfig, files: &[String]) -> Result<Vec<PathExplanation>> {
+    let allow = build_globset(&patterns.allow)?;
+    let deny = build_globset(&patterns.deny)?;
+    Ok(files
+        .iter()
+        .map(|path| {
+            let first = |set: &GlobSet, list: &[String]| {
+                set.matches(path).into_iter().min().map(|i| list[i].clone())
+            };
+            let decision = if let Some(pattern) = first(&deny, &patterns.deny) {
+                PathDecision::Denied { pattern }
+            } else if let Some(pattern) = first(&allow, &patterns.allow) {
+                PathDecision::Kept { pattern }
+            } else {
+                PathDecision::NotAllowed
+            };
+            PathExplanation {
+                path: path.clone(),
+                decision,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,154 @@
+//! Redaction of sensitive text before it leaves the machine.
+//!
+//! Patterns from `[privacy.redaction]` are compiled once into a `Redactor`,
+//! which the engine uses for LLM prompts and the CLI uses for written
+//! reports. An invalid pattern is a configuration error rather than a
+//! silently skipped rule, so a typo cannot let secrets through.
+
+use crate::config::{RedactionConfig, RedactionRule};
+use crate::error::{EngineError, Result};
+use regex::{Captures, Regex};
+use serde_json::Value;
+use std::cell::Cell;
+
+/// Placeholder used when redacting sensitive information.
+pub const REDACTION_PLACEHOLDER: &str = "[REDACTED]";
+
+/// Number of characters kept at each end of a partially masked value. Values
+/// shorter than four times this are masked entirely.
+const PARTIAL_KEEP: usize = 2;
+
+thread_local! {
+    static COMPILED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns how many redaction patterns have been compiled on the current
+/// thread. Lets callers check that hot paths reuse a `Redactor`.
+pub fn compiled_pattern_count() -> usize {
+    COMPILED.with(Cell::get)
+}
+
+/// A precompiled set of redaction rules.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    rules: Vec<(Regex, RedactionRule)>,
+}
+
+impl Redactor {
+    /// Compiles the configured patterns and rules. Returns
+    /// `EngineError::Config` naming the first pattern that fails to compile.
+    /// A disabled configuration yields a redactor that changes nothing.
+    pub fn new(config: &RedactionConfig) -> Result<Self> {
+        if !config.enabled {
+            return Ok(Self::default());
+        }
+        let patterns = config
+            .patterns
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (format!("patterns[{}]", i), RedactionRule::new(p)));
+        let rules = config
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (format!("rules[{}].pattern", i), r.clone()));
+
+        let mut compiled = Vec::new();
+        for (path, rule) in patterns.chain(rules) {
+            let re = Regex::new(&rule.pattern).map_err(|e| {
+                EngineError::Config(format!(
+                    "invalid regex `{}` at privacy.redaction.{}: {}",
+                    rule.pattern,
+                    path,
+                    e.to_string().lines().last().unwrap_or_default().trim()
+                ))
+            })?;
+            COMPILED.with(|c| c.set(c.get() + 1));
+            compiled.push((re, rule));
+        }
+        Ok(Self { rules: compiled })
+    }
+
+    /// Returns `true` if no rules are active.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Redacts every match in `text`. When a pattern has a capture group
+    /// only group 1 is replaced, so surrounding context such as a key name
+    /// survives.
+    pub fn redact(&self, text: &str) -> String {
+        let mut redacted = text.to_string();
+        for (re, rule) in &self.rules {
+            redacted = re
+                .replace_all(&redacted, |caps: &Captures| {
+                    let whole = caps.get(0).expect("match has group 0");
+                    match caps.get(1) {
+                        Some(group) => {
+                            let start = group.start() - whole.start();
+                            let end = group.end() - whole.start();
+                            format!(
+                                "{}{}{}",
+                                &whole.as_str()[..start],
+                                mask(rule, group.as_str()),
+                                &whole.as_str()[end..]
+                            )
+                        }
+                        None => mask(rule, whole.as_str()),
+                    }
+                })
+                .into_owned();
+        }
+        redacted
+    }
+
+    /// Redacts the string values of the JSON document `json`. Keys are kept,
+    /// so a pattern such as `token` cannot rename `tokens_used`. Text that is
+    /// not JSON is redacted whole.
+    pub fn redact_json(&self, json: &str) -> String {
+        let Ok(mut value) = serde_json::from_str::<Value>(json) else {
+            return self.redact(json);
+        };
+        self.redact_values(&mut value);
+        serde_json::to_string_pretty(&value).unwrap_or_else(|_| self.redact(json))
+    }
+
+    fn redact_values(&self, value: &mut Value) {
+        match value {
+            Value::String(text) => *text = self.redact(text),
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_values(item)),
+            Value::Object(fields) => fields
+                .values_mut()
+                .for_each(|field| self.redact_values(field)),
+            _ => {}
+        }
+    }
+}
+
+/// Masks `value` down to its first and last two characters, or to the
+/// placeholder when it is too short for that to hide anything.
+pub fn mask_partial(value: &str) -> String {
+    keep_ends(value).unwrap_or_else(|| REDACTION_PLACEHOLDER.to_string())
+}
+
+/// Produces the replacement for a single matched value.
+fn mask(rule: &RedactionRule, value: &str) -> String {
+    if rule.partial {
+        if let Some(masked) = keep_ends(value) {
+            return masked;
+        }
+    }
+    rule.replace
+        .clone()
+        .unwrap_or_else(|| REDACTION_PLACEHOLDER.to_string())
+}
+
+fn keep_ends(value: &str) -> Option<String> {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() <= PARTIAL_KEEP * 4 {
+        return None;
+    }
+    let head: String = chars[..PARTIAL_KEEP].iter().collect();
+    let tail: String = chars[chars.len() - PARTIAL_KEEP..].iter().collect();
+    Some(format!("{}\u{2026}{}", head, tail))
+}
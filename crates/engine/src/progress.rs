@@ -0,0 +1,88 @@
+//! Progress events emitted while a review runs.
+//!
+//! Pass a channel to [`ReviewEngine::run_with_progress`](crate::ReviewEngine::run_with_progress)
+//! to follow a run, for example to drive a progress bar. Telemetry sinks
+//! record the same events.
+
+use crate::telemetry::TelemetrySink;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+/// A step in a review run, in the order they occur.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressEvent {
+    /// The run has started.
+    Started,
+    /// The diff was parsed; `files` changed files will be scanned.
+    ParsedDiff { files: usize },
+    /// Scanning of the `index`-th of `total` files (counting from 1) began.
+    ScanningFile {
+        path: String,
+        index: usize,
+        total: usize,
+    },
+    /// A scanner finished with one file.
+    ScannerFinished {
+        name: &'static str,
+        path: String,
+        findings: usize,
+        elapsed: Duration,
+    },
+    /// A scanner reported a finding.
+    Finding {
+        path: String,
+        line: usize,
+        rule: String,
+    },
+    /// RAG context is being retrieved for `findings` findings.
+    RetrievingContext { findings: usize },
+    /// RAG context retrieval finished.
+    ContextRetrieved { elapsed: Duration },
+    /// A request was sent to the LLM provider.
+    CallingLlm,
+    /// The LLM provider answered, or failed with `error`.
+    LlmFinished {
+        tokens: u32,
+        cached: bool,
+        error: Option<String>,
+        elapsed: Duration,
+    },
+    /// The run finished.
+    Done {
+        findings: usize,
+        tokens: u32,
+        elapsed: Duration,
+    },
+}
+
+/// Forwards events to the caller's channel and to telemetry sinks.
+pub(crate) struct Observer<'a> {
+    sender: Option<Sender<ProgressEvent>>,
+    sinks: &'a [Box<dyn TelemetrySink>],
+}
+
+impl<'a> Observer<'a> {
+    pub(crate) fn new(
+        sender: Option<Sender<ProgressEvent>>,
+        sinks: &'a [Box<dyn TelemetrySink>],
+    ) -> Self {
+        Self { sender, sinks }
+    }
+
+    pub(crate) fn emit(&self, event: ProgressEvent) {
+        for sink in self.sinks {
+            sink.record(&event);
+        }
+        if let Some(sender) = &self.sender {
+            // A caller that stopped listening does not stop the review.
+            let _ = sender.send(event);
+        }
+    }
+
+    /// Lets every sink send what it has buffered.
+    pub(crate) async fn flush(&self) {
+        for sink in self.sinks {
+            sink.flush().await;
+        }
+    }
+}
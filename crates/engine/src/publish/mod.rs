@@ -0,0 +1,23 @@
+//! Notifying other services of a completed review.
+//!
+//! A `Publisher` sends a digest of the report somewhere people will see it,
+//! such as a chat channel. Publishing happens after the report is written,
+//! and callers treat a failure as a warning rather than failing the review.
+
+use crate::error::Result;
+use crate::report::ReviewReport;
+use async_trait::async_trait;
+
+pub mod slack;
+
+pub use slack::SlackPublisher;
+
+/// Sends a digest of a review somewhere.
+#[async_trait]
+pub trait Publisher: Send + Sync {
+    /// Short name of the destination, used in logs, e.g. `slack`.
+    fn name(&self) -> &'static str;
+
+    /// Publishes `report`.
+    async fn publish(&self, report: &ReviewReport) -> Result<()>;
+}
@@ -0,0 +1,231 @@
+//! Posting review digests to Slack.
+//!
+//! The message is sent to an incoming webhook as Block Kit blocks: a header,
+//! the summary, the finding counts by severity, the three most severe
+//! findings with links, and a link to the full report when one is given.
+//! Every text is redacted with `[privacy.redaction]` and cut to Slack's
+//! limits, so a large review still posts.
+
+use crate::config::{Config, Severity};
+use crate::error::{EngineError, Result};
+use crate::privacy::Redactor;
+use crate::publish::Publisher;
+use crate::report::ReviewReport;
+use crate::scanner::Issue;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Environment variable that overrides `[integrations.slack] webhook-url`.
+pub const WEBHOOK_ENV: &str = "SLACK_WEBHOOK_URL";
+
+/// Longest text Slack accepts in a section block.
+const MAX_SECTION_CHARS: usize = 3000;
+/// Longest text Slack accepts in a header block.
+const MAX_HEADER_CHARS: usize = 150;
+/// Longest description shown for each finding.
+const MAX_DESCRIPTION_CHARS: usize = 300;
+/// Number of findings listed in the message.
+const TOP_FINDINGS: usize = 3;
+
+/// Posts a digest of the review to a Slack incoming webhook.
+pub struct SlackPublisher {
+    webhook_url: String,
+    report_url: Option<String>,
+    redactor: Redactor,
+    client: Client,
+}
+
+impl SlackPublisher {
+    /// Creates a publisher posting to `webhook_url`, redacting every text
+    /// with `redactor`.
+    pub fn new(webhook_url: impl Into<String>, redactor: Redactor) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_else(|e| {
+                log::warn!("failed to configure the Slack HTTP client: {}", e);
+                Client::new()
+            });
+        Self {
+            webhook_url: webhook_url.into(),
+            report_url: None,
+            redactor,
+            client,
+        }
+    }
+
+    /// Creates a publisher from `SLACK_WEBHOOK_URL` or `[integrations.slack]
+    /// webhook-url`, redacting with `[privacy.redaction]`. Fails if neither
+    /// is set.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let webhook_url = std::env::var(WEBHOOK_ENV)
+            .ok()
+            .filter(|url| !url.is_empty())
+            .or_else(|| config.integrations.slack.webhook_url.clone())
+            .ok_or_else(|| {
+                EngineError::Config(format!(
+                    "publishing to Slack needs [integrations.slack] webhook-url or {}",
+                    WEBHOOK_ENV
+                ))
+            })?;
+        Ok(Self::new(
+            webhook_url,
+            Redactor::new(&config.privacy.redaction)?,
+        ))
+    }
+
+    /// Links the message to the full report, e.g. a CI artifact.
+    pub fn report_url(mut self, url: impl Into<String>) -> Self {
+        self.report_url = Some(url.into());
+        self
+    }
+
+    /// Builds the webhook payload for `report`.
+    fn payload(&self, report: &ReviewReport) -> Value {
+        let headline = self.redactor.redact(&report.stats.headline());
+        let mut blocks = vec![
+            json!({
+                "type": "header",
+                "text": {
+                    "type": "plain_text",
+                    "text": truncate(&format!("Code review: {}", headline), MAX_HEADER_CHARS),
+                },
+            }),
+            section(&escape(&self.redactor.redact(&report.summary))),
+        ];
+
+        let counts: Vec<Value> = [
+            Severity::Critical,
+            Severity::High,
+            Severity::Medium,
+            Severity::Low,
+        ]
+        .iter()
+        .map(|severity| {
+            let count = report
+                .stats
+                .issues_by_severity
+                .get(severity)
+                .copied()
+                .unwrap_or(0);
+            json!({
+                "type": "mrkdwn",
+                "text": format!("*{}*\n{}", severity.as_str(), count),
+            })
+        })
+        .collect();
+        blocks.push(json!({ "type": "section", "fields": counts }));
+
+        if !report.issues.is_empty() {
+            let mut ordered: Vec<&Issue> = report.issues.iter().collect();
+            // Stable, so findings of equal severity keep the report order.
+            ordered.sort_by(|a, b| b.severity.cmp(&a.severity));
+            let mut lines = vec!["*Top findings*".to_string()];
+            lines.extend(
+                ordered
+                    .iter()
+                    .take(TOP_FINDINGS)
+                    .map(|issue| self.finding(issue)),
+            );
+            if ordered.len() > TOP_FINDINGS {
+                let more = ordered.len() - TOP_FINDINGS;
+                lines.push(format!(
+                    "…and {} more finding{}",
+                    more,
+                    if more == 1 { "" } else { "s" }
+                ));
+            }
+            blocks.push(section(&lines.join("\n")));
+        }
+
+        if let Some(url) = &self.report_url {
+            blocks.push(json!({
+                "type": "context",
+                "elements": [{
+                    "type": "mrkdwn",
+                    "text": format!("<{}|View the full report>", escape(url)),
+                }],
+            }));
+        }
+
+        json!({ "text": headline, "blocks": blocks })
+    }
+
+    /// Formats one finding as a bullet, linked to the flagged line when the
+    /// report has a link for it.
+    fn finding(&self, issue: &Issue) -> String {
+        let title = escape(&self.redactor.redact(&issue.title));
+        let title = match &issue.url {
+            Some(url) => format!("<{}|{}>", escape(url), title.replace('|', "/")),
+            None => title,
+        };
+        let description = escape(&truncate(
+            &self.redactor.redact(&issue.description),
+            MAX_DESCRIPTION_CHARS,
+        ));
+        format!(
+            "• *{}* {} `{}:{}`\n{}",
+            issue.severity.as_str(),
+            title,
+            issue.file_path,
+            issue.line_number,
+            description
+        )
+    }
+}
+
+#[async_trait]
+impl Publisher for SlackPublisher {
+    fn name(&self) -> &'static str {
+        "slack"
+    }
+
+    async fn publish(&self, report: &ReviewReport) -> Result<()> {
+        let response = self
+            .client
+            .post(&self.webhook_url)
+            .json(&self.payload(report))
+            .send()
+            .await
+            .map_err(|e| {
+                EngineError::Publish(format!("Slack webhook failed: {}", e.without_url()))
+            })?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(EngineError::Publish(format!(
+                "Slack webhook returned {}: {}",
+                status,
+                body.trim()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A section block with `text`, cut to Slack's limit.
+fn section(text: &str) -> Value {
+    json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": truncate(text, MAX_SECTION_CHARS) },
+    })
+}
+
+/// Escapes the characters Slack treats as markup.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Cuts `text` to at most `max` characters, ending in an ellipsis when
+/// anything was removed.
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max.saturating_sub(1)).collect();
+    format!("{}\u{2026}", kept)
+}
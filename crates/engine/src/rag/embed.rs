@@ -0,0 +1,231 @@
+//! Text embedders used to index documents and query the vector store.
+//!
+//! The default `NgramEmbedder` hashes token bigrams and works offline. The
+//! `OpenAiEmbedder` calls the OpenAI embeddings API for better retrieval of
+//! semantically similar code. An index records which embedder built it, and
+//! queries must use the same one: vectors from different embedders are not
+//! comparable.
+
+use crate::config::{Config, EmbedderKind};
+use crate::error::{EngineError, Result};
+use crate::llm::{http_client, Timeouts};
+use async_trait::async_trait;
+use rayon::prelude::*;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Name recorded for the built-in n-gram embedder.
+pub const NGRAM_EMBEDDER: &str = "ngram";
+
+/// Default OpenAI embeddings model.
+pub const DEFAULT_OPENAI_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+
+/// Number of inputs sent per embeddings request.
+const OPENAI_BATCH: usize = 64;
+
+/// Inputs are truncated to this many characters to stay within the model's
+/// token limit.
+const OPENAI_MAX_INPUT_CHARS: usize = 16_000;
+
+/// Turns text into vectors for similarity search.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Identifies the embedder and model, e.g. `openai/text-embedding-3-small`.
+    /// Stored in the index so queries can detect a mismatch.
+    fn name(&self) -> String;
+
+    /// Embeds a single text.
+    async fn embed(&self, text: &str) -> Result<Vec<f32>>;
+
+    /// Embeds several texts, returning one vector per input in order.
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            embeddings.push(self.embed(text).await?);
+        }
+        Ok(embeddings)
+    }
+}
+
+/// Creates the embedder selected by `[rag] embedder`.
+pub fn create_embedder(config: &Config) -> Result<Arc<dyn Embedder>> {
+    match config.rag.embedder {
+        EmbedderKind::Ngram => Ok(Arc::new(NgramEmbedder)),
+        EmbedderKind::Openai => {
+            let api_key = config.llm.api_key.clone().ok_or_else(|| {
+                EngineError::Config("Missing OpenAI api_key for [rag] embedder".into())
+            })?;
+            let model = config
+                .rag
+                .model
+                .clone()
+                .unwrap_or_else(|| DEFAULT_OPENAI_EMBEDDING_MODEL.to_string());
+            Ok(Arc::new(OpenAiEmbedder::new(
+                api_key,
+                model,
+                config.llm.base_url.as_deref(),
+                Timeouts::from_config(config),
+            )))
+        }
+    }
+}
+
+/// Offline embedder hashing token bigrams into a fixed-size vector.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NgramEmbedder;
+
+#[async_trait]
+impl Embedder for NgramEmbedder {
+    fn name(&self) -> String {
+        NGRAM_EMBEDDER.to_string()
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        Ok(ngram_embedding(text))
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.par_iter().map(|t| ngram_embedding(t)).collect())
+    }
+}
+
+/// Generate a simple n-gram embedding for the provided text.
+///
+/// The embedding is created by hashing each token bigram into a fixed-size
+/// vector. The resulting vector is L1 normalised so that documents of
+/// different lengths can still be compared.
+pub(crate) fn ngram_embedding(text: &str) -> Vec<f32> {
+    const N: usize = 2; // bigrams
+    const DIM: usize = 128;
+    let mut vec = vec![0f32; DIM];
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    if tokens.len() < N {
+        return vec;
+    }
+    for i in 0..=tokens.len() - N {
+        let ngram = tokens[i..i + N].join(" ");
+        let mut hasher = DefaultHasher::new();
+        ngram.hash(&mut hasher);
+        let idx = (hasher.finish() as usize) % DIM;
+        vec[idx] += 1.0;
+    }
+    let sum: f32 = vec.iter().sum();
+    if sum > 0.0 {
+        for v in &mut vec {
+            *v /= sum;
+        }
+    }
+    vec
+}
+
+/// Embedder backed by the OpenAI embeddings API.
+pub struct OpenAiEmbedder {
+    client: Client,
+    api_key: String,
+    model: String,
+    url: String,
+}
+
+impl OpenAiEmbedder {
+    /// Creates an embedder for `model`. `base_url` is the `[llm] base-url`;
+    /// a chat completions URL is rewritten to the matching embeddings URL.
+    pub fn new(api_key: String, model: String, base_url: Option<&str>, timeouts: Timeouts) -> Self {
+        Self {
+            client: http_client(timeouts),
+            api_key,
+            model,
+            url: embeddings_url(base_url),
+        }
+    }
+}
+
+/// Derives the embeddings endpoint from the configured LLM base URL.
+fn embeddings_url(base_url: Option<&str>) -> String {
+    let base = base_url
+        .unwrap_or("https://api.openai.com/v1")
+        .trim_end_matches('/');
+    if base.ends_with("/embeddings") {
+        return base.to_string();
+    }
+    let base = base.strip_suffix("/chat/completions").unwrap_or(base);
+    format!("{}/embeddings", base)
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: Vec<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    index: usize,
+}
+
+#[async_trait]
+impl Embedder for OpenAiEmbedder {
+    fn name(&self) -> String {
+        format!("openai/{}", self.model)
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut embeddings = self.embed_batch(&[text.to_string()]).await?;
+        embeddings
+            .pop()
+            .ok_or_else(|| EngineError::Rag("embeddings response was empty".into()))
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(OPENAI_BATCH) {
+            let input = chunk.iter().map(|t| truncate(t)).collect();
+            let res = self
+                .client
+                .post(&self.url)
+                .bearer_auth(&self.api_key)
+                .json(&EmbeddingRequest {
+                    model: &self.model,
+                    input,
+                })
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| EngineError::Rag(format!("embeddings request failed: {e}")))?;
+            let mut body: EmbeddingResponse = res
+                .json()
+                .await
+                .map_err(|e| EngineError::Rag(format!("invalid embeddings response: {e}")))?;
+            if body.data.len() != chunk.len() {
+                return Err(EngineError::Rag(format!(
+                    "embeddings response had {} vectors for {} inputs",
+                    body.data.len(),
+                    chunk.len()
+                )));
+            }
+            body.data.sort_by_key(|d| d.index);
+            embeddings.extend(body.data.into_iter().map(|d| d.embedding));
+        }
+        Ok(embeddings)
+    }
+}
+
+/// Shortens `text` to the input limit. The API rejects empty inputs, so
+/// empty files are sent as a single space.
+fn truncate(text: &str) -> &str {
+    if text.is_empty() {
+        return " ";
+    }
+    match text.char_indices().nth(OPENAI_MAX_INPUT_CHARS) {
+        Some((i, _)) => &text[..i],
+        None => text,
+    }
+}
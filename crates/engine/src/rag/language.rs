@@ -0,0 +1,251 @@
+//! Per-language extraction of the metadata stored on indexed documents.
+//!
+//! Each supported language, detected from the file extension, describes how
+//! its function signatures are declared, which calls are logging or console
+//! output, and which lines handle errors. The conventions scanner reuses the
+//! same tables to build a baseline per language.
+
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// A language whose metadata the index can extract.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Rust,
+    Go,
+    Python,
+    JavaScript,
+    TypeScript,
+}
+
+/// Guidance given when a line deviates from the repository's conventions.
+pub(crate) struct Advice {
+    pub title: &'static str,
+    pub description: &'static str,
+    pub fix: &'static str,
+}
+
+/// How a language logs and handles errors.
+pub(crate) struct Conventions {
+    /// Substrings of calls to a logging library.
+    pub loggers: &'static [&'static str],
+    /// Substrings of direct console output.
+    pub prints: &'static [&'static str],
+    pub logging: Advice,
+    /// Substrings of lines that handle or propagate errors.
+    pub handled: &'static [&'static str],
+    /// Substrings of lines that panic on or swallow errors.
+    pub unhandled: &'static [&'static str],
+    /// Guidance for `unhandled` lines, if the language has a discouraged form.
+    pub errors: Option<Advice>,
+}
+
+/// Control-flow keywords that the JavaScript method pattern would otherwise
+/// mistake for method names.
+const KEYWORDS: [&str; 8] = [
+    "if", "for", "while", "switch", "catch", "return", "function", "with",
+];
+
+const RUST_SIGNATURES: &[&str] = &[
+    r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?fn\s+(?P<name>\w+)[^\n]*",
+];
+
+const GO_SIGNATURES: &[&str] = &[r"(?m)^\s*func\s+(?:\([^)]*\)\s*)?(?P<name>\w+)\s*\([^\n]*"];
+
+const PYTHON_SIGNATURES: &[&str] = &[r"(?m)^\s*(?:async\s+)?def\s+(?P<name>\w+)\s*\([^\n]*"];
+
+const JS_SIGNATURES: &[&str] = &[
+    r"(?m)^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\s*\*?\s*(?P<name>\w+)[^\n]*",
+    r"(?m)^\s*(?:export\s+)?(?:const|let|var)\s+(?P<name>\w+)\s*(?::[^=\n]+)?=\s*(?:async\s+)?(?:\([^)\n]*\)|\w+)\s*(?::[^=\n]+)?=>[^\n]*",
+    r"(?m)^\s+(?:(?:public|private|protected|static|async|readonly|override)\s+)*(?P<name>\w+)\s*\([^)\n]*\)\s*(?::\s*[^{\n]+)?\{[^\n]*",
+];
+
+const RUST: Conventions = Conventions {
+    loggers: &["log::", "tracing::"],
+    prints: &["println!", "eprintln!"],
+    logging: Advice {
+        title: "Inconsistent Logging",
+        description:
+            "Use logging macros (e.g., log::info!) instead of println!/eprintln! per repository conventions.",
+        fix: "Replace println!/eprintln! with appropriate log:: macros.",
+    },
+    handled: &["Result<", "Err("],
+    unhandled: &[".unwrap()", ".expect("],
+    errors: Some(Advice {
+        title: "Avoid unwrap/expect",
+        description:
+            "Prefer error propagation with Result and ? operator instead of unwrap()/expect() per repository conventions.",
+        fix: "Propagate errors using ? or handle them explicitly.",
+    }),
+};
+
+const GO: Conventions = Conventions {
+    loggers: &["log.", "logger.", "slog.", "zap."],
+    prints: &["fmt.Println", "fmt.Printf", "fmt.Print("],
+    logging: Advice {
+        title: "Inconsistent Logging",
+        description:
+            "Use the repository's logger (e.g., log.Printf or slog.Info) instead of fmt.Println per repository conventions.",
+        fix: "Replace fmt.Print* calls with the repository's logger.",
+    },
+    handled: &["if err != nil", "errors.Is(", "errors.As(", "fmt.Errorf("],
+    unhandled: &["panic("],
+    errors: Some(Advice {
+        title: "Avoid panic",
+        description:
+            "Return errors to the caller instead of calling panic() per repository conventions.",
+        fix: "Return the error, wrapping it with fmt.Errorf if useful.",
+    }),
+};
+
+const PYTHON: Conventions = Conventions {
+    loggers: &["logging.", "logger.", "log."],
+    prints: &["print("],
+    logging: Advice {
+        title: "Inconsistent Logging",
+        description:
+            "Use the logging module (e.g., logger.info) instead of print() per repository conventions.",
+        fix: "Replace print() with a logger call.",
+    },
+    handled: &["try:", "except ", "raise "],
+    unhandled: &["except:"],
+    errors: Some(Advice {
+        title: "Avoid bare except",
+        description:
+            "Catch specific exceptions instead of using a bare except: per repository conventions.",
+        fix: "Name the exceptions to catch, e.g. except ValueError:.",
+    }),
+};
+
+const JAVASCRIPT: Conventions = Conventions {
+    loggers: &["logger.", "log.info(", "log.warn(", "log.error(", "log.debug("],
+    prints: &["console.log", "console.error", "console.warn"],
+    logging: Advice {
+        title: "Inconsistent Logging",
+        description:
+            "Use the repository's logger (e.g., logger.info) instead of console.log per repository conventions.",
+        fix: "Replace console.* calls with the repository's logger.",
+    },
+    handled: &["try {", "catch (", "catch {", ".catch(", "throw "],
+    unhandled: &[],
+    errors: None,
+};
+
+impl Language {
+    /// Detects the language of `path` from its extension.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let ext = Path::new(path).extension()?.to_str()?;
+        match ext {
+            "rs" => Some(Language::Rust),
+            "go" => Some(Language::Go),
+            "py" | "pyi" => Some(Language::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Language::JavaScript),
+            "ts" | "tsx" | "mts" | "cts" => Some(Language::TypeScript),
+            _ => None,
+        }
+    }
+
+    /// Returns the lowercase name of the language.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Language::Rust => "rust",
+            Language::Go => "go",
+            Language::Python => "python",
+            Language::JavaScript => "javascript",
+            Language::TypeScript => "typescript",
+        }
+    }
+
+    pub(crate) fn conventions(&self) -> &'static Conventions {
+        match self {
+            Language::Rust => &RUST,
+            Language::Go => &GO,
+            Language::Python => &PYTHON,
+            Language::JavaScript | Language::TypeScript => &JAVASCRIPT,
+        }
+    }
+
+    fn signature_patterns(&self) -> &'static [Regex] {
+        static RUST_RE: OnceLock<Vec<Regex>> = OnceLock::new();
+        static GO_RE: OnceLock<Vec<Regex>> = OnceLock::new();
+        static PYTHON_RE: OnceLock<Vec<Regex>> = OnceLock::new();
+        static JS_RE: OnceLock<Vec<Regex>> = OnceLock::new();
+        let (cell, patterns) = match self {
+            Language::Rust => (&RUST_RE, RUST_SIGNATURES),
+            Language::Go => (&GO_RE, GO_SIGNATURES),
+            Language::Python => (&PYTHON_RE, PYTHON_SIGNATURES),
+            Language::JavaScript | Language::TypeScript => (&JS_RE, JS_SIGNATURES),
+        };
+        cell.get_or_init(|| patterns.iter().map(|p| Regex::new(p).unwrap()).collect())
+    }
+
+    /// Returns the declaration matches in `content`, in file order, with
+    /// control-flow keywords and duplicate matches removed.
+    fn signature_matches<'c>(&self, content: &'c str) -> Vec<Captures<'c>> {
+        let mut found: Vec<Captures> = Vec::new();
+        for re in self.signature_patterns() {
+            for caps in re.captures_iter(content) {
+                if KEYWORDS.contains(&&caps["name"]) {
+                    continue;
+                }
+                let start = caps.get(0).unwrap().start();
+                if !found.iter().any(|f| f.get(0).unwrap().start() == start) {
+                    found.push(caps);
+                }
+            }
+        }
+        found.sort_by_key(|caps| caps.get(0).unwrap().start());
+        found
+    }
+
+    /// Returns the function, method and arrow-function declarations in
+    /// `content`, trimmed, in file order.
+    pub fn function_signatures(&self, content: &str) -> Vec<String> {
+        self.signature_matches(content)
+            .iter()
+            .map(|caps| caps.get(0).unwrap().as_str().trim().to_string())
+            .collect()
+    }
+
+    /// Returns the name of every declared function with the zero-based line
+    /// it is declared on, in file order.
+    pub(crate) fn function_lines<'c>(&self, content: &'c str) -> Vec<(usize, &'c str)> {
+        self.signature_matches(content)
+            .iter()
+            .map(|caps| {
+                let name = caps.name("name").unwrap();
+                let line = content[..name.start()].matches('\n').count();
+                (line, name.as_str())
+            })
+            .collect()
+    }
+
+    /// Returns the lines of `content` that log or print to the console.
+    pub fn log_patterns(&self, content: &str) -> Vec<String> {
+        let c = self.conventions();
+        matching_lines(content, &[c.loggers, c.prints])
+    }
+
+    /// Returns the lines of `content` that handle, propagate or discard
+    /// errors.
+    pub fn error_snippets(&self, content: &str) -> Vec<String> {
+        let c = self.conventions();
+        matching_lines(content, &[c.handled, c.unhandled])
+    }
+}
+
+/// Returns whether `line` contains any of `patterns`.
+pub(crate) fn contains_any(line: &str, patterns: &[&str]) -> bool {
+    patterns.iter().any(|p| line.contains(p))
+}
+
+fn matching_lines(content: &str, groups: &[&[&str]]) -> Vec<String> {
+    content
+        .lines()
+        .filter(|line| groups.iter().any(|patterns| contains_any(line, patterns)))
+        .map(|l| l.trim().to_string())
+        .collect()
+}
@@ -6,18 +6,34 @@
 use crate::error::{EngineError, Result};
 use async_trait::async_trait;
 use globset::{Glob, GlobSet, GlobSetBuilder};
-use regex::Regex;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::fs;
-use std::hash::{Hash, Hasher};
-use std::path::Path;
-use std::time::UNIX_EPOCH;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use walkdir::WalkDir;
 
+pub mod embed;
+pub mod language;
+#[cfg(feature = "qdrant")]
+pub mod qdrant;
+mod search;
+pub mod stats;
+pub use embed::{create_embedder, Embedder, NgramEmbedder, OpenAiEmbedder};
+pub use language::Language;
+#[cfg(feature = "qdrant")]
+pub use qdrant::QdrantVectorStore;
+use search::SearchMatrix;
+pub use stats::IndexStats;
+
 const VCS_DIRS: [&str; 4] = [".git", ".hg", ".svn", ".bzr"];
 
+/// Directory holding reviewlens state (index, caches), never indexed itself.
+const STATE_DIR: &str = ".reviewlens";
+
 /// Represents a single indexed document along with extracted metadata.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Document {
@@ -33,14 +49,18 @@ pub struct Document {
     /// similarity search without relying on heavyweight language models.
     #[serde(default)]
     pub embedding: Vec<f32>,
+    /// Language detected from the file extension. Metadata is only
+    /// extracted from files in a supported language.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
     /// All function signatures discovered in this file.
     #[serde(default)]
     pub function_signatures: Vec<String>,
-    /// Lines that contain logging macros such as `log::info!`.
+    /// Lines that log or print, such as `log::info!` or `console.log`.
     #[serde(default)]
     pub log_patterns: Vec<String>,
     /// Lines that contain common error-handling patterns (`unwrap`,
-    /// `expect`, or `Result` usage).
+    /// `if err != nil`, `try`/`except` or `.catch(`).
     #[serde(default)]
     pub error_snippets: Vec<String>,
     /// Last modification time of the file in nanoseconds since Unix epoch.
@@ -48,65 +68,6 @@ pub struct Document {
     pub modified: u64,
 }
 
-/// Generate a simple n-gram embedding for the provided text.
-///
-/// The embedding is created by hashing each token bigram into a fixed-size
-/// vector. The resulting vector is L1 normalised so that documents of
-/// different lengths can still be compared.
-fn ngram_embedding(text: &str) -> Vec<f32> {
-    const N: usize = 2; // bigrams
-    const DIM: usize = 128;
-    let mut vec = vec![0f32; DIM];
-    let tokens: Vec<&str> = text.split_whitespace().collect();
-    if tokens.len() < N {
-        return vec;
-    }
-    for i in 0..=tokens.len() - N {
-        let ngram = tokens[i..i + N].join(" ");
-        let mut hasher = DefaultHasher::new();
-        ngram.hash(&mut hasher);
-        let idx = (hasher.finish() as usize) % DIM;
-        vec[idx] += 1.0;
-    }
-    let sum: f32 = vec.iter().sum();
-    if sum > 0.0 {
-        for v in &mut vec {
-            *v /= sum;
-        }
-    }
-    vec
-}
-
-fn extract_function_signatures(content: &str) -> Vec<String> {
-    let re = Regex::new(r"(?m)^\s*fn\s+\w+[^\n]*").unwrap();
-    re.find_iter(content)
-        .map(|m| m.as_str().trim().to_string())
-        .collect()
-}
-
-fn extract_log_patterns(content: &str) -> Vec<String> {
-    content
-        .lines()
-        .filter(|line| {
-            line.contains("log::") || line.contains("println!") || line.contains("eprintln!")
-        })
-        .map(|l| l.trim().to_string())
-        .collect()
-}
-
-fn extract_error_snippets(content: &str) -> Vec<String> {
-    content
-        .lines()
-        .filter(|line| {
-            line.contains(".unwrap()")
-                || line.contains(".expect(")
-                || line.contains("Result<")
-                || line.contains("Err(")
-        })
-        .map(|l| l.trim().to_string())
-        .collect()
-}
-
 fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.is_empty() || b.is_empty() || a.len() != b.len() {
         return 0.0;
@@ -131,8 +92,96 @@ pub trait VectorStore {
     /// Adds a document (which already contains its embedding) to the store.
     async fn add(&mut self, document: Document) -> Result<()>;
 
+    /// Adds several documents. Stores backed by a remote service should
+    /// override this to send them in bulk.
+    async fn add_batch(&mut self, documents: Vec<Document>) -> Result<()>
+    where
+        Self: Send,
+    {
+        for document in documents {
+            self.add(document).await?;
+        }
+        Ok(())
+    }
+
+    /// Searches for the most similar documents to a given query vector,
+    /// returning each with its similarity score, best first.
+    async fn search_scored(
+        &self,
+        query_embedding: Vec<f32>,
+        top_k: usize,
+    ) -> Result<Vec<(Document, f32)>>;
+
     /// Searches for the most similar documents to a given query vector.
-    async fn search(&self, query_embedding: Vec<f32>, top_k: usize) -> Result<Vec<Document>>;
+    async fn search(&self, query_embedding: Vec<f32>, top_k: usize) -> Result<Vec<Document>>
+    where
+        Self: Sync,
+    {
+        Ok(self
+            .search_scored(query_embedding, top_k)
+            .await?
+            .into_iter()
+            .map(|(doc, _)| doc)
+            .collect())
+    }
+
+    /// Searches for several query vectors at once, returning one scored
+    /// result list per query in order.
+    async fn search_batch_scored(
+        &self,
+        query_embeddings: Vec<Vec<f32>>,
+        top_k: usize,
+    ) -> Result<Vec<Vec<(Document, f32)>>>
+    where
+        Self: Sync,
+    {
+        let mut results = Vec::with_capacity(query_embeddings.len());
+        for query in query_embeddings {
+            results.push(self.search_scored(query, top_k).await?);
+        }
+        Ok(results)
+    }
+
+    /// Searches for several query vectors at once, returning one result list
+    /// per query in order.
+    async fn search_batch(
+        &self,
+        query_embeddings: Vec<Vec<f32>>,
+        top_k: usize,
+    ) -> Result<Vec<Vec<Document>>>
+    where
+        Self: Sync,
+    {
+        Ok(self
+            .search_batch_scored(query_embeddings, top_k)
+            .await?
+            .into_iter()
+            .map(|docs| docs.into_iter().map(|(doc, _)| doc).collect())
+            .collect())
+    }
+
+    /// Returns `EngineError::Rag` if the store's vectors were not produced by
+    /// `embedder`. Stores that do not record their embedder accept any.
+    fn check_embedder(&self, _embedder: &dyn Embedder) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns the number of stored documents, or `None` if the store cannot
+    /// tell without a remote call.
+    fn document_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// Explains why the store no longer reflects the working tree, or returns
+    /// `None` if it is fresh. Stores without build metadata are always fresh.
+    fn staleness(
+        &self,
+        _head: Option<&str>,
+        _newest_change: Option<SystemTime>,
+        _max_age: Duration,
+    ) -> Option<String> {
+        None
+    }
 }
 
 /// A trait for an indexer that processes source code and populates a vector store.
@@ -145,6 +194,25 @@ pub trait Indexer {
     async fn index_paths(&self, paths: &[String]) -> Result<()>;
 }
 
+/// A document retrieved for a query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetrievedDocument {
+    pub filename: String,
+    /// Cosine similarity between the query and the document.
+    pub score: f32,
+    pub text: String,
+}
+
+impl From<(Document, f32)> for RetrievedDocument {
+    fn from((doc, score): (Document, f32)) -> Self {
+        Self {
+            filename: doc.filename,
+            score,
+            text: doc.content,
+        }
+    }
+}
+
 // Example of a simple RAG context retriever.
 pub struct RagContextRetriever {
     /// The vector store used to search for similar documents.
@@ -152,48 +220,152 @@ pub struct RagContextRetriever {
     /// In a real implementation this would likely be backed by an external
     /// service such as Qdrant or Tantivy. Here we keep the trait object to
     /// allow different store implementations.
-    vector_store: Box<dyn VectorStore + Send + Sync>,
+    vector_store: Arc<dyn VectorStore + Send + Sync>,
+    /// Embeds queries; must match the embedder that built the store.
+    embedder: Arc<dyn Embedder>,
 }
 
 impl RagContextRetriever {
-    /// Creates a new `RagContextRetriever` with the provided vector store.
+    /// Creates a new `RagContextRetriever` with the provided vector store,
+    /// embedding queries with the default n-gram embedder.
     pub fn new(vector_store: Box<dyn VectorStore + Send + Sync>) -> Self {
-        Self { vector_store }
+        Self {
+            vector_store: Arc::from(vector_store),
+            embedder: Arc::new(NgramEmbedder),
+        }
     }
 
-    pub async fn retrieve(&self, query: &str) -> Result<String> {
+    /// Creates a retriever over a persisted index, embedding queries with
+    /// `embedder`. Fails if the index was built with a different embedder.
+    pub fn for_index(store: InMemoryVectorStore, embedder: Arc<dyn Embedder>) -> Result<Self> {
+        Self::for_store(Arc::new(store), embedder)
+    }
+
+    /// Creates a retriever over a shared vector store, embedding queries with
+    /// `embedder`. Fails if the store was built with a different embedder.
+    pub fn for_store(
+        vector_store: Arc<dyn VectorStore + Send + Sync>,
+        embedder: Arc<dyn Embedder>,
+    ) -> Result<Self> {
+        vector_store.check_embedder(embedder.as_ref())?;
+        Ok(Self {
+            vector_store,
+            embedder,
+        })
+    }
+
+    /// Embeds `query` and returns the most similar documents with their
+    /// score, best first.
+    pub async fn retrieve(&self, query: &str) -> Result<Vec<RetrievedDocument>> {
         log::debug!("Retrieving RAG context for query: {}", query);
-        // 1. Generate a lightweight embedding for the query.
-        let embedding = ngram_embedding(query);
+        let results = self.search(query, RETRIEVAL_TOP_K).await?;
+        Ok(results.into_iter().map(RetrievedDocument::from).collect())
+    }
 
-        // 2. Search the vector store.
-        let top_k = 5;
+    /// Embeds `query` and returns the `top_k` most similar documents with
+    /// their cosine similarity, best first.
+    pub async fn search(&self, query: &str, top_k: usize) -> Result<Vec<(Document, f32)>> {
+        let embedding = self.embedder.embed(query).await?;
+        self.vector_store
+            .search_scored(embedding, top_k)
+            .await
+            .map_err(|e| EngineError::Rag(format!("Vector store search failed: {e}")))
+    }
+
+    /// Retrieves context for several queries with one embedding batch and
+    /// one store search. Queries without results yield `None`.
+    pub async fn retrieve_batch(&self, queries: &[String]) -> Result<Vec<Option<String>>> {
+        log::debug!("Retrieving RAG context for {} queries", queries.len());
+        let embeddings = self.embedder.embed_batch(queries).await?;
         let results = self
             .vector_store
-            .search(embedding, top_k)
+            .search_batch(embeddings, RETRIEVAL_TOP_K)
             .await
             .map_err(|e| EngineError::Rag(format!("Vector store search failed: {e}")))?;
+        Ok(results.into_iter().map(format_context).collect())
+    }
 
-        // 3. Format and return the results as a string.
-        if results.is_empty() {
-            return Err(EngineError::Rag("No results found".into()));
-        }
+    /// Embeds all `queries` in one batch and searches the store in one pass,
+    /// returning the scored documents for each query in order. Equivalent to
+    /// calling [`search`](Self::search) for each query with the default
+    /// number of results.
+    pub async fn retrieve_many(&self, queries: &[String]) -> Result<Vec<Vec<(Document, f32)>>> {
+        log::debug!("Retrieving RAG context for {} queries", queries.len());
+        let embeddings = self.embedder.embed_batch(queries).await?;
+        self.vector_store
+            .search_batch_scored(embeddings, RETRIEVAL_TOP_K)
+            .await
+            .map_err(|e| EngineError::Rag(format!("Vector store search failed: {e}")))
+    }
+
+    /// Retrieves context for several queries as one block, as formatted by
+    /// [`merge_retrieved`]. Returns `None` if nothing relevant is found.
+    pub async fn retrieve_merged(&self, queries: &[String]) -> Result<Option<String>> {
+        Ok(merge_retrieved(self.retrieve_many(queries).await?))
+    }
+}
+
+/// Formats the results of several queries as one block, listing each
+/// document once in order of first retrieval. Documents with no similarity
+/// to the query that found them are dropped, so unrelated files never pad
+/// the prompt. Returns `None` if nothing relevant remains.
+pub fn merge_retrieved(results: impl IntoIterator<Item = Vec<(Document, f32)>>) -> Option<String> {
+    let mut seen = std::collections::HashSet::new();
+    let merged = results
+        .into_iter()
+        .flatten()
+        .filter(|(_, score)| *score > 0.0)
+        .map(|(doc, _)| doc)
+        .filter(|doc| seen.insert(doc.filename.clone()))
+        .collect();
+    format_context(merged)
+}
+
+/// Number of documents retrieved per query.
+const RETRIEVAL_TOP_K: usize = 5;
 
-        let formatted = results
+/// Formats retrieved documents as a numbered list, or `None` if empty.
+fn format_context(results: Vec<Document>) -> Option<String> {
+    if results.is_empty() {
+        return None;
+    }
+    Some(
+        results
             .into_iter()
             .enumerate()
             .map(|(i, doc)| format!("{}. {}: {}", i + 1, doc.filename, doc.content))
             .collect::<Vec<_>>()
-            .join("\n");
-
-        Ok(formatted)
-    }
+            .join("\n"),
+    )
 }
 
 /// A simple in-memory vector store for demonstration purposes.
+///
+/// Every document's embedding has the same length, the store's
+/// [`dimension`](Self::dimension) once recorded; searches reject queries of
+/// any other length. The accessors below keep the search matrix in step with
+/// the documents but do not check embedding lengths.
 #[derive(Default, Serialize, Deserialize)]
 pub struct InMemoryVectorStore {
     documents: Vec<Document>,
+    /// When the index was built, in seconds since the Unix epoch. Absent in
+    /// indexes written by older versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    built_at: Option<u64>,
+    /// Repository `HEAD` commit at build time, if the tree was a git checkout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    head_commit: Option<String>,
+    /// Name of the embedder that produced the document embeddings. Older
+    /// indexes were always built with the n-gram embedder.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    embedder: Option<String>,
+    /// Length of the stored embeddings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dimension: Option<usize>,
+    /// Normalised embedding matrix, built on the first search and dropped
+    /// whenever documents change.
+    #[serde(skip)]
+    matrix: OnceLock<SearchMatrix>,
 }
 
 impl InMemoryVectorStore {
@@ -202,38 +374,272 @@ impl InMemoryVectorStore {
         self.documents.len()
     }
 
+    /// Returns `true` if the store contains no documents.
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
     /// Returns an immutable slice of the indexed documents.
     pub fn documents(&self) -> &[Document] {
         &self.documents
     }
 
-    /// Adds a document to the store without computing embeddings. Useful for tests.
+    /// Returns the documents for in-place changes. The search matrix is
+    /// dropped and rebuilt on the next search, so edited embeddings take
+    /// effect, but they must keep the store's [`dimension`](Self::dimension).
+    pub fn documents_mut(&mut self) -> &mut Vec<Document> {
+        self.matrix.take();
+        &mut self.documents
+    }
+
+    /// Adds a document to the store without computing embeddings. Its
+    /// embedding must have the same length as every other document's.
     pub fn push_document(&mut self, document: Document) {
         self.documents.push(document);
+        self.matrix.take();
+    }
+
+    /// Removes the document indexed for `filename`. Returns `false` if there
+    /// was none.
+    pub fn remove_by_filename(&mut self, filename: &str) -> bool {
+        let before = self.documents.len();
+        self.retain(|doc| doc.filename != filename);
+        self.documents.len() != before
+    }
+
+    /// Keeps only the documents for which `keep` returns `true`.
+    pub fn retain(&mut self, keep: impl FnMut(&Document) -> bool) {
+        let before = self.documents.len();
+        self.documents.retain(keep);
+        if self.documents.len() != before {
+            self.matrix.take();
+        }
+    }
+
+    /// Ranks every document by exact cosine similarity. This is the
+    /// reference linear scan; `search` returns the same documents faster.
+    pub fn search_exact(&self, query_embedding: &[f32], top_k: usize) -> Vec<Document> {
+        self.search_exact_scored(query_embedding, top_k)
+            .into_iter()
+            .map(|(d, _)| d)
+            .collect()
+    }
+
+    fn search_exact_scored(&self, query_embedding: &[f32], top_k: usize) -> Vec<(Document, f32)> {
+        let mut scored: Vec<(f32, &Document)> = self
+            .documents
+            .iter()
+            .map(|doc| (cosine_similarity(query_embedding, &doc.embedding), doc))
+            .collect();
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        scored
+            .into_iter()
+            .take(top_k)
+            .map(|(score, d)| (d.clone(), score))
+            .collect()
+    }
+
+    /// Returns the search matrix, building it on first use.
+    fn matrix(&self) -> &SearchMatrix {
+        self.matrix.get_or_init(|| {
+            let dimension = self
+                .dimension
+                .or_else(|| self.documents.first().map(|d| d.embedding.len()))
+                .unwrap_or(0);
+            SearchMatrix::build(
+                dimension,
+                self.documents.iter().map(|d| d.embedding.as_slice()),
+            )
+        })
+    }
+
+    fn check_dimension(&self, query_embedding: &[f32]) -> Result<()> {
+        if let Some(dimension) = self.dimension {
+            if query_embedding.len() != dimension {
+                return Err(EngineError::Rag(format!(
+                    "query embedding has {} dimensions but the index has {}",
+                    query_embedding.len(),
+                    dimension
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn search_one(&self, query_embedding: &[f32], top_k: usize) -> Vec<(Document, f32)> {
+        let matrix = self.matrix();
+        if query_embedding.len() != matrix.dimension() {
+            return self.search_exact_scored(query_embedding, top_k);
+        }
+        matrix
+            .top_k(query_embedding, top_k)
+            .into_iter()
+            .map(|(i, score)| (self.documents[i].clone(), score))
+            .collect()
+    }
+
+    /// When the index was built, if recorded.
+    pub fn built_at(&self) -> Option<SystemTime> {
+        self.built_at
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    /// Repository `HEAD` commit the index was built from, if recorded.
+    pub fn head_commit(&self) -> Option<&str> {
+        self.head_commit.as_deref()
     }
+
+    /// Records when and from which commit the index was built.
+    pub fn set_build_info(&mut self, built_at: SystemTime, head_commit: Option<String>) {
+        self.built_at = Some(
+            built_at
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        );
+        self.head_commit = head_commit;
+    }
+
+    /// Name of the embedder that built the index.
+    pub fn embedder_name(&self) -> &str {
+        self.embedder.as_deref().unwrap_or(embed::NGRAM_EMBEDDER)
+    }
+
+    /// Length of the stored embeddings, if recorded.
+    pub fn dimension(&self) -> Option<usize> {
+        self.dimension
+    }
+
+    /// Records the embedder that produced the stored embeddings.
+    pub fn set_embedder(&mut self, name: impl Into<String>, dimension: Option<usize>) {
+        self.embedder = Some(name.into());
+        self.dimension = dimension;
+    }
+
+    /// Returns `EngineError::Rag` if `embedder` differs from the one that
+    /// built the index, since their vectors cannot be compared.
+    pub fn check_embedder(&self, embedder: &dyn Embedder) -> Result<()> {
+        let name = embedder.name();
+        if name != self.embedder_name() {
+            return Err(EngineError::Rag(format!(
+                "index was built with the `{}` embedder but `{}` is configured; \
+                 rebuild it with `reviewlens index --force`",
+                self.embedder_name(),
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Explains why the index no longer reflects the working tree, or
+    /// returns `None` if it is considered fresh.
+    ///
+    /// An index built from `head` is fresh. Otherwise it is stale when it was
+    /// built more than `max_age` before `newest_change` (the latest
+    /// modification among the reviewed files, or now if unknown). Indexes
+    /// without build metadata cannot be judged and are treated as fresh.
+    pub fn staleness(
+        &self,
+        head: Option<&str>,
+        newest_change: Option<SystemTime>,
+        max_age: Duration,
+    ) -> Option<String> {
+        let built_at = self.built_at()?;
+        if head.is_some() && head == self.head_commit() {
+            return None;
+        }
+        let reference = newest_change.unwrap_or_else(SystemTime::now);
+        let age = reference.duration_since(built_at).ok()?;
+        if age <= max_age {
+            return None;
+        }
+        let days = age.as_secs() / 86_400;
+        let mut reason = format!(
+            "built {} day{} before the newest change",
+            days,
+            if days == 1 { "" } else { "s" }
+        );
+        if let (Some(built), Some(current)) = (self.head_commit(), head) {
+            reason.push_str(&format!(
+                " at commit {} (HEAD is {})",
+                short_sha(built),
+                short_sha(current)
+            ));
+        }
+        Some(reason)
+    }
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(8)]
+}
+
+/// Returns the `HEAD` commit of the git repository at `repo`, or `None` if
+/// it is not a git checkout or git is unavailable.
+pub fn repository_head(repo: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let head = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!head.is_empty()).then_some(head)
 }
 
 #[async_trait]
 impl VectorStore for InMemoryVectorStore {
     /// Stores the document in memory along with its embedding.
     async fn add(&mut self, document: Document) -> Result<()> {
-        self.documents.push(document);
+        self.push_document(document);
         Ok(())
     }
 
-    /// Performs a naive cosine similarity search over stored embeddings.
-    async fn search(&self, query_embedding: Vec<f32>, top_k: usize) -> Result<Vec<Document>> {
-        let mut scored: Vec<(f32, Document)> = self
-            .documents
-            .iter()
-            .cloned()
-            .map(|doc| {
-                let score = cosine_similarity(&query_embedding, &doc.embedding);
-                (score, doc)
-            })
-            .collect();
-        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
-        Ok(scored.into_iter().take(top_k).map(|(_, d)| d).collect())
+    /// Returns the documents most similar to the query by cosine similarity,
+    /// using the normalised embedding matrix.
+    async fn search_scored(
+        &self,
+        query_embedding: Vec<f32>,
+        top_k: usize,
+    ) -> Result<Vec<(Document, f32)>> {
+        self.check_dimension(&query_embedding)?;
+        Ok(self.search_one(&query_embedding, top_k))
+    }
+
+    /// Searches all queries against one shared matrix, in parallel.
+    async fn search_batch_scored(
+        &self,
+        query_embeddings: Vec<Vec<f32>>,
+        top_k: usize,
+    ) -> Result<Vec<Vec<(Document, f32)>>> {
+        for query in &query_embeddings {
+            self.check_dimension(query)?;
+        }
+        self.matrix();
+        Ok(query_embeddings
+            .par_iter()
+            .map(|query| self.search_one(query, top_k))
+            .collect())
+    }
+
+    fn check_embedder(&self, embedder: &dyn Embedder) -> Result<()> {
+        InMemoryVectorStore::check_embedder(self, embedder)
+    }
+
+    fn document_count(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn staleness(
+        &self,
+        head: Option<&str>,
+        newest_change: Option<SystemTime>,
+        max_age: Duration,
+    ) -> Option<String> {
+        InMemoryVectorStore::staleness(self, head, newest_change, max_age)
     }
 }
 
@@ -244,14 +650,14 @@ impl InMemoryVectorStore {
             .map_err(|e| EngineError::Rag(format!("Failed to serialize store: {e}")))?;
         let compressed = zstd::encode_all(&data[..], 0)
             .map_err(|e| EngineError::Rag(format!("Failed to compress store: {e}")))?;
-        fs::write(path, compressed)?;
+        fs::write(&path, compressed).map_err(|e| EngineError::io(&path, e))?;
         Ok(())
     }
 
     /// Loads the vector store from the given path. If the file does not
     /// exist or cannot be deserialized, an error is returned.
     pub fn load_from_disk<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let data = fs::read(path)?;
+        let data = fs::read(&path).map_err(|e| EngineError::io(&path, e))?;
         let decompressed = zstd::decode_all(&data[..])
             .map_err(|e| EngineError::Rag(format!("Failed to decompress store: {e}")))?;
         serde_json::from_slice(&decompressed)
@@ -269,6 +675,9 @@ impl InMemoryVectorStore {
 /// index is loaded from disk and only files whose modification times have
 /// changed are re-processed. When a new or updated index is built, it is
 /// persisted to the given `output` path.
+///
+/// Changed files are read in parallel using one worker per CPU and embedded
+/// with the n-gram embedder; see [`index_repository_with`] to change either.
 pub async fn index_repository<P, Q>(
     path: P,
     output: Q,
@@ -276,6 +685,46 @@ pub async fn index_repository<P, Q>(
     allow: &[String],
     deny: &[String],
 ) -> Result<InMemoryVectorStore>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    index_repository_with(path, output, force, allow, deny, &IndexOptions::default()).await
+}
+
+/// Tuning for [`index_repository_with`].
+#[derive(Clone)]
+pub struct IndexOptions {
+    /// Maximum worker threads reading and parsing changed files. `0` uses
+    /// one worker per CPU and `1` reads files sequentially.
+    pub jobs: usize,
+    /// Embedder for document contents, recorded in the index.
+    pub embedder: Arc<dyn Embedder>,
+}
+
+impl Default for IndexOptions {
+    fn default() -> Self {
+        Self {
+            jobs: 0,
+            embedder: Arc::new(NgramEmbedder),
+        }
+    }
+}
+
+/// Like [`index_repository`], with a bounded worker pool and a chosen
+/// embedder. Cached documents are only reused if the existing index was
+/// built with the same embedder.
+///
+/// Documents are stored sorted by filename, so the index contents do not
+/// depend on the number of workers or the directory walk order.
+pub async fn index_repository_with<P, Q>(
+    path: P,
+    output: Q,
+    force: bool,
+    allow: &[String],
+    deny: &[String],
+    options: &IndexOptions,
+) -> Result<InMemoryVectorStore>
 where
     P: AsRef<Path>,
     Q: AsRef<Path>,
@@ -283,34 +732,127 @@ where
     let path_ref = path.as_ref();
     let output_ref = output.as_ref();
     log::info!(
-        "Indexing repository at {} (force={})",
+        "Indexing repository at {} (force={}, jobs={}, embedder={})",
         path_ref.display(),
-        force
+        force,
+        options.jobs,
+        options.embedder.name()
     );
 
     let allow_set = build_globset(allow)?;
     let deny_set = build_globset(deny)?;
 
+    let embedder_name = options.embedder.name();
     let mut store = if !force && output_ref.exists() {
         log::info!("Loading existing index from {}", output_ref.display());
         InMemoryVectorStore::load_from_disk(output_ref)?
     } else {
         InMemoryVectorStore::default()
     };
+    if store.embedder_name() != embedder_name {
+        log::info!(
+            "Existing index was built with the `{}` embedder; re-embedding all files",
+            store.embedder_name()
+        );
+        store.documents_mut().clear();
+    }
 
-    let mut existing = std::mem::take(&mut store.documents)
-        .into_iter()
+    let mut existing = store
+        .documents_mut()
+        .drain(..)
         .map(|d| (d.filename.clone(), d))
         .collect::<HashMap<_, _>>();
 
+    // Unchanged files are reused from the cache here; only the rest are
+    // dispatched to the worker pool.
     let mut new_documents = Vec::new();
+    let mut pending = Vec::new();
+
+    for file in matching_files(path_ref, &allow_set, &deny_set)? {
+        if !force {
+            if let Some(doc) = existing.get(&file.filename) {
+                if doc.modified == file.modified {
+                    new_documents.push(doc.clone());
+                    existing.remove(&file.filename);
+                    continue;
+                }
+            }
+        }
+        pending.push(file);
+    }
 
-    for entry in WalkDir::new(path_ref)
+    new_documents.extend(build_documents(pending, options).await?);
+    new_documents.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+    let dimension = new_documents.first().map(|d| d.embedding.len());
+    *store.documents_mut() = new_documents;
+    store.set_embedder(embedder_name, dimension);
+    store.set_build_info(SystemTime::now(), repository_head(path_ref));
+
+    if let Some(parent) = output_ref.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    store.save_to_disk(output_ref)?;
+    log::info!("Indexed {} files", store.len());
+    Ok(store)
+}
+
+/// Indexes every file under `path` matching `allow` and not `deny` into
+/// `store`, such as an external vector database. Unlike
+/// [`index_repository_with`], nothing is cached or written to disk: all
+/// matching files are re-embedded and upserted. Returns the number of
+/// documents added.
+pub async fn index_repository_into<P: AsRef<Path>>(
+    path: P,
+    allow: &[String],
+    deny: &[String],
+    options: &IndexOptions,
+    store: &mut (dyn VectorStore + Send + Sync),
+) -> Result<usize> {
+    let path_ref = path.as_ref();
+    log::info!(
+        "Indexing repository at {} into an external store (jobs={}, embedder={})",
+        path_ref.display(),
+        options.jobs,
+        options.embedder.name()
+    );
+    let allow_set = build_globset(allow)?;
+    let deny_set = build_globset(deny)?;
+    let files = matching_files(path_ref, &allow_set, &deny_set)?;
+    let mut documents = build_documents(files, options).await?;
+    documents.sort_by(|a, b| a.filename.cmp(&b.filename));
+    let count = documents.len();
+    store.add_batch(documents).await?;
+    log::info!("Indexed {} files", count);
+    Ok(count)
+}
+
+/// A file selected for indexing.
+pub(crate) struct IndexedFile {
+    pub(crate) path: PathBuf,
+    /// Path relative to the repository root.
+    pub(crate) filename: String,
+    /// Modification time in nanoseconds since the Unix epoch.
+    modified: u64,
+}
+
+/// Walks `root`, skipping version control and state directories, and returns
+/// the files matching `allow` and not `deny`.
+pub(crate) fn matching_files(
+    root: &Path,
+    allow: &GlobSet,
+    deny: &GlobSet,
+) -> Result<Vec<IndexedFile>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(root)
         .into_iter()
         .filter_entry(|e| {
             if e.file_type().is_dir() {
                 let name = e.file_name().to_string_lossy();
-                !VCS_DIRS.contains(&name.as_ref())
+                !VCS_DIRS.contains(&name.as_ref()) && name != STATE_DIR
             } else {
                 true
             }
@@ -318,8 +860,8 @@ where
         .filter_map(|e| e.ok())
     {
         if entry.file_type().is_file() {
-            let rel_path = entry.path().strip_prefix(path_ref).unwrap_or(entry.path());
-            if !(allow_set.is_match(rel_path) && !deny_set.is_match(rel_path)) {
+            let rel_path = entry.path().strip_prefix(root).unwrap_or(entry.path());
+            if !allow.is_match(rel_path) || deny.is_match(rel_path) {
                 continue;
             }
             let filename = rel_path.display().to_string();
@@ -329,49 +871,58 @@ where
                 .unwrap_or_default();
             let modified =
                 modified_time.as_secs() * 1_000_000_000 + u64::from(modified_time.subsec_nanos());
-
-            if !force {
-                if let Some(doc) = existing.get(&filename) {
-                    if doc.modified == modified {
-                        new_documents.push(doc.clone());
-                        existing.remove(&filename);
-                        continue;
-                    }
-                }
-            }
-
-            let content = fs::read_to_string(entry.path())?;
-            let embedding = ngram_embedding(&content);
-            let function_signatures = extract_function_signatures(&content);
-            let log_patterns = extract_log_patterns(&content);
-            let error_snippets = extract_error_snippets(&content);
-            let doc = Document {
-                filename: filename.clone(),
-                content,
-                embedding,
-                function_signatures,
-                log_patterns,
-                error_snippets,
+            files.push(IndexedFile {
+                path: entry.into_path(),
+                filename,
                 modified,
-            };
-            new_documents.push(doc);
+            });
         }
     }
+    Ok(files)
+}
 
-    store.documents = new_documents;
-
-    if let Some(parent) = output_ref.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent)?;
-        }
+/// Reads and parses `files` on a pool of `options.jobs` workers, then embeds
+/// their contents in one batch.
+async fn build_documents(files: Vec<IndexedFile>, options: &IndexOptions) -> Result<Vec<Document>> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.jobs)
+        .build()
+        .map_err(|e| EngineError::Rag(format!("Failed to start indexing workers: {e}")))?;
+    let mut built = pool.install(|| {
+        files
+            .into_par_iter()
+            .map(|file| build_document(&file.path, file.filename, file.modified))
+            .collect::<Result<Vec<_>>>()
+    })?;
+    let contents: Vec<String> = built.iter().map(|d| d.content.clone()).collect();
+    let embeddings = options.embedder.embed_batch(&contents).await?;
+    for (doc, embedding) in built.iter_mut().zip(embeddings) {
+        doc.embedding = embedding;
     }
+    Ok(built)
+}
 
-    store.save_to_disk(output_ref)?;
-    log::info!("Indexed {} files", store.len());
-    Ok(store)
+/// Reads `file` and extracts its convention metadata. The embedding is
+/// filled in afterwards by the configured embedder.
+fn build_document(file: &Path, filename: String, modified: u64) -> Result<Document> {
+    let content = fs::read_to_string(file).map_err(|e| EngineError::io(file, e))?;
+    let language = Language::from_path(&filename);
+    let extract = |f: fn(&Language, &str) -> Vec<String>| {
+        language.map(|l| f(&l, &content)).unwrap_or_default()
+    };
+    Ok(Document {
+        embedding: Vec::new(),
+        function_signatures: extract(Language::function_signatures),
+        log_patterns: extract(Language::log_patterns),
+        error_snippets: extract(Language::error_snippets),
+        filename,
+        language,
+        content,
+        modified,
+    })
 }
 
-fn build_globset(patterns: &[String]) -> Result<GlobSet> {
+pub(crate) fn build_globset(patterns: &[String]) -> Result<GlobSet> {
     let mut builder = GlobSetBuilder::new();
     for pattern in patterns {
         let glob = Glob::new(pattern).map_err(|e| EngineError::Config(e.to_string()))?;
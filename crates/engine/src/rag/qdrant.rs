@@ -0,0 +1,235 @@
+//! A `VectorStore` backed by a Qdrant collection, over its HTTP API.
+//!
+//! Each indexed file becomes one point whose id is derived from its
+//! filename, so re-indexing overwrites rather than duplicates. The document
+//! metadata travels in the point payload and is reconstructed on search.
+//! The collection is created with cosine distance on first upsert if it does
+//! not exist yet.
+
+use super::{Document, Language, VectorStore};
+use crate::config::{Config, QdrantConfig};
+use crate::error::{EngineError, Result};
+use crate::llm::{http_client, Timeouts};
+use async_trait::async_trait;
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+/// Number of points sent per upsert request.
+const UPSERT_BATCH: usize = 128;
+
+/// A vector store persisting documents in a Qdrant collection.
+pub struct QdrantVectorStore {
+    client: Client,
+    /// URL of the collection, e.g. `http://localhost:6333/collections/code`.
+    collection_url: String,
+    api_key: Option<String>,
+    /// Whether the collection is known to exist.
+    ready: bool,
+}
+
+impl QdrantVectorStore {
+    /// Creates a store for the collection described by `config`.
+    pub fn new(config: &QdrantConfig, timeouts: Timeouts) -> Self {
+        Self {
+            client: http_client(timeouts),
+            collection_url: format!(
+                "{}/collections/{}",
+                config.url.trim_end_matches('/'),
+                config.collection
+            ),
+            api_key: config.api_key.clone(),
+            ready: false,
+        }
+    }
+
+    /// Creates a store from the `[rag.qdrant]` section, failing with
+    /// `EngineError::Config` if it is missing.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let qdrant = config
+            .rag
+            .qdrant
+            .as_ref()
+            .ok_or_else(|| EngineError::Config("`[rag.qdrant]` is not configured".into()))?;
+        Ok(Self::new(qdrant, Timeouts::from_config(config)))
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self
+            .client
+            .request(method, format!("{}{}", self.collection_url, path));
+        match &self.api_key {
+            Some(key) => request.header("api-key", key),
+            None => request,
+        }
+    }
+
+    /// Creates the collection for `dimension`-long vectors unless it exists.
+    async fn ensure_collection(&mut self, dimension: usize) -> Result<()> {
+        if self.ready {
+            return Ok(());
+        }
+        let res = self
+            .request(Method::GET, "")
+            .send()
+            .await
+            .map_err(|e| qdrant_error("collection lookup", e))?;
+        if res.status() == StatusCode::NOT_FOUND {
+            log::info!("Creating Qdrant collection at {}", self.collection_url);
+            self.request(Method::PUT, "")
+                .json(&json!({"vectors": {"size": dimension, "distance": "Cosine"}}))
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| qdrant_error("collection creation", e))?;
+        } else {
+            res.error_for_status()
+                .map_err(|e| qdrant_error("collection lookup", e))?;
+        }
+        self.ready = true;
+        Ok(())
+    }
+}
+
+/// Document fields stored in a point's payload.
+#[derive(Serialize, Deserialize)]
+struct Payload {
+    filename: String,
+    content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language: Option<Language>,
+    #[serde(default)]
+    function_signatures: Vec<String>,
+    #[serde(default)]
+    log_patterns: Vec<String>,
+    #[serde(default)]
+    error_snippets: Vec<String>,
+    #[serde(default)]
+    modified: u64,
+}
+
+impl Payload {
+    fn new(doc: &Document) -> Self {
+        Self {
+            filename: doc.filename.clone(),
+            content: doc.content.clone(),
+            language: doc.language,
+            function_signatures: doc.function_signatures.clone(),
+            log_patterns: doc.log_patterns.clone(),
+            error_snippets: doc.error_snippets.clone(),
+            modified: doc.modified,
+        }
+    }
+
+    fn into_document(self, embedding: Vec<f32>) -> Document {
+        Document {
+            filename: self.filename,
+            content: self.content,
+            embedding,
+            language: self.language,
+            function_signatures: self.function_signatures,
+            log_patterns: self.log_patterns,
+            error_snippets: self.error_snippets,
+            modified: self.modified,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Point<'a> {
+    id: u64,
+    vector: &'a [f32],
+    payload: Payload,
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    result: Vec<ScoredPoint>,
+}
+
+#[derive(Deserialize)]
+struct ScoredPoint {
+    score: f32,
+    payload: Payload,
+    #[serde(default)]
+    vector: Option<Vec<f32>>,
+}
+
+/// Stable point id for `filename`: the first eight bytes of its SHA-256.
+pub fn point_id(filename: &str) -> u64 {
+    let digest = Sha256::digest(filename.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+fn qdrant_error(operation: &str, e: reqwest::Error) -> EngineError {
+    EngineError::Rag(format!("Qdrant {} failed: {}", operation, e))
+}
+
+#[async_trait]
+impl VectorStore for QdrantVectorStore {
+    /// Upserts the document as a single point.
+    async fn add(&mut self, document: Document) -> Result<()> {
+        self.add_batch(vec![document]).await
+    }
+
+    /// Upserts the documents in batches of `UPSERT_BATCH` points.
+    async fn add_batch(&mut self, documents: Vec<Document>) -> Result<()> {
+        let Some(dimension) = documents.first().map(|d| d.embedding.len()) else {
+            return Ok(());
+        };
+        self.ensure_collection(dimension).await?;
+        for chunk in documents.chunks(UPSERT_BATCH) {
+            let points: Vec<Point> = chunk
+                .iter()
+                .map(|doc| Point {
+                    id: point_id(&doc.filename),
+                    vector: &doc.embedding,
+                    payload: Payload::new(doc),
+                })
+                .collect();
+            self.request(Method::PUT, "/points?wait=true")
+                .json(&json!({ "points": points }))
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| qdrant_error("upsert", e))?;
+        }
+        Ok(())
+    }
+
+    /// Runs a cosine search in the collection, rebuilding each hit's
+    /// `Document` from its payload and stored vector.
+    async fn search_scored(
+        &self,
+        query_embedding: Vec<f32>,
+        top_k: usize,
+    ) -> Result<Vec<(Document, f32)>> {
+        let res = self
+            .request(Method::POST, "/points/search")
+            .json(&json!({
+                "vector": query_embedding,
+                "limit": top_k,
+                "with_payload": true,
+                "with_vector": true,
+            }))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| qdrant_error("search", e))?;
+        let body: SearchResponse = res
+            .json()
+            .await
+            .map_err(|e| qdrant_error("search response parsing", e))?;
+        Ok(body
+            .result
+            .into_iter()
+            .map(|point| {
+                let embedding = point.vector.unwrap_or_default();
+                (point.payload.into_document(embedding), point.score)
+            })
+            .collect())
+    }
+}
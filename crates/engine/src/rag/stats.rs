@@ -0,0 +1,92 @@
+//! Summary statistics about a persisted index, for `reviewlens index stats`.
+
+use super::InMemoryVectorStore;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Number of largest documents listed in `IndexStats::largest`.
+const LARGEST: usize = 10;
+
+/// Key used in the extension histogram for files without an extension.
+pub const NO_EXTENSION: &str = "(none)";
+
+/// Aggregate facts about an `InMemoryVectorStore`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct IndexStats {
+    /// Number of indexed documents.
+    pub documents: usize,
+    /// Total size of the stored document contents, in bytes.
+    pub content_bytes: usize,
+    /// Embedder that built the index.
+    pub embedder: String,
+    /// Length of the stored embeddings, if recorded or inferable.
+    pub dimension: Option<usize>,
+    /// Build time in seconds since the Unix epoch, if recorded.
+    pub built_at: Option<u64>,
+    /// Repository `HEAD` at build time, if recorded.
+    pub head_commit: Option<String>,
+    /// The largest documents by content size, largest first.
+    pub largest: Vec<DocumentSize>,
+    /// Documents stored without an embedding.
+    pub empty_embeddings: usize,
+    /// Document count per file extension.
+    pub extensions: BTreeMap<String, usize>,
+}
+
+/// A document's name and content size.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DocumentSize {
+    pub filename: String,
+    pub bytes: usize,
+}
+
+impl InMemoryVectorStore {
+    /// Computes summary statistics over the stored documents.
+    pub fn stats(&self) -> IndexStats {
+        let mut largest: Vec<DocumentSize> = self
+            .documents
+            .iter()
+            .map(|d| DocumentSize {
+                filename: d.filename.clone(),
+                bytes: d.content.len(),
+            })
+            .collect();
+        largest.sort_by(|a, b| {
+            b.bytes
+                .cmp(&a.bytes)
+                .then_with(|| a.filename.cmp(&b.filename))
+        });
+        largest.truncate(LARGEST);
+
+        let mut extensions = BTreeMap::new();
+        for doc in &self.documents {
+            let ext = Path::new(&doc.filename)
+                .extension()
+                .map(|e| e.to_string_lossy().into_owned())
+                .unwrap_or_else(|| NO_EXTENSION.to_string());
+            *extensions.entry(ext).or_insert(0) += 1;
+        }
+
+        IndexStats {
+            documents: self.documents.len(),
+            content_bytes: self.documents.iter().map(|d| d.content.len()).sum(),
+            embedder: self.embedder_name().to_string(),
+            dimension: self.dimension.or_else(|| {
+                self.documents
+                    .iter()
+                    .map(|d| d.embedding.len())
+                    .find(|len| *len > 0)
+            }),
+            built_at: self.built_at,
+            head_commit: self.head_commit.clone(),
+            largest,
+            empty_embeddings: self
+                .documents
+                .iter()
+                .filter(|d| d.embedding.is_empty())
+                .count(),
+            extensions,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! GitHub Actions annotations for findings.
+//!
+//! A workflow command such as `::error file=a.rs,line=3,title=...::message`
+//! printed to stdout shows up as an inline annotation on the pull request.
+//! High and critical findings become errors, the rest warnings. GitHub caps
+//! the annotations it displays, so at most `max` are printed, most severe
+//! first, followed by a notice counting the ones left out.
+
+use crate::config::Severity;
+use crate::scanner::Issue;
+
+/// Default number of findings annotated in one run.
+pub const DEFAULT_MAX_ANNOTATIONS: usize = 50;
+
+/// Returns one workflow command per finding, at most `max` of them, and a
+/// trailing `::notice` if some were omitted. `prefix` is joined to each file
+/// path, for repositories checked out below the workflow's working directory.
+pub fn github(issues: &[Issue], max: usize, prefix: Option<&str>) -> Vec<String> {
+    let mut ordered: Vec<&Issue> = issues.iter().collect();
+    // Stable, so findings of equal severity keep the report order.
+    ordered.sort_by(|a, b| b.severity.cmp(&a.severity));
+    let mut lines: Vec<String> = ordered
+        .iter()
+        .take(max)
+        .map(|issue| command(issue, prefix))
+        .collect();
+    if ordered.len() > max {
+        let omitted = ordered.len() - max;
+        lines.push(format!(
+            "::notice::{} more finding{} omitted; see the review report",
+            omitted,
+            if omitted == 1 { "" } else { "s" }
+        ));
+    }
+    lines
+}
+
+/// Formats the workflow command annotating `issue`.
+pub fn command(issue: &Issue, prefix: Option<&str>) -> String {
+    let level = match issue.severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium | Severity::Low => "warning",
+    };
+    let file = match prefix {
+        Some(prefix) => format!("{}/{}", prefix.trim_end_matches('/'), issue.file_path),
+        None => issue.file_path.clone(),
+    };
+    let mut properties = format!("file={}", escape_property(&file));
+    if issue.line_number > 0 {
+        properties.push_str(&format!(",line={}", issue.line_number));
+    }
+    properties.push_str(&format!(",title={}", escape_property(&issue.title)));
+    format!(
+        "::{} {}::{}",
+        level,
+        properties,
+        escape_data(&issue.description)
+    )
+}
+
+/// Escapes the message of a workflow command.
+pub fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a `key=value` property of a workflow command, which may not
+/// contain the `:` and `,` separators either.
+pub fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
@@ -0,0 +1,129 @@
+//! Reports spanning several repositories.
+//!
+//! `reviewlens check --workspace` reviews each repository on its own and
+//! nests the resulting reports in a `CombinedReport`, which renders them one
+//! after another under a heading per repository.
+
+use super::{JsonGenerator, MarkdownGenerator, ReportGenerator, ReviewReport};
+use crate::config::Severity;
+use crate::error::{EngineError, Result};
+use serde::{Deserialize, Serialize};
+
+/// The review of one repository in a workspace.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RepoReport {
+    /// Display name of the repository.
+    pub name: String,
+    /// Path of the repository, resolved against the workspace file.
+    pub path: String,
+    /// The repository's own report.
+    pub report: ReviewReport,
+}
+
+impl RepoReport {
+    /// The highest severity among the repository's issues.
+    pub fn highest_severity(&self) -> Option<Severity> {
+        self.report.issues.iter().map(|i| i.severity.clone()).max()
+    }
+}
+
+/// The reviews of every repository in a workspace, in workspace order.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CombinedReport {
+    pub repos: Vec<RepoReport>,
+}
+
+impl CombinedReport {
+    /// Total number of issues across all repositories.
+    pub fn total_issues(&self) -> usize {
+        self.repos
+            .iter()
+            .map(|repo| repo.report.stats.total_issues())
+            .sum()
+    }
+
+    /// The highest severity across all repositories.
+    pub fn highest_severity(&self) -> Option<Severity> {
+        self.repos
+            .iter()
+            .filter_map(RepoReport::highest_severity)
+            .max()
+    }
+
+    /// Renders an overview table followed by each repository's Markdown
+    /// report, with its headings demoted one level under `## <name>`.
+    pub fn to_markdown(&self) -> Result<String> {
+        let mut md = String::from("# Workspace Review Report\n\n");
+        md.push_str(&format!(
+            "{} repositor{}, {} issue{}\n\n",
+            self.repos.len(),
+            if self.repos.len() == 1 { "y" } else { "ies" },
+            self.total_issues(),
+            if self.total_issues() == 1 { "" } else { "s" }
+        ));
+        md.push_str("| Repository | Path | Files | Issues | Highest |\n|---|---|---|---|---|\n");
+        for repo in &self.repos {
+            md.push_str(&format!(
+                "| {} | `{}` | {} | {} | {} |\n",
+                repo.name.replace('|', "\\|"),
+                repo.path,
+                repo.report.stats.files_reviewed,
+                repo.report.stats.total_issues(),
+                repo.highest_severity()
+                    .as_ref()
+                    .map_or("-", Severity::as_str)
+            ));
+        }
+        for repo in &self.repos {
+            let nested = MarkdownGenerator.generate(&repo.report)?;
+            md.push_str(&format!("\n## {}\n\n", repo.name));
+            md.push_str(&demote_headings(
+                nested.trim_start_matches("# Code Review Report\n\n"),
+            ));
+        }
+        Ok(md)
+    }
+
+    /// Renders the workspace as JSON, with each report as the JSON
+    /// generator renders it on its own.
+    pub fn to_json(&self) -> Result<String> {
+        let repos = self
+            .repos
+            .iter()
+            .map(|repo| {
+                let report: serde_json::Value =
+                    serde_json::from_str(&JsonGenerator.generate(&repo.report)?)
+                        .map_err(|e| EngineError::Report(e.to_string()))?;
+                Ok(serde_json::json!({
+                    "name": repo.name,
+                    "path": repo.path,
+                    "report": report,
+                }))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        serde_json::to_string_pretty(&serde_json::json!({ "repos": repos }))
+            .map_err(|e| EngineError::Report(e.to_string()))
+    }
+
+    /// Parses a combined report from its JSON representation.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| EngineError::Report(e.to_string()))
+    }
+}
+
+/// Adds a `#` to every Markdown heading outside fenced code blocks.
+fn demote_headings(md: &str) -> String {
+    let mut fenced = false;
+    let mut out = String::with_capacity(md.len() + 64);
+    for line in md.lines() {
+        if line.starts_with("```") {
+            fenced = !fenced;
+        }
+        if !fenced && line.starts_with('#') {
+            out.push('#');
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
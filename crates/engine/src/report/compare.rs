@@ -0,0 +1,100 @@
+//! Comparison of two review reports.
+//!
+//! Findings are matched by fingerprint, so a finding that only moved to a
+//! different line is still recognised. Issues from reports written before
+//! fingerprints existed fall back to matching by rule, file, line and title.
+
+use super::ReviewReport;
+use crate::config::FailOn;
+use crate::scanner::Issue;
+use std::collections::HashSet;
+
+/// Findings of a later report classified against an earlier one.
+#[derive(Debug, Clone, Default)]
+pub struct ReportComparison {
+    /// Findings only in the later report.
+    pub new: Vec<Issue>,
+    /// Findings only in the earlier report.
+    pub resolved: Vec<Issue>,
+    /// Findings in both reports, as they appear in the later one.
+    pub persisting: Vec<Issue>,
+}
+
+/// Classifies the findings of `after` against those of `before`.
+pub fn compare(before: &ReviewReport, after: &ReviewReport) -> ReportComparison {
+    let before_keys: HashSet<String> = before.issues.iter().map(identity).collect();
+    let after_keys: HashSet<String> = after.issues.iter().map(identity).collect();
+    let mut comparison = ReportComparison::default();
+    for issue in &after.issues {
+        if before_keys.contains(&identity(issue)) {
+            comparison.persisting.push(issue.clone());
+        } else {
+            comparison.new.push(issue.clone());
+        }
+    }
+    comparison.resolved = before
+        .issues
+        .iter()
+        .filter(|issue| !after_keys.contains(&identity(issue)))
+        .cloned()
+        .collect();
+    comparison
+}
+
+impl ReportComparison {
+    /// Returns the new findings at or above `fail_on`.
+    pub fn failing_new(&self, fail_on: &FailOn) -> Vec<&Issue> {
+        match fail_on.threshold() {
+            Some(threshold) => self
+                .new
+                .iter()
+                .filter(|issue| issue.severity >= threshold)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Renders the comparison as Markdown with one section per bucket.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# Review Comparison\n\n");
+        md.push_str(&format!(
+            "{} new, {} resolved, {} persisting\n",
+            self.new.len(),
+            self.resolved.len(),
+            self.persisting.len()
+        ));
+        for (heading, issues) in [
+            ("New", &self.new),
+            ("Resolved", &self.resolved),
+            ("Persisting", &self.persisting),
+        ] {
+            md.push_str(&format!("\n## {} ({})\n\n", heading, issues.len()));
+            if issues.is_empty() {
+                md.push_str("None.\n");
+                continue;
+            }
+            for issue in issues {
+                md.push_str(&format!(
+                    "- `{}` {} at `{}:{}`\n",
+                    issue.severity.as_str(),
+                    issue.title,
+                    issue.file_path,
+                    issue.line_number
+                ));
+            }
+        }
+        md
+    }
+}
+
+/// The key `issue` is matched on: its fingerprint when it has one.
+fn identity(issue: &Issue) -> String {
+    if issue.fingerprint.is_empty() {
+        format!(
+            "{}\0{}\0{}\0{}",
+            issue.rule_id, issue.file_path, issue.line_number, issue.title
+        )
+    } else {
+        issue.fingerprint.clone()
+    }
+}
@@ -0,0 +1,164 @@
+//! Lightweight interaction analysis between changed files.
+//!
+//! Relationships are detected with simple per-language heuristics rather than
+//! a real parser: import statements (`use crate::x`, `import x`, `from x
+//! import`, `require("./x")`) and qualified calls (`x::f(`, `x.f(`) that name
+//! another changed file by its stem. Only edges between changed files are
+//! considered.
+
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// The kind of relationship between two files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EdgeKind {
+    /// The source file calls a function defined in the target file.
+    Calls,
+    /// The source file imports the target file without calling into it.
+    Imports,
+}
+
+/// A directed relationship between two changed files.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+}
+
+fn file_stem(path: &str) -> &str {
+    Path::new(path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(path)
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or(path)
+}
+
+fn imports(content: &str, stem: &str) -> bool {
+    let stem = regex::escape(stem);
+    let patterns = [
+        // Rust
+        format!(r"(?m)^\s*(pub\s+)?use\s+(crate|super|self)::{stem}\b"),
+        format!(r"(?m)^\s*(pub\s+)?use\s+{stem}::"),
+        format!(r"(?m)^\s*(pub\s+)?mod\s+{stem}\s*;"),
+        // Python
+        format!(r"(?m)^\s*import\s+([\w.]+\.)?{stem}\b"),
+        format!(r"(?m)^\s*from\s+[\w.]*\b{stem}\s+import\b"),
+        // JavaScript / TypeScript
+        format!(r#"(?m)from\s+['"][./\w-]*/{stem}(\.\w+)?['"]"#),
+        format!(r#"require\(\s*['"][./\w-]*/{stem}(\.\w+)?['"]\s*\)"#),
+        // Go
+        format!(r#"(?m)^\s*(import\s+)?"[\w./-]*/{stem}""#),
+    ];
+    patterns
+        .iter()
+        .filter_map(|p| Regex::new(p).ok())
+        .any(|re| re.is_match(content))
+}
+
+fn calls(content: &str, stem: &str) -> bool {
+    let stem = regex::escape(stem);
+    Regex::new(&format!(r"\b{stem}(::|\.)\w+\s*\("))
+        .map(|re| re.is_match(content))
+        .unwrap_or(false)
+}
+
+/// Finds relationships between the given `(path, content)` pairs.
+pub fn interactions(files: &[(String, String)]) -> BTreeSet<Edge> {
+    let mut edges = BTreeSet::new();
+    for (from, content) in files {
+        for (to, _) in files {
+            if from == to {
+                continue;
+            }
+            let stem = file_stem(to);
+            if stem.is_empty() {
+                continue;
+            }
+            let kind = if calls(content, stem) {
+                EdgeKind::Calls
+            } else if imports(content, stem) {
+                EdgeKind::Imports
+            } else {
+                continue;
+            };
+            edges.insert(Edge {
+                from: from.clone(),
+                to: to.clone(),
+                kind,
+            });
+        }
+    }
+    edges
+}
+
+/// Renders a Mermaid diagram for the given files, or `None` when no two files
+/// interact. Call relationships produce a `sequenceDiagram`; when files only
+/// import each other a `graph TD` is emitted instead. At most `max_edges`
+/// edges are rendered.
+pub fn render(files: &[(String, String)], max_edges: usize) -> Option<String> {
+    let edges = interactions(files);
+    if edges.is_empty() || max_edges == 0 {
+        return None;
+    }
+    let omitted = edges.len().saturating_sub(max_edges);
+    let has_calls = edges.iter().any(|e| e.kind == EdgeKind::Calls);
+
+    let mut diagram = String::new();
+    if has_calls {
+        diagram.push_str("sequenceDiagram\n");
+        for edge in edges.iter().take(max_edges) {
+            let label = match edge.kind {
+                EdgeKind::Calls => "calls",
+                EdgeKind::Imports => "uses",
+            };
+            diagram.push_str(&format!(
+                "    {}->>{}: {}\n",
+                file_name(&edge.from),
+                file_name(&edge.to),
+                label
+            ));
+        }
+        if omitted > 0 {
+            diagram.push_str(&format!(
+                "    Note over {}: {} more interaction{} omitted\n",
+                file_name(&edges.iter().next()?.from),
+                omitted,
+                if omitted == 1 { "" } else { "s" }
+            ));
+        }
+    } else {
+        diagram.push_str("graph TD\n");
+        for edge in edges.iter().take(max_edges) {
+            diagram.push_str(&format!(
+                "    {}[\"{}\"] -->|imports| {}[\"{}\"]\n",
+                node_id(&edge.from),
+                file_name(&edge.from),
+                node_id(&edge.to),
+                file_name(&edge.to)
+            ));
+        }
+        if omitted > 0 {
+            diagram.push_str(&format!(
+                "    %% {} more interaction{} omitted\n",
+                omitted,
+                if omitted == 1 { "" } else { "s" }
+            ));
+        }
+    }
+    Some(diagram)
+}
+
+/// Mermaid node ids may not contain path separators or dots.
+fn node_id(path: &str) -> String {
+    path.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
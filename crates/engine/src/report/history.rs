@@ -0,0 +1,142 @@
+//! Run history kept across reviews of a branch.
+//!
+//! `[report] history-path` names a JSON Lines file with one compact record
+//! per `check` run: when it ran, on which branch and commit, and the
+//! fingerprint and severity of each finding. A review compares its findings
+//! with the latest record for its branch to report what is new and what was
+//! resolved since then.
+
+use crate::config::Severity;
+use crate::error::{EngineError, Result};
+use crate::scanner::Issue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// One finding as recorded in the history.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct HistoryFinding {
+    pub fingerprint: String,
+    pub severity: Severity,
+}
+
+/// One review run as recorded in the history.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct HistoryRecord {
+    /// When the run finished, in RFC 3339.
+    pub timestamp: String,
+    pub branch: String,
+    /// The reviewed `HEAD` commit, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+    pub findings: Vec<HistoryFinding>,
+}
+
+impl HistoryRecord {
+    /// Records the findings of `issues` as a run of `branch` at `head`,
+    /// finishing now.
+    pub fn new(branch: &str, head: Option<&str>, issues: &[Issue]) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            branch: branch.to_string(),
+            head: head.map(str::to_string),
+            findings: issues
+                .iter()
+                .map(|issue| HistoryFinding {
+                    fingerprint: issue.fingerprint.clone(),
+                    severity: issue.severity.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// How the findings of a run compare with the previous run of its branch.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SinceLastRun {
+    pub branch: String,
+    /// When the previous run finished.
+    pub timestamp: String,
+    /// The commit the previous run reviewed, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+    /// Findings absent from the previous run.
+    pub new: usize,
+    /// Findings of the previous run that are gone.
+    pub resolved: usize,
+    /// Findings present in both runs.
+    pub persisting: usize,
+}
+
+impl SinceLastRun {
+    /// Compares `issues` with `previous` by fingerprint.
+    pub fn compare(previous: &HistoryRecord, issues: &[Issue]) -> Self {
+        let before: HashSet<&str> = previous
+            .findings
+            .iter()
+            .map(|f| f.fingerprint.as_str())
+            .collect();
+        let after: HashSet<&str> = issues.iter().map(|i| i.fingerprint.as_str()).collect();
+        Self {
+            branch: previous.branch.clone(),
+            timestamp: previous.timestamp.clone(),
+            head: previous.head.clone(),
+            new: after.difference(&before).count(),
+            resolved: before.difference(&after).count(),
+            persisting: after.intersection(&before).count(),
+        }
+    }
+}
+
+fn history_error(path: &Path, e: impl std::fmt::Display) -> EngineError {
+    EngineError::Report(format!("history file {}: {}", path.display(), e))
+}
+
+/// Reads every record in the history at `path`, oldest first. A missing file
+/// has no records; lines that do not parse are logged and skipped.
+pub fn load(path: &Path) -> Result<Vec<HistoryRecord>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(history_error(path, e)),
+    };
+    Ok(content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(i, line)| match serde_json::from_str(line) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                log::warn!("Skipping line {} of {}: {}", i + 1, path.display(), e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Returns the most recent record for `branch` in the history at `path`.
+pub fn latest(path: &Path, branch: &str) -> Result<Option<HistoryRecord>> {
+    Ok(load(path)?
+        .into_iter()
+        .rev()
+        .find(|record| record.branch == branch))
+}
+
+/// Appends `record` to the history at `path`, creating the file and its
+/// directory if needed, and keeps only the last `keep` records.
+pub fn append(path: &Path, record: &HistoryRecord, keep: usize) -> Result<()> {
+    let mut records = load(path)?;
+    records.push(record.clone());
+    let records = &records[records.len().saturating_sub(keep)..];
+    let mut out = String::new();
+    for record in records {
+        out.push_str(&serde_json::to_string(record).map_err(|e| history_error(path, e))?);
+        out.push('\n');
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| history_error(path, e))?;
+    }
+    fs::write(path, out).map_err(|e| history_error(path, e))
+}
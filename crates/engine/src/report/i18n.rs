@@ -0,0 +1,197 @@
+//! Translations of the fixed strings in Markdown reports.
+//!
+//! `[report] language` selects a table by its primary subtag, so `ja-JP`
+//! uses the Japanese strings. English is complete; any key missing from
+//! another table, and any language without a table, falls back to English.
+//! Scanner titles and descriptions are not translated.
+
+/// English strings, keyed by the name used in `text`.
+const EN: &[(&str, &str)] = &[
+    ("title", "Code Review Report"),
+    ("summary", "Summary"),
+    ("note", "Note"),
+    ("change-context", "Change Context"),
+    ("context-title", "Title"),
+    ("context-type", "Type"),
+    ("intent", "Intent vs. implementation"),
+    ("statistics", "📊 Statistics"),
+    ("severity", "Severity"),
+    ("confidence", "Confidence"),
+    ("count", "Count"),
+    ("rule", "Rule"),
+    ("tokens-used", "Tokens used"),
+    ("since-last-run", "Since Last Run"),
+    (
+        "since-last-run-line",
+        "{new} new, {resolved} resolved and {persisting} unchanged findings since the last run on `{branch}` ({when}).",
+    ),
+    ("policy-violations", "🚫 Policy Violations"),
+    (
+        "policy-violation-line",
+        "{count} `{severity}` findings; the policy allows at most {max}.",
+    ),
+    ("findings", "🚨 Security Findings"),
+    ("no-issues", "✅ No issues found."),
+    ("finding", "Title"),
+    ("file-line", "File:Line"),
+    ("description", "Description"),
+    ("suggested-fix", "Suggested Fix"),
+    ("author", "Author"),
+    ("see-fixes", "See `{rule}` in Suggested Fixes"),
+    ("hotspot", "hotspot"),
+    ("diff-suggestion", "Diff suggestion for {finding}"),
+    ("code-around", "Code around {finding}"),
+    ("llm-review", "LLM review of {finding}"),
+    ("finding-at", "`{title}` at `{location}`"),
+    ("suggested-fixes", "🛠️ Suggested Fixes"),
+    ("code-quality", "🧹 Code Quality & Conventions"),
+    ("no-code-quality", "No code quality issues found."),
+    ("location", "Location"),
+    ("quality-note", "Note"),
+    ("suppressed", "🔕 Suppressed Findings"),
+    ("reason", "Reason"),
+    ("no-reason", "_No reason given_"),
+    ("hotspots", "🔥 Hotspots"),
+    ("no-hotspots", "No hotspots identified."),
+    ("file", "File"),
+    ("changes", "Changes"),
+    ("diagram", "Diagram"),
+    ("appendix", "Appendix"),
+    ("run-metadata", "Run Metadata"),
+    ("config-snapshot", "Configuration Snapshot"),
+    (
+        "settings-intro",
+        "This review was run with the following settings:",
+    ),
+    (
+        "config-intro",
+        "This review was run with the following configuration:",
+    ),
+    ("retrieved-context", "Retrieved Context"),
+    ("retrieved-count", "Documents given to the LLM: {count}"),
+    ("retrieved-for", "score {score}, for {finding}"),
+];
+
+/// Japanese strings.
+const JA: &[(&str, &str)] = &[
+    ("title", "コードレビューレポート"),
+    ("summary", "概要"),
+    ("note", "注記"),
+    ("change-context", "変更の背景"),
+    ("context-title", "タイトル"),
+    ("context-type", "種別"),
+    ("intent", "意図と実装"),
+    ("statistics", "📊 統計"),
+    ("severity", "重大度"),
+    ("confidence", "確信度"),
+    ("count", "件数"),
+    ("rule", "ルール"),
+    ("tokens-used", "使用トークン数"),
+    ("since-last-run", "前回の実行からの変化"),
+    (
+        "since-last-run-line",
+        "`{branch}` の前回の実行（{when}）以降、新規 {new} 件、解決済み {resolved} 件、変化なし {persisting} 件です。",
+    ),
+    ("policy-violations", "🚫 ポリシー違反"),
+    (
+        "policy-violation-line",
+        "`{severity}` の指摘が {count} 件あります。ポリシーの上限は {max} 件です。",
+    ),
+    ("findings", "🚨 セキュリティ上の指摘"),
+    ("no-issues", "✅ 問題は見つかりませんでした。"),
+    ("finding", "タイトル"),
+    ("file-line", "ファイル:行"),
+    ("description", "説明"),
+    ("suggested-fix", "修正案"),
+    ("author", "作成者"),
+    ("see-fixes", "「修正案」の `{rule}` を参照"),
+    ("hotspot", "ホットスポット"),
+    ("diff-suggestion", "{finding} の差分の提案"),
+    ("code-around", "{finding} の周辺コード"),
+    ("llm-review", "{finding} に対する LLM のレビュー"),
+    ("finding-at", "`{location}` の `{title}`"),
+    ("suggested-fixes", "🛠️ 修正案"),
+    ("code-quality", "🧹 コード品質と規約"),
+    (
+        "no-code-quality",
+        "コード品質の問題は見つかりませんでした。",
+    ),
+    ("location", "場所"),
+    ("quality-note", "内容"),
+    ("suppressed", "🔕 抑制された指摘"),
+    ("reason", "理由"),
+    ("no-reason", "_理由なし_"),
+    ("hotspots", "🔥 ホットスポット"),
+    ("no-hotspots", "ホットスポットはありません。"),
+    ("file", "ファイル"),
+    ("changes", "変更"),
+    ("diagram", "図"),
+    ("appendix", "付録"),
+    ("run-metadata", "実行メタデータ"),
+    ("config-snapshot", "設定のスナップショット"),
+    ("settings-intro", "このレビューは次の設定で実行されました:"),
+    ("config-intro", "このレビューは次の構成で実行されました:"),
+    ("retrieved-context", "取得したコンテキスト"),
+    ("retrieved-count", "LLM に渡したドキュメント: {count} 件"),
+    ("retrieved-for", "スコア {score}、{finding}"),
+];
+
+/// Languages the LLM can be asked to write in, by primary subtag.
+const NAMES: &[(&str, &str)] = &[
+    ("en", "English"),
+    ("ja", "Japanese"),
+    ("zh", "Chinese"),
+    ("ko", "Korean"),
+    ("de", "German"),
+    ("fr", "French"),
+    ("es", "Spanish"),
+    ("pt", "Portuguese"),
+    ("it", "Italian"),
+    ("nl", "Dutch"),
+    ("ru", "Russian"),
+];
+
+/// Returns the primary subtag of `language`, lowercased: `ja` for `ja-JP`.
+fn primary(language: &str) -> String {
+    language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn lookup(table: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+/// Returns the string for `key` in `language`, falling back to English.
+/// Unknown keys are returned unchanged.
+pub fn text<'a>(language: &str, key: &'a str) -> &'a str {
+    let table = match primary(language).as_str() {
+        "ja" => JA,
+        _ => EN,
+    };
+    lookup(table, key)
+        .or_else(|| lookup(EN, key))
+        .unwrap_or(key)
+}
+
+/// Returns whether `language` is English, for which no instruction is
+/// added to LLM prompts.
+pub fn is_english(language: &str) -> bool {
+    primary(language) == "en"
+}
+
+/// Returns the instruction appended to LLM prompts so that the summary and
+/// comments are written in `language`, or `None` for English.
+pub fn prompt_instruction(language: &str) -> Option<String> {
+    if is_english(language) {
+        return None;
+    }
+    let code = primary(language);
+    let name = lookup(NAMES, &code).unwrap_or(language);
+    Some(format!(
+        "Write the summary and every comment in {} (`{}`). Keep JSON keys, code, file paths, rule ids and the \"Intent vs. implementation:\" label in English.",
+        name, language
+    ))
+}
@@ -0,0 +1,72 @@
+//! Links from findings to the reviewed source.
+//!
+//! `[report] link-template` is a URL with `{commit}`, `{path}` and `{line}`
+//! placeholders. When it is unset, a template is derived from the `origin`
+//! remote for repositories hosted on github.com or gitlab.com.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Fills in `template` for `path` at `line`. Returns `None` if the template
+/// needs a commit and `commit` is unknown.
+pub fn issue_url(template: &str, commit: Option<&str>, path: &str, line: usize) -> Option<String> {
+    let mut url = template.to_string();
+    if url.contains("{commit}") {
+        url = url.replace("{commit}", commit?);
+    }
+    Some(
+        url.replace("{path}", &path.replace(' ', "%20"))
+            .replace("{line}", &line.to_string()),
+    )
+}
+
+/// Derives a link template from a git remote URL on github.com or
+/// gitlab.com, in SSH or HTTPS form. Other hosts return `None`.
+pub fn template_for_remote(remote: &str) -> Option<String> {
+    let remote = remote.trim();
+    let (host, repo) = if let Some(rest) = remote
+        .strip_prefix("https://")
+        .or_else(|| remote.strip_prefix("http://"))
+        .or_else(|| remote.strip_prefix("ssh://"))
+    {
+        // Drop any credentials or user name before the host.
+        let rest = rest.rsplit_once('@').map_or(rest, |(_, host)| host);
+        rest.split_once('/')?
+    } else {
+        // scp-like syntax: `git@github.com:org/repo.git`.
+        let rest = remote.rsplit_once('@').map_or(remote, |(_, host)| host);
+        rest.split_once(':')?
+    };
+    // An explicit SSH port is not part of the web URL.
+    let host = host.split(':').next().unwrap_or(host);
+    let repo = repo.trim_end_matches('/').trim_end_matches(".git");
+    if repo.is_empty() {
+        return None;
+    }
+    match host {
+        "github.com" => Some(format!(
+            "https://github.com/{}/blob/{{commit}}/{{path}}#L{{line}}",
+            repo
+        )),
+        "gitlab.com" => Some(format!(
+            "https://gitlab.com/{}/-/blob/{{commit}}/{{path}}#L{{line}}",
+            repo
+        )),
+        _ => None,
+    }
+}
+
+/// Derives a link template from the `origin` remote of the repository at
+/// `repo`, or `None` if there is no such remote or its host is not known.
+pub fn detect_link_template(repo: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["remote", "get-url", "origin"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    template_for_remote(&String::from_utf8(output.stdout).ok()?)
+}
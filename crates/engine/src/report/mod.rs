@@ -4,23 +4,50 @@
 //! and formats them into a final report, such as a Markdown file.
 
 use crate::error::Result;
-use crate::{config::Config, scanner::Issue};
-use serde::Serialize;
+use crate::{
+    config::{Config, FailOn, IncludeConfig, PolicyViolation, Severity},
+    context::ReviewContext,
+    scanner::{Issue, Suppression},
+    schema::{SchemaKind, SCHEMA_VERSION},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub mod annotations;
+mod combined;
+mod compare;
+pub mod diagram;
+pub mod history;
+pub mod i18n;
+pub mod links;
+mod rules;
+mod snapshot;
+pub mod snippet;
+mod summary;
+pub use combined::{CombinedReport, RepoReport};
+pub use compare::{compare, ReportComparison};
+pub use rules::{summarize_rules, RuleSummary};
+pub use summary::summarize;
 
 /// Timing information for a run.
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TimingInfo {
     /// Total duration of the engine run in milliseconds.
     pub total_ms: u128,
+    /// Time spent retrieving RAG context, in milliseconds.
+    #[serde(default)]
+    pub retrieval_ms: u128,
 }
 
 /// Metadata captured during a review run.
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RuntimeMetadata {
     /// Version of the ruleset used during the run.
     pub ruleset_version: String,
     /// Identifier of the language model, if applicable.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
     /// Identifier for the driver/provider used.
     pub driver: String,
@@ -28,12 +55,153 @@ pub struct RuntimeMetadata {
     pub timings: TimingInfo,
     /// Whether the vector index was warm (true) or cold (false).
     pub index_warm: bool,
+    /// Whether `[rag] enabled` allowed the vector index to be used.
+    #[serde(default = "default_rag_enabled")]
+    pub rag_enabled: bool,
+    /// Number of documents in the vector index, if one was loaded and
+    /// reports its size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_documents: Option<usize>,
+    /// Whether the LLM summary was served from the response cache.
+    pub llm_cache_hit: bool,
+    /// Why the LLM summary was replaced by the fallback summary, if it was.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm_error: Option<String>,
+    /// Whether LLM calls were skipped to stay within `budget.tokens.max-per-run`.
+    pub budget_exhausted: bool,
+    /// Why a structured LLM response could not be parsed, in which case the
+    /// whole response was used as the summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm_parse_error: Option<String>,
+    /// Whether every changed file matched `[paths] docs-globs`, so the
+    /// scanners and the LLM were skipped.
+    #[serde(default)]
+    pub docs_only: bool,
+}
+
+fn default_rag_enabled() -> bool {
+    true
+}
+
+impl Default for RuntimeMetadata {
+    /// Empty metadata with the same defaults as a parsed report.
+    fn default() -> Self {
+        Self {
+            ruleset_version: String::new(),
+            model: None,
+            driver: String::new(),
+            timings: TimingInfo::default(),
+            index_warm: false,
+            rag_enabled: default_rag_enabled(),
+            index_documents: None,
+            llm_cache_hit: false,
+            llm_error: None,
+            budget_exhausted: false,
+            llm_parse_error: None,
+            docs_only: false,
+        }
+    }
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+/// A document the RAG index returned for a finding.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RetrievedContext {
+    /// Index of the finding in `issues` whose query retrieved the document.
+    pub query_issue_index: usize,
+    pub filename: String,
+    /// Cosine similarity between the query and the document.
+    pub score: f32,
+    /// The start of the document, redacted and truncated to
+    /// `[report] context-excerpt-chars`.
+    pub excerpt: String,
+}
+
+/// A changed file that was not reviewed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Aggregate statistics for a review run.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReviewStats {
+    /// Number of changed files that were scanned.
+    pub files_reviewed: usize,
+    /// Number of changed files skipped by path globs, without reviewable
+    /// hunks, over the `[limits]` caps, or because they could not be read.
+    pub files_skipped: usize,
+    /// Number of reviewable changed files left out because they did not
+    /// match the files requested with `ReviewEngine::with_files`.
+    #[serde(default)]
+    pub files_excluded: usize,
+    /// Files left out because the diff exceeded `[limits]`, with the reason.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_for_limits: Vec<SkippedFile>,
+    /// Files left out because their contents could not be read, for example
+    /// because they were deleted, with the reason.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unreadable: Vec<SkippedFile>,
+    /// Findings dropped for being below `[report] min-confidence`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub below_min_confidence: usize,
+    /// Lines added across reviewed files.
+    pub lines_added: usize,
+    /// Lines removed across reviewed files.
+    pub lines_removed: usize,
+    /// Issue counts keyed by severity.
+    pub issues_by_severity: BTreeMap<Severity, usize>,
+    /// Issue counts keyed by rule id.
+    pub issues_by_rule: BTreeMap<String, usize>,
+    /// Number of findings silenced by `reviewlens:ignore` directives.
+    pub suppressed: usize,
+    /// Tokens consumed by the LLM provider.
+    pub tokens_used: u32,
+}
+
+impl ReviewStats {
+    /// Total number of reported issues.
+    pub fn total_issues(&self) -> usize {
+        self.issues_by_severity.values().sum()
+    }
+
+    /// One-line human-readable summary of the statistics.
+    pub fn headline(&self) -> String {
+        let issues = self.total_issues();
+        let excluded = if self.files_excluded > 0 {
+            format!(", {} not selected", self.files_excluded)
+        } else {
+            String::new()
+        };
+        format!(
+            "Reviewed {} file{} (+{}/-{} lines, {} skipped{}): {} issue{}, {} suppressed",
+            self.files_reviewed,
+            if self.files_reviewed == 1 { "" } else { "s" },
+            self.lines_added,
+            self.lines_removed,
+            self.files_skipped,
+            excluded,
+            issues,
+            if issues == 1 { "" } else { "s" },
+            self.suppressed
+        )
+    }
 }
 
 /// Represents the final, consolidated review findings.
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ReviewReport {
     pub summary: String,
+    /// Aggregate statistics for the run.
+    pub stats: ReviewStats,
     pub issues: Vec<Issue>,
     /// Notes about code quality or convention deviations.
     pub code_quality: Vec<String>,
@@ -41,9 +209,82 @@ pub struct ReviewReport {
     pub hotspots: Vec<String>,
     /// Optional Mermaid sequence diagram showing file interactions.
     pub mermaid_diagram: Option<String>,
+    /// The configuration of the run. Generated reports embed only the part
+    /// selected by `[report] include-config`, so a parsed report may carry
+    /// defaults in place of the original settings.
+    #[serde(default)]
     pub config: Config,
     /// Runtime metadata such as model identifiers and timings.
     pub metadata: RuntimeMetadata,
+    /// Advisory notes about the run itself, such as a stale RAG index.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    /// Findings silenced by `reviewlens:ignore` directives on changed lines.
+    /// Rendered unless `[report] show-suppressed` is off.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed: Vec<Suppression>,
+    /// Findings grouped by rule, filled in when `[report] group-fixes` is
+    /// on. The Markdown report then lists each rule's fix once.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<RuleSummary>,
+    /// Title and description of the change, if given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<ReviewContext>,
+    /// The LLM's note on how the changes compare with the stated intent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intent_note: Option<String>,
+    /// New and resolved findings since the previous run on the branch, when
+    /// `[report] history-path` holds one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since_last_run: Option<history::SinceLastRun>,
+    /// The RAG context retrieved for the findings, when `[report]
+    /// include-context` is on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contexts: Vec<RetrievedContext>,
+    /// Severities with more findings than `[policy] max-findings` allows.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policy_violations: Vec<PolicyViolation>,
+}
+
+impl ReviewReport {
+    /// Parses a report written by `JsonGenerator`.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| crate::error::EngineError::Report(e.to_string()))
+    }
+
+    /// Returns the issues that meet the given failure threshold.
+    ///
+    /// Findings silenced with `reviewlens:ignore` never reach `issues`, so
+    /// only active findings are considered. `FailOn::Never` matches nothing,
+    /// and findings below `fail-on-confidence` never fail the run.
+    pub fn failing_issues(&self, fail_on: &FailOn) -> Vec<&Issue> {
+        match fail_on.threshold() {
+            Some(threshold) => self
+                .issues
+                .iter()
+                .filter(|issue| {
+                    issue.severity >= threshold
+                        && issue.confidence >= self.config.fail_on_confidence
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the issues that meet the `fail-on` threshold of their own
+    /// file, taking `[[overrides]]` in the report's configuration into account.
+    pub fn failing_issues_by_path(&self) -> Vec<&Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| {
+                self.config
+                    .fail_on_for(&issue.file_path)
+                    .threshold()
+                    .is_some_and(|threshold| issue.severity >= threshold)
+                    && issue.confidence >= self.config.fail_on_confidence
+            })
+            .collect()
+    }
 }
 
 /// A trait for generating a report from review findings.
@@ -66,56 +307,260 @@ pub struct MarkdownGenerator;
 /// A generator for creating JSON-formatted reports.
 pub struct JsonGenerator;
 
+/// Number of affected files listed before the rest are collapsed.
+const MAX_LISTED_FILES: usize = 10;
+
+/// Renders one rule of the Suggested Fixes section.
+fn render_rule(md: &mut String, rule: &RuleSummary) {
+    md.push_str(&format!(
+        "\n### `{}`: {}\n\n`{}` \u{b7} {} finding{} in {} file{}\n\n{}\n\n",
+        rule.rule,
+        rule.title,
+        rule.severity.as_str(),
+        rule.count,
+        if rule.count == 1 { "" } else { "s" },
+        rule.files.len(),
+        if rule.files.len() == 1 { "" } else { "s" },
+        rule.description
+    ));
+    if let Some(fix) = &rule.suggested_fix {
+        md.push_str(&format!("**Fix:** {}\n\n", fix));
+    }
+    let (listed, rest) = rule.files.split_at(rule.files.len().min(MAX_LISTED_FILES));
+    for file in listed {
+        md.push_str(&format!("- `{}`\n", file));
+    }
+    if !rest.is_empty() {
+        md.push_str(&format!(
+            "\n<details>\n<summary>{} more file{}</summary>\n\n",
+            rest.len(),
+            if rest.len() == 1 { "" } else { "s" }
+        ));
+        for file in rest {
+            md.push_str(&format!("- `{}`\n", file));
+        }
+        md.push_str("</details>\n");
+    }
+}
+
 impl ReportGenerator for MarkdownGenerator {
     fn generate(&self, report: &ReviewReport) -> Result<String> {
         let mut md = String::new();
+        let language = &report.config.report.language;
+        let t = |key| i18n::text(language, key);
 
-        md.push_str("# Code Review Report\n\n");
+        md.push_str(&format!("# {}\n\n", t("title")));
 
-        md.push_str("## Summary\n\n");
+        md.push_str(&format!("## {}\n\n", t("summary")));
         md.push_str(&report.summary);
         md.push_str("\n\n");
+        for note in &report.notes {
+            md.push_str(&format!("> **{}:** {}\n\n", t("note"), note));
+        }
+
+        if let Some(context) = &report.context {
+            md.push_str(&format!("## {}\n\n", t("change-context")));
+            if let Some(title) = &context.title {
+                md.push_str(&format!("**{}:** {}\n\n", t("context-title"), title.trim()));
+            }
+            if let Some(kind) = context.change_type() {
+                md.push_str(&format!("**{}:** `{}`\n\n", t("context-type"), kind));
+            }
+            if let Some(note) = &report.intent_note {
+                md.push_str(&format!("**{}:** {}\n\n", t("intent"), note));
+            }
+        }
 
-        md.push_str("## 🚨 Security Findings\n\n");
+        md.push_str(&format!("## {}\n\n", t("statistics")));
+        md.push_str(&format!("{}\n\n", report.stats.headline()));
+        if !report.stats.issues_by_severity.is_empty() {
+            md.push_str(&format!(
+                "| {} | {} |\n|---|---|\n",
+                t("severity"),
+                t("count")
+            ));
+            for (severity, count) in report.stats.issues_by_severity.iter().rev() {
+                md.push_str(&format!("| `{}` | {} |\n", severity.as_str(), count));
+            }
+            md.push('\n');
+        }
+        if !report.stats.issues_by_rule.is_empty() {
+            md.push_str(&format!("| {} | {} |\n|---|---|\n", t("rule"), t("count")));
+            for (rule, count) in &report.stats.issues_by_rule {
+                md.push_str(&format!("| {} | {} |\n", rule, count));
+            }
+            md.push('\n');
+        }
+        if report.stats.tokens_used > 0 {
+            md.push_str(&format!(
+                "{}: {}\n\n",
+                t("tokens-used"),
+                report.stats.tokens_used
+            ));
+        }
+
+        if !report.policy_violations.is_empty() {
+            md.push_str(&format!("## {}\n\n", t("policy-violations")));
+            for violation in &report.policy_violations {
+                md.push_str(&format!(
+                    "- {}\n",
+                    t("policy-violation-line")
+                        .replace("{count}", &violation.count.to_string())
+                        .replace("{severity}", violation.severity.as_str())
+                        .replace("{max}", &violation.max.to_string())
+                ));
+            }
+            md.push('\n');
+        }
+
+        if let Some(since) = &report.since_last_run {
+            let when = match &since.head {
+                Some(head) => format!(
+                    "{}, `{}`",
+                    since.timestamp,
+                    head.chars().take(8).collect::<String>()
+                ),
+                None => since.timestamp.clone(),
+            };
+            md.push_str(&format!("## {}\n\n", t("since-last-run")));
+            md.push_str(
+                &t("since-last-run-line")
+                    .replace("{new}", &since.new.to_string())
+                    .replace("{resolved}", &since.resolved.to_string())
+                    .replace("{persisting}", &since.persisting.to_string())
+                    .replace("{branch}", &since.branch)
+                    .replace("{when}", &when),
+            );
+            md.push_str("\n\n");
+        }
+
+        md.push_str(&format!("## {}\n\n", t("findings")));
 
         let mut sorted_issues = report.issues.clone();
         sorted_issues.sort_by(|a, b| b.severity.cmp(&a.severity));
 
         if sorted_issues.is_empty() {
-            md.push_str("✅ No issues found.\n");
+            md.push_str(&format!("{}\n", t("no-issues")));
         } else {
-            md.push_str("| Severity | Title | File:Line | Description | Suggested Fix |\n");
-            md.push_str("|---|---|---|---|---|\n");
+            let show_author = sorted_issues.iter().any(|i| i.author.is_some());
+            md.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} |",
+                t("severity"),
+                t("confidence"),
+                t("finding"),
+                t("file-line"),
+                t("description"),
+                t("suggested-fix")
+            ));
+            if show_author {
+                md.push_str(&format!(" {} |\n", t("author")));
+                md.push_str("|---|---|---|---|---|---|---|\n");
+            } else {
+                md.push_str("\n|---|---|---|---|---|---|\n");
+            }
             for issue in &sorted_issues {
+                let location = format!("`{}:{}`", issue.file_path, issue.line_number);
+                let location = match &issue.url {
+                    Some(url) => format!("[{}]({})", location, url),
+                    None => location,
+                };
+                // Grouped fixes are listed once per rule below the table.
+                let fix = match &issue.suggested_fix {
+                    Some(_) if !report.rules.is_empty() => {
+                        t("see-fixes").replace("{rule}", &issue.rule_id)
+                    }
+                    Some(fix) => fix.clone(),
+                    None => "-".to_string(),
+                };
+                let severity = match &issue.original_severity {
+                    Some(original) => {
+                        format!(
+                            "`{:?}` → `{:?}` ({})",
+                            original,
+                            issue.severity,
+                            t("hotspot")
+                        )
+                    }
+                    None => format!("`{:?}`", issue.severity),
+                };
                 md.push_str(&format!(
-                    "| `{:?}` | {} | `{}:{}` | {} | {} |\n",
-                    issue.severity,
+                    "| {} | `{}` | {} | {} | {} | {} |",
+                    severity,
+                    issue.confidence.as_str(),
                     issue.title,
-                    issue.file_path,
-                    issue.line_number,
+                    location,
                     issue.description,
-                    issue
-                        .suggested_fix
-                        .clone()
-                        .unwrap_or_else(|| "-".to_string())
+                    fix
                 ));
+                if show_author {
+                    match (&issue.author, &issue.commit) {
+                        (Some(author), Some(commit)) => md.push_str(&format!(
+                            " {} (`{}`) |",
+                            author,
+                            &commit[..commit.len().min(8)]
+                        )),
+                        (Some(author), None) => md.push_str(&format!(" {} |", author)),
+                        _ => md.push_str(" - |"),
+                    }
+                }
+                md.push('\n');
             }
 
             for issue in &sorted_issues {
-                if let Some(diff) = &issue.diff {
+                let finding = t("finding-at").replace("{title}", &issue.title).replace(
+                    "{location}",
+                    &format!("{}:{}", issue.file_path, issue.line_number),
+                );
+                if issue.diff.is_some() || issue.snippet.is_some() {
+                    let label = if issue.diff.is_some() {
+                        t("diff-suggestion")
+                    } else {
+                        t("code-around")
+                    };
                     md.push_str(&format!(
-                        "\n<details>\n<summary>Diff suggestion for `{}` at `{}:{}`</summary>\n\n```diff\n{}\n```\n</details>\n",
-                        issue.title, issue.file_path, issue.line_number, diff
+                        "\n<details>\n<summary>{}</summary>\n\n",
+                        label.replace("{finding}", &finding)
                     ));
+                    if let Some(snippet) = &issue.snippet {
+                        md.push_str(&format!("```\n{}\n```\n", snippet));
+                    }
+                    if let Some(diff) = &issue.diff {
+                        md.push_str(&format!("```diff\n{}\n```\n", diff));
+                    }
+                    md.push_str("</details>\n");
+                }
+                if issue.llm_comment.is_some() || issue.llm_patch.is_some() {
+                    md.push_str(&format!(
+                        "\n<details>\n<summary>{}</summary>\n\n",
+                        t("llm-review").replace("{finding}", &finding)
+                    ));
+                    if let Some(comment) = &issue.llm_comment {
+                        md.push_str(&format!("{}\n\n", comment));
+                    }
+                    if let Some(patch) = &issue.llm_patch {
+                        md.push_str(&format!("```diff\n{}\n```\n", patch));
+                    }
+                    md.push_str("</details>\n");
                 }
             }
         }
 
-        md.push_str("\n## 🧹 Code Quality & Conventions\n\n");
+        if !report.rules.is_empty() {
+            md.push_str(&format!("\n## {}\n", t("suggested-fixes")));
+            for rule in &report.rules {
+                render_rule(&mut md, rule);
+            }
+        }
+
+        md.push_str(&format!("\n## {}\n\n", t("code-quality")));
         if report.code_quality.is_empty() {
-            md.push_str("No code quality issues found.\n");
+            md.push_str(&format!("{}\n", t("no-code-quality")));
         } else {
-            md.push_str("| Location | Note |\n|---|---|\n");
+            md.push_str(&format!(
+                "| {} | {} |\n|---|---|\n",
+                t("location"),
+                t("quality-note")
+            ));
             for note in &report.code_quality {
                 if let Some((loc, desc)) = note.split_once(" - ") {
                     md.push_str(&format!("| `{}` | {} |\n", loc, desc));
@@ -125,11 +570,37 @@ impl ReportGenerator for MarkdownGenerator {
             }
         }
 
-        md.push_str("\n## 🔥 Hotspots\n\n");
+        if report.config.report.show_suppressed && !report.suppressed.is_empty() {
+            md.push_str(&format!("\n## {}\n\n", t("suppressed")));
+            md.push_str(&format!(
+                "| {} | {} | {} |\n|---|---|---|\n",
+                t("rule"),
+                t("location"),
+                t("reason")
+            ));
+            for suppression in &report.suppressed {
+                md.push_str(&format!(
+                    "| `{}` | `{}:{}` | {} |\n",
+                    suppression.rule,
+                    suppression.path,
+                    suppression.line,
+                    suppression
+                        .reason
+                        .as_deref()
+                        .map_or(t("no-reason").into(), |r| r.replace('|', "\\|"))
+                ));
+            }
+        }
+
+        md.push_str(&format!("\n## {}\n\n", t("hotspots")));
         if report.hotspots.is_empty() {
-            md.push_str("No hotspots identified.\n");
+            md.push_str(&format!("{}\n", t("no-hotspots")));
         } else {
-            md.push_str("| File | Changes |\n|---|---|\n");
+            md.push_str(&format!(
+                "| {} | {} |\n|---|---|\n",
+                t("file"),
+                t("changes")
+            ));
             for spot in &report.hotspots {
                 if let Some((file, changes)) = spot.split_once(" (") {
                     let changes = changes.trim_end_matches(')');
@@ -141,29 +612,61 @@ impl ReportGenerator for MarkdownGenerator {
         }
 
         if let Some(diagram) = &report.mermaid_diagram {
-            md.push_str("\n## Diagram\n\n");
+            md.push_str(&format!("\n## {}\n\n", t("diagram")));
             md.push_str("```mermaid\n");
             md.push_str(diagram);
             md.push_str("\n```\n");
         }
 
         md.push_str("\n---\n\n");
-        md.push_str("## Appendix\n\n");
+        md.push_str(&format!("## {}\n\n", t("appendix")));
 
-        md.push_str("### Run Metadata\n\n");
+        md.push_str(&format!("### {}\n\n", t("run-metadata")));
         md.push_str("```json\n");
         let metadata_json = serde_json::to_string_pretty(&report.metadata)
             .map_err(|e| crate::error::EngineError::Report(e.to_string()))?;
         md.push_str(&metadata_json);
         md.push_str("\n```\n\n");
 
-        md.push_str("### Configuration Snapshot\n\n");
-        md.push_str("This review was run with the following configuration:\n\n");
-        md.push_str("```json\n");
-        let config_json = serde_json::to_string_pretty(&report.config)
-            .map_err(|e| crate::error::EngineError::Report(e.to_string()))?;
-        md.push_str(&config_json);
-        md.push_str("\n```\n");
+        if let Some(snapshot) = snapshot::config_snapshot(&report.config)? {
+            md.push_str(&format!("### {}\n\n", t("config-snapshot")));
+            if report.config.report.include_config == IncludeConfig::Summary {
+                md.push_str(&format!("{}\n\n", t("settings-intro")));
+            } else {
+                md.push_str(&format!("{}\n\n", t("config-intro")));
+            }
+            md.push_str("```json\n");
+            let config_json = serde_json::to_string_pretty(&snapshot)
+                .map_err(|e| crate::error::EngineError::Report(e.to_string()))?;
+            md.push_str(&config_json);
+            md.push_str("\n```\n");
+        }
+
+        if !report.contexts.is_empty() {
+            md.push_str(&format!("\n### {}\n\n", t("retrieved-context")));
+            md.push_str(&format!(
+                "<details>\n<summary>{}</summary>\n\n",
+                t("retrieved-count").replace("{count}", &report.contexts.len().to_string())
+            ));
+            for context in &report.contexts {
+                let finding = match report.issues.get(context.query_issue_index) {
+                    Some(issue) => t("finding-at").replace("{title}", &issue.title).replace(
+                        "{location}",
+                        &format!("{}:{}", issue.file_path, issue.line_number),
+                    ),
+                    None => format!("#{}", context.query_issue_index),
+                };
+                md.push_str(&format!(
+                    "**`{}`** ({})\n\n```\n{}\n```\n\n",
+                    context.filename,
+                    t("retrieved-for")
+                        .replace("{score}", &format!("{:.2}", context.score))
+                        .replace("{finding}", &finding),
+                    context.excerpt
+                ));
+            }
+            md.push_str("</details>\n");
+        }
 
         Ok(md)
     }
@@ -171,7 +674,26 @@ impl ReportGenerator for MarkdownGenerator {
 
 impl ReportGenerator for JsonGenerator {
     fn generate(&self, report: &ReviewReport) -> Result<String> {
-        serde_json::to_string_pretty(report)
+        let mut value = serde_json::to_value(report)
+            .map_err(|e| crate::error::EngineError::Report(e.to_string()))?;
+        if let serde_json::Value::Object(fields) = &mut value {
+            fields.insert(
+                "$schema".to_string(),
+                serde_json::Value::String(SchemaKind::Report.id()),
+            );
+            fields.insert(
+                "schema_version".to_string(),
+                serde_json::Value::String(SCHEMA_VERSION.to_string()),
+            );
+            match snapshot::config_snapshot(&report.config)? {
+                Some(snapshot) => fields.insert("config".to_string(), snapshot),
+                None => fields.remove("config"),
+            };
+            if !report.config.report.show_suppressed {
+                fields.remove("suppressed");
+            }
+        }
+        serde_json::to_string_pretty(&value)
             .map_err(|e| crate::error::EngineError::Report(e.to_string()))
     }
 }
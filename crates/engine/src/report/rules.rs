@@ -0,0 +1,87 @@
+//! Findings grouped by rule.
+//!
+//! The same rule usually gives the same advice for every finding, so with
+//! `[report] group-fixes` the report lists each rule once, with how often
+//! and where it fired and its suggested fix, instead of repeating the fix on
+//! every row.
+
+use crate::config::Severity;
+use crate::scanner::Issue;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The findings of one rule, summarized.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RuleSummary {
+    /// Id of the rule, e.g. `secrets`.
+    pub rule: String,
+    /// The most common title among the rule's findings.
+    pub title: String,
+    /// The most common description among the rule's findings.
+    pub description: String,
+    /// The highest severity among the rule's findings.
+    pub severity: Severity,
+    /// Number of findings.
+    pub count: usize,
+    /// Files with at least one finding, sorted.
+    pub files: Vec<String>,
+    /// The most common suggested fix, if any finding has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_fix: Option<String>,
+}
+
+/// Groups `issues` by rule id, most severe rules first, then by number of
+/// findings and rule id. Ties between equally common texts go to the one
+/// seen first.
+pub fn summarize_rules(issues: &[Issue]) -> Vec<RuleSummary> {
+    let mut by_rule: BTreeMap<&str, Vec<&Issue>> = BTreeMap::new();
+    for issue in issues {
+        by_rule.entry(&issue.rule_id).or_default().push(issue);
+    }
+    let mut summaries: Vec<RuleSummary> = by_rule
+        .into_iter()
+        .map(|(rule, found)| {
+            let mut files: Vec<String> = found.iter().map(|i| i.file_path.clone()).collect();
+            files.sort();
+            files.dedup();
+            RuleSummary {
+                rule: rule.to_string(),
+                title: most_common(found.iter().map(|i| i.title.as_str())).unwrap_or_default(),
+                description: most_common(found.iter().map(|i| i.description.as_str()))
+                    .unwrap_or_default(),
+                severity: found
+                    .iter()
+                    .map(|i| i.severity.clone())
+                    .max()
+                    .unwrap_or(Severity::Low),
+                count: found.len(),
+                files,
+                suggested_fix: most_common(found.iter().filter_map(|i| i.suggested_fix.as_deref())),
+            }
+        })
+        .collect();
+    summaries.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then(b.count.cmp(&a.count))
+            .then(a.rule.cmp(&b.rule))
+    });
+    summaries
+}
+
+fn most_common<'a>(values: impl Iterator<Item = &'a str>) -> Option<String> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for value in values {
+        match counts.iter_mut().find(|(seen, _)| *seen == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value, 1)),
+        }
+    }
+    // `max_by_key` keeps the last maximum, so search in reverse.
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(value, _)| value.to_string())
+}
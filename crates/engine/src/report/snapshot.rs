@@ -0,0 +1,101 @@
+//! The configuration snapshot embedded in reports.
+//!
+//! `[report] include-config` selects how much is embedded. The summary keeps
+//! the shape of the configuration file so that reports still parse with
+//! `ReviewReport::from_json`. The full snapshot never contains API keys or
+//! URL credentials, and its strings are passed through the redactor.
+
+use crate::config::{Config, IncludeConfig};
+use crate::error::{EngineError, Result};
+use crate::privacy::Redactor;
+use crate::scanner::rule_enabled;
+use serde_json::{json, Map, Value};
+
+/// Returns the snapshot of `config` selected by its `include-config`
+/// setting, or `None` when it should be left out.
+pub(crate) fn config_snapshot(config: &Config) -> Result<Option<Value>> {
+    match config.report.include_config {
+        IncludeConfig::None => Ok(None),
+        IncludeConfig::Summary => Ok(Some(summary(config)?)),
+        IncludeConfig::Full => {
+            let mut value = to_value(config)?;
+            let redactor = Redactor::new(&config.privacy.redaction)?;
+            sanitize(&mut value, &redactor);
+            Ok(Some(value))
+        }
+    }
+}
+
+/// Provider, model, fail level, and the enabled rules with their severities.
+fn summary(config: &Config) -> Result<Value> {
+    let mut llm = Map::new();
+    llm.insert("provider".into(), to_value(&config.llm.provider)?);
+    if let Some(model) = &config.llm.model {
+        llm.insert("model".into(), Value::String(model.clone()));
+    }
+    let mut rules = Map::new();
+    if let Value::Object(all) = to_value(&config.rules)? {
+        for (rule, settings) in all {
+            if rule_enabled(config, &rule) {
+                rules.insert(
+                    rule,
+                    json!({ "enabled": true, "severity": settings["severity"] }),
+                );
+            }
+        }
+    }
+    for spec in config.scanners.external.iter().filter(|spec| spec.enabled) {
+        rules.insert(
+            spec.rule.clone(),
+            json!({ "enabled": true, "severity": spec.severity }),
+        );
+    }
+    Ok(json!({
+        "llm": llm,
+        "fail-on": config.fail_on.as_str(),
+        "rules": rules,
+    }))
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> Result<Value> {
+    serde_json::to_value(value).map_err(|e| EngineError::Report(e.to_string()))
+}
+
+/// Drops API keys and webhook URLs, whose path is the credential, strips
+/// credentials from other URLs, and redacts every string.
+fn sanitize(value: &mut Value, redactor: &Redactor) {
+    match value {
+        Value::Object(map) => {
+            map.remove("api-key");
+            map.remove("webhook-url");
+            for (key, inner) in map.iter_mut() {
+                if let (true, Value::String(url)) = (
+                    key.ends_with("url") || key.ends_with("endpoint"),
+                    &mut *inner,
+                ) {
+                    *url = strip_credentials(url);
+                }
+                sanitize(inner, redactor);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| sanitize(item, redactor)),
+        Value::String(text) => *text = redactor.redact(text),
+        _ => {}
+    }
+}
+
+/// Removes the user info and query string from `url`.
+fn strip_credentials(url: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return url.to_string();
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    format!("{}://{}{}", scheme, host, path)
+}
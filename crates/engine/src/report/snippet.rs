@@ -0,0 +1,47 @@
+//! Code snippets shown with findings.
+//!
+//! `[report] snippet-context` sets how many lines around the flagged one are
+//! captured, so a reviewer can judge a finding without opening the file.
+//! Each line is prefixed with its number, and the flagged line with `>`.
+//! Very long lines, such as minified code, are cut at a character boundary.
+
+/// Longest line, in characters, kept whole in a snippet.
+const MAX_LINE_CHARS: usize = 200;
+
+/// Returns lines `line - context ..= line + context` of `content`, clamped
+/// to the file, or `None` if `context` is zero or `line` is out of range.
+pub fn extract(content: &str, line: usize, context: usize) -> Option<String> {
+    if context == 0 || line == 0 {
+        return None;
+    }
+    let lines: Vec<&str> = content.lines().collect();
+    if line > lines.len() {
+        return None;
+    }
+    let first = line.saturating_sub(context).max(1);
+    let last = (line + context).min(lines.len());
+    let width = last.to_string().len();
+    let snippet = (first..=last)
+        .map(|n| {
+            let marker = if n == line { '>' } else { ' ' };
+            format!(
+                "{} {:>width$} | {}",
+                marker,
+                n,
+                truncate(lines[n - 1]),
+                width = width
+            )
+            .trim_end()
+            .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(snippet)
+}
+
+fn truncate(line: &str) -> String {
+    match line.char_indices().nth(MAX_LINE_CHARS) {
+        Some((end, _)) => format!("{}\u{2026}", &line[..end]),
+        None => line.to_string(),
+    }
+}
@@ -0,0 +1,66 @@
+//! Deterministic, offline review summaries.
+//!
+//! Used whenever no LLM summary is available: with the `null` provider, or
+//! when the LLM call fails or times out. The output depends only on the
+//! findings and statistics, so repeated runs produce identical text.
+
+use super::ReviewStats;
+use crate::scanner::Issue;
+
+/// Number of findings called out by title in the summary.
+const TOP_FINDINGS: usize = 3;
+
+/// Builds a human-readable summary from the findings: the number of files
+/// reviewed, a severity breakdown and the titles of the most severe issues.
+pub fn summarize(issues: &[Issue], stats: &ReviewStats) -> String {
+    let files = stats.files_reviewed;
+    let mut summary = format!("Reviewed {} file{}", files, plural(files));
+    if issues.is_empty() {
+        summary.push_str(" with no issues found.");
+        return summary;
+    }
+
+    let mut counts = std::collections::BTreeMap::new();
+    for issue in issues {
+        *counts.entry(issue.severity.clone()).or_insert(0usize) += 1;
+    }
+    let breakdown: Vec<String> = counts
+        .iter()
+        .rev()
+        .map(|(severity, count)| format!("{} {}", count, severity.as_str()))
+        .collect();
+    summary.push_str(&format!(
+        " and found {} issue{} ({}).",
+        issues.len(),
+        plural(issues.len()),
+        breakdown.join(", ")
+    ));
+
+    let mut ranked: Vec<&Issue> = issues.iter().collect();
+    ranked.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.file_path.cmp(&b.file_path))
+            .then_with(|| a.line_number.cmp(&b.line_number))
+    });
+    let top: Vec<String> = ranked
+        .iter()
+        .take(TOP_FINDINGS)
+        .map(|i| format!("{} in {}:{}", i.title, i.file_path, i.line_number))
+        .collect();
+    summary.push_str(" Top findings: ");
+    summary.push_str(&top.join("; "));
+    if issues.len() > TOP_FINDINGS {
+        summary.push_str(&format!("; and {} more", issues.len() - TOP_FINDINGS));
+    }
+    summary.push('.');
+    summary
+}
+
+fn plural(n: usize) -> &'static str {
+    if n == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
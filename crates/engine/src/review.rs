@@ -0,0 +1,191 @@
+//! The supported embedding API.
+//!
+//! [`review_diff`] reviews a unified diff in one call, and [`render_report`]
+//! turns the result into Markdown or JSON with the configured redaction
+//! applied. Everything the review needs besides the diff travels in
+//! [`ReviewOptions`], so embedders need not assemble a [`ReviewEngine`] or
+//! rely on the process working directory. The `check` command uses the same
+//! path.
+//!
+//! ```
+//! use engine::config::Config;
+//! use engine::{render_report, review_diff, Format, ReviewOptions};
+//!
+//! let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -0,0 +1 @@\n+let api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n";
+//! let dir = tempfile::tempdir()?;
+//! std::fs::write(
+//!     dir.path().join("a.rs"),
+//!     "let api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n",
+//! )?;
+//!
+//! // The default configuration uses the offline `null` LLM provider.
+//! let options = ReviewOptions::new(Config::default()).root(dir.path());
+//! let runtime = tokio::runtime::Runtime::new()?;
+//! let report = runtime.block_on(review_diff(diff, options))?;
+//! assert_eq!(report.issues.len(), 1);
+//!
+//! let json = render_report(&report, Format::Json)?;
+//! assert!(json.contains("\"file_path\": \"a.rs\""));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::config::Config;
+use crate::content::FileContentProvider;
+use crate::context::ReviewContext;
+use crate::error::Result;
+use crate::llm::LlmProvider;
+use crate::privacy::Redactor;
+use crate::progress::ProgressEvent;
+use crate::rag::VectorStore;
+use crate::report::{JsonGenerator, MarkdownGenerator, ReportGenerator, ReviewReport};
+use crate::ReviewEngine;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+/// Report formats [`render_report`] produces.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Markdown,
+    Json,
+}
+
+impl Format {
+    /// File extension used for reports in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Markdown => "md",
+            Format::Json => "json",
+        }
+    }
+
+    fn generator(&self) -> Box<dyn ReportGenerator> {
+        match self {
+            Format::Markdown => Box::new(MarkdownGenerator),
+            Format::Json => Box::new(JsonGenerator),
+        }
+    }
+}
+
+/// Everything [`review_diff`] needs besides the diff. Only the
+/// configuration is required; the rest defaults to what the configuration
+/// would create, reading changed files from the current directory.
+pub struct ReviewOptions {
+    /// Configuration of the review.
+    pub config: Config,
+    /// Repository root that diff paths are relative to.
+    pub root: Option<PathBuf>,
+    /// LLM provider to use instead of the one configured in `[llm]`.
+    pub llm: Option<Box<dyn LlmProvider>>,
+    /// Vector store for RAG context instead of loading `[index].path`.
+    pub vector_store: Option<Box<dyn VectorStore + Send + Sync>>,
+    /// Source of changed file contents instead of the files under `root`.
+    pub content: Option<Box<dyn FileContentProvider>>,
+    /// The format the caller intends to pass to [`render_report`]. The
+    /// review itself does not depend on it.
+    pub format: Format,
+    /// Review only changed files matching these paths or globs.
+    pub files: Vec<String>,
+    /// Stated intent of the change, such as a pull request title.
+    pub context: Option<ReviewContext>,
+    /// Branch under review, for comparing with its last recorded run.
+    pub branch: Option<String>,
+    /// Commit the repository is at, for freshness checks and links.
+    pub head_commit: Option<String>,
+    /// Receives a [`ProgressEvent`] at each step of the review.
+    pub progress: Option<Sender<ProgressEvent>>,
+}
+
+impl ReviewOptions {
+    /// Options that review with `config` and nothing else supplied.
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            root: None,
+            llm: None,
+            vector_store: None,
+            content: None,
+            format: Format::default(),
+            files: Vec::new(),
+            context: None,
+            branch: None,
+            head_commit: None,
+            progress: None,
+        }
+    }
+
+    /// Reads changed files relative to `root`.
+    pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    /// Uses `llm` instead of the provider configured in `[llm]`.
+    pub fn llm(mut self, llm: Box<dyn LlmProvider>) -> Self {
+        self.llm = Some(llm);
+        self
+    }
+
+    /// Uses `store` for RAG context instead of loading `[index].path`.
+    pub fn vector_store(mut self, store: Box<dyn VectorStore + Send + Sync>) -> Self {
+        self.vector_store = Some(store);
+        self
+    }
+
+    /// Reads changed files through `content` instead of the filesystem.
+    pub fn content(mut self, content: Box<dyn FileContentProvider>) -> Self {
+        self.content = Some(content);
+        self
+    }
+
+    /// Records the format the caller intends to render.
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+/// Reviews `diff` as configured by `options`. This is the supported
+/// embedding API; see the [module documentation](self) for an example.
+pub async fn review_diff(diff: &str, options: ReviewOptions) -> Result<ReviewReport> {
+    let mut builder = ReviewEngine::builder(options.config);
+    if let Some(llm) = options.llm {
+        builder = builder.llm(llm);
+    }
+    if let Some(store) = options.vector_store {
+        builder = builder.vector_store(store);
+    }
+    let mut engine = builder.build()?.with_files(options.files);
+    if let Some(root) = options.root {
+        engine = engine.with_root(root);
+    }
+    if let Some(context) = options.context {
+        engine = engine.with_context(context);
+    }
+    if let Some(branch) = options.branch {
+        engine = engine.with_branch(branch);
+    }
+    if let Some(head) = options.head_commit {
+        engine = engine.with_head_commit(head);
+    }
+    match &options.content {
+        Some(content) => {
+            engine
+                .run_with_provider_and_progress(diff, content.as_ref(), options.progress)
+                .await
+        }
+        None => engine.run_with_progress(diff, options.progress).await,
+    }
+}
+
+/// Renders `report` in `format`, redacted with the `[privacy.redaction]`
+/// rules of the configuration it was produced with. JSON reports have only
+/// their values redacted, so they keep the shape of the report schema.
+pub fn render_report(report: &ReviewReport, format: Format) -> Result<String> {
+    let rendered = format.generator().generate(report)?;
+    let redactor = Redactor::new(&report.config.privacy.redaction)?;
+    Ok(match format {
+        Format::Json => redactor.redact_json(&rendered),
+        Format::Markdown => redactor.redact(&rendered),
+    })
+}
@@ -0,0 +1,126 @@
+//! A diff scanner for files that should not have been committed.
+//!
+//! Build outputs, minified bundles, vendored dependencies and very large
+//! files are properties of the file rather than of its lines, so this
+//! scanner looks at the paths of added files and their size after the
+//! change. Each added file is reported at most once, at line 1, for the
+//! first of these checks that matches.
+
+use std::path::Path;
+
+use crate::config::{ArtifactsRuleConfig, Confidence, Config};
+use crate::diff_parser::ChangedFile;
+use crate::error::Result;
+use crate::scanner::{DiffScanner, Issue, RuleCategory};
+
+pub struct ArtifactsScanner;
+
+/// Why an added file looks like an artifact: a title, a description and a
+/// suggested fix.
+fn classify(
+    path: &str,
+    size: Option<u64>,
+    rule: &ArtifactsRuleConfig,
+) -> Option<(&'static str, String, &'static str)> {
+    let mut components: Vec<&str> = path.split('/').collect();
+    let name = components.pop().unwrap_or_default();
+    let lower = name.to_ascii_lowercase();
+
+    if let Some(dir) = components
+        .iter()
+        .find(|component| rule.vendored_dirs.iter().any(|dir| dir == *component))
+    {
+        return Some((
+            "Vendored Dependency Added",
+            format!(
+                "`{}` adds third-party code under `{}/`. Dependencies are usually installed by the package manager rather than committed.",
+                path, dir
+            ),
+            "Remove the directory from the change and add it to `.gitignore`.",
+        ));
+    }
+    if let Some(ext) = Path::new(&lower).extension().and_then(|e| e.to_str()) {
+        if rule
+            .compiled_extensions
+            .iter()
+            .any(|compiled| compiled.eq_ignore_ascii_case(ext))
+        {
+            return Some((
+                "Compiled Artifact Added",
+                format!(
+                    "`{}` is a compiled `.{}` file. Build outputs are reproducible from the sources and go stale as they change.",
+                    path, ext
+                ),
+                "Remove the file from the change and add its pattern to `.gitignore`.",
+            ));
+        }
+    }
+    if rule
+        .minified_suffixes
+        .iter()
+        .any(|suffix| lower.ends_with(&suffix.to_ascii_lowercase()))
+    {
+        return Some((
+            "Minified Bundle Added",
+            format!(
+                "`{}` is a minified bundle, which cannot be reviewed and hides what changed.",
+                path
+            ),
+            "Commit the unminified sources and build the bundle in CI.",
+        ));
+    }
+    let limit = rule.max_added_file_kb * 1024;
+    match size {
+        Some(size) if limit > 0 && size > limit => Some((
+            "Large File Added",
+            format!(
+                "`{}` is {} KB, more than the {} KB allowed by `rules.artifacts.max-added-file-kb`. Large files make every clone slower.",
+                path,
+                size.div_ceil(1024),
+                rule.max_added_file_kb
+            ),
+            "Store the file with Git LFS or outside the repository.",
+        )),
+        _ => None,
+    }
+}
+
+impl DiffScanner for ArtifactsScanner {
+    fn name(&self) -> &'static str {
+        "Committed Artifact Scanner"
+    }
+
+    fn scan_diff(&self, file: &ChangedFile, config: &Config) -> Result<Vec<Issue>> {
+        self.scan_diff_sized(file, None, config)
+    }
+
+    fn scan_diff_sized(
+        &self,
+        file: &ChangedFile,
+        size: Option<u64>,
+        config: &Config,
+    ) -> Result<Vec<Issue>> {
+        let rule = &config.rules.artifacts;
+        if !file.added {
+            return Ok(Vec::new());
+        }
+        let Some((title, description, fix)) = classify(&file.path, size, rule) else {
+            return Ok(Vec::new());
+        };
+        Ok(vec![Issue {
+            title: title.to_string(),
+            description,
+            file_path: file.path.clone(),
+            line_number: 1,
+            severity: rule.severity.clone(),
+            suggested_fix: Some(fix.to_string()),
+            rule_id: "artifacts".to_string(),
+            confidence: Confidence::High,
+            ..Default::default()
+        }])
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Quality
+    }
+}
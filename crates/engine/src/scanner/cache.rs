@@ -0,0 +1,174 @@
+//! On-disk caching of scanner findings.
+//!
+//! Repeated reviews of an unchanged file produce the same findings, so
+//! `[engine] scan-cache-path` stores what each scanner reported for a file,
+//! keyed by the rule id, the scanner's [`version`](super::Scanner::version),
+//! a SHA-256 of the file content and a digest of the rest of the scan request:
+//! the path, the changed lines and the effective configuration. A file that
+//! is unchanged between runs has the same diff, so it hits the cache. The
+//! engine still filters cached findings to the changed lines and applies
+//! ignore directives on every run. The least recently used entries are evicted
+//! once the cache exceeds its entry limit.
+
+use super::{Issue, ScanRequest};
+use crate::config::Config;
+use crate::error::{EngineError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The findings cached for one scan.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct CacheEntry {
+    issues: Vec<Issue>,
+    /// When the entry was last read or written, as a counter that increases
+    /// across runs.
+    used: u64,
+}
+
+/// Scanner findings cached on disk, keyed by file content.
+pub struct ScanCache {
+    path: PathBuf,
+    max_entries: usize,
+    entries: HashMap<String, CacheEntry>,
+    clock: u64,
+    dirty: bool,
+}
+
+impl ScanCache {
+    /// Opens the cache at `path`, keeping at most `max_entries` entries. A
+    /// missing or unreadable cache starts empty.
+    pub fn open(path: impl Into<PathBuf>, max_entries: usize) -> Self {
+        let path = path.into();
+        let entries = load(&path);
+        let clock = entries.values().map(|e| e.used).max().unwrap_or(0);
+        Self {
+            path,
+            max_entries,
+            entries,
+            clock,
+            dirty: false,
+        }
+    }
+
+    /// Opens the cache configured in `[engine]`, if any.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let path = config.engine.scan_cache_path.as_ref()?;
+        Some(Self::open(path, config.engine.scan_cache_max_entries))
+    }
+
+    /// Returns the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the findings cached for `key`, marking the entry as used.
+    pub fn get(&mut self, key: &str) -> Option<Vec<Issue>> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+        entry.used = self.clock;
+        self.dirty = true;
+        Some(entry.issues.clone())
+    }
+
+    /// Caches `issues` under `key`.
+    pub fn insert(&mut self, key: String, issues: Vec<Issue>) {
+        self.clock += 1;
+        self.entries.insert(
+            key,
+            CacheEntry {
+                issues,
+                used: self.clock,
+            },
+        );
+        self.dirty = true;
+    }
+
+    /// Evicts the least recently used entries beyond the limit and writes the
+    /// cache if it changed.
+    pub fn save(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        if self.entries.len() > self.max_entries {
+            let mut by_use: Vec<(u64, String)> = self
+                .entries
+                .iter()
+                .map(|(key, e)| (e.used, key.clone()))
+                .collect();
+            by_use.sort();
+            let excess = self.entries.len() - self.max_entries;
+            for (_, key) in by_use.into_iter().take(excess) {
+                self.entries.remove(&key);
+            }
+        }
+        let error = |e: &dyn std::fmt::Display| {
+            EngineError::Scanner(format!("scan cache {}: {}", self.path.display(), e))
+        };
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|e| error(&e))?;
+        }
+        let json = serde_json::to_string(&self.entries).map_err(|e| error(&e))?;
+        fs::write(&self.path, json).map_err(|e| error(&e))?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Digests everything a scan of `request` depends on: a SHA-256 of the
+/// content, the path, the changed lines and the effective configuration.
+pub fn request_digest(request: &ScanRequest) -> String {
+    let mut lines: Vec<usize> = request
+        .changed_lines
+        .map(|lines| lines.iter().copied().collect())
+        .unwrap_or_default();
+    lines.sort_unstable();
+    let mut hasher = Sha256::new();
+    hasher.update(Sha256::digest(request.content.as_bytes()));
+    hasher.update(request.path.as_bytes());
+    hasher.update([0]);
+    hasher.update(format!("{:?}", lines).as_bytes());
+    hasher.update([0]);
+    hasher.update(serde_json::to_string(request.config).unwrap_or_default());
+    hex(hasher)
+}
+
+/// Builds the cache key for `rule` at `version` scanning the request
+/// summarized by `digest`.
+pub fn key(rule: &str, version: u32, digest: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(rule.as_bytes());
+    hasher.update([0]);
+    hasher.update(version.to_le_bytes());
+    hasher.update(digest.as_bytes());
+    hex(hasher)
+}
+
+fn hex(hasher: Sha256) -> String {
+    hasher
+        .finalize()
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, b| {
+            let _ = write!(hex, "{:02x}", b);
+            hex
+        })
+}
+
+/// Reads the cache file. A missing or unreadable cache starts empty.
+fn load(path: &Path) -> HashMap<String, CacheEntry> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        log::warn!("ignoring corrupt scan cache {}: {}", path.display(), e);
+        HashMap::new()
+    })
+}
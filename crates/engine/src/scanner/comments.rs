@@ -0,0 +1,107 @@
+//! Comment syntaxes that `reviewlens:ignore` directives can be written in.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+/// How a comment is opened, and closed for block comments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CommentStyle {
+    /// `// ...`
+    Slash,
+    /// `/* ... */`
+    Block,
+    /// `# ...`
+    Hash,
+    /// `-- ...`
+    Dash,
+    /// `<!-- ... -->`
+    Html,
+    /// `; ...`
+    Semicolon,
+}
+
+const ALL: &[CommentStyle] = &[
+    CommentStyle::Html,
+    CommentStyle::Slash,
+    CommentStyle::Block,
+    CommentStyle::Hash,
+    CommentStyle::Dash,
+    CommentStyle::Semicolon,
+];
+const C_LIKE: &[CommentStyle] = &[CommentStyle::Slash, CommentStyle::Block];
+const HASH: &[CommentStyle] = &[CommentStyle::Hash];
+const SQL: &[CommentStyle] = &[CommentStyle::Dash, CommentStyle::Block];
+const MARKUP: &[CommentStyle] = &[CommentStyle::Html];
+const CSS: &[CommentStyle] = &[CommentStyle::Block];
+const INI: &[CommentStyle] = &[CommentStyle::Semicolon, CommentStyle::Hash];
+const LISP: &[CommentStyle] = &[CommentStyle::Semicolon];
+const LUA: &[CommentStyle] = &[CommentStyle::Dash];
+const PHP: &[CommentStyle] = &[CommentStyle::Slash, CommentStyle::Block, CommentStyle::Hash];
+const VUE: &[CommentStyle] = &[CommentStyle::Html, CommentStyle::Slash, CommentStyle::Block];
+
+/// The directive after a comment opener: a rule id and an optional reason,
+/// which for block comments ends at the closer.
+const DIRECTIVE: &str =
+    r"\s*reviewlens:ignore\s+(?P<rule>[A-Za-z0-9_-]+)(?:\s+(?P<reason>.*?))?\s*";
+
+fn directive(open: &str, close: Option<&str>) -> Regex {
+    let close = match close {
+        Some(close) => format!("(?:{}|$)", close),
+        None => "$".to_string(),
+    };
+    Regex::new(&format!("{}{}{}", open, DIRECTIVE, close)).unwrap()
+}
+
+impl CommentStyle {
+    /// Returns the comment styles used by files like `path`, chosen by
+    /// extension or, for files such as `Dockerfile`, by name. Unknown files
+    /// accept every style.
+    pub(crate) fn for_path(path: &str) -> &'static [CommentStyle] {
+        let path = Path::new(path);
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        match name {
+            "Dockerfile" | "Makefile" | "Gemfile" | "Rakefile" | "Vagrantfile" | ".gitignore"
+            | ".env" => return HASH,
+            _ => {}
+        }
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match ext.to_ascii_lowercase().as_str() {
+            "rs" | "go" | "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" | "java"
+            | "kt" | "kts" | "scala" | "swift" | "c" | "h" | "cc" | "cpp" | "cxx" | "hpp"
+            | "cs" | "dart" | "proto" | "scss" | "less" | "groovy" | "gradle" => C_LIKE,
+            "py" | "pyi" | "rb" | "sh" | "bash" | "zsh" | "fish" | "ps1" | "pl" | "r" | "yml"
+            | "yaml" | "toml" | "tf" | "tfvars" | "hcl" | "cfg" | "conf" | "properties"
+            | "dockerfile" | "mk" | "cmake" | "nix" | "ex" | "exs" => HASH,
+            "sql" => SQL,
+            "lua" | "hs" | "elm" | "ada" => LUA,
+            "html" | "htm" | "xml" | "xhtml" | "svg" => MARKUP,
+            "vue" | "svelte" => VUE,
+            "css" => CSS,
+            "php" => PHP,
+            "ini" => INI,
+            "lisp" | "clj" | "cljs" | "el" | "scm" | "asm" | "s" => LISP,
+            _ => ALL,
+        }
+    }
+
+    /// Returns the pattern matching a directive in this style, with `rule`
+    /// and `reason` groups. The match ends at the block comment's closer,
+    /// so any code after it is not part of the match.
+    pub(crate) fn regex(self) -> &'static Regex {
+        static SLASH: Lazy<Regex> = Lazy::new(|| directive("//", None));
+        static BLOCK: Lazy<Regex> = Lazy::new(|| directive(r"/\*", Some(r"\*/")));
+        static HASH: Lazy<Regex> = Lazy::new(|| directive("#", None));
+        static DASH: Lazy<Regex> = Lazy::new(|| directive("--", None));
+        static HTML: Lazy<Regex> = Lazy::new(|| directive("<!--", Some("-->")));
+        static SEMICOLON: Lazy<Regex> = Lazy::new(|| directive(";", None));
+        match self {
+            CommentStyle::Slash => &SLASH,
+            CommentStyle::Block => &BLOCK,
+            CommentStyle::Hash => &HASH,
+            CommentStyle::Dash => &DASH,
+            CommentStyle::Html => &HTML,
+            CommentStyle::Semicolon => &SEMICOLON,
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! A scanner for long or deeply nested functions touched by a change.
+//!
+//! Functions are found with the per-language signature patterns used by the
+//! index. Their bodies are delimited by brace matching, or by indentation for
+//! Python, so the measurements are estimates rather than a parse.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::config::{Confidence, Config};
+use crate::error::Result;
+use crate::rag::Language;
+use crate::scanner::{scan_file, Issue, RuleCategory, ScanRequest, Scanner};
+
+pub struct ComplexityScanner;
+
+// String literals and line comments are blanked before counting brackets, so
+// a `{` in a format string does not open a block. Rust uses `'` for lifetimes,
+// so only single-character literals are treated as strings there.
+static RUST_NOISE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#""(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)'|//.*"#).unwrap());
+static NOISE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#""(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'|`[^`]*`|//.*"#).unwrap());
+static PYTHON_NOISE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#""(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'|#.*"#).unwrap());
+
+/// A function's extent and deepest nesting inside its body.
+struct Span {
+    /// Zero-based last line of the function.
+    end: usize,
+    /// Levels of blocks nested inside the body; `0` for a flat body.
+    nesting: usize,
+}
+
+fn code(language: Language, line: &str) -> String {
+    let noise = match language {
+        Language::Rust => &RUST_NOISE_REGEX,
+        Language::Python => &PYTHON_NOISE_REGEX,
+        _ => &NOISE_REGEX,
+    };
+    noise.replace_all(line, "").into_owned()
+}
+
+/// Measures the function declared on line `start` by matching braces. A
+/// declaration that ends in `;` or an arrow function without a block spans
+/// its own line; an unclosed body runs to the end of the file.
+fn brace_span(language: Language, lines: &[&str], start: usize) -> Span {
+    let mut depth = 0usize;
+    let mut deepest = 0usize;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let code = code(language, line);
+        for ch in code.chars() {
+            match ch {
+                ';' if depth == 0 => return Span { end: i, nesting: 0 },
+                '{' => {
+                    depth += 1;
+                    deepest = deepest.max(depth);
+                }
+                '}' if depth > 0 => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Span {
+                            end: i,
+                            nesting: deepest - 1,
+                        };
+                    }
+                }
+                _ => {}
+            }
+        }
+        if depth == 0 && code.contains("=>") {
+            return Span { end: i, nesting: 0 };
+        }
+    }
+    Span {
+        end: lines.len().saturating_sub(1),
+        nesting: deepest.saturating_sub(1),
+    }
+}
+
+fn indent(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Counts the brackets `line` leaves open, negative if it closes more.
+fn open_brackets(line: &str) -> isize {
+    line.chars()
+        .map(|c| match c {
+            '(' | '[' | '{' => 1,
+            ')' | ']' | '}' => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Measures the Python function declared on line `start` from indentation.
+/// The body is every following line indented past the `def`; continuation
+/// lines inside brackets extend it without adding nesting.
+fn indent_span(lines: &[&str], start: usize) -> Span {
+    let def_indent = indent(lines[start]);
+    // The header may wrap its parameters over several lines.
+    let mut header_end = start;
+    let mut brackets = 0;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let code = code(Language::Python, line);
+        brackets += open_brackets(&code);
+        header_end = i;
+        if brackets <= 0 && code.trim_end().ends_with(':') {
+            break;
+        }
+        if brackets <= 0 && code.contains(':') {
+            // `def f(): return 1`
+            return Span { end: i, nesting: 0 };
+        }
+    }
+
+    let mut end = header_end;
+    let mut levels: Vec<usize> = Vec::new();
+    let mut deepest = 0;
+    let mut brackets = 0;
+    for (i, line) in lines.iter().enumerate().skip(header_end + 1) {
+        let code = code(Language::Python, line);
+        if code.trim().is_empty() {
+            continue;
+        }
+        let width = indent(line);
+        if width <= def_indent && brackets <= 0 {
+            break;
+        }
+        end = i;
+        if brackets <= 0 {
+            while levels.last().is_some_and(|&level| level > width) {
+                levels.pop();
+            }
+            if levels.last().is_none_or(|&level| level < width) {
+                levels.push(width);
+            }
+            deepest = deepest.max(levels.len());
+        }
+        brackets += open_brackets(&code);
+    }
+    Span {
+        end,
+        nesting: deepest.saturating_sub(1),
+    }
+}
+
+impl Scanner for ComplexityScanner {
+    fn name(&self) -> &'static str {
+        "Complexity Scanner"
+    }
+
+    fn scan(&self, file_path: &str, content: &str, config: &Config) -> Result<Vec<Issue>> {
+        scan_file(self, file_path, content, config)
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Quality
+    }
+
+    fn scan_with_context(&self, request: &ScanRequest) -> Result<Vec<Issue>> {
+        let Some(language) = Language::from_path(request.path) else {
+            return Ok(vec![]);
+        };
+        let rule = &request.config.rules.complexity;
+        let lines: Vec<&str> = request.content.lines().collect();
+        let mut issues = Vec::new();
+        for (start, name) in language.function_lines(request.content) {
+            if start >= lines.len() {
+                continue;
+            }
+            let span = match language {
+                Language::Python => indent_span(&lines, start),
+                _ => brace_span(language, &lines, start),
+            };
+            let length = span.end - start + 1;
+            if length <= rule.max_function_lines && span.nesting <= rule.max_nesting {
+                continue;
+            }
+            // Report on the first changed line so the finding survives the
+            // engine's changed-line filter.
+            let Some(line_number) = (start + 1..=span.end + 1).find(|&l| request.is_changed(l))
+            else {
+                continue;
+            };
+            issues.push(Issue {
+                title: "Complex Function".to_string(),
+                description: format!(
+                    "`{}` spans {} lines (limit {}) with a nesting depth of {} (limit {}).",
+                    name, length, rule.max_function_lines, span.nesting, rule.max_nesting
+                ),
+                file_path: request.path.to_string(),
+                line_number,
+                severity: rule.severity.clone(),
+                suggested_fix: Some(
+                    "Extract parts of the function into helpers, or return early to flatten nested blocks."
+                        .to_string(),
+                ),
+                rule_id: "complexity".to_string(),
+                confidence: Confidence::High,
+                ..Default::default()
+            });
+        }
+        Ok(issues)
+    }
+}
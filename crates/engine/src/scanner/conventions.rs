@@ -1,57 +1,103 @@
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
 
-use crate::config::Config;
+use crate::config::{Confidence, Config, ConventionsMode};
 use crate::error::Result;
-use crate::rag::InMemoryVectorStore;
-use crate::scanner::{find_ignore, parse_ignore_directives, Issue, Scanner};
+use crate::rag::language::{contains_any, Advice};
+use crate::rag::{Document, InMemoryVectorStore, Language};
+use crate::scanner::{scan_file, Issue, RuleCategory, ScanRequest, Scanner};
 
+/// Flags lines that deviate from the logging and error-handling conventions
+/// of indexed files in the same language. `[rules.conventions]` selects the
+/// checks and how strictly a convention must hold.
+///
+/// The engine builds it from the index it has already loaded. Otherwise the
+/// index at `[index].path` is read on the first scan; either way, the index
+/// is summarized once per scanner.
 #[derive(Default)]
 pub struct ConventionsScanner {
-    baseline: Mutex<Option<Baseline>>,
+    counts: OnceLock<HashMap<Language, Counts>>,
 }
 
-#[derive(Clone)]
-struct Baseline {
-    prefers_logging_macros: bool,
-    discourage_unwrap: bool,
+/// Occurrences of each convention across the indexed files of one language.
+#[derive(Clone, Copy, Default)]
+struct Counts {
+    loggers: usize,
+    prints: usize,
+    handled: usize,
+    unhandled: usize,
+}
+
+impl Counts {
+    /// Returns whether `preferred` and `discouraged` occurrences establish
+    /// the preferred form as a convention under `mode`.
+    fn established(mode: ConventionsMode, preferred: usize, discouraged: usize) -> bool {
+        match mode {
+            ConventionsMode::Majority => preferred >= discouraged,
+            ConventionsMode::Strict => discouraged == 0,
+        }
+    }
 }
 
 impl ConventionsScanner {
-    fn ensure_baseline(&self, config: &Config) -> Option<Baseline> {
-        let mut guard = self.baseline.lock().unwrap();
-        if guard.is_none() {
-            if let Some(path) = config.index_path() {
-                if let Ok(store) = InMemoryVectorStore::load_from_disk(path) {
-                    let mut log_macro = 0usize;
-                    let mut println = 0usize;
-                    let mut unwrap_expect = 0usize;
-                    let mut result_err = 0usize;
-                    for doc in store.documents() {
-                        for line in &doc.log_patterns {
-                            if line.contains("log::") {
-                                log_macro += 1;
-                            }
-                            if line.contains("println!") || line.contains("eprintln!") {
-                                println += 1;
-                            }
-                        }
-                        for line in &doc.error_snippets {
-                            if line.contains(".unwrap()") || line.contains(".expect(") {
-                                unwrap_expect += 1;
-                            }
-                            if line.contains("Result<") || line.contains("Err(") {
-                                result_err += 1;
-                            }
-                        }
-                    }
-                    *guard = Some(Baseline {
-                        prefers_logging_macros: log_macro >= println,
-                        discourage_unwrap: result_err >= unwrap_expect,
-                    });
-                }
-            }
+    /// Creates a scanner whose baselines are derived from `documents`.
+    pub fn from_documents(documents: &[Document]) -> Self {
+        Self {
+            counts: OnceLock::from(count(documents)),
         }
-        guard.clone()
+    }
+
+    /// Returns the counts for `language`, or `None` if the index has no
+    /// files in that language.
+    fn counts(&self, config: &Config, language: Language) -> Option<Counts> {
+        self.counts
+            .get_or_init(|| {
+                // A missing or unreadable index is not retried per file.
+                config
+                    .index_path()
+                    .and_then(|path| InMemoryVectorStore::load_from_disk(path).ok())
+                    .map(|store| count(store.documents()))
+                    .unwrap_or_default()
+            })
+            .get(&language)
+            .copied()
+    }
+}
+
+/// Counts the conventions of every language present in `documents`.
+fn count(documents: &[Document]) -> HashMap<Language, Counts> {
+    let mut counts: HashMap<Language, Counts> = HashMap::new();
+    for doc in documents {
+        // Indexes written before languages were recorded fall back to the
+        // file extension.
+        let Some(lang) = doc.language.or_else(|| Language::from_path(&doc.filename)) else {
+            continue;
+        };
+        let c = lang.conventions();
+        let count = counts.entry(lang).or_default();
+        for line in &doc.log_patterns {
+            count.loggers += contains_any(line, c.loggers) as usize;
+            count.prints += contains_any(line, c.prints) as usize;
+        }
+        for line in &doc.error_snippets {
+            count.handled += contains_any(line, c.handled) as usize;
+            count.unhandled += contains_any(line, c.unhandled) as usize;
+        }
+    }
+    counts
+}
+
+fn issue(advice: &Advice, file_path: &str, line_number: usize, config: &Config) -> Issue {
+    Issue {
+        title: advice.title.to_string(),
+        description: advice.description.to_string(),
+        file_path: file_path.to_string(),
+        line_number,
+        severity: config.rules.conventions.severity.clone(),
+        suggested_fix: Some(advice.fix.to_string()),
+        rule_id: "conventions".to_string(),
+        confidence: Confidence::Low,
+        ..Default::default()
     }
 }
 
@@ -61,68 +107,37 @@ impl Scanner for ConventionsScanner {
     }
 
     fn scan(&self, file_path: &str, content: &str, config: &Config) -> Result<Vec<Issue>> {
-        let baseline = match self.ensure_baseline(config) {
-            Some(b) => b,
+        scan_file(self, file_path, content, config)
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Quality
+    }
+
+    fn scan_with_context(&self, request: &ScanRequest) -> Result<Vec<Issue>> {
+        let (file_path, config) = (request.path, request.config);
+        let Some(language) = Language::from_path(file_path) else {
+            return Ok(vec![]);
+        };
+        let counts = match self.counts(config, language) {
+            Some(c) => c,
             None => return Ok(vec![]),
         };
+        let rule = &config.rules.conventions;
+        let check_logging =
+            rule.logging && Counts::established(rule.mode, counts.loggers, counts.prints);
+        let check_errors =
+            rule.error_handling && Counts::established(rule.mode, counts.handled, counts.unhandled);
+        let conventions = language.conventions();
 
         let mut issues = Vec::new();
-        let ignores = parse_ignore_directives(content);
-        for (i, line) in content.lines().enumerate() {
-            if baseline.prefers_logging_macros
-                && (line.contains("println!") || line.contains("eprintln!"))
-            {
-                if let Some(ignore) = find_ignore(&ignores, i + 1, "conventions") {
-                    log::info!(
-                        "Suppressed conventions at {}:{}{}",
-                        file_path,
-                        i + 1,
-                        ignore
-                            .reason
-                            .as_ref()
-                            .map(|r| format!(" - {}", r))
-                            .unwrap_or_default()
-                    );
-                } else {
-                    issues.push(Issue {
-                        title: "Inconsistent Logging".to_string(),
-                        description:
-                            "Use logging macros (e.g., log::info!) instead of println!/eprintln! per repository conventions."
-                                .to_string(),
-                        file_path: file_path.to_string(),
-                        line_number: i + 1,
-                        severity: config.rules.conventions.severity.clone(),
-                        suggested_fix: Some("Replace println!/eprintln! with appropriate log:: macros.".to_string()),
-                        diff: None,
-                    });
-                }
+        for (line_number, line) in request.lines() {
+            if check_logging && contains_any(line, conventions.prints) {
+                issues.push(issue(&conventions.logging, file_path, line_number, config));
             }
-            if baseline.discourage_unwrap
-                && (line.contains(".unwrap()") || line.contains(".expect("))
-            {
-                if let Some(ignore) = find_ignore(&ignores, i + 1, "conventions") {
-                    log::info!(
-                        "Suppressed conventions at {}:{}{}",
-                        file_path,
-                        i + 1,
-                        ignore
-                            .reason
-                            .as_ref()
-                            .map(|r| format!(" - {}", r))
-                            .unwrap_or_default()
-                    );
-                } else {
-                    issues.push(Issue {
-                        title: "Avoid unwrap/expect".to_string(),
-                        description:
-                            "Prefer error propagation with Result and ? operator instead of unwrap()/expect() per repository conventions."
-                                .to_string(),
-                        file_path: file_path.to_string(),
-                        line_number: i + 1,
-                        severity: config.rules.conventions.severity.clone(),
-                        suggested_fix: Some("Propagate errors using ? or handle them explicitly.".to_string()),
-                        diff: None,
-                    });
+            if let Some(advice) = &conventions.errors {
+                if check_errors && contains_any(line, conventions.unhandled) {
+                    issues.push(issue(advice, file_path, line_number, config));
                 }
             }
         }
@@ -0,0 +1,285 @@
+//! Scanners implemented as external programs.
+//!
+//! Each `[[scanners.external]]` entry runs its command once per changed file:
+//!
+//! ```toml
+//! [[scanners.external]]
+//! rule = "no-print"
+//! command = "./scripts/no-print"
+//! severity = "low"
+//! timeout-secs = 10
+//! options = { allow-in = ["scripts/"] }
+//! ```
+//!
+//! # Wire format
+//!
+//! The program receives one JSON object on stdin:
+//!
+//! ```json
+//! {
+//!   "rule": "no-print",
+//!   "path": "src/app.py",
+//!   "content": "print('hi')\n",
+//!   "changed_lines": [1],
+//!   "config": { "severity": "low", "options": { "allow-in": ["scripts/"] } }
+//! }
+//! ```
+//!
+//! `changed_lines` lists the one-based lines added or modified by the diff,
+//! sorted, or is `null` when every line should be reviewed.
+//!
+//! It must exit with status 0 and print a JSON array of findings on stdout:
+//!
+//! ```json
+//! [
+//!   {
+//!     "line": 1,
+//!     "title": "Print statement",
+//!     "description": "Use the logger instead of print().",
+//!     "suggested_fix": "log.info('hi')",
+//!     "fix": { "find": "print('hi')", "replace": "log.info('hi')" },
+//!     "confidence": "high"
+//!   }
+//! ]
+//! ```
+//!
+//! Only `line` and `title` are required; `confidence` is `high`, `medium`
+//! (the default) or `low`. A `fix` replaces the trimmed line `find` with
+//! `replace` under `reviewlens fix`; a free-text `diff` may be given instead
+//! for display only. Findings are reported under the entry's rule id at the
+//! configured severity, so `reviewlens:ignore <rule>` directives and
+//! `--rule <rule>=<severity>` overrides apply as for built-in rules. A
+//! non-zero exit status, output that is not a JSON array, or running past
+//! `timeout-secs` fails the scan with the program's stderr in the error.
+
+use crate::config::{Confidence, Config, ExternalScannerConfig, Severity};
+use crate::error::{EngineError, Result};
+use crate::scanner::{
+    scan_file, Fix, Issue, ScanRequest, Scanner, ScannerConstructor, ScannerDescriptor,
+};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a running program is polled for exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Runs a configured command as a scanner.
+#[derive(Clone)]
+pub struct ExternalScanner {
+    rule: &'static str,
+    name: &'static str,
+    command: String,
+    args: Vec<String>,
+    timeout: Duration,
+}
+
+impl ExternalScanner {
+    /// Creates a scanner for the `[[scanners.external]]` entry `spec`.
+    pub fn new(spec: &ExternalScannerConfig) -> Self {
+        // Scanners are created once per engine, and the registry hands out
+        // `&'static str` rule ids and names, so the strings are leaked.
+        Self {
+            rule: Box::leak(spec.rule.clone().into_boxed_str()),
+            name: Box::leak(format!("External Scanner ({})", spec.rule).into_boxed_str()),
+            command: spec.command.clone(),
+            args: spec.args.clone(),
+            timeout: Duration::from_secs(spec.timeout_secs),
+        }
+    }
+
+    /// The rule id this scanner reports under.
+    pub fn rule(&self) -> &'static str {
+        self.rule
+    }
+
+    /// Describes the `[[scanners.external]]` entry `spec` as a rule whose
+    /// factory creates its scanner.
+    pub fn descriptor(spec: &ExternalScannerConfig) -> ScannerDescriptor {
+        let scanner = Self::new(spec);
+        ScannerDescriptor {
+            id: scanner.rule,
+            name: scanner.name,
+            description: Box::leak(format!("Runs `{}`.", spec.command).into_boxed_str()),
+            default_severity: Some(spec.severity.clone()),
+            languages: &[],
+            settings: None,
+            factory: ScannerConstructor::File(Arc::new(move || Box::new(scanner.clone()))),
+        }
+    }
+
+    /// Runs the program with `input` on stdin and returns its stdout.
+    fn run(&self, input: Vec<u8>) -> Result<Vec<u8>> {
+        let mut child = Command::new(&self.command)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| self.error(format!("failed to run `{}`: {}", self.command, e)))?;
+
+        // Feed and drain the pipes on threads so a chatty program cannot
+        // block on a full pipe while we wait for it.
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        thread::spawn(move || {
+            // A program that exits without reading its input is not an error.
+            let _ = stdin.write_all(&input);
+        });
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let stdout = thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = stdout.read_to_end(&mut buf);
+            buf
+        });
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let stderr = thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = stderr.read_to_string(&mut buf);
+            buf
+        });
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(self.error(format!(
+                    "`{}` timed out after {}s",
+                    self.command,
+                    self.timeout.as_secs()
+                )));
+            }
+            thread::sleep(POLL_INTERVAL);
+        };
+
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+        if !status.success() {
+            return Err(self.error(format!(
+                "`{}` exited with {}: {}",
+                self.command,
+                status,
+                stderr.trim()
+            )));
+        }
+        Ok(stdout)
+    }
+
+    fn error(&self, message: String) -> EngineError {
+        EngineError::Scanner(format!("{}: {}", self.rule, message))
+    }
+}
+
+/// The request written to the program's stdin.
+#[derive(Serialize)]
+struct WireRequest<'a> {
+    rule: &'a str,
+    path: &'a str,
+    content: &'a str,
+    changed_lines: Option<Vec<usize>>,
+    config: WireRuleConfig<'a>,
+}
+
+#[derive(Serialize)]
+struct WireRuleConfig<'a> {
+    severity: &'a Severity,
+    options: &'a toml::Table,
+}
+
+/// A finding read from the program's stdout.
+#[derive(Deserialize)]
+struct WireFinding {
+    line: usize,
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    suggested_fix: Option<String>,
+    #[serde(default)]
+    diff: Option<String>,
+    #[serde(default)]
+    fix: Option<Fix>,
+    #[serde(default)]
+    confidence: Confidence,
+}
+
+impl Scanner for ExternalScanner {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn scan(&self, file_path: &str, content: &str, config: &Config) -> Result<Vec<Issue>> {
+        scan_file(self, file_path, content, config)
+    }
+
+    fn scan_with_context(&self, request: &ScanRequest) -> Result<Vec<Issue>> {
+        let spec = request
+            .config
+            .scanners
+            .external
+            .iter()
+            .find(|spec| spec.rule == self.rule);
+        let severity = spec.map_or(Severity::Medium, |spec| spec.severity.clone());
+        let no_options = toml::Table::new();
+        let changed_lines = request.changed_lines.map(|lines| {
+            let mut lines: Vec<usize> = lines.iter().copied().collect();
+            lines.sort_unstable();
+            lines
+        });
+        let input = serde_json::to_vec(&WireRequest {
+            rule: self.rule,
+            path: request.path,
+            content: request.content,
+            changed_lines,
+            config: WireRuleConfig {
+                severity: &severity,
+                options: spec.map_or(&no_options, |spec| &spec.options),
+            },
+        })
+        .map_err(|e| self.error(e.to_string()))?;
+
+        let output = self.run(input)?;
+        let findings: Vec<WireFinding> = serde_json::from_slice(&output)
+            .map_err(|e| self.error(format!("invalid output from `{}`: {}", self.command, e)))?;
+
+        let mut issues = Vec::new();
+        for finding in findings {
+            issues.push(Issue {
+                title: finding.title,
+                description: finding.description,
+                file_path: request.path.to_string(),
+                line_number: finding.line,
+                severity: severity.clone(),
+                suggested_fix: finding.suggested_fix,
+                diff: finding
+                    .diff
+                    .or_else(|| finding.fix.as_ref().map(Fix::to_diff)),
+                rule_id: self.rule.to_string(),
+                fix: finding.fix,
+                confidence: finding.confidence,
+                ..Default::default()
+            });
+        }
+        Ok(issues)
+    }
+}
+
+/// Returns a scanner for every `[[scanners.external]]` entry, paired with
+/// its rule id.
+pub fn load_external_scanners(config: &Config) -> Vec<(&'static str, Box<dyn Scanner>)> {
+    config
+        .scanners
+        .external
+        .iter()
+        .filter_map(|spec| {
+            let descriptor = ExternalScanner::descriptor(spec);
+            Some((descriptor.id, descriptor.scanner()?))
+        })
+        .collect()
+}
@@ -4,17 +4,21 @@
 //! rule-based detectors. This allows for a flexible and extensible scanning system.
 
 use crate::{
-    config::{Config, Severity},
-    error::Result,
+    config::{Confidence, Config, RulesConfig, Severity},
+    diff_parser::{ChangedFile, DiffPosition, Hunk},
+    error::{EngineError, Result},
+    rag::Language,
 };
+use chrono::NaiveDate;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use serde::Serialize;
-use std::collections::HashMap;
-use std::sync::{Mutex, Once};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, Once};
 
 /// Represents an issue found by a scanner.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Issue {
     pub title: String,
     pub description: String,
@@ -22,16 +26,211 @@ pub struct Issue {
     pub line_number: usize,
     pub severity: Severity,
     pub suggested_fix: Option<String>,
+    /// The suggested change for display, generated from `fix` when the
+    /// scanner has one.
     pub diff: Option<String>,
+    /// A replacement of the flagged line that `reviewlens fix` can apply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+    /// Author of the flagged line, filled in by `blame::enrich`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Commit that last touched the flagged line, filled in by `blame::enrich`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Id of the rule that reported the issue. The engine overwrites it with
+    /// the id the scanner was registered under.
+    #[serde(default)]
+    pub rule_id: String,
+    /// Identity of the finding that survives line-number drift, filled in by
+    /// the engine; see `fingerprint_issue`.
+    #[serde(default)]
+    pub fingerprint: String,
+    /// Link to the flagged line, filled in by the engine from
+    /// `[report] link-template`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The LLM's comment on this finding, from `[llm] structured-output`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm_comment: Option<String>,
+    /// A patch suggested by the LLM, from `[llm] structured-output`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm_patch: Option<String>,
+    /// The flagged line and the lines around it, numbered and redacted,
+    /// filled in by the engine from `[report] snippet-context`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+    /// Where the flagged line appears in the reviewed diff, filled in by
+    /// the engine. `None` when the line is outside every hunk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff_position: Option<DiffPosition>,
+    /// The severity reported by the scanner, when the engine escalated it
+    /// under `[report] escalate-hotspots`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_severity: Option<Severity>,
+    /// How likely the finding is to be real. Reports written before
+    /// confidence levels existed read as medium.
+    #[serde(default)]
+    pub confidence: Confidence,
+}
+
+impl Default for Issue {
+    /// An empty medium-severity finding, for building issues with
+    /// `..Default::default()`.
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            description: String::new(),
+            file_path: String::new(),
+            line_number: 0,
+            severity: Severity::Medium,
+            suggested_fix: None,
+            diff: None,
+            fix: None,
+            author: None,
+            commit: None,
+            rule_id: String::new(),
+            fingerprint: String::new(),
+            url: None,
+            llm_comment: None,
+            llm_patch: None,
+            snippet: None,
+            diff_position: None,
+            original_severity: None,
+            confidence: Confidence::default(),
+        }
+    }
+}
+
+/// A machine-applicable replacement of a flagged line.
+///
+/// Both sides are compared and written without the line's indentation, so a
+/// fix still applies after the file was reindented or lines were inserted
+/// above it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Fix {
+    /// The flagged line, trimmed.
+    pub find: String,
+    /// The line or lines that replace it, trimmed.
+    pub replace: String,
+}
+
+impl Fix {
+    /// Renders the fix as the diff shown in reports.
+    pub fn to_diff(&self) -> String {
+        format!("-{}\n+{}", self.find, self.replace.replace('\n', "\n+"))
+    }
+}
+
+/// Everything a scanner needs to review a single changed file.
+pub struct ScanRequest<'a> {
+    /// Path of the file as it appears in the diff.
+    pub path: &'a str,
+    /// Full contents of the file after the change.
+    pub content: &'a str,
+    /// The effective configuration for the run.
+    pub config: &'a Config,
+    /// One-based line numbers added or modified by the diff, or `None` to
+    /// treat every line as changed.
+    pub changed_lines: Option<&'a HashSet<usize>>,
+    /// The parsed diff hunks for this file.
+    pub hunks: &'a [Hunk],
+}
+
+impl<'a> ScanRequest<'a> {
+    /// Creates a request that covers every line of `content`.
+    pub fn whole_file(path: &'a str, content: &'a str, config: &'a Config) -> Self {
+        Self {
+            path,
+            content,
+            config,
+            changed_lines: None,
+            hunks: &[],
+        }
+    }
+
+    /// Returns `true` if the one-based `line` was changed.
+    pub fn is_changed(&self, line: usize) -> bool {
+        self.changed_lines
+            .map_or(true, |lines| lines.contains(&line))
+    }
+
+    /// Iterates over `(line_number, line)` pairs for changed lines only,
+    /// with one-based line numbers.
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(line_number, _)| self.is_changed(*line_number))
+    }
 }
 
 /// A trait for a scanner that checks code for specific issues.
+///
+/// Scanners report every finding tagged with their rule id; the engine
+/// applies `reviewlens:ignore` directives to them, see [`apply_suppressions`].
 pub trait Scanner: Send + Sync {
     /// Returns the name of the scanner.
     fn name(&self) -> &'static str;
 
     /// Scans a given file content and returns a list of issues found.
     fn scan(&self, file_path: &str, content: &str, config: &Config) -> Result<Vec<Issue>>;
+
+    /// Scans a changed file with access to its diff context. Scanners may
+    /// skip unchanged lines; the default delegates to `scan`.
+    fn scan_with_context(&self, request: &ScanRequest) -> Result<Vec<Issue>> {
+        self.scan(request.path, request.content, request.config)
+    }
+
+    /// Returns which report section the scanner's findings belong to.
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Security
+    }
+
+    /// Returns the version of the scanner's logic. Bump it whenever the same
+    /// file would produce different findings, so cached results from
+    /// `[engine] scan-cache-path` are discarded.
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+/// Where a scanner's findings are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleCategory {
+    /// Findings are listed as issues and count towards `fail-on`.
+    Security,
+    /// Findings are listed under Code Quality & Conventions.
+    Quality,
+}
+
+/// A scanner that reviews the parsed diff rather than the file contents
+/// after the change, so it can report on lines the change deleted.
+pub trait DiffScanner: Send + Sync {
+    /// Returns the name of the scanner.
+    fn name(&self) -> &'static str;
+
+    /// Scans the hunks of a changed file. Issues must reference lines of the
+    /// new file so that report links and inline ignore directives apply.
+    fn scan_diff(&self, file: &ChangedFile, config: &Config) -> Result<Vec<Issue>>;
+
+    /// Scans a changed file whose size after the change is `size` bytes,
+    /// when the content provider knows it. The default ignores the size.
+    fn scan_diff_sized(
+        &self,
+        file: &ChangedFile,
+        _size: Option<u64>,
+        config: &Config,
+    ) -> Result<Vec<Issue>> {
+        self.scan_diff(file, config)
+    }
+
+    /// Returns where the scanner's findings are reported.
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Security
+    }
 }
 
 /// Represents an inline suppression directive parsed from source code.
@@ -39,49 +238,229 @@ pub trait Scanner: Send + Sync {
 pub struct IgnoreDirective {
     pub rule: String,
     pub reason: Option<String>,
+    /// One-based line of the directive itself, which precedes the line it
+    /// silences when it stands alone.
+    pub line: usize,
+    /// Last day the directive applies, from `until=YYYY-MM-DD`; `None` if it
+    /// never expires.
+    pub expires: Option<NaiveDate>,
+}
+
+impl IgnoreDirective {
+    /// Returns whether the directive has expired by `today`. A directive
+    /// still applies on its `until` date.
+    pub fn expired(&self, today: NaiveDate) -> bool {
+        self.expires.is_some_and(|expires| expires < today)
+    }
+}
+
+/// A finding silenced by an inline `reviewlens:ignore` directive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Suppression {
+    pub rule: String,
+    pub path: String,
+    pub line: usize,
+    pub reason: Option<String>,
+}
+
+impl Suppression {
+    /// Records that `ignore` silenced `rule` at `path:line`.
+    pub fn new(rule: &str, path: &str, line: usize, ignore: &IgnoreDirective) -> Self {
+        Self {
+            rule: rule.to_string(),
+            path: path.to_string(),
+            line,
+            reason: ignore.reason.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for Suppression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Suppressed {} at {}:{}", self.rule, self.path, self.line)?;
+        match &self.reason {
+            Some(reason) => write!(f, " - {}", reason),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Mapping of line numbers to suppression directives.
 pub type IgnoreMap = HashMap<usize, Vec<IgnoreDirective>>;
 
-static IGNORE_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"//\s*reviewlens:ignore\s+([A-Za-z0-9_-]+)(?:\s+(.*))?").unwrap());
-
-/// Parses `// reviewlens:ignore` directives within a file's contents.
-pub fn parse_ignore_directives(content: &str) -> IgnoreMap {
+/// Parses `reviewlens:ignore` directives within the contents of `path`,
+/// keyed by the line they silence. The comment syntaxes accepted depend on
+/// the file extension: `//` and `/* */` for C-like languages, `#` for
+/// Python, shell, YAML and Terraform, `--` for SQL, `<!-- -->` for markup
+/// and `;` for INI files, or any of them for unknown files. A directive on a
+/// line of its own applies to the next line.
+///
+/// An `until=YYYY-MM-DD` attribute after the rule id sets when the directive
+/// expires. A malformed date is logged and the directive never expires.
+pub fn parse_ignore_directives(path: &str, content: &str) -> IgnoreMap {
+    let styles = CommentStyle::for_path(path);
     let mut map: IgnoreMap = HashMap::new();
     for (i, line) in content.lines().enumerate() {
-        if let Some(caps) = IGNORE_REGEX.captures(line) {
-            let rule = caps[1].to_string();
-            let reason = caps
-                .get(2)
-                .map(|m| m.as_str().trim().to_string())
-                .filter(|s| !s.is_empty());
-            let target = if line.trim_start().starts_with("//") {
-                i + 2
-            } else {
-                i + 1
-            };
-            map.entry(target)
-                .or_insert_with(Vec::new)
-                .push(IgnoreDirective { rule, reason });
+        let found = styles
+            .iter()
+            .filter_map(|style| style.regex().captures(line))
+            .min_by_key(|caps| caps.get(0).unwrap().start());
+        if let Some(caps) = found {
+            let comment = caps.get(0).unwrap();
+            let rule = caps["rule"].to_string();
+            let mut reason = caps.name("reason").map_or("", |m| m.as_str().trim());
+            let mut expires = None;
+            if let Some(rest) = reason.strip_prefix("until=") {
+                let (date, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                    Ok(date) => expires = Some(date),
+                    Err(_) => log::warn!(
+                        "{}:{}: ignoring malformed `until={}` (expected YYYY-MM-DD); the directive never expires",
+                        path,
+                        i + 1,
+                        date
+                    ),
+                }
+                reason = rest.trim();
+            }
+            let reason = Some(reason.to_string()).filter(|s| !s.is_empty());
+            let own_line = line[..comment.start()].trim().is_empty()
+                && line[comment.end()..].trim().is_empty();
+            let target = if own_line { i + 2 } else { i + 1 };
+            map.entry(target).or_default().push(IgnoreDirective {
+                rule,
+                reason,
+                line: i + 1,
+                expires,
+            });
         }
     }
     map
 }
 
-/// Returns an ignore directive for a given rule at a line, if present.
-pub fn find_ignore<'a>(map: &'a IgnoreMap, line: usize, rule: &str) -> Option<&'a IgnoreDirective> {
+/// Returns the ignore directive for `rule` at `line` that is still in effect
+/// on `today`, if present.
+pub fn find_ignore<'a>(
+    map: &'a IgnoreMap,
+    line: usize,
+    rule: &str,
+    today: NaiveDate,
+) -> Option<&'a IgnoreDirective> {
     map.get(&line)
-        .and_then(|vec| vec.iter().find(|d| d.rule == rule))
+        .and_then(|vec| vec.iter().find(|d| d.rule == rule && !d.expired(today)))
 }
 
+/// Removes the findings in `issues` that a directive in `ignores` silences
+/// for their `rule_id` on `today`, logging and returning them as
+/// suppressions.
+///
+/// Scanners report every finding; the engine applies the directives of each
+/// file once, to the findings of all rules.
+pub fn apply_suppressions(
+    issues: &mut Vec<Issue>,
+    ignores: &IgnoreMap,
+    today: NaiveDate,
+) -> Vec<Suppression> {
+    let mut suppressed = Vec::new();
+    issues.retain(
+        |issue| match find_ignore(ignores, issue.line_number, &issue.rule_id, today) {
+            Some(ignore) => {
+                let suppression =
+                    Suppression::new(&issue.rule_id, &issue.file_path, issue.line_number, ignore);
+                log::info!("{}", suppression);
+                suppressed.push(suppression);
+                false
+            }
+            None => true,
+        },
+    );
+    suppressed
+}
+
+/// Returns a low-severity note, reported on the directive's line, for each
+/// finding in `issues` that a directive in `ignores` would silence had it
+/// not expired by `today`.
+pub fn expired_suppressions(issues: &[Issue], ignores: &IgnoreMap, today: NaiveDate) -> Vec<Issue> {
+    let mut notes = Vec::new();
+    for issue in issues {
+        let Some(directives) = ignores.get(&issue.line_number) else {
+            continue;
+        };
+        for directive in directives
+            .iter()
+            .filter(|d| d.rule == issue.rule_id && d.expired(today))
+        {
+            notes.push(Issue {
+                title: "Expired Suppression".to_string(),
+                description: format!(
+                    "`reviewlens:ignore {}` expired on {} and no longer silences {} at line {}.",
+                    directive.rule,
+                    directive.expires.unwrap(),
+                    issue.title,
+                    issue.line_number
+                ),
+                file_path: issue.file_path.clone(),
+                line_number: directive.line,
+                severity: Severity::Low,
+                suggested_fix: Some(
+                    "Fix the finding, or extend the `until=` date if the exception still holds."
+                        .to_string(),
+                ),
+                rule_id: directive.rule.clone(),
+                confidence: Confidence::High,
+                ..Default::default()
+            });
+        }
+    }
+    notes
+}
+
+/// Scans every line of `content` with `scanner` and drops the findings
+/// silenced by ignore directives, as the engine would. Built-in scanners
+/// implement `Scanner::scan` with this.
+pub fn scan_file(
+    scanner: &dyn Scanner,
+    file_path: &str,
+    content: &str,
+    config: &Config,
+) -> Result<Vec<Issue>> {
+    let mut issues =
+        scanner.scan_with_context(&ScanRequest::whole_file(file_path, content, config))?;
+    let today = chrono::Local::now().date_naive();
+    apply_suppressions(
+        &mut issues,
+        &parse_ignore_directives(file_path, content),
+        today,
+    );
+    Ok(issues)
+}
+
+pub mod cache;
+pub use cache::ScanCache;
+mod comments;
+use comments::CommentStyle;
+
 // --- Built-in Scanners ---
 
 pub mod secrets;
 pub use secrets::SecretsScanner;
 pub mod conventions;
 pub use conventions::ConventionsScanner;
+pub mod network_hygiene;
+pub use network_hygiene::NetworkHygieneScanner;
+pub mod removed_controls;
+pub use removed_controls::RemovedControlsScanner;
+pub mod artifacts;
+pub use artifacts::ArtifactsScanner;
+pub mod todo;
+pub use todo::TodoScanner;
+pub mod complexity;
+pub use complexity::ComplexityScanner;
+pub mod external;
+pub use external::{load_external_scanners, ExternalScanner};
+#[cfg(feature = "test-util")]
+pub mod testing;
 
 static SQL_INJECTION_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
     vec![
@@ -98,33 +477,26 @@ impl Scanner for SqlInjectionGoScanner {
     }
 
     fn scan(&self, file_path: &str, content: &str, config: &Config) -> Result<Vec<Issue>> {
+        scan_file(self, file_path, content, config)
+    }
+
+    fn scan_with_context(&self, request: &ScanRequest) -> Result<Vec<Issue>> {
+        let (file_path, config) = (request.path, request.config);
         let mut issues = Vec::new();
-        let ignores = parse_ignore_directives(content);
-        for (i, line) in content.lines().enumerate() {
+        for (line_number, line) in request.lines() {
             for regex in &*SQL_INJECTION_PATTERNS {
                 if regex.is_match(line) {
-                    if let Some(ignore) = find_ignore(&ignores, i + 1, "sql-injection-go") {
-                        log::info!(
-                            "Suppressed sql-injection-go at {}:{}{}",
-                            file_path,
-                            i + 1,
-                            ignore
-                                .reason
-                                .as_ref()
-                                .map(|r| format!(" - {}", r))
-                                .unwrap_or_default()
-                        );
-                    } else {
-                        issues.push(Issue {
-                            title: "Potential SQL Injection".to_string(),
-                            description: "Dynamic SQL query construction detected. Use parameterized queries instead.".to_string(),
-                            file_path: file_path.to_string(),
-                            line_number: i + 1,
-                            severity: config.rules.sql_injection_go.severity.clone(),
-                            suggested_fix: Some("Use parameterized queries instead of string concatenation.".to_string()),
-                            diff: Some(format!("-{}\n+db.Query(\"...\", params)", line.trim())),
-                        });
-                    }
+                    issues.push(Issue {
+                        title: "Potential SQL Injection".to_string(),
+                        description: "Dynamic SQL query construction detected. Use parameterized queries instead.".to_string(),
+                        file_path: file_path.to_string(),
+                        line_number,
+                        severity: config.rules.sql_injection_go.severity.clone(),
+                        suggested_fix: Some("Use parameterized queries instead of string concatenation.".to_string()),
+                        diff: Some(format!("-{}\n+db.Query(\"...\", params)", line.trim())),
+                        rule_id: "sql-injection-go".to_string(),
+                        ..Default::default()
+                    });
                     break;
                 }
             }
@@ -138,6 +510,28 @@ static HTTP_DEFAULT_CLIENT_REGEX: Lazy<Regex> =
 static HTTP_CLIENT_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new("(?i)&?http\\.Client\\{[^}]*\\}").unwrap());
 
+/// Adds a timeout to the `http.Client` literal on `line`.
+fn timeout_fix(line: &str) -> Option<Fix> {
+    let find = line.trim();
+    let literal = HTTP_CLIENT_REGEX.find(find)?.as_str();
+    let open = literal.find('{')?;
+    let fields = literal[open + 1..literal.len() - 1].trim();
+    let fields = if fields.is_empty() {
+        String::new()
+    } else {
+        format!(", {}", fields)
+    };
+    let with_timeout = format!(
+        "{}{{Timeout: 10 * time.Second{}}}",
+        &literal[..open],
+        fields
+    );
+    Some(Fix {
+        find: find.to_string(),
+        replace: find.replacen(literal, &with_timeout, 1),
+    })
+}
+
 pub struct HttpTimeoutsGoScanner;
 impl Scanner for HttpTimeoutsGoScanner {
     fn name(&self) -> &'static str {
@@ -145,45 +539,45 @@ impl Scanner for HttpTimeoutsGoScanner {
     }
 
     fn scan(&self, file_path: &str, content: &str, config: &Config) -> Result<Vec<Issue>> {
+        scan_file(self, file_path, content, config)
+    }
+
+    fn scan_with_context(&self, request: &ScanRequest) -> Result<Vec<Issue>> {
+        let (file_path, config) = (request.path, request.config);
         let mut issues = Vec::new();
-        let ignores = parse_ignore_directives(content);
-        for (i, line) in content.lines().enumerate() {
+        for (line_number, line) in request.lines() {
             let uses_default_client = HTTP_DEFAULT_CLIENT_REGEX.is_match(line);
             let client_without_timeout =
                 HTTP_CLIENT_REGEX.is_match(line) && !line.contains("Timeout:");
             if uses_default_client || client_without_timeout {
-                if let Some(ignore) = find_ignore(&ignores, i + 1, "http-timeouts-go") {
-                    log::info!(
-                        "Suppressed http-timeouts-go at {}:{}{}",
-                        file_path,
-                        i + 1,
-                        ignore
-                            .reason
-                            .as_ref()
-                            .map(|r| format!(" - {}", r))
-                            .unwrap_or_default()
-                    );
+                let fix = if uses_default_client {
+                    None
                 } else {
-                    issues.push(Issue {
-                        title: "HTTP Request Without Timeout".to_string(),
-                        description:
-                            "HTTP requests should set a timeout to avoid hanging indefinitely."
-                                .to_string(),
-                        file_path: file_path.to_string(),
-                        line_number: i + 1,
-                        severity: config.rules.http_timeouts_go.severity.clone(),
-                        suggested_fix: Some("Use an http.Client with a Timeout set.".to_string()),
-                        diff: Some(if uses_default_client {
-                            "-http.Get(url)\n+client := &http.Client{Timeout: 10 * time.Second}\n+client.Get(url)"
-                                .to_string()
-                        } else {
-                            format!(
-                                "-{}\n+&http.Client{{Timeout: 10 * time.Second}}",
-                                line.trim()
-                            )
-                        }),
-                    });
-                }
+                    timeout_fix(line)
+                };
+                issues.push(Issue {
+                    title: "HTTP Request Without Timeout".to_string(),
+                    description:
+                        "HTTP requests should set a timeout to avoid hanging indefinitely."
+                            .to_string(),
+                    file_path: file_path.to_string(),
+                    line_number,
+                    severity: config.rules.http_timeouts_go.severity.clone(),
+                    suggested_fix: Some("Use an http.Client with a Timeout set.".to_string()),
+                    diff: Some(match &fix {
+                        Some(fix) => fix.to_diff(),
+                        None => "-http.Get(url)\n+client := &http.Client{Timeout: 10 * time.Second}\n+client.Get(url)"
+                            .to_string(),
+                    }),
+                    rule_id: "http-timeouts-go".to_string(),
+                    fix,
+                    confidence: if uses_default_client {
+                        Confidence::High
+                    } else {
+                        Confidence::Medium
+                    },
+                    ..Default::default()
+                });
             }
         }
         Ok(issues)
@@ -195,53 +589,433 @@ impl Scanner for HttpTimeoutsGoScanner {
 /// Factory type for creating scanners.
 pub type ScannerFactory = fn() -> Box<dyn Scanner>;
 
-/// Global registry of scanners accessible by name.
-static REGISTRY: Lazy<Mutex<HashMap<&'static str, ScannerFactory>>> =
+/// Factory type for creating diff scanners.
+pub type DiffScannerFactory = fn() -> Box<dyn DiffScanner>;
+
+/// Creates the scanner for a rule.
+#[derive(Clone)]
+pub enum ScannerConstructor {
+    /// A scanner run over each changed file's contents.
+    File(Arc<dyn Fn() -> Box<dyn Scanner> + Send + Sync>),
+    /// A scanner run over each changed file's diff.
+    Diff(Arc<dyn Fn() -> Box<dyn DiffScanner> + Send + Sync>),
+}
+
+/// Where a built-in rule's `enabled` and `severity` settings live in
+/// `[rules]`.
+#[derive(Clone, Copy)]
+pub struct RuleSettings {
+    pub get: fn(&RulesConfig) -> (bool, &Severity),
+    pub get_mut: fn(&mut RulesConfig) -> (&mut bool, &mut Severity),
+}
+
+/// A rule in the registry: what it checks and how to create its scanner.
+#[derive(Clone)]
+pub struct ScannerDescriptor {
+    /// Rule id used in `[rules]`, `--rule` and ignore directives.
+    pub id: &'static str,
+    /// Display name of the scanner.
+    pub name: &'static str,
+    /// One-line description of what the rule flags.
+    pub description: &'static str,
+    /// Severity of the rule's findings unless configured otherwise. `None`
+    /// for custom scanners, which set their own.
+    pub default_severity: Option<Severity>,
+    /// Languages the rule applies to; empty if it applies to every file.
+    pub languages: &'static [&'static str],
+    /// The rule's `[rules]` settings. `None` for custom and external
+    /// scanners.
+    pub settings: Option<RuleSettings>,
+    pub factory: ScannerConstructor,
+}
+
+impl ScannerDescriptor {
+    /// Describes a custom scanner registered with only a name and factory.
+    fn custom(id: &'static str, factory: ScannerConstructor) -> Self {
+        Self {
+            id,
+            name: id,
+            description: "",
+            default_severity: None,
+            languages: &[],
+            settings: None,
+            factory,
+        }
+    }
+
+    /// Returns whether the rule runs on the file at `path`: always when it
+    /// lists no languages, otherwise when `path` is in one of them.
+    pub fn applies_to(&self, path: &str) -> bool {
+        self.languages.is_empty()
+            || Language::from_path(path)
+                .is_some_and(|language| self.languages.contains(&language.as_str()))
+    }
+
+    /// Creates the scanner, if the rule runs on file contents.
+    pub fn scanner(&self) -> Option<Box<dyn Scanner>> {
+        match &self.factory {
+            ScannerConstructor::File(factory) => Some(factory()),
+            ScannerConstructor::Diff(_) => None,
+        }
+    }
+
+    /// Creates the diff scanner, if the rule runs on diffs.
+    pub fn diff_scanner(&self) -> Option<Box<dyn DiffScanner>> {
+        match &self.factory {
+            ScannerConstructor::Diff(factory) => Some(factory()),
+            ScannerConstructor::File(_) => None,
+        }
+    }
+}
+
+/// Global registry of scanners accessible by rule id.
+static REGISTRY: Lazy<Mutex<HashMap<&'static str, ScannerDescriptor>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
+/// Registers `descriptor`, replacing any rule with the same id.
+pub fn register(descriptor: ScannerDescriptor) {
+    let mut registry = REGISTRY.lock().unwrap();
+    registry.insert(descriptor.id, descriptor);
+}
+
 /// Registers a scanner factory under a specific name.
 pub fn register_scanner(name: &'static str, constructor: ScannerFactory) {
-    let mut registry = REGISTRY.lock().unwrap();
-    registry.insert(name, constructor);
+    register(ScannerDescriptor::custom(
+        name,
+        ScannerConstructor::File(Arc::new(constructor)),
+    ));
+}
+
+/// Registers a diff scanner factory under a specific name.
+pub fn register_diff_scanner(name: &'static str, constructor: DiffScannerFactory) {
+    register(ScannerDescriptor::custom(
+        name,
+        ScannerConstructor::Diff(Arc::new(constructor)),
+    ));
+}
+
+/// Settings accessors for the `[rules.<field>]` table of a built-in rule.
+macro_rules! rule_settings {
+    ($field:ident) => {
+        Some(RuleSettings {
+            get: |rules| (rules.$field.enabled, &rules.$field.severity),
+            get_mut: |rules| (&mut rules.$field.enabled, &mut rules.$field.severity),
+        })
+    };
+}
+
+fn builtin_descriptors() -> Vec<ScannerDescriptor> {
+    fn file(factory: ScannerFactory) -> ScannerConstructor {
+        ScannerConstructor::File(Arc::new(factory))
+    }
+    vec![
+        ScannerDescriptor {
+            id: "secrets",
+            name: "Secrets Scanner",
+            description: "API keys, tokens, private keys and other credentials in source.",
+            default_severity: Some(Severity::High),
+            languages: &[],
+            settings: rule_settings!(secrets),
+            factory: file(|| Box::new(SecretsScanner)),
+        },
+        ScannerDescriptor {
+            id: "sql-injection-go",
+            name: "SQL Injection Scanner (Go)",
+            description: "SQL queries built by concatenating or formatting strings.",
+            default_severity: Some(Severity::Critical),
+            languages: &["go"],
+            settings: rule_settings!(sql_injection_go),
+            factory: file(|| Box::new(SqlInjectionGoScanner)),
+        },
+        ScannerDescriptor {
+            id: "http-timeouts-go",
+            name: "HTTP Timeouts Scanner (Go)",
+            description: "HTTP requests through the default client or a client without a timeout.",
+            default_severity: Some(Severity::Medium),
+            languages: &["go"],
+            settings: rule_settings!(http_timeouts_go),
+            factory: file(|| Box::new(HttpTimeoutsGoScanner)),
+        },
+        ScannerDescriptor {
+            id: "conventions",
+            name: "Convention Deviation Scanner",
+            description: "Logging and error handling that differ from the indexed repository.",
+            default_severity: Some(Severity::Low),
+            languages: &["rust", "go", "python", "javascript", "typescript"],
+            settings: rule_settings!(conventions),
+            factory: file(|| Box::new(ConventionsScanner::default())),
+        },
+        ScannerDescriptor {
+            id: "network-hygiene",
+            name: "Network Hygiene Scanner",
+            description: "Hardcoded IP addresses, internal hostnames and exposed debug endpoints.",
+            default_severity: Some(Severity::Medium),
+            languages: &[],
+            settings: rule_settings!(network_hygiene),
+            factory: file(|| Box::new(NetworkHygieneScanner)),
+        },
+        ScannerDescriptor {
+            id: "todo",
+            name: "TODO Debt Scanner",
+            description: "New TODO, FIXME, HACK and XXX comments without a ticket reference.",
+            default_severity: Some(Severity::Low),
+            languages: &[],
+            settings: rule_settings!(todo),
+            factory: file(|| Box::new(TodoScanner)),
+        },
+        ScannerDescriptor {
+            id: "complexity",
+            name: "Complexity Scanner",
+            description: "Changed functions that are very long or deeply nested.",
+            default_severity: Some(Severity::Low),
+            languages: &["rust", "go", "python", "javascript", "typescript"],
+            settings: rule_settings!(complexity),
+            factory: file(|| Box::new(ComplexityScanner)),
+        },
+        ScannerDescriptor {
+            id: "removed-controls",
+            name: "Removed Security Control Scanner",
+            description: "Deleted lines that held authentication, CSRF or validation checks.",
+            default_severity: Some(Severity::High),
+            languages: &[],
+            settings: rule_settings!(removed_controls),
+            factory: ScannerConstructor::Diff(Arc::new(|| {
+                Box::new(RemovedControlsScanner) as Box<dyn DiffScanner>
+            })),
+        },
+        ScannerDescriptor {
+            id: "artifacts",
+            name: "Committed Artifact Scanner",
+            description: "Added files that are large, minified, compiled or vendored.",
+            default_severity: Some(Severity::Medium),
+            languages: &[],
+            settings: rule_settings!(artifacts),
+            factory: ScannerConstructor::Diff(Arc::new(|| {
+                Box::new(ArtifactsScanner) as Box<dyn DiffScanner>
+            })),
+        },
+    ]
 }
 
+/// The built-in rules, in the order they run.
+static BUILTIN: Lazy<Vec<ScannerDescriptor>> = Lazy::new(builtin_descriptors);
+
 fn register_builtin_scanners() {
     static INIT: Once = Once::new();
     INIT.call_once(|| {
-        register_scanner("secrets", || Box::new(SecretsScanner));
-        register_scanner("sql-injection-go", || Box::new(SqlInjectionGoScanner));
-        register_scanner("http-timeouts-go", || Box::new(HttpTimeoutsGoScanner));
-        register_scanner("conventions", || Box::new(ConventionsScanner::default()));
+        for descriptor in BUILTIN.iter() {
+            register(descriptor.clone());
+        }
     });
 }
 
-/// Returns all scanners enabled via configuration.
-pub fn load_enabled_scanners(config: &Config) -> Vec<Box<dyn Scanner>> {
-    register_builtin_scanners();
+/// The `[rules]` settings of the built-in rule `rule`.
+fn builtin_settings(rule: &str) -> Option<RuleSettings> {
+    BUILTIN
+        .iter()
+        .find(|descriptor| descriptor.id == rule)?
+        .settings
+}
 
-    let registry = REGISTRY.lock().unwrap();
-    let mut scanners: Vec<Box<dyn Scanner>> = Vec::new();
+/// Returns whether the built-in or external rule `rule` is enabled in
+/// `config`. Unknown rule ids (custom scanners) are always enabled.
+pub fn rule_enabled(config: &Config, rule: &str) -> bool {
+    if let Some(settings) = builtin_settings(rule) {
+        return (settings.get)(&config.rules).0;
+    }
+    config
+        .scanners
+        .external
+        .iter()
+        .find(|scanner| scanner.rule == rule)
+        .is_none_or(|scanner| scanner.enabled)
+}
+
+/// Returns whether the rule `rule` runs on the file at `path`, given the
+/// languages its descriptor lists. Custom and external rules run on every
+/// file.
+pub fn rule_applies_to(rule: &str, path: &str) -> bool {
+    BUILTIN
+        .iter()
+        .find(|descriptor| descriptor.id == rule)
+        .is_none_or(|descriptor| descriptor.applies_to(path))
+}
+
+/// Returns the ids of every registered scanner, built-in or custom, sorted.
+pub fn registered_rule_names() -> Vec<&'static str> {
+    register_builtin_scanners();
+    let mut names: Vec<&'static str> = REGISTRY.lock().unwrap().keys().copied().collect();
+    names.sort_unstable();
+    names
+}
 
-    if config.rules.secrets.enabled {
-        if let Some(factory) = registry.get("secrets") {
-            scanners.push(factory());
+/// Sets the severity of the built-in or external rule `rule` in `config`
+/// (enabling it), or disables it when `severity` is `None`. Fails with
+/// `EngineError::Config` listing the known rules if `rule` is unknown.
+pub fn override_rule(config: &mut Config, rule: &str, severity: Option<Severity>) -> Result<()> {
+    let (enabled, level) = match builtin_settings(rule) {
+        Some(settings) => (settings.get_mut)(&mut config.rules),
+        _ if config.scanners.external.iter().any(|s| s.rule == rule) => {
+            let scanner = config
+                .scanners
+                .external
+                .iter_mut()
+                .find(|s| s.rule == rule)
+                .expect("external rule exists");
+            (&mut scanner.enabled, &mut scanner.severity)
         }
-    }
-    if config.rules.sql_injection_go.enabled {
-        if let Some(factory) = registry.get("sql-injection-go") {
-            scanners.push(factory());
+        _ => {
+            let mut names = registered_rule_names();
+            names.extend(config.scanners.external.iter().map(|s| s.rule.as_str()));
+            names.sort_unstable();
+            let message = if names.contains(&rule) {
+                format!("rule `{}` has no configurable severity", rule)
+            } else {
+                format!(
+                    "unknown rule `{}`; valid rules are: {}",
+                    rule,
+                    names.join(", ")
+                )
+            };
+            return Err(EngineError::Config(message));
         }
-    }
-    if config.rules.http_timeouts_go.enabled {
-        if let Some(factory) = registry.get("http-timeouts-go") {
-            scanners.push(factory());
+    };
+    match severity {
+        Some(severity) => {
+            *enabled = true;
+            *level = severity;
         }
+        None => *enabled = false,
     }
-    if config.rules.conventions.enabled {
-        if let Some(factory) = registry.get("conventions") {
-            scanners.push(factory());
-        }
+    Ok(())
+}
+
+/// Returns a scanner for the rule `rule`: a registered scanner, built-in or
+/// custom, or a `[[scanners.external]]` entry of `config`. Returns `None`
+/// for unknown rules and for rules that only run on diffs.
+pub fn scanner_for_rule(config: &Config, rule: &str) -> Option<Box<dyn Scanner>> {
+    descriptor_for_rule(config, rule)?.scanner()
+}
+
+/// Returns the descriptor of the rule `rule`, registered or external.
+pub fn descriptor_for_rule(config: &Config, rule: &str) -> Option<ScannerDescriptor> {
+    register_builtin_scanners();
+    if let Some(descriptor) = REGISTRY.lock().unwrap().get(rule) {
+        return Some(descriptor.clone());
     }
+    config
+        .scanners
+        .external
+        .iter()
+        .find(|spec| spec.rule == rule)
+        .map(ExternalScanner::descriptor)
+}
 
-    scanners
+/// Returns whether `rule` is a registered diff scanner.
+pub fn is_diff_rule(rule: &str) -> bool {
+    register_builtin_scanners();
+    REGISTRY
+        .lock()
+        .unwrap()
+        .get(rule)
+        .is_some_and(|descriptor| descriptor.diff_scanner().is_some())
+}
+
+/// Returns every rule: the built-in rules in the order they run, then
+/// custom scanners by id, then the `[[scanners.external]]` entries of
+/// `config`.
+pub fn descriptors(config: &Config) -> Vec<ScannerDescriptor> {
+    register_builtin_scanners();
+    let registry = REGISTRY.lock().unwrap();
+    let builtin: Vec<&str> = BUILTIN.iter().map(|descriptor| descriptor.id).collect();
+    let mut custom: Vec<&ScannerDescriptor> = registry
+        .values()
+        .filter(|descriptor| !builtin.contains(&descriptor.id))
+        .collect();
+    custom.sort_by_key(|descriptor| descriptor.id);
+    builtin
+        .iter()
+        .filter_map(|id| registry.get(id))
+        .chain(custom)
+        .cloned()
+        .chain(
+            config
+                .scanners
+                .external
+                .iter()
+                .map(ExternalScanner::descriptor),
+        )
+        .collect()
+}
+
+/// A rule as listed by `reviewlens rules`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RuleInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_severity: Option<Severity>,
+    /// Languages the rule applies to; empty if it applies to every file.
+    pub languages: Vec<String>,
+    /// Whether the rule runs under `config`, before any `[[overrides]]`.
+    pub enabled: bool,
+}
+
+/// Lists every rule in the order of `descriptors`, with whether it is
+/// enabled in `config`.
+pub fn rule_catalog(config: &Config) -> Vec<RuleInfo> {
+    descriptors(config)
+        .into_iter()
+        .map(|descriptor| RuleInfo {
+            id: descriptor.id.to_string(),
+            name: descriptor.name.to_string(),
+            description: descriptor.description.to_string(),
+            default_severity: descriptor.default_severity,
+            languages: descriptor.languages.iter().map(|l| l.to_string()).collect(),
+            enabled: rule_enabled(config, descriptor.id),
+        })
+        .collect()
+}
+
+/// Returns the built-in scanners accepted by `filter`, paired with their rule id.
+pub fn load_rule_scanners(filter: impl Fn(&str) -> bool) -> Vec<(&'static str, Box<dyn Scanner>)> {
+    register_builtin_scanners();
+
+    let registry = REGISTRY.lock().unwrap();
+    BUILTIN
+        .iter()
+        .map(|descriptor| descriptor.id)
+        .filter(|rule| filter(rule))
+        .filter_map(|rule| registry.get(rule)?.scanner().map(|scanner| (rule, scanner)))
+        .collect()
+}
+
+/// Returns the built-in diff scanners accepted by `filter`, paired with
+/// their rule id.
+pub fn load_diff_rule_scanners(
+    filter: impl Fn(&str) -> bool,
+) -> Vec<(&'static str, Box<dyn DiffScanner>)> {
+    register_builtin_scanners();
+
+    let registry = REGISTRY.lock().unwrap();
+    BUILTIN
+        .iter()
+        .map(|descriptor| descriptor.id)
+        .filter(|rule| filter(rule))
+        .filter_map(|rule| {
+            registry
+                .get(rule)?
+                .diff_scanner()
+                .map(|scanner| (rule, scanner))
+        })
+        .collect()
+}
+
+/// Returns all scanners enabled via configuration.
+pub fn load_enabled_scanners(config: &Config) -> Vec<Box<dyn Scanner>> {
+    load_rule_scanners(|rule| rule_enabled(config, rule))
+        .into_iter()
+        .map(|(_, scanner)| scanner)
+        .collect()
 }
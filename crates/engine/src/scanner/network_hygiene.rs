@@ -0,0 +1,179 @@
+//! A scanner for hardcoded network addresses and exposed debug endpoints.
+
+use globset::{Glob, GlobSetBuilder};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::scanner::{scan_file, Issue, ScanRequest, Scanner};
+
+pub struct NetworkHygieneScanner;
+
+// String literals are the only place hosts are considered hardcoded; this
+// keeps Rust paths such as `a::b` and version numbers in code out of scope.
+static STRING_LITERAL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#""([^"\\]|\\.)*"|'([^'\\]|\\.)*'|`[^`]*`"#).unwrap());
+static IPV4_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(?:\d{1,3}\.){3}\d{1,3}\b").unwrap());
+static IPV6_CANDIDATE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[0-9A-Fa-f]{0,4}(?::[0-9A-Fa-f]{0,4}){2,7}").unwrap());
+static URL_HOST_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b[a-z][a-z0-9+.-]*://([a-z0-9.-]+)").unwrap());
+static DEBUG_ENDPOINT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)/debug/pprof|/actuator\b").unwrap());
+static ROUTE_REGISTRATION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(HandleFunc|Handle|Route|Mount|Group|GET|POST|Any|get|post|use|route|add_url_rule|path)\s*\(",
+    )
+    .unwrap()
+});
+static PPROF_IMPORT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"_\s+"net/http/pprof""#).unwrap());
+
+/// Returns `true` for paths that look like test code.
+fn is_test_path(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    lower.ends_with("_test.go")
+        || lower.contains("/tests/")
+        || lower.starts_with("tests/")
+        || lower.contains("/test/")
+        || lower.starts_with("test/")
+        || lower.contains(".test.")
+        || lower.contains(".spec.")
+        || lower
+            .rsplit('/')
+            .next()
+            .is_some_and(|name| name.starts_with("test_"))
+}
+
+fn is_internal_host(host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    host == "localhost"
+        || host.ends_with(".localhost")
+        || host.ends_with(".internal")
+        || host.ends_with(".corp")
+}
+
+/// Finds hardcoded IP literals in a string literal.
+fn ip_literals(literal: &str) -> Vec<(String, bool)> {
+    let mut found = Vec::new();
+    for m in IPV4_REGEX.find_iter(literal) {
+        if let Ok(ip) = m.as_str().parse::<Ipv4Addr>() {
+            found.push((
+                m.as_str().to_string(),
+                ip.is_loopback() || ip.is_unspecified(),
+            ));
+        }
+    }
+    for m in IPV6_CANDIDATE_REGEX.find_iter(literal) {
+        let candidate = m.as_str();
+        if !candidate.contains("::") && candidate.matches(':').count() != 7 {
+            continue;
+        }
+        if let Ok(ip) = candidate.parse::<Ipv6Addr>() {
+            found.push((
+                candidate.to_string(),
+                ip.is_loopback() || ip.is_unspecified(),
+            ));
+        }
+    }
+    found
+}
+
+impl Scanner for NetworkHygieneScanner {
+    fn name(&self) -> &'static str {
+        "Network Hygiene Scanner"
+    }
+
+    fn scan(&self, file_path: &str, content: &str, config: &Config) -> Result<Vec<Issue>> {
+        scan_file(self, file_path, content, config)
+    }
+
+    fn scan_with_context(&self, request: &ScanRequest) -> Result<Vec<Issue>> {
+        let (file_path, config) = (request.path, request.config);
+        let rule = &config.rules.network_hygiene;
+        let mut exempt = GlobSetBuilder::new();
+        for pattern in &rule.exempt_paths {
+            if let Ok(glob) = Glob::new(pattern) {
+                exempt.add(glob);
+            }
+        }
+        if exempt
+            .build()
+            .map(|set| set.is_match(file_path))
+            .unwrap_or(false)
+        {
+            return Ok(vec![]);
+        }
+        let allowed = |host: &str| {
+            rule.allowed_hosts
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(host))
+        };
+        let in_test = is_test_path(file_path);
+
+        let mut issues = Vec::new();
+        for (line_number, line) in request.lines() {
+            let mut finding: Option<(String, String, String)> = None;
+
+            if DEBUG_ENDPOINT_REGEX.is_match(line) && ROUTE_REGISTRATION_REGEX.is_match(line)
+                || PPROF_IMPORT_REGEX.is_match(line)
+            {
+                finding = Some((
+                    "Debug Endpoint Exposed".to_string(),
+                    "A debug or management endpoint is registered and may expose internals in production.".to_string(),
+                    "Serve debug endpoints on a separate, non-public listener or enable them only via configuration.".to_string(),
+                ));
+            }
+
+            for literal in STRING_LITERAL_REGEX.find_iter(line) {
+                if finding.is_some() {
+                    break;
+                }
+                let literal = literal.as_str();
+                for caps in URL_HOST_REGEX.captures_iter(literal) {
+                    let host = &caps[1];
+                    if !in_test && is_internal_host(host) && !allowed(host) {
+                        finding = Some((
+                            "Hardcoded Internal URL".to_string(),
+                            format!("A URL pointing at internal host `{}` is hardcoded.", host),
+                            "Read service URLs from configuration or an environment variable."
+                                .to_string(),
+                        ));
+                        break;
+                    }
+                }
+                if finding.is_some() {
+                    break;
+                }
+                for (ip, local) in ip_literals(literal) {
+                    if (local && in_test) || allowed(&ip) {
+                        continue;
+                    }
+                    finding = Some((
+                        "Hardcoded IP Address".to_string(),
+                        format!("The IP address `{}` is hardcoded.", ip),
+                        "Read addresses from configuration or an environment variable.".to_string(),
+                    ));
+                    break;
+                }
+            }
+
+            let Some((title, description, suggested_fix)) = finding else {
+                continue;
+            };
+            issues.push(Issue {
+                title,
+                description,
+                file_path: file_path.to_string(),
+                line_number,
+                severity: rule.severity.clone(),
+                suggested_fix: Some(suggested_fix),
+                rule_id: "network-hygiene".to_string(),
+                ..Default::default()
+            });
+        }
+        Ok(issues)
+    }
+}
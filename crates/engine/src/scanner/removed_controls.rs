@@ -0,0 +1,141 @@
+//! A diff scanner for security controls deleted by a change.
+//!
+//! Regular scanners only see the file after the change, so a deleted
+//! `authenticate` middleware or CSRF check leaves nothing behind for them to
+//! flag. This scanner walks the removed lines of each hunk instead and
+//! reports those matching `rules.removed-controls.patterns`.
+
+use regex::Regex;
+
+use crate::config::{Config, Severity};
+use crate::diff_parser::{ChangedFile, Hunk, Line};
+use crate::error::{EngineError, Result};
+use crate::scanner::{DiffScanner, Issue};
+
+pub struct RemovedControlsScanner;
+
+/// Pairs each removed line of `hunk` with the nearest line of the new file:
+/// the line that now follows the removal, or the hunk's last line when the
+/// removal is at its end.
+fn anchored_removals(hunk: &Hunk) -> Vec<(usize, &str)> {
+    let last = (hunk.new_start + hunk.new_lines.saturating_sub(1)).max(1) as usize;
+    let mut new_line = hunk.new_start as usize;
+    let mut removed = Vec::new();
+    for line in &hunk.lines {
+        match line {
+            Line::Removed(text) => removed.push((new_line.clamp(1, last), text.as_str())),
+            Line::Added(_) | Line::Context(_) => new_line += 1,
+        }
+    }
+    removed
+}
+
+impl DiffScanner for RemovedControlsScanner {
+    fn name(&self) -> &'static str {
+        "Removed Security Control Scanner"
+    }
+
+    fn scan_diff(&self, file: &ChangedFile, config: &Config) -> Result<Vec<Issue>> {
+        let rule = &config.rules.removed_controls;
+        let patterns = rule
+            .patterns
+            .iter()
+            .map(|p| {
+                Regex::new(p).map_err(|e| {
+                    EngineError::Config(format!(
+                        "rules.removed-controls.patterns: invalid regex `{}`: {}",
+                        p, e
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if file.deleted {
+            return Ok(deleted_file_issue(file, &patterns, &rule.severity)
+                .into_iter()
+                .collect());
+        }
+        // A control that is only moved or reworded still appears on an added
+        // line, so the same match anywhere in the new lines clears it.
+        let added: Vec<&str> = file
+            .hunks
+            .iter()
+            .flat_map(|h| &h.lines)
+            .filter_map(|l| match l {
+                Line::Added(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        let mut issues = Vec::new();
+        for hunk in &file.hunks {
+            let old_lines = hunk.removed_line_numbers();
+            for ((line_number, text), old_line) in
+                anchored_removals(hunk).into_iter().zip(old_lines)
+            {
+                let Some(found) = patterns.iter().find_map(|p| p.find(text)) else {
+                    continue;
+                };
+                if added.iter().any(|a| a.contains(found.as_str())) {
+                    continue;
+                }
+                issues.push(Issue {
+                    description: format!(
+                        "The change deletes `{}` (old line {}), which looks like a security control. Confirm it is still enforced elsewhere.",
+                        text.trim(),
+                        old_line
+                    ),
+                    line_number,
+                    diff: Some(format!("-{}", text.trim())),
+                    ..removed_control(file, &rule.severity)
+                });
+            }
+        }
+        Ok(issues)
+    }
+}
+
+/// Reports a deleted file once, at line 1 of its old path, quoting the
+/// first removed line that matches `patterns`.
+fn deleted_file_issue(
+    file: &ChangedFile,
+    patterns: &[Regex],
+    severity: &Severity,
+) -> Option<Issue> {
+    let matches: Vec<(usize, &str)> = file
+        .hunks
+        .iter()
+        .flat_map(Hunk::removed_lines)
+        .filter(|(_, text)| patterns.iter().any(|p| p.is_match(text)))
+        .collect();
+    let (old_line, text) = *matches.first()?;
+    let count = match matches.len() {
+        1 => "1 removed line matches".to_string(),
+        n => format!("{} removed lines match", n),
+    };
+    Some(Issue {
+        description: format!(
+            "The change deletes `{}`, which held security controls such as `{}` (old line {}); {} `rules.removed-controls.patterns`. Confirm they are still enforced elsewhere.",
+            file.path,
+            text.trim(),
+            old_line,
+            count
+        ),
+        line_number: 1,
+        diff: Some(format!("-{}", text.trim())),
+        ..removed_control(file, severity)
+    })
+}
+
+/// The fields shared by every finding of the rule.
+fn removed_control(file: &ChangedFile, severity: &Severity) -> Issue {
+    Issue {
+        title: "Security Control Removed".to_string(),
+        description: String::new(),
+        file_path: file.path.clone(),
+        line_number: 1,
+        severity: severity.clone(),
+        suggested_fix: Some("Restore the check, or move it rather than delete it.".to_string()),
+        rule_id: "removed-controls".to_string(),
+        ..Default::default()
+    }
+}
@@ -1,69 +1,275 @@
 //! A scanner for detecting secrets and credentials.
+//!
+//! Each line is matched against named patterns, most specific first, and
+//! reported with the title of the first one that matches. Findings use the
+//! rule severity unless `[rules.secrets.patterns]` overrides it for the
+//! pattern's id. Provider-specific formats and private keys are reported with
+//! high confidence, the generic `api-key` and `token` assignments with
+//! medium. Lockfiles and obvious placeholder values are skipped, and the
+//! secret value is masked in the finding's description and diff.
 
 use once_cell::sync::Lazy;
-use regex::Regex;
+use regex::{Match, Regex};
+use std::path::Path;
 
-use crate::config::Config;
+use crate::config::{Confidence, Config};
 use crate::error::Result;
-use crate::scanner::{find_ignore, parse_ignore_directives, Issue, Scanner};
+use crate::privacy::mask_partial;
+use crate::scanner::{scan_file, Issue, ScanRequest, Scanner};
 
 pub struct SecretsScanner;
 
-// A set of regexes to detect common secret patterns.
-// Using `once_cell::sync::Lazy` for one-time compilation of regexes.
-static SECRET_REGEXES: Lazy<Vec<Regex>> = Lazy::new(|| {
+/// A named secret pattern. When the regex has capture groups, the first one
+/// that participates in a match is the secret value; otherwise the whole
+/// match is.
+struct SecretPattern {
+    /// Id used in `[rules.secrets.patterns]`.
+    id: &'static str,
+    title: &'static str,
+    /// How to confirm and remediate a leak of this kind of secret.
+    hint: &'static str,
+    /// Provider-specific formats are rarely anything else; generic
+    /// assignments often are.
+    confidence: Confidence,
+    regex: Regex,
+}
+
+impl SecretPattern {
+    /// Returns one entry per match on `line` whose value is not a
+    /// placeholder, holding the value if the match contains one. A private
+    /// key header alone, for instance, has no value to mask.
+    fn secrets<'a>(&self, line: &'a str) -> Vec<Option<Match<'a>>> {
+        let grouped = self.regex.captures_len() > 1;
+        self.regex
+            .captures_iter(line)
+            .filter_map(|caps| {
+                let whole = caps.get(0)?;
+                let value = if grouped {
+                    caps.iter().skip(1).flatten().next()
+                } else {
+                    Some(whole)
+                };
+                let checked = value.unwrap_or(whole);
+                (!is_placeholder(checked.as_str())).then_some(value)
+            })
+            .collect()
+    }
+}
+
+const GENERIC_HINT: &str =
+    "Remove secrets from source control and use secure storage or environment variables.";
+
+// Provider-specific patterns come before the generic ones so that the most
+// precise title wins. Compiled once on first use.
+static SECRET_PATTERNS: Lazy<Vec<SecretPattern>> = Lazy::new(|| {
+    let pattern = |id, title, hint, regex: &str| SecretPattern {
+        id,
+        title,
+        hint,
+        confidence: Confidence::High,
+        regex: Regex::new(regex).unwrap(),
+    };
+    let generic = |id, regex: &str| SecretPattern {
+        confidence: Confidence::Medium,
+        ..pattern(id, "Potential Secret Found", GENERIC_HINT, regex)
+    };
     vec![
-        // Generic API Key
-        Regex::new(r#"(?i)api[_-]?key\s*[:=]\s*['"][a-zA-Z0-9\-_]{16,}['"]"#).unwrap(),
-        // AWS Secret Key
-        Regex::new(r#"(?i)aws_secret_access_key\s*[:=]\s*['"][a-zA-Z0-9/+=]{40}['"]"#).unwrap(),
-        // Generic Token
-        Regex::new(r#"(?i)token\s*[:=]\s*['"][a-zA-Z0-9\-_]{20,}['"]"#).unwrap(),
-        // Private Key
-        Regex::new(r"-----BEGIN [A-Z ]+ PRIVATE KEY-----").unwrap(),
+        pattern(
+            "github-pat",
+            "GitHub personal access token",
+            "Check it with `GET https://api.github.com/user`, then revoke it under Settings > Developer settings and load it from a secret store.",
+            r"\bghp_[A-Za-z0-9]{36}\b",
+        ),
+        pattern(
+            "github-fine-grained-pat",
+            "GitHub fine-grained personal access token",
+            "Check it with `GET https://api.github.com/user`, then revoke it under Settings > Developer settings and load it from a secret store.",
+            r"\bgithub_pat_[A-Za-z0-9_]{60,}",
+        ),
+        pattern(
+            "github-oauth",
+            "GitHub OAuth access token",
+            "Revoke the grant through the OAuth app that issued it and load it from a secret store.",
+            r"\bgho_[A-Za-z0-9]{36}\b",
+        ),
+        pattern(
+            "slack-token",
+            "Slack token",
+            "Check it with Slack's `auth.test` method, then rotate it in the app settings and load it from a secret store.",
+            r"\bxox[baprs]-[A-Za-z0-9-]{10,}",
+        ),
+        pattern(
+            "stripe-secret-key",
+            "Stripe live secret key",
+            "Roll the key in the Stripe dashboard under Developers > API keys and load it from a secret store.",
+            r"\bsk_live_[A-Za-z0-9]{16,}",
+        ),
+        pattern(
+            "stripe-restricted-key",
+            "Stripe live restricted key",
+            "Roll the key in the Stripe dashboard under Developers > API keys and load it from a secret store.",
+            r"\brk_live_[A-Za-z0-9]{16,}",
+        ),
+        pattern(
+            "gcp-service-account",
+            "GCP service account private key",
+            "Delete the key in IAM > Service accounts and prefer workload identity over key files.",
+            r#""private_key"\s*:\s*"(-----BEGIN[^"]*)"#,
+        ),
+        pattern(
+            "npm-token",
+            "npm access token",
+            "Check it with `npm whoami`, then revoke it on npmjs.com under Access Tokens and read it from the environment.",
+            r"\b(npm_[A-Za-z0-9]{36})\b|_authToken\s*=\s*([^\s$][^\s]{19,})",
+        ),
+        pattern(
+            "jwt",
+            "JSON Web Token",
+            "Decode the JWT to see what it grants and when it expires; revoke the session or rotate the signing key if it is still valid.",
+            r"\beyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,}",
+        ),
+        pattern(
+            "aws-secret-key",
+            "AWS secret access key",
+            "Deactivate the key in IAM and use an instance role or the environment instead.",
+            r#"(?i)aws_secret_access_key\s*[:=]\s*['"]([a-zA-Z0-9/+=]{40})['"]"#,
+        ),
+        pattern(
+            "private-key",
+            "Private key",
+            GENERIC_HINT,
+            r"-----BEGIN [A-Z ]+ PRIVATE KEY-----(?:\\n)?([A-Za-z0-9+/=]{16,})?",
+        ),
+        generic(
+            "api-key",
+            r#"(?i)api[_-]?key\s*[:=]\s*['"]([a-zA-Z0-9\-_]{16,})['"]"#,
+        ),
+        generic(
+            "token",
+            r#"(?i)token\s*[:=]\s*['"]([a-zA-Z0-9\-_]{20,})['"]"#,
+        ),
     ]
 });
 
+/// Returns the ids of the secret patterns, for `[rules.secrets.patterns]`.
+pub fn pattern_ids() -> Vec<&'static str> {
+    SECRET_PATTERNS.iter().map(|pattern| pattern.id).collect()
+}
+
+/// Dependency lockfiles, which are full of hashes that look like secrets.
+const LOCKFILES: &[&str] = &[
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "Cargo.lock",
+    "Gemfile.lock",
+    "Pipfile.lock",
+    "poetry.lock",
+    "composer.lock",
+    "go.sum",
+];
+
+/// Substrings of values that are examples rather than real secrets.
+const PLACEHOLDERS: &[&str] = &[
+    "xxx",
+    "changeme",
+    "change_me",
+    "change-me",
+    "placeholder",
+    "example",
+    "dummy",
+];
+
+fn is_lockfile(path: &str) -> bool {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| LOCKFILES.contains(&name))
+}
+
+/// Replaces every secret value in `text` with its masked form, so that a
+/// finding or its snippet does not leak what it reports.
+pub(crate) fn mask_values(text: &str) -> String {
+    let mut values: Vec<Match> = SECRET_PATTERNS
+        .iter()
+        .flat_map(|pattern| pattern.secrets(text))
+        .flatten()
+        .collect();
+    values.sort_by_key(|value| (value.start(), std::cmp::Reverse(value.end())));
+    let mut masked = String::with_capacity(text.len());
+    let mut end = 0;
+    for value in values {
+        // Values nested in one already masked are covered by it.
+        if value.start() < end {
+            continue;
+        }
+        masked.push_str(&text[end..value.start()]);
+        masked.push_str(&mask_partial(value.as_str()));
+        end = value.end();
+    }
+    masked.push_str(&text[end..]);
+    masked
+}
+
+fn is_placeholder(value: &str) -> bool {
+    let value = value.to_ascii_lowercase();
+    PLACEHOLDERS
+        .iter()
+        .any(|placeholder| value.contains(placeholder))
+}
+
 impl Scanner for SecretsScanner {
     fn name(&self) -> &'static str {
         "Secrets Scanner"
     }
 
     fn scan(&self, file_path: &str, content: &str, config: &Config) -> Result<Vec<Issue>> {
+        scan_file(self, file_path, content, config)
+    }
+
+    fn scan_with_context(&self, request: &ScanRequest) -> Result<Vec<Issue>> {
+        let (file_path, config) = (request.path, request.config);
         let mut issues = Vec::new();
-        let ignores = parse_ignore_directives(content);
-        for (i, line) in content.lines().enumerate() {
-            for regex in &*SECRET_REGEXES {
-                if regex.is_match(line) {
-                    if let Some(ignore) = find_ignore(&ignores, i + 1, "secrets") {
-                        log::info!(
-                            "Suppressed secrets at {}:{}{}",
-                            file_path,
-                            i + 1,
-                            ignore
-                                .reason
-                                .as_ref()
-                                .map(|r| format!(" - {}", r))
-                                .unwrap_or_default()
-                        );
-                    } else {
-                        issues.push(Issue {
-                            title: "Potential Secret Found".to_string(),
-                            description: format!(
-                                "A line matching the pattern for a secret was found: `{}`. Please verify and rotate if necessary.",
-                                regex.as_str()
-                            ),
-                            file_path: file_path.to_string(),
-                            line_number: i + 1,
-                            severity: config.rules.secrets.severity.clone(),
-                            suggested_fix: Some("Remove secrets from source control and use secure storage or environment variables.".to_string()),
-                            diff: Some(format!("-{}\n+<redacted>", line.trim())),
-                        });
-                    }
-                    // Don't flag the same line multiple times
-                    break;
-                }
-            }
+        if is_lockfile(file_path) {
+            return Ok(issues);
+        }
+        let rule = &config.rules.secrets;
+        for (line_number, line) in request.lines() {
+            // Report each line once, for the most specific real match.
+            let Some((pattern, value)) = SECRET_PATTERNS.iter().find_map(|pattern| {
+                let value = pattern.secrets(line).into_iter().next()?;
+                Some((pattern, value))
+            }) else {
+                continue;
+            };
+            let description = match value {
+                Some(value) => format!(
+                    "A value matching the `{}` secret pattern was found: `{}`. Please verify and rotate if necessary.",
+                    pattern.id,
+                    mask_partial(value.as_str())
+                ),
+                None => format!(
+                    "A line matching the `{}` secret pattern was found. Please verify and rotate if necessary.",
+                    pattern.id
+                ),
+            };
+            issues.push(Issue {
+                title: pattern.title.to_string(),
+                description,
+                file_path: file_path.to_string(),
+                line_number,
+                severity: rule
+                    .patterns
+                    .get(pattern.id)
+                    .unwrap_or(&rule.severity)
+                    .clone(),
+                suggested_fix: Some(pattern.hint.to_string()),
+                diff: Some(format!("-{}\n+<redacted>", mask_values(line.trim()))),
+                rule_id: "secrets".to_string(),
+                confidence: pattern.confidence,
+                ..Default::default()
+            });
         }
         Ok(issues)
     }
@@ -0,0 +1,187 @@
+//! Helpers for testing scanners, available with the `test-util` feature.
+//!
+//! A scanner test in another crate needs little more than an annotated
+//! fixture:
+//!
+//! ```ignore
+//! use engine::scanner::testing::{assert_fixture, SingleRuleConfig};
+//!
+//! #[test]
+//! fn flags_debug_endpoints() {
+//!     let config = SingleRuleConfig::new("debug-endpoints").build();
+//!     assert_fixture(&DebugEndpointScanner, "tests/fixtures/debug.go", &config);
+//! }
+//! ```
+//!
+//! where `tests/fixtures/debug.go` marks each line that should be flagged
+//! with a trailing `// EXPECT: debug-endpoints`.
+
+use crate::config::{Config, Severity};
+use crate::scanner::{descriptor_for_rule, override_rule, registered_rule_names, Scanner};
+use std::path::Path;
+
+/// Marker that ends a fixture line expected to be flagged, followed by the
+/// rule id. Several rules may be listed, separated by commas.
+pub const EXPECT_MARKER: &str = "// EXPECT:";
+
+/// Builds a `Config` with every built-in rule disabled except one.
+pub struct SingleRuleConfig {
+    rule: String,
+    severity: Option<Severity>,
+    config: Config,
+}
+
+impl SingleRuleConfig {
+    /// Starts from the default configuration with only `rule` enabled.
+    /// Custom rule ids have no configuration and are left as they are.
+    pub fn new(rule: &str) -> Self {
+        Self {
+            rule: rule.to_string(),
+            severity: None,
+            config: Config::default(),
+        }
+    }
+
+    /// Reports the rule's findings at `severity` instead of its default.
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = Some(severity);
+        self
+    }
+
+    /// Starts from `config` instead of the default configuration, for rules
+    /// that take extra settings.
+    pub fn base(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns the configuration.
+    pub fn build(self) -> Config {
+        let mut config = self.config;
+        for rule in registered_rule_names() {
+            if rule != self.rule {
+                // Rules without configuration cannot be disabled; they are
+                // only run when passed to the helpers explicitly.
+                let _ = override_rule(&mut config, rule, None);
+            }
+        }
+        let severity = self
+            .severity
+            .or_else(|| builtin_severity(&config, &self.rule));
+        if let Some(severity) = severity {
+            let _ = override_rule(&mut config, &self.rule, Some(severity));
+        }
+        config
+    }
+}
+
+/// The configured severity of a built-in rule.
+fn builtin_severity(config: &Config, rule: &str) -> Option<Severity> {
+    let settings = descriptor_for_rule(config, rule)?.settings?;
+    Some((settings.get)(&config.rules).1.clone())
+}
+
+/// Source code annotated with the findings a scanner should report.
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    /// The source, annotations included.
+    pub content: String,
+    /// Expected `(line_number, rule_id)` pairs, with one-based line numbers.
+    pub expected: Vec<(usize, String)>,
+}
+
+impl Fixture {
+    /// Parses the `// EXPECT: <rule>` annotations in `content`.
+    pub fn parse(content: &str) -> Self {
+        let mut expected = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if let Some((_, rules)) = line.rsplit_once(EXPECT_MARKER) {
+                expected.extend(
+                    rules
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|rule| !rule.is_empty())
+                        .map(|rule| (i + 1, rule.to_string())),
+                );
+            }
+        }
+        Self {
+            content: content.to_string(),
+            expected,
+        }
+    }
+
+    /// Reads and parses the fixture at `path`, panicking if it cannot be read.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("failed to read fixture {}: {}", path.display(), e));
+        Self::parse(&content)
+    }
+}
+
+/// Scans `content` as `path` with the default configuration and asserts
+/// that the findings are exactly `expected`, given as `(line_number,
+/// rule_id)` pairs in any order.
+pub fn assert_findings(
+    scanner: &dyn Scanner,
+    path: &str,
+    content: &str,
+    expected: &[(usize, &str)],
+) {
+    assert_findings_with(scanner, path, content, &Config::default(), expected);
+}
+
+/// Like `assert_findings`, scanning with `config`.
+pub fn assert_findings_with(
+    scanner: &dyn Scanner,
+    path: &str,
+    content: &str,
+    config: &Config,
+    expected: &[(usize, &str)],
+) {
+    let issues = scanner
+        .scan(path, content, config)
+        .unwrap_or_else(|e| panic!("{} failed on {}: {}", scanner.name(), path, e));
+    let mut actual: Vec<(usize, &str)> = issues
+        .iter()
+        .map(|issue| (issue.line_number, issue.rule_id.as_str()))
+        .collect();
+    actual.sort_unstable();
+    let mut expected = expected.to_vec();
+    expected.sort_unstable();
+    assert_eq!(
+        actual,
+        expected,
+        "{} reported unexpected findings in {}:\n{}",
+        scanner.name(),
+        path,
+        issues
+            .iter()
+            .map(|issue| format!(
+                "  line {} [{}] {}",
+                issue.line_number, issue.rule_id, issue.title
+            ))
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+}
+
+/// Loads the fixture at `path` and asserts that scanning it with `config`
+/// reports exactly its annotated findings.
+pub fn assert_fixture(scanner: &dyn Scanner, path: impl AsRef<Path>, config: &Config) {
+    let path = path.as_ref();
+    let fixture = Fixture::load(path);
+    let expected: Vec<(usize, &str)> = fixture
+        .expected
+        .iter()
+        .map(|(line, rule)| (*line, rule.as_str()))
+        .collect();
+    assert_findings_with(
+        scanner,
+        &path.to_string_lossy(),
+        &fixture.content,
+        config,
+        &expected,
+    );
+}
@@ -0,0 +1,66 @@
+//! A scanner for TODO-style markers added by a change.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::config::{Confidence, Config};
+use crate::error::Result;
+use crate::scanner::{scan_file, Issue, RuleCategory, ScanRequest, Scanner};
+
+pub struct TodoScanner;
+
+// Markers are matched in upper case only, so words such as `todo` in
+// identifiers and prose are left alone.
+static MARKER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(TODO|FIXME|HACK|XXX)\b").unwrap());
+// A ticket key (`JIRA-123`), an issue number (`#42`) or a URL after the
+// marker counts as a link.
+static ISSUE_LINK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b[A-Z][A-Z0-9_]+-\d+\b|(?:^|[^\w&])#\d+\b|\bhttps?://\S+").unwrap());
+
+impl Scanner for TodoScanner {
+    fn name(&self) -> &'static str {
+        "TODO Debt Scanner"
+    }
+
+    fn scan(&self, file_path: &str, content: &str, config: &Config) -> Result<Vec<Issue>> {
+        scan_file(self, file_path, content, config)
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Quality
+    }
+
+    fn scan_with_context(&self, request: &ScanRequest) -> Result<Vec<Issue>> {
+        let rule = &request.config.rules.todo;
+        let mut issues = Vec::new();
+        for (line_number, line) in request.lines() {
+            let Some(marker) = MARKER_REGEX.find(line) else {
+                continue;
+            };
+            let linked = ISSUE_LINK_REGEX.is_match(&line[marker.end()..]);
+            if rule.require_issue_link && linked {
+                continue;
+            }
+            let description = if linked {
+                format!("New `{}` comment.", marker.as_str())
+            } else {
+                format!("New `{}` comment without an issue link.", marker.as_str())
+            };
+            issues.push(Issue {
+                title: "Unresolved TODO".to_string(),
+                description,
+                file_path: request.path.to_string(),
+                line_number,
+                severity: rule.severity.clone(),
+                suggested_fix: Some(format!(
+                    "Resolve it before merging or reference a ticket, e.g. `{}(PROJ-123)`.",
+                    marker.as_str()
+                )),
+                rule_id: "todo".to_string(),
+                confidence: Confidence::High,
+                ..Default::default()
+            });
+        }
+        Ok(issues)
+    }
+}
@@ -0,0 +1,71 @@
+//! JSON Schemas for the JSON report and the configuration file.
+//!
+//! Schemas are versioned with the crate: each `$id` names the release that
+//! wrote it, and JSON reports carry the same id in `$schema` along with
+//! `schema_version`, so consumers can tell which shape to expect.
+//! Generating a schema requires the `schema` feature.
+
+/// Version of the report and configuration formats.
+pub const SCHEMA_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A format with a JSON Schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchemaKind {
+    /// The JSON report written by `check --format json`.
+    Report,
+    /// `reviewlens.toml`.
+    Config,
+}
+
+impl SchemaKind {
+    /// Returns the kebab-case name of the format.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchemaKind::Report => "report",
+            SchemaKind::Config => "config",
+        }
+    }
+
+    /// Returns the `$id` of the schema for this format in this release.
+    pub fn id(&self) -> String {
+        format!(
+            "{}/schemas/{}/{}.schema.json",
+            env!("CARGO_PKG_HOMEPAGE"),
+            SCHEMA_VERSION,
+            self.as_str()
+        )
+    }
+}
+
+/// Generates the JSON Schema (draft 7) for `kind`.
+#[cfg(feature = "schema")]
+pub fn generate(kind: SchemaKind) -> serde_json::Value {
+    use schemars::gen::SchemaSettings;
+    use serde_json::{json, Value};
+
+    let generator = SchemaSettings::draft07().into_generator();
+    let root = match kind {
+        SchemaKind::Report => generator.into_root_schema_for::<crate::report::ReviewReport>(),
+        SchemaKind::Config => generator.into_root_schema_for::<crate::config::Config>(),
+    };
+    let mut schema = serde_json::to_value(root).unwrap_or(Value::Null);
+    if let Value::Object(fields) = &mut schema {
+        fields.insert("$id".to_string(), Value::String(kind.id()));
+    }
+    if kind == SchemaKind::Report {
+        if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+            properties.insert(
+                "$schema".to_string(),
+                json!({ "description": "The `$id` of this schema.", "type": "string" }),
+            );
+            properties.insert(
+                "schema_version".to_string(),
+                json!({
+                    "description": "Version of reviewlens that wrote the report.",
+                    "type": "string",
+                }),
+            );
+        }
+    }
+    schema
+}
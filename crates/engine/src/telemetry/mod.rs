@@ -1,3 +1,11 @@
+//! Run telemetry, recorded from [`ProgressEvent`]s by one or more sinks.
+//!
+//! [`Telemetry`] writes newline-delimited JSON events to a file or stdout.
+//! With the `otel` feature, [`otlp::OtlpSink`] exports the run as
+//! OpenTelemetry spans. Both are configured in `[telemetry]`, and library
+//! users can add their own [`TelemetrySink`] through the engine builder.
+
+use async_trait::async_trait;
 use serde::Serialize;
 use std::fs::File;
 use std::io::{self, Write};
@@ -5,16 +13,36 @@ use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::config::TelemetryConfig;
+use crate::privacy::Redactor;
+use crate::progress::ProgressEvent;
+
+#[cfg(feature = "otel")]
+pub mod otlp;
+
+/// Receives the progress events of every run.
+#[async_trait]
+pub trait TelemetrySink: Send + Sync {
+    /// Records `event`. Must not block for long; sinks that talk to the
+    /// network should buffer here and send in [`flush`](Self::flush).
+    fn record(&self, event: &ProgressEvent);
+
+    /// Sends anything buffered. Called once a run has finished; a sink that
+    /// cannot deliver logs the problem rather than failing the review.
+    async fn flush(&self) {}
+}
 
 /// Minimal telemetry emitter that writes newline-delimited JSON events.
 pub struct Telemetry {
     writer: Mutex<Box<dyn Write + Send>>,
+    redactor: Redactor,
 }
 
 impl Telemetry {
-    /// Creates a telemetry instance from configuration. Returns `Ok(None)` when telemetry is disabled.
+    /// Creates a telemetry instance from configuration. Returns `Ok(None)`
+    /// when telemetry is disabled, or when only `otlp-endpoint` is set so
+    /// that spans are not mirrored to stdout.
     pub fn from_config(cfg: &TelemetryConfig) -> io::Result<Option<Self>> {
-        if !cfg.enabled {
+        if !cfg.enabled || (cfg.file.is_none() && cfg.otlp_endpoint.is_some()) {
             return Ok(None);
         }
         let writer: Box<dyn Write + Send> = if let Some(path) = &cfg.file {
@@ -24,9 +52,16 @@ impl Telemetry {
         };
         Ok(Some(Self {
             writer: Mutex::new(writer),
+            redactor: Redactor::default(),
         }))
     }
 
+    /// Redacts file paths and rule names in `finding` events with `redactor`.
+    pub fn with_redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
     fn emit<T: Serialize>(&self, event: &T) {
         if let Ok(mut w) = self.writer.lock() {
             if serde_json::to_writer(&mut *w, event).is_ok() {
@@ -61,11 +96,13 @@ impl Telemetry {
             line: usize,
             rule: &'a str,
         }
+        let file = self.redactor.redact(file);
+        let rule = self.redactor.redact(rule);
         self.emit(&Finding {
             event: "finding",
-            file,
+            file: &file,
             line,
-            rule,
+            rule: &rule,
         });
     }
 
@@ -84,3 +121,16 @@ impl Telemetry {
         });
     }
 }
+
+impl TelemetrySink for Telemetry {
+    fn record(&self, event: &ProgressEvent) {
+        match event {
+            ProgressEvent::Started => self.run_started(),
+            ProgressEvent::Finding { path, line, rule } => self.finding(path, *line, rule),
+            ProgressEvent::Done {
+                findings, elapsed, ..
+            } => self.run_finished(*findings, elapsed.as_millis()),
+            _ => {}
+        }
+    }
+}
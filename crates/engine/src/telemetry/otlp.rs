@@ -0,0 +1,308 @@
+//! Exports each run as OpenTelemetry spans over OTLP/HTTP with JSON
+//! encoding.
+//!
+//! A run becomes one trace: a `review` root span with a child span for diff
+//! parsing, one per scanner and file, one for RAG retrieval, and one per LLM
+//! call. Spans are buffered while the run progresses and posted to
+//! `{otlp-endpoint}/v1/traces` when it finishes. A collector that cannot be
+//! reached costs a warning, never the review.
+
+use super::TelemetrySink;
+use crate::config::Config;
+use crate::llm::{http_client, Timeouts};
+use crate::privacy::Redactor;
+use crate::progress::ProgressEvent;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// OTLP `SPAN_KIND_INTERNAL`.
+const KIND_INTERNAL: u8 = 1;
+/// OTLP `STATUS_CODE_ERROR`.
+const STATUS_ERROR: u8 = 2;
+
+/// A telemetry sink exporting runs to an OTLP/HTTP collector.
+pub struct OtlpSink {
+    client: Client,
+    /// The traces endpoint, e.g. `http://localhost:4318/v1/traces`.
+    url: String,
+    provider: String,
+    model: Option<String>,
+    redactor: Redactor,
+    trace: Mutex<Trace>,
+}
+
+/// Spans of the run in progress.
+#[derive(Default)]
+struct Trace {
+    trace_id: String,
+    root_id: String,
+    started: Option<SystemTime>,
+    /// Findings RAG context is being retrieved for.
+    retrieving: usize,
+    spans: Vec<Value>,
+    /// Whether the root span has been added and the trace can be sent.
+    finished: bool,
+}
+
+impl OtlpSink {
+    /// Creates a sink posting to the collector at `endpoint`. LLM spans are
+    /// labelled with the provider and model from `config`.
+    pub fn new(endpoint: &str, config: &Config, redactor: Redactor) -> Self {
+        Self {
+            client: http_client(Timeouts::from_config(config)),
+            url: format!("{}/v1/traces", endpoint.trim_end_matches('/')),
+            provider: config.llm.provider.as_str().to_string(),
+            model: config.llm.model.clone(),
+            redactor,
+            trace: Mutex::new(Trace::default()),
+        }
+    }
+
+    /// Creates a sink from `[telemetry] otlp-endpoint`, if telemetry is
+    /// enabled and the endpoint is set.
+    pub fn from_config(config: &Config, redactor: Redactor) -> Option<Self> {
+        let endpoint = config.telemetry.otlp_endpoint.as_ref()?;
+        config
+            .telemetry
+            .enabled
+            .then(|| Self::new(endpoint, config, redactor))
+    }
+
+    fn llm_attributes(&self) -> Vec<Value> {
+        let mut attributes = vec![string("llm.provider", &self.provider)];
+        if let Some(model) = &self.model {
+            attributes.push(string("llm.model", model));
+        }
+        attributes
+    }
+}
+
+#[async_trait]
+impl TelemetrySink for OtlpSink {
+    fn record(&self, event: &ProgressEvent) {
+        let Ok(mut trace) = self.trace.lock() else {
+            return;
+        };
+        let now = SystemTime::now();
+        match event {
+            ProgressEvent::Started => {
+                *trace = Trace {
+                    trace_id: new_id(16),
+                    root_id: new_id(8),
+                    started: Some(now),
+                    ..Trace::default()
+                };
+            }
+            ProgressEvent::ParsedDiff { files } => {
+                let start = trace.started.unwrap_or(now);
+                trace.child(
+                    "diff.parse",
+                    start,
+                    now,
+                    vec![int("files", *files as u64)],
+                    None,
+                );
+            }
+            ProgressEvent::ScannerFinished {
+                name,
+                path,
+                findings,
+                elapsed,
+            } => {
+                let attributes = vec![
+                    string("scanner.name", name),
+                    string("file.path", &self.redactor.redact(path)),
+                    int("findings", *findings as u64),
+                ];
+                trace.child(name, before(now, *elapsed), now, attributes, None);
+            }
+            ProgressEvent::RetrievingContext { findings } => trace.retrieving = *findings,
+            ProgressEvent::ContextRetrieved { elapsed } => {
+                let attributes = vec![int("findings", trace.retrieving as u64)];
+                trace.child("rag.retrieve", before(now, *elapsed), now, attributes, None);
+            }
+            ProgressEvent::LlmFinished {
+                tokens,
+                cached,
+                error,
+                elapsed,
+            } => {
+                let mut attributes = self.llm_attributes();
+                attributes.push(int("llm.tokens", u64::from(*tokens)));
+                attributes.push(boolean("llm.cached", *cached));
+                let error = error.as_deref().map(|e| self.redactor.redact(e));
+                trace.child(
+                    "llm.generate",
+                    before(now, *elapsed),
+                    now,
+                    attributes,
+                    error,
+                );
+            }
+            ProgressEvent::Done {
+                findings,
+                tokens,
+                elapsed,
+            } => {
+                let mut attributes = self.llm_attributes();
+                attributes.push(int("findings", *findings as u64));
+                attributes.push(int("llm.tokens", u64::from(*tokens)));
+                let start = trace.started.unwrap_or_else(|| before(now, *elapsed));
+                let root = span(
+                    &trace.trace_id,
+                    &trace.root_id,
+                    None,
+                    "review",
+                    start,
+                    now,
+                    attributes,
+                    None,
+                );
+                trace.spans.insert(0, root);
+                trace.finished = true;
+            }
+            ProgressEvent::ScanningFile { .. }
+            | ProgressEvent::Finding { .. }
+            | ProgressEvent::CallingLlm => {}
+        }
+    }
+
+    async fn flush(&self) {
+        let spans = match self.trace.lock() {
+            Ok(mut trace) if trace.finished => std::mem::take(&mut *trace).spans,
+            _ => return,
+        };
+        let body = json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [string("service.name", "reviewlens")],
+                },
+                "scopeSpans": [{
+                    "scope": { "name": "reviewlens", "version": env!("CARGO_PKG_VERSION") },
+                    "spans": spans,
+                }],
+            }],
+        });
+        match self.client.post(&self.url).json(&body).send().await {
+            Ok(response) if response.status().is_success() => {
+                log::debug!("Exported {} spans to {}", spans_in(&body), self.url)
+            }
+            Ok(response) => log::warn!(
+                "OTLP collector at {} returned {}; spans dropped",
+                self.url,
+                response.status()
+            ),
+            Err(e) => log::warn!("Could not export spans to {}: {}", self.url, e),
+        }
+    }
+}
+
+impl Trace {
+    fn child(
+        &mut self,
+        name: &str,
+        start: SystemTime,
+        end: SystemTime,
+        attributes: Vec<Value>,
+        error: Option<String>,
+    ) {
+        // Events outside a run have no trace to belong to.
+        if self.started.is_none() {
+            return;
+        }
+        let span = span(
+            &self.trace_id,
+            &new_id(8),
+            Some(&self.root_id),
+            name,
+            start,
+            end,
+            attributes,
+            error,
+        );
+        self.spans.push(span);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn span(
+    trace_id: &str,
+    span_id: &str,
+    parent: Option<&str>,
+    name: &str,
+    start: SystemTime,
+    end: SystemTime,
+    attributes: Vec<Value>,
+    error: Option<String>,
+) -> Value {
+    let mut span = json!({
+        "traceId": trace_id,
+        "spanId": span_id,
+        "name": name,
+        "kind": KIND_INTERNAL,
+        "startTimeUnixNano": unix_nanos(start),
+        "endTimeUnixNano": unix_nanos(end),
+        "attributes": attributes,
+    });
+    if let Some(parent) = parent {
+        span["parentSpanId"] = json!(parent);
+    }
+    if let Some(message) = error {
+        span["status"] = json!({ "code": STATUS_ERROR, "message": message });
+    }
+    span
+}
+
+fn spans_in(body: &Value) -> usize {
+    body["resourceSpans"][0]["scopeSpans"][0]["spans"]
+        .as_array()
+        .map_or(0, Vec::len)
+}
+
+fn string(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// OTLP JSON encodes 64-bit integers as strings.
+fn int(key: &str, value: u64) -> Value {
+    json!({ "key": key, "value": { "intValue": value.to_string() } })
+}
+
+fn boolean(key: &str, value: bool) -> Value {
+    json!({ "key": key, "value": { "boolValue": value } })
+}
+
+fn before(time: SystemTime, elapsed: Duration) -> SystemTime {
+    time.checked_sub(elapsed).unwrap_or(time)
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+        .to_string()
+}
+
+/// Returns `bytes` pseudo-random bytes as lowercase hex, unique within the
+/// process and unlikely to collide across processes.
+fn new_id(bytes: usize) -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = Sha256::new();
+    hasher.update(unix_nanos(SystemTime::now()));
+    hasher.update(std::process::id().to_le_bytes());
+    hasher.update(COUNTER.fetch_add(1, Ordering::Relaxed).to_le_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .take(bytes)
+        .fold(String::with_capacity(bytes * 2), |mut hex, b| {
+            let _ = write!(hex, "{:02x}", b);
+            hex
+        })
+}
@@ -0,0 +1,85 @@
+//! Workspace files listing repositories to review together.
+//!
+//! A workspace file is TOML with one `[[repo]]` table per repository:
+//!
+//! ```toml
+//! [[repo]]
+//! name = "api"          # optional, defaults to the directory name
+//! path = "../api"       # relative to the workspace file
+//! base = "origin/main"  # optional, defaults to `check --diff`
+//! ```
+
+use crate::error::{EngineError, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// A repository listed in a workspace file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceRepo {
+    /// Display name of the repository.
+    pub name: String,
+    /// Path of the repository, resolved against the workspace file.
+    pub path: PathBuf,
+    /// Base reference to diff against, if set.
+    pub base: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkspaceFile {
+    #[serde(default)]
+    repo: Vec<RepoEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RepoEntry {
+    name: Option<String>,
+    path: PathBuf,
+    base: Option<String>,
+}
+
+/// Reads the repositories listed in the workspace file at `path`. Returns
+/// `EngineError::Config` if the file is malformed, lists no repositories, or
+/// lists two with the same name.
+pub fn load(path: &Path) -> Result<Vec<WorkspaceRepo>> {
+    let content = std::fs::read_to_string(path).map_err(|e| EngineError::io(path, e))?;
+    let file: WorkspaceFile = toml::from_str(&content)
+        .map_err(|e| EngineError::Config(format!("{}: {}", path.display(), e)))?;
+    if file.repo.is_empty() {
+        return Err(EngineError::Config(format!(
+            "{}: no [[repo]] entries",
+            path.display()
+        )));
+    }
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let mut repos: Vec<WorkspaceRepo> = Vec::new();
+    for entry in file.repo {
+        let resolved = dir.join(&entry.path);
+        let name = entry.name.unwrap_or_else(|| {
+            resolved
+                .canonicalize()
+                .ok()
+                .as_deref()
+                .unwrap_or(&resolved)
+                .file_name()
+                .map_or_else(
+                    || entry.path.display().to_string(),
+                    |n| n.to_string_lossy().into_owned(),
+                )
+        });
+        if repos.iter().any(|repo| repo.name == name) {
+            return Err(EngineError::Config(format!(
+                "{}: repository name `{}` is used twice",
+                path.display(),
+                name
+            )));
+        }
+        repos.push(WorkspaceRepo {
+            name,
+            path: resolved,
+            base: entry.base,
+        });
+    }
+    Ok(repos)
+}
@@ -0,0 +1,65 @@
+use engine::config::Severity;
+use engine::report::annotations::{command, escape_data, escape_property, github};
+use engine::scanner::Issue;
+
+fn issue(severity: Severity, path: &str, line: usize, title: &str, description: &str) -> Issue {
+    Issue {
+        title: title.into(),
+        description: description.into(),
+        file_path: path.into(),
+        line_number: line,
+        severity,
+        rule_id: "rule".into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn data_escapes_percent_and_newlines() {
+    assert_eq!(escape_data("100% sure\r\nnext"), "100%25 sure%0D%0Anext");
+}
+
+#[test]
+fn properties_also_escape_separators() {
+    assert_eq!(escape_property("a: b, c%\n"), "a%3A b%2C c%25%0A");
+}
+
+#[test]
+fn high_findings_are_errors_and_medium_ones_warnings() {
+    let high = issue(
+        Severity::High,
+        "src/a.rs",
+        3,
+        "Secret: key",
+        "Found a key.\nRotate it.",
+    );
+    assert_eq!(
+        command(&high, None),
+        "::error file=src/a.rs,line=3,title=Secret%3A key::Found a key.%0ARotate it."
+    );
+    let medium = issue(Severity::Medium, "b.go", 0, "Timeout", "No timeout");
+    assert_eq!(
+        command(&medium, Some("services/api/")),
+        "::warning file=services/api/b.go,title=Timeout::No timeout"
+    );
+}
+
+#[test]
+fn cap_keeps_the_most_severe_and_counts_the_rest() {
+    let issues = vec![
+        issue(Severity::Low, "a.rs", 1, "low", "low"),
+        issue(Severity::Critical, "a.rs", 2, "critical", "critical"),
+        issue(Severity::Medium, "a.rs", 3, "medium", "medium"),
+        issue(Severity::Critical, "a.rs", 4, "critical 2", "critical"),
+    ];
+    let lines = github(&issues, 2, None);
+    assert_eq!(lines.len(), 3, "{:#?}", lines);
+    assert!(lines[0].contains("line=2"));
+    assert!(lines[1].contains("line=4"));
+    assert_eq!(
+        lines[2],
+        "::notice::2 more findings omitted; see the review report"
+    );
+
+    assert_eq!(github(&issues, 4, None).len(), 4);
+}
@@ -0,0 +1,126 @@
+use engine::config::Config;
+use engine::diff_parser;
+use engine::scanner::{ArtifactsScanner, DiffScanner, Issue};
+use engine::ReviewEngine;
+
+/// A diff adding `path`, as binary when `lines` is empty.
+fn added(path: &str, lines: &[&str]) -> String {
+    let mut diff = format!("diff --git a/{0} b/{0}\nnew file mode 100644\n", path);
+    if lines.is_empty() {
+        diff.push_str(&format!("Binary files /dev/null and b/{} differ\n", path));
+        return diff;
+    }
+    diff.push_str(&format!(
+        "--- /dev/null\n+++ b/{}\n@@ -0,0 +1,{} @@\n",
+        path,
+        lines.len()
+    ));
+    for line in lines {
+        diff.push_str(&format!("+{}\n", line));
+    }
+    diff
+}
+
+fn scan(diff: &str, size: Option<u64>, config: &Config) -> Vec<Issue> {
+    let files = diff_parser::parse(diff).unwrap();
+    ArtifactsScanner
+        .scan_diff_sized(&files[0], size, config)
+        .unwrap()
+}
+
+#[test]
+fn flags_compiled_and_vendored_files() {
+    let config = Config::default();
+    let issues = scan(
+        &added("app/__pycache__/main.cpython-312.pyc", &[]),
+        None,
+        &config,
+    );
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].title, "Compiled Artifact Added");
+    assert_eq!(issues[0].rule_id, "artifacts");
+    assert_eq!(issues[0].line_number, 1);
+
+    let issues = scan(
+        &added(
+            "web/node_modules/left-pad/index.min.js",
+            &["module.exports=1"],
+        ),
+        None,
+        &config,
+    );
+    assert_eq!(issues.len(), 1, "one finding per file: {:?}", issues);
+    assert_eq!(issues[0].title, "Vendored Dependency Added");
+}
+
+#[test]
+fn size_limit_is_configurable() {
+    let diff = added("data/dump.csv", &["id,name"]);
+    let mut config = Config::default();
+    assert!(scan(&diff, Some(1024 * 1024), &config).is_empty());
+    let issues = scan(&diff, Some(1024 * 1024 + 1), &config);
+    assert_eq!(issues[0].title, "Large File Added");
+    assert!(
+        issues[0].description.contains("1025 KB"),
+        "{}",
+        issues[0].description
+    );
+
+    config.rules.artifacts.max_added_file_kb = 0;
+    assert!(scan(&diff, Some(u64::MAX), &config).is_empty());
+}
+
+#[test]
+fn only_added_files_are_checked() {
+    let diff = "diff --git a/dist/app.min.js b/dist/app.min.js\n--- a/dist/app.min.js\n+++ b/dist/app.min.js\n@@ -1 +1 @@\n-a()\n+b()\n";
+    assert!(scan(diff, Some(u64::MAX), &Config::default()).is_empty());
+}
+
+#[tokio::test]
+async fn engine_reports_large_and_minified_files_as_code_quality() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("assets")).unwrap();
+    std::fs::create_dir_all(root.join("dist")).unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("assets/model.bin"), vec![0u8; 2 * 1024 * 1024]).unwrap();
+    std::fs::write(root.join("dist/app.min.js"), "function a(){return 1}\n").unwrap();
+    std::fs::write(root.join("src/app.js"), "export const answer = 42;\n").unwrap();
+    let diff = [
+        added("assets/model.bin", &[]),
+        added("dist/app.min.js", &["function a(){return 1}"]),
+        added("src/app.js", &["export const answer = 42;"]),
+    ]
+    .concat();
+
+    let engine = ReviewEngine::new(Config::default())
+        .unwrap()
+        .with_root(root);
+    let report = engine.run(&diff).await.unwrap();
+
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+    let flagged = |path: &str| {
+        report
+            .code_quality
+            .iter()
+            .filter(|note| note.starts_with(&format!("{}:1 - ", path)))
+            .count()
+    };
+    assert_eq!(flagged("assets/model.bin"), 1, "{:?}", report.code_quality);
+    assert_eq!(flagged("dist/app.min.js"), 1, "{:?}", report.code_quality);
+    assert_eq!(flagged("src/app.js"), 0, "{:?}", report.code_quality);
+}
+
+#[tokio::test]
+async fn disabled_rule_keeps_binary_files_out_of_the_review() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("main.o"), [0u8; 16]).unwrap();
+    let mut config = Config::default();
+    config.rules.artifacts.enabled = false;
+    let engine = ReviewEngine::new(config).unwrap().with_root(dir.path());
+    let report = engine.run(&added("main.o", &[])).await.unwrap();
+
+    assert!(report.code_quality.is_empty(), "{:?}", report.code_quality);
+    assert_eq!(report.stats.files_reviewed, 0);
+    assert_eq!(report.stats.files_skipped, 1);
+}
@@ -0,0 +1,36 @@
+use engine::blame::{blame_lines, parse_porcelain};
+use std::collections::BTreeSet;
+
+const PORCELAIN: &str = "\
+1111111111111111111111111111111111111111 2 2 1
+author Alice
+author-mail <alice@example.com>
+author-time 1700000000
+summary first
+filename a.js
+\tconst API_KEY = \"x\";
+1111111111111111111111111111111111111111 5 5 1
+\tconst TOKEN = \"y\";
+0000000000000000000000000000000000000000 7 7 1
+author Not Committed Yet
+filename a.js
+\tconst NEW = 1;
+";
+
+#[test]
+fn parses_porcelain_and_skips_uncommitted_lines() {
+    let blame = parse_porcelain(PORCELAIN);
+    assert_eq!(blame.len(), 2);
+    assert_eq!(blame[&2].author, "Alice");
+    assert_eq!(blame[&5].author, "Alice");
+    assert_eq!(blame[&5].commit, "1".repeat(40));
+    assert!(!blame.contains_key(&7));
+}
+
+#[test]
+fn blame_outside_a_repository_is_empty() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.js"), "x\n").unwrap();
+    let lines: BTreeSet<usize> = [1].into_iter().collect();
+    assert!(blame_lines(dir.path(), "a.js", &lines).is_empty());
+}
@@ -1,5 +1,11 @@
-use engine::config::Config;
+use async_trait::async_trait;
+use engine::config::{Config, Provider, Severity};
+use engine::error::Result;
+use engine::llm::{estimate_tokens, LlmProvider, LlmResponse};
+use engine::scanner::{Issue, Scanner};
 use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 fn diff_for_file(path: &str, line: &str) -> String {
     format!(
@@ -40,5 +46,157 @@ async fn succeeds_within_token_budget() {
 
     std::env::set_current_dir(temp.path()).unwrap();
     let report = engine.run(&diff).await.unwrap();
-    assert!(report.summary.len() > 0 || report.issues.is_empty());
+    assert!(!report.summary.is_empty() || report.issues.is_empty());
+}
+
+/// Reports a fixed token usage per call and records every prompt. Synthesis
+/// prompts are answered with "merged", others with "partial N".
+struct MeteredProvider {
+    usage: u32,
+    prompts: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl LlmProvider for MeteredProvider {
+    async fn generate(&self, prompt: &str) -> Result<LlmResponse> {
+        let mut prompts = self.prompts.lock().unwrap();
+        prompts.push(prompt.to_string());
+        let content = if prompt.starts_with("Combine") {
+            "merged".to_string()
+        } else {
+            format!("partial {}", prompts.len())
+        };
+        Ok(LlmResponse {
+            content,
+            token_usage: self.usage,
+            cached: false,
+        })
+    }
+}
+
+/// Flags every line containing `TODO`.
+struct TodoScanner;
+
+impl Scanner for TodoScanner {
+    fn name(&self) -> &'static str {
+        "TODO Scanner"
+    }
+
+    fn scan(&self, file_path: &str, content: &str, _config: &Config) -> Result<Vec<Issue>> {
+        Ok(content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.contains("TODO"))
+            .map(|(i, _)| Issue {
+                title: "Unresolved TODO".into(),
+                description: "Resolve the TODO before merging.".into(),
+                file_path: file_path.to_string(),
+                line_number: i + 1,
+                severity: Severity::Low,
+                ..Default::default()
+            })
+            .collect())
+    }
+}
+
+/// A diff adding eight TODO lines to `main.rs`.
+fn todo_diff() -> (String, HashMap<String, String>) {
+    let content = (1..=8)
+        .map(|i| format!("// TODO {i}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let added: String = content.lines().flat_map(|l| ["+", l, "\n"]).collect();
+    let diff = format!(
+        "diff --git a/main.rs b/main.rs\n--- a/main.rs\n+++ b/main.rs\n@@ -0,0 +1,8 @@\n{}",
+        added
+    );
+    let mut files = HashMap::new();
+    files.insert("main.rs".to_string(), content);
+    (diff, files)
+}
+
+fn metered_engine(config: Config, usage: u32) -> (ReviewEngine, Arc<Mutex<Vec<String>>>) {
+    let prompts = Arc::new(Mutex::new(Vec::new()));
+    let mut config = config;
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    let engine = ReviewEngine::builder(config)
+        .llm(Box::new(MeteredProvider {
+            usage,
+            prompts: prompts.clone(),
+        }))
+        .scanners(vec![("todo", Box::new(TodoScanner))])
+        .build()
+        .unwrap();
+    (engine, prompts)
+}
+
+#[tokio::test]
+async fn oversized_prompt_is_split_into_batches_and_synthesized() {
+    let mut config = Config::default();
+    config.llm.max_prompt_tokens = Some(60);
+    let (engine, prompts) = metered_engine(config, 10);
+    let (diff, files) = todo_diff();
+
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    assert_eq!(report.issues.len(), 8);
+    assert_eq!(report.summary, "merged");
+    assert!(!report.metadata.budget_exhausted);
+    assert!(report.notes.is_empty());
+
+    let prompts = prompts.lock().unwrap();
+    let (synthesis, batches) = prompts.split_last().unwrap();
+    assert!(batches.len() > 1, "{:?}", batches);
+    assert_eq!(report.stats.tokens_used, 10 * prompts.len() as u32);
+    for prompt in prompts.iter() {
+        assert!(estimate_tokens(prompt) <= 60, "{}", prompt);
+    }
+    // Every finding is summarized exactly once, in order.
+    let lines: Vec<&str> = batches
+        .iter()
+        .flat_map(|p| p.lines().filter(|l| l.starts_with("main.rs:")))
+        .collect();
+    let expected: Vec<String> = (1..=8)
+        .map(|i| format!("main.rs:{i} Unresolved TODO - Resolve the TODO before merging."))
+        .collect();
+    assert_eq!(lines, expected);
+    for i in 1..=batches.len() {
+        assert!(synthesis.contains(&format!("partial {i}")), "{}", synthesis);
+    }
+}
+
+#[tokio::test]
+async fn stops_calling_once_budget_would_be_exceeded() {
+    let mut config = Config::default();
+    config.llm.max_prompt_tokens = Some(60);
+    config.budget.tokens.max_per_run = Some(200);
+    let (engine, prompts) = metered_engine(config, 90);
+    let (diff, files) = todo_diff();
+
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    assert_eq!(prompts.lock().unwrap().len(), 2);
+    assert_eq!(report.stats.tokens_used, 180);
+    assert!(report.metadata.budget_exhausted);
+    assert!(report.metadata.llm_error.is_none());
+    assert_eq!(report.summary, "partial 1\n\npartial 2");
+    assert_eq!(report.notes.len(), 1);
+    assert!(report.notes[0].contains("2 of"), "{}", report.notes[0]);
+}
+
+#[tokio::test]
+async fn exhausted_budget_falls_back_without_calling_provider() {
+    let mut config = Config::default();
+    config.budget.tokens.max_per_run = Some(0);
+    let (engine, prompts) = metered_engine(config, 10);
+    let (diff, files) = todo_diff();
+
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    assert!(prompts.lock().unwrap().is_empty());
+    assert!(report.metadata.budget_exhausted);
+    assert_eq!(report.stats.tokens_used, 0);
+    assert!(
+        report.summary.starts_with("Reviewed 1 file"),
+        "{}",
+        report.summary
+    );
 }
@@ -0,0 +1,52 @@
+use engine::config::{apply_ci_mode, Config, Provider};
+use engine::error::EngineError;
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+#[test]
+fn ci_mode_pins_temperature() {
+    let mut config = Config::default();
+    config.generation.temperature = Some(0.7);
+    apply_ci_mode(&mut config).unwrap();
+    assert_eq!(config.generation.temperature, Some(0.0));
+}
+
+#[test]
+fn ci_mode_requires_model_for_remote_provider() {
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    let err = apply_ci_mode(&mut config).unwrap_err();
+    assert!(matches!(err, EngineError::Config(ref msg) if msg.contains("[llm].model")));
+
+    config.llm.model = Some("gpt-test".into());
+    apply_ci_mode(&mut config).unwrap();
+}
+
+fn added_file(path: &str, line: &str) -> String {
+    format!(
+        "diff --git a/{p} b/{p}\n--- a/{p}\n+++ b/{p}\n@@ -0,0 +1 @@\n+{l}\n",
+        p = path,
+        l = line
+    )
+}
+
+#[tokio::test]
+async fn issues_are_sorted_by_location() {
+    let secret = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+    let diff: String = ["b.rs", "a.rs"]
+        .iter()
+        .map(|p| added_file(p, secret))
+        .collect();
+    let files: HashMap<String, String> = ["a.rs", "b.rs"]
+        .iter()
+        .map(|p| (p.to_string(), format!("{}\n", secret)))
+        .collect();
+
+    let report = ReviewEngine::new(Config::default())
+        .unwrap()
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap();
+    let paths: Vec<&str> = report.issues.iter().map(|i| i.file_path.as_str()).collect();
+    assert_eq!(paths, vec!["a.rs", "b.rs"]);
+}
@@ -0,0 +1,33 @@
+//! Helpers shared by the engine integration tests. Each test binary uses
+//! only some of them.
+#![allow(dead_code)]
+
+use engine::config::Config;
+use engine::report::ReviewReport;
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+/// A diff adding `content` as the new file `path`.
+pub fn file_diff(path: &str, content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut diff = format!(
+        "diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n@@ -0,0 +1,{1} @@\n",
+        path,
+        lines.len()
+    );
+    for line in lines {
+        diff.push_str(&format!("+{}\n", line));
+    }
+    diff
+}
+
+/// Reviews `content` added as the new file `path`, read from memory.
+pub async fn review_file(config: &Config, path: &str, content: &str) -> ReviewReport {
+    let mut files = HashMap::new();
+    files.insert(path.to_string(), content.to_string());
+    let engine = ReviewEngine::new(config.clone()).unwrap();
+    engine
+        .run_with_provider(&file_diff(path, content), &files)
+        .await
+        .unwrap()
+}
@@ -0,0 +1,94 @@
+use engine::config::{Config, FailOn, Severity};
+use engine::report::{
+    compare, JsonGenerator, ReportGenerator, ReviewReport, RuntimeMetadata, TimingInfo,
+};
+use engine::scanner::Issue;
+
+fn issue(title: &str, line: usize, severity: Severity, fingerprint: &str) -> Issue {
+    Issue {
+        title: title.into(),
+        description: format!("{} description", title),
+        file_path: "src/lib.rs".into(),
+        line_number: line,
+        severity,
+        rule_id: "secrets".into(),
+        fingerprint: fingerprint.into(),
+        ..Default::default()
+    }
+}
+
+fn report(issues: Vec<Issue>) -> ReviewReport {
+    ReviewReport {
+        summary: "Summary".into(),
+        issues,
+        code_quality: Vec::new(),
+        hotspots: Vec::new(),
+        mermaid_diagram: None,
+        config: Config::default(),
+        metadata: RuntimeMetadata {
+            ruleset_version: "1.0.0".into(),
+            model: None,
+            driver: "null".into(),
+            timings: TimingInfo {
+                total_ms: 5,
+                ..Default::default()
+            },
+            index_warm: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn json_report_round_trips() {
+    let original = report(vec![issue("Leaked key", 3, Severity::High, "abc")]);
+    let json = JsonGenerator.generate(&original).unwrap();
+    let parsed = ReviewReport::from_json(&json).unwrap();
+    assert_eq!(parsed.issues.len(), 1);
+    assert_eq!(parsed.issues[0].fingerprint, "abc");
+    assert_eq!(parsed.issues[0].severity, Severity::High);
+    assert_eq!(parsed.config.fail_on, original.config.fail_on);
+    assert_eq!(JsonGenerator.generate(&parsed).unwrap(), json);
+}
+
+#[test]
+fn from_json_rejects_malformed_reports() {
+    assert!(ReviewReport::from_json("{\"summary\": 1}").is_err());
+}
+
+#[test]
+fn classifies_new_resolved_and_persisting_by_fingerprint() {
+    let before = report(vec![
+        issue("Fixed", 2, Severity::High, "fixed"),
+        issue("Kept", 10, Severity::Medium, "kept"),
+    ]);
+    let after = report(vec![
+        // Moved down by inserted lines, but the same finding.
+        issue("Kept", 14, Severity::Medium, "kept"),
+        issue("Added", 20, Severity::Critical, "added"),
+    ]);
+    let comparison = compare(&before, &after);
+    let titles = |issues: &[Issue]| issues.iter().map(|i| i.title.clone()).collect::<Vec<_>>();
+    assert_eq!(titles(&comparison.new), ["Added"]);
+    assert_eq!(titles(&comparison.resolved), ["Fixed"]);
+    assert_eq!(titles(&comparison.persisting), ["Kept"]);
+    assert_eq!(comparison.persisting[0].line_number, 14);
+
+    assert_eq!(comparison.failing_new(&FailOn::High).len(), 1);
+    assert!(comparison.failing_new(&FailOn::Never).is_empty());
+
+    let md = comparison.to_markdown();
+    assert!(md.contains("1 new, 1 resolved, 1 persisting"));
+    assert!(md.contains("## New (1)"));
+    assert!(md.contains("`critical` Added at `src/lib.rs:20`"));
+}
+
+#[test]
+fn issues_without_fingerprints_match_by_location() {
+    let before = report(vec![issue("Old", 4, Severity::Low, "")]);
+    let after = report(vec![issue("Old", 4, Severity::Low, "")]);
+    let comparison = compare(&before, &after);
+    assert!(comparison.new.is_empty());
+    assert_eq!(comparison.persisting.len(), 1);
+}
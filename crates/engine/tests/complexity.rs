@@ -0,0 +1,165 @@
+use engine::config::Config;
+use engine::scanner::{ComplexityScanner, ScanRequest, Scanner};
+use engine::ReviewEngine;
+use std::collections::{HashMap, HashSet};
+
+fn config(max_function_lines: usize, max_nesting: usize) -> Config {
+    let mut config = Config::default();
+    config.rules.complexity.max_function_lines = max_function_lines;
+    config.rules.complexity.max_nesting = max_nesting;
+    config
+}
+
+fn descriptions(path: &str, content: &str, config: &Config) -> Vec<String> {
+    ComplexityScanner
+        .scan(path, content, config)
+        .unwrap()
+        .into_iter()
+        .map(|issue| issue.description)
+        .collect()
+}
+
+const RUST: &str = "\
+fn short() -> u8 { 1 }
+
+pub fn long(items: &[u8]) -> usize {
+    let mut total = 0;
+    for item in items {
+        if *item > 1 {
+            total += 1;
+        }
+    }
+    println!(\"{{ {} }}\", total);
+    total
+}
+";
+
+#[test]
+fn rust_line_count_boundary() {
+    // `long` spans lines 3-12.
+    assert!(descriptions("src/lib.rs", RUST, &config(10, 5)).is_empty());
+    assert_eq!(
+        descriptions("src/lib.rs", RUST, &config(9, 5)),
+        vec!["`long` spans 10 lines (limit 9) with a nesting depth of 2 (limit 5)."]
+    );
+}
+
+#[test]
+fn rust_nesting_boundary() {
+    assert!(descriptions("src/lib.rs", RUST, &config(80, 2)).is_empty());
+    assert_eq!(descriptions("src/lib.rs", RUST, &config(80, 1)).len(), 1);
+    // The one-line function has no nesting and a single line.
+    assert_eq!(descriptions("src/lib.rs", RUST, &config(1, 0)).len(), 1);
+}
+
+#[test]
+fn go_functions_and_methods() {
+    let content = "\
+package main
+
+func (s *Server) Handle(w http.ResponseWriter) {
+\tif s.ready {
+\t\tfor _, h := range s.hooks {
+\t\t\th()
+\t\t}
+\t}
+}
+
+func Ping() { return }
+";
+    assert!(descriptions("main.go", content, &config(7, 2)).is_empty());
+    assert_eq!(
+        descriptions("main.go", content, &config(6, 1)),
+        vec!["`Handle` spans 7 lines (limit 6) with a nesting depth of 2 (limit 1)."]
+    );
+}
+
+#[test]
+fn unclosed_function_runs_to_end_of_file() {
+    let content = "function build(items) {\n  if (items) {\n    items.push(1);\n";
+    assert_eq!(
+        descriptions("src/app.js", content, &config(2, 5)),
+        vec!["`build` spans 3 lines (limit 2) with a nesting depth of 1 (limit 5)."]
+    );
+}
+
+#[test]
+fn typescript_arrow_functions() {
+    let content = "\
+export const double = (x: number): number => x * 2
+const visit = async (nodes: Node[]) => {
+  for (const node of nodes) {
+    if (node.children) {
+      await visit(node.children);
+    }
+  }
+};
+";
+    assert_eq!(
+        descriptions("src/tree.ts", content, &config(6, 5)),
+        vec!["`visit` spans 7 lines (limit 6) with a nesting depth of 2 (limit 5)."]
+    );
+}
+
+#[test]
+fn python_bodies_follow_indentation() {
+    let content = "\
+def one(): return 1
+
+def walk(tree,
+         depth=0):
+    for node in tree:
+        if node.children:
+            walk(node.children,
+                    depth + 1)
+
+    return depth
+
+class Other:
+    pass
+";
+    // `walk` spans lines 3-10; the wrapped call adds no nesting.
+    assert!(descriptions("walk.py", content, &config(8, 2)).is_empty());
+    assert_eq!(
+        descriptions("walk.py", content, &config(7, 1)),
+        vec!["`walk` spans 8 lines (limit 7) with a nesting depth of 2 (limit 1)."]
+    );
+}
+
+#[test]
+fn only_functions_overlapping_changed_lines_are_reported() {
+    let config = config(9, 5);
+    let scan = |changed: &HashSet<usize>| {
+        ComplexityScanner
+            .scan_with_context(&ScanRequest {
+                path: "src/lib.rs",
+                content: RUST,
+                config: &config,
+                changed_lines: Some(changed),
+                hunks: &[],
+            })
+            .unwrap()
+    };
+    assert!(scan(&HashSet::from([1])).is_empty());
+
+    let issues = scan(&HashSet::from([1, 8]));
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].line_number, 8);
+}
+
+#[tokio::test]
+async fn findings_are_listed_under_code_quality() {
+    let mut files = HashMap::new();
+    files.insert("src/lib.rs".to_string(), RUST.to_string());
+    let diff = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -7,0 +8 @@\n+            total += 1;\n";
+    let report = ReviewEngine::new(config(9, 5))
+        .unwrap()
+        .run_with_provider(diff, &files)
+        .await
+        .unwrap();
+    assert!(report.issues.is_empty());
+    assert_eq!(
+        report.code_quality,
+        vec!["src/lib.rs:8 - `long` spans 10 lines (limit 9) with a nesting depth of 2 (limit 5)."]
+    );
+}
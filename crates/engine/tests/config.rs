@@ -60,3 +60,60 @@ fn default_config_is_sane() {
     assert!(config.rules.secrets.enabled);
     assert_eq!(config.rules.secrets.severity, Severity::High);
 }
+
+#[test]
+fn rule_severities_round_trip_through_toml() {
+    let toml = r#"
+[rules.secrets]
+severity = "low"
+
+[rules.sql-injection-go]
+severity = "high"
+
+[rules.http-timeouts-go]
+severity = "critical"
+
+[rules.conventions]
+severity = "medium"
+
+[rules.network-hygiene]
+severity = "low"
+
+[rules.removed-controls]
+severity = "medium"
+"#;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("reviewlens.toml");
+    fs::write(&path, toml).unwrap();
+    let config = Config::load_from_path(&path).unwrap();
+    let rules = &config.rules;
+    assert_eq!(rules.secrets.severity, Severity::Low);
+    assert_eq!(rules.sql_injection_go.severity, Severity::High);
+    assert_eq!(rules.http_timeouts_go.severity, Severity::Critical);
+    assert_eq!(rules.conventions.severity, Severity::Medium);
+    assert_eq!(rules.network_hygiene.severity, Severity::Low);
+    assert_eq!(rules.removed_controls.severity, Severity::Medium);
+
+    fs::write(&path, toml::to_string(&config).unwrap()).unwrap();
+    let reloaded = Config::load_from_path(&path).unwrap();
+    assert_eq!(reloaded.rules, config.rules);
+}
+
+#[test]
+fn omitted_rules_take_their_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("reviewlens.toml");
+    fs::write(
+        &path,
+        "[rules.secrets]\nenabled = false\n\n[rules.sql-injection-go]\nenabled = false\n",
+    )
+    .unwrap();
+    let rules = Config::load_from_path(&path).unwrap().rules;
+    assert!(!rules.secrets.enabled);
+    assert!(!rules.sql_injection_go.enabled);
+    assert_eq!(rules.sql_injection_go.severity, Severity::Critical);
+    assert!(rules.http_timeouts_go.enabled);
+    assert_eq!(rules.http_timeouts_go.severity, Severity::Medium);
+    assert!(rules.conventions.enabled);
+    assert_eq!(rules.conventions.severity, Severity::Low);
+}
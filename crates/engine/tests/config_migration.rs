@@ -0,0 +1,127 @@
+use engine::config::{
+    migrate_document, unknown_keys, Config, ConventionsMode, Deprecation, Severity,
+};
+use std::fs;
+
+const OLD: &str = r#"# Rebuilt nightly
+index_path = "custom/index.json.zst" # by CI
+
+[llm]
+provider = "null"
+
+# Legacy rule names
+[rules.sql_injection_go]
+enabled = false
+severity = "low"
+
+[rules]
+http-timeouts = { enabled = true, severity = "high" }
+"#;
+
+fn messages(deprecations: &[Deprecation]) -> Vec<String> {
+    deprecations.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn load_moves_deprecated_keys_and_reports_each_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let user = dir.path().join("user.toml");
+    let repo = dir.path().join("reviewlens.toml");
+    fs::write(&user, "index_path = \"user/index.json.zst\"\n").unwrap();
+    fs::write(&repo, OLD).unwrap();
+
+    let (config, provenance) = Config::load_layered(Some(&user), &repo).unwrap();
+    assert_eq!(config.index_path(), Some("custom/index.json.zst"));
+    assert!(!config.rules.sql_injection_go.enabled);
+    assert_eq!(config.rules.sql_injection_go.severity, Severity::Low);
+    assert_eq!(config.rules.http_timeouts_go.severity, Severity::High);
+    assert_eq!(
+        messages(provenance.deprecations()),
+        [
+            "index_path is deprecated, use [index] path",
+            "rules.sql_injection_go is deprecated, use [rules.sql-injection-go]",
+            "rules.http-timeouts is deprecated, use [rules.http-timeouts-go]",
+        ]
+    );
+}
+
+#[test]
+fn current_key_wins_over_deprecated_one() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("reviewlens.toml");
+    fs::write(
+        &repo,
+        "index-path = \"old.json.zst\"\n\n[index]\npath = \"new.json.zst\"\n",
+    )
+    .unwrap();
+
+    let (config, provenance) = Config::load_layered(None, &repo).unwrap();
+    assert_eq!(config.index_path(), Some("new.json.zst"));
+    assert_eq!(
+        messages(provenance.deprecations()),
+        ["index-path is deprecated, use [index] path (ignored because it is already set)"]
+    );
+}
+
+#[test]
+fn migrate_moves_the_deprecated_index_field() {
+    #[allow(deprecated)]
+    let mut config = Config {
+        index: None,
+        index_path: Some("legacy.json.zst".into()),
+        ..Config::default()
+    };
+    let deprecations = config.migrate();
+    assert_eq!(deprecations.len(), 1);
+    assert_eq!(config.index.unwrap().path, "legacy.json.zst");
+    assert!(Config::default().migrate().is_empty());
+}
+
+#[test]
+fn deprecated_keys_are_not_reported_as_unknown() {
+    assert!(unknown_keys(OLD).is_empty(), "{:?}", unknown_keys(OLD));
+}
+
+#[test]
+fn rewritten_file_keeps_comments_and_layout() {
+    let (migrated, deprecations) = migrate_document(OLD).unwrap();
+    assert_eq!(deprecations.len(), 3);
+    assert_eq!(
+        migrated,
+        r#"[llm]
+provider = "null"
+
+# Legacy rule names
+[rules.sql-injection-go]
+enabled = false
+severity = "low"
+
+[rules]
+http-timeouts-go = { enabled = true, severity = "high" }
+
+[index]
+# Rebuilt nightly
+path = "custom/index.json.zst" # by CI
+"#
+    );
+    assert!(migrate_document(&migrated).unwrap().1.is_empty());
+}
+
+#[test]
+fn convention_deviation_is_an_alias_for_conventions() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("reviewlens.toml");
+    fs::write(
+        &repo,
+        "[rules.convention-deviation]\nseverity = \"medium\"\nmode = \"strict\"\n",
+    )
+    .unwrap();
+
+    let (config, provenance) = Config::load_layered(None, &repo).unwrap();
+    assert_eq!(config.rules.conventions.severity, Severity::Medium);
+    assert_eq!(config.rules.conventions.mode, ConventionsMode::Strict);
+    assert_eq!(
+        messages(provenance.deprecations()),
+        ["rules.convention-deviation is deprecated, use [rules.conventions]"]
+    );
+}
@@ -0,0 +1,109 @@
+use engine::config::{unknown_keys, Config, Provider};
+
+#[test]
+fn typo_in_rule_name_is_reported_with_suggestion() {
+    let problems = unknown_keys("[rules.secret]\nenabled = false\n");
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].path, "rules.secret");
+    assert!(problems[0].message.contains("did you mean `secrets`?"));
+}
+
+#[test]
+fn unknown_key_without_close_match_has_no_suggestion() {
+    let problems = unknown_keys("[llm]\nprovider = \"null\"\nzzzzzzzz = 1\n");
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].path, "llm.zzzzzzzz");
+    assert_eq!(problems[0].message, "unknown key");
+}
+
+#[test]
+fn optional_keys_are_known() {
+    let toml = "index-path = \"x\"\n[llm]\nmodel = \"m\"\napi-key = \"k\"\nbase-url = \"u\"\n";
+    assert!(unknown_keys(toml).is_empty());
+    let toml = "[generation]\ntop-p = 0.9\nseed = 1\nstop = [\"END\"]\n";
+    assert!(unknown_keys(toml).is_empty());
+}
+
+#[test]
+fn out_of_range_top_p_is_reported() {
+    let mut config = Config::default();
+    config.generation.top_p = Some(1.5);
+    let problems = config.validate();
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].path, "generation.top-p");
+}
+
+#[test]
+fn invalid_redaction_regex_is_reported() {
+    let mut config = Config::default();
+    config.privacy.redaction.patterns = vec!["ok".into(), "(unclosed".into()];
+    let problems = config.validate();
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].path, "privacy.redaction.patterns[1]");
+    assert!(problems[0].message.contains("invalid regex"));
+}
+
+#[test]
+fn missing_model_for_openai_is_reported_with_other_problems() {
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.api_key = Some("key".into());
+    config.paths.deny = vec!["a/{b".into()];
+    let problems = config.validate();
+    let paths: Vec<&str> = problems.iter().map(|p| p.path.as_str()).collect();
+    assert_eq!(paths, vec!["paths.deny[0]", "llm.model"]);
+}
+
+#[test]
+fn default_config_is_valid() {
+    assert!(Config::default().validate().is_empty());
+}
+
+fn path_config(allow: &[&str], deny: &[&str]) -> Config {
+    let mut config = Config::default();
+    config.paths.allow = allow.iter().map(|p| p.to_string()).collect();
+    config.paths.deny = deny.iter().map(|p| p.to_string()).collect();
+    config
+}
+
+#[test]
+fn allow_patterns_shadowed_by_deny_are_reported() {
+    let config = path_config(&["src/**/*.rs", "docs/*.md"], &["src/**"]);
+    let warnings = config.path_warnings(None);
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert_eq!(warnings[0].path, "paths.allow[0]");
+    assert!(warnings[0].message.contains("denied by `src/**`"));
+
+    // Covered only by the deny patterns together.
+    let config = path_config(&["{src,lib}/*.rs"], &["src/**", "lib/**"]);
+    let warnings = config.path_warnings(None);
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert!(warnings[0].message.contains("denied by `paths.deny`"));
+
+    let config = path_config(&["**/*.rs"], &["vendor/**", "**/*_test.rs"]);
+    assert!(config.path_warnings(None).is_empty());
+    assert!(Config::default().path_warnings(None).is_empty());
+}
+
+#[cfg(not(windows))]
+#[test]
+fn backslashes_in_path_patterns_are_reported() {
+    let config = path_config(&["**/*"], &["vendor\\**"]);
+    let warnings = config.path_warnings(None);
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert_eq!(warnings[0].path, "paths.deny[0]");
+    assert!(warnings[0].message.contains("backslash"));
+}
+
+#[test]
+fn patterns_matching_no_files_are_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+
+    let config = path_config(&["src/**/*.rs", "app/**/*.py"], &["vendor/**"]);
+    let warnings = config.path_warnings(Some(dir.path()));
+    let paths: Vec<&str> = warnings.iter().map(|w| w.path.as_str()).collect();
+    assert_eq!(paths, vec!["paths.allow[1]", "paths.deny[0]"]);
+    assert!(warnings[0].message.contains("matches no files"));
+}
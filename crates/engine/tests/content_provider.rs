@@ -0,0 +1,174 @@
+use engine::config::Config;
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+#[tokio::test]
+async fn reviews_in_memory_contents_without_filesystem() {
+    let line = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+    let diff = format!(
+        "diff --git a/src/secret.rs b/src/secret.rs\n--- a/src/secret.rs\n+++ b/src/secret.rs\n@@ -0,0 +1 @@\n+{}\n",
+        line
+    );
+    let mut files = HashMap::new();
+    files.insert("src/secret.rs".to_string(), format!("{}\n", line));
+
+    let engine = ReviewEngine::new(Config::default()).unwrap();
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].file_path, "src/secret.rs");
+    assert_eq!(report.issues[0].line_number, 1);
+}
+
+#[tokio::test]
+async fn missing_in_memory_file_is_skipped() {
+    let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -0,0 +1 @@\n+fn a() {}\n";
+    let files: HashMap<String, String> = HashMap::new();
+    let engine = ReviewEngine::new(Config::default()).unwrap();
+    let report = engine.run_with_provider(diff, &files).await.unwrap();
+    assert_eq!(report.stats.files_reviewed, 0);
+    assert_eq!(report.stats.unreadable.len(), 1);
+}
+
+#[tokio::test]
+async fn deleted_file_is_skipped_and_the_rest_reviewed() {
+    let secret = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+    let diff = format!(
+        "diff --git a/gone.rs b/gone.rs\n--- a/gone.rs\n+++ b/gone.rs\n@@ -0,0 +1 @@\n+fn a() {{}}\n\
+         diff --git a/secret.rs b/secret.rs\n--- a/secret.rs\n+++ b/secret.rs\n@@ -0,0 +1 @@\n+{}\n",
+        secret
+    );
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("secret.rs"), format!("{}\n", secret)).unwrap();
+
+    let engine = ReviewEngine::new(Config::default())
+        .unwrap()
+        .with_root(dir.path());
+    let report = engine.run(&diff).await.unwrap();
+
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].file_path, "secret.rs");
+    assert_eq!(report.stats.files_reviewed, 1);
+    assert_eq!(report.stats.files_skipped, 1);
+    assert_eq!(report.stats.unreadable[0].path, "gone.rs");
+    assert_eq!(report.stats.unreadable[0].reason, "the file does not exist");
+    assert!(report.notes.iter().any(|note| note.contains("gone.rs")));
+}
+
+#[tokio::test]
+async fn deleted_file_is_not_read_or_scanned() {
+    let diff = "diff --git a/old.py b/old.py\ndeleted file mode 100644\nindex 3b18e51..0000000\n--- a/old.py\n+++ /dev/null\n@@ -1,2 +0,0 @@\n-import os\n-api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"\n";
+    let dir = tempfile::tempdir().unwrap();
+
+    let engine = ReviewEngine::new(Config::default())
+        .unwrap()
+        .with_root(dir.path());
+    let report = engine.run(diff).await.unwrap();
+
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+    assert_eq!(report.stats.files_reviewed, 0);
+    assert_eq!(report.stats.files_skipped, 1);
+    assert!(report.stats.unreadable.is_empty());
+    assert!(report.hotspots.iter().all(|h| !h.contains("/dev/null")));
+}
+
+#[tokio::test]
+async fn invalid_utf8_is_reviewed_lossily() {
+    let diff = "diff --git a/legacy.rs b/legacy.rs\n--- a/legacy.rs\n+++ b/legacy.rs\n@@ -0,0 +1,2 @@\n+// caf\u{e9}\n+api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"\n";
+    let dir = tempfile::tempdir().unwrap();
+    // Latin-1 encoded "café", which is not valid UTF-8.
+    let mut bytes = b"// caf\xe9\n".to_vec();
+    bytes.extend_from_slice(b"api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"\n");
+    std::fs::write(dir.path().join("legacy.rs"), bytes).unwrap();
+
+    let provider = engine::content::FsContentProvider::new(dir.path());
+    use engine::content::FileContentProvider;
+    assert!(provider
+        .read("legacy.rs")
+        .unwrap()
+        .starts_with("// caf\u{fffd}"));
+
+    let engine = ReviewEngine::new(Config::default())
+        .unwrap()
+        .with_root(dir.path());
+    let report = engine.run(diff).await.unwrap();
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].line_number, 2);
+    assert!(report.stats.unreadable.is_empty());
+}
+
+#[test]
+fn diff_paths_are_joined_with_native_separators() {
+    use engine::content::{native_path, FileContentProvider, FsContentProvider};
+    use std::path::Path;
+
+    let root = Path::new("repo");
+    let expected = root.join("src").join("lib.rs");
+    assert_eq!(native_path(root, "src/lib.rs").unwrap(), expected);
+    assert_eq!(native_path(root, "src\\lib.rs").unwrap(), expected);
+    assert_eq!(native_path(root, "./src//lib.rs").unwrap(), expected);
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src").join("lib.rs"), "fn a() {}\n").unwrap();
+    let provider = FsContentProvider::new(dir.path());
+    assert_eq!(provider.read("src\\lib.rs").unwrap(), "fn a() {}\n");
+    assert!(provider.modified("src/lib.rs").is_some());
+}
+
+#[test]
+fn paths_outside_the_root_are_refused() {
+    use engine::content::{native_path, FileContentProvider, FsContentProvider};
+    use engine::error::EngineError;
+    use std::path::Path;
+
+    for path in ["/etc/passwd", "../x", "src/../../x", "..\\x"] {
+        let err = native_path(Path::new("repo"), path).unwrap_err();
+        assert!(matches!(err, EngineError::Config(_)), "{}: {:?}", path, err);
+    }
+
+    let parent = tempfile::tempdir().unwrap();
+    let root = parent.path().join("repo");
+    std::fs::create_dir(&root).unwrap();
+    std::fs::write(parent.path().join("x"), "outside\n").unwrap();
+    let provider = FsContentProvider::new(&root);
+    assert!(provider.read("../x").is_err());
+    assert!(provider.read("/etc/passwd").is_err());
+    assert!(provider.size("../x").is_none());
+}
+
+#[test]
+fn concurrent_runs_read_from_their_own_roots() {
+    let secret = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"\n";
+    let clean = "let greeting = \"hello\";\n";
+    let repo = |content: &str| {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), content).unwrap();
+        dir
+    };
+    let (first, second) = (repo(secret), repo(clean));
+    let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -0,0 +1 @@\n+placeholder\n";
+    let cwd = std::env::current_dir().unwrap();
+
+    // Each run gets its own thread and runtime, as two library users would.
+    let review = |root: &std::path::Path| {
+        let engine = ReviewEngine::new(Config::default())
+            .unwrap()
+            .with_root(root);
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(engine.run(diff))
+            .unwrap()
+    };
+    let (a, b) = std::thread::scope(|scope| {
+        let a = scope.spawn(|| review(first.path()));
+        let b = scope.spawn(|| review(second.path()));
+        (a.join().unwrap(), b.join().unwrap())
+    });
+
+    assert_eq!(a.issues.len(), 1);
+    assert!(b.issues.is_empty(), "{:?}", b.issues);
+    assert_eq!(std::env::current_dir().unwrap(), cwd);
+}
@@ -0,0 +1,130 @@
+use engine::config::{Config, ConventionsMode, IndexConfig};
+use engine::rag::{Document, InMemoryVectorStore, Language};
+use engine::scanner::{ConventionsScanner, Scanner};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::file_diff;
+
+const MAIN: &str = "fn main() {\n    println!(\"hi\");\n    let _ = foo().unwrap();\n}\n";
+
+fn rust_doc(filename: &str) -> Document {
+    Document {
+        filename: filename.into(),
+        content: String::new(),
+        embedding: vec![],
+        language: Some(Language::Rust),
+        function_signatures: vec![],
+        log_patterns: vec!["log::info!(\"hi\")".into()],
+        error_snippets: vec!["Result<()>".into()],
+        modified: 0,
+    }
+}
+
+/// Saves an index holding one Rust document and returns a configuration
+/// pointing at it.
+fn saved_index(dir: &Path) -> Config {
+    let mut store = InMemoryVectorStore::default();
+    store.push_document(rust_doc("lib.rs"));
+    let index_path = dir.join("index.json.zst");
+    store.save_to_disk(&index_path).unwrap();
+    Config {
+        index: Some(IndexConfig {
+            path: index_path.to_string_lossy().into(),
+            ..Default::default()
+        }),
+        ..Config::default()
+    }
+}
+
+fn diff(files: &HashMap<String, String>) -> String {
+    let mut diff = String::new();
+    let mut paths: Vec<&String> = files.keys().collect();
+    paths.sort();
+    for path in paths {
+        diff.push_str(&file_diff(path, &files[path]));
+    }
+    diff
+}
+
+#[test]
+fn baselines_are_derived_from_documents() {
+    let config = Config::default();
+    let scanner = ConventionsScanner::from_documents(&[rust_doc("lib.rs")]);
+    let issues = scanner.scan("src/main.rs", MAIN, &config).unwrap();
+    let lines: Vec<usize> = issues.iter().map(|i| i.line_number).collect();
+    assert_eq!(lines, vec![2, 3]);
+
+    let scanner = ConventionsScanner::from_documents(&[]);
+    assert!(scanner
+        .scan("src/main.rs", MAIN, &config)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn strict_mode_requires_every_file_to_follow_the_convention() {
+    let mut mixed = rust_doc("b.rs");
+    mixed.log_patterns.push("println!(\"debug\")".into());
+    let docs = [rust_doc("a.rs"), rust_doc("c.rs"), mixed];
+    let scanner = ConventionsScanner::from_documents(&docs);
+
+    let mut config = Config::default();
+    let lines = |config: &Config| -> Vec<usize> {
+        let issues = scanner.scan("src/main.rs", MAIN, config).unwrap();
+        issues.iter().map(|i| i.line_number).collect()
+    };
+    assert_eq!(lines(&config), vec![2, 3]);
+    config.rules.conventions.mode = ConventionsMode::Strict;
+    assert_eq!(lines(&config), vec![3]);
+}
+
+#[test]
+fn checks_can_be_turned_off() {
+    let scanner = ConventionsScanner::from_documents(&[rust_doc("lib.rs")]);
+    let mut config = Config::default();
+    config.rules.conventions.logging = false;
+    let issues = scanner.scan("src/main.rs", MAIN, &config).unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].title, "Avoid unwrap/expect");
+
+    config.rules.conventions.error_handling = false;
+    assert!(scanner
+        .scan("src/main.rs", MAIN, &config)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn scanner_reads_the_index_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = saved_index(dir.path());
+    let scanner = ConventionsScanner::default();
+    assert_eq!(scanner.scan("a.rs", MAIN, &config).unwrap().len(), 2);
+
+    fs::remove_file(config.index_path().unwrap()).unwrap();
+    assert_eq!(scanner.scan("b.rs", MAIN, &config).unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn engine_reads_the_index_once_per_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = saved_index(dir.path());
+    let engine = ReviewEngine::new(config.clone()).unwrap();
+    fs::remove_file(config.index_path().unwrap()).unwrap();
+
+    let files: HashMap<String, String> = ["src/a.rs", "src/b.rs", "src/c.rs"]
+        .into_iter()
+        .map(|path| (path.to_string(), MAIN.to_string()))
+        .collect();
+    let report = engine
+        .run_with_provider(&diff(&files), &files)
+        .await
+        .unwrap();
+    // Convention deviations are reported as code quality notes.
+    assert_eq!(report.code_quality.len(), 6, "{:?}", report.code_quality);
+    assert!(report.code_quality[5].starts_with("src/c.rs:3 - "));
+}
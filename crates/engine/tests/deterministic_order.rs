@@ -0,0 +1,97 @@
+use engine::config::{apply_ci_mode, Config};
+use engine::report::{JsonGenerator, ReportGenerator, ReviewReport};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+mod common;
+use common::file_diff;
+
+const SECRET: &str = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+const SQL: &str = "db.Query(\"SELECT * FROM users WHERE id = \" + id)";
+const HTTP: &str = "http.Get(url)";
+
+fn files() -> Vec<(&'static str, Vec<&'static str>)> {
+    vec![
+        ("src/main.go", vec![HTTP, SQL, SECRET]),
+        ("lib/config.py", vec![SECRET, "x = 1", SECRET]),
+        ("lib/db.go", vec![SQL, HTTP]),
+    ]
+}
+
+async fn review(files: &[(&str, Vec<&str>)]) -> ReviewReport {
+    review_with(Config::default(), files).await
+}
+
+async fn review_with(config: Config, files: &[(&str, Vec<&str>)]) -> ReviewReport {
+    let diff: String = files
+        .iter()
+        .map(|(path, lines)| file_diff(path, &lines.join("\n")))
+        .collect();
+    let contents: HashMap<String, String> = files
+        .iter()
+        .map(|(path, lines)| (path.to_string(), lines.join("\n")))
+        .collect();
+    let engine = ReviewEngine::new(config).unwrap();
+    let mut report = engine.run_with_provider(&diff, &contents).await.unwrap();
+    report.metadata.timings.total_ms = 0;
+    report
+}
+
+#[tokio::test]
+async fn identical_runs_produce_identical_reports() {
+    let first = JsonGenerator.generate(&review(&files()).await).unwrap();
+    let second = JsonGenerator.generate(&review(&files()).await).unwrap();
+    assert_eq!(first, second);
+}
+
+#[tokio::test]
+async fn issues_are_ordered_by_severity_path_line_and_rule() {
+    let report = review(&files()).await;
+    let order: Vec<(String, usize)> = report
+        .issues
+        .iter()
+        .map(|issue| (issue.file_path.clone(), issue.line_number))
+        .collect();
+    let expected = [
+        ("lib/db.go", 1),
+        ("src/main.go", 2),
+        ("lib/config.py", 1),
+        ("lib/config.py", 3),
+        ("src/main.go", 3),
+        ("lib/db.go", 2),
+        ("src/main.go", 1),
+    ];
+    assert_eq!(
+        order,
+        expected
+            .iter()
+            .map(|(path, line)| (path.to_string(), *line))
+            .collect::<Vec<_>>()
+    );
+
+    let mut reversed = files();
+    reversed.reverse();
+    let issues = |report: &ReviewReport| {
+        report
+            .issues
+            .iter()
+            .map(|issue| issue.fingerprint.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(issues(&review(&reversed).await), issues(&report));
+}
+
+#[tokio::test]
+async fn ci_mode_keeps_the_same_order() {
+    let mut config = Config::default();
+    apply_ci_mode(&mut config).unwrap();
+    let key = |report: &ReviewReport| {
+        report
+            .issues
+            .iter()
+            .map(|issue| (issue.severity.clone(), issue.rule_id.clone()))
+            .collect::<Vec<_>>()
+    };
+    let ci = review_with(config, &files()).await;
+    assert_eq!(key(&ci), key(&review(&files()).await));
+}
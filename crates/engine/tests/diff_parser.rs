@@ -26,6 +26,7 @@ fn parse_simple_unified_diff() {
     let file = &files[0];
     assert_eq!(file.path, "foo.txt");
     assert_eq!(file.hunks.len(), 1);
+    assert!(!file.added);
 
     let hunk = &file.hunks[0];
     assert_eq!(hunk.old_start, 1);
@@ -68,6 +69,28 @@ Binary files /dev/null and b/image.png differ
     let file = &files[0];
     assert_eq!(file.path, "image.png");
     assert!(file.hunks.is_empty());
+    assert!(file.added);
+}
+
+#[test]
+fn parse_deleted_file_keeps_old_path() {
+    let diff = r#"diff --git a/auth.go b/auth.go
+deleted file mode 100644
+index 3b18e51..0000000
+--- a/auth.go
++++ /dev/null
+@@ -1,2 +0,0 @@
+-package auth
+-func Check() {}
+"#;
+
+    let files = diff_parser::parse(diff).expect("should parse");
+    assert_eq!(files.len(), 1);
+    let file = &files[0];
+    assert_eq!(file.path, "auth.go");
+    assert!(file.deleted);
+    assert!(!file.added);
+    assert_eq!(file.deletions(), 2);
 }
 
 #[test]
@@ -111,3 +134,188 @@ fn parse_multiple_hunks() {
     assert!(matches!(h2.lines[2], Line::Added(ref l) if l == "line5mod"));
     assert!(matches!(h2.lines[3], Line::Added(ref l) if l == "line6"));
 }
+
+#[test]
+fn counts_and_line_numbers_for_interleaved_changes() {
+    let diff = r#"diff --git a/foo.txt b/foo.txt
+--- a/foo.txt
++++ b/foo.txt
+@@ -3,6 +3,6 @@
+ keep1
+-old1
++new1
+ keep2
+-old2
+-old3
++new2
+ keep3
+@@ -20,2 +20,3 @@
+ tail
++extra
+ end
+"#;
+
+    let files = diff_parser::parse(diff).expect("should parse");
+    let file = &files[0];
+    assert_eq!(file.additions(), 3);
+    assert_eq!(file.deletions(), 3);
+
+    let hunk = &file.hunks[0];
+    assert_eq!((hunk.additions(), hunk.deletions()), (2, 3));
+    assert_eq!(hunk.removed_line_numbers(), vec![4, 6, 7]);
+    assert_eq!(hunk.removed_lines()[1], (6, "old2"));
+    assert_eq!(hunk.added_line_numbers(), vec![4, 6]);
+
+    let mut changed: Vec<usize> = diff_parser::changed_new_lines(file).into_iter().collect();
+    changed.sort_unstable();
+    assert_eq!(changed, vec![4, 6, 21]);
+}
+
+#[test]
+fn no_newline_marker_does_not_shift_line_numbers() {
+    let diff = r#"diff --git a/foo.txt b/foo.txt
+--- a/foo.txt
++++ b/foo.txt
+@@ -1,2 +1,2 @@
+ first
+-last
+\ No newline at end of file
++last
+"#;
+
+    let files = diff_parser::parse(diff).expect("should parse");
+    let file = &files[0];
+    assert_eq!((file.additions(), file.deletions()), (1, 1));
+    assert_eq!(file.hunks[0].removed_line_numbers(), vec![2]);
+    let changed: Vec<usize> = diff_parser::changed_new_lines(file).into_iter().collect();
+    assert_eq!(changed, vec![2]);
+}
+
+const TWO_HUNKS: &str = r#"diff --git a/foo.rs b/foo.rs
+--- a/foo.rs
++++ b/foo.rs
+@@ -1,3 +1,3 @@
+ one
+-two
++TWO
+ three
+@@ -10,2 +10,3 @@
+ ten
++ten and a half
+ eleven
+"#;
+
+#[test]
+fn line_mapper_positions_added_and_context_lines() {
+    use engine::diff_parser::{DiffPosition, LineMapper};
+
+    let files = diff_parser::parse(TWO_HUNKS).expect("should parse");
+    let mapper = LineMapper::new(&files);
+
+    // A context line keeps its old-file line number.
+    assert_eq!(
+        mapper.position_in_diff("foo.rs", 1),
+        Some(DiffPosition {
+            hunk: 0,
+            offset: 1,
+            old_line: Some(1),
+        })
+    );
+    // The added line follows the removed one within the hunk.
+    assert_eq!(
+        mapper.position_in_diff("foo.rs", 2),
+        Some(DiffPosition {
+            hunk: 0,
+            offset: 3,
+            old_line: None,
+        })
+    );
+    assert_eq!(
+        mapper.position_in_diff("foo.rs", 3),
+        Some(DiffPosition {
+            hunk: 0,
+            offset: 4,
+            old_line: Some(3),
+        })
+    );
+    assert!(mapper.is_commentable("foo.rs", 3));
+
+    // Lines of the second hunk are indexed from its own header.
+    assert_eq!(
+        mapper.position_in_diff("foo.rs", 11),
+        Some(DiffPosition {
+            hunk: 1,
+            offset: 2,
+            old_line: None,
+        })
+    );
+    assert_eq!(
+        mapper.position_in_diff("foo.rs", 12),
+        Some(DiffPosition {
+            hunk: 1,
+            offset: 3,
+            old_line: Some(11),
+        })
+    );
+
+    // Lines between or outside hunks, and other files, have no position.
+    assert_eq!(mapper.position_in_diff("foo.rs", 5), None);
+    assert!(!mapper.is_commentable("foo.rs", 13));
+    assert!(!mapper.is_commentable("bar.rs", 1));
+}
+
+#[tokio::test]
+async fn engine_attaches_diff_positions_to_findings() {
+    use engine::config::Config;
+    use engine::diff_parser::DiffPosition;
+    use std::collections::HashMap;
+
+    let diff = "diff --git a/client.go b/client.go\n--- a/client.go\n+++ b/client.go\n@@ -1,2 +1,3 @@\n package main\n+var resp, err = http.Get(url)\n func main() {}\n";
+    let mut files = HashMap::new();
+    files.insert(
+        "client.go".to_string(),
+        "package main\nvar resp, err = http.Get(url)\nfunc main() {}\n".to_string(),
+    );
+    let report = engine::ReviewEngine::new(Config::default())
+        .unwrap()
+        .run_with_provider(diff, &files)
+        .await
+        .unwrap();
+    let issue = report
+        .issues
+        .iter()
+        .find(|issue| issue.rule_id == "http-timeouts-go")
+        .unwrap();
+    assert_eq!(
+        issue.diff_position,
+        Some(DiffPosition {
+            hunk: 0,
+            offset: 2,
+            old_line: None,
+        })
+    );
+}
+
+#[test]
+fn windows_paths_are_normalized_to_forward_slashes() {
+    let diff = "diff --git a\\src\\lib.rs b\\src\\lib.rs\n\
+                --- a\\src\\lib.rs\n\
+                +++ b\\src\\lib.rs\n\
+                @@ -1 +1 @@\n\
+                -old\n\
+                +new\n\
+                diff --git a\\assets\\logo.png b\\assets\\logo.png\n\
+                Binary files a\\assets\\logo.png and b\\assets\\logo.png differ\n";
+
+    let files = diff_parser::parse(diff).expect("should parse");
+    let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(paths, vec!["src/lib.rs", "assets/logo.png"]);
+    assert_eq!(files[0].hunks.len(), 1);
+}
+
+#[test]
+fn normalize_path_uses_forward_slashes() {
+    assert_eq!(diff_parser::normalize_path("src\\a\\b.rs"), "src/a/b.rs");
+    assert_eq!(diff_parser::normalize_path("src/a/b.rs"), "src/a/b.rs");
+    assert_eq!(diff_parser::normalize_path("b.rs"), "b.rs");
+}
@@ -0,0 +1,80 @@
+use engine::config::Config;
+use engine::{ReviewEngine, DOCS_ONLY_SUMMARY};
+use std::collections::HashMap;
+
+const SECRET: &str = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+
+fn diff_for(paths: &[&str]) -> String {
+    paths
+        .iter()
+        .flat_map(|path| {
+            [
+                format!("diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n", path),
+                format!("@@ -0,0 +1 @@\n+{}\n", SECRET),
+            ]
+        })
+        .collect()
+}
+
+fn files_for(paths: &[&str]) -> HashMap<String, String> {
+    paths
+        .iter()
+        .map(|path| (path.to_string(), format!("{}\n", SECRET)))
+        .collect()
+}
+
+#[tokio::test]
+async fn docs_only_change_skips_analysis() {
+    let paths = [
+        "CHANGELOG.md",
+        "docs/notes.txt",
+        "LICENSE-MIT",
+        "Cargo.lock",
+    ];
+    let engine = ReviewEngine::new(Config::default()).unwrap();
+    let report = engine
+        .run_with_provider(&diff_for(&paths), &files_for(&paths))
+        .await
+        .unwrap();
+
+    assert!(report.metadata.docs_only);
+    assert_eq!(report.summary, DOCS_ONLY_SUMMARY);
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+    assert!(report.hotspots.is_empty());
+    assert_eq!(report.stats.tokens_used, 0);
+}
+
+#[tokio::test]
+async fn mixed_change_is_analyzed() {
+    let paths = ["README.md", "src/config.rs"];
+    let engine = ReviewEngine::new(Config::default()).unwrap();
+    let report = engine
+        .run_with_provider(&diff_for(&paths), &files_for(&paths))
+        .await
+        .unwrap();
+
+    assert!(!report.metadata.docs_only);
+    assert_ne!(report.summary, DOCS_ONLY_SUMMARY);
+    let mut flagged: Vec<&str> = report
+        .issues
+        .iter()
+        .map(|issue| issue.file_path.as_str())
+        .collect();
+    flagged.sort();
+    assert_eq!(flagged, ["README.md", "src/config.rs"]);
+}
+
+#[tokio::test]
+async fn empty_docs_globs_force_a_full_review() {
+    let paths = ["README.md"];
+    let mut config = Config::default();
+    config.paths.docs_globs.clear();
+    let engine = ReviewEngine::new(config).unwrap();
+    let report = engine
+        .run_with_provider(&diff_for(&paths), &files_for(&paths))
+        .await
+        .unwrap();
+
+    assert!(!report.metadata.docs_only);
+    assert_eq!(report.issues.len(), 1);
+}
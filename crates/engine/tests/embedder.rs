@@ -0,0 +1,170 @@
+use engine::config::{Config, EmbedderKind};
+use engine::error::EngineError;
+use engine::llm::Timeouts;
+use engine::rag::{
+    create_embedder, index_repository_with, Embedder, InMemoryVectorStore, IndexOptions,
+    NgramEmbedder, OpenAiEmbedder, RagContextRetriever,
+};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Serves OpenAI-style embeddings, returning `[len(input), 1, 0]` for each
+/// input in reverse order. Records the request paths it receives.
+fn fake_embeddings() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!(
+        "http://{}/v1/chat/completions",
+        listener.local_addr().unwrap()
+    );
+    let paths = Arc::new(Mutex::new(Vec::new()));
+    let seen = paths.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let path = request_line.split_whitespace().nth(1).unwrap().to_string();
+            seen.lock().unwrap().push(path);
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = v.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let request: Value = serde_json::from_slice(&body).unwrap();
+            let data: Vec<Value> = request["input"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, text)| {
+                    let len = text.as_str().unwrap().len() as f32;
+                    json!({"object": "embedding", "index": i, "embedding": [len, 1.0, 0.0]})
+                })
+                .collect();
+            let json = json!({"object": "list", "data": data}).to_string();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                json.len(),
+                json
+            )
+            .unwrap();
+        }
+    });
+    (base, paths)
+}
+
+fn openai_embedder(base_url: &str) -> OpenAiEmbedder {
+    OpenAiEmbedder::new(
+        "key".into(),
+        "text-embedding-3-small".into(),
+        Some(base_url),
+        Timeouts {
+            request: Duration::from_secs(5),
+            connect: Duration::from_secs(5),
+        },
+    )
+}
+
+#[tokio::test]
+async fn openai_embedder_batches_in_input_order() {
+    let (url, paths) = fake_embeddings();
+    let embedder = openai_embedder(&url);
+    assert_eq!(embedder.name(), "openai/text-embedding-3-small");
+
+    let texts = vec!["a".to_string(), "bbb".to_string(), "cc".to_string()];
+    let embeddings = embedder.embed_batch(&texts).await.unwrap();
+    assert_eq!(
+        embeddings,
+        vec![
+            vec![1.0, 1.0, 0.0],
+            vec![3.0, 1.0, 0.0],
+            vec![2.0, 1.0, 0.0]
+        ]
+    );
+    // The chat completions base URL is rewritten to the embeddings endpoint.
+    assert_eq!(*paths.lock().unwrap(), vec!["/v1/embeddings".to_string()]);
+}
+
+#[tokio::test]
+async fn index_records_embedder_and_dimension() {
+    let (url, _) = fake_embeddings();
+    let repo = tempfile::tempdir().unwrap();
+    fs::write(repo.path().join("a.rs"), "fn a() {}").unwrap();
+    fs::write(repo.path().join("b.rs"), "fn b() { a() }").unwrap();
+    let output = repo.path().join(".reviewlens/index.json.zst");
+    let options = IndexOptions {
+        embedder: Arc::new(openai_embedder(&url)),
+        ..IndexOptions::default()
+    };
+
+    index_repository_with(repo.path(), &output, true, &["*.rs".into()], &[], &options)
+        .await
+        .unwrap();
+    let store = InMemoryVectorStore::load_from_disk(&output).unwrap();
+    assert_eq!(store.embedder_name(), "openai/text-embedding-3-small");
+    assert_eq!(store.dimension(), Some(3));
+    assert_eq!(store.documents()[1].embedding, vec![14.0, 1.0, 0.0]);
+
+    let rag = RagContextRetriever::for_index(store, Arc::new(openai_embedder(&url))).unwrap();
+    let context = rag.retrieve("fn a").await.unwrap();
+    assert!(context.iter().any(|doc| doc.filename == "a.rs"));
+}
+
+#[tokio::test]
+async fn retrieval_refuses_a_mismatched_embedder() {
+    let (url, _) = fake_embeddings();
+    let repo = tempfile::tempdir().unwrap();
+    fs::write(repo.path().join("a.rs"), "fn a() {}").unwrap();
+    let output = repo.path().join("index.json.zst");
+    let options = IndexOptions {
+        embedder: Arc::new(openai_embedder(&url)),
+        ..IndexOptions::default()
+    };
+    let store = index_repository_with(repo.path(), &output, true, &["*.rs".into()], &[], &options)
+        .await
+        .unwrap();
+
+    let err = RagContextRetriever::for_index(store, Arc::new(NgramEmbedder))
+        .err()
+        .expect("mismatched embedder is rejected");
+    assert!(matches!(err, EngineError::Rag(_)));
+    assert!(err.to_string().contains("openai/text-embedding-3-small"));
+    assert!(err.to_string().contains("ngram"));
+}
+
+#[tokio::test]
+async fn legacy_index_is_treated_as_ngram() {
+    let store = InMemoryVectorStore::default();
+    assert_eq!(store.embedder_name(), "ngram");
+    assert!(RagContextRetriever::for_index(store, Arc::new(NgramEmbedder)).is_ok());
+}
+
+#[test]
+fn openai_embedder_requires_an_api_key() {
+    let mut config = Config::default();
+    config.rag.embedder = EmbedderKind::Openai;
+    assert!(matches!(
+        create_embedder(&config).err(),
+        Some(EngineError::Config(_))
+    ));
+    config.llm.api_key = Some("key".into());
+    assert_eq!(
+        create_embedder(&config).unwrap().name(),
+        "openai/text-embedding-3-small"
+    );
+}
@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use engine::config::{Config, Provider, Severity};
+use engine::error::Result;
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::rag::{Document, VectorStore};
+use engine::scanner::{Issue, Scanner};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+struct RecordingProvider(Arc<Mutex<Vec<String>>>);
+
+#[async_trait]
+impl LlmProvider for RecordingProvider {
+    async fn generate(&self, prompt: &str) -> Result<LlmResponse> {
+        self.0.lock().unwrap().push(prompt.to_string());
+        Ok(LlmResponse {
+            content: "summary from gateway".into(),
+            token_usage: 1,
+            cached: false,
+        })
+    }
+}
+
+/// Always returns one fixed document, standing in for an external store.
+struct FixedStore;
+
+#[async_trait]
+impl VectorStore for FixedStore {
+    async fn add(&mut self, _document: Document) -> Result<()> {
+        Ok(())
+    }
+
+    async fn search_scored(
+        &self,
+        _query_embedding: Vec<f32>,
+        _top_k: usize,
+    ) -> Result<Vec<(Document, f32)>> {
+        Ok(vec![(
+            Document {
+                filename: "remote/helpers.go".into(),
+                content: "func quote(s string) string".into(),
+                embedding: vec![],
+                language: None,
+                function_signatures: vec![],
+                log_patterns: vec![],
+                error_snippets: vec![],
+                modified: 0,
+            },
+            0.9,
+        )])
+    }
+}
+
+/// Flags every line containing `TODO`.
+struct TodoScanner;
+
+impl Scanner for TodoScanner {
+    fn name(&self) -> &'static str {
+        "TODO Scanner"
+    }
+
+    fn scan(&self, file_path: &str, content: &str, _config: &Config) -> Result<Vec<Issue>> {
+        Ok(content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.contains("TODO"))
+            .map(|(i, _)| Issue {
+                title: "Unresolved TODO".into(),
+                description: "Resolve the TODO before merging.".into(),
+                file_path: file_path.to_string(),
+                line_number: i + 1,
+                severity: Severity::Low,
+                ..Default::default()
+            })
+            .collect())
+    }
+}
+
+#[tokio::test]
+async fn builder_uses_injected_provider_store_and_scanners() {
+    // No API key: the configured provider would fail to build.
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+
+    let prompts = Arc::new(Mutex::new(Vec::new()));
+    let engine = ReviewEngine::builder(config)
+        .llm(Box::new(RecordingProvider(prompts.clone())))
+        .vector_store(Box::new(FixedStore))
+        .scanners(vec![("todo", Box::new(TodoScanner))])
+        .build()
+        .unwrap();
+
+    let content = "fn main() {\n    // TODO: handle errors\n}\n";
+    let added: String = content.lines().flat_map(|l| ["+", l, "\n"]).collect();
+    let diff = format!(
+        "diff --git a/main.rs b/main.rs\n--- a/main.rs\n+++ b/main.rs\n@@ -0,0 +1,3 @@\n{}",
+        added
+    );
+    let mut files = HashMap::new();
+    files.insert("main.rs".to_string(), content.to_string());
+
+    for _ in 0..2 {
+        let report = engine.run_with_provider(&diff, &files).await.unwrap();
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].title, "Unresolved TODO");
+        assert_eq!(report.summary, "summary from gateway");
+        assert!(report.metadata.index_warm);
+    }
+
+    let prompts = prompts.lock().unwrap();
+    assert_eq!(prompts.len(), 2);
+    assert!(
+        prompts[0].contains("main.rs:2 Unresolved TODO"),
+        "{}",
+        prompts[0]
+    );
+    let context = prompts[0].split("Context:").nth(1).unwrap();
+    assert!(context.contains("remote/helpers.go"), "{}", context);
+}
@@ -1,13 +1,9 @@
 use engine::error::EngineError;
 
 fn map_error_to_exit_code(err: anyhow::Error) -> i32 {
-    if let Some(engine_error) = err.downcast_ref::<EngineError>() {
-        match engine_error {
-            EngineError::Config(_) => 2,
-            _ => 3,
-        }
-    } else {
-        3
+    match err.downcast_ref::<EngineError>() {
+        Some(EngineError::Config(_)) => 2,
+        _ => 3,
     }
 }
 
@@ -20,6 +16,15 @@ fn config_error_returns_exit_code_two() {
 #[test]
 fn runtime_error_returns_exit_code_three() {
     let io_err = std::io::Error::new(std::io::ErrorKind::Other, "io failure");
-    let err: anyhow::Error = EngineError::Io(io_err).into();
+    let err: anyhow::Error = EngineError::from(io_err).into();
     assert_eq!(map_error_to_exit_code(err), 3);
 }
+
+#[test]
+fn io_error_names_the_path() {
+    let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "not found");
+    let err = EngineError::io("reviewlens.toml", io_err);
+    assert_eq!(err.to_string(), "I/O error on reviewlens.toml: not found");
+    let err = EngineError::from(std::io::Error::other("disk full"));
+    assert_eq!(err.to_string(), "I/O error: disk full");
+}
@@ -0,0 +1,192 @@
+#![cfg(unix)]
+
+use engine::config::{Config, ExternalScannerConfig, Severity};
+use engine::scanner::{override_rule, ExternalScanner, Scanner};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+const CONTENT: &str = "first\nsecond\nthird\n";
+
+fn spec(command: &str, args: &[&str]) -> ExternalScannerConfig {
+    ExternalScannerConfig {
+        rule: "canned".into(),
+        command: command.into(),
+        args: args.iter().map(|a| a.to_string()).collect(),
+        enabled: true,
+        severity: Severity::Low,
+        timeout_secs: 5,
+        options: toml::Table::new(),
+    }
+}
+
+fn fixture() -> ExternalScannerConfig {
+    spec(
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/external_scanner.sh"
+        ),
+        &[],
+    )
+}
+
+fn config_with(spec: ExternalScannerConfig) -> Config {
+    let mut config = Config::default();
+    config.scanners.external.push(spec);
+    config
+}
+
+/// Reviews `content` as a newly added `app.py` with only `config`'s scanners.
+async fn review(config: Config, content: &str) -> Vec<engine::scanner::Issue> {
+    let added: String = content.lines().flat_map(|line| ["+", line, "\n"]).collect();
+    let diff = format!(
+        "diff --git a/app.py b/app.py\n--- a/app.py\n+++ b/app.py\n@@ -0,0 +1,{} @@\n{}",
+        content.lines().count(),
+        added
+    );
+    let mut files = HashMap::new();
+    files.insert("app.py".to_string(), content.to_string());
+    let engine = ReviewEngine::new(config).unwrap();
+    engine
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap()
+        .issues
+}
+
+#[tokio::test]
+async fn engine_reports_findings_under_the_declared_rule() {
+    let issues = review(config_with(fixture()), CONTENT).await;
+    assert_eq!(issues.len(), 1);
+    let issue = &issues[0];
+    assert_eq!(issue.rule_id, "canned");
+    assert_eq!(issue.title, "Canned finding");
+    assert_eq!(issue.file_path, "app.py");
+    assert_eq!(issue.line_number, 2);
+    assert_eq!(issue.severity, Severity::Low);
+}
+
+#[tokio::test]
+async fn ignore_directives_and_severity_overrides_apply() {
+    let ignored = "first\nsecond # reviewlens:ignore canned not relevant\nthird\n";
+    let config = config_with(fixture());
+    assert!(review(config.clone(), ignored).await.is_empty());
+
+    let mut config = config;
+    override_rule(&mut config, "canned", Some(Severity::Critical)).unwrap();
+    assert_eq!(
+        review(config.clone(), CONTENT).await[0].severity,
+        Severity::Critical
+    );
+
+    override_rule(&mut config, "canned", None).unwrap();
+    assert!(review(config, CONTENT).await.is_empty());
+}
+
+#[test]
+fn sends_request_as_json_on_stdin() {
+    let dir = tempfile::tempdir().unwrap();
+    let captured = dir.path().join("request.json");
+    let mut spec = spec(
+        "sh",
+        &[
+            "-c",
+            "cat > \"$1\"; echo '[]'",
+            "sh",
+            captured.to_str().unwrap(),
+        ],
+    );
+    spec.options
+        .insert("strict".into(), toml::Value::Boolean(true));
+    let config = config_with(spec.clone());
+    let changed = [3, 1].into_iter().collect();
+    let request = engine::scanner::ScanRequest {
+        path: "app.py",
+        content: CONTENT,
+        config: &config,
+        changed_lines: Some(&changed),
+        hunks: &[],
+    };
+    let issues = ExternalScanner::new(&spec)
+        .scan_with_context(&request)
+        .unwrap();
+    assert!(issues.is_empty());
+
+    let sent: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(captured).unwrap()).unwrap();
+    assert_eq!(sent["rule"], "canned");
+    assert_eq!(sent["path"], "app.py");
+    assert_eq!(sent["content"], CONTENT);
+    assert_eq!(sent["changed_lines"], serde_json::json!([1, 3]));
+    assert_eq!(sent["config"]["severity"], "low");
+    assert_eq!(sent["config"]["options"]["strict"], true);
+}
+
+#[test]
+fn non_zero_exit_surfaces_stderr() {
+    let spec = spec("sh", &["-c", "echo 'rules file missing' >&2; exit 3"]);
+    let err = ExternalScanner::new(&spec)
+        .scan("app.py", CONTENT, &config_with(spec.clone()))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("canned"), "{}", err);
+    assert!(err.contains("rules file missing"), "{}", err);
+}
+
+#[test]
+fn invalid_output_is_an_error() {
+    let spec = spec("sh", &["-c", "echo not json"]);
+    let err = ExternalScanner::new(&spec)
+        .scan("app.py", CONTENT, &config_with(spec.clone()))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("invalid output"), "{}", err);
+}
+
+#[test]
+fn slow_scanner_is_killed_after_timeout() {
+    let mut spec = spec("sh", &["-c", "sleep 10"]);
+    spec.timeout_secs = 1;
+    let started = std::time::Instant::now();
+    let err = ExternalScanner::new(&spec)
+        .scan("app.py", CONTENT, &config_with(spec.clone()))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("timed out after 1s"), "{}", err);
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+}
+
+#[test]
+fn validation_rejects_clashing_rule_ids() {
+    let mut config = config_with(fixture());
+    config.scanners.external.push(fixture());
+    let mut builtin = fixture();
+    builtin.rule = "secrets".into();
+    config.scanners.external.push(builtin);
+    let paths: Vec<String> = config.validate().into_iter().map(|p| p.path).collect();
+    assert_eq!(
+        paths,
+        ["scanners.external[1].rule", "scanners.external[2].rule"]
+    );
+}
+
+#[test]
+fn parses_from_toml() {
+    let config: Config = toml::from_str(
+        r#"
+[[scanners.external]]
+rule = "no-print"
+command = "./scripts/no-print"
+options = { allow-in = ["scripts/"] }
+"#,
+    )
+    .unwrap();
+    let spec = &config.scanners.external[0];
+    assert_eq!(spec.rule, "no-print");
+    assert!(spec.enabled);
+    assert_eq!(spec.severity, Severity::Medium);
+    assert_eq!(spec.timeout_secs, 30);
+    assert!(
+        engine::config::unknown_keys("[[scanners.external]]\nrule = \"x\"\ncommand = \"y\"\n")
+            .is_empty()
+    );
+}
@@ -0,0 +1,64 @@
+use engine::config::{Config, FailOn, Severity};
+use engine::report::{ReviewReport, RuntimeMetadata, TimingInfo};
+use engine::scanner::Issue;
+
+fn issue(severity: Severity) -> Issue {
+    Issue {
+        title: "Test issue".into(),
+        description: "This is a test".into(),
+        file_path: "lib.rs".into(),
+        line_number: 1,
+        severity,
+        ..Default::default()
+    }
+}
+
+fn report_with(issues: Vec<Issue>) -> ReviewReport {
+    ReviewReport {
+        summary: String::new(),
+        issues,
+        code_quality: vec![],
+        hotspots: vec![],
+        mermaid_diagram: None,
+        config: Config::default(),
+        metadata: RuntimeMetadata {
+            ruleset_version: "v1".into(),
+            model: None,
+            driver: "null".into(),
+            timings: TimingInfo {
+                total_ms: 0,
+                ..Default::default()
+            },
+            index_warm: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn never_ignores_all_issues() {
+    let report = report_with(vec![issue(Severity::Critical), issue(Severity::Low)]);
+    assert!(report.failing_issues(&FailOn::Never).is_empty());
+}
+
+#[test]
+fn threshold_exactly_met_fails() {
+    let report = report_with(vec![issue(Severity::High), issue(Severity::Low)]);
+    let failing = report.failing_issues(&FailOn::High);
+    assert_eq!(failing.len(), 1);
+    assert_eq!(failing[0].severity, Severity::High);
+}
+
+#[test]
+fn threshold_not_met_passes() {
+    let report = report_with(vec![issue(Severity::Medium), issue(Severity::Low)]);
+    assert!(report.failing_issues(&FailOn::High).is_empty());
+}
+
+#[test]
+fn fail_on_never_parses_from_config() {
+    let config: Config = toml::from_str("fail-on = \"never\"\n").unwrap();
+    assert_eq!(config.fail_on, FailOn::Never);
+    assert_eq!(Config::default().fail_on, FailOn::High);
+}
@@ -0,0 +1,62 @@
+use engine::config::Config;
+use engine::scanner::Issue;
+use engine::{fingerprint_issue, ReviewEngine};
+use std::collections::HashMap;
+
+const SECRET: &str = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+
+/// Reviews `lines` as a newly added `src/app.rs` and returns its findings.
+async fn review(lines: &[&str]) -> Vec<Issue> {
+    let added: String = lines.iter().flat_map(|line| ["+", line, "\n"]).collect();
+    let diff = format!(
+        "diff --git a/src/app.rs b/src/app.rs\n--- a/src/app.rs\n+++ b/src/app.rs\n@@ -0,0 +1,{} @@\n{}",
+        lines.len(),
+        added
+    );
+    let mut files = HashMap::new();
+    files.insert("src/app.rs".to_string(), format!("{}\n", lines.join("\n")));
+    let engine = ReviewEngine::new(Config::default()).unwrap();
+    engine
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap()
+        .issues
+}
+
+#[tokio::test]
+async fn fingerprint_survives_lines_inserted_above() {
+    let before = review(&["fn main() {}", SECRET]).await;
+    let after = review(&["// header", "use std::fs;", "", "fn main() {}", SECRET]).await;
+    assert_eq!(before.len(), 1);
+    assert_eq!(after.len(), 1);
+    assert_ne!(before[0].line_number, after[0].line_number);
+    assert!(!before[0].fingerprint.is_empty());
+    assert_eq!(before[0].fingerprint, after[0].fingerprint);
+}
+
+#[tokio::test]
+async fn fingerprint_changes_with_flagged_line_content() {
+    let before = review(&[SECRET]).await;
+    let after = review(&["api_key = \"ZYXWVUTSRQPONMLKJIHGFEDCBA\""]).await;
+    assert_eq!(before[0].line_number, after[0].line_number);
+    assert_ne!(before[0].fingerprint, after[0].fingerprint);
+}
+
+#[tokio::test]
+async fn duplicate_lines_get_distinct_fingerprints() {
+    let issues = review(&[SECRET, SECRET]).await;
+    assert_eq!(issues.len(), 2);
+    assert_ne!(issues[0].fingerprint, issues[1].fingerprint);
+}
+
+#[tokio::test]
+async fn helper_recomputes_engine_fingerprint() {
+    let issues = review(&[SECRET]).await;
+    assert_eq!(issues[0].rule_id, "secrets");
+    assert_eq!(fingerprint_issue(&issues[0], SECRET), issues[0].fingerprint);
+    // Indentation is not part of the identity.
+    assert_eq!(
+        fingerprint_issue(&issues[0], &format!("    {}", SECRET)),
+        issues[0].fingerprint
+    );
+}
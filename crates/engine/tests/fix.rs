@@ -0,0 +1,124 @@
+use engine::config::Config;
+use engine::fix::{apply_fixes, FixStatus};
+use engine::scanner::{Fix, HttpTimeoutsGoScanner, Issue, Scanner};
+use std::fs;
+use tempfile::tempdir;
+
+const SOURCE: &str = "func fetch() {\n\tclient := &http.Client{Transport: t}\n}\n";
+
+/// Scans `SOURCE` for an `http.Client` without a timeout.
+fn issues() -> Vec<Issue> {
+    let issues = HttpTimeoutsGoScanner
+        .scan("net.go", SOURCE, &Config::default())
+        .unwrap();
+    assert_eq!(issues.len(), 1);
+    issues
+}
+
+/// An issue on `line` of `app.go` whose fix replaces `find` with `replace`.
+fn issue_with_fix(line: usize, find: &str, replace: &str) -> Issue {
+    Issue {
+        file_path: "app.go".into(),
+        line_number: line,
+        rule_id: "rule".into(),
+        fix: Some(Fix {
+            find: find.into(),
+            replace: replace.into(),
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn client_without_timeout_carries_a_fix() {
+    let issue = &issues()[0];
+    let fix = issue.fix.as_ref().unwrap();
+    assert_eq!(fix.find, "client := &http.Client{Transport: t}");
+    assert_eq!(
+        fix.replace,
+        "client := &http.Client{Timeout: 10 * time.Second, Transport: t}"
+    );
+    assert_eq!(issue.diff.as_deref(), Some(fix.to_diff().as_str()));
+}
+
+#[test]
+fn applies_a_fix_to_the_line_after_it_moved() {
+    let dir = tempdir().unwrap();
+    let drifted = format!("// Package net fetches things.\n\n{}", SOURCE);
+    fs::write(dir.path().join("net.go"), &drifted).unwrap();
+
+    let outcomes = apply_fixes(dir.path(), &issues(), false).unwrap();
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(outcomes[0].status, FixStatus::Applied);
+    assert_eq!(outcomes[0].line, 4);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("net.go")).unwrap(),
+        "// Package net fetches things.\n\nfunc fetch() {\n\tclient := &http.Client{Timeout: 10 * time.Second, Transport: t}\n}\n"
+    );
+}
+
+#[test]
+fn multi_line_fixes_keep_crlf_and_shift_later_lines() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("app.go"),
+        "\ta := 1\r\n\tb := 2\r\n\tc := 3\r\n",
+    )
+    .unwrap();
+    let issues = [
+        issue_with_fix(1, "a := 1", "x := 0\na := 1"),
+        issue_with_fix(3, "c := 3", "c := 4"),
+    ];
+
+    let outcomes = apply_fixes(dir.path(), &issues, false).unwrap();
+    let lines: Vec<usize> = outcomes.iter().map(|outcome| outcome.line).collect();
+    assert_eq!(lines, [1, 4]);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("app.go")).unwrap(),
+        "\tx := 0\r\n\ta := 1\r\n\tb := 2\r\n\tc := 4\r\n"
+    );
+}
+
+#[test]
+fn skips_a_fix_whose_line_changed() {
+    let dir = tempdir().unwrap();
+    let edited = SOURCE.replace("Transport: t", "Transport: t2");
+    fs::write(dir.path().join("net.go"), &edited).unwrap();
+
+    let outcomes = apply_fixes(dir.path(), &issues(), false).unwrap();
+    assert_eq!(
+        outcomes[0].status,
+        FixStatus::Skipped("line no longer matches".into())
+    );
+    assert_eq!(outcomes[0].line, 2);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("net.go")).unwrap(),
+        edited
+    );
+}
+
+#[test]
+fn dry_run_reports_without_writing() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("net.go"), SOURCE).unwrap();
+
+    let outcomes = apply_fixes(dir.path(), &issues(), true).unwrap();
+    assert_eq!(outcomes[0].status, FixStatus::Applied);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("net.go")).unwrap(),
+        SOURCE
+    );
+}
+
+#[test]
+fn skips_paths_outside_the_repository() {
+    let dir = tempdir().unwrap();
+    let mut issues = issues();
+    issues[0].file_path = "../net.go".into();
+
+    let outcomes = apply_fixes(dir.path(), &issues, false).unwrap();
+    assert_eq!(
+        outcomes[0].status,
+        FixStatus::Skipped("path is outside the repository".into())
+    );
+}
@@ -0,0 +1,4 @@
+#!/bin/sh
+# Reports a canned finding on line 2, whatever the request.
+cat > /dev/null
+echo '[{"line": 2, "title": "Canned finding", "description": "Reported by the fixture."}]'
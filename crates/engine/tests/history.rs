@@ -0,0 +1,95 @@
+use engine::config::Config;
+use engine::report::history::{self, HistoryRecord};
+use engine::report::{MarkdownGenerator, ReportGenerator, ReviewReport};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::path::Path;
+use tempfile::tempdir;
+
+const FIRST: &str = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+const SECOND: &str = "token = \"ZYXWVUTSRQPONMLKJIHGFEDCBA\"";
+
+/// Reviews a new `config.js` made of `lines` on `branch`, recording the run
+/// in the history at `path` as `check` does.
+async fn run(path: &Path, branch: &str, lines: &[&str]) -> ReviewReport {
+    let mut config = Config::default();
+    config.report.history_path = Some(path.to_string_lossy().into_owned());
+    let content: String = lines.iter().flat_map(|l| [*l, "\n"]).collect();
+    let added: String = lines.iter().flat_map(|l| ["+", l, "\n"]).collect();
+    let diff = format!(
+        "diff --git a/config.js b/config.js\n--- a/config.js\n+++ b/config.js\n@@ -0,0 +1,{} @@\n{}",
+        lines.len(),
+        added
+    );
+    let mut files = HashMap::new();
+    files.insert("config.js".to_string(), content);
+    let report = ReviewEngine::new(config)
+        .unwrap()
+        .with_branch(branch)
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap();
+    let record = HistoryRecord::new(branch, Some("0123456789abcdef"), &report.issues);
+    history::append(path, &record, 100).unwrap();
+    report
+}
+
+#[tokio::test]
+async fn second_run_reports_new_and_resolved_findings() {
+    let temp = tempdir().unwrap();
+    let path = temp.path().join(".reviewlens/history.jsonl");
+
+    let report = run(&path, "feature", &[FIRST]).await;
+    assert_eq!(report.issues.len(), 1);
+    assert!(report.since_last_run.is_none());
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(!md.contains("## Since Last Run"));
+
+    let report = run(&path, "feature", &["", SECOND]).await;
+    let since = report.since_last_run.clone().unwrap();
+    assert_eq!(
+        (since.new, since.resolved, since.persisting),
+        (1, 1, 0),
+        "{:?}",
+        since
+    );
+    assert_eq!(since.branch, "feature");
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(md.contains("## Since Last Run\n\n1 new, 1 resolved and 0 unchanged findings since the last run on `feature` ("), "{}", md);
+    assert!(md.contains(", `01234567`)."));
+
+    let report = run(&path, "feature", &["", SECOND]).await;
+    let since = report.since_last_run.unwrap();
+    assert_eq!((since.new, since.resolved, since.persisting), (0, 0, 1));
+}
+
+#[tokio::test]
+async fn runs_are_compared_within_their_branch() {
+    let temp = tempdir().unwrap();
+    let path = temp.path().join("history.jsonl");
+    run(&path, "main", &[FIRST]).await;
+    let report = run(&path, "feature", &[FIRST]).await;
+    assert!(report.since_last_run.is_none());
+    let report = run(&path, "main", &[FIRST, SECOND]).await;
+    let since = report.since_last_run.unwrap();
+    assert_eq!((since.new, since.resolved, since.persisting), (1, 0, 1));
+}
+
+#[test]
+fn append_keeps_the_last_entries() {
+    let temp = tempdir().unwrap();
+    let path = temp.path().join("history.jsonl");
+    for branch in ["a", "b", "c", "d"] {
+        history::append(&path, &HistoryRecord::new(branch, None, &[]), 3).unwrap();
+    }
+    let branches: Vec<String> = history::load(&path)
+        .unwrap()
+        .into_iter()
+        .map(|r| r.branch)
+        .collect();
+    assert_eq!(branches, ["b", "c", "d"]);
+    assert!(history::latest(&path, "a").unwrap().is_none());
+    assert!(history::latest(&temp.path().join("missing.jsonl"), "a")
+        .unwrap()
+        .is_none());
+}
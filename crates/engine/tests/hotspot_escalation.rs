@@ -0,0 +1,104 @@
+use engine::config::{Config, FailOn, Severity};
+use engine::report::{JsonGenerator, MarkdownGenerator, ReportGenerator, ReviewReport};
+use engine::ReviewEngine;
+use serde_json::Value;
+use std::collections::HashMap;
+
+const SECRET: &str = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+
+/// Reviews a newly added `config.js` holding a secret reported as medium.
+async fn review(escalate: bool) -> ReviewReport {
+    let mut config = Config::default();
+    config.rules.secrets.severity = Severity::Medium;
+    config.report.escalate_hotspots = escalate;
+    let diff = format!(
+        "diff --git a/config.js b/config.js\n--- a/config.js\n+++ b/config.js\n@@ -0,0 +1 @@\n+{}\n",
+        SECRET
+    );
+    let mut files = HashMap::new();
+    files.insert("config.js".to_string(), format!("{}\n", SECRET));
+    ReviewEngine::new(config)
+        .unwrap()
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn medium_finding_in_a_hotspot_fails_a_high_threshold() {
+    let report = review(false).await;
+    assert!(report.hotspots[0].starts_with("config.js"));
+    assert_eq!(report.issues[0].severity, Severity::Medium);
+    assert!(report.failing_issues(&FailOn::High).is_empty());
+
+    let report = review(true).await;
+    let issue = &report.issues[0];
+    assert_eq!(issue.severity, Severity::High);
+    assert_eq!(issue.original_severity, Some(Severity::Medium));
+    assert_eq!(report.failing_issues(&FailOn::High).len(), 1);
+    assert_eq!(
+        report.stats.issues_by_severity.get(&Severity::High),
+        Some(&1)
+    );
+    assert_eq!(report.stats.issues_by_severity.get(&Severity::Medium), None);
+}
+
+#[tokio::test]
+async fn escalation_is_shown_in_both_formats() {
+    let report = review(true).await;
+    let markdown = MarkdownGenerator.generate(&report).unwrap();
+    assert!(
+        markdown.contains("| `Medium` → `High` (hotspot) | "),
+        "{}",
+        markdown
+    );
+
+    let json: Value = serde_json::from_str(&JsonGenerator.generate(&report).unwrap()).unwrap();
+    assert_eq!(json["issues"][0]["severity"], "high");
+    assert_eq!(json["issues"][0]["original_severity"], "medium");
+
+    let report = review(false).await;
+    let json: Value = serde_json::from_str(&JsonGenerator.generate(&report).unwrap()).unwrap();
+    assert!(json["issues"][0].get("original_severity").is_none());
+}
+
+#[tokio::test]
+async fn escalated_findings_are_sorted_by_their_new_severity() {
+    let sql = "db.Query(\"SELECT * FROM users WHERE id = \" + id)";
+    let mut config = Config::default();
+    config.rules.secrets.severity = Severity::Medium;
+    config.report.escalate_hotspots = true;
+    config.report.escalate_by = 2;
+    let diff = format!(
+        "diff --git a/z.go b/z.go\n--- a/z.go\n+++ b/z.go\n@@ -0,0 +1 @@\n+{}\n\
+         diff --git a/a.js b/a.js\n--- a/a.js\n+++ b/a.js\n@@ -0,0 +1 @@\n+{}\n",
+        sql, SECRET
+    );
+    let mut files = HashMap::new();
+    files.insert("z.go".to_string(), format!("{}\n", sql));
+    files.insert("a.js".to_string(), format!("{}\n", SECRET));
+    let report = ReviewEngine::new(config)
+        .unwrap()
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap();
+
+    let order: Vec<(&str, Severity)> = report
+        .issues
+        .iter()
+        .map(|issue| (issue.file_path.as_str(), issue.severity.clone()))
+        .collect();
+    assert_eq!(
+        order,
+        [("a.js", Severity::Critical), ("z.go", Severity::Critical)]
+    );
+    assert_eq!(report.issues[0].original_severity, Some(Severity::Medium));
+}
+
+#[test]
+fn severities_are_raised_up_to_critical() {
+    assert_eq!(Severity::Low.raised(1), Severity::Medium);
+    assert_eq!(Severity::Medium.raised(2), Severity::Critical);
+    assert_eq!(Severity::High.raised(5), Severity::Critical);
+    assert_eq!(Severity::Low.raised(0), Severity::Low);
+}
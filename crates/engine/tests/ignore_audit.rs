@@ -0,0 +1,63 @@
+use engine::audit::{audit_file, audit_ignores, DirectiveStatus};
+use engine::config::Config;
+use engine::scanner::parse_ignore_directives;
+use std::fs;
+
+const SOURCE: &str = "\
+// reviewlens:ignore secrets test fixture
+const API_KEY = \"ABCDEFGHIJKLMNOPQRSTUVWX\";
+// reviewlens:ignore secrets rotated long ago
+const name = \"reviewlens\";
+const url = \"x\"; // reviewlens:ignore no-such-rule
+// reviewlens:ignore removed-controls
+";
+
+#[test]
+fn directives_record_their_own_line() {
+    let ignores = parse_ignore_directives("a.js", SOURCE);
+    let standalone = &ignores[&2][0];
+    assert_eq!(standalone.rule, "secrets");
+    assert_eq!(standalone.line, 1);
+    let trailing = &ignores[&5][0];
+    assert_eq!(trailing.rule, "no-such-rule");
+    assert_eq!(trailing.line, 5);
+}
+
+#[test]
+fn directives_are_classified() {
+    let audits = audit_file("a.js", SOURCE, &Config::default()).unwrap();
+    let summary: Vec<(usize, usize, &str, DirectiveStatus)> = audits
+        .iter()
+        .map(|a| (a.line, a.target, a.rule.as_str(), a.status))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (1, 2, "secrets", DirectiveStatus::Active),
+            (3, 4, "secrets", DirectiveStatus::Stale),
+            (5, 5, "no-such-rule", DirectiveStatus::UnknownRule),
+            (6, 7, "removed-controls", DirectiveStatus::Unchecked),
+        ]
+    );
+    assert_eq!(audits[0].reason.as_deref(), Some("test fixture"));
+}
+
+#[test]
+fn repository_audit_honors_path_globs() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("vendor")).unwrap();
+    fs::write(dir.path().join("a.js"), SOURCE).unwrap();
+    fs::write(dir.path().join("vendor/b.js"), SOURCE).unwrap();
+    fs::write(dir.path().join("blob.bin"), [0xff, 0xfe, 0x00]).unwrap();
+
+    let mut config = Config::default();
+    config.paths.deny = vec!["vendor/**".into()];
+    let audits = audit_ignores(dir.path(), &config).unwrap();
+    assert_eq!(audits.len(), 4);
+    assert!(audits.iter().all(|a| a.path == "a.js"));
+
+    config.paths.deny.clear();
+    let audits = audit_ignores(dir.path(), &config).unwrap();
+    assert_eq!(audits.len(), 8);
+    assert_eq!(audits[4].path, "vendor/b.js");
+}
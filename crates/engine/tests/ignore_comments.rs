@@ -0,0 +1,148 @@
+use engine::config::Config;
+use engine::scanner::{parse_ignore_directives, scan_file, IgnoreMap, SecretsScanner};
+
+/// Returns `(target line, rule, reason)` for every directive, in line order.
+fn directives(map: &IgnoreMap) -> Vec<(usize, String, Option<String>)> {
+    let mut found: Vec<_> = map
+        .iter()
+        .flat_map(|(target, list)| {
+            list.iter()
+                .map(move |d| (*target, d.rule.clone(), d.reason.clone()))
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+fn parsed(path: &str, content: &str) -> Vec<(usize, String, Option<String>)> {
+    directives(&parse_ignore_directives(path, content))
+}
+
+fn entry(target: usize, rule: &str, reason: Option<&str>) -> (usize, String, Option<String>) {
+    (target, rule.to_string(), reason.map(String::from))
+}
+
+#[test]
+fn slash_comments() {
+    let content = "// reviewlens:ignore secrets fixture\nlet a = 1;\nlet b = 2; // reviewlens:ignore secrets\n";
+    assert_eq!(
+        parsed("src/lib.rs", content),
+        vec![
+            entry(2, "secrets", Some("fixture")),
+            entry(3, "secrets", None)
+        ]
+    );
+}
+
+#[test]
+fn hash_comments() {
+    let content =
+        "# reviewlens:ignore secrets fixture\nkey = 1\nother = 2  # reviewlens:ignore secrets\n";
+    for path in ["app.py", "deploy.sh", "main.tf", "Gemfile", "Dockerfile"] {
+        assert_eq!(
+            parsed(path, content),
+            vec![
+                entry(2, "secrets", Some("fixture")),
+                entry(3, "secrets", None)
+            ],
+            "{}",
+            path
+        );
+    }
+    // `#` is not a comment in Rust.
+    assert!(parsed("src/lib.rs", content).is_empty());
+}
+
+#[test]
+fn dash_comments() {
+    let content = "-- reviewlens:ignore secrets seed data\nINSERT INTO t VALUES ('x');\nSELECT 1; -- reviewlens:ignore secrets\n";
+    assert_eq!(
+        parsed("schema.sql", content),
+        vec![
+            entry(2, "secrets", Some("seed data")),
+            entry(3, "secrets", None)
+        ]
+    );
+}
+
+#[test]
+fn html_comments() {
+    let content = "<!-- reviewlens:ignore secrets docs example -->\n<p>key</p>\n<p>key</p> <!-- reviewlens:ignore secrets -->\n";
+    assert_eq!(
+        parsed("index.html", content),
+        vec![
+            entry(2, "secrets", Some("docs example")),
+            entry(3, "secrets", None)
+        ]
+    );
+}
+
+#[test]
+fn block_comments() {
+    let content = "\
+/* reviewlens:ignore secrets closed on this line */
+const a = 1;
+/* reviewlens:ignore secrets */ const b = 2;
+const c = 3; /* reviewlens:ignore secrets trailing */
+/* reviewlens:ignore secrets continues
+   onto the next line */
+";
+    assert_eq!(
+        parsed("app.css", content),
+        vec![
+            entry(2, "secrets", Some("closed on this line")),
+            entry(3, "secrets", None),
+            entry(4, "secrets", Some("trailing")),
+            entry(6, "secrets", Some("continues")),
+        ]
+    );
+}
+
+#[test]
+fn semicolon_comments() {
+    let content = "; reviewlens:ignore secrets local only\npassword = hunter2\n";
+    assert_eq!(
+        parsed("settings.ini", content),
+        vec![entry(2, "secrets", Some("local only"))]
+    );
+}
+
+#[test]
+fn unknown_files_accept_every_style() {
+    let content = "\
+# reviewlens:ignore a
+x
+-- reviewlens:ignore b
+x
+<!-- reviewlens:ignore c -->
+x
+// reviewlens:ignore d
+x
+";
+    let rules: Vec<String> = parsed("notes.txt", content)
+        .into_iter()
+        .map(|(_, rule, _)| rule)
+        .collect();
+    assert_eq!(rules, vec!["a", "b", "c", "d"]);
+}
+
+#[test]
+fn yaml_directives_suppress_secrets() {
+    let config = Config::default();
+    let flagged = "api_key: \"ABCDEFGHIJKLMNOPQRSTUVWX\"\n";
+    assert_eq!(
+        scan_file(&SecretsScanner, "config.yaml", flagged, &config)
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let ignored = format!("# reviewlens:ignore secrets test fixture\n{}", flagged);
+    assert!(scan_file(&SecretsScanner, "config.yaml", &ignored, &config)
+        .unwrap()
+        .is_empty());
+    let trailing = "api_key: \"ABCDEFGHIJKLMNOPQRSTUVWX\" # reviewlens:ignore secrets\n";
+    assert!(scan_file(&SecretsScanner, "config.yml", trailing, &config)
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use engine::{
+    config::{Config, IndexConfig, Provider},
+    llm::{LlmProvider, LlmResponse},
+    rag::{index_repository, InMemoryVectorStore},
+    report::{MarkdownGenerator, ReportGenerator},
+    ReviewEngine,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+const DAY: Duration = Duration::from_secs(86_400);
+
+struct EchoProvider;
+
+#[async_trait]
+impl LlmProvider for EchoProvider {
+    async fn generate(&self, _prompt: &str) -> engine::error::Result<LlmResponse> {
+        Ok(LlmResponse {
+            content: "summary".into(),
+            token_usage: 1,
+            cached: false,
+        })
+    }
+}
+
+fn save_index(path: &Path, built_at: SystemTime, head: &str) {
+    let mut store = InMemoryVectorStore::default();
+    store.set_build_info(built_at, Some(head.to_string()));
+    store.save_to_disk(path).unwrap();
+}
+
+fn engine_for(index_path: &Path, max_age_days: u64, head: &str) -> ReviewEngine {
+    let mut config = Config {
+        index: Some(IndexConfig {
+            path: index_path.to_string_lossy().into_owned(),
+            max_age_days,
+        }),
+        ..Config::default()
+    };
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config.llm.api_key = Some("key".into());
+    ReviewEngine::new(config)
+        .unwrap()
+        .with_llm_provider(Box::new(EchoProvider))
+        .with_head_commit(head)
+}
+
+fn sample() -> (String, HashMap<String, String>) {
+    let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -0,0 +1 @@\n+fn a() {}\n";
+    let mut files = HashMap::new();
+    files.insert("a.rs".to_string(), "fn a() {}".to_string());
+    (diff.to_string(), files)
+}
+
+#[tokio::test]
+async fn recent_index_is_fresh() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = dir.path().join("index.json.zst");
+    save_index(&index, SystemTime::now() - DAY, "aaaa");
+    let (diff, files) = sample();
+
+    let report = engine_for(&index, 14, "bbbb")
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap();
+    assert!(report.metadata.index_warm);
+    assert!(report.notes.is_empty());
+}
+
+#[tokio::test]
+async fn index_built_from_head_is_fresh_regardless_of_age() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = dir.path().join("index.json.zst");
+    save_index(&index, SystemTime::now() - 90 * DAY, "aaaa");
+    let (diff, files) = sample();
+
+    let report = engine_for(&index, 14, "aaaa")
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap();
+    assert!(report.metadata.index_warm);
+    assert!(report.notes.is_empty());
+}
+
+#[tokio::test]
+async fn old_index_from_other_commit_is_stale() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = dir.path().join("index.json.zst");
+    save_index(&index, SystemTime::now() - 30 * DAY, "aaaa1111ffff");
+    let (diff, files) = sample();
+
+    let report = engine_for(&index, 14, "bbbb2222ffff")
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap();
+    assert!(!report.metadata.index_warm);
+    assert_eq!(report.notes.len(), 1);
+    let note = &report.notes[0];
+    assert!(note.contains("aaaa1111"), "{}", note);
+    assert!(note.contains("bbbb2222"), "{}", note);
+    assert!(note.contains("reviewlens index"), "{}", note);
+
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(md.contains("> **Note:** The RAG index is stale"));
+}
+
+#[tokio::test]
+async fn max_age_days_is_configurable() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = dir.path().join("index.json.zst");
+    save_index(&index, SystemTime::now() - 30 * DAY, "aaaa");
+    let (diff, files) = sample();
+
+    let report = engine_for(&index, 60, "bbbb")
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap();
+    assert!(report.metadata.index_warm);
+    assert!(report.notes.is_empty());
+}
+
+#[tokio::test]
+async fn legacy_index_without_metadata_is_not_flagged() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = dir.path().join("index.json.zst");
+    let json = serde_json::to_vec(&json!({"documents": []})).unwrap();
+    std::fs::write(&index, zstd::encode_all(&json[..], 0).unwrap()).unwrap();
+
+    let store = InMemoryVectorStore::load_from_disk(&index).unwrap();
+    assert!(store.built_at().is_none());
+    assert!(store.head_commit().is_none());
+
+    let (diff, files) = sample();
+    let report = engine_for(&index, 14, "bbbb")
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap();
+    assert!(report.metadata.index_warm);
+    assert!(report.notes.is_empty());
+}
+
+#[test]
+fn staleness_is_measured_against_newest_change() {
+    let built = SystemTime::now() - 30 * DAY;
+    let mut store = InMemoryVectorStore::default();
+    store.set_build_info(built, Some("aaaa".into()));
+
+    assert!(store
+        .staleness(Some("bbbb"), Some(built + DAY), 14 * DAY)
+        .is_none());
+    let reason = store
+        .staleness(Some("bbbb"), Some(built + 20 * DAY), 14 * DAY)
+        .unwrap();
+    assert!(reason.starts_with("built 20 days before the newest change"));
+}
+
+#[tokio::test]
+async fn index_repository_records_build_time() {
+    let repo = tempfile::tempdir().unwrap();
+    std::fs::write(repo.path().join("lib.rs"), "fn a() {}").unwrap();
+    let output = repo.path().join(".reviewlens/index.json.zst");
+
+    let before = SystemTime::now() - Duration::from_secs(1);
+    index_repository(repo.path(), &output, true, &["**".to_string()], &[])
+        .await
+        .unwrap();
+    let store = InMemoryVectorStore::load_from_disk(&output).unwrap();
+    assert!(store.built_at().unwrap() >= before);
+}
@@ -1,5 +1,7 @@
+use async_trait::async_trait;
 use engine::{
-    config::{Config, IndexConfig},
+    config::{Config, IndexConfig, Provider},
+    llm::{LlmProvider, LlmResponse},
     ReviewEngine,
 };
 use serde_json::json;
@@ -24,6 +26,19 @@ fn build_index(docs: &[(&str, &str)]) -> NamedTempFile {
     file
 }
 
+struct EchoProvider;
+
+#[async_trait]
+impl LlmProvider for EchoProvider {
+    async fn generate(&self, _prompt: &str) -> engine::error::Result<LlmResponse> {
+        Ok(LlmResponse {
+            content: "summary".into(),
+            token_usage: 1,
+            cached: false,
+        })
+    }
+}
+
 #[tokio::test]
 async fn loads_index_from_index_table() {
     let dir = tempdir().unwrap();
@@ -36,12 +51,21 @@ async fn loads_index_from_index_table() {
     );
 
     let index = build_index(&[("existing.rs", "fn existing() { log::info!(\\\"hi\\\"); }")]);
-    let mut config = Config::default();
-    config.index = Some(IndexConfig {
-        path: index.path().to_str().unwrap().to_string(),
-    });
+    let mut config = Config {
+        index: Some(IndexConfig {
+            path: index.path().to_str().unwrap().to_string(),
+            ..IndexConfig::default()
+        }),
+        ..Config::default()
+    };
+    // The index is only consulted when building an LLM prompt.
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config.llm.api_key = Some("key".into());
 
-    let engine = ReviewEngine::new(config).unwrap();
+    let engine = ReviewEngine::new(config)
+        .unwrap()
+        .with_llm_provider(Box::new(EchoProvider));
     let report = engine.run(&diff).await.unwrap();
     assert!(report.metadata.index_warm);
 }
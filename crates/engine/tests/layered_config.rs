@@ -0,0 +1,61 @@
+use engine::config::{Config, ConfigSource, Provider};
+use std::fs;
+
+#[test]
+fn repo_file_overrides_only_the_model_from_user_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let user = dir.path().join("user.toml");
+    let repo = dir.path().join("reviewlens.toml");
+    fs::write(
+        &user,
+        "[llm]\nprovider = \"openai\"\nmodel = \"user-model\"\napi-key = \"user-key\"\n",
+    )
+    .unwrap();
+    fs::write(&repo, "[llm]\nmodel = \"repo-model\"\n").unwrap();
+
+    let (config, provenance) = Config::load_layered(Some(&user), &repo).unwrap();
+    assert_eq!(config.llm.provider, Provider::Openai);
+    assert_eq!(config.llm.model.as_deref(), Some("repo-model"));
+    assert_eq!(config.llm.api_key.as_deref(), Some("user-key"));
+    assert_eq!(provenance.source("llm"), ConfigSource::Repo(repo.clone()));
+    assert_eq!(provenance.source("paths"), ConfigSource::Default);
+}
+
+#[test]
+fn user_file_alone_sets_provider() {
+    let dir = tempfile::tempdir().unwrap();
+    let user = dir.path().join("user.toml");
+    fs::write(&user, "[llm]\nprovider = \"anthropic\"\n").unwrap();
+
+    let (config, provenance) =
+        Config::load_layered(Some(&user), &dir.path().join("missing.toml")).unwrap();
+    assert_eq!(config.llm.provider, Provider::Anthropic);
+    assert_eq!(provenance.source("llm"), ConfigSource::User(user.clone()));
+}
+
+#[test]
+fn missing_layers_fall_back_to_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let (config, _) = Config::load_layered(None, &dir.path().join("missing.toml")).unwrap();
+    assert_eq!(config, Config::default());
+}
+
+#[test]
+fn merge_replaces_vectors_and_keeps_unset_options() {
+    let mut base = Config::default();
+    base.llm.model = Some("base-model".into());
+    base.llm.api_key = Some("secret".into());
+    base.paths.deny = vec!["vendor/**".into(), "target/**".into()];
+
+    let overlay: toml::Table =
+        toml::from_str("[paths]\ndeny = [\"dist/**\"]\n\n[report.hotspot-weights]\nchurn = 5\n")
+            .unwrap();
+    let merged = base.merge(&overlay).unwrap();
+
+    assert_eq!(merged.paths.deny, vec!["dist/**".to_string()]);
+    assert_eq!(merged.paths.allow, vec!["**/*".to_string()]);
+    assert_eq!(merged.llm.model.as_deref(), Some("base-model"));
+    assert_eq!(merged.llm.api_key.as_deref(), Some("secret"));
+    assert_eq!(merged.report.hotspot_weights.churn, 5);
+    assert_eq!(merged.report.hotspot_weights.severity, 3);
+}
@@ -0,0 +1,120 @@
+use engine::config::{Config, LimitsConfig};
+use engine::diff_parser::{self, ChangedFile};
+use engine::limits;
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+/// A diff adding `lines` lines to each file, in the given order.
+fn diff(files: &[(&str, usize)]) -> String {
+    let mut diff = String::new();
+    for (path, lines) in files {
+        diff.push_str(&format!(
+            "diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n@@ -0,0 +1,{1} @@\n",
+            path, lines
+        ));
+        for i in 0..*lines {
+            diff.push_str(&format!("+line {}\n", i));
+        }
+    }
+    diff
+}
+
+fn files() -> Vec<ChangedFile> {
+    diff_parser::parse(&diff(&[
+        ("vendor/lib/a.go", 50),
+        ("src/main.rs", 10),
+        ("docs/guide.md", 100),
+        ("src/big.rs", 30),
+        ("src/util.go", 20),
+    ]))
+    .unwrap()
+}
+
+fn paths(files: &[ChangedFile]) -> Vec<&str> {
+    files.iter().map(|f| f.path.as_str()).collect()
+}
+
+#[test]
+fn max_files_prefers_source_with_most_churn() {
+    let limits = LimitsConfig {
+        max_files: 2,
+        ..LimitsConfig::unlimited()
+    };
+    let (selected, skipped) = limits::select(files(), &limits);
+    assert_eq!(paths(&selected), vec!["src/big.rs", "src/util.go"]);
+    let skipped: Vec<(&str, &str)> = skipped
+        .iter()
+        .map(|s| (s.path.as_str(), s.reason.as_str()))
+        .collect();
+    assert_eq!(
+        skipped,
+        vec![
+            ("vendor/lib/a.go", "max-files (2) reached"),
+            ("src/main.rs", "max-files (2) reached"),
+            ("docs/guide.md", "max-files (2) reached"),
+        ]
+    );
+}
+
+#[test]
+fn per_file_and_total_line_limits() {
+    let limits = LimitsConfig {
+        max_lines_per_file: 25,
+        ..LimitsConfig::unlimited()
+    };
+    let (selected, skipped) = limits::select(files(), &limits);
+    assert_eq!(paths(&selected), vec!["src/main.rs", "src/util.go"]);
+    assert_eq!(
+        skipped[2].reason,
+        "30 changed lines exceed max-lines-per-file (25)"
+    );
+
+    // big.rs (30) fits, util.go (20) would overflow, main.rs (10) still fits.
+    let limits = LimitsConfig {
+        max_total_lines: 45,
+        ..LimitsConfig::unlimited()
+    };
+    let (selected, _) = limits::select(files(), &limits);
+    assert_eq!(paths(&selected), vec!["src/main.rs", "src/big.rs"]);
+}
+
+#[test]
+fn unlimited_keeps_everything() {
+    let (selected, skipped) = limits::select(files(), &LimitsConfig::unlimited());
+    assert_eq!(selected.len(), 5);
+    assert!(skipped.is_empty());
+    assert_eq!(limits::note(&skipped), None);
+}
+
+#[tokio::test]
+async fn report_notes_skipped_files() {
+    let config = Config {
+        limits: LimitsConfig {
+            max_files: 1,
+            ..LimitsConfig::unlimited()
+        },
+        ..Config::default()
+    };
+    let engine = ReviewEngine::new(config).unwrap();
+    let diff = diff(&[("secret.txt", 1), ("main.rs", 2)]);
+    let mut files = HashMap::new();
+    files.insert(
+        "secret.txt".to_string(),
+        "api_key = \"ABCDEFGHIJKLMNOP\"\n".to_string(),
+    );
+    files.insert("main.rs".to_string(), "fn main() {}\n\n".to_string());
+
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+    assert_eq!(report.stats.files_reviewed, 1);
+    assert_eq!(report.stats.files_skipped, 1);
+    assert_eq!(report.stats.skipped_for_limits[0].path, "secret.txt");
+    assert!(
+        report
+            .notes
+            .iter()
+            .any(|n| n.starts_with("1 file skipped due to size limits (secret.txt)")),
+        "{:?}",
+        report.notes
+    );
+}
@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use engine::config::{Config, Provider};
+use engine::error::Result;
+use engine::llm::cache::CachingProvider;
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+struct CountingProvider(Arc<AtomicUsize>);
+
+#[async_trait]
+impl LlmProvider for CountingProvider {
+    async fn generate(&self, prompt: &str) -> Result<LlmResponse> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Ok(LlmResponse {
+            content: format!("summary of {}", prompt),
+            token_usage: 42,
+            cached: false,
+        })
+    }
+}
+
+fn caching(
+    calls: &Arc<AtomicUsize>,
+    path: &std::path::Path,
+    ttl: u64,
+    max: usize,
+) -> CachingProvider {
+    CachingProvider::new(
+        Box::new(CountingProvider(calls.clone())),
+        path,
+        "openai\0gpt\0Some(0.0)",
+        Duration::from_secs(ttl),
+        max,
+    )
+}
+
+#[tokio::test]
+async fn second_identical_prompt_hits_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cache/llm.json");
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let first = caching(&calls, &path, 3600, 10)
+        .generate("prompt")
+        .await
+        .unwrap();
+    assert!(!first.cached);
+    assert_eq!(first.token_usage, 42);
+
+    // A fresh provider reads the entry back from disk.
+    let second = caching(&calls, &path, 3600, 10)
+        .generate("prompt")
+        .await
+        .unwrap();
+    assert!(second.cached);
+    assert_eq!(second.token_usage, 0);
+    assert_eq!(second.content, first.content);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn expired_entries_are_not_served() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("llm.json");
+    let calls = Arc::new(AtomicUsize::new(0));
+    let provider = caching(&calls, &path, 0, 10);
+    provider.generate("prompt").await.unwrap();
+    let again = provider.generate("prompt").await.unwrap();
+    assert!(!again.cached);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn cache_is_capped_at_max_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("llm.json");
+    let calls = Arc::new(AtomicUsize::new(0));
+    let provider = caching(&calls, &path, 3600, 2);
+    for prompt in ["a", "b", "c"] {
+        provider.generate(prompt).await.unwrap();
+    }
+    let stored: HashMap<String, serde_json::Value> =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(stored.len(), 2);
+}
+
+/// Serves a fixed OpenAI-style completion and counts requests.
+fn fake_openai() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}/v1/chat/completions",
+        listener.local_addr().unwrap()
+    );
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = v.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            counter.fetch_add(1, Ordering::SeqCst);
+            let json = r#"{"choices":[{"message":{"role":"assistant","content":"LLM summary"}}],"usage":{"total_tokens":17}}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                json.len(),
+                json
+            )
+            .unwrap();
+        }
+    });
+    (url, hits)
+}
+
+#[tokio::test]
+async fn rerunning_engine_on_same_diff_uses_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let (url, hits) = fake_openai();
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config.llm.api_key = Some("key".into());
+    config.llm.base_url = Some(url);
+    config.llm.cache = true;
+    config.llm.cache_path = Some(dir.path().join("llm.json").to_string_lossy().into_owned());
+
+    let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -0,0 +1 @@\n+fn a() {}\n";
+    let mut files = HashMap::new();
+    files.insert("a.rs".to_string(), "fn a() {}\n".to_string());
+
+    let first = ReviewEngine::new(config.clone())
+        .unwrap()
+        .run_with_provider(diff, &files)
+        .await
+        .unwrap();
+    assert_eq!(first.stats.tokens_used, 17);
+    assert!(!first.metadata.llm_cache_hit);
+
+    let second = ReviewEngine::new(config)
+        .unwrap()
+        .run_with_provider(diff, &files)
+        .await
+        .unwrap();
+    assert_eq!(second.summary, "LLM summary");
+    assert_eq!(second.stats.tokens_used, 0);
+    assert!(second.metadata.llm_cache_hit);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
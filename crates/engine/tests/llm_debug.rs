@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use engine::config::Config;
+use engine::error::{EngineError, Result};
+use engine::llm::debug::DebugLogProvider;
+use engine::llm::{create_llm_provider, LlmProvider, LlmResponse};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+const PROMPT: &str = "Review this change:\n+api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"\n";
+
+fn config(dir: &Path) -> Config {
+    let mut config = Config::default();
+    config.llm.debug_dir = Some(dir.to_string_lossy().into());
+    config.llm.model = Some("offline".into());
+    config.generation.temperature = Some(0.2);
+    config
+}
+
+fn response(dir: &Path, n: usize) -> Value {
+    let json = fs::read_to_string(dir.join(format!("{:03}-response.json", n))).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[tokio::test]
+async fn null_provider_calls_are_written_redacted() {
+    let dir = tempfile::tempdir().unwrap();
+    let llm = create_llm_provider(&config(dir.path())).unwrap();
+    llm.generate(PROMPT).await.unwrap();
+    llm.generate("second").await.unwrap();
+
+    let request = fs::read_to_string(dir.path().join("001-request.txt")).unwrap();
+    assert!(request.contains("provider: null"), "{}", request);
+    assert!(request.contains("model: offline"));
+    assert!(request.contains("temperature: 0.2"));
+    assert!(request.contains("[REDACTED]"));
+    assert!(!request.contains("api_key"));
+
+    let first = response(dir.path(), 1);
+    assert_eq!(first["provider"], "null");
+    assert_eq!(first["model"], "offline");
+    assert!(first["token_usage"].as_u64().unwrap() > 0);
+    assert!(first["latency_ms"].is_u64());
+    assert!(first["content"]
+        .as_str()
+        .unwrap()
+        .contains("dummy response"));
+    assert!(first.get("error").is_none());
+    assert!(dir.path().join("002-request.txt").exists());
+    assert!(dir.path().join("002-response.json").exists());
+}
+
+#[tokio::test]
+async fn numbering_continues_after_existing_files() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("007-request.txt"), "").unwrap();
+    let llm = create_llm_provider(&config(dir.path())).unwrap();
+    llm.generate("hello").await.unwrap();
+    assert!(dir.path().join("008-request.txt").exists());
+    assert!(dir.path().join("008-response.json").exists());
+}
+
+struct FailingProvider;
+
+#[async_trait]
+impl LlmProvider for FailingProvider {
+    async fn generate(&self, _prompt: &str) -> Result<LlmResponse> {
+        Err(EngineError::LlmProvider(
+            "rejected token = abc123".to_string(),
+        ))
+    }
+}
+
+#[tokio::test]
+async fn failed_calls_are_written_with_the_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = config(dir.path());
+    let llm =
+        DebugLogProvider::from_config(Box::new(FailingProvider), dir.path(), &config).unwrap();
+    assert!(llm.generate(PROMPT).await.is_err());
+
+    assert!(dir.path().join("001-request.txt").exists());
+    let logged = response(dir.path(), 1);
+    let error = logged["error"].as_str().unwrap();
+    assert!(error.contains("rejected"), "{}", error);
+    assert!(!error.contains("token"));
+    assert!(logged.get("content").is_none());
+    assert!(logged.get("token_usage").is_none());
+}
@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use engine::config::{Config, Provider};
+use engine::error::{EngineError, Result};
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+struct FailingProvider;
+
+#[async_trait]
+impl LlmProvider for FailingProvider {
+    async fn generate(&self, _prompt: &str) -> Result<LlmResponse> {
+        Err(EngineError::LlmProvider("503 Service Unavailable".into()))
+    }
+}
+
+fn remote_config() -> Config {
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config.llm.api_key = Some("key".into());
+    config
+}
+
+fn secret_diff() -> (String, HashMap<String, String>) {
+    let line = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+    let diff = format!(
+        "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -0,0 +1 @@\n+{}\n",
+        line
+    );
+    let mut files = HashMap::new();
+    files.insert("a.rs".to_string(), format!("{}\n", line));
+    (diff, files)
+}
+
+#[tokio::test]
+async fn provider_error_still_produces_report() {
+    let (diff, files) = secret_diff();
+    let engine = ReviewEngine::new(remote_config())
+        .unwrap()
+        .with_llm_provider(Box::new(FailingProvider));
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+
+    assert_eq!(report.issues.len(), 1);
+    assert!(report.summary.starts_with("Reviewed 1 file"));
+    let error = report.metadata.llm_error.expect("error should be recorded");
+    assert!(error.contains("503"));
+}
+
+#[tokio::test]
+async fn required_llm_propagates_provider_error() {
+    let (diff, files) = secret_diff();
+    let mut config = remote_config();
+    config.llm.required = true;
+    let engine = ReviewEngine::new(config)
+        .unwrap()
+        .with_llm_provider(Box::new(FailingProvider));
+    let err = engine.run_with_provider(&diff, &files).await.err().unwrap();
+    assert!(matches!(err, EngineError::LlmProvider(_)));
+}
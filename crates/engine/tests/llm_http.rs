@@ -0,0 +1,215 @@
+use engine::config::{Config, GenerationConfig, Provider};
+use engine::error::EngineError;
+use engine::llm::anthropic::AnthropicProvider;
+use engine::llm::deepseek::DeepSeekProvider;
+use engine::llm::openai::OpenAiProvider;
+use engine::llm::{create_llm_provider, LlmProvider, Timeouts};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+/// Answers one request with `status` and `body`, and sends the request body
+/// it received to the returned channel.
+fn canned_server(status: &str, body: &str) -> (String, Receiver<serde_json::Value>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/v1/messages", listener.local_addr().unwrap());
+    let response = format!(
+        "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut request = vec![0; length];
+        reader.read_exact(&mut request).unwrap();
+        tx.send(serde_json::from_slice(&request).unwrap()).unwrap();
+        stream.write_all(response.as_bytes()).unwrap();
+    });
+    (url, rx)
+}
+
+fn timeouts() -> Timeouts {
+    Timeouts {
+        request: Duration::from_secs(5),
+        connect: Duration::from_secs(5),
+    }
+}
+
+#[tokio::test]
+async fn anthropic_sends_max_tokens_and_system_and_counts_usage() {
+    let (url, request) = canned_server(
+        "200 OK",
+        r#"{"content":[{"type":"text","text":"Looks good."}],"usage":{"input_tokens":12,"output_tokens":5}}"#,
+    );
+    let provider = AnthropicProvider::new(
+        "key".into(),
+        "claude-test".into(),
+        0.0,
+        256,
+        Some(url),
+        timeouts(),
+    )
+    .with_system("You review code.");
+
+    let response = provider.generate("Review this").await.unwrap();
+    assert_eq!(response.content, "Looks good.");
+    assert_eq!(response.token_usage, 17);
+
+    let sent = request.recv().unwrap();
+    assert_eq!(sent["max_tokens"], 256);
+    assert_eq!(sent["system"], "You review code.");
+    assert_eq!(sent["messages"][0]["content"], "Review this");
+}
+
+#[tokio::test]
+async fn anthropic_error_body_becomes_provider_error() {
+    let (url, _request) = canned_server(
+        "400 Bad Request",
+        r#"{"type":"error","error":{"type":"invalid_request_error","message":"max_tokens: Field required"}}"#,
+    );
+    let provider = AnthropicProvider::new(
+        "key".into(),
+        "claude-test".into(),
+        0.0,
+        1024,
+        Some(url),
+        timeouts(),
+    );
+
+    match provider.generate("prompt").await {
+        Err(EngineError::LlmProvider(message)) => {
+            assert!(message.contains("400"), "{}", message);
+            assert!(
+                message.contains("max_tokens: Field required"),
+                "{}",
+                message
+            );
+            assert!(message.contains("invalid_request_error"), "{}", message);
+        }
+        other => panic!("expected a provider error, got {:?}", other.err()),
+    }
+}
+
+#[tokio::test]
+async fn openai_compatible_error_bodies_become_provider_errors() {
+    let body =
+        r#"{"error":{"message":"Incorrect API key provided","type":"invalid_request_error"}}"#;
+
+    let (url, _request) = canned_server("401 Unauthorized", body);
+    let openai = OpenAiProvider::new("key".into(), "gpt-test".into(), 0.0, Some(url), timeouts());
+    let (url, _request) = canned_server("401 Unauthorized", body);
+    let deepseek =
+        DeepSeekProvider::new("key".into(), "ds-test".into(), 0.0, Some(url), timeouts());
+
+    for provider in [&openai as &dyn LlmProvider, &deepseek] {
+        match provider.generate("prompt").await {
+            Err(EngineError::LlmProvider(message)) => {
+                assert!(message.contains("401"), "{}", message);
+                assert!(
+                    message.contains("Incorrect API key provided"),
+                    "{}",
+                    message
+                );
+            }
+            other => panic!("expected a provider error, got {:?}", other.err()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn unrecognized_error_body_is_reported_verbatim() {
+    let (url, _request) = canned_server("502 Bad Gateway", "upstream unavailable");
+    let provider = OpenAiProvider::new("key".into(), "gpt-test".into(), 0.0, Some(url), timeouts());
+
+    let err = provider.generate("prompt").await.err().unwrap();
+    assert!(
+        matches!(&err, EngineError::LlmProvider(message) if message.contains("upstream unavailable")),
+        "{:?}",
+        err
+    );
+}
+
+/// Sends one prompt through the provider `create_llm_provider` builds for
+/// `provider`, returning the request body it sent.
+async fn request_for(provider: Provider, response: &str) -> serde_json::Value {
+    let (url, request) = canned_server("200 OK", response);
+    let mut config = Config::default();
+    config.llm.provider = provider;
+    config.llm.model = Some("model".into());
+    config.llm.api_key = Some("key".into());
+    config.llm.base_url = Some(url);
+    config.llm.cache = false;
+    config.generation = GenerationConfig {
+        temperature: Some(0.2),
+        max_tokens: 4096,
+        top_p: Some(0.9),
+        seed: Some(42),
+        stop: vec!["END".into(), "\n\n---".into()],
+    };
+    create_llm_provider(&config)
+        .unwrap()
+        .generate("prompt")
+        .await
+        .unwrap();
+    request.recv().unwrap()
+}
+
+#[tokio::test]
+async fn generation_settings_reach_each_provider_request() {
+    let chat = r#"{"choices":[{"message":{"role":"assistant","content":"ok"}}]}"#;
+    for provider in [Provider::Openai, Provider::Deepseek] {
+        let sent = request_for(provider.clone(), chat).await;
+        assert_eq!(sent["max_tokens"], 4096);
+        assert_eq!(sent["top_p"], 0.9);
+        assert_eq!(sent["stop"], serde_json::json!(["END", "\n\n---"]));
+        match provider {
+            Provider::Openai => assert_eq!(sent["seed"], 42),
+            _ => assert!(sent.get("seed").is_none(), "{}", sent),
+        }
+    }
+
+    let messages = r#"{"content":[{"type":"text","text":"ok"}]}"#;
+    let sent = request_for(Provider::Anthropic, messages).await;
+    assert_eq!(sent["max_tokens"], 4096);
+    assert_eq!(sent["top_p"], 0.9);
+    assert_eq!(
+        sent["stop_sequences"],
+        serde_json::json!(["END", "\n\n---"])
+    );
+    assert!(sent.get("seed").is_none(), "{}", sent);
+    assert!(sent.get("stop").is_none(), "{}", sent);
+}
+
+#[tokio::test]
+async fn unset_generation_settings_are_omitted() {
+    let (url, request) = canned_server(
+        "200 OK",
+        r#"{"choices":[{"message":{"role":"assistant","content":"ok"}}]}"#,
+    );
+    let provider = OpenAiProvider::new("key".into(), "gpt-test".into(), 0.0, Some(url), timeouts())
+        .with_generation(&GenerationConfig::default());
+    provider.generate("prompt").await.unwrap();
+
+    let sent = request.recv().unwrap();
+    assert_eq!(sent["max_tokens"], 1024);
+    for key in ["top_p", "seed", "stop"] {
+        assert!(sent.get(key).is_none(), "{} sent: {}", key, sent);
+    }
+}
@@ -0,0 +1,117 @@
+use engine::config::{Config, Provider};
+use engine::error::EngineError;
+use engine::llm::{build_http_client, create_llm_provider};
+use engine::ReviewEngine;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{channel, Receiver};
+
+/// Plays an HTTP proxy that answers one request with a canned OpenAI
+/// response, and sends the request line it received to the returned channel.
+fn proxy_stub() -> (String, Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let body = r#"{"choices":[{"message":{"role":"assistant","content":"OK"}}],"usage":{"total_tokens":3}}"#;
+    let response = format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut request = vec![0; length];
+        reader.read_exact(&mut request).unwrap();
+        tx.send(request_line.trim().to_string()).unwrap();
+        stream.write_all(response.as_bytes()).unwrap();
+    });
+    (url, rx)
+}
+
+fn openai_config() -> Config {
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.api_key = Some("key".into());
+    config.llm.model = Some("gpt-test".into());
+    config.llm.base_url = Some("http://llm.invalid/v1/chat/completions".into());
+    config
+}
+
+#[tokio::test]
+async fn requests_go_through_the_configured_proxy() {
+    let (proxy, request_line) = proxy_stub();
+    let mut config = openai_config();
+    config.llm.proxy = Some(proxy);
+
+    let provider = create_llm_provider(&config).unwrap();
+    let response = provider.generate("Reply with OK").await.unwrap();
+
+    assert_eq!(response.content, "OK");
+    assert_eq!(
+        request_line.recv().unwrap(),
+        "POST http://llm.invalid/v1/chat/completions HTTP/1.1"
+    );
+}
+
+#[test]
+fn missing_ca_bundle_fails_at_engine_construction() {
+    let mut config = openai_config();
+    config.llm.ca_bundle_path = Some("/nonexistent/ca.pem".into());
+    let err = ReviewEngine::new(config)
+        .err()
+        .expect("engine should not build");
+    match err {
+        EngineError::Config(message) => {
+            assert!(message.contains("llm.ca-bundle-path"), "{}", message)
+        }
+        other => panic!("expected a config error, got {:?}", other),
+    }
+}
+
+#[test]
+fn ca_bundle_without_certificates_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ca.pem");
+    std::fs::write(&path, "not a certificate\n").unwrap();
+    let mut config = openai_config();
+    config.llm.ca_bundle_path = Some(path.to_string_lossy().into_owned());
+    assert!(matches!(
+        build_http_client(&config.llm),
+        Err(EngineError::Config(_))
+    ));
+}
+
+#[test]
+fn ca_bundle_and_proxy_are_accepted() {
+    let mut config = openai_config();
+    config.llm.ca_bundle_path =
+        Some(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/test_ca.pem").to_string());
+    config.llm.proxy = Some("http://proxy.invalid:3128".into());
+    assert!(ReviewEngine::new(config).is_ok());
+}
+
+#[test]
+fn invalid_proxy_url_is_a_config_error() {
+    let mut config = openai_config();
+    config.llm.proxy = Some("not a url".into());
+    assert!(matches!(
+        build_http_client(&config.llm),
+        Err(EngineError::Config(_))
+    ));
+}
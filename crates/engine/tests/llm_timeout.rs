@@ -0,0 +1,83 @@
+use engine::config::{Config, Provider};
+use engine::error::EngineError;
+use engine::llm::openai::OpenAiProvider;
+use engine::llm::{LlmProvider, Timeouts};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::net::TcpListener;
+use std::time::{Duration, Instant};
+
+/// Accepts connections and never answers within the test's timeout.
+fn stalled_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}/v1/chat/completions",
+        listener.local_addr().unwrap()
+    );
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = stream.unwrap();
+            std::thread::spawn(move || {
+                let mut reader = BufReader::new(stream);
+                let mut line = String::new();
+                let _ = reader.read_line(&mut line);
+                std::thread::sleep(Duration::from_secs(10));
+            });
+        }
+    });
+    url
+}
+
+fn timeouts() -> Timeouts {
+    Timeouts {
+        request: Duration::from_secs(1),
+        connect: Duration::from_secs(1),
+    }
+}
+
+#[tokio::test]
+async fn delayed_response_maps_to_timeout_error() {
+    let provider = OpenAiProvider::new(
+        "key".into(),
+        "gpt-test".into(),
+        0.0,
+        Some(stalled_server()),
+        timeouts(),
+    );
+    let start = Instant::now();
+    let err = provider.generate("prompt").await.err().unwrap();
+    assert!(matches!(err, EngineError::LlmTimeout(_)), "{:?}", err);
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn engine_falls_back_to_scanner_summary_on_timeout() {
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config.llm.api_key = Some("key".into());
+    config.llm.base_url = Some(stalled_server());
+    config.llm.timeout_secs = 1;
+
+    let line = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+    let diff = format!(
+        "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -0,0 +1 @@\n+{}\n",
+        line
+    );
+    let mut files = HashMap::new();
+    files.insert("a.rs".to_string(), format!("{}\n", line));
+
+    let report = ReviewEngine::new(config)
+        .unwrap()
+        .run_with_provider(&diff, &files)
+        .await
+        .expect("a timeout should not fail the review");
+    assert!(
+        report.summary.contains("Reviewed 1 file"),
+        "{}",
+        report.summary
+    );
+    assert!(report.summary.contains("Potential Secret Found"));
+    assert_eq!(report.issues.len(), 1);
+}
@@ -0,0 +1,86 @@
+use engine::config::Config;
+use engine::scanner::{NetworkHygieneScanner, Scanner};
+
+#[test]
+fn flags_hardcoded_ip_and_internal_url() {
+    let content = r#"
+        addr := "10.1.2.3:8080"
+        api := "https://billing.internal/v1"
+        v6 := "fd00::1"
+    "#;
+    let issues = NetworkHygieneScanner
+        .scan("client.go", content, &Config::default())
+        .unwrap();
+    let titles: Vec<&str> = issues.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Hardcoded IP Address",
+            "Hardcoded Internal URL",
+            "Hardcoded IP Address"
+        ]
+    );
+    assert!(issues[0]
+        .suggested_fix
+        .as_deref()
+        .unwrap()
+        .contains("environment variable"));
+}
+
+#[test]
+fn loopback_and_localhost_are_allowed_in_tests() {
+    let content = r#"
+        srv := "127.0.0.1:0"
+        url := "http://localhost:8080/health"
+    "#;
+    let config = Config::default();
+    assert!(NetworkHygieneScanner
+        .scan("server_test.go", content, &config)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        NetworkHygieneScanner
+            .scan("server.go", content, &config)
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn allowed_hosts_and_exempt_paths_are_respected() {
+    let mut config = Config::default();
+    config.rules.network_hygiene.allowed_hosts = vec!["10.0.12.4".into(), "metrics.corp".into()];
+    let content = "a = \"10.0.12.4\"\nb = \"http://metrics.corp:9090\"\n";
+    assert!(NetworkHygieneScanner
+        .scan("src/app.py", content, &config)
+        .unwrap()
+        .is_empty());
+
+    let fixture = include_str!("../../../fixtures/network-hygiene/config/hosts.yaml");
+    let config = Config::default();
+    assert!(NetworkHygieneScanner
+        .scan("config/hosts.yaml", fixture, &config)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn flags_pprof_handler_registration() {
+    let fixture = include_str!("../../../fixtures/network-hygiene/main.go");
+    let issues = NetworkHygieneScanner
+        .scan("main.go", fixture, &Config::default())
+        .unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].title, "Debug Endpoint Exposed");
+    assert_eq!(issues[0].line_number, 9);
+}
+
+#[test]
+fn respects_ignore_directive() {
+    let content = "addr := \"10.1.2.3\" // reviewlens:ignore network-hygiene fixed lab host";
+    assert!(NetworkHygieneScanner
+        .scan("client.go", content, &Config::default())
+        .unwrap()
+        .is_empty());
+}
@@ -1,5 +1,9 @@
-use engine::config::Config;
+use engine::config::{Config, IndexConfig, Severity};
+use engine::rag::InMemoryVectorStore;
+use engine::report::{summarize, ReviewStats};
+use engine::scanner::Issue;
 use engine::ReviewEngine;
+use std::collections::HashMap;
 
 fn diff_for_file(path: &str, line: &str) -> String {
     format!(
@@ -11,10 +15,10 @@ fn diff_for_file(path: &str, line: &str) -> String {
 #[tokio::test]
 async fn generates_fallback_summary() {
     let temp = tempfile::tempdir().unwrap();
-    let file_path = temp.path().join("secret.txt");
+    let file_path = temp.path().join("secret.env");
     let content = "api_key = \"ABCDEFGHIJKLMNOP\""; // triggers secret scanner
     std::fs::write(&file_path, content).unwrap();
-    let diff = diff_for_file("secret.txt", content);
+    let diff = diff_for_file("secret.env", content);
 
     let engine = ReviewEngine::new(Config::default()).unwrap();
     std::env::set_current_dir(temp.path()).unwrap();
@@ -23,3 +27,88 @@ async fn generates_fallback_summary() {
     assert!(report.summary.contains("Reviewed 1 file"));
     assert!(report.summary.contains("Potential Secret Found"));
 }
+
+#[tokio::test]
+async fn summary_is_deterministic_with_severity_breakdown() {
+    let lines = [
+        "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"",
+        "token = \"abcdefghijklmnopqrstuvwxyz\"",
+    ];
+    let diff: String = lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| diff_for_file(&format!("f{}.env", i), l))
+        .collect();
+    let files: HashMap<String, String> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| (format!("f{}.env", i), l.to_string()))
+        .collect();
+
+    let engine = ReviewEngine::new(Config::default()).unwrap();
+    let first = engine.run_with_provider(&diff, &files).await.unwrap();
+    let second = engine.run_with_provider(&diff, &files).await.unwrap();
+
+    assert_eq!(first.summary, second.summary);
+    assert_eq!(
+        first.summary,
+        "Reviewed 2 files and found 2 issues (2 high). Top findings: \
+         Potential Secret Found in f0.env:1; Potential Secret Found in f1.env:1."
+    );
+}
+
+#[tokio::test]
+async fn null_provider_skips_rag_retrieval() {
+    // A loadable index would make the run warm if RAG were consulted.
+    let dir = tempfile::tempdir().unwrap();
+    let index_path = dir.path().join("index.json.zst");
+    InMemoryVectorStore::default()
+        .save_to_disk(&index_path)
+        .unwrap();
+    let config = Config {
+        index: Some(IndexConfig {
+            path: index_path.to_string_lossy().into_owned(),
+            ..IndexConfig::default()
+        }),
+        ..Config::default()
+    };
+    let diff = diff_for_file("a.rs", "fn a() {}");
+    let mut files = HashMap::new();
+    files.insert("a.rs".to_string(), "fn a() {}".to_string());
+
+    let report = ReviewEngine::new(config)
+        .unwrap()
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap();
+    assert!(!report.metadata.index_warm);
+    assert_eq!(report.summary, "Reviewed 1 file with no issues found.");
+}
+
+#[test]
+fn summarize_lists_top_three_by_severity() {
+    let issue = |title: &str, severity: Severity, line: usize| Issue {
+        title: title.into(),
+        description: String::new(),
+        file_path: "a.go".into(),
+        line_number: line,
+        severity,
+        ..Default::default()
+    };
+    let issues = vec![
+        issue("Low one", Severity::Low, 1),
+        issue("Critical one", Severity::Critical, 2),
+        issue("Medium one", Severity::Medium, 3),
+        issue("High one", Severity::High, 4),
+    ];
+    let stats = ReviewStats {
+        files_reviewed: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        summarize(&issues, &stats),
+        "Reviewed 1 file and found 4 issues (1 critical, 1 high, 1 medium, 1 low). \
+         Top findings: Critical one in a.go:2; High one in a.go:4; Medium one in a.go:3; \
+         and 1 more."
+    );
+}
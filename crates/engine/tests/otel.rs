@@ -0,0 +1,170 @@
+#![cfg(feature = "otel")]
+
+use async_trait::async_trait;
+use engine::config::{Config, Provider, TelemetryConfig};
+use engine::error::Result;
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::ReviewEngine;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::file_diff;
+
+const SECRET: &str = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+
+/// Path and JSON body of each export received.
+type Exports = Arc<Mutex<Vec<(String, Value)>>>;
+
+/// Accepts OTLP/HTTP exports and records them.
+fn fake_collector() -> (String, Exports) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let seen = requests.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let path = request_line.split_whitespace().nth(1).unwrap().to_string();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = v.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            seen.lock()
+                .unwrap()
+                .push((path, serde_json::from_slice(&body).unwrap_or(Value::Null)));
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{{}}"
+            )
+            .unwrap();
+        }
+    });
+    (base, requests)
+}
+
+struct FixedProvider;
+
+#[async_trait]
+impl LlmProvider for FixedProvider {
+    async fn generate(&self, _prompt: &str) -> Result<LlmResponse> {
+        Ok(LlmResponse {
+            content: "summary".into(),
+            token_usage: 42,
+            cached: false,
+        })
+    }
+}
+
+fn config(endpoint: &str) -> Config {
+    let mut config = Config {
+        telemetry: TelemetryConfig {
+            enabled: true,
+            file: None,
+            otlp_endpoint: Some(endpoint.into()),
+        },
+        ..Config::default()
+    };
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config
+}
+
+fn attribute<'a>(span: &'a Value, key: &str) -> Option<&'a Value> {
+    span["attributes"]
+        .as_array()?
+        .iter()
+        .find(|a| a["key"] == key)
+        .map(|a| &a["value"])
+}
+
+#[tokio::test]
+async fn exports_one_trace_per_run() {
+    let (endpoint, requests) = fake_collector();
+    let engine = ReviewEngine::builder(config(&format!("{}/", endpoint)))
+        .llm(Box::new(FixedProvider))
+        .build()
+        .unwrap();
+    let mut files = HashMap::new();
+    files.insert("a.rs".to_string(), format!("{}\n", SECRET));
+    engine
+        .run_with_provider(&file_diff("a.rs", SECRET), &files)
+        .await
+        .unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    let (path, body) = &requests[0];
+    assert_eq!(path, "/v1/traces");
+    let spans = body["resourceSpans"][0]["scopeSpans"][0]["spans"]
+        .as_array()
+        .unwrap();
+
+    let root = &spans[0];
+    assert_eq!(root["name"], "review");
+    assert!(root.get("parentSpanId").is_none());
+    assert_eq!(attribute(root, "findings").unwrap()["intValue"], "1");
+    assert_eq!(attribute(root, "llm.tokens").unwrap()["intValue"], "42");
+    for span in &spans[1..] {
+        assert_eq!(span["traceId"], root["traceId"]);
+        assert_eq!(span["parentSpanId"], root["spanId"]);
+    }
+
+    let named = |name: &str| spans.iter().filter(|s| s["name"] == name).count();
+    assert_eq!(named("diff.parse"), 1);
+    assert_eq!(named("llm.generate"), 1);
+    let scanners: Vec<&Value> = spans
+        .iter()
+        .filter(|s| attribute(s, "scanner.name").is_some())
+        .collect();
+    assert!(!scanners.is_empty());
+    assert!(scanners
+        .iter()
+        .any(|s| attribute(s, "findings").unwrap()["intValue"] == "1"));
+
+    let llm = spans.iter().find(|s| s["name"] == "llm.generate").unwrap();
+    assert_eq!(
+        attribute(llm, "llm.provider").unwrap()["stringValue"],
+        "openai"
+    );
+    assert_eq!(
+        attribute(llm, "llm.model").unwrap()["stringValue"],
+        "gpt-test"
+    );
+}
+
+#[tokio::test]
+async fn unreachable_collector_does_not_fail_the_review() {
+    // Bind and drop a listener to find a port nothing listens on.
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let engine = ReviewEngine::builder(config(&format!("http://127.0.0.1:{}", port)))
+        .llm(Box::new(FixedProvider))
+        .build()
+        .unwrap();
+    let mut files = HashMap::new();
+    files.insert("a.rs".to_string(), format!("{}\n", SECRET));
+    let report = engine
+        .run_with_provider(&file_diff("a.rs", SECRET), &files)
+        .await
+        .unwrap();
+    assert_eq!(report.issues.len(), 1);
+}
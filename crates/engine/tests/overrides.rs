@@ -0,0 +1,93 @@
+use engine::config::{Config, FailOn, Severity};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+const CONFIG: &str = r#"
+fail-on = "high"
+
+[rules.sql-injection-go]
+enabled = true
+severity = "high"
+
+[[overrides]]
+paths = ["docs/**"]
+[overrides.rules.secrets]
+enabled = false
+
+[[overrides]]
+paths = ["services/payments/**"]
+fail-on = "medium"
+[overrides.rules.sql-injection-go]
+severity = "critical"
+"#;
+
+const SECRET: &str = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+const SQL: &str = "query := \"SELECT * FROM users WHERE id = \" + id";
+
+fn added_file(path: &str, line: &str) -> String {
+    format!(
+        "diff --git a/{p} b/{p}\n--- a/{p}\n+++ b/{p}\n@@ -0,0 +1 @@\n+{l}\n",
+        p = path,
+        l = line
+    )
+}
+
+async fn review(files: &[(&str, &str)]) -> Vec<engine::scanner::Issue> {
+    let config: Config = toml::from_str(CONFIG).unwrap();
+    let diff: String = files.iter().map(|(p, l)| added_file(p, l)).collect();
+    let contents: HashMap<String, String> = files
+        .iter()
+        .map(|(p, l)| (p.to_string(), format!("{}\n", l)))
+        .collect();
+    let engine = ReviewEngine::new(config).unwrap();
+    engine
+        .run_with_provider(&diff, &contents)
+        .await
+        .unwrap()
+        .issues
+}
+
+#[tokio::test]
+async fn override_disables_secrets_under_docs() {
+    let issues = review(&[("docs/setup.md", SECRET), ("src/lib.rs", SECRET)]).await;
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].file_path, "src/lib.rs");
+}
+
+#[tokio::test]
+async fn override_raises_severity_under_payments() {
+    let issues = review(&[
+        ("services/payments/db.go", SQL),
+        ("services/users/db.go", SQL),
+    ])
+    .await;
+    let severity_of = |path: &str| {
+        issues
+            .iter()
+            .find(|i| i.file_path == path)
+            .map(|i| i.severity.clone())
+            .unwrap()
+    };
+    assert_eq!(severity_of("services/payments/db.go"), Severity::Critical);
+    assert_eq!(severity_of("services/users/db.go"), Severity::High);
+}
+
+#[test]
+fn effective_config_applies_matching_overrides_only() {
+    let config: Config = toml::from_str(CONFIG).unwrap();
+    assert!(!config.for_path("docs/a.md").unwrap().rules.secrets.enabled);
+    assert!(config.for_path("src/a.rs").unwrap().rules.secrets.enabled);
+    assert_eq!(config.fail_on_for("services/payments/x.go"), FailOn::Medium);
+    assert_eq!(config.fail_on_for("docs/a.md"), FailOn::High);
+}
+
+#[test]
+fn validate_reports_unknown_override_rule_keys() {
+    let config: Config = toml::from_str(
+        "[[overrides]]\npaths = [\"docs/**\"]\n[overrides.rules.secret]\nenabled = false\n",
+    )
+    .unwrap();
+    let problems = config.validate();
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].path, "overrides[0].rules.secret");
+}
@@ -1,4 +1,5 @@
 use engine::config::Config;
+use engine::paths::{self, PathDecision};
 use engine::ReviewEngine;
 
 fn diff_for_file(path: &str, line: &str) -> String {
@@ -58,3 +59,46 @@ async fn respects_deny_patterns() {
     assert_eq!(report.issues.len(), 1);
     assert_eq!(report.issues[0].file_path, "included.rs");
 }
+
+#[test]
+fn explain_names_the_deciding_pattern() {
+    let mut config = Config::default();
+    config.paths.allow = vec!["src/**".into(), "**/*.rs".into()];
+    config.paths.deny = vec!["**/generated/**".into()];
+    let files = [
+        "src/lib.rs".to_string(),
+        "docs/guide.md".to_string(),
+        "src/generated/api.rs".to_string(),
+    ];
+
+    let explanations = paths::explain(&config.paths, &files).unwrap();
+    let decisions: Vec<(&str, &PathDecision)> = explanations
+        .iter()
+        .map(|e| (e.path.as_str(), &e.decision))
+        .collect();
+    assert_eq!(
+        decisions,
+        [
+            (
+                "src/lib.rs",
+                &PathDecision::Kept {
+                    pattern: "src/**".into()
+                }
+            ),
+            ("docs/guide.md", &PathDecision::NotAllowed),
+            (
+                "src/generated/api.rs",
+                &PathDecision::Denied {
+                    pattern: "**/generated/**".into()
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn explain_rejects_an_invalid_glob() {
+    let mut config = Config::default();
+    config.paths.deny = vec!["src/[".into()];
+    assert!(paths::explain(&config.paths, &["a.rs".to_string()]).is_err());
+}
@@ -0,0 +1,142 @@
+use engine::config::{Config, ConfigOverride, PolicyConfig, PolicyViolation, Severity};
+use engine::error::EngineError;
+use engine::{render_report, review_diff, Format, ReviewEngine, ReviewOptions};
+use std::collections::HashMap;
+
+const SECRETS: [&str; 2] = [
+    "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"",
+    "token = \"ZYXWVUTSRQPONMLKJIHGFEDC\"",
+];
+
+fn config_error(config: Config) -> String {
+    match ReviewEngine::new(config) {
+        Err(EngineError::Config(msg)) => msg,
+        Err(other) => panic!("unexpected error: {:?}", other),
+        Ok(_) => panic!("the configuration was accepted"),
+    }
+}
+
+async fn review(policy: PolicyConfig) -> engine::report::ReviewReport {
+    let added: String = SECRETS.iter().flat_map(|line| ["+", line, "\n"]).collect();
+    let diff = format!(
+        "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -0,0 +1,2 @@\n{}",
+        added
+    );
+    let mut files = HashMap::new();
+    files.insert("src/a.rs".to_string(), format!("{}\n", SECRETS.join("\n")));
+    let config = Config {
+        policy,
+        ..Config::default()
+    };
+    let options = ReviewOptions::new(config)
+        .root("/nonexistent")
+        .content(Box::new(files));
+    review_diff(&diff, options).await.unwrap()
+}
+
+#[test]
+fn disabling_a_required_rule_is_a_config_error() {
+    let mut config = Config::default();
+    config.policy.required_rules = vec!["secrets".into()];
+    config.rules.secrets.enabled = false;
+    let msg = config_error(config);
+    assert!(
+        msg.contains("required rule `secrets` is disabled"),
+        "{}",
+        msg
+    );
+}
+
+#[test]
+fn disabling_redaction_is_a_config_error_when_forbidden() {
+    let mut config = Config::default();
+    config.policy.forbid_disabling_redaction = true;
+    config.privacy.redaction.enabled = false;
+    let msg = config_error(config);
+    assert!(msg.contains("[privacy.redaction]"), "{}", msg);
+}
+
+#[test]
+fn overrides_may_not_disable_a_required_rule() {
+    let mut config = Config::default();
+    config.policy.required_rules = vec!["secrets".into()];
+    config.overrides.push(ConfigOverride {
+        paths: vec!["fixtures/**".into()],
+        rules: Some(toml::from_str("secrets = { enabled = false }").unwrap()),
+        fail_on: None,
+    });
+    let msg = config_error(config);
+    assert!(msg.contains("disabled by overrides[0]"), "{}", msg);
+}
+
+#[test]
+fn unknown_required_rules_are_reported() {
+    let mut config = Config::default();
+    config.policy.required_rules = vec!["secret".into()];
+    let msg = config_error(config);
+    assert!(msg.contains("`secret` is not a known rule"), "{}", msg);
+}
+
+#[test]
+fn policy_files_use_the_policy_table_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("reviewlens-policy.toml");
+    std::fs::write(
+        &path,
+        "required-rules = [\"secrets\"]\nmax-findings = { critical = 0, high = 5 }\nforbid-disabling-redaction = true\n",
+    )
+    .unwrap();
+    let policy = PolicyConfig::load_from_path(&path).unwrap();
+    assert_eq!(policy.required_rules, ["secrets"]);
+    assert_eq!(policy.max_findings[&Severity::Critical], 0);
+    assert_eq!(policy.max_findings[&Severity::High], 5);
+    assert!(policy.forbid_disabling_redaction);
+
+    std::fs::write(&path, "max-finding = { high = 1 }\n").unwrap();
+    let err = PolicyConfig::load_from_path(&path).unwrap_err();
+    assert!(matches!(err, EngineError::Config(msg) if msg.contains("max-finding")));
+}
+
+#[tokio::test]
+async fn findings_over_the_limit_are_listed_in_the_report() {
+    let policy = PolicyConfig {
+        required_rules: vec!["secrets".into()],
+        max_findings: [(Severity::Critical, 0), (Severity::High, 1)]
+            .into_iter()
+            .collect(),
+        ..PolicyConfig::default()
+    };
+    let report = review(policy).await;
+
+    assert_eq!(report.stats.issues_by_severity[&Severity::High], 2);
+    assert_eq!(
+        report.policy_violations,
+        [PolicyViolation {
+            severity: Severity::High,
+            count: 2,
+            max: 1,
+        }]
+    );
+    let md = render_report(&report, Format::Markdown).unwrap();
+    assert!(md.contains("## 🚫 Policy Violations"), "{}", md);
+    assert!(md.contains("2 `high` findings; the policy allows at most 1."));
+    let json = render_report(&report, Format::Json).unwrap();
+    assert!(json.contains("\"policy_violations\""), "{}", json);
+}
+
+#[tokio::test]
+async fn compliant_runs_have_no_violations() {
+    let policy = PolicyConfig {
+        required_rules: vec!["secrets".into()],
+        max_findings: [(Severity::Critical, 0), (Severity::High, 5)]
+            .into_iter()
+            .collect(),
+        forbid_disabling_redaction: true,
+    };
+    let report = review(policy).await;
+
+    assert_eq!(report.issues.len(), 2);
+    assert!(report.policy_violations.is_empty());
+    let md = render_report(&report, Format::Markdown).unwrap();
+    assert!(!md.contains("Policy Violations"));
+}
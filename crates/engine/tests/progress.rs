@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use engine::config::{Config, Provider};
+use engine::error::Result;
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::progress::ProgressEvent;
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::sync::mpsc::channel;
+
+mod common;
+use common::file_diff;
+
+const SECRET: &str = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+
+struct FixedProvider;
+
+#[async_trait]
+impl LlmProvider for FixedProvider {
+    async fn generate(&self, _prompt: &str) -> Result<LlmResponse> {
+        Ok(LlmResponse {
+            content: "summary".into(),
+            token_usage: 1,
+            cached: false,
+        })
+    }
+}
+
+/// Drops the fields that vary between runs so sequences can be compared.
+fn shape(event: &ProgressEvent) -> String {
+    match event {
+        ProgressEvent::Started => "started".into(),
+        ProgressEvent::ParsedDiff { files } => format!("parsed {}", files),
+        ProgressEvent::ScanningFile { path, index, total } => {
+            format!("scanning {}/{} {}", index, total, path)
+        }
+        ProgressEvent::ScannerFinished { path, .. } => format!("scanner {}", path),
+        ProgressEvent::Finding { path, line, rule } => {
+            format!("finding {}:{} {}", path, line, rule)
+        }
+        ProgressEvent::RetrievingContext { findings } => format!("retrieving {}", findings),
+        ProgressEvent::ContextRetrieved { .. } => "retrieved".into(),
+        ProgressEvent::CallingLlm => "llm".into(),
+        ProgressEvent::LlmFinished { tokens, error, .. } => {
+            format!("llm finished {} {:?}", tokens, error)
+        }
+        ProgressEvent::Done {
+            findings, tokens, ..
+        } => format!("done {} {}", findings, tokens),
+    }
+}
+
+#[tokio::test]
+async fn reports_each_step_in_order() {
+    let diff = format!("{}{}", file_diff("a.rs", SECRET), file_diff("b.rs", "ok"));
+    let mut files = HashMap::new();
+    files.insert("a.rs".to_string(), format!("{}\n", SECRET));
+    files.insert("b.rs".to_string(), "ok\n".to_string());
+
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    let engine = ReviewEngine::builder(config)
+        .llm(Box::new(FixedProvider))
+        .build()
+        .unwrap();
+
+    let (tx, rx) = channel();
+    let report = engine
+        .run_with_provider_and_progress(&diff, &files, Some(tx))
+        .await
+        .unwrap();
+    let events: Vec<ProgressEvent> = rx.iter().collect();
+
+    // Every scanner reports once per file; keep one entry per file.
+    let mut sequence: Vec<String> = events.iter().map(shape).collect();
+    sequence.dedup();
+    assert_eq!(
+        sequence,
+        [
+            "started",
+            "parsed 2",
+            "scanning 1/2 a.rs",
+            "scanner a.rs",
+            "finding a.rs:1 secrets",
+            "scanner a.rs",
+            "scanning 2/2 b.rs",
+            "scanner b.rs",
+            "llm",
+            "llm finished 1 None",
+            "done 1 1",
+        ]
+    );
+    assert_eq!(report.issues.len(), 1);
+
+    let finished = |file: &str| {
+        events
+            .iter()
+            .filter(|event| {
+                matches!(event, ProgressEvent::ScannerFinished { path, .. } if path == file)
+            })
+            .count()
+    };
+    assert!(finished("a.rs") > 0);
+    assert_eq!(finished("a.rs"), finished("b.rs"));
+}
+
+#[tokio::test]
+async fn run_without_a_listener_still_succeeds() {
+    let engine = ReviewEngine::new(Config::default()).unwrap();
+    let mut files = HashMap::new();
+    files.insert("a.rs".to_string(), format!("{}\n", SECRET));
+    let report = engine
+        .run_with_provider_and_progress(&file_diff("a.rs", SECRET), &files, None)
+        .await
+        .unwrap();
+    assert_eq!(report.issues.len(), 1);
+}
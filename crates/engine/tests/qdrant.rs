@@ -0,0 +1,229 @@
+#![cfg(feature = "qdrant")]
+
+use engine::config::QdrantConfig;
+use engine::llm::Timeouts;
+use engine::rag::qdrant::point_id;
+use engine::rag::{index_repository_into, IndexOptions, QdrantVectorStore, VectorStore};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A request received by the fake Qdrant server.
+#[derive(Debug, Clone)]
+struct Recorded {
+    method: String,
+    path: String,
+    api_key: Option<String>,
+    body: Value,
+}
+
+/// Serves a minimal Qdrant HTTP API for collection `code`, which does not
+/// exist until created. Searches return one fixed hit.
+fn fake_qdrant() -> (String, Arc<Mutex<Vec<Recorded>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let seen = requests.clone();
+    std::thread::spawn(move || {
+        let mut created = false;
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap().to_string();
+            let path = parts.next().unwrap().to_string();
+            let (mut length, mut api_key) = (0, None);
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                let lower = line.to_ascii_lowercase();
+                if let Some(v) = lower.strip_prefix("content-length:") {
+                    length = v.trim().parse().unwrap();
+                }
+                if lower.starts_with("api-key:") {
+                    api_key = Some(line["api-key:".len()..].trim().to_string());
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+            seen.lock().unwrap().push(Recorded {
+                method: method.clone(),
+                path: path.clone(),
+                api_key,
+                body,
+            });
+
+            let (status, response) = match (method.as_str(), path.as_str()) {
+                ("GET", "/collections/code") if !created => (
+                    "404 Not Found",
+                    json!({"status": {"error": "Not found: Collection `code` doesn't exist!"}}),
+                ),
+                ("PUT", "/collections/code") => {
+                    created = true;
+                    ("200 OK", json!({"result": true, "status": "ok"}))
+                }
+                ("POST", "/collections/code/points/search") => (
+                    "200 OK",
+                    json!({"result": [{
+                        "id": 7,
+                        "version": 0,
+                        "score": 0.75,
+                        "payload": {
+                            "filename": "store/users.go",
+                            "content": "func FindUser() {}",
+                            "function_signatures": ["func FindUser() {}"],
+                            "log_patterns": [],
+                            "error_snippets": [],
+                            "modified": 42
+                        },
+                        "vector": [0.6, 0.8]
+                    }], "status": "ok"}),
+                ),
+                _ => (
+                    "200 OK",
+                    json!({"result": {"operation_id": 0, "status": "completed"}, "status": "ok"}),
+                ),
+            };
+            let json = response.to_string();
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                json.len(),
+                json
+            )
+            .unwrap();
+        }
+    });
+    (base, requests)
+}
+
+fn store(url: &str, api_key: Option<&str>) -> QdrantVectorStore {
+    QdrantVectorStore::new(
+        &QdrantConfig {
+            url: format!("{}/", url),
+            collection: "code".into(),
+            api_key: api_key.map(str::to_string),
+        },
+        Timeouts {
+            request: Duration::from_secs(5),
+            connect: Duration::from_secs(5),
+        },
+    )
+}
+
+#[tokio::test]
+async fn indexing_creates_collection_and_upserts_documents() {
+    let (url, requests) = fake_qdrant();
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a.go"), "func A() {}\n").unwrap();
+    fs::write(dir.path().join("b.go"), "func B() {}\n").unwrap();
+    fs::write(dir.path().join("notes.txt"), "skip me\n").unwrap();
+
+    let mut store = store(&url, Some("secret"));
+    let count = index_repository_into(
+        dir.path(),
+        &["**/*.go".into()],
+        &[],
+        &IndexOptions::default(),
+        &mut store,
+    )
+    .await
+    .unwrap();
+    assert_eq!(count, 2);
+
+    let requests = requests.lock().unwrap();
+    let calls: Vec<(&str, &str)> = requests
+        .iter()
+        .map(|r| (r.method.as_str(), r.path.as_str()))
+        .collect();
+    assert_eq!(
+        calls,
+        vec![
+            ("GET", "/collections/code"),
+            ("PUT", "/collections/code"),
+            ("PUT", "/collections/code/points?wait=true"),
+        ]
+    );
+    assert!(requests
+        .iter()
+        .all(|r| r.api_key.as_deref() == Some("secret")));
+
+    let dimension = requests[1].body["vectors"]["size"].as_u64().unwrap();
+    assert!(dimension > 0);
+    assert_eq!(requests[1].body["vectors"]["distance"], "Cosine");
+
+    let points = requests[2].body["points"].as_array().unwrap();
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[0]["id"], point_id("a.go"));
+    assert_eq!(points[0]["payload"]["filename"], "a.go");
+    assert_eq!(points[0]["payload"]["content"], "func A() {}\n");
+    assert_eq!(
+        points[0]["vector"].as_array().unwrap().len() as u64,
+        dimension
+    );
+    assert_eq!(points[1]["payload"]["filename"], "b.go");
+}
+
+#[tokio::test]
+async fn search_reconstructs_documents_with_scores() {
+    let (url, requests) = fake_qdrant();
+    let store = store(&url, None);
+
+    let hits = store.search_scored(vec![1.0, 0.0], 3).await.unwrap();
+    assert_eq!(hits.len(), 1);
+    let (doc, score) = &hits[0];
+    assert_eq!(doc.filename, "store/users.go");
+    assert_eq!(doc.content, "func FindUser() {}");
+    assert_eq!(doc.function_signatures, vec!["func FindUser() {}"]);
+    assert_eq!(doc.modified, 42);
+    assert_eq!(doc.embedding, vec![0.6, 0.8]);
+    assert_eq!(*score, 0.75);
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/collections/code/points/search");
+    assert_eq!(requests[0].api_key, None);
+    assert_eq!(
+        requests[0].body,
+        json!({"vector": [1.0, 0.0], "limit": 3, "with_payload": true, "with_vector": true})
+    );
+}
+
+#[tokio::test]
+async fn failed_upsert_is_a_rag_error() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf);
+            let _ = stream.write_all(
+                b"HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            );
+        }
+    });
+    let mut store = store(&url, None);
+    let doc = engine::rag::Document {
+        filename: "a.go".into(),
+        content: String::new(),
+        embedding: vec![1.0],
+        language: None,
+        function_signatures: vec![],
+        log_patterns: vec![],
+        error_snippets: vec![],
+        modified: 0,
+    };
+    let err = store.add(doc).await.unwrap_err();
+    assert!(err.to_string().contains("Qdrant"), "{}", err);
+}
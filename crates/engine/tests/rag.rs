@@ -1,5 +1,6 @@
 use engine::rag::{
-    index_repository, Document, InMemoryVectorStore, RagContextRetriever, VectorStore,
+    index_repository, index_repository_with, Document, InMemoryVectorStore, IndexOptions,
+    RagContextRetriever, VectorStore,
 };
 use std::env;
 use std::fs;
@@ -14,6 +15,7 @@ async fn retrieves_context_from_saved_store() {
         filename: "doc.txt".into(),
         content: "example context".into(),
         embedding: vec![1.0; 128],
+        language: None,
         function_signatures: vec![],
         log_patterns: vec![],
         error_snippets: vec![],
@@ -39,7 +41,9 @@ async fn retrieves_context_from_saved_store() {
 
     let rag = RagContextRetriever::new(Box::new(loaded));
     let ctx = rag.retrieve("whatever").await.unwrap();
-    assert!(ctx.contains("example context"));
+    assert_eq!(ctx.len(), 1);
+    assert_eq!(ctx[0].filename, "doc.txt");
+    assert_eq!(ctx[0].text, "example context");
 }
 
 #[tokio::test]
@@ -134,3 +138,79 @@ async fn respects_path_filters_and_ignores_vcs_dirs() {
     assert!(!text.contains("other.txt"));
     assert!(!text.contains(".git"));
 }
+
+fn jobs(jobs: usize) -> IndexOptions {
+    IndexOptions {
+        jobs,
+        ..IndexOptions::default()
+    }
+}
+
+#[tokio::test]
+async fn parallel_indexing_matches_sequential_output() {
+    let dir = tempdir().unwrap();
+    for i in 0..300 {
+        let sub = dir.path().join(format!("mod{}", i % 7));
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(
+            sub.join(format!("file{}.rs", i)),
+            format!(
+                "pub fn f{i}() -> Result<(), E> {{\n    log::info!(\"{i}\");\n    g().unwrap();\n}}\n"
+            ),
+        )
+        .unwrap();
+    }
+    let index_dir = tempdir().unwrap();
+    let allow = vec!["**/*.rs".into()];
+
+    let sequential = index_dir.path().join("sequential.json.zst");
+    let parallel = index_dir.path().join("parallel.json.zst");
+    let a = index_repository_with(dir.path(), &sequential, true, &allow, &[], &jobs(1))
+        .await
+        .unwrap();
+    let b = index_repository_with(dir.path(), &parallel, true, &allow, &[], &jobs(8))
+        .await
+        .unwrap();
+
+    assert_eq!(a.len(), 300);
+    let names: Vec<&str> = b.documents().iter().map(|d| d.filename.as_str()).collect();
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+    assert_eq!(
+        serde_json::to_string(a.documents()).unwrap(),
+        serde_json::to_string(b.documents()).unwrap()
+    );
+}
+
+#[test]
+fn stats_summarize_documents() {
+    let mut store = InMemoryVectorStore::default();
+    let doc = |name: &str, content: &str, embedding: Vec<f32>| Document {
+        filename: name.into(),
+        content: content.into(),
+        embedding,
+        language: None,
+        function_signatures: vec![],
+        log_patterns: vec![],
+        error_snippets: vec![],
+        modified: 0,
+    };
+    store.push_document(doc("src/a.rs", "fn a() {}", vec![1.0; 4]));
+    store.push_document(doc("src/b.rs", "fn b() { a() }", vec![1.0; 4]));
+    store.push_document(doc("README.md", "# hi", vec![]));
+    store.push_document(doc("Makefile", "all:", vec![1.0; 4]));
+
+    let stats = store.stats();
+    assert_eq!(stats.documents, 4);
+    assert_eq!(stats.content_bytes, 9 + 14 + 4 + 4);
+    assert_eq!(stats.embedder, "ngram");
+    assert_eq!(stats.dimension, Some(4));
+    assert_eq!(stats.empty_embeddings, 1);
+    assert_eq!(stats.built_at, None);
+    assert_eq!(stats.largest[0].filename, "src/b.rs");
+    assert_eq!(stats.largest.len(), 4);
+    assert_eq!(stats.extensions.get("rs"), Some(&2));
+    assert_eq!(stats.extensions.get("md"), Some(&1));
+    assert_eq!(stats.extensions.get("(none)"), Some(&1));
+}
@@ -0,0 +1,247 @@
+use async_trait::async_trait;
+use engine::config::{Config, Provider};
+use engine::error::Result;
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::rag::{
+    index_repository_with, merge_retrieved, Document, Embedder, InMemoryVectorStore, IndexOptions,
+    RagContextRetriever, VectorStore,
+};
+use engine::report::{JsonGenerator, ReportGenerator, ReviewReport};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Counts occurrences of a fixed vocabulary.
+struct KeywordEmbedder;
+
+const VOCABULARY: [&str; 5] = ["SELECT", "users", "orders", "http", "template"];
+
+#[async_trait]
+impl Embedder for KeywordEmbedder {
+    fn name(&self) -> String {
+        "keyword".into()
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        Ok(VOCABULARY
+            .iter()
+            .map(|word| text.matches(word).count() as f32)
+            .collect())
+    }
+}
+
+/// Wraps an in-memory store and counts searches.
+struct CountingStore {
+    inner: InMemoryVectorStore,
+    searches: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl VectorStore for CountingStore {
+    async fn add(&mut self, document: Document) -> Result<()> {
+        self.inner.add(document).await
+    }
+
+    async fn search_scored(
+        &self,
+        query_embedding: Vec<f32>,
+        top_k: usize,
+    ) -> Result<Vec<(Document, f32)>> {
+        self.searches.fetch_add(1, Ordering::SeqCst);
+        self.inner.search_scored(query_embedding, top_k).await
+    }
+
+    async fn search_batch_scored(
+        &self,
+        query_embeddings: Vec<Vec<f32>>,
+        top_k: usize,
+    ) -> Result<Vec<Vec<(Document, f32)>>> {
+        self.searches.fetch_add(1, Ordering::SeqCst);
+        self.inner
+            .search_batch_scored(query_embeddings, top_k)
+            .await
+    }
+
+    fn document_count(&self) -> Option<usize> {
+        self.inner.document_count()
+    }
+}
+
+struct RecordingProvider(Arc<Mutex<Vec<String>>>);
+
+#[async_trait]
+impl LlmProvider for RecordingProvider {
+    async fn generate(&self, prompt: &str) -> Result<LlmResponse> {
+        self.0.lock().unwrap().push(prompt.to_string());
+        Ok(LlmResponse {
+            content: "summary".into(),
+            token_usage: 1,
+            cached: false,
+        })
+    }
+}
+
+async fn seeded_store(dir: &std::path::Path) -> InMemoryVectorStore {
+    let repo = dir.join("repo");
+    fs::create_dir_all(&repo).unwrap();
+    fs::write(repo.join("users.go"), "SELECT name FROM users\n").unwrap();
+    fs::write(repo.join("orders.go"), "SELECT total FROM orders\n").unwrap();
+    fs::write(repo.join("page.go"), "http template\n").unwrap();
+    let index = dir.join("index.json.zst");
+    let options = IndexOptions {
+        embedder: Arc::new(KeywordEmbedder),
+        ..IndexOptions::default()
+    };
+    index_repository_with(&repo, &index, true, &["**/*.go".into()], &[], &options)
+        .await
+        .unwrap();
+    InMemoryVectorStore::load_from_disk(&index).unwrap()
+}
+
+fn names(results: &[(Document, f32)]) -> Vec<(String, f32)> {
+    results
+        .iter()
+        .map(|(doc, score)| (doc.filename.clone(), *score))
+        .collect()
+}
+
+#[tokio::test]
+async fn batched_retrieval_matches_sequential_searches() {
+    let dir = tempfile::tempdir().unwrap();
+    let rag =
+        RagContextRetriever::for_index(seeded_store(dir.path()).await, Arc::new(KeywordEmbedder))
+            .unwrap();
+    let queries: Vec<String> = [
+        "SELECT * FROM users",
+        "SELECT * FROM orders",
+        "users users",
+        "nothing relevant",
+    ]
+    .iter()
+    .map(|q| q.to_string())
+    .collect();
+
+    let batched = rag.retrieve_many(&queries).await.unwrap();
+    let mut sequential = Vec::new();
+    for query in &queries {
+        sequential.push(rag.search(query, 5).await.unwrap());
+    }
+    assert_eq!(batched.len(), sequential.len());
+    for (batch, single) in batched.iter().zip(&sequential) {
+        assert_eq!(names(batch), names(single));
+    }
+
+    // Documents found by several queries are listed once.
+    let merged = rag.retrieve_merged(&queries).await.unwrap().unwrap();
+    assert_eq!(merged, merge_retrieved(sequential).unwrap());
+    assert_eq!(merged.matches("users.go").count(), 1, "{}", merged);
+}
+
+#[tokio::test]
+async fn engine_retrieves_once_across_prompt_batches() {
+    let dir = tempfile::tempdir().unwrap();
+    let searches = Arc::new(AtomicUsize::new(0));
+    let store = CountingStore {
+        inner: seeded_store(dir.path()).await,
+        searches: searches.clone(),
+    };
+
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config.llm.max_prompt_tokens = Some(60);
+    let prompts = Arc::new(Mutex::new(Vec::new()));
+    let engine = ReviewEngine::builder(config)
+        .llm(Box::new(RecordingProvider(prompts.clone())))
+        .vector_store(Box::new(store))
+        .embedder(Arc::new(KeywordEmbedder))
+        .build()
+        .unwrap();
+
+    let lines = [
+        "db.Query(\"SELECT * FROM users WHERE id = \" + id)",
+        "db.Query(\"SELECT * FROM orders WHERE id = \" + id)",
+        "db.Query(\"SELECT * FROM users WHERE name = \" + name)",
+    ];
+    let added: String = lines.iter().flat_map(|l| ["+", l, "\n"]).collect();
+    let diff = format!(
+        "diff --git a/db.go b/db.go\n--- a/db.go\n+++ b/db.go\n@@ -0,0 +1,3 @@\n{}",
+        added
+    );
+    let mut files = HashMap::new();
+    files.insert("db.go".to_string(), lines.join("\n"));
+
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    assert_eq!(report.issues.len(), 3);
+    assert!(
+        prompts.lock().unwrap().len() > 1,
+        "expected batched prompts"
+    );
+    assert_eq!(searches.load(Ordering::SeqCst), 1);
+    let json = JsonGenerator.generate(&report).unwrap();
+    assert!(json.contains("\"retrieval_ms\""));
+}
+
+/// Reviews a one-line SQL injection with an LLM and `inner` as the index,
+/// returning the report and the number of store searches.
+async fn review_with(config: Config, inner: InMemoryVectorStore) -> (ReviewReport, usize) {
+    let searches = Arc::new(AtomicUsize::new(0));
+    let store = CountingStore {
+        inner,
+        searches: searches.clone(),
+    };
+    let mut config = config;
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    let engine = ReviewEngine::builder(config)
+        .llm(Box::new(RecordingProvider(Arc::default())))
+        .vector_store(Box::new(store))
+        .embedder(Arc::new(KeywordEmbedder))
+        .build()
+        .unwrap();
+    let line = "db.Query(\"SELECT * FROM users WHERE id = \" + id)";
+    let diff = format!(
+        "diff --git a/db.go b/db.go\n--- a/db.go\n+++ b/db.go\n@@ -0,0 +1 @@\n+{}\n",
+        line
+    );
+    let mut files = HashMap::new();
+    files.insert("db.go".to_string(), line.to_string());
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    (report, searches.load(Ordering::SeqCst))
+}
+
+#[tokio::test]
+async fn disabled_rag_skips_retrieval_with_identical_findings() {
+    let dir = tempfile::tempdir().unwrap();
+    let (enabled, searches) = review_with(Config::default(), seeded_store(dir.path()).await).await;
+    assert_eq!(searches, 1);
+    assert!(enabled.metadata.rag_enabled);
+    assert_eq!(enabled.metadata.index_documents, Some(3));
+
+    let mut config = Config::default();
+    config.rag.enabled = false;
+    let (disabled, searches) = review_with(config, seeded_store(dir.path()).await).await;
+    assert_eq!(searches, 0);
+    assert!(!disabled.metadata.rag_enabled);
+    assert!(!disabled.metadata.index_warm);
+    assert_eq!(disabled.metadata.index_documents, None);
+    let fingerprints = |report: &ReviewReport| -> Vec<String> {
+        report
+            .issues
+            .iter()
+            .map(|i| i.fingerprint.clone())
+            .collect()
+    };
+    assert_eq!(fingerprints(&disabled), fingerprints(&enabled));
+}
+
+#[tokio::test]
+async fn empty_index_is_not_searched() {
+    let (report, searches) = review_with(Config::default(), InMemoryVectorStore::default()).await;
+    assert_eq!(searches, 0);
+    assert_eq!(report.issues.len(), 1);
+    assert!(report.metadata.rag_enabled);
+    assert_eq!(report.metadata.index_documents, Some(0));
+}
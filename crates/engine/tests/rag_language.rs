@@ -0,0 +1,242 @@
+use engine::config::Config;
+use engine::rag::{index_repository, InMemoryVectorStore, Language};
+use engine::scanner::{ConventionsScanner, Scanner};
+use std::fs;
+use tempfile::tempdir;
+
+const RUST: &str = r#"
+pub fn load(path: &str) -> Result<String, Error> {
+    log::info!("loading {path}");
+    let text = read(path).unwrap();
+    Ok(text)
+}
+
+async fn save() {}
+"#;
+
+const GO: &str = r#"
+package store
+
+func Open(path string) (*Store, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, fmt.Errorf("open: %w", err)
+	}
+	log.Printf("opened %s", path)
+	return &Store{f}, nil
+}
+
+func (s *Store) Close() error {
+	fmt.Println("closing")
+	panic("unreachable")
+}
+"#;
+
+const PYTHON: &str = r#"
+import logging
+
+def load(path):
+    try:
+        return open(path).read()
+    except OSError as e:
+        logging.warning("failed: %s", e)
+    except:
+        print("unknown")
+
+async def save(data):
+    pass
+"#;
+
+const TYPESCRIPT: &str = r#"
+export async function fetchUser(id: string): Promise<User> {
+  return api.get(id).catch((e) => logger.error(e));
+}
+
+export const render = (user: User): string => user.name;
+
+class Cache {
+  private get(key: string): string | undefined {
+    if (this.has(key)) {
+      console.log("hit");
+    }
+    return undefined;
+  }
+}
+"#;
+
+#[test]
+fn languages_are_detected_from_extensions() {
+    assert_eq!(Language::from_path("src/lib.rs"), Some(Language::Rust));
+    assert_eq!(Language::from_path("cmd/main.go"), Some(Language::Go));
+    assert_eq!(Language::from_path("app.py"), Some(Language::Python));
+    assert_eq!(
+        Language::from_path("web/app.jsx"),
+        Some(Language::JavaScript)
+    );
+    assert_eq!(
+        Language::from_path("web/app.ts"),
+        Some(Language::TypeScript)
+    );
+    assert_eq!(Language::from_path("README.md"), None);
+    assert_eq!(Language::from_path("Makefile"), None);
+}
+
+#[test]
+fn rust_metadata_is_extracted() {
+    let rust = Language::Rust;
+    assert_eq!(
+        rust.function_signatures(RUST),
+        vec![
+            "pub fn load(path: &str) -> Result<String, Error> {",
+            "async fn save() {}"
+        ]
+    );
+    assert_eq!(
+        rust.log_patterns(RUST),
+        vec![r#"log::info!("loading {path}");"#]
+    );
+    assert_eq!(
+        rust.error_snippets(RUST),
+        vec![
+            "pub fn load(path: &str) -> Result<String, Error> {",
+            "let text = read(path).unwrap();"
+        ]
+    );
+}
+
+#[test]
+fn go_metadata_is_extracted() {
+    let go = Language::Go;
+    assert_eq!(
+        go.function_signatures(GO),
+        vec![
+            "func Open(path string) (*Store, error) {",
+            "func (s *Store) Close() error {"
+        ]
+    );
+    assert_eq!(
+        go.log_patterns(GO),
+        vec![
+            r#"log.Printf("opened %s", path)"#,
+            r#"fmt.Println("closing")"#
+        ]
+    );
+    assert_eq!(
+        go.error_snippets(GO),
+        vec![
+            "if err != nil {",
+            r#"return nil, fmt.Errorf("open: %w", err)"#,
+            r#"panic("unreachable")"#
+        ]
+    );
+}
+
+#[test]
+fn python_metadata_is_extracted() {
+    let python = Language::Python;
+    assert_eq!(
+        python.function_signatures(PYTHON),
+        vec!["def load(path):", "async def save(data):"]
+    );
+    assert_eq!(
+        python.log_patterns(PYTHON),
+        vec![r#"logging.warning("failed: %s", e)"#, r#"print("unknown")"#]
+    );
+    assert_eq!(
+        python.error_snippets(PYTHON),
+        vec!["try:", "except OSError as e:", "except:"]
+    );
+}
+
+#[test]
+fn typescript_metadata_is_extracted() {
+    let ts = Language::TypeScript;
+    assert_eq!(
+        ts.function_signatures(TYPESCRIPT),
+        vec![
+            "export async function fetchUser(id: string): Promise<User> {",
+            "export const render = (user: User): string => user.name;",
+            "private get(key: string): string | undefined {"
+        ]
+    );
+    assert_eq!(
+        ts.log_patterns(TYPESCRIPT),
+        vec![
+            "return api.get(id).catch((e) => logger.error(e));",
+            r#"console.log("hit");"#
+        ]
+    );
+    assert_eq!(
+        ts.error_snippets(TYPESCRIPT),
+        vec!["return api.get(id).catch((e) => logger.error(e));"]
+    );
+}
+
+async fn index(files: &[(&str, &str)]) -> (tempfile::TempDir, Config) {
+    let dir = tempdir().unwrap();
+    for (name, content) in files {
+        fs::write(dir.path().join(name), content).unwrap();
+    }
+    let index_path = dir.path().join(".reviewlens/index.json.zst");
+    index_repository(dir.path(), &index_path, true, &["**/*".into()], &[])
+        .await
+        .unwrap();
+    let mut config = Config::default();
+    config.index = Some(engine::config::IndexConfig {
+        path: index_path.to_string_lossy().into(),
+        ..Default::default()
+    });
+    (dir, config)
+}
+
+#[tokio::test]
+async fn documents_record_their_language() {
+    let (_dir, config) = index(&[("store.go", GO), ("notes.txt", "fn not_code() {}")]).await;
+    let store = InMemoryVectorStore::load_from_disk(config.index_path().unwrap()).unwrap();
+    let doc = |name: &str| {
+        store
+            .documents()
+            .iter()
+            .find(|d| d.filename == name)
+            .unwrap()
+            .clone()
+    };
+    let go = doc("store.go");
+    assert_eq!(go.language, Some(Language::Go));
+    assert_eq!(go.function_signatures.len(), 2);
+    let notes = doc("notes.txt");
+    assert_eq!(notes.language, None);
+    assert!(notes.function_signatures.is_empty());
+}
+
+#[tokio::test]
+async fn conventions_use_the_baseline_of_the_file_language() {
+    // Go code logs through `log`; Python code prints.
+    let go = "func a() {\n\tlog.Println(\"a\")\n\tif err != nil {\n\t\treturn err\n\t}\n}\n";
+    let python = "def a():\n    print(\"a\")\n    print(\"b\")\n";
+    let (_dir, config) = index(&[("a.go", go), ("a.py", python)]).await;
+    let scanner = ConventionsScanner::default();
+
+    let issues = scanner
+        .scan(
+            "b.go",
+            "func b() {\n\tfmt.Println(\"b\")\n\tpanic(err)\n}\n",
+            &config,
+        )
+        .unwrap();
+    let titles: Vec<(usize, &str)> = issues
+        .iter()
+        .map(|i| (i.line_number, i.title.as_str()))
+        .collect();
+    assert_eq!(
+        titles,
+        vec![(2, "Inconsistent Logging"), (3, "Avoid panic")]
+    );
+
+    let issues = scanner.scan("b.py", "print(\"b\")\n", &config).unwrap();
+    assert!(issues.is_empty(), "{:?}", issues);
+
+    // No TypeScript files were indexed, so there is no baseline.
+    let issues = scanner.scan("b.ts", "console.log(1);\n", &config).unwrap();
+    assert!(issues.is_empty());
+}
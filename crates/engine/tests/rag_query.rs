@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use engine::config::{Config, IndexConfig, Provider};
+use engine::error::Result;
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::rag::{
+    index_repository_with, Embedder, InMemoryVectorStore, IndexOptions, RagContextRetriever,
+};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+/// Counts occurrences of a fixed vocabulary. Text without any of the words
+/// embeds to a zero vector and so matches nothing.
+struct KeywordEmbedder;
+
+const VOCABULARY: [&str; 5] = ["SELECT", "users", "fmt.Sprintf", "http", "template"];
+
+#[async_trait]
+impl Embedder for KeywordEmbedder {
+    fn name(&self) -> String {
+        "keyword".into()
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        Ok(VOCABULARY
+            .iter()
+            .map(|word| text.matches(word).count() as f32)
+            .collect())
+    }
+}
+
+struct CapturingProvider(Arc<Mutex<String>>);
+
+#[async_trait]
+impl LlmProvider for CapturingProvider {
+    async fn generate(&self, prompt: &str) -> Result<LlmResponse> {
+        *self.0.lock().unwrap() = prompt.to_string();
+        Ok(LlmResponse {
+            content: "summary".into(),
+            token_usage: 1,
+            cached: false,
+        })
+    }
+}
+
+const HANDLER: &str = r#"package api
+
+func lookup(id string) {
+	rows, err := db.Query(fmt.Sprintf("SELECT name FROM users WHERE id = %s", id))
+	handle(rows, err)
+}
+
+func lookupByEmail(email string) {
+	rows, err := db.Query(fmt.Sprintf("SELECT name FROM users WHERE email = %s", email))
+	handle(rows, err)
+}
+"#;
+
+async fn seeded_index(dir: &std::path::Path) -> std::path::PathBuf {
+    let repo = dir.join("repo");
+    fs::create_dir_all(repo.join("store")).unwrap();
+    fs::create_dir_all(repo.join("web")).unwrap();
+    fs::write(
+        repo.join("store/users.go"),
+        "func FindUser(id string) {\n\tdb.QueryRow(\"SELECT name FROM users WHERE id = $1\", id)\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("web/page.go"),
+        "func Render(w http.ResponseWriter) {\n\ttemplate.Execute(w, nil)\n}\n",
+    )
+    .unwrap();
+    let index = dir.join("index.json.zst");
+    let options = IndexOptions {
+        embedder: Arc::new(KeywordEmbedder),
+        ..IndexOptions::default()
+    };
+    index_repository_with(&repo, &index, true, &["**/*.go".into()], &[], &options)
+        .await
+        .unwrap();
+    index
+}
+
+#[tokio::test]
+async fn snippet_query_retrieves_related_file_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = seeded_index(dir.path()).await;
+
+    let mut config = Config {
+        index: Some(IndexConfig {
+            path: index.to_string_lossy().into_owned(),
+            ..IndexConfig::default()
+        }),
+        ..Config::default()
+    };
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config.llm.api_key = Some("key".into());
+
+    let prompt = Arc::new(Mutex::new(String::new()));
+    let engine = ReviewEngine::new(config)
+        .unwrap()
+        .with_llm_provider(Box::new(CapturingProvider(prompt.clone())))
+        .with_embedder(Arc::new(KeywordEmbedder));
+
+    let added: String = HANDLER.lines().flat_map(|l| ["+", l, "\n"]).collect();
+    let diff = format!(
+        "diff --git a/api/handler.go b/api/handler.go\n--- a/api/handler.go\n+++ b/api/handler.go\n@@ -0,0 +1,{} @@\n{}",
+        HANDLER.lines().count(),
+        added
+    );
+    let mut files = HashMap::new();
+    files.insert("api/handler.go".to_string(), HANDLER.to_string());
+
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    assert_eq!(report.issues.len(), 2);
+    assert!(report.metadata.index_warm);
+
+    let prompt = prompt.lock().unwrap();
+    let context = prompt.split("Context:").nth(1).unwrap();
+    // Both findings retrieve the store file, but it is listed only once.
+    assert_eq!(context.matches("store/users.go").count(), 1, "{}", context);
+    assert!(!context.contains("web/page.go"), "{}", context);
+}
+
+#[tokio::test]
+async fn description_query_retrieves_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = seeded_index(dir.path()).await;
+    let store = InMemoryVectorStore::load_from_disk(&index).unwrap();
+    let rag = RagContextRetriever::for_index(store, Arc::new(KeywordEmbedder)).unwrap();
+
+    let description = "api/handler.go:4 Dynamic SQL query construction detected. \
+                       Use parameterized queries instead."
+        .to_string();
+    assert_eq!(rag.retrieve_merged(&[description]).await.unwrap(), None);
+
+    let snippet = HANDLER.lines().nth(3).unwrap().to_string();
+    let context = rag.retrieve_merged(&[snippet]).await.unwrap().unwrap();
+    assert!(context.starts_with("1. store/users.go"), "{}", context);
+}
@@ -0,0 +1,123 @@
+use engine::rag::{Document, InMemoryVectorStore, VectorStore};
+
+/// Deterministic pseudo-random vectors (64-bit LCG) so the corpus is fixed.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> f32 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 40) as f32 / (1u64 << 24) as f32) - 0.5
+    }
+
+    fn vector(&mut self, dim: usize) -> Vec<f32> {
+        (0..dim).map(|_| self.next()).collect()
+    }
+}
+
+fn doc(name: String, embedding: Vec<f32>) -> Document {
+    Document {
+        filename: name,
+        content: String::new(),
+        embedding,
+        language: None,
+        function_signatures: vec![],
+        log_patterns: vec![],
+        error_snippets: vec![],
+        modified: 0,
+    }
+}
+
+fn corpus(rng: &mut Lcg, docs: usize, dim: usize) -> InMemoryVectorStore {
+    let mut store = InMemoryVectorStore::default();
+    for i in 0..docs {
+        store.push_document(doc(format!("doc{i}"), rng.vector(dim)));
+    }
+    store
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let na: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let nb: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    dot / (na * nb)
+}
+
+/// Asserts both result lists have the same scores rank by rank, so they can
+/// only differ where documents tie.
+fn assert_same_ranking(query: &[f32], fast: &[Document], exact: &[Document]) {
+    assert_eq!(fast.len(), exact.len());
+    for (f, e) in fast.iter().zip(exact) {
+        let (sf, se) = (cosine(query, &f.embedding), cosine(query, &e.embedding));
+        assert!(
+            (sf - se).abs() < 1e-5,
+            "{} ({}) vs {} ({})",
+            f.filename,
+            sf,
+            e.filename,
+            se
+        );
+    }
+}
+
+#[tokio::test]
+async fn search_matches_exact_scan_on_seeded_corpus() {
+    let mut rng = Lcg(42);
+    let store = corpus(&mut rng, 3_000, 64);
+    for _ in 0..25 {
+        let query = rng.vector(64);
+        let fast = store.search(query.clone(), 10).await.unwrap();
+        let exact = store.search_exact(&query, 10);
+        assert_same_ranking(&query, &fast, &exact);
+        let names = |docs: &[Document]| docs.iter().map(|d| d.filename.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&fast), names(&exact));
+    }
+}
+
+#[tokio::test]
+async fn search_batch_matches_individual_searches() {
+    let mut rng = Lcg(7);
+    let store = corpus(&mut rng, 500, 32);
+    let queries: Vec<Vec<f32>> = (0..20).map(|_| rng.vector(32)).collect();
+
+    let batch = store.search_batch(queries.clone(), 5).await.unwrap();
+    assert_eq!(batch.len(), queries.len());
+    for (query, results) in queries.iter().zip(&batch) {
+        let exact = store.search_exact(query, 5);
+        assert_same_ranking(query, results, &exact);
+    }
+}
+
+#[tokio::test]
+async fn add_invalidates_the_search_matrix() {
+    let mut rng = Lcg(3);
+    let mut store = corpus(&mut rng, 100, 16);
+    let query = rng.vector(16);
+    let before = store.search(query.clone(), 1).await.unwrap();
+    assert_ne!(before[0].filename, "exact-match");
+
+    store
+        .add(doc("exact-match".into(), query.clone()))
+        .await
+        .unwrap();
+    let after = store.search(query, 1).await.unwrap();
+    assert_eq!(after[0].filename, "exact-match");
+}
+
+#[tokio::test]
+async fn zero_and_mismatched_embeddings_score_zero_like_exact_scan() {
+    let mut store = InMemoryVectorStore::default();
+    store.push_document(doc("zero".into(), vec![0.0; 4]));
+    store.push_document(doc("short".into(), vec![1.0; 2]));
+    store.push_document(doc("match".into(), vec![1.0, 0.0, 0.0, 0.0]));
+    store.push_document(doc("opposite".into(), vec![-1.0, 0.0, 0.0, 0.0]));
+
+    let query = vec![1.0, 0.0, 0.0, 0.0];
+    let fast = store.search(query.clone(), 4).await.unwrap();
+    let exact = store.search_exact(&query, 4);
+    let names = |docs: &[Document]| docs.iter().map(|d| d.filename.clone()).collect::<Vec<_>>();
+    assert_eq!(names(&fast), vec!["match", "zero", "short", "opposite"]);
+    assert_eq!(names(&fast), names(&exact));
+}
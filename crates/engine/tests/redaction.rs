@@ -1,4 +1,5 @@
-use engine::config::Config;
+use engine::config::{Config, RedactionRule};
+use engine::privacy::Redactor;
 use engine::redact_text;
 
 #[test]
@@ -27,3 +28,55 @@ fn respects_disabled_redaction() {
     let output = redact_text(&config, input);
     assert_eq!(output, input);
 }
+
+#[test]
+fn redacts_only_first_capture_group() {
+    let mut config = Config::default();
+    config.privacy.redaction.patterns = vec![r#"api_key\s*=\s*"(\S+)""#.to_string()];
+    let output = redact_text(&config, r#"api_key = "abc123" is set"#);
+    assert_eq!(output, r#"api_key = "[REDACTED]" is set"#);
+}
+
+#[test]
+fn partial_rule_keeps_ends_of_value() {
+    let mut config = Config::default();
+    config.privacy.redaction.patterns.clear();
+    config.privacy.redaction.rules.push(RedactionRule {
+        pattern: r"sk_\w+".to_string(),
+        replace: None,
+        partial: true,
+    });
+    let output = redact_text(&config, "key sk_live_0123456789abf3a9 used");
+    assert_eq!(output, "key sk\u{2026}a9 used");
+
+    // Short values are masked entirely so little of the secret leaks.
+    let output = redact_text(&config, "key sk_1234 used");
+    assert_eq!(output, "key [REDACTED] used");
+}
+
+#[test]
+fn structured_rules_load_alongside_legacy_patterns() {
+    let toml = r#"
+[privacy.redaction]
+enabled = true
+patterns = ["password"]
+
+[[privacy.redaction.rules]]
+pattern = 'secret=(\w+)'
+replace = "***"
+"#;
+    let config: Config = toml::from_str(toml).unwrap();
+    assert_eq!(config.privacy.redaction.patterns, vec!["password"]);
+    assert_eq!(config.privacy.redaction.rules.len(), 1);
+    let output = redact_text(&config, "password secret=hunter2");
+    assert_eq!(output, "[REDACTED] secret=***");
+}
+
+#[test]
+fn json_redaction_keeps_keys() {
+    let redactor = Redactor::new(&Config::default().privacy.redaction).unwrap();
+    let json = r#"{"stats":{"tokens_used":3},"summary":"rotate the token"}"#;
+    let output: serde_json::Value = serde_json::from_str(&redactor.redact_json(json)).unwrap();
+    assert_eq!(output["stats"]["tokens_used"], 3);
+    assert_eq!(output["summary"], "rotate the [REDACTED]");
+}
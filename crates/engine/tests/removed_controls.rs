@@ -0,0 +1,130 @@
+use engine::config::{Config, Severity};
+use engine::diff_parser;
+use engine::scanner::{DiffScanner, RemovedControlsScanner};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+const DIFF: &str = r#"diff --git a/server.js b/server.js
+--- a/server.js
++++ b/server.js
+@@ -1,5 +1,4 @@
+ const app = express();
+-app.use(authenticate);
+ app.use(logger);
+ app.get("/", index);
+ app.listen(8080);
+"#;
+
+fn scan(diff: &str, config: &Config) -> Vec<engine::scanner::Issue> {
+    let files = diff_parser::parse(diff).unwrap();
+    RemovedControlsScanner.scan_diff(&files[0], config).unwrap()
+}
+
+#[test]
+fn flags_deleted_control_at_nearest_new_line() {
+    let issues = scan(DIFF, &Config::default());
+    assert_eq!(issues.len(), 1);
+    let issue = &issues[0];
+    assert_eq!(issue.title, "Security Control Removed");
+    assert_eq!(issue.file_path, "server.js");
+    // The deleted line sat above `app.use(logger);`, now line 2.
+    assert_eq!(issue.line_number, 2);
+    assert_eq!(issue.severity, Severity::High);
+    assert!(
+        issue.description.contains("old line 2"),
+        "{}",
+        issue.description
+    );
+    assert_eq!(issue.diff.as_deref(), Some("-app.use(authenticate);"));
+}
+
+#[test]
+fn ignores_controls_that_are_moved_or_reworded() {
+    let diff = r#"diff --git a/forms.py b/forms.py
+--- a/forms.py
++++ b/forms.py
+@@ -1,3 +1,3 @@
+ def submit(request):
+-    validate(request.form)
++    validate(request.form, strict=True)
+     save(request.form)
+"#;
+    assert!(scan(diff, &Config::default()).is_empty());
+}
+
+#[test]
+fn removal_at_end_of_hunk_points_at_last_line() {
+    let diff = r#"diff --git a/lib.rs b/lib.rs
+--- a/lib.rs
++++ b/lib.rs
+@@ -1,2 +1,1 @@
+ pub fn run() {}
+-#[deny(unsafe_code)]
+"#;
+    let mut config = Config::default();
+    config.rules.removed_controls.patterns = vec![r"#\[deny\(".into()];
+    let issues = scan(diff, &config);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].line_number, 1);
+}
+
+#[test]
+fn deleted_file_is_reported_once_at_its_old_path() {
+    let diff = r#"diff --git a/middleware/auth.js b/middleware/auth.js
+deleted file mode 100644
+index 3b18e51..0000000
+--- a/middleware/auth.js
++++ /dev/null
+@@ -1,4 +0,0 @@
+-const csrf = require("csurf");
+-module.exports = (app) => {
+-  app.use(authenticate);
+-};
+"#;
+    let issues = scan(diff, &Config::default());
+    assert_eq!(issues.len(), 1, "{:?}", issues);
+    let issue = &issues[0];
+    assert_eq!(issue.file_path, "middleware/auth.js");
+    assert_eq!(issue.line_number, 1);
+    assert!(
+        issue
+            .description
+            .contains("(old line 1); 2 removed lines match"),
+        "{}",
+        issue.description
+    );
+}
+
+#[test]
+fn invalid_pattern_is_reported_by_validation() {
+    let mut config = Config::default();
+    config.rules.removed_controls.patterns = vec!["(".into()];
+    let problems = config.validate();
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].path, "rules.removed-controls.patterns[0]");
+}
+
+#[tokio::test]
+async fn engine_reports_and_suppresses_removed_controls() {
+    let engine = ReviewEngine::new(Config::default()).unwrap();
+
+    let content =
+        "const app = express();\napp.use(logger);\napp.get(\"/\", index);\napp.listen(8080);\n";
+    let mut files = HashMap::new();
+    files.insert("server.js".to_string(), content.to_string());
+    let report = engine.run_with_provider(DIFF, &files).await.unwrap();
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].line_number, 2);
+
+    let suppressed = "const app = express();\napp.use(logger); // reviewlens:ignore removed-controls auth moved to gateway\napp.get(\"/\", index);\napp.listen(8080);\n";
+    files.insert("server.js".to_string(), suppressed.to_string());
+    let report = engine.run_with_provider(DIFF, &files).await.unwrap();
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+    assert_eq!(report.suppressed.len(), 1);
+    assert_eq!(report.suppressed[0].rule, "removed-controls");
+    assert_eq!(report.suppressed[0].line, 2);
+    assert_eq!(
+        report.suppressed[0].reason.as_deref(),
+        Some("auth moved to gateway")
+    );
+}
@@ -18,9 +18,14 @@ fn markdown_generator_no_issues() {
             ruleset_version: "v1".into(),
             model: Some("test-model".into()),
             driver: "null".into(),
-            timings: TimingInfo { total_ms: 0 },
+            timings: TimingInfo {
+                total_ms: 0,
+                ..Default::default()
+            },
             index_warm: true,
+            ..Default::default()
         },
+        ..Default::default()
     };
     let md = generator.generate(&report).unwrap();
     assert!(md.contains("✅ No issues found."));
@@ -40,6 +45,7 @@ fn markdown_generator_with_issues() {
         severity: Severity::High,
         suggested_fix: Some("Apply the recommended change".into()),
         diff: Some("-old\n+new".into()),
+        ..Default::default()
     };
     let report = ReviewReport {
         summary: "Issues".into(),
@@ -52,9 +58,14 @@ fn markdown_generator_with_issues() {
             ruleset_version: "v1".into(),
             model: Some("test-model".into()),
             driver: "null".into(),
-            timings: TimingInfo { total_ms: 0 },
+            timings: TimingInfo {
+                total_ms: 0,
+                ..Default::default()
+            },
             index_warm: false,
+            ..Default::default()
         },
+        ..Default::default()
     };
     let md = generator.generate(&report).unwrap();
     assert!(md.contains("Test issue"));
@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use engine::config::{Config, IndexConfig, Provider};
+use engine::error::Result;
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::rag::{index_repository_with, Embedder, IndexOptions};
+use engine::report::ReviewReport;
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+
+/// Counts occurrences of a fixed vocabulary, so only files sharing words
+/// with a finding are retrieved for it.
+struct KeywordEmbedder;
+
+const VOCABULARY: [&str; 3] = ["SELECT", "users", "template"];
+
+#[async_trait]
+impl Embedder for KeywordEmbedder {
+    fn name(&self) -> String {
+        "keyword".into()
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        Ok(VOCABULARY
+            .iter()
+            .map(|word| text.matches(word).count() as f32)
+            .collect())
+    }
+}
+
+struct StaticProvider;
+
+#[async_trait]
+impl LlmProvider for StaticProvider {
+    async fn generate(&self, _prompt: &str) -> Result<LlmResponse> {
+        Ok(LlmResponse {
+            content: "summary".into(),
+            token_usage: 1,
+            cached: false,
+        })
+    }
+}
+
+const HANDLER: &str =
+    "func lookup(id string) {\n\tdb.Query(fmt.Sprintf(\"SELECT name FROM users WHERE id = %s\", id))\n}\n";
+
+async fn review(dir: &std::path::Path, configure: impl FnOnce(&mut Config)) -> ReviewReport {
+    let repo = dir.join("repo");
+    fs::create_dir_all(&repo).unwrap();
+    fs::write(
+        repo.join("users.go"),
+        "// password hunter2\nfunc FindUser(id string) {\n\tdb.QueryRow(\"SELECT name FROM users WHERE id = $1\", id)\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("page.go"),
+        "func Render() { template.Execute() }\n",
+    )
+    .unwrap();
+    let index = dir.join("index.json.zst");
+    let options = IndexOptions {
+        embedder: Arc::new(KeywordEmbedder),
+        ..IndexOptions::default()
+    };
+    index_repository_with(&repo, &index, true, &["**/*.go".into()], &[], &options)
+        .await
+        .unwrap();
+
+    let mut config = Config {
+        index: Some(IndexConfig {
+            path: index.to_string_lossy().into_owned(),
+            ..IndexConfig::default()
+        }),
+        ..Config::default()
+    };
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config.llm.api_key = Some("key".into());
+    config.privacy.redaction.patterns = vec!["hunter2".into()];
+    configure(&mut config);
+
+    let engine = ReviewEngine::new(config)
+        .unwrap()
+        .with_llm_provider(Box::new(StaticProvider))
+        .with_embedder(Arc::new(KeywordEmbedder));
+    let added: String = HANDLER.lines().flat_map(|l| ["+", l, "\n"]).collect();
+    let diff = format!(
+        "diff --git a/api.go b/api.go\n--- a/api.go\n+++ b/api.go\n@@ -0,0 +1,3 @@\n{}",
+        added
+    );
+    let files = HashMap::from([("api.go".to_string(), HANDLER.to_string())]);
+    engine.run_with_provider(&diff, &files).await.unwrap()
+}
+
+#[tokio::test]
+async fn include_context_lists_redacted_truncated_excerpts() {
+    let dir = tempfile::tempdir().unwrap();
+    let report = review(dir.path(), |config| {
+        config.report.include_context = true;
+        config.report.context_excerpt_chars = 30;
+    })
+    .await;
+
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.contexts.len(), 1, "{:?}", report.contexts);
+    let context = &report.contexts[0];
+    assert_eq!(context.query_issue_index, 0);
+    assert_eq!(context.filename, "users.go");
+    assert!(context.score > 0.0);
+    assert_eq!(context.excerpt, "// password [REDACTED]\nfunc Fi\u{2026}");
+
+    let json = engine::render_report(&report, engine::Format::Json).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["contexts"][0]["filename"], "users.go");
+    assert_eq!(value["contexts"][0]["query_issue_index"], 0);
+    assert!(!json.contains("hunter2"));
+
+    let markdown = engine::render_report(&report, engine::Format::Markdown).unwrap();
+    assert!(markdown.contains("### Retrieved Context"), "{}", markdown);
+    assert!(markdown.contains("<summary>Documents given to the LLM: 1</summary>"));
+    assert!(markdown.contains("**`users.go`** (score "), "{}", markdown);
+    assert!(!markdown.contains("hunter2"));
+}
+
+#[tokio::test]
+async fn context_is_left_out_by_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let report = review(dir.path(), |_| {}).await;
+
+    assert!(report.contexts.is_empty());
+    let json = engine::render_report(&report, engine::Format::Json).unwrap();
+    assert!(!json.contains("\"contexts\""));
+}
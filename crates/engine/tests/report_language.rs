@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use engine::config::{Config, Provider};
+use engine::error::Result;
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::report::i18n;
+use engine::report::{MarkdownGenerator, ReportGenerator, ReviewReport};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const SECRET: &str = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+
+struct RecordingProvider(Arc<Mutex<Vec<String>>>);
+
+#[async_trait]
+impl LlmProvider for RecordingProvider {
+    async fn generate(&self, prompt: &str) -> Result<LlmResponse> {
+        self.0.lock().unwrap().push(prompt.to_string());
+        Ok(LlmResponse {
+            content: "要約".into(),
+            token_usage: 1,
+            cached: false,
+        })
+    }
+}
+
+/// Reviews a newly added file holding a secret with the report in
+/// `language`, returning the report and the prompts sent to the LLM.
+async fn review(language: &str) -> (ReviewReport, Vec<String>) {
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config.report.language = language.into();
+    let prompts = Arc::new(Mutex::new(Vec::new()));
+    let engine = ReviewEngine::builder(config)
+        .llm(Box::new(RecordingProvider(prompts.clone())))
+        .build()
+        .unwrap();
+    let diff = format!(
+        "diff --git a/config.js b/config.js\n--- a/config.js\n+++ b/config.js\n@@ -0,0 +1 @@\n+{}\n",
+        SECRET
+    );
+    let mut files = HashMap::new();
+    files.insert("config.js".to_string(), format!("{}\n", SECRET));
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    let prompts = prompts.lock().unwrap().clone();
+    (report, prompts)
+}
+
+#[tokio::test]
+async fn headings_follow_the_report_language() {
+    let (report, _) = review("en").await;
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(md.starts_with("# Code Review Report\n"));
+    assert!(md.contains("## 🚨 Security Findings"));
+    assert!(
+        md.contains("| Severity | Confidence | Title | File:Line | Description | Suggested Fix |")
+    );
+
+    let (report, _) = review("ja").await;
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(md.starts_with("# コードレビューレポート\n"), "{}", md);
+    assert!(md.contains("## 概要\n\n要約"));
+    assert!(md.contains("## 🚨 セキュリティ上の指摘"));
+    assert!(md.contains("| 重大度 | 確信度 | タイトル | ファイル:行 | 説明 | 修正案 |"));
+    assert!(md.contains("ホットスポット"));
+    assert!(!md.contains("Security Findings"));
+}
+
+#[tokio::test]
+async fn prompt_asks_for_the_report_language() {
+    let (_, prompts) = review("ja-JP").await;
+    assert_eq!(prompts.len(), 1);
+    assert!(
+        prompts[0].contains("Write the summary and every comment in Japanese (`ja-JP`)."),
+        "{}",
+        prompts[0]
+    );
+
+    let (_, prompts) = review("en").await;
+    assert!(!prompts[0].contains("Write the summary and every comment"));
+}
+
+#[test]
+fn missing_translations_fall_back_to_english() {
+    assert_eq!(i18n::text("fr", "no-issues"), "✅ No issues found.");
+    assert_eq!(
+        i18n::text("ja", "no-issues"),
+        "✅ 問題は見つかりませんでした。"
+    );
+    assert_eq!(i18n::text("ja", "no-such-key"), "no-such-key");
+    // Languages without a table still get an LLM instruction.
+    assert!(i18n::prompt_instruction("fr")
+        .unwrap()
+        .contains("in French (`fr`)"));
+    assert!(i18n::prompt_instruction("en-GB").is_none());
+}
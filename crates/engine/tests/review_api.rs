@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use engine::config::{Config, Provider};
+use engine::error::Result;
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::{render_report, review_diff, Format, ReviewOptions};
+use std::collections::HashMap;
+
+const SECRET: &str = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+
+fn diff() -> String {
+    format!(
+        "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -0,0 +1 @@\n+{}\n",
+        SECRET
+    )
+}
+
+struct FixedProvider;
+
+#[async_trait]
+impl LlmProvider for FixedProvider {
+    async fn generate(&self, _prompt: &str) -> Result<LlmResponse> {
+        Ok(LlmResponse {
+            content: "summary from embedder".into(),
+            token_usage: 7,
+            cached: false,
+        })
+    }
+}
+
+#[tokio::test]
+async fn reviews_injected_contents_with_an_injected_provider() {
+    let mut files = HashMap::new();
+    files.insert("src/a.rs".to_string(), format!("{}\n", SECRET));
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    let options = ReviewOptions::new(config)
+        .root("/nonexistent")
+        .content(Box::new(files))
+        .llm(Box::new(FixedProvider));
+
+    let report = review_diff(&diff(), options).await.unwrap();
+
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.summary, "summary from embedder");
+    assert_eq!(report.stats.tokens_used, 7);
+}
+
+#[tokio::test]
+async fn rendered_reports_are_redacted() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/a.rs"), format!("{}\n", SECRET)).unwrap();
+    let options = ReviewOptions::new(Config::default())
+        .root(dir.path())
+        .format(Format::Markdown);
+    let format = options.format;
+
+    let report = review_diff(&diff(), options).await.unwrap();
+
+    for format in [format, Format::Json] {
+        let rendered = render_report(&report, format).unwrap();
+        assert!(rendered.contains("src/a.rs"), "{}", rendered);
+        assert!(
+            !rendered.contains("ABCDEFGHIJKLMNOPQRSTUVWX"),
+            "{}",
+            rendered
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use engine::config::{Config, Provider};
+use engine::context::ReviewContext;
+use engine::error::{EngineError, Result};
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::report::{MarkdownGenerator, ReportGenerator, ReviewReport};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+fn context(title: &str, body: &str) -> ReviewContext {
+    ReviewContext {
+        title: Some(title.into()),
+        body: Some(body.into()),
+    }
+}
+
+#[test]
+fn pull_request_events_are_parsed() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("event.json");
+    fs::write(
+        &path,
+        r#"{"action": "opened", "pull_request": {"number": 7, "title": "fix(db): close connections", "body": "Closes pooled connections on shutdown."}}"#,
+    )
+    .unwrap();
+    let parsed = ReviewContext::from_github_event(&path).unwrap().unwrap();
+    assert_eq!(
+        parsed,
+        context(
+            "fix(db): close connections",
+            "Closes pooled connections on shutdown."
+        )
+    );
+
+    // GitHub sends `null` for an empty description.
+    fs::write(
+        &path,
+        r#"{"pull_request": {"title": "Docs", "body": null}}"#,
+    )
+    .unwrap();
+    let parsed = ReviewContext::from_github_event(&path).unwrap().unwrap();
+    assert_eq!(parsed.title.as_deref(), Some("Docs"));
+    assert_eq!(parsed.body, None);
+}
+
+#[test]
+fn events_without_a_pull_request_have_no_context() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("event.json");
+    fs::write(&path, r#"{"ref": "refs/heads/main", "commits": []}"#).unwrap();
+    assert_eq!(ReviewContext::from_github_event(&path).unwrap(), None);
+
+    fs::write(&path, "not json").unwrap();
+    assert!(matches!(
+        ReviewContext::from_github_event(&path),
+        Err(EngineError::Config(_))
+    ));
+}
+
+#[test]
+fn conventional_commit_types_are_recognized() {
+    let kind = |title: &str| context(title, "").change_type().map(str::to_string);
+    assert_eq!(kind("feat: add SSO").as_deref(), Some("feat"));
+    assert_eq!(
+        kind("fix(auth)!: reject expired tokens").as_deref(),
+        Some("fix")
+    );
+    assert_eq!(kind("Update README"), None);
+    assert_eq!(kind("Note: this is not a type"), None);
+    assert_eq!(kind("feat(auth: broken scope"), None);
+}
+
+/// Answers every prompt with `response` and records the prompts.
+struct RecordingProvider {
+    response: String,
+    prompts: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl LlmProvider for RecordingProvider {
+    async fn generate(&self, prompt: &str) -> Result<LlmResponse> {
+        self.prompts.lock().unwrap().push(prompt.to_string());
+        Ok(LlmResponse {
+            content: self.response.clone(),
+            token_usage: 1,
+            cached: false,
+        })
+    }
+}
+
+async fn review(
+    context: Option<ReviewContext>,
+    response: &str,
+    structured: bool,
+) -> (ReviewReport, Vec<String>) {
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config.llm.structured_output = structured;
+    let prompts = Arc::new(Mutex::new(Vec::new()));
+    let mut engine = ReviewEngine::builder(config)
+        .llm(Box::new(RecordingProvider {
+            response: response.to_string(),
+            prompts: prompts.clone(),
+        }))
+        .build()
+        .unwrap();
+    if let Some(context) = context {
+        engine = engine.with_context(context);
+    }
+
+    let line = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+    let diff = format!(
+        "diff --git a/key.rs b/key.rs\n--- a/key.rs\n+++ b/key.rs\n@@ -0,0 +1 @@\n+{}\n",
+        line
+    );
+    let mut files = HashMap::new();
+    files.insert("key.rs".to_string(), format!("{}\n", line));
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    let prompts = prompts.lock().unwrap().clone();
+    (report, prompts)
+}
+
+#[tokio::test]
+async fn prompt_includes_the_redacted_context_and_asks_for_an_intent_note() {
+    let pr = context(
+        "feat(billing): add invoices",
+        "Adds invoice export.\nTest with api_key = \"ZYXWVUTSRQPONMLKJIHGFEDC\"",
+    );
+    let response = "Looks reasonable.\n\nIntent vs. implementation: key.rs is not mentioned.";
+    let (report, prompts) = review(Some(pr.clone()), response, false).await;
+
+    let prompt = &prompts[0];
+    assert!(
+        prompt.contains("Title: feat(billing): add invoices"),
+        "{}",
+        prompt
+    );
+    assert!(prompt.contains("Type: feat"));
+    assert!(prompt.contains("Adds invoice export."));
+    assert!(!prompt.contains("ZYXWVUTSRQPONMLKJIHGFEDC"));
+    assert!(prompt.contains("Changed files: key.rs"));
+    assert!(prompt.contains("\"Intent vs. implementation:\""));
+
+    assert_eq!(report.summary, "Looks reasonable.");
+    assert_eq!(
+        report.intent_note.as_deref(),
+        Some("key.rs is not mentioned.")
+    );
+    assert_eq!(report.context, Some(pr));
+
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(md.contains("## Change Context"));
+    assert!(md.contains("**Title:** feat(billing): add invoices"));
+    assert!(md.contains("**Type:** `feat`"));
+    assert!(md.contains("**Intent vs. implementation:** key.rs is not mentioned."));
+}
+
+#[tokio::test]
+async fn structured_responses_carry_the_intent_note() {
+    let response = r#"{"summary": "One key.", "suggestions": [], "intent": "The key is unrelated to the title."}"#;
+    let (report, prompts) = review(Some(context("Refactor", "")), response, true).await;
+    assert!(prompts[0].contains("\"intent\" field"), "{}", prompts[0]);
+    assert_eq!(report.summary, "One key.");
+    assert_eq!(
+        report.intent_note.as_deref(),
+        Some("The key is unrelated to the title.")
+    );
+}
+
+#[tokio::test]
+async fn without_context_the_prompt_and_report_are_unchanged() {
+    let response = "Summary.\n\nIntent vs. implementation: n/a";
+    let (report, prompts) = review(Some(ReviewContext::default()), response, false).await;
+    assert!(!prompts[0].contains("The author describes"));
+    assert!(!prompts[0].contains("Intent vs. implementation"));
+    assert_eq!(report.summary, response);
+    assert!(report.context.is_none());
+    assert!(report.intent_note.is_none());
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(!md.contains("## Change Context"));
+}
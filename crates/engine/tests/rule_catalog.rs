@@ -0,0 +1,124 @@
+use engine::config::{Config, ExternalScannerConfig, Severity};
+use engine::scanner::{
+    descriptor_for_rule, register_scanner, registered_rule_names, rule_applies_to, rule_catalog,
+    Issue, Scanner,
+};
+
+struct TodoScanner;
+
+impl Scanner for TodoScanner {
+    fn name(&self) -> &'static str {
+        "TODO Scanner"
+    }
+
+    fn scan(
+        &self,
+        _file_path: &str,
+        _content: &str,
+        _config: &Config,
+    ) -> engine::error::Result<Vec<Issue>> {
+        Ok(vec![])
+    }
+}
+
+fn external(rule: &str) -> ExternalScannerConfig {
+    ExternalScannerConfig {
+        rule: rule.into(),
+        command: "./scripts/no-print".into(),
+        args: vec![],
+        enabled: true,
+        severity: Severity::Low,
+        timeout_secs: 30,
+        options: Default::default(),
+    }
+}
+
+#[test]
+fn every_built_in_rule_is_listed_with_its_default_severity() {
+    let config = Config::default();
+    let catalog = rule_catalog(&config);
+    let ids: Vec<&str> = catalog.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(
+        &ids[..9],
+        [
+            "secrets",
+            "sql-injection-go",
+            "http-timeouts-go",
+            "conventions",
+            "network-hygiene",
+            "todo",
+            "complexity",
+            "removed-controls",
+            "artifacts",
+        ]
+    );
+    for id in registered_rule_names() {
+        assert!(ids.contains(&id), "{} missing", id);
+    }
+
+    let rules = &config.rules;
+    let defaults = [
+        rules.secrets.severity.clone(),
+        rules.sql_injection_go.severity.clone(),
+        rules.http_timeouts_go.severity.clone(),
+        rules.conventions.severity.clone(),
+        rules.network_hygiene.severity.clone(),
+        rules.todo.severity.clone(),
+        rules.complexity.severity.clone(),
+        rules.removed_controls.severity.clone(),
+        rules.artifacts.severity.clone(),
+    ];
+    for (rule, severity) in catalog.iter().zip(defaults) {
+        assert_eq!(rule.default_severity, Some(severity), "{}", rule.id);
+        assert!(rule.enabled);
+        assert!(!rule.description.is_empty());
+    }
+    assert_eq!(catalog[1].languages, ["go"]);
+    assert!(catalog[0].languages.is_empty());
+}
+
+#[test]
+fn rules_only_apply_to_their_languages() {
+    assert!(rule_applies_to("sql-injection-go", "api/users.go"));
+    assert!(!rule_applies_to("sql-injection-go", "api/users.py"));
+    assert!(rule_applies_to("complexity", "web/app.tsx"));
+    assert!(!rule_applies_to("complexity", "README"));
+    assert!(rule_applies_to("secrets", "config/prod.env"));
+    assert!(rule_applies_to("no-print", "app.rb"));
+}
+
+#[test]
+fn catalog_reflects_the_configuration() {
+    let mut config = Config::default();
+    config.rules.conventions.enabled = false;
+    config.scanners.external.push(external("no-print"));
+    let catalog = rule_catalog(&config);
+    let conventions = catalog.iter().find(|r| r.id == "conventions").unwrap();
+    assert!(!conventions.enabled);
+
+    let last = catalog.last().unwrap();
+    assert_eq!(last.id, "no-print");
+    assert_eq!(last.name, "External Scanner (no-print)");
+    assert_eq!(last.description, "Runs `./scripts/no-print`.");
+    assert_eq!(last.default_severity, Some(Severity::Low));
+    assert!(last.enabled);
+}
+
+#[test]
+fn custom_scanners_register_through_descriptors() {
+    register_scanner("fixme", || Box::new(TodoScanner));
+    let config = Config::default();
+    let descriptor = descriptor_for_rule(&config, "fixme").unwrap();
+    assert_eq!(descriptor.id, "fixme");
+    assert_eq!(descriptor.default_severity, None);
+    assert_eq!(descriptor.scanner().unwrap().name(), "TODO Scanner");
+    assert!(descriptor.diff_scanner().is_none());
+
+    let catalog = rule_catalog(&config);
+    let todo = catalog.iter().find(|r| r.id == "fixme").unwrap();
+    assert!(todo.enabled);
+    assert!(descriptor_for_rule(&config, "removed-controls")
+        .unwrap()
+        .diff_scanner()
+        .is_some());
+}
@@ -0,0 +1,159 @@
+use engine::config::{Config, Severity};
+use engine::report::{
+    summarize_rules, JsonGenerator, MarkdownGenerator, ReportGenerator, ReviewReport,
+    RuntimeMetadata, TimingInfo,
+};
+use engine::scanner::Issue;
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+mod common;
+use common::file_diff;
+
+const SQL_FIX: &str = "Use parameterized queries instead of string concatenation.";
+
+fn issue(rule: &str, severity: Severity, path: &str, line: usize, fix: &str) -> Issue {
+    Issue {
+        title: format!("{} finding", rule),
+        description: format!("{} detected.", rule),
+        file_path: path.into(),
+        line_number: line,
+        severity,
+        suggested_fix: Some(fix.into()),
+        rule_id: rule.into(),
+        ..Default::default()
+    }
+}
+
+/// 20 findings from three rules: 12 secrets in 12 files, 5 SQL injections
+/// in two files (one with an odd fix) and 3 missing timeouts in one file.
+fn issues() -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for i in 0..12 {
+        let path = format!("config/{:02}.py", i);
+        issues.push(issue("secrets", Severity::High, &path, 1, "Rotate it."));
+    }
+    for line in 1..=4 {
+        issues.push(issue(
+            "sql-injection-go",
+            Severity::High,
+            "db.go",
+            line,
+            SQL_FIX,
+        ));
+    }
+    issues.push(issue(
+        "sql-injection-go",
+        Severity::Critical,
+        "store.go",
+        9,
+        "Escape it.",
+    ));
+    for line in 1..=3 {
+        issues.push(issue(
+            "http-timeouts-go",
+            Severity::Medium,
+            "client.go",
+            line,
+            "Set a timeout.",
+        ));
+    }
+    issues
+}
+
+fn report(issues: Vec<Issue>) -> ReviewReport {
+    ReviewReport {
+        summary: "Summary".into(),
+        rules: summarize_rules(&issues),
+        issues,
+        code_quality: Vec::new(),
+        hotspots: Vec::new(),
+        mermaid_diagram: None,
+        config: Config::default(),
+        metadata: RuntimeMetadata {
+            ruleset_version: "v1".into(),
+            model: None,
+            driver: "null".into(),
+            timings: TimingInfo {
+                total_ms: 0,
+                ..Default::default()
+            },
+            index_warm: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn findings_are_grouped_by_rule() {
+    let rules = summarize_rules(&issues());
+    let ids: Vec<&str> = rules.iter().map(|r| r.rule.as_str()).collect();
+    assert_eq!(ids, ["sql-injection-go", "secrets", "http-timeouts-go"]);
+
+    let sql = &rules[0];
+    assert_eq!(sql.count, 5);
+    assert_eq!(sql.severity, Severity::Critical);
+    assert_eq!(sql.files, ["db.go", "store.go"]);
+    assert_eq!(sql.suggested_fix.as_deref(), Some(SQL_FIX));
+    assert_eq!(sql.title, "sql-injection-go finding");
+
+    assert_eq!(rules[1].count, 12);
+    assert_eq!(rules[1].files.len(), 12);
+    assert_eq!(rules[2].count, 3);
+    assert_eq!(rules.iter().map(|r| r.count).sum::<usize>(), 20);
+}
+
+#[test]
+fn markdown_lists_each_fix_once() {
+    let md = MarkdownGenerator.generate(&report(issues())).unwrap();
+    assert!(md.contains("## 🛠️ Suggested Fixes"), "{}", md);
+    assert_eq!(md.matches(SQL_FIX).count(), 1, "{}", md);
+    assert_eq!(md.matches("Rotate it.").count(), 1);
+    assert_eq!(
+        md.matches("| See `sql-injection-go` in Suggested Fixes |")
+            .count(),
+        5
+    );
+    assert!(md.contains("### `secrets`: secrets finding"));
+    assert!(md.contains("`high` · 12 findings in 12 files"));
+    assert!(md.contains("- `config/09.py`\n\n<details>\n<summary>2 more files</summary>"));
+    assert!(md.contains("- `config/11.py`\n</details>"));
+}
+
+#[test]
+fn json_includes_the_rule_summaries() {
+    let report = report(issues());
+    let json = JsonGenerator.generate(&report).unwrap();
+    let parsed = ReviewReport::from_json(&json).unwrap();
+    assert_eq!(parsed.rules, report.rules);
+}
+
+#[tokio::test]
+async fn group_fixes_off_keeps_the_fix_on_each_row() {
+    let content = "resp, err := http.Get(a)\nresp, err = http.Get(b)\n";
+    let mut files = HashMap::new();
+    files.insert("client.go".to_string(), content.to_string());
+    let diff = file_diff("client.go", content);
+
+    let grouped = ReviewEngine::new(Config::default())
+        .unwrap()
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap();
+    assert_eq!(grouped.rules.len(), 1);
+    assert_eq!(grouped.rules[0].count, 2);
+
+    let mut config = Config::default();
+    config.report.group_fixes = false;
+    let report = ReviewEngine::new(config)
+        .unwrap()
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap();
+    assert!(report.rules.is_empty());
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(!md.contains("Suggested Fixes"));
+    let fix = report.issues[0].suggested_fix.as_deref().unwrap();
+    assert_eq!(md.matches(fix).count(), 2);
+}
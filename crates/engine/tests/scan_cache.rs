@@ -0,0 +1,153 @@
+use engine::config::{Config, Severity};
+use engine::error::Result;
+use engine::report::ReviewReport;
+use engine::scanner::{Issue, ScanCache, Scanner};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+/// Reports every line containing `bad`, changed or not, and counts scans.
+struct CountingScanner {
+    scans: Arc<AtomicUsize>,
+    version: u32,
+}
+
+impl Scanner for CountingScanner {
+    fn name(&self) -> &'static str {
+        "Counting Scanner"
+    }
+
+    fn scan(&self, file_path: &str, content: &str, _config: &Config) -> Result<Vec<Issue>> {
+        self.scans.fetch_add(1, Ordering::SeqCst);
+        Ok(content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.contains("bad"))
+            .map(|(i, _)| Issue {
+                title: "Bad".into(),
+                description: "A bad line.".into(),
+                file_path: file_path.to_string(),
+                line_number: i + 1,
+                severity: Severity::Medium,
+                ..Default::default()
+            })
+            .collect())
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+/// Reviews files whose first line is unchanged and second line added, with
+/// a fresh engine that caches at `cache`. Returns the report and the number
+/// of scans.
+async fn review(
+    cache: Option<&Path>,
+    version: u32,
+    files: &[(&str, &str)],
+) -> (ReviewReport, usize) {
+    let mut config = Config::default();
+    config.engine.scan_cache_path = cache.map(|p| p.to_string_lossy().into_owned());
+    config.engine.scan_cache_max_entries = 10;
+    // The interaction diagram plays no part here.
+    config.report.diagram = false;
+    let scans = Arc::new(AtomicUsize::new(0));
+    let engine = ReviewEngine::builder(config)
+        .scanners(vec![(
+            "counting",
+            Box::new(CountingScanner {
+                scans: scans.clone(),
+                version,
+            }),
+        )])
+        .build()
+        .unwrap();
+    let mut diff = String::new();
+    let mut contents = HashMap::new();
+    for (path, second) in files {
+        diff.push_str(&format!(
+            "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -1 +1,2 @@\n bad old\n+{second}\n"
+        ));
+        contents.insert(path.to_string(), format!("bad old\n{second}\n"));
+    }
+    let report = engine.run_with_provider(&diff, &contents).await.unwrap();
+    (report, scans.load(Ordering::SeqCst))
+}
+
+fn findings(report: &ReviewReport) -> Vec<(String, usize, String)> {
+    report
+        .issues
+        .iter()
+        .map(|i| (i.file_path.clone(), i.line_number, i.fingerprint.clone()))
+        .collect()
+}
+
+#[tokio::test]
+async fn second_run_reuses_cached_findings() {
+    let temp = tempdir().unwrap();
+    let cache = temp.path().join("cache/scan.json");
+    let files = [("a.rs", "bad new"), ("b.rs", "fine")];
+
+    let (first, scans) = review(Some(&cache), 1, &files).await;
+    assert_eq!(scans, 2);
+    // The unchanged first line is filtered out.
+    assert_eq!(
+        findings(&first)
+            .iter()
+            .map(|f| (f.0.as_str(), f.1))
+            .collect::<Vec<_>>(),
+        [("a.rs", 2)]
+    );
+    assert_eq!(ScanCache::open(&cache, 10).len(), 2);
+
+    let (second, scans) = review(Some(&cache), 1, &files).await;
+    assert_eq!(scans, 0);
+    assert_eq!(findings(&second), findings(&first));
+    assert_eq!(second.issues[0].rule_id, "counting");
+}
+
+#[tokio::test]
+async fn changed_content_and_versions_miss() {
+    let temp = tempdir().unwrap();
+    let cache = temp.path().join("scan.json");
+    review(Some(&cache), 1, &[("a.rs", "bad new")]).await;
+
+    let (report, scans) = review(Some(&cache), 1, &[("a.rs", "bad newer")]).await;
+    assert_eq!(scans, 1);
+    assert_eq!(report.issues.len(), 1);
+
+    let (_, scans) = review(Some(&cache), 2, &[("a.rs", "bad newer")]).await;
+    assert_eq!(scans, 1);
+}
+
+#[tokio::test]
+async fn without_a_cache_every_run_scans() {
+    let files = [("a.rs", "bad new")];
+    assert_eq!(review(None, 1, &files).await.1, 1);
+    assert_eq!(review(None, 1, &files).await.1, 1);
+}
+
+#[tokio::test]
+async fn least_recently_used_entries_are_evicted() {
+    let temp = tempdir().unwrap();
+    let cache = temp.path().join("scan.json");
+    let files: Vec<(String, String)> = (0..12)
+        .map(|i| (format!("f{i}.rs"), format!("bad {i}")))
+        .collect();
+    let files: Vec<(&str, &str)> = files
+        .iter()
+        .map(|(p, c)| (p.as_str(), c.as_str()))
+        .collect();
+    review(Some(&cache), 1, &files).await;
+    assert_eq!(ScanCache::open(&cache, 10).len(), 10);
+
+    // The last ten files are still cached; the first two were evicted.
+    let (_, scans) = review(Some(&cache), 1, &files[2..]).await;
+    assert_eq!(scans, 0);
+    let (_, scans) = review(Some(&cache), 1, &files[..2]).await;
+    assert_eq!(scans, 2);
+}
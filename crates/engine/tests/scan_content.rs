@@ -0,0 +1,86 @@
+use engine::config::{Config, ConfigOverride, Severity};
+use engine::ReviewEngine;
+
+const RUST: &str = "fn main() {\n    // TODO: handle errors\n    let api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n    // reviewlens:ignore secrets test fixture\n    let token = \"ZYXWVUTSRQPONMLKJIHGFEDC\";\n}\n";
+
+const GO: &str = "func lookup(id string) {\n\tdb.Query(fmt.Sprintf(\"SELECT name FROM users WHERE id = %s\", id))\n\tresp, _ := http.Get(\"http://example.com\")\n}\n";
+
+fn engine(config: Config) -> ReviewEngine {
+    ReviewEngine::new(config).unwrap()
+}
+
+fn rules(issues: &[engine::scanner::Issue]) -> Vec<(usize, &str)> {
+    issues
+        .iter()
+        .map(|issue| (issue.line_number, issue.rule_id.as_str()))
+        .collect()
+}
+
+#[test]
+fn scans_a_rust_buffer_with_suppressions() {
+    let engine = engine(Config::default());
+    let issues = engine.scan_content("src/main.rs", RUST).unwrap();
+
+    assert_eq!(rules(&issues), [(2, "todo"), (3, "secrets")]);
+    assert!(issues.iter().all(|issue| !issue.fingerprint.is_empty()));
+    assert!(issues.iter().all(|issue| issue.file_path == "src/main.rs"));
+}
+
+#[test]
+fn scans_a_go_buffer_with_go_rules() {
+    let engine = engine(Config::default());
+    let issues = engine.scan_content("api/lookup.go", GO).unwrap();
+
+    assert_eq!(
+        rules(&issues),
+        [(2, "sql-injection-go"), (3, "http-timeouts-go")]
+    );
+}
+
+#[test]
+fn go_rules_skip_files_in_other_languages() {
+    let engine = engine(Config::default());
+    let issues = engine.scan_content("api/lookup.py", GO).unwrap();
+
+    assert_eq!(rules(&issues), []);
+}
+
+#[test]
+fn repeated_scans_are_stable() {
+    let engine = engine(Config::default());
+    let first = engine.scan_content("api/lookup.go", GO).unwrap();
+    let edited = format!("// a new first line\n{}", GO);
+    let second = engine.scan_content("api/lookup.go", &edited).unwrap();
+
+    let fingerprints = |issues: &[engine::scanner::Issue]| {
+        issues
+            .iter()
+            .map(|issue| issue.fingerprint.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(fingerprints(&first), fingerprints(&second));
+    assert_eq!(second[0].line_number, 3);
+}
+
+#[test]
+fn paths_and_overrides_scope_the_rules() {
+    let mut config = Config::default();
+    config.paths.deny = vec!["vendor/**".into()];
+    config.overrides.push(ConfigOverride {
+        paths: vec!["legacy/**".into()],
+        rules: Some(
+            toml::from_str("todo = { enabled = false }\nsecrets = { severity = \"critical\" }")
+                .unwrap(),
+        ),
+        fail_on: None,
+    });
+    let engine = engine(config);
+
+    assert!(engine
+        .scan_content("vendor/lib.rs", RUST)
+        .unwrap()
+        .is_empty());
+    let issues = engine.scan_content("legacy/main.rs", RUST).unwrap();
+    assert_eq!(rules(&issues), [(3, "secrets")]);
+    assert_eq!(issues[0].severity, Severity::Critical);
+}
@@ -0,0 +1,71 @@
+use engine::config::{Config, Severity};
+use engine::error::Result;
+use engine::scanner::testing::{assert_findings, Fixture, SingleRuleConfig};
+use engine::scanner::{rule_enabled, Issue, Scanner};
+
+/// A third-party style scanner flagging `dbg!` calls.
+struct DbgScanner;
+
+impl Scanner for DbgScanner {
+    fn name(&self) -> &'static str {
+        "Dbg Scanner"
+    }
+
+    fn scan(&self, file_path: &str, content: &str, _config: &Config) -> Result<Vec<Issue>> {
+        Ok(content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.contains("dbg!("))
+            .map(|(i, _)| Issue {
+                title: "Leftover dbg!".into(),
+                description: String::new(),
+                file_path: file_path.into(),
+                line_number: i + 1,
+                severity: Severity::Low,
+                rule_id: "dbg".into(),
+                ..Default::default()
+            })
+            .collect())
+    }
+}
+
+#[test]
+fn custom_scanner_test_in_a_few_lines() {
+    let fixture = Fixture::parse("fn main() {\n    dbg!(1); // EXPECT: dbg\n}\n");
+    assert_eq!(fixture.expected, [(2, "dbg".to_string())]);
+    assert_findings(&DbgScanner, "main.rs", &fixture.content, &[(2, "dbg")]);
+}
+
+#[test]
+#[should_panic(expected = "Dbg Scanner reported unexpected findings in main.rs")]
+fn missing_finding_fails_with_scanner_name() {
+    assert_findings(&DbgScanner, "main.rs", "dbg!(1);", &[]);
+}
+
+#[test]
+fn fixture_lines_may_expect_several_rules() {
+    let fixture = Fixture::parse("a\nb // EXPECT: secrets, dbg\n");
+    assert_eq!(
+        fixture.expected,
+        [(2, "secrets".to_string()), (2, "dbg".to_string())]
+    );
+}
+
+#[test]
+fn single_rule_config_disables_other_builtin_rules() {
+    let config = SingleRuleConfig::new("secrets")
+        .severity(Severity::Critical)
+        .build();
+    assert!(rule_enabled(&config, "secrets"));
+    assert_eq!(config.rules.secrets.severity, Severity::Critical);
+    for rule in [
+        "sql-injection-go",
+        "http-timeouts-go",
+        "conventions",
+        "network-hygiene",
+        "removed-controls",
+        "artifacts",
+    ] {
+        assert!(!rule_enabled(&config, rule), "{} still enabled", rule);
+    }
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "schema")]
+
+use engine::config::Config;
+use engine::schema::{generate, SchemaKind, SCHEMA_VERSION};
+use engine::{render_report, review_diff, Format, ReviewOptions};
+use jsonschema::JSONSchema;
+use serde_json::Value;
+use std::collections::HashMap;
+
+const CONTENT: &str =
+    "fn main() {\n    // TODO: handle errors\n    let api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\";\n}\n";
+
+/// Fails with every validation error if `instance` does not match `schema`.
+fn assert_valid(schema: &Value, instance: &Value) {
+    let compiled = JSONSchema::compile(schema).expect("the schema compiles");
+    if let Err(errors) = compiled.validate(instance) {
+        let errors: Vec<String> = errors
+            .map(|e| format!("{} at {}", e, e.instance_path))
+            .collect();
+        panic!("invalid instance:\n{}", errors.join("\n"));
+    };
+}
+
+async fn report_json(config: Config) -> Value {
+    let mut files = HashMap::new();
+    files.insert("src/main.rs".to_string(), CONTENT.to_string());
+    let added: String = CONTENT.lines().flat_map(|l| ["+", l, "\n"]).collect();
+    let diff = format!(
+        "diff --git a/src/main.rs b/src/main.rs\n--- a/src/main.rs\n+++ b/src/main.rs\n@@ -0,0 +1,4 @@\n{}",
+        added
+    );
+    let options = ReviewOptions::new(config)
+        .root("/nonexistent")
+        .content(Box::new(files));
+    let report = review_diff(&diff, options).await.unwrap();
+    assert!(!report.issues.is_empty());
+    serde_json::from_str(&render_report(&report, Format::Json).unwrap()).unwrap()
+}
+
+#[test]
+fn schemas_are_versioned() {
+    for kind in [SchemaKind::Report, SchemaKind::Config] {
+        let schema = generate(kind);
+        let id = schema["$id"].as_str().unwrap();
+        assert!(id.contains(SCHEMA_VERSION), "{}", id);
+        assert!(id.ends_with(&format!("{}.schema.json", kind.as_str())));
+        assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+    }
+}
+
+#[tokio::test]
+async fn generated_reports_match_the_report_schema() {
+    let schema = generate(SchemaKind::Report);
+    let report = report_json(Config::default()).await;
+    assert_eq!(report["$schema"], schema["$id"]);
+    assert_eq!(report["schema_version"], SCHEMA_VERSION);
+    assert_valid(&schema, &report);
+
+    let mut full = Config::default();
+    full.report.include_config = engine::config::IncludeConfig::Full;
+    assert_valid(&schema, &report_json(full).await);
+}
+
+#[test]
+fn report_schema_rejects_malformed_findings() {
+    let schema = generate(SchemaKind::Report);
+    let compiled = JSONSchema::compile(&schema).unwrap();
+    let mut report: Value = serde_json::from_str(
+        r#"{"summary":"","issues":[],"stats":{"files_reviewed":0,"files_skipped":0,"lines_added":0,"lines_removed":0,"issues_by_severity":{},"issues_by_rule":{},"tokens_used":0,"suppressed":0},"code_quality":[],"hotspots":[],"mermaid_diagram":null,"metadata":{"ruleset_version":"v1","driver":"null","timings":{"total_ms":0},"index_warm":false,"llm_cache_hit":false,"budget_exhausted":false}}"#,
+    )
+    .unwrap();
+    assert_valid(&schema, &report);
+    report["issues"] = serde_json::json!([{ "title": "missing fields", "severity": "severe" }]);
+    assert!(!compiled.is_valid(&report));
+}
+
+#[test]
+fn default_config_matches_the_config_schema() {
+    let schema = generate(SchemaKind::Config);
+    assert_valid(&schema, &serde_json::to_value(Config::default()).unwrap());
+    let parsed: Config = toml::from_str(
+        "fail-on = \"critical\"\n[rules.secrets]\nseverity = \"critical\"\n[policy]\nmax-findings = { high = 2 }\n",
+    )
+    .unwrap();
+    assert_valid(&schema, &serde_json::to_value(parsed).unwrap());
+    assert!(!JSONSchema::compile(&schema)
+        .unwrap()
+        .is_valid(&serde_json::json!({ "fail-on": "sometimes" })));
+}
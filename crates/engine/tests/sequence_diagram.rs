@@ -16,12 +16,14 @@ async fn generates_sequence_diagram_when_files_interact() {
         "diff --git a/{a} b/{a}\n--- a/{a}\n+++ b/{a}\n@@ -0,0 +1,1 @@\n+use crate::b; fn a() {{ b::b(); }}\n\
 diff --git a/{b} b/{b}\n--- a/{b}\n+++ b/{b}\n@@ -0,0 +1,1 @@\n+use crate::c; fn b() {{ c::c(); }}\n\
 diff --git a/{c} b/{c}\n--- a/{c}\n+++ b/{c}\n@@ -0,0 +1,1 @@\n+use crate::a; fn c() {{ a::a(); }}\n",
-        a = file_a.to_str().unwrap(),
-        b = file_b.to_str().unwrap(),
-        c = file_c.to_str().unwrap()
+        a = "a.rs",
+        b = "b.rs",
+        c = "c.rs"
     );
 
-    let engine = ReviewEngine::new(Config::default()).unwrap();
+    let engine = ReviewEngine::new(Config::default())
+        .unwrap()
+        .with_root(dir.path());
     let report = engine.run(&diff).await.unwrap();
     let diagram = report.mermaid_diagram.expect("expected diagram");
     assert!(diagram.contains("sequenceDiagram"));
@@ -29,3 +31,62 @@ diff --git a/{c} b/{c}\n--- a/{c}\n+++ b/{c}\n@@ -0,0 +1,1 @@\n+use crate::a; fn
     assert!(diagram.contains("b.rs->>c.rs"));
     assert!(diagram.contains("c.rs->>a.rs"));
 }
+
+#[test]
+fn two_files_with_imports_only_render_graph() {
+    let files = vec![
+        (
+            "app.py".to_string(),
+            "from models import User\n".to_string(),
+        ),
+        ("models.py".to_string(), "class User: pass\n".to_string()),
+    ];
+    let diagram = engine::report::diagram::render(&files, 20).expect("expected diagram");
+    assert!(diagram.starts_with("graph TD"));
+    assert!(diagram.contains("app_py[\"app.py\"] -->|imports| models_py[\"models.py\"]"));
+}
+
+#[test]
+fn diagram_is_capped_at_max_edges() {
+    let files = vec![
+        (
+            "a.rs".to_string(),
+            "fn a() { b::run(); c::run(); }".to_string(),
+        ),
+        ("b.rs".to_string(), "fn b() { c::run(); }".to_string()),
+        ("c.rs".to_string(), "fn c() {}".to_string()),
+    ];
+    let diagram = engine::report::diagram::render(&files, 2).expect("expected diagram");
+    assert!(diagram.starts_with("sequenceDiagram"));
+    assert_eq!(diagram.matches("->>").count(), 2);
+    assert!(diagram.contains("1 more interaction omitted"));
+}
+
+#[test]
+fn unrelated_files_produce_no_diagram() {
+    let files = vec![
+        ("a.rs".to_string(), "fn a() {}".to_string()),
+        ("b.rs".to_string(), "fn b() {}".to_string()),
+    ];
+    assert!(engine::report::diagram::render(&files, 20).is_none());
+}
+
+#[tokio::test]
+async fn diagram_can_be_disabled() {
+    let mut files = std::collections::HashMap::new();
+    files.insert("a.rs".to_string(), "fn a() { b::b(); }\n".to_string());
+    files.insert("b.rs".to_string(), "fn b() {}\n".to_string());
+    let diff =
+        "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -0,0 +1,1 @@\n+fn a() { b::b(); }\n\
+diff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n@@ -0,0 +1,1 @@\n+fn b() {}\n";
+
+    let mut config = Config::default();
+    let engine = ReviewEngine::new(config.clone()).unwrap();
+    let report = engine.run_with_provider(diff, &files).await.unwrap();
+    assert!(report.mermaid_diagram.is_some());
+
+    config.report.diagram = false;
+    let engine = ReviewEngine::new(config).unwrap();
+    let report = engine.run_with_provider(diff, &files).await.unwrap();
+    assert!(report.mermaid_diagram.is_none());
+}
@@ -0,0 +1,253 @@
+use engine::config::{Config, RedactionConfig, Severity};
+use engine::error::EngineError;
+use engine::privacy::Redactor;
+use engine::publish::{Publisher, SlackPublisher};
+use engine::report::{ReviewReport, ReviewStats, RuntimeMetadata, TimingInfo};
+use engine::scanner::Issue;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{channel, Receiver};
+
+/// Answers one request with `status` and `body`, and sends the request body
+/// it received to the returned channel.
+fn canned_server(status: &str, body: &str) -> (String, Receiver<serde_json::Value>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}/services/T0/B0/secret",
+        listener.local_addr().unwrap()
+    );
+    let response = format!(
+        "HTTP/1.1 {}\r\ncontent-type: text/plain\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut request = vec![0; length];
+        reader.read_exact(&mut request).unwrap();
+        tx.send(serde_json::from_slice(&request).unwrap()).unwrap();
+        stream.write_all(response.as_bytes()).unwrap();
+    });
+    (url, rx)
+}
+
+fn issue(title: &str, severity: Severity, line_number: usize) -> Issue {
+    Issue {
+        title: title.into(),
+        description: "Found in the change".into(),
+        file_path: "src/api.go".into(),
+        line_number,
+        severity,
+        rule_id: "test".into(),
+        ..Default::default()
+    }
+}
+
+fn report_with(summary: &str, issues: Vec<Issue>) -> ReviewReport {
+    let mut stats = ReviewStats {
+        files_reviewed: 1,
+        ..ReviewStats::default()
+    };
+    for issue in &issues {
+        *stats
+            .issues_by_severity
+            .entry(issue.severity.clone())
+            .or_default() += 1;
+    }
+    ReviewReport {
+        summary: summary.into(),
+        issues,
+        stats,
+        code_quality: vec![],
+        hotspots: vec![],
+        mermaid_diagram: None,
+        config: Config::default(),
+        metadata: RuntimeMetadata {
+            ruleset_version: "v1".into(),
+            model: None,
+            driver: "null".into(),
+            timings: TimingInfo {
+                total_ms: 0,
+                ..Default::default()
+            },
+            index_warm: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Every text in a Block Kit payload, from sections, fields and contexts.
+fn texts(payload: &serde_json::Value) -> Vec<String> {
+    let mut texts = Vec::new();
+    for block in payload["blocks"].as_array().unwrap() {
+        if let Some(text) = block["text"]["text"].as_str() {
+            texts.push(text.to_string());
+        }
+        for key in ["fields", "elements"] {
+            for item in block[key].as_array().into_iter().flatten() {
+                texts.push(item["text"].as_str().unwrap().to_string());
+            }
+        }
+    }
+    texts
+}
+
+#[tokio::test]
+async fn posts_summary_counts_and_top_findings() {
+    let (url, request) = canned_server("200 OK", "ok");
+    let mut linked = issue("SQL injection", Severity::Critical, 12);
+    linked.url = Some("https://example.com/blob/abc/src/api.go#L12".into());
+    let report = report_with(
+        "Adds a user lookup.",
+        vec![
+            issue("TODO left", Severity::Low, 1),
+            linked,
+            issue("Missing timeout", Severity::High, 30),
+            issue("Weak hash", Severity::Medium, 40),
+        ],
+    );
+    SlackPublisher::new(url, Redactor::default())
+        .report_url("https://ci.example.com/artifacts/42")
+        .publish(&report)
+        .await
+        .unwrap();
+
+    let sent = request.recv().unwrap();
+    let blocks = sent["blocks"].as_array().unwrap();
+    assert_eq!(blocks[0]["type"], "header");
+    assert_eq!(blocks[1]["text"]["text"], "Adds a user lookup.");
+    assert!(
+        sent["text"].as_str().unwrap().contains("4 issues"),
+        "{}",
+        sent
+    );
+
+    let counts: Vec<&str> = blocks[2]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|field| field["text"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        counts,
+        ["*critical*\n1", "*high*\n1", "*medium*\n1", "*low*\n1"]
+    );
+
+    let findings = blocks[3]["text"]["text"].as_str().unwrap();
+    let lines: Vec<&str> = findings.lines().filter(|l| l.starts_with('•')).collect();
+    assert_eq!(lines.len(), 3, "{}", findings);
+    assert!(lines[0].contains("<https://example.com/blob/abc/src/api.go#L12|SQL injection>"));
+    assert!(lines[0].contains("`src/api.go:12`"));
+    assert!(lines[1].contains("Missing timeout"));
+    assert!(lines[2].contains("Weak hash"));
+    assert!(findings.contains("and 1 more finding"), "{}", findings);
+
+    assert_eq!(
+        blocks[4]["elements"][0]["text"],
+        "<https://ci.example.com/artifacts/42|View the full report>"
+    );
+}
+
+#[tokio::test]
+async fn truncates_a_hundred_findings_to_slack_limits() {
+    let (url, request) = canned_server("200 OK", "ok");
+    let issues: Vec<Issue> = (0..100)
+        .map(|i| {
+            let mut issue = issue(&format!("Finding {}", i), Severity::High, i + 1);
+            issue.description = "x".repeat(5000);
+            issue
+        })
+        .collect();
+    let report = report_with(&"Long summary. ".repeat(1000), issues);
+    SlackPublisher::new(url, Redactor::default())
+        .publish(&report)
+        .await
+        .unwrap();
+
+    let sent = request.recv().unwrap();
+    let blocks = sent["blocks"].as_array().unwrap();
+    assert!(blocks.len() <= 50);
+    assert!(blocks[0]["text"]["text"].as_str().unwrap().chars().count() <= 150);
+    for text in texts(&sent) {
+        assert!(text.chars().count() <= 3000, "{} chars", text.len());
+    }
+    assert!(blocks[1]["text"]["text"]
+        .as_str()
+        .unwrap()
+        .ends_with('\u{2026}'));
+
+    let findings = blocks[3]["text"]["text"].as_str().unwrap();
+    assert_eq!(findings.matches("Finding ").count(), 3);
+    assert!(findings.contains("Finding 0") && findings.contains("Finding 2"));
+    assert!(findings.contains("and 97 more findings"), "{}", findings);
+    assert_eq!(blocks[2]["fields"][1]["text"], "*high*\n100");
+}
+
+#[tokio::test]
+async fn redacts_and_escapes_every_text() {
+    let (url, request) = canned_server("200 OK", "ok");
+    let mut leaky = issue("Token hunter2 <leaked>", Severity::High, 3);
+    leaky.description = "The password hunter2 is committed".into();
+    let report = report_with("Mentions hunter2 & more", vec![leaky]);
+    let redaction = RedactionConfig {
+        patterns: vec!["hunter2".into()],
+        ..RedactionConfig::default()
+    };
+    SlackPublisher::new(url, Redactor::new(&redaction).unwrap())
+        .publish(&report)
+        .await
+        .unwrap();
+
+    let sent = request.recv().unwrap();
+    let body = sent.to_string();
+    assert!(!body.contains("hunter2"), "{}", body);
+    let texts = texts(&sent);
+    assert!(texts.iter().any(|t| t.contains("&amp; more")));
+    assert!(texts.iter().any(|t| t.contains("&lt;leaked&gt;")));
+}
+
+#[tokio::test]
+async fn reports_a_rejected_webhook_as_a_publish_error() {
+    let (url, _request) = canned_server("404 Not Found", "no_service");
+    let report = report_with("Nothing to see.", Vec::new());
+    let err = SlackPublisher::new(url, Redactor::default())
+        .publish(&report)
+        .await
+        .unwrap_err();
+    match err {
+        EngineError::Publish(msg) => {
+            assert!(msg.contains("404") && msg.contains("no_service"), "{}", msg)
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn from_config_requires_a_webhook_url() {
+    std::env::remove_var("SLACK_WEBHOOK_URL");
+    let err = SlackPublisher::from_config(&Config::default())
+        .err()
+        .unwrap();
+    assert!(matches!(err, EngineError::Config(msg) if msg.contains("webhook-url")));
+
+    let mut config = Config::default();
+    config.integrations.slack.webhook_url = Some("https://hooks.slack.com/x".into());
+    assert!(SlackPublisher::from_config(&config).is_ok());
+}
@@ -0,0 +1,79 @@
+use engine::config::Config;
+use engine::report::snippet::extract;
+use engine::report::{JsonGenerator, MarkdownGenerator, ReportGenerator, ReviewReport};
+
+mod common;
+use common::review_file;
+
+const NEAR_TOP: &str = "\
+resp, err := http.Get(url)
+if err != nil {
+    return err
+}
+defer resp.Body.Close()
+";
+
+#[tokio::test]
+async fn window_is_clamped_at_the_top_of_the_file() {
+    let report = review_file(&Config::default(), "client.go", NEAR_TOP).await;
+    let issue = report
+        .issues
+        .iter()
+        .find(|issue| issue.rule_id == "http-timeouts-go")
+        .unwrap();
+    assert_eq!(issue.line_number, 1);
+    assert_eq!(
+        issue.snippet.as_deref(),
+        Some("> 1 | resp, err := http.Get(url)\n  2 | if err != nil {\n  3 |     return err")
+    );
+
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(
+        md.contains("```\n> 1 | resp, err := http.Get(url)\n"),
+        "{}",
+        md
+    );
+    let json = JsonGenerator.generate(&report).unwrap();
+    let parsed = ReviewReport::from_json(&json).unwrap();
+    assert_eq!(parsed.issues[0].snippet, issue.snippet);
+}
+
+#[tokio::test]
+async fn zero_context_disables_snippets() {
+    let mut config = Config::default();
+    config.report.snippet_context = 0;
+    let report = review_file(&config, "client.go", NEAR_TOP).await;
+    assert!(!report.issues.is_empty());
+    assert!(report.issues.iter().all(|issue| issue.snippet.is_none()));
+    let json = JsonGenerator.generate(&report).unwrap();
+    assert!(!json.contains("\"snippet\""));
+}
+
+#[tokio::test]
+async fn snippets_mask_secret_values() {
+    let content = "// 🔑 keys\napi_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"\n// 🎉 done\n";
+    let report = review_file(&Config::default(), "config.py", content).await;
+    let snippet = report.issues[0].snippet.as_deref().unwrap();
+    assert!(snippet.contains("1 | // 🔑 keys"), "{}", snippet);
+    assert!(snippet.contains("3 | // 🎉 done"), "{}", snippet);
+    assert!(!snippet.contains("ABCDEFGHIJKLMNOPQRSTUVWX"), "{}", snippet);
+}
+
+#[test]
+fn long_lines_are_cut_between_characters() {
+    let line = "🦀".repeat(300);
+    let snippet = extract(&line, 1, 2).unwrap();
+    let code = snippet.strip_prefix("> 1 | ").unwrap();
+    assert_eq!(code.chars().count(), 201);
+    assert!(code.ends_with("🦀\u{2026}"));
+}
+
+#[test]
+fn window_is_clamped_at_the_end_of_the_file() {
+    let content = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+    assert_eq!(
+        extract(content, 10, 2).as_deref(),
+        Some("   8 | h\n   9 | i\n> 10 | j")
+    );
+    assert_eq!(extract(content, 11, 2), None);
+}
@@ -1,51 +1,44 @@
-use engine::config::{Config, RuleConfig, RulesConfig, Severity};
+use engine::config::Severity;
+use engine::scanner::testing::{assert_findings_with, assert_fixture, SingleRuleConfig};
 use engine::scanner::{Scanner, SqlInjectionGoScanner};
 
-/// Build a configuration fixture with only the SQL injection rule enabled.
-///
-/// Using `Config::default()` pulled in all rules which could introduce
-/// nondeterministic behaviour if other tests mutate global configuration.
-/// By constructing the relevant rule explicitly we keep this test isolated
-/// and deterministic.
-fn test_config() -> Config {
-    Config {
-        rules: RulesConfig {
-            sql_injection_go: RuleConfig {
-                enabled: true,
-                severity: Severity::Medium,
-            },
-            ..Default::default()
-        },
-        ..Default::default()
-    }
-}
-
 #[test]
 fn detects_dynamic_sql_concatenation() {
-    let scanner = SqlInjectionGoScanner;
+    let config = SingleRuleConfig::new("sql-injection-go")
+        .severity(Severity::Medium)
+        .build();
     let content = r#"
         query := "SELECT * FROM users WHERE name = '" + user + "'"
         rows, _ := db.Query(query)
     "#;
-    let config = test_config();
-    let issues = scanner
+    assert_findings_with(
+        &SqlInjectionGoScanner,
+        "user.go",
+        content,
+        &config,
+        &[(2, "sql-injection-go")],
+    );
+    let issues = SqlInjectionGoScanner
         .scan("user.go", content, &config)
-        .expect("scan should work");
-    assert_eq!(issues.len(), 1);
-    let issue = &issues[0];
-    assert_eq!(issue.line_number, 2);
-    assert_eq!(issue.severity, config.rules.sql_injection_go.severity);
+        .unwrap();
+    assert_eq!(issues[0].severity, Severity::Medium);
 }
 
 #[test]
 fn allows_parameterized_query() {
-    let scanner = SqlInjectionGoScanner;
+    let config = SingleRuleConfig::new("sql-injection-go").build();
     let content = r#"
         rows, _ := db.Query("SELECT * FROM users WHERE id = ?", id)
     "#;
-    let config = test_config();
-    let issues = scanner
-        .scan("user.go", content, &config)
-        .expect("scan should work");
-    assert!(issues.is_empty());
+    assert_findings_with(&SqlInjectionGoScanner, "user.go", content, &config, &[]);
+}
+
+#[test]
+fn flags_annotated_fixture() {
+    let config = SingleRuleConfig::new("sql-injection-go").build();
+    assert_fixture(
+        &SqlInjectionGoScanner,
+        "../../fixtures/sql-injection/main.go",
+        &config,
+    );
 }
@@ -0,0 +1,79 @@
+use engine::config::{Config, Severity};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+fn file_diff(path: &str, added: &[&str], removed: &[&str]) -> String {
+    let mut diff = format!(
+        "diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n@@ -1,{1} +1,{2} @@\n",
+        path,
+        removed.len(),
+        added.len()
+    );
+    for line in removed {
+        diff.push_str(&format!("-{}\n", line));
+    }
+    for line in added {
+        diff.push_str(&format!("+{}\n", line));
+    }
+    diff
+}
+
+#[tokio::test]
+async fn stats_count_files_lines_and_findings() {
+    let secret = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+    let sql = "db.Query(\"SELECT * FROM users WHERE id = \" + id)";
+    let ignored = "token = \"ABCDEFGHIJKLMNOPQRSTUVWXYZ\" # reviewlens:ignore secrets test fixture";
+    let main_go = [sql, "http.Get(url)"];
+    let mut diff = String::new();
+    diff.push_str(&file_diff("config.py", &[secret, ignored], &["old = 1"]));
+    diff.push_str(&file_diff("main.go", &main_go, &[]));
+    diff.push_str(&file_diff("vendor/lib.go", &[sql], &[]));
+    diff.push_str(
+        "diff --git a/logo.png b/logo.png\nBinary files a/logo.png and b/logo.png differ\n",
+    );
+
+    let mut files = HashMap::new();
+    files.insert(
+        "config.py".to_string(),
+        format!("{}\n{}\n", secret, ignored),
+    );
+    files.insert("main.go".to_string(), main_go.join("\n"));
+
+    let mut config = Config::default();
+    config.paths.deny = vec!["vendor/**".into()];
+    let engine = ReviewEngine::new(config).unwrap();
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    let stats = &report.stats;
+
+    assert_eq!(stats.files_reviewed, 2);
+    assert_eq!(stats.files_skipped, 2);
+    assert_eq!(stats.lines_added, 4);
+    assert_eq!(stats.lines_removed, 1);
+    assert_eq!(stats.suppressed, 1);
+    assert_eq!(stats.total_issues(), 3);
+    assert_eq!(stats.issues_by_severity.get(&Severity::Critical), Some(&1));
+    assert_eq!(stats.issues_by_severity.get(&Severity::High), Some(&1));
+    assert_eq!(stats.issues_by_severity.get(&Severity::Medium), Some(&1));
+    assert_eq!(stats.issues_by_rule.get("secrets"), Some(&1));
+    assert_eq!(stats.issues_by_rule.get("sql-injection-go"), Some(&1));
+    assert_eq!(stats.issues_by_rule.get("http-timeouts-go"), Some(&1));
+    assert_eq!(stats.tokens_used, 0);
+    assert!(report.summary.starts_with("Reviewed 2 files"));
+    assert_eq!(
+        stats.headline(),
+        "Reviewed 2 files (+4/-1 lines, 2 skipped): 3 issues, 1 suppressed"
+    );
+}
+
+#[tokio::test]
+async fn directives_that_silence_nothing_are_not_counted() {
+    let line = "name = \"report\" # reviewlens:ignore secrets not a secret";
+    let diff = file_diff("app.py", &[line], &[]);
+    let mut files = HashMap::new();
+    files.insert("app.py".to_string(), format!("{}\n", line));
+
+    let engine = ReviewEngine::new(Config::default()).unwrap();
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    assert!(report.suppressed.is_empty());
+    assert_eq!(report.stats.suppressed, 0);
+}
@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use engine::config::{Config, Provider};
+use engine::error::Result;
+use engine::llm::structured::{parse_review, LlmSuggestion};
+use engine::llm::{LlmProvider, LlmResponse};
+use engine::report::{MarkdownGenerator, ReportGenerator, ReviewReport};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const REVIEW: &str = r#"{"summary": "One hardcoded key.", "suggestions": [{"issue_index": 0, "comment": "Load the key from the environment.", "suggested_patch": "-api_key = \"...\"\n+api_key = env(\"API_KEY\")"}]}"#;
+
+#[test]
+fn parses_well_formed_output() {
+    let review = parse_review(REVIEW).unwrap();
+    assert_eq!(review.summary, "One hardcoded key.");
+    assert_eq!(
+        review.suggestions,
+        vec![LlmSuggestion {
+            issue_index: 0,
+            comment: "Load the key from the environment.".into(),
+            suggested_patch: Some("-api_key = \"...\"\n+api_key = env(\"API_KEY\")".into()),
+        }]
+    );
+}
+
+#[test]
+fn parses_fenced_output_with_surrounding_prose() {
+    let response = format!(
+        "Here is my review:\n\n```json\n{}\n```\n\nLet me know if {{you}} need more.",
+        REVIEW
+    );
+    let review = parse_review(&response).unwrap();
+    assert_eq!(review.summary, "One hardcoded key.");
+    assert_eq!(review.suggestions.len(), 1);
+}
+
+#[test]
+fn rejects_malformed_output() {
+    assert!(parse_review("The code looks fine.").is_err());
+    assert!(parse_review(r#"{"summary": "cut off", "suggestions": [{"issue_ind"#).is_err());
+    assert!(parse_review(r#"{"comment": "no summary"}"#).is_err());
+}
+
+/// Answers every prompt with `response` and records the prompts.
+struct CannedProvider {
+    response: String,
+    prompts: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl LlmProvider for CannedProvider {
+    async fn generate(&self, prompt: &str) -> Result<LlmResponse> {
+        self.prompts.lock().unwrap().push(prompt.to_string());
+        Ok(LlmResponse {
+            content: self.response.clone(),
+            token_usage: 1,
+            cached: false,
+        })
+    }
+}
+
+async fn review(response: &str, structured: bool) -> (ReviewReport, Vec<String>) {
+    let mut config = Config::default();
+    config.llm.provider = Provider::Openai;
+    config.llm.model = Some("gpt-test".into());
+    config.llm.structured_output = structured;
+    let prompts = Arc::new(Mutex::new(Vec::new()));
+    let engine = ReviewEngine::builder(config)
+        .llm(Box::new(CannedProvider {
+            response: response.to_string(),
+            prompts: prompts.clone(),
+        }))
+        .build()
+        .unwrap();
+
+    let line = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+    let diff = format!(
+        "diff --git a/key.rs b/key.rs\n--- a/key.rs\n+++ b/key.rs\n@@ -0,0 +1 @@\n+{}\n",
+        line
+    );
+    let mut files = HashMap::new();
+    files.insert("key.rs".to_string(), format!("{}\n", line));
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    let prompts = prompts.lock().unwrap().clone();
+    (report, prompts)
+}
+
+#[tokio::test]
+async fn attaches_comments_and_patches_to_issues() {
+    let (report, prompts) = review(&format!("```json\n{}\n```", REVIEW), true).await;
+    assert!(prompts[0].contains("[0] key.rs:1"), "{}", prompts[0]);
+    assert!(prompts[0].contains("issue_index"));
+
+    assert_eq!(report.summary, "One hardcoded key.");
+    assert_eq!(report.metadata.llm_parse_error, None);
+    let issue = &report.issues[0];
+    assert_eq!(
+        issue.llm_comment.as_deref(),
+        Some("Load the key from the environment.")
+    );
+    assert!(issue
+        .llm_patch
+        .as_deref()
+        .unwrap()
+        .contains("env(\"API_KEY\")"));
+
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(md.contains("LLM review of `Potential Secret Found` at `key.rs:1`"));
+    assert!(md.contains("Load the key from the environment."));
+    assert!(md.contains("+api_key = env(\"API_KEY\")"));
+}
+
+#[tokio::test]
+async fn malformed_output_falls_back_to_the_whole_response() {
+    let (report, _) = review("The key should not be committed.", true).await;
+    assert_eq!(report.summary, "The key should not be committed.");
+    assert!(report.metadata.llm_parse_error.is_some());
+    assert!(report.issues[0].llm_comment.is_none());
+}
+
+#[tokio::test]
+async fn unstructured_mode_keeps_the_plain_prompt() {
+    let (report, prompts) = review(REVIEW, false).await;
+    assert!(!prompts[0].contains("issue_index"));
+    assert_eq!(report.summary, REVIEW);
+    assert!(report.issues[0].llm_comment.is_none());
+    assert_eq!(report.metadata.llm_parse_error, None);
+}
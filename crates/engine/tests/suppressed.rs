@@ -0,0 +1,152 @@
+use engine::config::{Config, IndexConfig};
+use engine::rag::{Document, InMemoryVectorStore};
+use engine::report::{JsonGenerator, MarkdownGenerator, ReportGenerator, ReviewReport};
+use engine::scanner::Suppression;
+
+mod common;
+use common::review_file;
+
+const CONTENT: &str = "\
+api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\" // reviewlens:ignore secrets test fixture
+ok = 1
+// reviewlens:ignore secrets rotated in INC-42
+token = \"ABCDEFGHIJKLMNOPQRSTUVWXYZ\"
+";
+
+async fn review(config: Config) -> ReviewReport {
+    review_file(&config, "config.js", CONTENT).await
+}
+
+#[tokio::test]
+async fn report_lists_each_suppressed_finding_with_its_reason() {
+    let report = review(Config::default()).await;
+    assert!(report.issues.is_empty());
+    assert_eq!(
+        report.suppressed,
+        [
+            Suppression {
+                rule: "secrets".into(),
+                path: "config.js".into(),
+                line: 1,
+                reason: Some("test fixture".into()),
+            },
+            Suppression {
+                rule: "secrets".into(),
+                path: "config.js".into(),
+                line: 4,
+                reason: Some("rotated in INC-42".into()),
+            },
+        ]
+    );
+
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(md.contains("## 🔕 Suppressed Findings"), "{}", md);
+    assert!(md.contains("| `secrets` | `config.js:1` | test fixture |"));
+    assert!(md.contains("| `secrets` | `config.js:4` | rotated in INC-42 |"));
+
+    let json = JsonGenerator.generate(&report).unwrap();
+    let parsed = ReviewReport::from_json(&json).unwrap();
+    assert_eq!(parsed.suppressed, report.suppressed);
+}
+
+#[tokio::test]
+async fn show_suppressed_off_hides_the_section() {
+    let mut config = Config::default();
+    config.report.show_suppressed = false;
+    let report = review(config).await;
+    assert_eq!(report.suppressed.len(), 2);
+
+    let md = MarkdownGenerator.generate(&report).unwrap();
+    assert!(!md.contains("Suppressed Findings"));
+    let json = JsonGenerator.generate(&report).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(value.get("suppressed").is_none());
+}
+
+/// Reviews a single added `line` in `path`, without and then with an ignore
+/// directive for `rule` on it.
+async fn review_line(
+    config: &Config,
+    rule: &str,
+    path: &str,
+    line: &str,
+) -> (ReviewReport, ReviewReport) {
+    let ignored = format!("{} // reviewlens:ignore {} checked", line, rule);
+    (
+        review_file(config, path, line).await,
+        review_file(config, path, &ignored).await,
+    )
+}
+
+#[tokio::test]
+async fn engine_applies_directives_for_every_built_in_rule() {
+    let cases = [
+        (
+            "secrets",
+            "config.js",
+            "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"",
+        ),
+        (
+            "sql-injection-go",
+            "db.go",
+            "db.Query(\"SELECT * FROM users WHERE id = \" + id)",
+        ),
+        (
+            "http-timeouts-go",
+            "client.go",
+            "resp, err := http.Get(url)",
+        ),
+        ("network-hygiene", "client.go", "addr := \"10.1.2.3:8080\""),
+    ];
+    for (rule, path, line) in cases {
+        let (flagged, ignored) = review_line(&Config::default(), rule, path, line).await;
+        assert!(
+            flagged.issues.iter().any(|issue| issue.rule_id == rule),
+            "{} not flagged",
+            rule
+        );
+        assert!(
+            ignored.issues.iter().all(|issue| issue.rule_id != rule),
+            "{} not suppressed",
+            rule
+        );
+        assert!(ignored
+            .suppressed
+            .iter()
+            .all(|s| s.rule == rule && s.line == 1));
+        assert!(!ignored.suppressed.is_empty(), "{} not recorded", rule);
+        assert_eq!(ignored.suppressed[0].reason.as_deref(), Some("checked"));
+    }
+}
+
+#[tokio::test]
+async fn engine_applies_directives_to_convention_notes() {
+    let mut store = InMemoryVectorStore::default();
+    store.push_document(Document {
+        filename: "lib.rs".into(),
+        content: String::new(),
+        embedding: vec![],
+        language: None,
+        function_signatures: vec![],
+        log_patterns: vec!["log::info!(\"hi\")".into()],
+        error_snippets: vec!["Result<()>".into()],
+        modified: 0,
+    });
+    let dir = tempfile::tempdir().unwrap();
+    let index_path = dir.path().join("index.json.zst");
+    store.save_to_disk(&index_path).unwrap();
+    let config = Config {
+        index: Some(IndexConfig {
+            path: index_path.to_string_lossy().into(),
+            ..Default::default()
+        }),
+        ..Config::default()
+    };
+
+    let line = "fn main() { println!(\"hi\"); }";
+    let (flagged, ignored) = review_line(&config, "conventions", "src/main.rs", line).await;
+    assert_eq!(flagged.code_quality.len(), 1);
+    assert!(ignored.code_quality.is_empty());
+    assert_eq!(ignored.suppressed.len(), 1);
+    assert_eq!(ignored.suppressed[0].rule, "conventions");
+}
@@ -0,0 +1,101 @@
+use chrono::NaiveDate;
+use engine::config::Config;
+use engine::report::ReviewReport;
+use engine::scanner::{find_ignore, parse_ignore_directives};
+use engine::ReviewEngine;
+use std::collections::HashMap;
+
+const SECRET: &str = "api_key = \"ABCDEFGHIJKLMNOPQRSTUVWX\"";
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()
+}
+
+/// Reviews a new `config.js` holding a secret silenced by `directive`.
+async fn review(directive: &str) -> ReviewReport {
+    let content = format!("// {}\n{}\n", directive, SECRET);
+    let diff = format!(
+        "diff --git a/config.js b/config.js\n--- a/config.js\n+++ b/config.js\n@@ -0,0 +1,2 @@\n+// {}\n+{}\n",
+        directive, SECRET
+    );
+    let mut files = HashMap::new();
+    files.insert("config.js".to_string(), content);
+    ReviewEngine::new(Config::default())
+        .unwrap()
+        .with_today(today())
+        .run_with_provider(&diff, &files)
+        .await
+        .unwrap()
+}
+
+#[test]
+fn until_attribute_is_parsed_apart_from_the_reason() {
+    let ignores = parse_ignore_directives(
+        "a.js",
+        "x(); // reviewlens:ignore secrets until=2025-06-30 migrating to vault\n",
+    );
+    let directive = &ignores[&1][0];
+    assert_eq!(directive.expires, NaiveDate::from_ymd_opt(2025, 6, 30));
+    assert_eq!(directive.reason.as_deref(), Some("migrating to vault"));
+
+    let ignores = parse_ignore_directives(
+        "a.js",
+        "x(); // reviewlens:ignore secrets until=2025-06-30\n",
+    );
+    assert_eq!(ignores[&1][0].reason, None);
+}
+
+#[test]
+fn directives_apply_through_their_until_date() {
+    let ignores = parse_ignore_directives(
+        "a.js",
+        "x(); // reviewlens:ignore secrets until=2025-07-01\n",
+    );
+    assert!(find_ignore(&ignores, 1, "secrets", today()).is_some());
+    let tomorrow = today().succ_opt().unwrap();
+    assert!(find_ignore(&ignores, 1, "secrets", tomorrow).is_none());
+}
+
+#[tokio::test]
+async fn active_directive_suppresses() {
+    let report = review("reviewlens:ignore secrets until=2025-12-31 migrating to vault").await;
+    assert!(report.issues.is_empty());
+    assert_eq!(report.suppressed.len(), 1);
+    assert_eq!(
+        report.suppressed[0].reason.as_deref(),
+        Some("migrating to vault")
+    );
+    assert!(report.code_quality.is_empty());
+}
+
+#[tokio::test]
+async fn directive_expired_yesterday_no_longer_suppresses() {
+    let report = review("reviewlens:ignore secrets until=2025-06-30 migrating to vault").await;
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].line_number, 2);
+    assert!(report.suppressed.is_empty());
+    assert_eq!(report.stats.suppressed, 0);
+    assert_eq!(report.code_quality.len(), 1);
+    assert!(
+        report.code_quality[0].starts_with(
+            "config.js:1 - `reviewlens:ignore secrets` expired on 2025-06-30 and no longer silences"
+        ),
+        "{:?}",
+        report.code_quality
+    );
+}
+
+#[tokio::test]
+async fn malformed_date_never_expires() {
+    let ignores = parse_ignore_directives(
+        "a.js",
+        "x(); // reviewlens:ignore secrets until=2025-13-45 migrating\n",
+    );
+    assert_eq!(ignores[&1][0].expires, None);
+    assert_eq!(ignores[&1][0].reason.as_deref(), Some("migrating"));
+
+    let report = review("reviewlens:ignore secrets until=30/06/2025").await;
+    assert!(report.issues.is_empty());
+    assert_eq!(report.suppressed.len(), 1);
+    assert!(report.code_quality.is_empty());
+}
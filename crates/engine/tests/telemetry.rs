@@ -1,7 +1,12 @@
+use async_trait::async_trait;
 use engine::{
     config::{Config, TelemetryConfig},
+    progress::ProgressEvent,
+    telemetry::{Telemetry, TelemetrySink},
     ReviewEngine,
 };
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 fn diff_for_file(path: &str, line: &str) -> String {
     format!(
@@ -13,16 +18,19 @@ fn diff_for_file(path: &str, line: &str) -> String {
 #[tokio::test]
 async fn writes_telemetry_events() {
     let temp = tempfile::tempdir().unwrap();
-    let file_path = temp.path().join("secret.txt");
+    let file_path = temp.path().join("secret.env");
     let content = "api_key = \"ABCDEFGHIJKLMNOP\""; // triggers secret scanner
     std::fs::write(&file_path, content).unwrap();
-    let diff = diff_for_file("secret.txt", content);
+    let diff = diff_for_file("secret.env", content);
 
     let telemetry_path = temp.path().join("telemetry.jsonl");
-    let mut config = Config::default();
-    config.telemetry = TelemetryConfig {
-        enabled: true,
-        file: Some(telemetry_path.to_string_lossy().into()),
+    let config = Config {
+        telemetry: TelemetryConfig {
+            enabled: true,
+            file: Some(telemetry_path.to_string_lossy().into()),
+            otlp_endpoint: None,
+        },
+        ..Config::default()
     };
 
     let engine = ReviewEngine::new(config).unwrap();
@@ -36,3 +44,103 @@ async fn writes_telemetry_events() {
     assert!(lines[1].contains("finding"));
     assert!(lines[2].contains("run_finished"));
 }
+
+#[tokio::test]
+async fn redacts_paths_in_finding_events() {
+    let temp = tempfile::tempdir().unwrap();
+    let content = "api_key = \"ABCDEFGHIJKLMNOP\"";
+    let diff = diff_for_file("customers/acme/secret.env", content);
+    let mut files = HashMap::new();
+    files.insert("customers/acme/secret.env".to_string(), content.to_string());
+
+    let telemetry_path = temp.path().join("telemetry.jsonl");
+    let mut config = Config {
+        telemetry: TelemetryConfig {
+            enabled: true,
+            file: Some(telemetry_path.to_string_lossy().into()),
+            otlp_endpoint: None,
+        },
+        ..Config::default()
+    };
+    config.privacy.redaction.enabled = true;
+    config.privacy.redaction.patterns = vec!["acme".into()];
+
+    let engine = ReviewEngine::new(config).unwrap();
+    let report = engine.run_with_provider(&diff, &files).await.unwrap();
+    assert_eq!(report.issues.len(), 1);
+
+    let data = std::fs::read_to_string(&telemetry_path).unwrap();
+    let finding = data.lines().nth(1).unwrap();
+    assert!(
+        finding.contains("customers/[REDACTED]/secret.env"),
+        "{}",
+        finding
+    );
+    assert!(!data.contains("acme"), "{}", data);
+    assert!(data.lines().last().unwrap().contains("\"findings\":1"));
+}
+
+/// Collects the events it is given and counts flushes.
+#[derive(Clone, Default)]
+struct MemorySink {
+    events: Arc<Mutex<Vec<ProgressEvent>>>,
+    flushes: Arc<Mutex<usize>>,
+}
+
+#[async_trait]
+impl TelemetrySink for MemorySink {
+    fn record(&self, event: &ProgressEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+
+    async fn flush(&self) {
+        *self.flushes.lock().unwrap() += 1;
+    }
+}
+
+#[tokio::test]
+async fn custom_sinks_receive_events_alongside_the_file() {
+    let temp = tempfile::tempdir().unwrap();
+    let content = "api_key = \"ABCDEFGHIJKLMNOP\"";
+    let mut files = HashMap::new();
+    files.insert("secret.env".to_string(), content.to_string());
+
+    let telemetry_path = temp.path().join("telemetry.jsonl");
+    let config = Config {
+        telemetry: TelemetryConfig {
+            enabled: true,
+            file: Some(telemetry_path.to_string_lossy().into()),
+            otlp_endpoint: None,
+        },
+        ..Config::default()
+    };
+    let sink = MemorySink::default();
+    let engine = ReviewEngine::builder(config)
+        .telemetry_sink(Box::new(sink.clone()))
+        .build()
+        .unwrap();
+    engine
+        .run_with_provider(&diff_for_file("secret.env", content), &files)
+        .await
+        .unwrap();
+
+    let events = sink.events.lock().unwrap();
+    assert_eq!(events.first(), Some(&ProgressEvent::Started));
+    assert!(matches!(
+        events.last(),
+        Some(ProgressEvent::Done { findings: 1, .. })
+    ));
+    assert_eq!(*sink.flushes.lock().unwrap(), 1);
+    let data = std::fs::read_to_string(&telemetry_path).unwrap();
+    assert_eq!(data.lines().count(), 3);
+}
+
+#[test]
+fn otlp_endpoint_alone_does_not_write_to_stdout() {
+    let config = TelemetryConfig {
+        enabled: true,
+        file: None,
+        otlp_endpoint: Some("http://localhost:4318".into()),
+    };
+    assert!(Telemetry::from_config(&config).unwrap().is_none());
+}
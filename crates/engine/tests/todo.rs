@@ -0,0 +1,90 @@
+use engine::config::Config;
+use engine::scanner::{ScanRequest, Scanner, TodoScanner};
+use engine::ReviewEngine;
+use std::collections::{HashMap, HashSet};
+
+fn flagged_lines(content: &str, config: &Config) -> Vec<usize> {
+    TodoScanner
+        .scan("src/lib.rs", content, config)
+        .unwrap()
+        .iter()
+        .map(|issue| issue.line_number)
+        .collect()
+}
+
+#[test]
+fn only_unlinked_markers_are_flagged_by_default() {
+    let content = "\
+// TODO: handle the error
+// TODO(JIRA-123): handle the error
+// FIXME #42 flaky on CI
+# HACK see https://github.com/org/repo/issues/7
+/* XXX remove before release */
+// HACK(alice) works around the parser
+let todo_count = 0; // not a marker
+";
+    assert_eq!(flagged_lines(content, &Config::default()), vec![1, 5, 6]);
+}
+
+#[test]
+fn linked_markers_are_flagged_without_require_issue_link() {
+    let mut config = Config::default();
+    config.rules.todo.require_issue_link = false;
+    let content = "// TODO(JIRA-123): handle the error\n// FIXME later\n";
+    let issues = TodoScanner.scan("main.go", content, &config).unwrap();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].description, "New `TODO` comment.");
+    assert_eq!(
+        issues[1].description,
+        "New `FIXME` comment without an issue link."
+    );
+    assert!(issues.iter().all(|issue| issue.rule_id == "todo"));
+}
+
+#[test]
+fn only_changed_lines_are_reported() {
+    let config = Config::default();
+    let content = "// TODO: old debt\nfn main() {}\n// TODO: new debt\n";
+    let changed = HashSet::from([2, 3]);
+    let request = ScanRequest {
+        path: "main.rs",
+        content,
+        config: &config,
+        changed_lines: Some(&changed),
+        hunks: &[],
+    };
+    let lines: Vec<usize> = TodoScanner
+        .scan_with_context(&request)
+        .unwrap()
+        .iter()
+        .map(|issue| issue.line_number)
+        .collect();
+    assert_eq!(lines, vec![3]);
+}
+
+#[tokio::test]
+async fn findings_are_listed_under_code_quality() {
+    let content = "fn main() {\n    // TODO: handle errors\n}\n// FIXME: untouched\n";
+    let diff = "diff --git a/main.rs b/main.rs\n--- a/main.rs\n+++ b/main.rs\n@@ -1,2 +1,3 @@\n fn main() {\n+    // TODO: handle errors\n }\n";
+    let mut files = HashMap::new();
+    files.insert("main.rs".to_string(), content.to_string());
+    let report = ReviewEngine::new(Config::default())
+        .unwrap()
+        .run_with_provider(diff, &files)
+        .await
+        .unwrap();
+    assert!(report.issues.is_empty());
+    assert_eq!(
+        report.code_quality,
+        vec!["main.rs:2 - New `TODO` comment without an issue link."]
+    );
+
+    let mut config = Config::default();
+    config.rules.todo.enabled = false;
+    let report = ReviewEngine::new(config)
+        .unwrap()
+        .run_with_provider(diff, &files)
+        .await
+        .unwrap();
+    assert!(report.code_quality.is_empty());
+}
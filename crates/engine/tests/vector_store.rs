@@ -0,0 +1,87 @@
+use engine::rag::{Document, InMemoryVectorStore, VectorStore};
+
+fn doc(name: &str, embedding: Vec<f32>) -> Document {
+    Document {
+        filename: name.to_string(),
+        content: String::new(),
+        embedding,
+        language: None,
+        function_signatures: vec![],
+        log_patterns: vec![],
+        error_snippets: vec![],
+        modified: 0,
+    }
+}
+
+fn store() -> InMemoryVectorStore {
+    let mut store = InMemoryVectorStore::default();
+    store.push_document(doc("a.rs", vec![1.0, 0.0]));
+    store.push_document(doc("b.rs", vec![0.0, 1.0]));
+    store.push_document(doc("c.rs", vec![0.7, 0.7]));
+    store
+}
+
+async fn nearest(store: &InMemoryVectorStore, query: Vec<f32>) -> Vec<String> {
+    store
+        .search(query, 3)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|d| d.filename)
+        .collect()
+}
+
+#[test]
+fn push_document_and_documents() {
+    let mut store = InMemoryVectorStore::default();
+    assert!(store.is_empty());
+    assert!(store.documents().is_empty());
+    store.push_document(doc("a.rs", vec![1.0, 0.0]));
+    assert!(!store.is_empty());
+    assert_eq!(store.len(), 1);
+    assert_eq!(store.documents()[0].filename, "a.rs");
+}
+
+#[tokio::test]
+async fn removed_documents_are_no_longer_found() {
+    let mut store = store();
+    // Build the search matrix before removing.
+    assert_eq!(nearest(&store, vec![1.0, 0.0]).await[0], "a.rs");
+
+    assert!(store.remove_by_filename("a.rs"));
+    assert!(!store.remove_by_filename("a.rs"));
+    assert_eq!(store.len(), 2);
+    assert_eq!(nearest(&store, vec![1.0, 0.0]).await, ["c.rs", "b.rs"]);
+}
+
+#[tokio::test]
+async fn retain_keeps_matching_documents() {
+    let mut store = store();
+    assert_eq!(nearest(&store, vec![0.0, 1.0]).await[0], "b.rs");
+    store.retain(|d| d.filename != "b.rs");
+    assert_eq!(
+        store
+            .documents()
+            .iter()
+            .map(|d| d.filename.as_str())
+            .collect::<Vec<_>>(),
+        ["a.rs", "c.rs"]
+    );
+    assert_eq!(nearest(&store, vec![0.0, 1.0]).await, ["c.rs", "a.rs"]);
+
+    store.retain(|_| false);
+    assert!(store.is_empty());
+    assert!(nearest(&store, vec![0.0, 1.0]).await.is_empty());
+}
+
+#[tokio::test]
+async fn edited_embeddings_take_effect() {
+    let mut store = store();
+    assert_eq!(nearest(&store, vec![1.0, 0.0]).await[0], "a.rs");
+    store.documents_mut()[1].embedding = vec![1.0, 0.01];
+    store.documents_mut()[0].embedding = vec![0.0, 1.0];
+    assert_eq!(
+        nearest(&store, vec![1.0, 0.0]).await,
+        ["b.rs", "c.rs", "a.rs"]
+    );
+}
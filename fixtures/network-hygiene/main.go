@@ -0,0 +1,10 @@
+package main
+
+import (
+    "net/http"
+    "net/http/pprof"
+)
+
+func routes(mux *http.ServeMux) {
+    mux.HandleFunc("/debug/pprof/", pprof.Index)
+}
@@ -1,6 +1,6 @@
 package main
 
 func main() {
-    var api_key = "aaaaaaaaaaaaaaaaaaaaaaaa"
+    var api_key = "aaaaaaaaaaaaaaaaaaaaaaaa" // EXPECT: secrets
     _ = api_key
 }
@@ -3,6 +3,6 @@ package main
 import "database/sql"
 
 func vulnerable(db *sql.DB, user string) {
-    query := "SELECT * FROM users WHERE name = '" + user + "'"
+    query := "SELECT * FROM users WHERE name = '" + user + "'" // EXPECT: sql-injection-go
     db.Query(query)
 }